@@ -0,0 +1,14 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Queues a developer note for the thread's next turn, starting one if the
+ * thread is idle. Meant for external supervisors steering long runs; the
+ * server only accepts it when started with `CODE_APP_SERVER_CONTROL_TOKEN`.
+ */
+export type TurnInjectNoteParams = { threadId: string, text: string, 
+/**
+ * Must match the server's `CODE_APP_SERVER_CONTROL_TOKEN`.
+ */
+controlToken: string, };
@@ -313,6 +313,8 @@ export type { Turn } from "./Turn";
 export type { TurnCompletedNotification } from "./TurnCompletedNotification";
 export type { TurnDiffUpdatedNotification } from "./TurnDiffUpdatedNotification";
 export type { TurnError } from "./TurnError";
+export type { TurnInjectNoteParams } from "./TurnInjectNoteParams";
+export type { TurnInjectNoteResponse } from "./TurnInjectNoteResponse";
 export type { TurnInterruptParams } from "./TurnInterruptParams";
 export type { TurnInterruptResponse } from "./TurnInterruptResponse";
 export type { TurnPlanStep } from "./TurnPlanStep";
@@ -379,6 +379,10 @@ client_request_definitions! {
         params: v2::TurnInterruptParams,
         response: v2::TurnInterruptResponse,
     },
+    TurnInjectNote => "turn/injectNote" {
+        params: v2::TurnInjectNoteParams,
+        response: v2::TurnInjectNoteResponse,
+    },
     #[experimental("thread/realtime/start")]
     ThreadRealtimeStart => "thread/realtime/start" {
         params: v2::ThreadRealtimeStartParams,
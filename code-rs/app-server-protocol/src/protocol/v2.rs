@@ -3569,6 +3569,24 @@ pub struct TurnInterruptParams {
 #[ts(export_to = "v2/")]
 pub struct TurnInterruptResponse {}
 
+/// Queues a developer note for the thread's next turn, starting one if the
+/// thread is idle. Meant for external supervisors steering long runs; the
+/// server only accepts it when started with `CODE_APP_SERVER_CONTROL_TOKEN`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct TurnInjectNoteParams {
+    pub thread_id: String,
+    pub text: String,
+    /// Must match the server's `CODE_APP_SERVER_CONTROL_TOKEN`.
+    pub control_token: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct TurnInjectNoteResponse {}
+
 // User input types
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
@@ -1678,6 +1678,7 @@ fn derive_config_from_params(
         dynamic_tools,
         compact_prompt_override: None,
         compact_prompt_override_file: None,
+        sandbox_preset: None,
     };
 
     let cli_overrides = cli_overrides
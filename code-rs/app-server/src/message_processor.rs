@@ -58,6 +58,21 @@ use toml::map::Map;
 
 mod v2;
 
+/// Enables `turn/injectNote`; requests must carry the same value in
+/// `controlToken`.
+const CONTROL_TOKEN_ENV_VAR: &str = "CODE_APP_SERVER_CONTROL_TOKEN";
+
+/// Compares every byte so the time taken does not reveal how much of the
+/// token a caller guessed right.
+fn control_token_matches(expected: &str, provided: &str) -> bool {
+    expected.len() == provided.len()
+        && expected
+            .bytes()
+            .zip(provided.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
 pub(crate) struct MessageProcessor {
     outgoing: Arc<OutgoingMessageSender>,
     command_exec_manager: crate::command_exec::CommandExecManager,
@@ -72,6 +87,8 @@ pub(crate) struct MessageProcessor {
     thread_state_manager: ThreadStateManager,
     cli_overrides: Vec<(String, TomlValue)>,
     code_linux_sandbox_exe: Option<PathBuf>,
+    /// `CODE_APP_SERVER_CONTROL_TOKEN`; `turn/injectNote` is refused without it.
+    control_token: Option<String>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -132,6 +149,9 @@ impl MessageProcessor {
             thread_state_manager: ThreadStateManager::default(),
             cli_overrides,
             code_linux_sandbox_exe: sandbox_exe,
+            control_token: std::env::var(CONTROL_TOKEN_ENV_VAR)
+                .ok()
+                .filter(|token| !token.is_empty()),
         }
     }
 
@@ -660,6 +680,10 @@ impl MessageProcessor {
                 self.turn_interrupt_v2(connection_id, request_id, params)
                     .await;
             }
+            AppServerClientRequest::TurnInjectNote { params, .. } => {
+                self.turn_inject_note_v2(connection_id, request_id, params)
+                    .await;
+            }
             AppServerClientRequest::FsReadFile { params, .. } => {
                 self.fs_read_file_v2(connection_id, request_id, params).await;
             }
@@ -1254,6 +1278,14 @@ mod tests {
         }
     }
 
+    #[test]
+    fn control_token_matches_only_exact_tokens() {
+        assert!(control_token_matches("s3cret", "s3cret"));
+        assert!(!control_token_matches("s3cret", "s3creT"));
+        assert!(!control_token_matches("s3cret", "s3cre"));
+        assert!(!control_token_matches("s3cret", ""));
+    }
+
     #[test]
     fn config_write_rejects_unreadable_existing_path() {
         let (outgoing_tx, _outgoing_rx) = mpsc::channel::<OutgoingEnvelope>(8);
@@ -110,6 +110,8 @@ use code_app_server_protocol::TurnCompletedNotification;
 use code_app_server_protocol::TurnStartParams;
 use code_app_server_protocol::TurnStartResponse;
 use code_app_server_protocol::TurnStartedNotification;
+use code_app_server_protocol::TurnInjectNoteParams;
+use code_app_server_protocol::TurnInjectNoteResponse;
 use code_app_server_protocol::TurnInterruptParams;
 use code_app_server_protocol::TurnInterruptResponse;
 use code_app_server_protocol::TurnStatus;
@@ -157,7 +159,9 @@ use serde::Deserialize;
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
+use super::CONTROL_TOKEN_ENV_VAR;
 use super::MessageProcessor;
+use super::control_token_matches;
 use crate::error_code::INTERNAL_ERROR_CODE;
 use crate::error_code::INVALID_PARAMS_ERROR_CODE;
 use crate::error_code::INVALID_REQUEST_ERROR_CODE;
@@ -336,7 +340,7 @@ impl MessageProcessor {
 
         let started_network_proxy = match config.network_proxy.as_ref() {
             Some(proxy_spec) => match proxy_spec
-                .start_proxy(&local_policy, None, None, false)
+                .start_proxy(&local_policy, None, None, None, false)
                 .await
             {
                 Ok(proxy) => Some(proxy),
@@ -2060,6 +2064,7 @@ impl MessageProcessor {
             excluded_tools,
             tx_event,
             code_core::protocol::AskForApproval::Never,
+            self.base_config.spawned_env.clone(),
         )
         .await
         {
@@ -3558,6 +3563,94 @@ impl MessageProcessor {
             .await;
     }
 
+    pub(super) async fn turn_inject_note_v2(
+        &self,
+        connection_id: ConnectionId,
+        request_id: mcp_types::RequestId,
+        params: TurnInjectNoteParams,
+    ) {
+        let rejection = match self.control_token.as_deref() {
+            None => Some(format!(
+                "turn/injectNote is disabled; start the server with {CONTROL_TOKEN_ENV_VAR} set"
+            )),
+            Some(expected) if !control_token_matches(expected, &params.control_token) => {
+                Some("invalid control token".to_string())
+            }
+            Some(_) if params.text.trim().is_empty() => Some("note text is empty".to_string()),
+            Some(_) => None,
+        };
+        if let Some(message) = rejection {
+            self.outgoing
+                .send_error_to_connection(
+                    connection_id,
+                    request_id,
+                    JSONRPCErrorError {
+                        code: INVALID_REQUEST_ERROR_CODE,
+                        message,
+                        data: None,
+                    },
+                )
+                .await;
+            return;
+        }
+
+        let thread_id = match code_protocol::ConversationId::from_string(&params.thread_id) {
+            Ok(id) => id,
+            Err(err) => {
+                self.outgoing
+                    .send_error_to_connection(
+                        connection_id,
+                        request_id,
+                        JSONRPCErrorError {
+                            code: INVALID_REQUEST_ERROR_CODE,
+                            message: format!("invalid thread id: {err}"),
+                            data: None,
+                        },
+                    )
+                    .await;
+                return;
+            }
+        };
+
+        let conversation = match self.conversation_manager.get_conversation(thread_id).await {
+            Ok(conversation) => conversation,
+            Err(err) => {
+                self.outgoing
+                    .send_error_to_connection(
+                        connection_id,
+                        request_id,
+                        JSONRPCErrorError {
+                            code: INVALID_REQUEST_ERROR_CODE,
+                            message: format!("thread not found: {err}"),
+                            data: None,
+                        },
+                    )
+                    .await;
+                return;
+            }
+        };
+
+        let op = code_core::protocol::Op::AddPendingInputDeveloper { text: params.text };
+        if let Err(err) = conversation.submit(op).await {
+            self.outgoing
+                .send_error_to_connection(
+                    connection_id,
+                    request_id,
+                    JSONRPCErrorError {
+                        code: INTERNAL_ERROR_CODE,
+                        message: format!("failed to inject note: {err}"),
+                        data: None,
+                    },
+                )
+                .await;
+            return;
+        }
+
+        self.outgoing
+            .send_response_to_connection(connection_id, request_id, TurnInjectNoteResponse {})
+            .await;
+    }
+
     async fn ensure_thread_listener(
         &self,
         thread_id: String,
@@ -4211,6 +4304,7 @@ async fn list_accessible_connector_metadata_from_codex_apps_mcp(
         HashSet::new(),
         tx_event,
         code_core::protocol::AskForApproval::Never,
+        config.spawned_env.clone(),
     )
     .await
     {
@@ -85,6 +85,11 @@ pub struct BrowserConfig {
     /// (e.g. `"Basic \<base64>"`) for correlating requests to a Codex network attempt.
     #[serde(default)]
     pub proxy_authorization: Option<String>,
+
+    /// Directory that receives files downloaded in the browser. Defaults to a
+    /// per-process directory under the system temp dir.
+    #[serde(default)]
+    pub download_dir: Option<PathBuf>,
 }
 
 impl Default for BrowserConfig {
@@ -112,6 +117,7 @@ impl Default for BrowserConfig {
             proxy_server: None,
             proxy_bypass_list: None,
             proxy_authorization: None,
+            download_dir: None,
         }
     }
 }
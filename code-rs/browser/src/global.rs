@@ -2,6 +2,7 @@ use crate::config::BrowserConfig;
 use crate::manager::BrowserManager;
 use once_cell::sync::Lazy;
 use std::sync::Arc;
+use std::sync::OnceLock;
 use tokio::sync::RwLock;
 
 type SharedBrowserManager = Arc<RwLock<Option<Arc<BrowserManager>>>>;
@@ -66,3 +67,56 @@ pub async fn set_last_connection(port: Option<u16>, ws: Option<String>) {
     *guard = (port, ws);
     tracing::debug!("Updated last Chrome connection cache: port={:?}, ws={:?}", port, ws_for_log);
 }
+
+/// Lifecycle of a Chrome process launched by a browser manager. External
+/// Chrome instances that we only connect to are never reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BrowserProcessEvent {
+    Launched(u32),
+    Exited(u32),
+}
+
+type ProcessObserver = Box<dyn Fn(BrowserProcessEvent) + Send + Sync>;
+
+static PROCESS_OBSERVER: OnceLock<ProcessObserver> = OnceLock::new();
+
+/// Install a process-wide observer for launched Chrome processes (used by core
+/// to track helpers that must not outlive a crashed session). Only the first
+/// observer installed is kept.
+pub fn set_process_observer<F>(observer: F)
+where
+    F: Fn(BrowserProcessEvent) + Send + Sync + 'static,
+{
+    let _ = PROCESS_OBSERVER.set(Box::new(observer));
+}
+
+pub(crate) fn notify_process_event(event: BrowserProcessEvent) {
+    if let Some(observer) = PROCESS_OBSERVER.get() {
+        observer(event);
+    }
+}
+
+/// `--proxy-server` and `--proxy-bypass-list` values for launched Chrome.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DefaultProxy {
+    pub server: Option<String>,
+    pub bypass_list: Option<String>,
+}
+
+static DEFAULT_PROXY: Lazy<std::sync::RwLock<DefaultProxy>> =
+    Lazy::new(|| std::sync::RwLock::new(DefaultProxy::default()));
+
+/// Set the proxy used by launched Chrome instances whose config leaves
+/// `proxy_server` unset (used by core to apply its `[network]` settings).
+pub fn set_default_proxy(proxy: DefaultProxy) {
+    if let Ok(mut guard) = DEFAULT_PROXY.write() {
+        *guard = proxy;
+    }
+}
+
+pub(crate) fn default_proxy() -> DefaultProxy {
+    DEFAULT_PROXY
+        .read()
+        .map(|guard| guard.clone())
+        .unwrap_or_default()
+}
@@ -17,6 +17,12 @@ pub use config::BrowserConfig;
 pub use config::ViewportConfig;
 pub use config::WaitStrategy;
 pub use manager::BrowserManager;
+pub use manager::DownloadInfo;
+pub use manager::DownloadState;
+pub use page::DomOutlineOptions;
+pub use page::HarSummary;
+pub use page::MockInfo;
+pub use page::MockRule;
 pub use page::Page;
 pub use page::ScreenshotMode;
 pub use page::ScreenshotRegion;
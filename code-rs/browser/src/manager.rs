@@ -2,6 +2,7 @@ use crate::config::BrowserConfig;
 use crate::page::Page;
 use chromiumoxide::Browser;
 use serde::Serialize;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::{Mutex, RwLock};
 use tokio::time::{Duration, Instant};
@@ -9,6 +10,7 @@ use tokio::time::{Duration, Instant};
 mod cdp;
 mod connection;
 mod cleanup;
+mod downloads;
 mod input;
 mod monitor;
 mod navigation;
@@ -18,11 +20,16 @@ mod screenshot;
 mod status;
 mod targets;
 
+pub use downloads::DownloadInfo;
+pub use downloads::DownloadState;
+
 /// Timeout for `browser.fetch_targets()` CDP calls.
 const FETCH_TARGETS_TIMEOUT: Duration = Duration::from_millis(1200);
 
 type NavigationCallback = Box<dyn Fn(String) + Send + Sync>;
 type NavigationCallbackSlot = Arc<RwLock<Option<NavigationCallback>>>;
+type DownloadCallback = Box<dyn Fn(DownloadInfo) + Send + Sync>;
+type DownloadCallbackSlot = Arc<RwLock<Option<DownloadCallback>>>;
 type LastAppliedMetrics = Option<(i64, i64, f64, bool, std::time::Instant)>;
 type LastAppliedMetricsSlot = Arc<Mutex<LastAppliedMetrics>>;
 
@@ -30,6 +37,9 @@ pub struct BrowserManager {
     pub config: Arc<RwLock<BrowserConfig>>,
     browser: Arc<Mutex<Option<Browser>>>,
     page: Arc<Mutex<Option<Arc<Page>>>>,
+    /// Pages attached so far, by target id. Switching back to a tab reuses its
+    /// `Page`, so the cursor position and captured console/network logs survive.
+    tabs: Arc<Mutex<HashMap<String, Arc<Page>>>>,
     // Dedicated background page for screenshots to prevent focus stealing
     background_page: Arc<Mutex<Option<Arc<Page>>>>,
     last_activity: Arc<Mutex<Instant>>,
@@ -41,6 +51,9 @@ pub struct BrowserManager {
     navigation_callback: NavigationCallbackSlot,
     navigation_monitor_handle: Arc<Mutex<Option<tokio::task::JoinHandle<()>>>>,
     viewport_monitor_handle: Arc<Mutex<Option<tokio::task::JoinHandle<()>>>>,
+    downloads: Arc<Mutex<downloads::DownloadLog>>,
+    download_callback: DownloadCallbackSlot,
+    download_monitor_handle: Arc<Mutex<Option<tokio::task::JoinHandle<()>>>>,
     /// Gate to temporarily disable all automatic viewport corrections (post-initial set)
     auto_viewport_correction_enabled: Arc<tokio::sync::RwLock<bool>>,
     /// Track last applied device metrics to avoid redundant overrides
@@ -68,6 +81,7 @@ impl BrowserManager {
             config: Arc::new(RwLock::new(config)),
             browser: Arc::new(Mutex::new(None)),
             page: Arc::new(Mutex::new(None)),
+            tabs: Arc::new(Mutex::new(HashMap::new())),
             background_page: Arc::new(Mutex::new(None)),
             last_activity: Arc::new(Mutex::new(Instant::now())),
             idle_monitor_handle: Arc::new(Mutex::new(None)),
@@ -78,6 +92,9 @@ impl BrowserManager {
             navigation_callback: Arc::new(tokio::sync::RwLock::new(None)),
             navigation_monitor_handle: Arc::new(Mutex::new(None)),
             viewport_monitor_handle: Arc::new(Mutex::new(None)),
+            downloads: Arc::new(Mutex::new(downloads::DownloadLog::default())),
+            download_callback: Arc::new(tokio::sync::RwLock::new(None)),
+            download_monitor_handle: Arc::new(Mutex::new(None)),
             auto_viewport_correction_enabled: Arc::new(tokio::sync::RwLock::new(true)),
             last_metrics_applied: Arc::new(Mutex::new(None)),
         }
@@ -7,6 +7,9 @@ use tracing::info;
 use tracing::warn;
 
 use super::super::BrowserManager;
+use super::start::launched_pid;
+use crate::global::BrowserProcessEvent;
+use crate::global::notify_process_event;
 
 impl BrowserManager {
     pub async fn stop(&self) -> Result<()> {
@@ -19,9 +22,11 @@ impl BrowserManager {
         }
 
         self.stop_navigation_monitor().await;
+        self.stop_download_monitor().await;
 
         let mut page_guard = self.page.lock().await;
         *page_guard = None;
+        self.tabs.lock().await.clear();
 
         // Also cleanup the background page
         let mut background_page_guard = self.background_page.lock().await;
@@ -38,7 +43,12 @@ impl BrowserManager {
                 // Just drop the connection, don't close the browser
             } else {
                 info!("Stopping browser we launched");
-                browser.close().await?;
+                let pid = launched_pid(&mut browser);
+                let closed = browser.close().await;
+                if let Some(pid) = pid {
+                    notify_process_event(BrowserProcessEvent::Exited(pid));
+                }
+                closed?;
             }
         }
 
@@ -162,9 +172,11 @@ impl BrowserManager {
         }
 
         self.stop_navigation_monitor().await;
+        self.stop_download_monitor().await;
 
         // Clear page state
         *self.page.lock().await = None;
+        self.tabs.lock().await.clear();
         *self.background_page.lock().await = None;
 
         // Drop the browser handle (does NOT close external Chrome — just drops the WS)
@@ -15,6 +15,8 @@ use tracing::info;
 use tracing::warn;
 
 use super::super::BrowserManager;
+use crate::global::BrowserProcessEvent;
+use crate::global::notify_process_event;
 use super::discover_ws_via_host_port;
 use super::scan_for_chrome_debug_port;
 use super::should_stop_handler;
@@ -60,6 +62,13 @@ impl Drop for BrowserLaunchLockFile {
     }
 }
 
+/// PID of the Chrome process `browser` launched, if it launched one.
+pub(super) fn launched_pid(browser: &mut Browser) -> Option<u32> {
+    browser
+        .get_mut_child()
+        .and_then(|child| child.as_mut_inner().id())
+}
+
 fn is_temporary_internal_launch_error_message(message: &str) -> bool {
     let message = message.to_ascii_lowercase();
     // macOS: EAGAIN = os error 35
@@ -408,17 +417,26 @@ impl BrowserManager {
                     // Set a longer timeout for CDP requests (60 seconds instead of default 30)
                     .request_timeout(Duration::from_secs(60));
 
-                if let Some(proxy_server) = config
+                // An explicit proxy (e.g. the managed network proxy) wins over
+                // the host-wide default, bypass list included.
+                let (proxy_server, proxy_bypass_list) = match config
                     .proxy_server
                     .as_deref()
                     .map(str::trim)
                     .filter(|value| !value.is_empty())
                 {
+                    Some(server) => (Some(server.to_owned()), config.proxy_bypass_list.clone()),
+                    None => {
+                        let default = crate::global::default_proxy();
+                        (default.server, default.bypass_list)
+                    }
+                };
+
+                if let Some(proxy_server) = proxy_server {
                     builder = builder.arg(format!("--proxy-server={proxy_server}"));
                 }
 
-                if let Some(proxy_bypass_list) = config
-                    .proxy_bypass_list
+                if let Some(proxy_bypass_list) = proxy_bypass_list
                     .as_deref()
                     .map(str::trim)
                     .filter(|value| !value.is_empty())
@@ -436,7 +454,12 @@ impl BrowserManager {
                 let browser_config = builder.build().map_err(BrowserError::CdpError)?;
 
                 match Browser::launch(browser_config).await {
-                    Ok((browser, handler)) => break (browser, handler, user_data_path),
+                    Ok((mut browser, handler)) => {
+                        if let Some(pid) = launched_pid(&mut browser) {
+                            notify_process_event(BrowserProcessEvent::Launched(pid));
+                        }
+                        break (browser, handler, user_data_path);
+                    }
                     Err(e) => {
                         let message = e.to_string();
 
@@ -492,7 +515,11 @@ impl BrowserManager {
                 }
             }
             warn!("[cdp/bm] event handler ended; clearing browser state so it can restart");
-            *browser_arc.lock().await = None;
+            if let Some(mut browser) = browser_arc.lock().await.take()
+                && let Some(pid) = launched_pid(&mut browser)
+            {
+                notify_process_event(BrowserProcessEvent::Exited(pid));
+            }
             *page_arc.lock().await = None;
             *background_page_arc.lock().await = None;
         });
@@ -0,0 +1,321 @@
+//! File downloads started in the controlled Chrome.
+//!
+//! `Browser.setDownloadBehavior` sends every download to the configured
+//! download directory under its CDP guid; once Chrome reports it complete the
+//! file is renamed to its suggested name. Downloads are tracked for the whole
+//! browser connection, so they stay listed across tab switches.
+
+use crate::BrowserError;
+use crate::Result;
+use crate::config::BrowserConfig;
+use chromiumoxide::Browser;
+use chromiumoxide::cdp::browser_protocol::browser as cdp_browser;
+use chromiumoxide::listeners::EventStream;
+use futures::StreamExt;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::debug;
+use tracing::info;
+use tracing::warn;
+
+use super::BrowserManager;
+use super::DownloadCallbackSlot;
+
+const DOWNLOADS_DIR: &str = "code-browser-downloads";
+/// Downloads remembered per browser manager; the oldest are forgotten first.
+const MAX_TRACKED_DOWNLOADS: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DownloadState {
+    InProgress,
+    Completed,
+    Canceled,
+}
+
+/// A download started in the browser.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DownloadInfo {
+    pub guid: String,
+    pub url: String,
+    pub suggested_filename: String,
+    pub state: DownloadState,
+    pub received_bytes: u64,
+    pub total_bytes: u64,
+    /// Where the file was saved; set once the download completed.
+    pub path: Option<PathBuf>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct DownloadLog {
+    entries: VecDeque<DownloadInfo>,
+}
+
+impl DownloadLog {
+    fn on_will_begin(&mut self, event: &Value) {
+        let Some(guid) = event.get("guid").and_then(Value::as_str) else {
+            return;
+        };
+        if self.entries.len() == MAX_TRACKED_DOWNLOADS {
+            self.entries.pop_front();
+        }
+        let text = |key: &str| {
+            event
+                .get(key)
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_owned()
+        };
+        self.entries.push_back(DownloadInfo {
+            guid: guid.to_owned(),
+            url: text("url"),
+            suggested_filename: text("suggestedFilename"),
+            state: DownloadState::InProgress,
+            received_bytes: 0,
+            total_bytes: 0,
+            path: None,
+        });
+    }
+
+    /// Applies a `downloadProgress` event and returns the download when this
+    /// event is the one that finished it.
+    fn on_progress(&mut self, event: &Value) -> Option<DownloadInfo> {
+        let guid = event.get("guid").and_then(Value::as_str)?;
+        let entry = self.entries.iter_mut().find(|entry| entry.guid == guid)?;
+        let bytes = |key: &str| event.get(key).and_then(Value::as_f64).unwrap_or(0.0) as u64;
+        entry.received_bytes = bytes("receivedBytes");
+        entry.total_bytes = bytes("totalBytes");
+        let state = match event.get("state").and_then(Value::as_str) {
+            Some("completed") => DownloadState::Completed,
+            Some("canceled") => DownloadState::Canceled,
+            _ => DownloadState::InProgress,
+        };
+        let finished =
+            entry.state == DownloadState::InProgress && state != DownloadState::InProgress;
+        entry.state = state;
+        finished.then(|| entry.clone())
+    }
+
+    fn set_path(&mut self, guid: &str, path: PathBuf) {
+        if let Some(entry) = self.entries.iter_mut().find(|entry| entry.guid == guid) {
+            entry.path = Some(path);
+        }
+    }
+
+    fn list(&self) -> Vec<DownloadInfo> {
+        self.entries.iter().cloned().collect()
+    }
+}
+
+/// Download directory for `config`: `download_dir`, or a per-process
+/// directory under the system temp dir.
+fn resolve_download_dir(config: &BrowserConfig) -> PathBuf {
+    config.download_dir.clone().unwrap_or_else(|| {
+        std::env::temp_dir()
+            .join(DOWNLOADS_DIR)
+            .join(std::process::id().to_string())
+    })
+}
+
+/// A file name safe to create in the download directory. Chrome already
+/// sanitizes suggested names, but they come from the page.
+fn sanitize_filename(name: &str) -> String {
+    let cleaned: String = name
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_control() || matches!(c, '/' | '\\' | ':') {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect();
+    let cleaned = cleaned.trim_start_matches('.').to_owned();
+    if cleaned.is_empty() {
+        "download".to_owned()
+    } else {
+        cleaned
+    }
+}
+
+/// `dir/name`, or `dir/name (N).ext` if that already exists.
+fn unique_path(dir: &Path, name: &str) -> PathBuf {
+    let candidate = dir.join(name);
+    if !candidate.exists() {
+        return candidate;
+    }
+    let (stem, extension) = match name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => (stem, Some(extension)),
+        _ => (name, None),
+    };
+    (1..)
+        .map(|n| match extension {
+            Some(extension) => dir.join(format!("{stem} ({n}).{extension}")),
+            None => dir.join(format!("{stem} ({n})")),
+        })
+        .find(|path| !path.exists())
+        .unwrap_or(candidate)
+}
+
+impl BrowserManager {
+    /// Set a callback to be called when a download finishes (completed or
+    /// canceled).
+    pub async fn set_download_callback<F>(&self, callback: F)
+    where
+        F: Fn(DownloadInfo) + Send + Sync + 'static,
+    {
+        let mut callback_guard = self.download_callback.write().await;
+        *callback_guard = Some(Box::new(callback));
+    }
+
+    /// Directory that receives browser downloads.
+    pub async fn download_dir(&self) -> PathBuf {
+        resolve_download_dir(&*self.config.read().await)
+    }
+
+    /// Downloads seen on the current browser connection, oldest first.
+    pub async fn list_downloads(&self) -> Vec<DownloadInfo> {
+        self.downloads.lock().await.list()
+    }
+
+    /// Route downloads to the download directory and start tracking them, once
+    /// per browser connection.
+    pub(super) async fn start_download_monitor(&self, browser: &Browser, config: &BrowserConfig) {
+        let mut handle_guard = self.download_monitor_handle.lock().await;
+        if handle_guard
+            .as_ref()
+            .is_some_and(|handle| !handle.is_finished())
+        {
+            return;
+        }
+
+        let dir = resolve_download_dir(config);
+        let (mut will_begin, mut progress) = match enable_downloads(browser, &dir).await {
+            Ok(streams) => streams,
+            Err(e) => {
+                warn!("Failed to enable browser downloads: {}", e);
+                return;
+            }
+        };
+        info!("Browser downloads go to {}", dir.display());
+
+        let log = Arc::clone(&self.downloads);
+        let callback = Arc::clone(&self.download_callback);
+        *handle_guard = Some(tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    Some(evt) = will_begin.next() => {
+                        if let Ok(value) = serde_json::to_value(&*evt) {
+                            log.lock().await.on_will_begin(&value);
+                        }
+                    }
+                    Some(evt) = progress.next() => {
+                        let Ok(value) = serde_json::to_value(&*evt) else {
+                            continue;
+                        };
+                        let Some(mut download) = log.lock().await.on_progress(&value) else {
+                            continue;
+                        };
+                        if download.state == DownloadState::Completed {
+                            let path = finish_download(&dir, &download).await;
+                            log.lock().await.set_path(&download.guid, path.clone());
+                            download.path = Some(path);
+                        }
+                        debug!("Download {} finished: {:?}", download.url, download.state);
+                        if let Some(ref callback) = *callback.read().await {
+                            callback(download);
+                        }
+                    }
+                    else => break,
+                }
+            }
+        }));
+    }
+
+    pub(super) async fn stop_download_monitor(&self) {
+        if let Some(handle) = self.download_monitor_handle.lock().await.take() {
+            handle.abort();
+        }
+    }
+}
+
+async fn enable_downloads(
+    browser: &Browser,
+    dir: &Path,
+) -> Result<(
+    EventStream<cdp_browser::EventDownloadWillBegin>,
+    EventStream<cdp_browser::EventDownloadProgress>,
+)> {
+    tokio::fs::create_dir_all(dir).await?;
+    let will_begin = browser
+        .event_listener::<cdp_browser::EventDownloadWillBegin>()
+        .await?;
+    let progress = browser
+        .event_listener::<cdp_browser::EventDownloadProgress>()
+        .await?;
+    let params = cdp_browser::SetDownloadBehaviorParams::builder()
+        .behavior(cdp_browser::SetDownloadBehaviorBehavior::AllowAndName)
+        .download_path(dir.display().to_string())
+        .events_enabled(true)
+        .build()
+        .map_err(BrowserError::CdpError)?;
+    browser.execute(params).await?;
+    Ok((will_begin, progress))
+}
+
+/// Renames the guid-named file Chrome wrote to the suggested file name.
+async fn finish_download(dir: &Path, download: &DownloadInfo) -> PathBuf {
+    let saved = dir.join(&download.guid);
+    let target = unique_path(dir, &sanitize_filename(&download.suggested_filename));
+    match tokio::fs::rename(&saved, &target).await {
+        Ok(()) => target,
+        Err(e) => {
+            warn!("Failed to rename download {}: {}", saved.display(), e);
+            saved
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn progress_reports_each_download_once() {
+        let mut log = DownloadLog::default();
+        log.on_will_begin(&json!({
+            "guid": "g1",
+            "url": "https://example.com/report.pdf",
+            "suggestedFilename": "report.pdf",
+        }));
+        let progress = |state: &str| json!({"guid": "g1", "receivedBytes": 10.0, "totalBytes": 10.0, "state": state});
+        assert_eq!(log.on_progress(&progress("inProgress")), None);
+        let done = log.on_progress(&progress("completed")).expect("completed");
+        assert_eq!(done.suggested_filename, "report.pdf");
+        assert_eq!(done.received_bytes, 10);
+        assert_eq!(log.on_progress(&progress("completed")), None);
+        assert_eq!(log.on_progress(&json!({"guid": "unknown"})), None);
+
+        log.set_path("g1", PathBuf::from("/tmp/report.pdf"));
+        assert_eq!(log.list()[0].path, Some(PathBuf::from("/tmp/report.pdf")));
+    }
+
+    #[test]
+    fn saved_names_stay_in_the_download_directory() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        assert_eq!(sanitize_filename("../../etc/passwd"), "_.._etc_passwd");
+        assert_eq!(sanitize_filename("  "), "download");
+        assert_eq!(unique_path(dir.path(), "a.txt"), dir.path().join("a.txt"));
+        std::fs::write(dir.path().join("a.txt"), "x").expect("write");
+        assert_eq!(
+            unique_path(dir.path(), "a.txt"),
+            dir.path().join("a (1).txt")
+        );
+    }
+}
@@ -1,5 +1,10 @@
 use crate::Result;
+use crate::page::DomOutlineOptions;
+use crate::page::HarSummary;
+use crate::page::MockInfo;
+use crate::page::MockRule;
 use crate::page::Page;
+use std::path::Path;
 use tracing::warn;
 
 use super::BrowserManager;
@@ -66,6 +71,45 @@ impl BrowserManager {
         page.execute_javascript(code).await
     }
 
+    /// Write the current page's captured network traffic to `path` as HAR,
+    /// optionally clearing the capture afterwards.
+    pub async fn export_har(&self, path: &Path, clear: bool) -> Result<HarSummary> {
+        let page = self.get_or_create_page().await?;
+        page.export_har(path, clear).await
+    }
+
+    /// Stub responses for requests matching `rule` on the current page.
+    pub async fn add_mock(&self, rule: MockRule) -> Result<()> {
+        let page = self.get_or_create_page().await?;
+        page.add_mock(rule).await
+    }
+
+    /// Stop stubbing `url_pattern` on the current page; returns how many
+    /// mocks were removed.
+    pub async fn remove_mock(&self, url_pattern: &str) -> Result<usize> {
+        let page = self.get_or_create_page().await?;
+        page.remove_mock(url_pattern).await
+    }
+
+    /// Remove every mock from the current page.
+    pub async fn clear_mocks(&self) -> Result<usize> {
+        let page = self.get_or_create_page().await?;
+        page.clear_mocks().await
+    }
+
+    /// Mocks registered on the current page.
+    pub async fn list_mocks(&self) -> Result<Vec<MockInfo>> {
+        let page = self.get_or_create_page().await?;
+        Ok(page.list_mocks().await)
+    }
+
+    /// Text outline of the current page's accessibility tree; see
+    /// [`Page::dom_outline`].
+    pub async fn dom_outline(&self, options: &DomOutlineOptions) -> Result<String> {
+        let page = self.get_or_create_page().await?;
+        page.dom_outline(options).await
+    }
+
     /// Capture console logs from the browser, including errors and unhandled rejections.
     pub async fn get_console_logs(&self, lines: Option<usize>) -> Result<serde_json::Value> {
         let page = self.get_or_create_page().await?;
@@ -39,6 +39,7 @@ impl BrowserManager {
                 }
                 Ok(Err(e)) => {
                     warn!("Existing page returned error: {}, will create new page", e);
+                    self.forget_tab(&page.target_id()).await;
                     *page_guard = None;
                 }
                 Err(_) => {
@@ -186,6 +187,7 @@ impl BrowserManager {
 
         let page = Arc::new(Page::new(cdp_page, config.clone()));
         *page_guard = Some(Arc::clone(&page));
+        self.register_tab(&page).await;
 
         // Inject the virtual cursor when page is created
         debug!("Injecting virtual cursor for new page");
@@ -200,6 +202,8 @@ impl BrowserManager {
         // would return no logs. This eagerly hooks console methods now.
         page_ops::install_console_capture(&page, "on page creation").await;
 
+        // Route downloads to the download directory (once per connection)
+        self.start_download_monitor(browser, &config).await;
         // Start navigation monitoring for this page
         self.start_navigation_monitor(Arc::clone(&page)).await;
         // Start viewport monitor (low-frequency, non-invasive)
@@ -29,6 +29,42 @@ pub(super) fn is_controllable_target_url(url: &str) -> bool {
 }
 
 impl BrowserManager {
+    /// Remember `page` so a later switch to its tab reuses it.
+    pub(super) async fn register_tab(&self, page: &Arc<Page>) {
+        self.tabs
+            .lock()
+            .await
+            .insert(page.target_id(), Arc::clone(page));
+    }
+
+    pub(super) async fn forget_tab(&self, target_id: &str) {
+        self.tabs.lock().await.remove(target_id);
+    }
+
+    /// The registered page for `target_id`, if it still responds.
+    async fn registered_tab(&self, target_id: &str) -> Option<Arc<Page>> {
+        let page = self.tabs.lock().await.get(target_id).cloned()?;
+        match tokio::time::timeout(Duration::from_secs(2), page.get_current_url()).await {
+            Ok(Ok(_)) => Some(page),
+            _ => {
+                debug!("Dropping unresponsive tab {target_id} from the registry");
+                self.forget_tab(target_id).await;
+                None
+            }
+        }
+    }
+
+    /// Make `page` the page that browser actions operate on.
+    async fn activate_page(&self, page: &Arc<Page>) {
+        {
+            let mut page_guard = self.page.lock().await;
+            *page_guard = Some(Arc::clone(page));
+        }
+        self.start_navigation_monitor(Arc::clone(page)).await;
+        self.start_viewport_monitor(Arc::clone(page)).await;
+        self.set_auto_viewport_correction(false).await;
+    }
+
     pub async fn list_page_targets(&self) -> Result<Vec<BrowserTargetSummary>> {
         let active_target_id = self.page.lock().await.as_ref().map(|page| page.target_id());
 
@@ -92,10 +128,7 @@ impl BrowserManager {
 
         let page = Arc::new(Page::new(cdp_page, config));
         let target_id = page.target_id();
-        {
-            let mut page_guard = self.page.lock().await;
-            *page_guard = Some(Arc::clone(&page));
-        }
+        self.register_tab(&page).await;
 
         debug!("Injecting virtual cursor for new tab");
         if let Err(e) = page.inject_virtual_cursor().await {
@@ -105,9 +138,7 @@ impl BrowserManager {
         // Ensure console capture is installed for the current document (matches get_or_create_page).
         page_ops::install_console_capture(&page, "for new tab").await;
 
-        self.start_navigation_monitor(Arc::clone(&page)).await;
-        self.start_viewport_monitor(Arc::clone(&page)).await;
-        self.set_auto_viewport_correction(false).await;
+        self.activate_page(&page).await;
         Ok(target_id)
     }
 
@@ -115,6 +146,12 @@ impl BrowserManager {
         self.ensure_browser().await?;
         self.update_activity().await;
 
+        if let Some(page) = self.registered_tab(target_id).await {
+            debug!("Switching back to registered tab {target_id}");
+            self.activate_page(&page).await;
+            return Ok(());
+        }
+
         let browser_guard = self.browser.lock().await;
         let browser = browser_guard.as_ref().ok_or(BrowserError::NotInitialized)?;
 
@@ -158,10 +195,7 @@ impl BrowserManager {
         let config = self.config.read().await.clone();
 
         let page = Arc::new(Page::new(cdp_page, config));
-        {
-            let mut page_guard = self.page.lock().await;
-            *page_guard = Some(Arc::clone(&page));
-        }
+        self.register_tab(&page).await;
 
         debug!("Injecting virtual cursor for switched page");
         if let Err(e) = page.inject_virtual_cursor().await {
@@ -170,9 +204,7 @@ impl BrowserManager {
 
         page_ops::install_console_capture(&page, "after switching target").await;
 
-        self.start_navigation_monitor(Arc::clone(&page)).await;
-        self.start_viewport_monitor(Arc::clone(&page)).await;
-        self.set_auto_viewport_correction(false).await;
+        self.activate_page(&page).await;
         Ok(())
     }
 
@@ -201,6 +233,7 @@ impl BrowserManager {
                 "No page found for target_id {target_id}"
             )));
         }
+        self.forget_tab(target_id).await;
 
         if active_target_id.as_deref() == Some(target_id) {
             let mut page_guard = self.page.lock().await;
@@ -226,6 +259,7 @@ impl BrowserManager {
     pub async fn close_page(&self) -> Result<()> {
         let mut page_guard = self.page.lock().await;
         if let Some(page) = page_guard.take() {
+            self.forget_tab(&page.target_id()).await;
             page.close()?;
         }
         Ok(())
@@ -0,0 +1,374 @@
+//! Text outline of a page built from its accessibility tree.
+//!
+//! `Accessibility.getFullAXTree` supplies roles, names, values and states;
+//! `DOMSnapshot.captureSnapshot` adds layout boxes, keyed by backend node id.
+//! Ignored nodes and unnamed generic containers are folded into their parent
+//! so the outline stays close to what a screen reader would announce.
+
+use super::Page;
+
+use crate::Result;
+use serde_json::Value;
+use serde_json::json;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use tracing::debug;
+
+/// Roles that only group other nodes; they are shown when they have a name.
+const LAYOUT_ROLES: &[&str] = &["generic", "none", "presentation", "InlineTextBox"];
+/// Boolean states listed after a node when true.
+const FLAG_PROPERTIES: &[&str] = &[
+    "focused", "disabled", "required", "readonly", "expanded", "selected", "modal",
+];
+/// Longest name or value shown for a node, in characters.
+const MAX_TEXT_CHARS: usize = 120;
+
+/// Limits for [`Page::dom_outline`].
+#[derive(Debug, Clone)]
+pub struct DomOutlineOptions {
+    /// Deepest outline level shown; deeper nodes are left out.
+    pub max_depth: usize,
+    /// Size limit of the outline, in bytes.
+    pub max_chars: usize,
+    /// Append viewport bounding boxes (`@x,y wxh`, CSS pixels).
+    pub include_bounds: bool,
+}
+
+impl Default for DomOutlineOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: 30,
+            max_chars: 20_000,
+            include_bounds: true,
+        }
+    }
+}
+
+impl Page {
+    /// Returns an indented outline of the page's accessibility tree, one node
+    /// per line: role, quoted name, value, states and bounding box.
+    pub async fn dom_outline(&self, options: &DomOutlineOptions) -> Result<String> {
+        let tree = self
+            .execute_cdp_raw("Accessibility.getFullAXTree", json!({}))
+            .await?;
+        let bounds = if options.include_bounds {
+            match self
+                .execute_cdp_raw(
+                    "DOMSnapshot.captureSnapshot",
+                    json!({ "computedStyles": [] }),
+                )
+                .await
+            {
+                Ok(snapshot) => layout_bounds(&snapshot),
+                Err(e) => {
+                    debug!("DOMSnapshot.captureSnapshot failed, omitting bounds: {}", e);
+                    HashMap::new()
+                }
+            }
+        } else {
+            HashMap::new()
+        };
+        Ok(render_outline(&tree, &bounds, options))
+    }
+}
+
+/// Viewport boxes of the main document's laid-out nodes, keyed by backend
+/// node id.
+fn layout_bounds(snapshot: &Value) -> HashMap<i64, [f64; 4]> {
+    let mut bounds = HashMap::new();
+    let Some(document) = snapshot.pointer("/documents/0") else {
+        return bounds;
+    };
+    let backend_ids = document
+        .pointer("/nodes/backendNodeId")
+        .and_then(Value::as_array);
+    let node_index = document
+        .pointer("/layout/nodeIndex")
+        .and_then(Value::as_array);
+    let boxes = document.pointer("/layout/bounds").and_then(Value::as_array);
+    let (Some(backend_ids), Some(node_index), Some(boxes)) = (backend_ids, node_index, boxes)
+    else {
+        return bounds;
+    };
+    let scroll_x = document
+        .get("scrollOffsetX")
+        .and_then(Value::as_f64)
+        .unwrap_or(0.0);
+    let scroll_y = document
+        .get("scrollOffsetY")
+        .and_then(Value::as_f64)
+        .unwrap_or(0.0);
+    for (index, rect) in node_index.iter().zip(boxes) {
+        let backend_id = index
+            .as_u64()
+            .and_then(|i| backend_ids.get(i as usize))
+            .and_then(Value::as_i64);
+        let rect: Option<Vec<f64>> = rect
+            .as_array()
+            .map(|values| values.iter().filter_map(Value::as_f64).collect());
+        if let (Some(backend_id), Some([x, y, width, height])) = (backend_id, rect.as_deref()) {
+            // The first box is the node's own; later ones belong to its text runs.
+            bounds
+                .entry(backend_id)
+                .or_insert([x - scroll_x, y - scroll_y, *width, *height]);
+        }
+    }
+    bounds
+}
+
+fn ax_text(node: &Value, key: &str) -> Option<String> {
+    let value = node.get(key)?.get("value")?;
+    let text = match value {
+        Value::String(text) => text.split_whitespace().collect::<Vec<_>>().join(" "),
+        Value::Null => return None,
+        other => other.to_string(),
+    };
+    if text.is_empty() {
+        return None;
+    }
+    if text.chars().count() > MAX_TEXT_CHARS {
+        let kept: String = text.chars().take(MAX_TEXT_CHARS).collect();
+        return Some(format!("{kept}…"));
+    }
+    Some(text)
+}
+
+/// States worth showing: true boolean flags, `checked`/`pressed` and heading
+/// `level`.
+fn ax_states(node: &Value) -> Vec<String> {
+    let Some(properties) = node.get("properties").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+    let mut states = Vec::new();
+    for property in properties {
+        let Some(name) = property.get("name").and_then(Value::as_str) else {
+            continue;
+        };
+        let value = property.pointer("/value/value");
+        match name {
+            "checked" | "pressed" => match value {
+                Some(Value::String(state)) if state == "true" => states.push(name.to_owned()),
+                Some(Value::Bool(true)) => states.push(name.to_owned()),
+                Some(Value::String(state)) if state == "mixed" => {
+                    states.push(format!("{name}=mixed"));
+                }
+                _ => {}
+            },
+            "level" => {
+                if let Some(level) = value.and_then(Value::as_i64) {
+                    states.push(format!("level={level}"));
+                }
+            }
+            _ if FLAG_PROPERTIES.contains(&name) => {
+                if value.and_then(Value::as_bool) == Some(true) {
+                    states.push(name.to_owned());
+                }
+            }
+            _ => {}
+        }
+    }
+    states
+}
+
+/// Renders the `Accessibility.getFullAXTree` result `tree` as an indented
+/// outline within the limits of `options`.
+fn render_outline(
+    tree: &Value,
+    bounds: &HashMap<i64, [f64; 4]>,
+    options: &DomOutlineOptions,
+) -> String {
+    let nodes: Vec<&Value> = tree
+        .get("nodes")
+        .and_then(Value::as_array)
+        .map(|nodes| nodes.iter().collect())
+        .unwrap_or_default();
+    let by_id: HashMap<&str, &Value> = nodes
+        .iter()
+        .filter_map(|node| Some((node.get("nodeId")?.as_str()?, *node)))
+        .collect();
+    let Some(root) = nodes
+        .iter()
+        .find(|node| node.get("parentId").is_none())
+        .or_else(|| nodes.first())
+    else {
+        return "The page has no accessibility tree.".to_owned();
+    };
+
+    let mut out = String::new();
+    let mut shown = 0usize;
+    let mut omitted = 0usize;
+    let mut too_deep = false;
+    let mut seen: HashSet<&str> = HashSet::new();
+    // (node, outline depth, name of the nearest shown ancestor)
+    let mut stack: Vec<(&Value, usize, Option<String>)> = vec![(*root, 0, None)];
+    while let Some((node, depth, parent_name)) = stack.pop() {
+        if let Some(id) = node.get("nodeId").and_then(Value::as_str)
+            && !seen.insert(id)
+        {
+            continue;
+        }
+        let role = node
+            .pointer("/role/value")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let name = ax_text(node, "name");
+        let ignored = node.get("ignored").and_then(Value::as_bool) == Some(true);
+        let folded = ignored
+            || role.is_empty()
+            || (LAYOUT_ROLES.contains(&role) && name.is_none())
+            // Text already announced as its parent's name.
+            || (role == "StaticText" && (name.is_none() || name == parent_name));
+
+        let (child_depth, child_parent_name) = if folded {
+            (depth, parent_name)
+        } else if depth >= options.max_depth {
+            too_deep = true;
+            continue;
+        } else {
+            let mut line = format!("{}- {}", "  ".repeat(depth), display_role(role));
+            if let Some(name) = &name {
+                line.push_str(&format!(" \"{}\"", name.replace('"', "\\\"")));
+            }
+            if let Some(value) = ax_text(node, "value") {
+                line.push_str(&format!(" value=\"{}\"", value.replace('"', "\\\"")));
+            }
+            for state in ax_states(node) {
+                line.push_str(&format!(" [{state}]"));
+            }
+            if let Some([x, y, width, height]) = node
+                .get("backendDOMNodeId")
+                .and_then(Value::as_i64)
+                .and_then(|id| bounds.get(&id))
+                && *width > 0.0
+                && *height > 0.0
+            {
+                line.push_str(&format!(" @{x:.0},{y:.0} {width:.0}x{height:.0}"));
+            }
+            // Once the outline is full, keep walking only to count what is left out.
+            if omitted > 0 || out.len() + line.len() + 1 > options.max_chars {
+                omitted += 1;
+            } else {
+                out.push_str(&line);
+                out.push('\n');
+                shown += 1;
+            }
+            (depth + 1, name)
+        };
+
+        if let Some(children) = node.get("childIds").and_then(Value::as_array) {
+            for child in children.iter().rev() {
+                if let Some(child) = child.as_str().and_then(|id| by_id.get(id)) {
+                    stack.push((*child, child_depth, child_parent_name.clone()));
+                }
+            }
+        }
+    }
+
+    if shown == 0 && omitted == 0 {
+        out.push_str("The page has no visible accessible content.\n");
+    }
+    if omitted > 0 {
+        out.push_str(&format!(
+            "[outline truncated at {} bytes; {omitted} more nodes not shown]\n",
+            options.max_chars
+        ));
+    }
+    if too_deep {
+        out.push_str(&format!(
+            "[nodes deeper than {} levels not shown]\n",
+            options.max_depth
+        ));
+    }
+    out
+}
+
+fn display_role(role: &str) -> &str {
+    match role {
+        "StaticText" => "text",
+        "RootWebArea" => "document",
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: &str, parent: Option<&str>, role: &str, name: &str, children: &[&str]) -> Value {
+        let mut node = json!({
+            "nodeId": id,
+            "ignored": false,
+            "role": {"type": "role", "value": role},
+            "name": {"type": "computedString", "value": name},
+            "childIds": children,
+            "backendDOMNodeId": id.parse::<i64>().unwrap_or_default(),
+        });
+        if let Some(parent) = parent {
+            node["parentId"] = json!(parent);
+        }
+        node
+    }
+
+    fn sample_tree() -> Value {
+        let mut button = node("4", Some("2"), "button", "Sign in", &["5"]);
+        button["properties"] = json!([
+            {"name": "focused", "value": {"type": "booleanOrUndefined", "value": true}},
+            {"name": "disabled", "value": {"type": "boolean", "value": false}},
+        ]);
+        let mut input = node("6", Some("2"), "textbox", "Email", &[]);
+        input["value"] = json!({"type": "string", "value": "a@b.c"});
+        json!({"nodes": [
+            node("1", None, "RootWebArea", "Login", &["2"]),
+            node("2", Some("1"), "generic", "", &["3", "4", "6"]),
+            node("3", Some("2"), "heading", "Welcome", &[]),
+            button,
+            node("5", Some("4"), "StaticText", "Sign in", &[]),
+            input,
+        ]})
+    }
+
+    #[test]
+    fn outline_folds_containers_and_repeated_text() {
+        let bounds = HashMap::from([(4, [10.0, 20.0, 80.0, 24.0])]);
+        let outline = render_outline(&sample_tree(), &bounds, &DomOutlineOptions::default());
+        assert_eq!(
+            outline,
+            "- document \"Login\"\n  \
+             - heading \"Welcome\"\n  \
+             - button \"Sign in\" [focused] @10,20 80x24\n  \
+             - textbox \"Email\" value=\"a@b.c\"\n"
+        );
+    }
+
+    #[test]
+    fn outline_respects_depth_and_size_limits() {
+        let shallow = DomOutlineOptions {
+            max_depth: 1,
+            ..DomOutlineOptions::default()
+        };
+        let outline = render_outline(&sample_tree(), &HashMap::new(), &shallow);
+        assert!(outline.starts_with("- document \"Login\"\n["));
+        assert!(outline.contains("deeper than 1 levels"));
+
+        let small = DomOutlineOptions {
+            max_chars: 40,
+            ..DomOutlineOptions::default()
+        };
+        let outline = render_outline(&sample_tree(), &HashMap::new(), &small);
+        assert!(outline.contains("3 more nodes not shown"), "{outline}");
+    }
+
+    #[test]
+    fn layout_bounds_are_relative_to_the_viewport() {
+        let snapshot = json!({"documents": [{
+            "scrollOffsetX": 0.0,
+            "scrollOffsetY": 100.0,
+            "nodes": {"backendNodeId": [1, 4, 7]},
+            "layout": {"nodeIndex": [1, 2], "bounds": [[10.0, 120.0, 80.0, 24.0], [0.0, 0.0, 5.0, 5.0]]},
+        }]});
+        let bounds = layout_bounds(&snapshot);
+        assert_eq!(bounds.get(&4), Some(&[10.0, 20.0, 80.0, 24.0]));
+        assert_eq!(bounds.get(&7), Some(&[0.0, -100.0, 5.0, 5.0]));
+        assert_eq!(bounds.get(&1), None);
+    }
+}
@@ -0,0 +1,409 @@
+//! Request interception with canned responses (CDP `Fetch` domain).
+//!
+//! Each mock pairs a URL pattern with a response, usually read from a local
+//! fixture. `Fetch.enable` is issued with one pattern per mock so only
+//! matching requests are paused; a paused request is fulfilled by the first
+//! mock that matches it and let through otherwise. Mocks belong to the page
+//! (tab) they were added on.
+
+use super::Page;
+use super::RawCdpCommand;
+
+use crate::Result;
+use base64::Engine as _;
+use chromiumoxide::cdp::browser_protocol::fetch as cdp_fetch;
+use chromiumoxide::page::Page as CdpPage;
+use futures::StreamExt;
+use serde::Serialize;
+use serde_json::Value;
+use serde_json::json;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::debug;
+use tracing::warn;
+
+/// A response served for requests whose URL matches `url_pattern`.
+#[derive(Debug, Clone)]
+pub struct MockRule {
+    /// URL pattern: `*` matches any run of characters, `?` exactly one, and
+    /// `\` escapes the next character (the CDP `Fetch` pattern syntax).
+    pub url_pattern: String,
+    /// Only requests with this HTTP method match; any method when `None`.
+    pub method: Option<String>,
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+    /// Fixture the body was read from.
+    pub fixture: Option<PathBuf>,
+}
+
+impl MockRule {
+    /// A mock answering `url_pattern` with the contents of `fixture`. The
+    /// content type is guessed from the file extension.
+    pub async fn from_fixture(url_pattern: String, fixture: &Path) -> Result<Self> {
+        let body = tokio::fs::read(fixture).await?;
+        let mut headers = Vec::new();
+        if let Some(content_type) = content_type_for(fixture) {
+            headers.push(("Content-Type".to_owned(), content_type.to_owned()));
+        }
+        Ok(Self {
+            url_pattern,
+            method: None,
+            status: 200,
+            headers,
+            body,
+            fixture: Some(fixture.to_path_buf()),
+        })
+    }
+
+    fn matches(&self, url: &str, method: &str) -> bool {
+        self.method
+            .as_deref()
+            .is_none_or(|expected| expected.eq_ignore_ascii_case(method))
+            && pattern_matches(&self.url_pattern, url)
+    }
+
+    fn same_target(&self, other: &Self) -> bool {
+        self.url_pattern == other.url_pattern && self.method == other.method
+    }
+}
+
+/// A registered mock, as listed by [`Page::list_mocks`].
+#[derive(Debug, Clone, Serialize)]
+pub struct MockInfo {
+    pub url_pattern: String,
+    pub method: Option<String>,
+    pub status: u16,
+    pub body_bytes: usize,
+    pub fixture: Option<PathBuf>,
+    /// Requests answered by this mock so far.
+    pub hits: u64,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct MockRules {
+    rules: Vec<(MockRule, u64)>,
+}
+
+impl MockRules {
+    /// Adds `rule`, replacing a mock for the same pattern and method.
+    fn insert(&mut self, rule: MockRule) {
+        match self
+            .rules
+            .iter_mut()
+            .find(|(existing, _)| existing.same_target(&rule))
+        {
+            Some(slot) => *slot = (rule, 0),
+            None => self.rules.push((rule, 0)),
+        }
+    }
+
+    fn remove(&mut self, url_pattern: &str) -> usize {
+        let before = self.rules.len();
+        self.rules
+            .retain(|(rule, _)| rule.url_pattern != url_pattern);
+        before - self.rules.len()
+    }
+
+    /// The first mock matching the request, counting it as a hit.
+    fn answer(&mut self, url: &str, method: &str) -> Option<MockRule> {
+        let (rule, hits) = self
+            .rules
+            .iter_mut()
+            .find(|(rule, _)| rule.matches(url, method))?;
+        *hits += 1;
+        Some(rule.clone())
+    }
+
+    fn fetch_patterns(&self) -> Vec<Value> {
+        let mut patterns: Vec<&str> = self
+            .rules
+            .iter()
+            .map(|(rule, _)| rule.url_pattern.as_str())
+            .collect();
+        patterns.sort_unstable();
+        patterns.dedup();
+        patterns
+            .into_iter()
+            .map(|pattern| json!({ "urlPattern": pattern, "requestStage": "Request" }))
+            .collect()
+    }
+
+    fn list(&self) -> Vec<MockInfo> {
+        self.rules
+            .iter()
+            .map(|(rule, hits)| MockInfo {
+                url_pattern: rule.url_pattern.clone(),
+                method: rule.method.clone(),
+                status: rule.status,
+                body_bytes: rule.body.len(),
+                fixture: rule.fixture.clone(),
+                hits: *hits,
+            })
+            .collect()
+    }
+}
+
+impl Page {
+    /// Serves `rule` for matching requests on this page from now on.
+    pub async fn add_mock(&self, rule: MockRule) -> Result<()> {
+        let mut mocks = self.mocks.lock().await;
+        mocks.insert(rule);
+        self.sync_fetch_patterns(&mocks).await
+    }
+
+    /// Removes the mocks registered for `url_pattern`; returns how many.
+    pub async fn remove_mock(&self, url_pattern: &str) -> Result<usize> {
+        let mut mocks = self.mocks.lock().await;
+        let removed = mocks.remove(url_pattern);
+        if removed > 0 {
+            self.sync_fetch_patterns(&mocks).await?;
+        }
+        Ok(removed)
+    }
+
+    /// Removes every mock; returns how many there were.
+    pub async fn clear_mocks(&self) -> Result<usize> {
+        let mut mocks = self.mocks.lock().await;
+        let removed = mocks.rules.len();
+        mocks.rules.clear();
+        self.sync_fetch_patterns(&mocks).await?;
+        Ok(removed)
+    }
+
+    pub async fn list_mocks(&self) -> Vec<MockInfo> {
+        self.mocks.lock().await.list()
+    }
+
+    /// Pauses requests for exactly the registered patterns, or stops
+    /// intercepting when there are none.
+    async fn sync_fetch_patterns(&self, mocks: &MockRules) -> Result<()> {
+        if mocks.rules.is_empty() {
+            self.execute_cdp_raw("Fetch.disable", json!({})).await?;
+        } else {
+            self.execute_cdp_raw(
+                "Fetch.enable",
+                json!({ "patterns": mocks.fetch_patterns() }),
+            )
+            .await?;
+        }
+        Ok(())
+    }
+}
+
+/// Answers the page's paused requests from `mocks` until the page goes away.
+pub(super) async fn serve_mocks(cdp_page: Arc<CdpPage>, mocks: Arc<Mutex<MockRules>>) {
+    let Ok(mut paused) = cdp_page
+        .event_listener::<cdp_fetch::EventRequestPaused>()
+        .await
+    else {
+        warn!("Failed to subscribe to Fetch.requestPaused; request mocking is unavailable");
+        return;
+    };
+    while let Some(evt) = paused.next().await {
+        let Ok(value) = serde_json::to_value(&*evt) else {
+            continue;
+        };
+        let Some(request_id) = value.get("requestId").and_then(Value::as_str) else {
+            continue;
+        };
+        let url = value
+            .pointer("/request/url")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let method = value
+            .pointer("/request/method")
+            .and_then(Value::as_str)
+            .unwrap_or("GET");
+        let command = match mocks.lock().await.answer(url, method) {
+            Some(rule) => {
+                debug!("Mocking {} {} with status {}", method, url, rule.status);
+                RawCdpCommand::new("Fetch.fulfillRequest", fulfill_params(request_id, &rule))
+            }
+            None => RawCdpCommand::new("Fetch.continueRequest", json!({ "requestId": request_id })),
+        };
+        if let Err(e) = cdp_page.execute(command).await {
+            warn!("Failed to answer paused request {}: {}", url, e);
+        }
+    }
+}
+
+fn fulfill_params(request_id: &str, rule: &MockRule) -> Value {
+    let mut headers: Vec<Value> = rule
+        .headers
+        .iter()
+        .map(|(name, value)| json!({ "name": name, "value": value }))
+        .collect();
+    // Mocked APIs are usually called cross-origin from the page under test.
+    if !rule
+        .headers
+        .iter()
+        .any(|(name, _)| name.eq_ignore_ascii_case("access-control-allow-origin"))
+    {
+        headers.push(json!({ "name": "Access-Control-Allow-Origin", "value": "*" }));
+    }
+    json!({
+        "requestId": request_id,
+        "responseCode": rule.status,
+        "responseHeaders": headers,
+        "body": base64::engine::general_purpose::STANDARD.encode(&rule.body),
+    })
+}
+
+fn content_type_for(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    Some(match extension.as_str() {
+        "json" => "application/json",
+        "html" | "htm" => "text/html; charset=utf-8",
+        "js" | "mjs" => "text/javascript",
+        "css" => "text/css",
+        "txt" => "text/plain; charset=utf-8",
+        "xml" => "application/xml",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        _ => return None,
+    })
+}
+
+/// Whether `text` matches the CDP URL `pattern`.
+fn pattern_matches(pattern: &str, text: &str) -> bool {
+    enum Token {
+        Any,
+        One,
+        Char(char),
+    }
+    let mut tokens = Vec::new();
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        tokens.push(match c {
+            '*' => Token::Any,
+            '?' => Token::One,
+            '\\' => Token::Char(chars.next().unwrap_or('\\')),
+            c => Token::Char(c),
+        });
+    }
+    let text: Vec<char> = text.chars().collect();
+
+    // Greedy wildcard matching, backtracking to the last `*`.
+    let (mut t, mut p) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match tokens.get(p) {
+            Some(Token::Any) => {
+                star = Some((p, t));
+                p += 1;
+            }
+            Some(Token::One) => {
+                p += 1;
+                t += 1;
+            }
+            Some(Token::Char(c)) if *c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((star_p, star_t)) => {
+                    p = star_p + 1;
+                    t = star_t + 1;
+                    star = Some((star_p, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    tokens[p..].iter().all(|token| matches!(token, Token::Any))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(pattern: &str, method: Option<&str>, body: &str) -> MockRule {
+        MockRule {
+            url_pattern: pattern.to_owned(),
+            method: method.map(str::to_owned),
+            status: 200,
+            headers: Vec::new(),
+            body: body.as_bytes().to_vec(),
+            fixture: None,
+        }
+    }
+
+    #[test]
+    fn patterns_follow_fetch_wildcards() {
+        assert!(pattern_matches(
+            "*/api/users*",
+            "https://app.test/api/users?page=2"
+        ));
+        assert!(pattern_matches(
+            "https://app.test/v?/items",
+            "https://app.test/v2/items"
+        ));
+        assert!(!pattern_matches(
+            "https://app.test/v?/items",
+            "https://app.test/v10/items"
+        ));
+        assert!(pattern_matches(
+            r"*/search\?q=*",
+            "https://app.test/search?q=rust"
+        ));
+        assert!(!pattern_matches(
+            r"*/search\?q=*",
+            "https://app.test/searchXq=rust"
+        ));
+        assert!(!pattern_matches(
+            "*/api/orders",
+            "https://app.test/api/orders/7"
+        ));
+    }
+
+    #[test]
+    fn first_matching_mock_answers_and_counts_hits() {
+        let mut mocks = MockRules::default();
+        mocks.insert(rule("*/api/users", Some("POST"), "created"));
+        mocks.insert(rule("*/api/*", None, "fallback"));
+        mocks.insert(rule("*/api/users", Some("POST"), "replaced"));
+
+        let answer = |mocks: &mut MockRules, method: &str| {
+            mocks
+                .answer("https://app.test/api/users", method)
+                .map(|rule| String::from_utf8(rule.body).unwrap_or_default())
+        };
+        assert_eq!(answer(&mut mocks, "post").as_deref(), Some("replaced"));
+        assert_eq!(answer(&mut mocks, "GET").as_deref(), Some("fallback"));
+        assert_eq!(
+            mocks.answer("https://other.test/", "GET").map(|r| r.status),
+            None
+        );
+
+        let listed = mocks.list();
+        assert_eq!(listed.len(), 2);
+        assert_eq!(listed[0].hits, 1);
+        assert_eq!(listed[1].hits, 1);
+        assert_eq!(mocks.fetch_patterns().len(), 2);
+        assert_eq!(mocks.remove("*/api/users"), 1);
+    }
+
+    #[test]
+    fn fulfill_adds_cors_header_unless_given() {
+        let mut mock = rule("*", None, "{}");
+        let params = fulfill_params("r1", &mock);
+        assert_eq!(
+            params["responseHeaders"][0]["name"],
+            "Access-Control-Allow-Origin"
+        );
+        assert_eq!(params["body"], "e30=");
+
+        mock.headers.push((
+            "access-control-allow-origin".to_owned(),
+            "https://app.test".to_owned(),
+        ));
+        let params = fulfill_params("r1", &mock);
+        assert_eq!(params["responseHeaders"].as_array().map(Vec::len), Some(1));
+    }
+}
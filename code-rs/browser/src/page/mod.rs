@@ -16,11 +16,19 @@ use tracing::debug;
 use tracing::warn;
 
 mod console;
+mod dom;
 mod input;
+mod mock;
 mod navigation;
+mod network;
 mod screenshot;
 mod viewport;
 
+pub use dom::DomOutlineOptions;
+pub use mock::MockInfo;
+pub use mock::MockRule;
+pub use network::HarSummary;
+
 // Externalized virtual cursor script (editable JS)
 const VIRTUAL_CURSOR_JS: &str = include_str!("../js/virtual_cursor.js");
 // Externalized per-document bootstrap (tab blocking, SPA hooks, console capture, stealth).
@@ -45,6 +53,10 @@ pub struct Page {
     cursor_state: Arc<Mutex<CursorState>>,
     // Buffer for CDP-captured console logs
     console_logs: Arc<Mutex<VecDeque<serde_json::Value>>>,
+    // Requests captured from the CDP Network domain, exported by `export_har`
+    network_log: Arc<Mutex<network::NetworkLog>>,
+    // Responses stubbed through the CDP Fetch domain, see `add_mock`
+    mocks: Arc<Mutex<mock::MockRules>>,
     // Screenshot path preflight cache:
     // - We strongly prefer compositor captures via from_surface(false) to avoid visible flashes in the
     //   user's real Chrome window. However, that path can be flaky or unavailable when the window is not
@@ -120,6 +132,8 @@ impl Page {
             cursor_state: Arc::new(Mutex::new(initial_cursor)),
             preflight_cache: Arc::new(Mutex::new(None)),
             console_logs: Arc::new(Mutex::new(VecDeque::new())),
+            network_log: Arc::new(Mutex::new(network::NetworkLog::default())),
+            mocks: Arc::new(Mutex::new(mock::MockRules::default())),
         };
 
         // Register a unified bootstrap (runs on every new document):
@@ -194,6 +208,12 @@ impl Page {
             }
         });
 
+        tokio::spawn(network::capture_network(
+            page.cdp_page.clone(),
+            page.network_log.clone(),
+        ));
+        tokio::spawn(mock::serve_mocks(page.cdp_page.clone(), page.mocks.clone()));
+
         page
     }
 
@@ -0,0 +1,499 @@
+//! Network traffic capture (CDP `Network` domain) and HAR export.
+//!
+//! Requests are recorded from `Network.requestWillBeSent`,
+//! `Network.responseReceived`, `Network.loadingFinished` and
+//! `Network.loadingFailed`. Bodies are not fetched; only request post data is
+//! kept, truncated. Credential-bearing headers are redacted when recorded so
+//! they never reach disk.
+
+use super::Page;
+
+use crate::Result;
+use chromiumoxide::cdp::browser_protocol::network as cdp_network;
+use chromiumoxide::page::Page as CdpPage;
+use futures::StreamExt;
+use serde_json::Value;
+use serde_json::json;
+use std::collections::VecDeque;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Requests kept per page; the oldest are dropped first.
+const MAX_NETWORK_ENTRIES: usize = 1000;
+/// Request post data kept per entry, in bytes.
+const MAX_POST_DATA_BYTES: usize = 64 * 1024;
+/// Failed requests listed in a [`HarSummary`].
+const MAX_LISTED_FAILURES: usize = 20;
+const REDACTED: &str = "[REDACTED]";
+const SENSITIVE_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+    "x-api-key",
+    "x-auth-token",
+    "x-csrf-token",
+];
+
+#[derive(Debug, Clone, Default)]
+struct NetworkEntry {
+    request_id: String,
+    resource_type: Option<String>,
+    wall_time: f64,
+    started: f64,
+    ended: Option<f64>,
+    method: String,
+    url: String,
+    request_headers: Vec<(String, String)>,
+    post_data: Option<String>,
+    status: Option<i64>,
+    status_text: String,
+    protocol: Option<String>,
+    response_headers: Vec<(String, String)>,
+    mime_type: String,
+    encoded_length: Option<f64>,
+    error: Option<String>,
+}
+
+impl NetworkEntry {
+    fn failed(&self) -> bool {
+        self.error.is_some() || self.status.is_some_and(|status| status >= 400)
+    }
+
+    fn describe(&self) -> String {
+        let outcome = match (&self.error, self.status) {
+            (Some(error), _) => error.clone(),
+            (None, Some(status)) => format!("{status} {}", self.status_text)
+                .trim_end()
+                .to_owned(),
+            (None, None) => "pending".to_owned(),
+        };
+        format!("{} {} -> {outcome}", self.method, self.url)
+    }
+
+    fn to_har(&self) -> Value {
+        let started = chrono::DateTime::from_timestamp_millis((self.wall_time * 1000.0) as i64)
+            .unwrap_or_default()
+            .to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+        let time_ms = self
+            .ended
+            .map_or(-1.0, |ended| ((ended - self.started) * 1000.0).max(0.0));
+        let http_version = self
+            .protocol
+            .clone()
+            .unwrap_or_else(|| "HTTP/1.1".to_owned());
+        let mut request = json!({
+            "method": self.method,
+            "url": self.url,
+            "httpVersion": http_version,
+            "headers": har_headers(&self.request_headers),
+            "queryString": query_string(&self.url),
+            "cookies": [],
+            "headersSize": -1,
+            "bodySize": self.post_data.as_ref().map_or(0, String::len),
+        });
+        if let Some(text) = &self.post_data {
+            let mime_type = header_value(&self.request_headers, "content-type").unwrap_or_default();
+            request["postData"] = json!({ "mimeType": mime_type, "text": text });
+        }
+        let redirect_url = header_value(&self.response_headers, "location").unwrap_or_default();
+        let mut entry = json!({
+            "startedDateTime": started,
+            "time": time_ms,
+            "request": request,
+            "response": {
+                "status": self.status.unwrap_or(0),
+                "statusText": self.status_text,
+                "httpVersion": http_version,
+                "headers": har_headers(&self.response_headers),
+                "cookies": [],
+                "content": {
+                    "size": self.encoded_length.unwrap_or(-1.0),
+                    "mimeType": self.mime_type,
+                },
+                "redirectURL": redirect_url,
+                "headersSize": -1,
+                "bodySize": self.encoded_length.unwrap_or(-1.0),
+            },
+            "cache": {},
+            "timings": { "send": 0, "wait": time_ms, "receive": 0 },
+            "_requestId": self.request_id,
+        });
+        if let Some(resource_type) = &self.resource_type {
+            entry["_resourceType"] = json!(resource_type);
+        }
+        if let Some(error) = &self.error {
+            entry["_error"] = json!(error);
+        }
+        entry
+    }
+}
+
+/// Requests seen on a page, oldest first, capped at [`MAX_NETWORK_ENTRIES`].
+#[derive(Debug, Default)]
+pub(crate) struct NetworkLog {
+    entries: VecDeque<NetworkEntry>,
+    dropped: usize,
+}
+
+impl NetworkLog {
+    fn open_entry(&mut self, request_id: &str) -> Option<&mut NetworkEntry> {
+        self.entries
+            .iter_mut()
+            .rev()
+            .find(|entry| entry.request_id == request_id && entry.ended.is_none())
+    }
+
+    /// `Network.requestWillBeSent`. A redirect reuses the request id, so the
+    /// previous hop is completed with the redirect response first.
+    fn on_request(&mut self, event: &Value) {
+        let request_id = str_field(event, "requestId");
+        let timestamp = event
+            .get("timestamp")
+            .and_then(Value::as_f64)
+            .unwrap_or(0.0);
+        if let Some(redirect) = event.get("redirectResponse")
+            && let Some(entry) = self.open_entry(&request_id)
+        {
+            apply_response(entry, redirect);
+            entry.ended = Some(timestamp);
+        }
+
+        let request = event.get("request").cloned().unwrap_or(Value::Null);
+        let post_data = request
+            .get("postData")
+            .and_then(Value::as_str)
+            .map(|data| truncate_bytes(data, MAX_POST_DATA_BYTES));
+        if self.entries.len() == MAX_NETWORK_ENTRIES {
+            self.entries.pop_front();
+            self.dropped += 1;
+        }
+        self.entries.push_back(NetworkEntry {
+            request_id,
+            resource_type: event.get("type").and_then(Value::as_str).map(str::to_owned),
+            wall_time: event.get("wallTime").and_then(Value::as_f64).unwrap_or(0.0),
+            started: timestamp,
+            method: str_field(&request, "method"),
+            url: str_field(&request, "url"),
+            request_headers: redacted_headers(request.get("headers")),
+            post_data,
+            ..NetworkEntry::default()
+        });
+    }
+
+    /// `Network.responseReceived`.
+    fn on_response(&mut self, event: &Value) {
+        let request_id = str_field(event, "requestId");
+        if let Some(entry) = self.open_entry(&request_id)
+            && let Some(response) = event.get("response")
+        {
+            apply_response(entry, response);
+        }
+    }
+
+    /// `Network.loadingFinished`.
+    fn on_finished(&mut self, event: &Value) {
+        let request_id = str_field(event, "requestId");
+        if let Some(entry) = self.open_entry(&request_id) {
+            entry.ended = event.get("timestamp").and_then(Value::as_f64);
+            if let Some(length) = event.get("encodedDataLength").and_then(Value::as_f64) {
+                entry.encoded_length = Some(length);
+            }
+        }
+    }
+
+    /// `Network.loadingFailed`.
+    fn on_failed(&mut self, event: &Value) {
+        let request_id = str_field(event, "requestId");
+        if let Some(entry) = self.open_entry(&request_id) {
+            entry.ended = event.get("timestamp").and_then(Value::as_f64);
+            let canceled = event.get("canceled").and_then(Value::as_bool) == Some(true);
+            let error = str_field(event, "errorText");
+            entry.error = Some(if canceled && error.is_empty() {
+                "canceled".to_owned()
+            } else {
+                error
+            });
+        }
+    }
+
+    fn to_har(&self) -> Value {
+        json!({
+            "log": {
+                "version": "1.2",
+                "creator": { "name": "code", "version": env!("CARGO_PKG_VERSION") },
+                "pages": [],
+                "entries": self.entries.iter().map(NetworkEntry::to_har).collect::<Vec<_>>(),
+            }
+        })
+    }
+
+    fn summary(&self) -> HarSummary {
+        let failed: Vec<&NetworkEntry> = self.entries.iter().filter(|e| e.failed()).collect();
+        HarSummary {
+            entries: self.entries.len(),
+            dropped: self.dropped,
+            failed: failed.len(),
+            failed_requests: failed
+                .iter()
+                .rev()
+                .take(MAX_LISTED_FAILURES)
+                .rev()
+                .map(|entry| entry.describe())
+                .collect(),
+        }
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.dropped = 0;
+    }
+}
+
+/// Result of [`Page::export_har`].
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct HarSummary {
+    /// Requests written to the HAR file.
+    pub entries: usize,
+    /// Older requests dropped because of the capture limit.
+    pub dropped: usize,
+    /// Requests that failed or returned a 4xx/5xx status.
+    pub failed: usize,
+    /// The most recent failures, as `METHOD URL -> outcome`.
+    pub failed_requests: Vec<String>,
+}
+
+fn str_field(value: &Value, key: &str) -> String {
+    value
+        .get(key)
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_owned()
+}
+
+fn apply_response(entry: &mut NetworkEntry, response: &Value) {
+    entry.status = response.get("status").and_then(Value::as_i64);
+    entry.status_text = str_field(response, "statusText");
+    entry.protocol = response
+        .get("protocol")
+        .and_then(Value::as_str)
+        .map(str::to_uppercase);
+    entry.response_headers = redacted_headers(response.get("headers"));
+    entry.mime_type = str_field(response, "mimeType");
+    entry.encoded_length = response.get("encodedDataLength").and_then(Value::as_f64);
+}
+
+fn redacted_headers(headers: Option<&Value>) -> Vec<(String, String)> {
+    let Some(Value::Object(headers)) = headers else {
+        return Vec::new();
+    };
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if SENSITIVE_HEADERS.contains(&name.to_ascii_lowercase().as_str()) {
+                REDACTED.to_owned()
+            } else {
+                value
+                    .as_str()
+                    .map_or_else(|| value.to_string(), str::to_owned)
+            };
+            (name.clone(), value)
+        })
+        .collect()
+}
+
+fn header_value(headers: &[(String, String)], name: &str) -> Option<String> {
+    headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.clone())
+}
+
+fn har_headers(headers: &[(String, String)]) -> Vec<Value> {
+    headers
+        .iter()
+        .map(|(name, value)| json!({ "name": name, "value": value }))
+        .collect()
+}
+
+fn query_string(url: &str) -> Vec<Value> {
+    url::Url::parse(url)
+        .map(|url| {
+            url.query_pairs()
+                .map(|(name, value)| json!({ "name": name, "value": value }))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn truncate_bytes(text: &str, max: usize) -> String {
+    if text.len() <= max {
+        return text.to_owned();
+    }
+    let mut end = max;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}…[truncated {} bytes]", &text[..end], text.len() - end)
+}
+
+/// Feeds the page's `Network` events into `log` until the page goes away.
+pub(super) async fn capture_network(cdp_page: Arc<CdpPage>, log: Arc<Mutex<NetworkLog>>) {
+    // Best-effort enable; page setup normally enables the domain already.
+    let _ = cdp_page.execute(cdp_network::EnableParams::default()).await;
+    let (Ok(mut requests), Ok(mut responses), Ok(mut finished), Ok(mut failed)) = (
+        cdp_page
+            .event_listener::<cdp_network::EventRequestWillBeSent>()
+            .await,
+        cdp_page
+            .event_listener::<cdp_network::EventResponseReceived>()
+            .await,
+        cdp_page
+            .event_listener::<cdp_network::EventLoadingFinished>()
+            .await,
+        cdp_page
+            .event_listener::<cdp_network::EventLoadingFailed>()
+            .await,
+    ) else {
+        tracing::warn!("Failed to subscribe to network events; HAR capture is unavailable");
+        return;
+    };
+
+    loop {
+        tokio::select! {
+            Some(evt) = requests.next() => {
+                if let Ok(value) = serde_json::to_value(&*evt) {
+                    log.lock().await.on_request(&value);
+                }
+            }
+            Some(evt) = responses.next() => {
+                if let Ok(value) = serde_json::to_value(&*evt) {
+                    log.lock().await.on_response(&value);
+                }
+            }
+            Some(evt) = finished.next() => {
+                if let Ok(value) = serde_json::to_value(&*evt) {
+                    log.lock().await.on_finished(&value);
+                }
+            }
+            Some(evt) = failed.next() => {
+                if let Ok(value) = serde_json::to_value(&*evt) {
+                    log.lock().await.on_failed(&value);
+                }
+            }
+            else => break,
+        }
+    }
+}
+
+impl Page {
+    /// Writes the network traffic captured on this page as a HAR 1.2 file.
+    /// Response bodies are not included and credential headers are redacted.
+    pub async fn export_har(&self, path: &Path, clear: bool) -> Result<HarSummary> {
+        let (har, summary) = {
+            let mut log = self.network_log.lock().await;
+            let har = log.to_har();
+            let summary = log.summary();
+            if clear {
+                log.clear();
+            }
+            (har, summary)
+        };
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let text = serde_json::to_string_pretty(&har).map_err(std::io::Error::other)?;
+        tokio::fs::write(path, text).await?;
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(id: &str, url: &str, ts: f64) -> Value {
+        json!({
+            "requestId": id,
+            "timestamp": ts,
+            "wallTime": 1_700_000_000.0 + ts,
+            "type": "Fetch",
+            "request": {
+                "method": "POST",
+                "url": url,
+                "headers": { "Authorization": "Bearer secret", "Content-Type": "application/json" },
+                "postData": "{\"q\":1}"
+            }
+        })
+    }
+
+    #[test]
+    fn records_requests_redacts_credentials_and_reports_failures() {
+        let mut log = NetworkLog::default();
+        log.on_request(&request("1", "https://api.example.com/items?page=2", 1.0));
+        log.on_response(&json!({
+            "requestId": "1",
+            "response": {
+                "status": 500,
+                "statusText": "Internal Server Error",
+                "protocol": "h2",
+                "mimeType": "application/json",
+                "headers": { "set-cookie": "session=abc", "content-type": "application/json" }
+            }
+        }));
+        log.on_finished(&json!({ "requestId": "1", "timestamp": 1.25, "encodedDataLength": 42.0 }));
+        log.on_request(&request("2", "https://api.example.com/other", 2.0));
+        log.on_failed(&json!({ "requestId": "2", "timestamp": 2.5, "errorText": "net::ERR_CONNECTION_REFUSED" }));
+
+        let har = log.to_har();
+        let entries = har["log"]["entries"].as_array().expect("entries");
+        assert_eq!(entries.len(), 2);
+        let first = &entries[0];
+        assert_eq!(first["time"], json!(250.0));
+        assert_eq!(first["response"]["status"], json!(500));
+        assert_eq!(first["response"]["httpVersion"], json!("H2"));
+        assert_eq!(
+            first["request"]["postData"]["mimeType"],
+            json!("application/json")
+        );
+        assert_eq!(
+            first["request"]["queryString"],
+            json!([{ "name": "page", "value": "2" }])
+        );
+        let text = har.to_string();
+        assert!(!text.contains("secret"), "authorization must be redacted");
+        assert!(!text.contains("session=abc"), "set-cookie must be redacted");
+        assert_eq!(entries[1]["_error"], json!("net::ERR_CONNECTION_REFUSED"));
+
+        let summary = log.summary();
+        assert_eq!(summary.entries, 2);
+        assert_eq!(summary.failed, 2);
+        assert_eq!(
+            summary.failed_requests,
+            vec![
+                "POST https://api.example.com/items?page=2 -> 500 Internal Server Error".to_owned(),
+                "POST https://api.example.com/other -> net::ERR_CONNECTION_REFUSED".to_owned(),
+            ]
+        );
+    }
+
+    #[test]
+    fn caps_entries_and_post_data() {
+        let mut log = NetworkLog::default();
+        for n in 0..MAX_NETWORK_ENTRIES + 5 {
+            log.on_request(&request(&n.to_string(), "https://example.com/", n as f64));
+        }
+        let summary = log.summary();
+        assert_eq!(summary.entries, MAX_NETWORK_ENTRIES);
+        assert_eq!(summary.dropped, 5);
+
+        let long = "é".repeat(MAX_POST_DATA_BYTES);
+        let truncated = truncate_bytes(&long, MAX_POST_DATA_BYTES);
+        assert!(truncated.len() < long.len());
+        assert!(truncated.ends_with(&format!(
+            "[truncated {} bytes]",
+            long.len() - MAX_POST_DATA_BYTES
+        )));
+    }
+}
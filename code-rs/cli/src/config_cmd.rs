@@ -3,7 +3,13 @@ use anyhow::Result;
 use clap::Parser;
 use clap::Subcommand;
 use clap::ValueEnum;
+use code_common::CliConfigOverrides;
 use code_core::config::find_code_home;
+use code_core::config_loader::LayerVerdict;
+use code_core::config_loader::LoaderOverrides;
+use code_core::config_loader::describe_layer_source;
+use code_core::config_loader::load_config_layers_state_with_cwd;
+use code_core::config_loader::toml_value_at_path;
 use jsonschema::Draft;
 use jsonschema::JSONSchema;
 use serde_json::Value as JsonValue;
@@ -13,6 +19,9 @@ use std::path::PathBuf;
 
 #[derive(Debug, Parser)]
 pub struct ConfigCli {
+    #[clap(skip)]
+    pub config_overrides: CliConfigOverrides,
+
     #[command(subcommand)]
     subcommand: ConfigSubcommand,
 }
@@ -24,6 +33,9 @@ enum ConfigSubcommand {
 
     /// Validate a config file against one or more schemas.
     Validate(ValidateArgs),
+
+    /// Show the value each config layer sets for a key and which one wins.
+    Explain(ExplainArgs),
 }
 
 #[derive(Debug, Clone, Copy, ValueEnum)]
@@ -34,6 +46,13 @@ enum SchemaKind {
     Code,
 }
 
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum SchemaFormat {
+    /// Draft-07 JSON Schema. For the `code` schema, each top-level key also
+    /// lists the config layers that may set it under `x-code-layers`.
+    JsonSchema,
+}
+
 #[derive(Debug, Clone, Copy, ValueEnum)]
 enum ValidateSchemaKind {
     Codex,
@@ -47,6 +66,10 @@ struct SchemaArgs {
     #[arg(long, value_enum, default_value_t = SchemaKind::Code)]
     schema: SchemaKind,
 
+    /// Output format.
+    #[arg(long, value_enum, default_value_t = SchemaFormat::JsonSchema)]
+    format: SchemaFormat,
+
     /// Optional path to write the schema JSON to. When omitted, prints to stdout.
     #[arg(short, long, value_name = "PATH")]
     out: Option<PathBuf>,
@@ -63,19 +86,35 @@ struct ValidateArgs {
     path: Option<PathBuf>,
 }
 
+#[derive(Debug, Parser)]
+struct ExplainArgs {
+    /// Dotted config key, e.g. `approval_policy` or `tui.theme.name`.
+    #[arg(value_name = "KEY")]
+    key: String,
+
+    /// Directory whose project layers to include (defaults to the current directory).
+    #[arg(long, value_name = "PATH")]
+    cwd: Option<PathBuf>,
+}
+
 impl ConfigCli {
     pub async fn run(self) -> Result<()> {
         match self.subcommand {
             ConfigSubcommand::Schema(args) => run_schema(args),
             ConfigSubcommand::Validate(args) => run_validate(args),
+            ConfigSubcommand::Explain(args) => run_explain(self.config_overrides, args).await,
         }
     }
 }
 
 fn run_schema(args: SchemaArgs) -> Result<()> {
-    let schema_json = match args.schema {
-        SchemaKind::Codex => code_core::config::schema::codex_config_schema_json().to_vec(),
-        SchemaKind::Code => code_core::config::schema::config_schema_json()?,
+    let schema_json = match (args.schema, args.format) {
+        (SchemaKind::Codex, SchemaFormat::JsonSchema) => {
+            code_core::config::schema::codex_config_schema_json().to_vec()
+        }
+        (SchemaKind::Code, SchemaFormat::JsonSchema) => {
+            code_core::config::schema::config_schema_with_layers_json()?
+        }
     };
 
     if let Some(out) = args.out {
@@ -137,6 +176,71 @@ fn run_validate(args: ValidateArgs) -> Result<()> {
     }
 }
 
+async fn run_explain(cli_overrides: CliConfigOverrides, args: ExplainArgs) -> Result<()> {
+    let overrides = cli_overrides.parse_overrides().map_err(anyhow::Error::msg)?;
+    let code_home = find_code_home().context("failed to resolve CODE_HOME")?;
+    let cwd = match args.cwd {
+        Some(cwd) => cwd,
+        None => std::env::current_dir().context("failed to read the current directory")?,
+    };
+    let stack = load_config_layers_state_with_cwd(
+        &code_home,
+        Some(cwd.as_path()),
+        &overrides,
+        LoaderOverrides::default(),
+    )
+    .await
+    .context("failed to load config layers")?;
+    let explained = stack.explain(&args.key);
+
+    match (&explained.effective, &explained.winner) {
+        (None, _) => println!("{}: not set by any layer (built-in default applies)", explained.key),
+        (Some(value), Some(winner)) => println!(
+            "{} = {value}
+  from {}",
+            explained.key,
+            describe_layer_source(winner)
+        ),
+        (Some(_), None) => println!("{}: table merged from the layers below", explained.key),
+    }
+
+    println!();
+    println!("Layers (lowest to highest precedence):");
+    for layer in &explained.layers {
+        let source = describe_layer_source(&layer.source);
+        let value = layer
+            .value
+            .as_ref()
+            .map_or_else(|| "-".to_owned(), ToString::to_string);
+        let verdict = match &layer.verdict {
+            LayerVerdict::NotSet => "not set".to_owned(),
+            LayerVerdict::Disabled(reason) => format!("ignored: {reason}"),
+            LayerVerdict::Winner => "effective".to_owned(),
+            LayerVerdict::Merged => "merged".to_owned(),
+            LayerVerdict::Overridden => match &explained.winner {
+                Some(winner) => format!("overridden by {}", describe_layer_source(winner)),
+                None => "overridden by a higher layer".to_owned(),
+            },
+        };
+        println!("  {source}");
+        if layer.value.is_some() {
+            println!("    value: {value}");
+        }
+        println!("    {verdict}");
+    }
+
+    // Profiles are applied after layering, so they can still change the result.
+    let effective_config = stack.effective_config();
+    if let Some(profile) = effective_config.get("profile").and_then(toml::Value::as_str) {
+        let profile_key = format!("profiles.{profile}.{}", explained.key);
+        if let Some(value) = toml_value_at_path(&effective_config, &profile_key) {
+            println!();
+            println!("Note: active profile `{profile}` sets {} = {value}, which takes precedence.", explained.key);
+        }
+    }
+    Ok(())
+}
+
 fn validate_one(
     label: &str,
     schema_json: &[u8],
@@ -0,0 +1,119 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use anyhow::Result;
+use clap::Parser;
+use code_common::CliConfigOverrides;
+use code_core::config::Config;
+use code_core::config::ConfigOverrides;
+use code_core::config_types::McpServerTransportConfig;
+use code_core::default_client::DEFAULT_ORIGINATOR;
+use code_core::default_client::create_client;
+use code_core::http_client::OutboundNetworkSettings;
+use code_core::http_client::outbound_network_settings;
+use code_core::http_client::validate_ca_bundle;
+
+/// Check that Code can reach its model provider and MCP servers through the
+/// configured proxy and CA settings.
+#[derive(Debug, Parser)]
+pub struct DebugNetworkCommand {
+    #[clap(skip)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Additional URL to probe (repeatable).
+    #[arg(long = "url", value_name = "URL")]
+    urls: Vec<String>,
+
+    /// Per-request timeout in seconds.
+    #[arg(long = "timeout", value_name = "SECONDS", default_value_t = 10)]
+    timeout_secs: u64,
+}
+
+impl DebugNetworkCommand {
+    pub async fn run(self) -> Result<()> {
+        let overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(|e| anyhow::anyhow!(e))?;
+        // Loading the config publishes `[network]` to every client built below.
+        let config = Config::load_with_cli_overrides(overrides, ConfigOverrides::default())
+            .context("failed to load configuration")?;
+
+        print_settings(&outbound_network_settings());
+
+        let mut targets = vec![(
+            format!("model provider ({})", config.model_provider.name),
+            config.model_provider.base_url_for_probe(),
+        )];
+        let mut mcp_servers: Vec<_> = config.mcp_servers.iter().collect();
+        mcp_servers.sort_by(|(a, _), (b, _)| a.cmp(b));
+        for (name, server) in mcp_servers {
+            if let McpServerTransportConfig::StreamableHttp { url, .. } = &server.transport {
+                targets.push((format!("MCP server `{name}`"), url.clone()));
+            }
+        }
+        targets.extend(self.urls.into_iter().map(|url| ("--url".to_owned(), url)));
+
+        println!();
+        let client = create_client(DEFAULT_ORIGINATOR);
+        let timeout = Duration::from_secs(self.timeout_secs.max(1));
+        let mut failures = 0usize;
+        for (label, url) in &targets {
+            // Any HTTP response, even 401 or 404, proves the proxy and TLS
+            // path work; only transport errors count as failures.
+            match client.get(url).timeout(timeout).send().await {
+                Ok(response) => println!("ok    {label}: {url} (HTTP {})", response.status().as_u16()),
+                Err(err) => {
+                    failures += 1;
+                    println!("FAIL  {label}: {url}\n      {}", describe_error(&err));
+                }
+            }
+        }
+
+        if failures > 0 {
+            anyhow::bail!("{failures} of {} connectivity checks failed", targets.len());
+        }
+        Ok(())
+    }
+}
+
+fn print_settings(settings: &OutboundNetworkSettings) {
+    let source = |configured: bool| if configured { "[network]" } else { "environment" };
+    let show = |value: Option<String>| value.unwrap_or_else(|| "(none)".to_owned());
+
+    println!(
+        "HTTP proxy:  {} ({})",
+        show(settings.effective_http_proxy()),
+        source(settings.http_proxy.is_some())
+    );
+    println!(
+        "HTTPS proxy: {} ({})",
+        show(settings.effective_https_proxy()),
+        source(settings.https_proxy.is_some())
+    );
+    println!(
+        "No proxy:    {} ({})",
+        show(settings.effective_no_proxy()),
+        source(!settings.no_proxy.is_empty())
+    );
+    match &settings.ca_bundle {
+        Some(path) => match validate_ca_bundle(path) {
+            Ok(count) => println!("CA bundle:   {} ({count} certificates)", path.display()),
+            Err(err) => println!("CA bundle:   {err}"),
+        },
+        None => println!("CA bundle:   (none; system roots plus SSL_CERT_FILE and friends)"),
+    }
+}
+
+/// Flattens the error's source chain, which is where TLS and proxy
+/// failures put the useful detail.
+fn describe_error(err: &reqwest::Error) -> String {
+    let mut parts = vec![err.to_string()];
+    let mut source = std::error::Error::source(err);
+    while let Some(cause) = source {
+        parts.push(cause.to_string());
+        source = cause.source();
+    }
+    parts.dedup();
+    parts.join(": ")
+}
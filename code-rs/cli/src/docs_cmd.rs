@@ -0,0 +1,98 @@
+use anyhow::Context;
+use anyhow::Result;
+use clap::Parser;
+use clap::Subcommand;
+use code_common::CliConfigOverrides;
+use code_core::config::Config;
+use code_core::config::ConfigOverrides;
+use code_core::docs_bundles::DocsBundle;
+use code_core::docs_bundles::build_docs_index;
+
+#[derive(Debug, Parser)]
+pub struct DocsCli {
+    #[clap(skip)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    subcommand: DocsSubcommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum DocsSubcommand {
+    /// Build or rebuild the search index for `[docs_bundles]` entries.
+    Index {
+        /// Bundles to index (defaults to every enabled bundle).
+        names: Vec<String>,
+    },
+
+    /// List configured bundles and whether they have been indexed.
+    List,
+}
+
+impl DocsCli {
+    pub async fn run(self) -> Result<()> {
+        let overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides, ConfigOverrides::default())
+            .context("failed to load configuration")?;
+
+        match self.subcommand {
+            DocsSubcommand::Index { names } => run_index(&config.docs_bundles, &names),
+            DocsSubcommand::List => {
+                run_list(&config.docs_bundles);
+                Ok(())
+            }
+        }
+    }
+}
+
+fn run_index(bundles: &[DocsBundle], names: &[String]) -> Result<()> {
+    if bundles.is_empty() {
+        println!("No docs bundles configured. Add a [docs_bundles.<name>] table to config.toml.");
+        return Ok(());
+    }
+    for name in names {
+        if !bundles.iter().any(|bundle| &bundle.name == name) {
+            anyhow::bail!("no enabled docs bundle named `{name}`");
+        }
+    }
+
+    let mut failures = 0usize;
+    for bundle in bundles
+        .iter()
+        .filter(|bundle| names.is_empty() || names.contains(&bundle.name))
+    {
+        match build_docs_index(bundle) {
+            Ok(stats) => println!(
+                "Indexed `{}`: {} sections from {} files",
+                bundle.name, stats.sections, stats.files
+            ),
+            Err(err) => {
+                failures += 1;
+                eprintln!("Failed to index `{}`: {err}", bundle.name);
+            }
+        }
+    }
+
+    if failures > 0 {
+        anyhow::bail!("{failures} docs bundle(s) failed to index");
+    }
+    Ok(())
+}
+
+fn run_list(bundles: &[DocsBundle]) {
+    if bundles.is_empty() {
+        println!("No docs bundles configured.");
+        return;
+    }
+    for bundle in bundles {
+        let status = if bundle.index_path.is_file() {
+            "indexed"
+        } else {
+            "not indexed"
+        };
+        println!("{}  {}  ({status})", bundle.name, bundle.root.display());
+    }
+}
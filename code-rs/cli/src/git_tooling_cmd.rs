@@ -0,0 +1,104 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Context;
+use anyhow::Result;
+use clap::Parser;
+use clap::Subcommand;
+use code_common::CliConfigOverrides;
+use code_core::config::Config;
+use code_core::config::ConfigOverrides;
+use code_git_tooling::GhostRetention;
+
+#[derive(Debug, Parser)]
+pub struct GitToolingCli {
+    #[clap(skip)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    subcommand: GitToolingSubcommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum GitToolingSubcommand {
+    /// Report and prune stale ghost commit refs and worktree registrations.
+    Gc(GcArgs),
+}
+
+#[derive(Debug, Parser)]
+struct GcArgs {
+    /// Only report what would be pruned.
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Ghost commits to keep per session (0 keeps all). Defaults to
+    /// `ghost_commits.keep_per_session`.
+    #[arg(long, value_name = "N")]
+    keep_per_session: Option<usize>,
+
+    /// Prune ghost commits older than this many days (0 never expires).
+    /// Defaults to `ghost_commits.max_age_days`.
+    #[arg(long, value_name = "DAYS")]
+    max_age_days: Option<u64>,
+
+    /// Repository to clean up (defaults to the current directory).
+    #[arg(long, value_name = "PATH")]
+    cwd: Option<PathBuf>,
+}
+
+impl GitToolingCli {
+    pub async fn run(self) -> Result<()> {
+        match self.subcommand {
+            GitToolingSubcommand::Gc(args) => run_gc(self.config_overrides, args),
+        }
+    }
+}
+
+fn run_gc(cli_overrides: CliConfigOverrides, args: GcArgs) -> Result<()> {
+    let overrides_vec = cli_overrides.parse_overrides().map_err(anyhow::Error::msg)?;
+    let overrides = ConfigOverrides {
+        cwd: args.cwd.clone(),
+        ..ConfigOverrides::default()
+    };
+    let config = Config::load_with_cli_overrides(overrides_vec, overrides)?;
+
+    let mut retention = config.ghost_commits.retention();
+    if let Some(keep) = args.keep_per_session {
+        retention.keep_per_session = (keep > 0).then_some(keep);
+    }
+    if let Some(days) = args.max_age_days {
+        retention.max_age = (days > 0).then(|| Duration::from_secs(days.saturating_mul(24 * 60 * 60)));
+    }
+
+    let report = code_git_tooling::prune_ghost_refs(&config.cwd, &retention, args.dry_run)
+        .with_context(|| format!("failed to prune ghost commits in {}", config.cwd.display()))?;
+    let worktrees = code_git_tooling::prune_stale_worktrees(&config.cwd, args.dry_run)
+        .context("failed to prune stale worktrees")?;
+
+    let verb = if args.dry_run { "Would prune" } else { "Pruned" };
+    println!("Ghost commits: {} kept ({})", report.kept, describe_retention(&retention));
+    println!("{verb} {} ghost commit ref(s)", report.pruned.len());
+    for ghost in &report.pruned {
+        let short = ghost.commit_id.get(..12).unwrap_or(&ghost.commit_id);
+        println!("  {short}  session {}", ghost.session);
+    }
+    println!("{verb} {} stale worktree(s)", worktrees.len());
+    for line in &worktrees {
+        println!("  {line}");
+    }
+    if !args.dry_run && !report.pruned.is_empty() {
+        println!("Objects are reclaimed on git's next garbage collection (`git gc`).");
+    }
+    Ok(())
+}
+
+fn describe_retention(retention: &GhostRetention) -> String {
+    let keep = retention
+        .keep_per_session
+        .map_or_else(|| "all per session".to_owned(), |keep| format!("newest {keep} per session"));
+    let age = retention.max_age.map_or_else(
+        || "no age limit".to_owned(),
+        |age| format!("max {} day(s)", age.as_secs() / (24 * 60 * 60)),
+    );
+    format!("{keep}, {age}")
+}
@@ -51,9 +51,19 @@ use tokio::runtime::{Builder as TokioRuntimeBuilder, Handle as TokioHandle};
 
 mod mcp_cmd;
 mod config_cmd;
+mod debug_network_cmd;
+mod docs_cmd;
+mod git_tooling_cmd;
+mod trust_cmd;
+mod usage_cmd;
 
 use crate::mcp_cmd::McpCli;
 use crate::config_cmd::ConfigCli;
+use crate::git_tooling_cmd::GitToolingCli;
+use crate::trust_cmd::TrustCli;
+use crate::usage_cmd::UsageCli;
+use crate::debug_network_cmd::DebugNetworkCommand;
+use crate::docs_cmd::DocsCli;
 
 const CLI_COMMAND_NAME: &str = "code";
 pub(crate) const CODEX_SECURE_MODE_ENV_VAR: &str = "CODEX_SECURE_MODE";
@@ -134,6 +144,7 @@ enum Subcommand {
     Mcp(McpCli),
 
     /// \[experimental\] Run the Codex MCP server (stdio transport).
+    #[clap(visible_alias = "mcp-serve")]
     McpServer,
 
     /// \[experimental\] Run the app server.
@@ -159,6 +170,9 @@ enum Subcommand {
     /// Fork a previous interactive session (picker by default; use --last to fork the most recent).
     Fork(ForkCommand),
 
+    /// Start an interactive session from a template in `.code/templates/`.
+    New(NewCommand),
+
     /// Run a command under a Codex sandbox.
     Sandbox(SandboxArgs),
 
@@ -179,9 +193,22 @@ enum Subcommand {
     /// Inspect and validate configuration files.
     Config(ConfigCli),
 
+    /// Trust, untrust, or list projects whose `.code/` config and hooks are loaded.
+    Trust(TrustCli),
+
+    /// Index and list local documentation bundles for the `docs_search` tool.
+    Docs(DocsCli),
+
     /// Manage locally-stored secrets (keyring-backed, encrypted at rest).
     Secrets(SecretsCli),
 
+    /// Report recorded usage, such as request latency per provider and model.
+    Usage(UsageCli),
+
+    /// Maintain the git state Code keeps for undo and review snapshots.
+    #[clap(name = "git-tooling")]
+    GitTooling(GitToolingCli),
+
     /// Download and run preview artifact by slug.
     Preview(PreviewArgs),
 
@@ -299,7 +326,12 @@ struct ResumeCommand {
     session_id: Option<String>,
 
     /// Continue the most recent session without showing the picker.
-    #[arg(long = "last", default_value_t = false, conflicts_with = "session_id")]
+    #[arg(
+        long = "last",
+        alias = "latest",
+        default_value_t = false,
+        conflicts_with = "session_id"
+    )]
     last: bool,
 
     #[clap(flatten)]
@@ -321,6 +353,25 @@ struct ForkCommand {
     config_overrides: TuiCli,
 }
 
+#[derive(Debug, Parser)]
+struct NewCommand {
+    /// Template to start from: `.code/templates/<NAME>.toml` in the project.
+    #[arg(
+        long = "template",
+        short = 't',
+        value_name = "NAME",
+        required_unless_present = "list"
+    )]
+    template: Option<String>,
+
+    /// List the project's templates and exit.
+    #[arg(long = "list", default_value_t = false, conflicts_with = "template")]
+    list: bool,
+
+    #[clap(flatten)]
+    config_overrides: TuiCli,
+}
+
 #[derive(Debug, Parser)]
 struct ReviewCommand {
     #[clap(flatten)]
@@ -421,6 +472,14 @@ struct ReviewExecOptions {
     /// When running /review, write the structured review output JSON to this file.
     #[arg(long = "review-output-json", value_name = "FILE")]
     review_output_json: Option<PathBuf>,
+
+    /// When running /review, write the findings to this file as SARIF 2.1.0.
+    #[arg(long = "review-output-sarif", value_name = "FILE")]
+    review_output_sarif: Option<PathBuf>,
+
+    /// Write review finding paths relative to the repository root.
+    #[arg(long = "review-paths-relative", default_value_t = false)]
+    review_paths_relative: bool,
 }
 
 #[derive(Debug, Parser)]
@@ -569,6 +628,9 @@ enum DebugCommand {
 
     /// Tooling: helps debug the app server.
     AppServer(DebugAppServerCommand),
+
+    /// Check connectivity through the configured proxy and CA settings.
+    Network(DebugNetworkCommand),
 }
 
 #[derive(Debug, Parser)]
@@ -915,6 +977,13 @@ async fn cli_main(code_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()>
                 )
                 .await?;
             }
+            DebugCommand::Network(mut network_cmd) => {
+                prepend_config_flags(
+                    &mut network_cmd.config_overrides,
+                    root_config_overrides.clone(),
+                );
+                network_cmd.run().await?;
+            }
             DebugCommand::AppServer(cmd) => match cmd.subcommand {
                 DebugAppServerSubcommand::SendMessageV2(cmd) => {
                     let code_bin = std::env::current_exe()?;
@@ -971,9 +1040,70 @@ async fn cli_main(code_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()>
         Some(Subcommand::Doctor) => {
             doctor_main().await?;
         }
-        Some(Subcommand::Config(config_cli)) => {
+        Some(Subcommand::Config(mut config_cli)) => {
+            prepend_config_flags(
+                &mut config_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
             config_cli.run().await?;
         }
+        Some(Subcommand::New(NewCommand {
+            template,
+            list,
+            mut config_overrides,
+        })) => {
+            config_overrides.finalize_defaults();
+            let cwd = match config_overrides.cwd.clone().or_else(|| interactive.cwd.clone()) {
+                Some(cwd) => cwd,
+                None => std::env::current_dir()?,
+            };
+            match template {
+                Some(template) if !list => {
+                    interactive = finalize_new_interactive(
+                        interactive,
+                        root_config_overrides.clone(),
+                        &cwd,
+                        &template,
+                        config_overrides,
+                    )?;
+                    let ExitSummary {
+                        token_usage,
+                        session_id,
+                    } = code_tui::run_main(interactive, code_linux_sandbox_exe).await?;
+                    if !token_usage.is_zero() {
+                        println!(
+                            "{}",
+                            code_core::protocol::FinalOutput::from(token_usage)
+                        );
+                    }
+                    if let Some(session_id) = session_id {
+                        println!(
+                            "To continue this session, run {} resume {}",
+                            resume_command_name(),
+                            session_id
+                        );
+                    }
+                }
+                _ => print_session_templates(&cwd)?,
+            }
+        }
+        Some(Subcommand::Trust(trust_cli)) => {
+            trust_cli.run().await?;
+        }
+        Some(Subcommand::Usage(usage_cli)) => {
+            usage_cli.run()?;
+        }
+        Some(Subcommand::Docs(mut docs_cli)) => {
+            prepend_config_flags(&mut docs_cli.config_overrides, root_config_overrides.clone());
+            docs_cli.run().await?;
+        }
+        Some(Subcommand::GitTooling(mut git_tooling_cli)) => {
+            prepend_config_flags(
+                &mut git_tooling_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            git_tooling_cli.run().await?;
+        }
         Some(Subcommand::Secrets(secrets_cli)) => {
             let code_home = code_core::config::find_code_home()
                 .context("failed to resolve CODE_HOME for secrets store")?;
@@ -1105,6 +1235,8 @@ fn apply_review_exec_options(mut exec_cli: ExecCli, options: ReviewExecOptions)
     exec_cli.include_plan_tool = options.include_plan_tool;
     exec_cli.last_message_file = options.last_message_file;
     exec_cli.review_output_json = options.review_output_json;
+    exec_cli.review_output_sarif = options.review_output_sarif;
+    exec_cli.review_paths_relative = options.review_paths_relative;
     exec_cli
 }
 
@@ -1477,6 +1609,48 @@ fn finalize_resume_interactive(
     interactive
 }
 
+/// Build the final `TuiCli` for a `code new --template` invocation: the
+/// template's context pack joins the session instructions, and its prompt is
+/// sent first unless one was given on the command line.
+fn finalize_new_interactive(
+    mut interactive: TuiCli,
+    root_config_overrides: CliConfigOverrides,
+    cwd: &Path,
+    template_name: &str,
+    new_cli: TuiCli,
+) -> anyhow::Result<TuiCli> {
+    let template = code_core::session_templates::load_template(cwd, template_name)?;
+    let code_home = code_core::config::find_code_home()?;
+    let pack = code_core::session_templates::build_context_pack(&template, cwd, &code_home);
+
+    interactive.finalize_defaults();
+    merge_resume_cli_flags(&mut interactive, new_cli);
+    interactive.session_context = Some(pack);
+    if interactive.prompt.is_none() {
+        interactive.prompt = template.prompt;
+    }
+    prepend_config_flags(&mut interactive.config_overrides, root_config_overrides);
+    Ok(interactive)
+}
+
+fn print_session_templates(cwd: &Path) -> anyhow::Result<()> {
+    let templates = code_core::session_templates::list_templates(cwd)?;
+    if templates.is_empty() {
+        println!(
+            "No templates defined. Add one as {}/<name>.toml.",
+            code_core::session_templates::templates_dir(cwd).display()
+        );
+        return Ok(());
+    }
+    for template in templates {
+        match template.description {
+            Some(description) => println!("{}\t{description}", template.name),
+            None => println!("{}", template.name),
+        }
+    }
+    Ok(())
+}
+
 /// Build the final `TuiCli` for a `code fork` invocation.
 fn finalize_fork_interactive(
     mut interactive: TuiCli,
@@ -2323,6 +2497,8 @@ mod tests {
             include_plan_tool: true,
             last_message_file: Some(PathBuf::from("last.txt")),
             review_output_json: Some(PathBuf::from("review.json")),
+            review_output_sarif: Some(PathBuf::from("review.sarif")),
+            review_paths_relative: true,
         };
 
         let exec_cli = apply_review_exec_options(exec_cli, options);
@@ -2351,6 +2527,11 @@ mod tests {
             exec_cli.review_output_json,
             Some(PathBuf::from("review.json"))
         );
+        assert_eq!(
+            exec_cli.review_output_sarif,
+            Some(PathBuf::from("review.sarif"))
+        );
+        assert!(exec_cli.review_paths_relative);
         assert!(exec_cli.command.is_none());
     }
 
@@ -0,0 +1,111 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use clap::Parser;
+use clap::Subcommand;
+use code_core::config::find_code_home;
+use code_core::config::list_project_trust;
+use code_core::config::project_trust_root;
+use code_core::config::remove_project_trust;
+use code_core::config::set_project_trusted;
+use code_core::config_loader::LoaderOverrides;
+use code_core::config_loader::load_config_layers_state_with_cwd;
+
+#[derive(Debug, Parser)]
+pub struct TrustCli {
+    #[command(subcommand)]
+    subcommand: TrustSubcommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum TrustSubcommand {
+    /// Trust a project so its `.code/` config layers and hooks are loaded.
+    Add(TrustPathArgs),
+
+    /// Remove the trust entry for a project.
+    Remove(TrustPathArgs),
+
+    /// List projects with a recorded trust level.
+    List,
+}
+
+#[derive(Debug, Parser)]
+struct TrustPathArgs {
+    /// Project directory (defaults to the git root of the current directory).
+    #[arg(value_name = "PATH")]
+    path: Option<PathBuf>,
+}
+
+impl TrustCli {
+    pub async fn run(self) -> Result<()> {
+        let code_home = find_code_home().context("failed to resolve CODE_HOME")?;
+        match self.subcommand {
+            TrustSubcommand::Add(args) => run_add(&code_home, args).await,
+            TrustSubcommand::Remove(args) => run_remove(&code_home, args),
+            TrustSubcommand::List => run_list(&code_home),
+        }
+    }
+}
+
+/// Resolves the project key the same way session startup does: the git root
+/// of the given directory, canonicalized.
+fn resolve_project(path: Option<PathBuf>) -> Result<PathBuf> {
+    let dir = match path {
+        Some(path) => path,
+        None => std::env::current_dir().context("failed to read the current directory")?,
+    };
+    let dir = dir
+        .canonicalize()
+        .with_context(|| format!("failed to resolve {}", dir.display()))?;
+    Ok(project_trust_root(&dir))
+}
+
+async fn run_add(code_home: &Path, args: TrustPathArgs) -> Result<()> {
+    let project = resolve_project(args.path)?;
+    // Report what was being skipped before the grant, while it is still untrusted.
+    let ignored = load_config_layers_state_with_cwd(
+        code_home,
+        Some(project.as_path()),
+        &[],
+        LoaderOverrides::default(),
+    )
+    .await
+    .map(|stack| stack.ignored_project_layers())
+    .unwrap_or_default();
+
+    set_project_trusted(code_home, &project)
+        .with_context(|| format!("failed to trust {}", project.display()))?;
+    println!("Trusted {}", project.display());
+    for layer in &ignored {
+        for file in layer.config_file.iter().chain(layer.hooks_file.iter()) {
+            println!("  now loaded: {}", file.display());
+        }
+    }
+    Ok(())
+}
+
+fn run_remove(code_home: &Path, args: TrustPathArgs) -> Result<()> {
+    let project = resolve_project(args.path)?;
+    if remove_project_trust(code_home, &project)
+        .with_context(|| format!("failed to update trust for {}", project.display()))?
+    {
+        println!("Removed trust for {}", project.display());
+    } else {
+        println!("No trust entry for {}", project.display());
+    }
+    Ok(())
+}
+
+fn run_list(code_home: &Path) -> Result<()> {
+    let entries = list_project_trust(code_home).context("failed to read config.toml")?;
+    if entries.is_empty() {
+        println!("No trusted projects.");
+        return Ok(());
+    }
+    for (path, level) in entries {
+        println!("{level:<10} {}", path.display());
+    }
+    Ok(())
+}
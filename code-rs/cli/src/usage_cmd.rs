@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+
+use anyhow::Context;
+use anyhow::Result;
+use chrono::Duration;
+use chrono::Utc;
+use clap::Parser;
+use clap::Subcommand;
+use code_core::config::find_code_home;
+use code_core::latency_usage::LatencySummary;
+use code_core::latency_usage::summarize_latency;
+use serde::Serialize;
+
+/// Time-to-first-token growth, relative to the previous window, flagged as a
+/// regression.
+const REGRESSION_THRESHOLD: f64 = 0.2;
+
+#[derive(Debug, Parser)]
+pub struct UsageCli {
+    #[command(subcommand)]
+    subcommand: UsageSubcommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum UsageSubcommand {
+    /// Compare time to first token and streaming speed per provider and model.
+    Latency(LatencyArgs),
+}
+
+#[derive(Debug, Parser)]
+struct LatencyArgs {
+    /// Days to report, ending today. The same number of days before them is
+    /// the baseline for regressions.
+    #[arg(long, value_name = "N", default_value_t = 7)]
+    days: u32,
+
+    /// Time-to-first-token target in milliseconds; adds the share of
+    /// requests that met it.
+    #[arg(long, value_name = "MS")]
+    slo_ms: Option<u64>,
+
+    /// Print JSON instead of a table.
+    #[arg(long)]
+    json: bool,
+}
+
+impl UsageCli {
+    pub fn run(self) -> Result<()> {
+        let code_home = find_code_home().context("failed to resolve CODE_HOME")?;
+        match self.subcommand {
+            UsageSubcommand::Latency(args) => run_latency(&code_home, args),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct LatencyRow {
+    #[serde(flatten)]
+    current: LatencySummary,
+    previous_first_token_p50_ms: Option<u64>,
+    regressed: bool,
+}
+
+fn run_latency(code_home: &std::path::Path, args: LatencyArgs) -> Result<()> {
+    if args.days == 0 {
+        anyhow::bail!("--days must be at least 1");
+    }
+    let days = i64::from(args.days);
+    let end = Utc::now().date_naive() + Duration::days(1);
+    let start = end - Duration::days(days);
+    let current = summarize_latency(code_home, start, end, args.slo_ms)
+        .context("failed to read usage/latency.json")?;
+    let previous: HashMap<(String, String), Option<u64>> =
+        summarize_latency(code_home, start - Duration::days(days), start, None)
+            .context("failed to read usage/latency.json")?
+            .into_iter()
+            .map(|summary| ((summary.provider, summary.model), summary.first_token_p50_ms))
+            .collect();
+
+    let rows: Vec<LatencyRow> = current
+        .into_iter()
+        .map(|summary| {
+            let previous_first_token_p50_ms = previous
+                .get(&(summary.provider.clone(), summary.model.clone()))
+                .copied()
+                .flatten();
+            let regressed = match (summary.first_token_p50_ms, previous_first_token_p50_ms) {
+                (Some(now), Some(before)) if before > 0 => {
+                    (now as f64 - before as f64) / before as f64 > REGRESSION_THRESHOLD
+                }
+                _ => false,
+            };
+            LatencyRow {
+                current: summary,
+                previous_first_token_p50_ms,
+                regressed,
+            }
+        })
+        .collect();
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&rows)?);
+        return Ok(());
+    }
+    if rows.is_empty() {
+        println!("No requests recorded in the last {days} day(s).");
+        return Ok(());
+    }
+
+    let label_width = rows
+        .iter()
+        .map(|row| row.current.provider.len() + row.current.model.len() + 1)
+        .max()
+        .unwrap_or(0)
+        .max("PROVIDER/MODEL".len());
+    let slo_header = args
+        .slo_ms
+        .map(|slo| slo_column(&format!("<={slo}ms")))
+        .unwrap_or_default();
+    println!(
+        "{:<label_width$}  {:>6}  {:>6}  {:>8}  {:>8}  {:>8}  {:>7}{slo_header}  P50 VS PREV",
+        "PROVIDER/MODEL", "REQS", "FAILED", "TTFT P50", "TTFT P90", "TTFT P99", "TOK/S",
+    );
+    for row in &rows {
+        let summary = &row.current;
+        let label = format!("{}/{}", summary.provider, summary.model);
+        let tokens_per_sec = summary
+            .tokens_per_sec
+            .map_or_else(|| "-".to_owned(), |rate| format!("{rate:.1}"));
+        let slo = match (args.slo_ms, summary.within_slo) {
+            (None, _) => String::new(),
+            (Some(_), Some(share)) => slo_column(&format!("{:.0}%", share * 100.0)),
+            (Some(_), None) => slo_column("-"),
+        };
+        println!(
+            "{label:<label_width$}  {:>6}  {:>6}  {:>8}  {:>8}  {:>8}  {tokens_per_sec:>7}{slo}  {}",
+            summary.requests,
+            summary.failures,
+            format_ms(summary.first_token_p50_ms),
+            format_ms(summary.first_token_p90_ms),
+            format_ms(summary.first_token_p99_ms),
+            describe_change(summary.first_token_p50_ms, row.previous_first_token_p50_ms, row.regressed),
+        );
+    }
+    if rows.iter().any(|row| row.regressed) {
+        println!();
+        println!(
+            "REGRESSED: median time to first token rose more than {:.0}% over the previous {days} day(s).",
+            REGRESSION_THRESHOLD * 100.0
+        );
+    }
+    Ok(())
+}
+
+fn slo_column(text: &str) -> String {
+    format!("  {text:>9}")
+}
+
+fn format_ms(ms: Option<u64>) -> String {
+    match ms {
+        None => "-".to_owned(),
+        Some(ms) if ms >= 10_000 => format!("{:.1}s", ms as f64 / 1000.0),
+        Some(ms) => format!("{ms}ms"),
+    }
+}
+
+fn describe_change(now: Option<u64>, before: Option<u64>, regressed: bool) -> String {
+    let (Some(now), Some(before)) = (now, before) else {
+        return "-".to_owned();
+    };
+    if before == 0 {
+        return "-".to_owned();
+    }
+    let change = (now as f64 - before as f64) / before as f64 * 100.0;
+    let marker = if regressed { "  REGRESSED" } else { "" };
+    format!("{change:+.0}% (was {}){marker}", format_ms(Some(before)))
+}
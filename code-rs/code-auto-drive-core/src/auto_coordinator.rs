@@ -50,6 +50,13 @@ use crate::auto_compact::{
     estimate_item_tokens,
 };
 use crate::coordinator_user_schema::{parse_user_turn_reply, user_turn_schema};
+use crate::stop_conditions::first_met_stop_condition;
+use crate::coordinator_wrap_up::{
+    parse_wrap_up_reply,
+    wrap_up_instruction,
+    wrap_up_schema,
+    WrapUpReport,
+};
 use crate::session_metrics::SessionMetrics;
 use crate::retry::{retry_with_backoff, RetryDecision, RetryError, RetryOptions};
 #[cfg(feature = "dev-faults")]
@@ -492,6 +499,11 @@ pub enum AutoCoordinatorEvent {
         conversation: Arc<[ResponseItem]>,
         show_notice: bool,
     },
+    /// Handoff note from the wrap-up turn after the turn cap was reached.
+    /// Sent just before the final decision.
+    Handoff {
+        note: String,
+    },
     StopAck,
 }
 
@@ -504,6 +516,7 @@ impl AutoCoordinatorEvent {
             Self::UserReply { .. } => "user_reply",
             Self::TokenMetrics { .. } => "token_metrics",
             Self::CompactedHistory { .. } => "compacted_history",
+            Self::Handoff { .. } => "handoff",
             Self::StopAck => "stop_ack",
         }
     }
@@ -2114,6 +2127,8 @@ fn run_auto_loop(inputs: AutoLoopInputs) -> Result<()> {
     let mut coordinator_turns_seen: u32 = 0;
     let mut active_model_slug = config.model.clone();
     let mut prev_compact_summary: Option<String> = None;
+    // Set when a CLI turn reports back; stop conditions run once per turn.
+    let mut stop_check_pending = false;
 
     loop {
         if stopped {
@@ -2140,6 +2155,31 @@ fn run_auto_loop(inputs: AutoLoopInputs) -> Result<()> {
                 continue;
             }
 
+            if std::mem::take(&mut stop_check_pending)
+                && let Some(reason) =
+                    first_met_stop_condition(&config.auto_drive.stop_conditions, &config.cwd)
+            {
+                debug!("auto coordinator stop condition met: {reason}");
+                decision_seq = decision_seq.wrapping_add(1);
+                let current_seq = decision_seq;
+                event_tx.send(AutoCoordinatorEvent::Decision {
+                    seq: current_seq,
+                    status: AutoCoordinatorStatus::Success,
+                    status_title: Some("Stop condition met".to_owned()),
+                    status_sent_to_user: Some(format!(
+                        "Stopped because a configured stop condition was met: {reason}."
+                    )),
+                    goal: None,
+                    cli: None,
+                    agents_timing: None,
+                    agents: Vec::new(),
+                    transcript: Vec::new(),
+                });
+                pending_ack_seq = Some(current_seq);
+                stopped = true;
+                continue;
+            }
+
             let conv = conv.as_ref().to_vec();
             let mut conv = filter_popular_commands(conv);
             let compaction_result = maybe_compact(CompactionRequest {
@@ -2223,17 +2263,61 @@ fn run_auto_loop(inputs: AutoLoopInputs) -> Result<()> {
                         && matches!(status, AutoCoordinatorStatus::Continue)
                     {
                         warn!(
-                            "auto coordinator turn cap reached ({coordinator_turns_seen}/{coordinator_turn_cap}); stopping"
+                            "auto coordinator turn cap reached ({coordinator_turns_seen}/{coordinator_turn_cap}); requesting wrap-up"
+                        );
+                        // One last turn to summarize and hand off instead of
+                        // stopping mid-flight. The pending CLI step is dropped.
+                        event_tx.send(AutoCoordinatorEvent::Action {
+                            message: "Turn limit reached; writing a handoff note…".to_owned(),
+                        });
+                        let mut wrap_up_conversation = conv.as_ref().to_vec();
+                        wrap_up_conversation.push(make_message(
+                            "developer",
+                            wrap_up_instruction(coordinator_turns_seen, coordinator_turn_cap),
+                        ));
+                        let wrap_up_schema = wrap_up_schema();
+                        let mut status_sent_to_user = format!(
+                            "Stopped after {coordinator_turns_seen} coordinator turns (cap={coordinator_turn_cap}) to prevent a runaway session."
                         );
+                        match request_wrap_up_report(DecisionRequest {
+                            runtime: &runtime,
+                            client: client.as_ref(),
+                            developer_intro,
+                            primary_goal: &primary_goal_message,
+                            coordinator_prompt: coordinator_prompt_message.as_deref(),
+                            time_budget_message: None,
+                            time_budget_deadline,
+                            loop_warning: None,
+                            schema: &wrap_up_schema,
+                            conversation: Arc::from(wrap_up_conversation),
+                            auto_instructions: auto_instructions.as_deref(),
+                            event_tx: &event_tx,
+                            cancel_token: &cancel_token,
+                            model_slug: active_model_slug.clone(),
+                            require_cli_model_routing: false,
+                            allowed_cli_routing_entries: &allowed_cli_routing_entries,
+                        }) {
+                            Ok(report) => {
+                                status_sent_to_user.push(' ');
+                                status_sent_to_user.push_str(report.progress_summary.trim());
+                                event_tx.send(AutoCoordinatorEvent::Handoff {
+                                    note: report.render(coordinator_turns_seen),
+                                });
+                            }
+                            Err(failure) => {
+                                warn!(
+                                    "coordinator wrap-up turn failed (schema={}): {:#}",
+                                    failure.schema_label, failure.error
+                                );
+                            }
+                        }
                         decision_seq = decision_seq.wrapping_add(1);
                         let current_seq = decision_seq;
                         let event = AutoCoordinatorEvent::Decision {
                             seq: current_seq,
                             status: AutoCoordinatorStatus::Failed,
                             status_title: Some("Turn limit reached".to_owned()),
-                            status_sent_to_user: Some(format!(
-                                "Stopped after {coordinator_turns_seen} coordinator turns (cap={coordinator_turn_cap}) to prevent a runaway session."
-                            )),
+                            status_sent_to_user: Some(status_sent_to_user),
                             goal,
                             cli: None,
                             agents_timing: None,
@@ -2515,6 +2599,7 @@ fn run_auto_loop(inputs: AutoLoopInputs) -> Result<()> {
             }
             Ok(AutoCoordinatorCommand::UpdateConversation(conv)) => {
                 consecutive_decision_failures = 0;
+                stop_check_pending = !config.auto_drive.stop_conditions.is_empty();
                 let conv = conv.as_ref().to_vec();
                 let filtered = Arc::<[ResponseItem]>::from(enforce_hard_message_limit(
                     filter_popular_commands(conv),
@@ -3152,6 +3237,15 @@ fn request_user_turn_decision(
     Ok((user_response, cli_command))
 }
 
+#[tracing::instrument(skip_all)]
+fn request_wrap_up_report(request: DecisionRequest<'_>) -> Result<WrapUpReport, DecisionFailure> {
+    let result = request_decision(request)
+        .map_err(|err| DecisionFailure::new(err, "auto_coordinator_wrap_up", None))?;
+    parse_wrap_up_reply(&result.output_text).map_err(|err| {
+        DecisionFailure::new(err, "auto_coordinator_wrap_up", Some(result.output_text.clone()))
+    })
+}
+
 fn request_decision_with_model(
     request: DecisionRequest<'_>,
 ) -> Result<RequestStreamResult> {
@@ -3,6 +3,7 @@ use std::time::{Duration, Instant};
 use code_common::elapsed::format_duration;
 use code_core::config_types::ReasoningEffort;
 use code_core::protocol::ReviewContextMetadata;
+use code_core::protocol::ReviewFinding;
 use code_core::protocol::ReviewOutputEvent;
 use code_protocol::protocol::ReviewTarget;
 use code_core::review_coord::{bump_snapshot_epoch, try_acquire_lock};
@@ -227,6 +228,8 @@ pub struct AutoResolveState {
     pub last_fix_message: Option<String>,
     pub last_reviewed_commit: Option<String>,
     pub snapshot_epoch: Option<u64>,
+    /// Findings left out of fix passes, reported when the loop ends.
+    pub acknowledged_findings: Vec<ReviewFinding>,
 }
 
 impl AutoResolveState {
@@ -258,6 +261,7 @@ impl AutoResolveState {
             last_fix_message: None,
             last_reviewed_commit: None,
             snapshot_epoch: None,
+            acknowledged_findings: Vec::new(),
         }
     }
 }
@@ -265,6 +269,8 @@ impl AutoResolveState {
 #[derive(Clone)]
 pub enum AutoResolvePhase {
     WaitingForReview,
+    /// Waiting for the user to choose which findings to fix.
+    AwaitingSelection { review: ReviewOutputEvent, selected: Vec<bool> },
     PendingFix { review: ReviewOutputEvent },
     AwaitingFix { review: ReviewOutputEvent },
     AwaitingJudge { review: ReviewOutputEvent },
@@ -0,0 +1,116 @@
+//! Wrap-up turn requested when the coordinator turn cap is reached.
+//!
+//! Instead of stopping mid-flight, the coordinator gets one last turn that
+//! may not start new work: it summarizes progress, lists what is left and
+//! writes a handoff note for whoever picks the task up next.
+
+use anyhow::Context;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::auto_coordinator::extract_first_json_object;
+
+pub(crate) fn wrap_up_instruction(turns: u32, cap: u32) -> String {
+    format!(
+        "Turn budget reached: {turns} of {cap} coordinator turns used. This is your final turn and no further CLI turns will run. Do not plan or request new work. Respond with the wrap-up JSON: summarize what was accomplished, list the work that remains (most important first), and write a concise handoff note a teammate can act on without reading this transcript (current state, where to resume, known risks)."
+    )
+}
+
+pub(crate) fn wrap_up_schema() -> Value {
+    serde_json::json!({
+        "type": "object",
+        "additionalProperties": false,
+        "properties": {
+            "progress_summary": {
+                "type": "string",
+                "maxLength": 1200,
+                "description": "What was accomplished toward the goal so far."
+            },
+            "remaining_work": {
+                "type": "array",
+                "maxItems": 12,
+                "items": { "type": "string", "maxLength": 300 },
+                "description": "Outstanding work, most important first. Empty when nothing is left."
+            },
+            "handoff_note": {
+                "type": "string",
+                "maxLength": 2000,
+                "description": "Note for the next person or run: current state, where to resume, known risks."
+            }
+        },
+        "required": ["progress_summary", "remaining_work", "handoff_note"]
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub(crate) struct WrapUpReport {
+    pub progress_summary: String,
+    pub remaining_work: Vec<String>,
+    pub handoff_note: String,
+}
+
+impl WrapUpReport {
+    /// Markdown handoff surfaced in run summaries and the last-message file.
+    pub(crate) fn render(&self, turns: u32) -> String {
+        let mut out = format!(
+            "Auto Drive stopped after reaching its turn budget ({turns} coordinator turns).\n\n## Progress\n{}\n",
+            self.progress_summary.trim()
+        );
+        out.push_str("\n## Remaining work\n");
+        let remaining: Vec<&str> = self
+            .remaining_work
+            .iter()
+            .map(|item| item.trim())
+            .filter(|item| !item.is_empty())
+            .collect();
+        if remaining.is_empty() {
+            out.push_str("- Nothing outstanding.\n");
+        }
+        for item in remaining {
+            out.push_str("- ");
+            out.push_str(item);
+            out.push('\n');
+        }
+        out.push_str("\n## Handoff\n");
+        out.push_str(self.handoff_note.trim());
+        out
+    }
+}
+
+pub(crate) fn parse_wrap_up_reply(raw: &str) -> anyhow::Result<WrapUpReport> {
+    match serde_json::from_str(raw) {
+        Ok(report) => Ok(report),
+        Err(first_err) => {
+            let Some(blob) = extract_first_json_object(raw) else {
+                return Err(first_err).context("parsing coordinator wrap-up JSON");
+            };
+            let first_err_msg = first_err.to_string();
+            serde_json::from_str(&blob).with_context(|| {
+                format!(
+                    "parsing coordinator wrap-up JSON (after salvage); initial parse error: {first_err_msg}"
+                )
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+
+    #[test]
+    fn parses_and_renders_report() -> Result<()> {
+        let raw = r#"Final answer:
+{"progress_summary":"Ported the parser.","remaining_work":["Fix CLI flags"," "],"handoff_note":"Resume in src/cli.rs."}"#;
+        let report = parse_wrap_up_reply(raw)?;
+        assert_eq!(report.remaining_work.len(), 2);
+
+        let rendered = report.render(20);
+        assert!(rendered.starts_with("Auto Drive stopped after reaching its turn budget (20"));
+        assert!(rendered.contains("## Progress\nPorted the parser.\n"));
+        assert!(rendered.contains("## Remaining work\n- Fix CLI flags\n\n## Handoff"));
+        assert!(rendered.ends_with("Resume in src/cli.rs."));
+        Ok(())
+    }
+}
@@ -0,0 +1,149 @@
+//! Choosing which review findings an auto-resolve fix pass works on.
+//!
+//! `auto_drive.auto_resolve_findings` decides whether every finding goes to
+//! the fix phase or only those meeting the configured priority/confidence
+//! threshold (optionally adjusted by the user in a picker). Findings left out
+//! are carried along as acknowledged-but-unfixed so the final report can
+//! still list them.
+
+use code_core::config_types::AutoDriveSettings;
+use code_core::config_types::AutoResolveFindingSelection;
+use code_core::protocol::ReviewFinding;
+use code_core::protocol::ReviewOutputEvent;
+
+/// Whether `finding` passes the configured priority and confidence bounds.
+/// Unset bounds accept everything.
+pub fn finding_meets_threshold(finding: &ReviewFinding, settings: &AutoDriveSettings) -> bool {
+    let priority_ok = settings
+        .auto_resolve_max_priority
+        .is_none_or(|max| finding.priority <= max);
+    let confidence_ok = settings
+        .auto_resolve_min_confidence
+        .is_none_or(|min| finding.confidence_score >= min);
+    priority_ok && confidence_ok
+}
+
+/// Initial selection for `review`: all findings in `all` mode, otherwise
+/// those meeting the threshold.
+pub fn default_finding_selection(review: &ReviewOutputEvent, settings: &AutoDriveSettings) -> Vec<bool> {
+    review
+        .findings
+        .iter()
+        .map(|finding| {
+            settings.auto_resolve_findings == AutoResolveFindingSelection::All
+                || finding_meets_threshold(finding, settings)
+        })
+        .collect()
+}
+
+/// Splits `review` into the output handed to the fix phase (selected
+/// findings only) and the findings left unfixed. Missing entries in
+/// `selected` count as not selected.
+pub fn split_findings(review: &ReviewOutputEvent, selected: &[bool]) -> (ReviewOutputEvent, Vec<ReviewFinding>) {
+    let (chosen, skipped): (Vec<_>, Vec<_>) = review
+        .findings
+        .iter()
+        .enumerate()
+        .partition(|(index, _)| selected.get(*index).copied().unwrap_or(false));
+    let to_fix = ReviewOutputEvent {
+        findings: chosen.into_iter().map(|(_, finding)| finding.clone()).collect(),
+        ..review.clone()
+    };
+    let acknowledged = skipped.into_iter().map(|(_, finding)| finding.clone()).collect();
+    (to_fix, acknowledged)
+}
+
+/// Applies the configured selection without asking; `pick` behaves like
+/// `threshold` here.
+pub fn select_findings_for_fix(
+    review: &ReviewOutputEvent,
+    settings: &AutoDriveSettings,
+) -> (ReviewOutputEvent, Vec<ReviewFinding>) {
+    split_findings(review, &default_finding_selection(review, settings))
+}
+
+/// Whether a finding with the same title and location was already
+/// acknowledged in an earlier review pass.
+pub fn is_acknowledged(acknowledged: &[ReviewFinding], finding: &ReviewFinding) -> bool {
+    acknowledged.iter().any(|existing| {
+        existing.title == finding.title && existing.code_location == finding.code_location
+    })
+}
+
+/// Adds `findings` to `acknowledged`, skipping duplicates.
+pub fn extend_acknowledged(acknowledged: &mut Vec<ReviewFinding>, findings: Vec<ReviewFinding>) {
+    for finding in findings {
+        if !is_acknowledged(acknowledged, &finding) {
+            acknowledged.push(finding);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use code_core::protocol::ReviewCodeLocation;
+    use code_core::protocol::ReviewLineRange;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    fn finding(title: &str, priority: i32, confidence_score: f32) -> ReviewFinding {
+        ReviewFinding {
+            title: title.to_owned(),
+            body: String::new(),
+            confidence_score,
+            priority,
+            code_location: ReviewCodeLocation {
+                absolute_file_path: PathBuf::from("/repo/src/lib.rs"),
+                line_range: ReviewLineRange { start: 1, end: 1 },
+            },
+        }
+    }
+
+    fn titles(findings: &[ReviewFinding]) -> Vec<&str> {
+        findings.iter().map(|finding| finding.title.as_str()).collect()
+    }
+
+    fn review() -> ReviewOutputEvent {
+        ReviewOutputEvent {
+            findings: vec![
+                finding("p0 sure", 0, 0.9),
+                finding("p1 unsure", 1, 0.3),
+                finding("p3 sure", 3, 0.95),
+            ],
+            overall_explanation: "three issues".to_owned(),
+            ..ReviewOutputEvent::default()
+        }
+    }
+
+    #[test]
+    fn all_mode_fixes_everything() {
+        let (to_fix, acknowledged) = select_findings_for_fix(&review(), &AutoDriveSettings::default());
+        assert_eq!(to_fix, review());
+        assert!(acknowledged.is_empty());
+    }
+
+    #[test]
+    fn threshold_mode_acknowledges_findings_outside_the_bounds() {
+        let settings = AutoDriveSettings {
+            auto_resolve_findings: AutoResolveFindingSelection::Threshold,
+            auto_resolve_max_priority: Some(1),
+            auto_resolve_min_confidence: Some(0.5),
+            ..AutoDriveSettings::default()
+        };
+        let (to_fix, acknowledged) = select_findings_for_fix(&review(), &settings);
+        assert_eq!(titles(&to_fix.findings), vec!["p0 sure"]);
+        assert_eq!(to_fix.overall_explanation, "three issues");
+        assert_eq!(titles(&acknowledged), vec!["p1 unsure", "p3 sure"]);
+    }
+
+    #[test]
+    fn split_uses_explicit_selection_and_dedupes_acknowledged() {
+        let (to_fix, skipped) = split_findings(&review(), &[false, true]);
+        assert_eq!(titles(&to_fix.findings), vec!["p1 unsure"]);
+
+        let mut acknowledged = vec![finding("p0 sure", 0, 0.9)];
+        extend_acknowledged(&mut acknowledged, skipped);
+        assert_eq!(titles(&acknowledged), vec!["p0 sure", "p3 sure"]);
+    }
+}
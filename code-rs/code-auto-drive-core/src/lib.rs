@@ -4,8 +4,11 @@ mod auto_compact;
 mod session_metrics;
 mod coordinator_router;
 mod coordinator_user_schema;
+mod coordinator_wrap_up;
 mod controller;
+mod finding_selection;
 mod retry;
+mod stop_conditions;
 
 #[cfg(feature = "dev-faults")]
 mod faults;
@@ -46,6 +49,14 @@ pub use controller::{
     AUTO_RESOLVE_REVIEW_FOLLOWUP,
 };
 
+pub use finding_selection::{
+    default_finding_selection,
+    extend_acknowledged,
+    finding_meets_threshold,
+    is_acknowledged,
+    select_findings_for_fix,
+    split_findings,
+};
 pub use auto_drive_history::AutoDriveHistory;
 pub use session_metrics::SessionMetrics;
 pub use coordinator_router::{
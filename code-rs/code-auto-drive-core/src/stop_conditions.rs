@@ -0,0 +1,150 @@
+//! User-defined stop conditions from `[auto_drive.stop_conditions]`.
+//!
+//! The coordinator checks them each time a CLI turn completes, before asking
+//! the model for the next decision, and ends the run as soon as one holds.
+//! File markers are checked first since they are cheap; commands run in the
+//! session directory and count as unmet when they fail to start or time out.
+
+use std::path::Path;
+use std::process::Command;
+use std::process::Stdio;
+use std::time::Duration;
+use std::time::Instant;
+
+use code_core::config_types::AutoDriveCommandStopCondition;
+use code_core::config_types::AutoDriveFileStopCondition;
+use code_core::config_types::AutoDriveStopConditions;
+use tracing::debug;
+use tracing::warn;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Describes the first condition that holds, or `None` when none do.
+pub(crate) fn first_met_stop_condition(
+    conditions: &AutoDriveStopConditions,
+    cwd: &Path,
+) -> Option<String> {
+    if let Some(file) = conditions
+        .files
+        .iter()
+        .find(|condition| file_condition_met(condition, cwd))
+    {
+        return Some(format!(
+            "{} contains \"{}\"",
+            file.path.display(),
+            file.contains
+        ));
+    }
+    conditions
+        .commands
+        .iter()
+        .find(|condition| command_condition_met(condition, cwd))
+        .map(|command| format!("`{}` exited with {}", command.run, command.exit_code))
+}
+
+fn file_condition_met(condition: &AutoDriveFileStopCondition, cwd: &Path) -> bool {
+    match std::fs::read_to_string(cwd.join(&condition.path)) {
+        Ok(contents) => contents.contains(&condition.contains),
+        Err(err) => {
+            debug!(
+                "auto drive stop condition: cannot read {}: {err}",
+                condition.path.display()
+            );
+            false
+        }
+    }
+}
+
+fn command_condition_met(condition: &AutoDriveCommandStopCondition, cwd: &Path) -> bool {
+    let mut command = if cfg!(windows) {
+        let mut command = Command::new("cmd");
+        command.arg("/C");
+        command
+    } else {
+        let mut command = Command::new("sh");
+        command.arg("-c");
+        command
+    };
+    let mut child = match command
+        .arg(&condition.run)
+        .current_dir(cwd)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(err) => {
+            warn!("auto drive stop condition `{}` failed to start: {err}", condition.run);
+            return false;
+        }
+    };
+
+    let deadline = Instant::now() + Duration::from_secs(condition.timeout_secs);
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => return status.code() == Some(condition.exit_code),
+            Ok(None) if Instant::now() < deadline => std::thread::sleep(POLL_INTERVAL),
+            Ok(None) => {
+                warn!(
+                    "auto drive stop condition `{}` timed out after {}s",
+                    condition.run, condition.timeout_secs
+                );
+                let _ = child.kill();
+                let _ = child.wait();
+                return false;
+            }
+            Err(err) => {
+                warn!("auto drive stop condition `{}` failed: {err}", condition.run);
+                return false;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    #[test]
+    fn file_marker_is_reported() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        std::fs::write(dir.path().join("STATUS.md"), "phase 2\nALL DONE\n").expect("write status");
+        let mut conditions = AutoDriveStopConditions {
+            commands: Vec::new(),
+            files: vec![AutoDriveFileStopCondition {
+                path: PathBuf::from("STATUS.md"),
+                contains: "SHIPPED".to_owned(),
+            }],
+        };
+        assert_eq!(first_met_stop_condition(&conditions, dir.path()), None);
+
+        conditions.files[0].contains = "ALL DONE".to_owned();
+        assert_eq!(
+            first_met_stop_condition(&conditions, dir.path()).as_deref(),
+            Some("STATUS.md contains \"ALL DONE\"")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn command_exit_code_must_match() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let command = |run: &str, exit_code: i32| AutoDriveStopConditions {
+            commands: vec![AutoDriveCommandStopCondition {
+                run: run.to_owned(),
+                exit_code,
+                timeout_secs: 5,
+            }],
+            files: Vec::new(),
+        };
+        assert_eq!(first_met_stop_condition(&command("exit 1", 0), dir.path()), None);
+        assert_eq!(
+            first_met_stop_condition(&command("exit 3", 3), dir.path()).as_deref(),
+            Some("`exit 3` exited with 3")
+        );
+        assert_eq!(first_met_stop_condition(&command("test -f missing", 0), dir.path()), None);
+    }
+}
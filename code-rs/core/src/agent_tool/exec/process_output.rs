@@ -7,6 +7,11 @@ pub(super) async fn stream_child_output(
     agent_id: &str,
     mut child: tokio::process::Child,
 ) -> Result<(std::process::ExitStatus, String, String), String> {
+    let _registration = crate::child_registry::register(
+        crate::child_registry::ChildKind::Agent,
+        child.id(),
+        agent_id,
+    );
     let agent_id_owned = agent_id.to_owned();
     let stop_flag = Arc::new(AtomicBool::new(false));
     let stop_clone = stop_flag.clone();
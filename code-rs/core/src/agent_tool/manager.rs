@@ -411,6 +411,15 @@ impl AgentManager {
         }
     }
 
+    /// Cancels every agent that is still running, returning how many were stopped.
+    pub fn cancel_all(&mut self) -> usize {
+        let agent_ids: Vec<String> = self.handles.keys().cloned().collect();
+        agent_ids
+            .iter()
+            .filter(|agent_id| self.cancel_agent(agent_id))
+            .count()
+    }
+
     pub async fn cancel_batch(&mut self, batch_id: &str) -> usize {
         let agent_ids: Vec<String> = self
             .agents
@@ -138,19 +138,27 @@ pub(crate) async fn apply_patch(
             .await;
     }
 
-    let auto_approved = match assess_patch_safety(
+    let git_head_reason = sess.pending_git_head_reconfirmation();
+    let safety = match assess_patch_safety(
         &action,
         sess.get_approval_policy(),
-        sess.get_sandbox_policy(),
+        &sess.get_sandbox_policy(),
         sess.get_cwd(),
     ) {
+        SafetyCheck::AutoApprove { .. } if git_head_reason.is_some() => SafetyCheck::AskUser,
+        safety => safety,
+    };
+    let auto_approved = match safety {
         SafetyCheck::AutoApprove { .. } => true,
         SafetyCheck::AskUser => {
             let rx = sess
-                .request_patch_approval(sub_id.to_owned(), call_id.to_owned(), &action, None, None)
+                .request_patch_approval(sub_id.to_owned(), call_id.to_owned(), &action, git_head_reason, None)
                 .await;
             match rx.await.unwrap_or_default() {
-                ReviewDecision::Approved | ReviewDecision::ApprovedForSession => false,
+                ReviewDecision::Approved | ReviewDecision::ApprovedForSession => {
+                    sess.clear_git_head_reconfirmation();
+                    false
+                }
                 ReviewDecision::Denied | ReviewDecision::Abort => {
                     return ApplyPatchResult::Reply(ResponseInputItem::FunctionCallOutput {
                         call_id: call_id.to_owned(),
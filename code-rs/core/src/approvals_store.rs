@@ -0,0 +1,162 @@
+//! Command approvals kept across sessions in `.code/approvals.toml` at the
+//! repository root (or the working directory outside a repository).
+//!
+//! Entries are written when a command is approved with `persist` set on
+//! `Op::RegisterApprovedCommand`, from `/approvals add`, or with
+//! `code exec approvals add`. Like the rest of `.code/`, they are only loaded
+//! for trusted projects, so a cloned repository cannot pre-approve commands.
+
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::protocol::ApprovedCommandMatchKind;
+
+const APPROVALS_FILE: &str = "approvals.toml";
+const APPROVALS_HEADER: &str = "# Commands approved for every session in this project.\n\
+                                # Managed by `/approvals` and `code exec approvals`.\n\n";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredApproval {
+    pub argv: Vec<String>,
+    pub match_kind: ApprovedCommandMatchKind,
+}
+
+impl StoredApproval {
+    /// `cargo test` (prefix) style label for listings.
+    pub fn describe(&self) -> String {
+        let command = shlex::try_join(self.argv.iter().map(String::as_str))
+            .unwrap_or_else(|_| self.argv.join(" "));
+        let kind = match self.match_kind {
+            ApprovedCommandMatchKind::Exact => "exact",
+            ApprovedCommandMatchKind::Prefix => "prefix",
+        };
+        format!("{command} ({kind})")
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ApprovalsFile {
+    #[serde(default)]
+    commands: Vec<StoredApproval>,
+}
+
+/// `.code/approvals.toml` for the project containing `cwd`.
+pub fn approvals_path(cwd: &Path) -> PathBuf {
+    crate::git_info::get_git_repo_root(cwd)
+        .unwrap_or_else(|| cwd.to_path_buf())
+        .join(".code")
+        .join(APPROVALS_FILE)
+}
+
+/// Stored approvals in the order they were added.
+pub fn load_approvals(cwd: &Path) -> std::io::Result<Vec<StoredApproval>> {
+    let path = approvals_path(cwd);
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    let file: ApprovalsFile = toml::from_str(&contents).map_err(|err| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("{}: {err}", path.display()),
+        )
+    })?;
+    Ok(file
+        .commands
+        .into_iter()
+        .filter(|approval| !approval.argv.is_empty())
+        .collect())
+}
+
+/// Saves an approval; returns `false` when the same pattern is already stored.
+pub fn add_approval(
+    cwd: &Path,
+    command: &[String],
+    match_kind: ApprovedCommandMatchKind,
+) -> std::io::Result<bool> {
+    let argv = crate::command_canonicalization::normalize_command_for_persistence(command);
+    if argv.is_empty() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "cannot store an empty command",
+        ));
+    }
+    let mut approvals = load_approvals(cwd)?;
+    let approval = StoredApproval { argv, match_kind };
+    if approvals.contains(&approval) {
+        return Ok(false);
+    }
+    approvals.push(approval);
+    write_approvals(cwd, approvals)?;
+    Ok(true)
+}
+
+/// Removes the stored approvals for `command` with `match_kind`; returns
+/// whether any were stored.
+pub fn revoke_approval(
+    cwd: &Path,
+    command: &[String],
+    match_kind: ApprovedCommandMatchKind,
+) -> std::io::Result<bool> {
+    let argv = crate::command_canonicalization::normalize_command_for_persistence(command);
+    let mut approvals = load_approvals(cwd)?;
+    let before = approvals.len();
+    approvals.retain(|approval| !(approval.argv == argv && approval.match_kind == match_kind));
+    if approvals.len() == before {
+        return Ok(false);
+    }
+    write_approvals(cwd, approvals)?;
+    Ok(true)
+}
+
+fn write_approvals(cwd: &Path, commands: Vec<StoredApproval>) -> std::io::Result<()> {
+    let path = approvals_path(cwd);
+    let Some(dir) = path.parent() else {
+        return Err(std::io::Error::other("no directory for the approvals store"));
+    };
+    fs::create_dir_all(dir)?;
+    let body = toml::to_string(&ApprovalsFile { commands }).map_err(std::io::Error::other)?;
+    let tmp = tempfile::NamedTempFile::new_in(dir)?;
+    fs::write(tmp.path(), format!("{APPROVALS_HEADER}{body}"))?;
+    tmp.persist(&path).map_err(|err| err.error)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::ApprovedCommandMatchKind::Exact;
+    use crate::protocol::ApprovedCommandMatchKind::Prefix;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn argv(command: &str) -> Vec<String> {
+        command.split(' ').map(str::to_owned).collect()
+    }
+
+    #[test]
+    fn add_list_and_revoke_round_trip() {
+        let project = TempDir::new().expect("tempdir");
+        let cwd = project.path();
+        assert_eq!(load_approvals(cwd).expect("empty"), Vec::new());
+
+        assert!(add_approval(cwd, &argv("cargo test"), Prefix).expect("add"));
+        assert!(!add_approval(cwd, &argv("cargo test"), Prefix).expect("duplicate"));
+        assert!(add_approval(cwd, &argv("git status"), Exact).expect("add"));
+
+        let stored = load_approvals(cwd).expect("load");
+        assert_eq!(
+            stored.iter().map(StoredApproval::describe).collect::<Vec<_>>(),
+            vec!["cargo test (prefix)", "git status (exact)"]
+        );
+
+        assert!(!revoke_approval(cwd, &argv("cargo test"), Exact).expect("kind differs"));
+        assert!(revoke_approval(cwd, &argv("cargo test"), Prefix).expect("revoke"));
+        assert_eq!(load_approvals(cwd).expect("load").len(), 1);
+    }
+}
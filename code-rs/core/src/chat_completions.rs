@@ -28,9 +28,9 @@ use crate::debug_logger::DebugLogger;
 use crate::error::CodexErr;
 use crate::error::Result;
 use crate::error::RetryLimitReachedError;
-use crate::error::UnexpectedResponseError;
 use crate::model_family::ModelFamily;
 use crate::openai_tools::create_tools_json_for_chat_completions_api;
+use crate::provider_errors::unexpected_status_error;
 use crate::util::{backoff, header_map_to_json};
 use std::sync::{Arc, Mutex};
 use code_protocol::models::ContentItem;
@@ -485,11 +485,7 @@ pub(crate) async fn stream_chat_completions(
                         );
                         let _ = logger.end_request_log(&request_id);
                     }
-                    return Err(CodexErr::UnexpectedStatus(UnexpectedResponseError {
-                        status,
-                        body,
-                        request_id: None,
-                    }));
+                    return Err(unexpected_status_error(status, body, None));
                 }
 
                 if attempt > max_retries {
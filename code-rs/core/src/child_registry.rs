@@ -0,0 +1,396 @@
+//! Registry of helper processes spawned on behalf of sessions.
+//!
+//! REPL kernels, MCP stdio servers, agent processes and the internal browser
+//! are normally stopped with their session, but a crash or `kill -9` skips
+//! that cleanup and leaves them running. Every live helper is therefore
+//! recorded in `~/.code/children/pid-<pid>.json`. On startup, [`init`] adopts
+//! the files of owners that are no longer running and terminates the helpers
+//! they list. A helper is only signalled while its PID still refers to the
+//! process that was recorded (same start time), so a reused PID is left alone.
+
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use tracing::info;
+use tracing::warn;
+
+/// How long an orphaned helper gets to exit after SIGTERM before SIGKILL.
+const ORPHAN_TERM_GRACE: Duration = Duration::from_secs(2);
+const ORPHAN_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ChildKind {
+    ReplKernel,
+    McpServer,
+    Agent,
+    Browser,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct ChildRecord {
+    kind: ChildKind,
+    pid: u32,
+    /// Start time of `pid` when it was registered; see [`process_identity`].
+    identity: String,
+    label: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RegistryFile {
+    owner_pid: u32,
+    children: Vec<ChildRecord>,
+}
+
+struct Registry {
+    path: PathBuf,
+    children: BTreeMap<u64, ChildRecord>,
+}
+
+impl Registry {
+    fn persist(&self) {
+        if self.children.is_empty() {
+            let _ = fs::remove_file(&self.path);
+            return;
+        }
+        let file = RegistryFile {
+            owner_pid: std::process::id(),
+            children: self.children.values().cloned().collect(),
+        };
+        let result = serde_json::to_vec_pretty(&file)
+            .map_err(std::io::Error::other)
+            .and_then(|contents| {
+                if let Some(parent) = self.path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                // Write then rename so a crash never leaves a truncated file.
+                let tmp = self.path.with_extension("json.tmp");
+                fs::write(&tmp, contents)?;
+                fs::rename(&tmp, &self.path)
+            });
+        if let Err(err) = result {
+            warn!("failed to update child registry {}: {err}", self.path.display());
+        }
+    }
+}
+
+static REGISTRY: LazyLock<Mutex<Option<Registry>>> = LazyLock::new(|| Mutex::new(None));
+static NEXT_TOKEN: AtomicU64 = AtomicU64::new(1);
+
+/// Points the registry at `code_home` and reaps helpers left behind by
+/// sessions that died without cleaning up. Only the first call per process
+/// has an effect; reaping runs on a background thread so startup never waits
+/// on slow-to-exit orphans.
+pub(crate) fn init(code_home: &Path) {
+    let dir = code_home.join("children");
+    {
+        let Ok(mut registry) = REGISTRY.lock() else {
+            return;
+        };
+        if registry.is_some() {
+            return;
+        }
+        *registry = Some(Registry {
+            path: dir.join(format!("pid-{}.json", std::process::id())),
+            children: BTreeMap::new(),
+        });
+    }
+
+    #[cfg(feature = "browser-automation")]
+    code_browser::global::set_process_observer(track_browser_process);
+
+    let spawned = std::thread::Builder::new()
+        .name("child-reaper".to_string())
+        .spawn(move || {
+            let reaped = reap_orphans(&dir, std::process::id());
+            if reaped > 0 {
+                info!("terminated {reaped} helper process(es) orphaned by a previous session");
+            }
+        });
+    if let Err(err) = spawned {
+        warn!("failed to start orphaned helper cleanup: {err}");
+    }
+}
+
+/// Removes its child from the registry when dropped. Hold it for as long as
+/// the helper process is owned.
+#[derive(Debug)]
+pub(crate) struct ChildRegistration {
+    token: u64,
+}
+
+impl Drop for ChildRegistration {
+    fn drop(&mut self) {
+        if let Ok(mut registry) = REGISTRY.lock()
+            && let Some(registry) = registry.as_mut()
+            && registry.children.remove(&self.token).is_some()
+        {
+            registry.persist();
+        }
+    }
+}
+
+/// Records a freshly spawned helper. Returns `None` when the registry has not
+/// been initialized or the process can't be identified reliably enough to be
+/// reaped safely later.
+pub(crate) fn register(kind: ChildKind, pid: Option<u32>, label: &str) -> Option<ChildRegistration> {
+    let pid = pid?;
+    let identity = process_identity(pid)?;
+    let mut registry = REGISTRY.lock().ok()?;
+    let registry = registry.as_mut()?;
+    let token = NEXT_TOKEN.fetch_add(1, Ordering::Relaxed);
+    registry.children.insert(
+        token,
+        ChildRecord {
+            kind,
+            pid,
+            identity,
+            label: label.to_string(),
+        },
+    );
+    registry.persist();
+    Some(ChildRegistration { token })
+}
+
+/// Registrations for Chrome processes launched by the browser manager,
+/// keyed by PID because the browser crate reports launch and exit separately.
+#[cfg(feature = "browser-automation")]
+static BROWSER_REGISTRATIONS: LazyLock<Mutex<std::collections::HashMap<u32, ChildRegistration>>> =
+    LazyLock::new(|| Mutex::new(std::collections::HashMap::new()));
+
+#[cfg(feature = "browser-automation")]
+fn track_browser_process(event: code_browser::global::BrowserProcessEvent) {
+    use code_browser::global::BrowserProcessEvent;
+
+    let Ok(mut registrations) = BROWSER_REGISTRATIONS.lock() else {
+        return;
+    };
+    match event {
+        BrowserProcessEvent::Launched(pid) => {
+            if let Some(registration) = register(ChildKind::Browser, Some(pid), "chrome") {
+                registrations.insert(pid, registration);
+            }
+        }
+        BrowserProcessEvent::Exited(pid) => {
+            registrations.remove(&pid);
+        }
+    }
+}
+
+/// Adopts registry files in `dir` whose owner is no longer running and
+/// terminates the helpers they list. Returns how many helpers were signalled.
+fn reap_orphans(dir: &Path, self_pid: u32) -> usize {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+    let mut reaped = 0;
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let Some(owner) = owner_pid(&name.to_string_lossy()) else {
+            continue;
+        };
+        if owner == self_pid || pid_alive(owner) {
+            continue;
+        }
+        // Claim the file first so two sessions starting at once don't both
+        // reap it. A claim left behind by a reaper that died is picked up
+        // again because its owner is then the dead reaper.
+        let claimed = dir.join(format!("adopted-{self_pid}-{owner}.json"));
+        if fs::rename(entry.path(), &claimed).is_err() {
+            continue;
+        }
+        let file = fs::read(&claimed)
+            .ok()
+            .and_then(|contents| serde_json::from_slice::<RegistryFile>(&contents).ok())
+            .unwrap_or_default();
+        for record in &file.children {
+            if terminate_orphan(record) {
+                reaped += 1;
+            }
+        }
+        let _ = fs::remove_file(&claimed);
+    }
+    reaped
+}
+
+/// `pid-<owner>.json` or `adopted-<owner>-<original owner>.json`.
+fn owner_pid(file_name: &str) -> Option<u32> {
+    let stem = file_name.strip_suffix(".json")?;
+    if let Some(pid) = stem.strip_prefix("pid-") {
+        return pid.parse().ok();
+    }
+    let rest = stem.strip_prefix("adopted-")?;
+    rest.split('-').next()?.parse().ok()
+}
+
+fn terminate_orphan(record: &ChildRecord) -> bool {
+    let still_ours = || process_identity(record.pid).as_deref() == Some(record.identity.as_str());
+    if !still_ours() {
+        return false;
+    }
+    info!(
+        kind = ?record.kind,
+        pid = record.pid,
+        label = %record.label,
+        "terminating orphaned helper process"
+    );
+    send_signal(record.pid, Signal::Terminate);
+    let deadline = std::time::Instant::now() + ORPHAN_TERM_GRACE;
+    while std::time::Instant::now() < deadline {
+        if !still_ours() {
+            return true;
+        }
+        std::thread::sleep(ORPHAN_POLL_INTERVAL);
+    }
+    if still_ours() {
+        send_signal(record.pid, Signal::Kill);
+    }
+    true
+}
+
+enum Signal {
+    Terminate,
+    Kill,
+}
+
+#[cfg(unix)]
+fn send_signal(pid: u32, signal: Signal) {
+    let signal = match signal {
+        Signal::Terminate => libc::SIGTERM,
+        Signal::Kill => libc::SIGKILL,
+    };
+    // SAFETY: sending a signal to a pid has no memory-safety preconditions.
+    unsafe {
+        libc::kill(pid as libc::pid_t, signal);
+    }
+}
+
+#[cfg(not(unix))]
+fn send_signal(_pid: u32, _signal: Signal) {}
+
+#[cfg(unix)]
+fn pid_alive(pid: u32) -> bool {
+    // SAFETY: signal 0 only performs the existence/permission check.
+    if unsafe { libc::kill(pid as libc::pid_t, 0) } == 0 {
+        return true;
+    }
+    std::io::Error::last_os_error().raw_os_error() != Some(libc::ESRCH)
+}
+
+#[cfg(not(unix))]
+fn pid_alive(_pid: u32) -> bool {
+    // Without a way to signal helpers, never adopt another owner's file.
+    true
+}
+
+/// Start time of a running (non-zombie) process, used to tell the recorded
+/// helper apart from an unrelated process that later reused its PID.
+#[cfg(target_os = "linux")]
+fn process_identity(pid: u32) -> Option<String> {
+    let stat = fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    // The command name may contain spaces or parentheses; fields resume
+    // after the last `)`, starting with the state (field 3).
+    let fields: Vec<&str> = stat.rsplit_once(')')?.1.split_whitespace().collect();
+    if fields.first() == Some(&"Z") {
+        return None;
+    }
+    // `starttime` is field 22 of /proc/<pid>/stat.
+    fields.get(19).map(|start| format!("start:{start}"))
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+fn process_identity(pid: u32) -> Option<String> {
+    let output = std::process::Command::new("ps")
+        .args(["-o", "lstart=", "-p", &pid.to_string()])
+        .output()
+        .ok()?;
+    let started = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !started.is_empty()).then(|| format!("lstart:{started}"))
+}
+
+#[cfg(not(unix))]
+fn process_identity(_pid: u32) -> Option<String> {
+    None
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use std::os::unix::process::ExitStatusExt;
+    use std::process::Command;
+    use tempfile::tempdir;
+
+    fn dead_pid() -> u32 {
+        let mut child = Command::new("true").spawn().expect("spawn true");
+        let pid = child.id();
+        child.wait().expect("wait true");
+        pid
+    }
+
+    fn write_registry(dir: &Path, owner: u32, children: Vec<ChildRecord>) -> PathBuf {
+        fs::create_dir_all(dir).expect("create registry dir");
+        let path = dir.join(format!("pid-{owner}.json"));
+        let file = RegistryFile { owner_pid: owner, children };
+        fs::write(&path, serde_json::to_vec(&file).expect("serialize")).expect("write registry");
+        path
+    }
+
+    fn record_for(child: &std::process::Child) -> ChildRecord {
+        ChildRecord {
+            kind: ChildKind::McpServer,
+            pid: child.id(),
+            identity: process_identity(child.id()).expect("identity of live child"),
+            label: "sleep".to_string(),
+        }
+    }
+
+    #[test]
+    fn orphans_of_dead_owners_are_terminated() {
+        let tmp = tempdir().expect("tempdir");
+        let mut orphan = Command::new("sleep").arg("30").spawn().expect("spawn sleep");
+        let registry = write_registry(tmp.path(), dead_pid(), vec![record_for(&orphan)]);
+
+        assert_eq!(reap_orphans(tmp.path(), std::process::id()), 1);
+        let status = orphan.wait().expect("wait orphan");
+        assert_eq!(status.signal(), Some(libc::SIGTERM));
+        assert!(!registry.exists());
+        assert_eq!(fs::read_dir(tmp.path()).expect("read dir").count(), 0);
+    }
+
+    #[test]
+    fn live_owners_and_reused_pids_are_left_alone() {
+        let tmp = tempdir().expect("tempdir");
+        let mut owner = Command::new("sleep").arg("30").spawn().expect("spawn owner");
+        let mut helper = Command::new("sleep").arg("30").spawn().expect("spawn helper");
+
+        let live = write_registry(tmp.path(), owner.id(), vec![record_for(&helper)]);
+        let mut reused = record_for(&helper);
+        reused.identity = "start:0".to_string();
+        write_registry(tmp.path(), dead_pid(), vec![reused]);
+
+        assert_eq!(reap_orphans(tmp.path(), std::process::id()), 0);
+        assert!(live.exists());
+        assert_eq!(helper.try_wait().expect("poll helper"), None);
+
+        let _ = helper.kill();
+        let _ = owner.kill();
+        let _ = helper.wait();
+        let _ = owner.wait();
+    }
+
+    #[test]
+    fn owner_pid_parses_claimed_files() {
+        assert_eq!(owner_pid("pid-42.json"), Some(42));
+        assert_eq!(owner_pid("adopted-7-42.json"), Some(7));
+        assert_eq!(owner_pid("pid-42.json.tmp"), None);
+        assert_eq!(owner_pid("notes.txt"), None);
+    }
+}
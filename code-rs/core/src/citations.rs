@@ -0,0 +1,229 @@
+//! Source tracking for answers built from web search and fetch tools.
+//!
+//! A [`SourceTracker`] collects the URLs a turn's tools returned and, once the
+//! answer is final, keeps the ones the answer actually links to. Frontends
+//! render the result as a numbered source list; the TUI, `code exec --json`
+//! and `code exec export` all share this matching.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::openai_tools::WEB_SEARCH_TOOL_NAME;
+use crate::protocol::EventMsg;
+
+/// A page that backed part of an answer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Source {
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SourceTracker {
+    sources: Vec<Source>,
+    /// Set once the native `web_search` tool ran. Its results never reach
+    /// us, so every link in the answer counts as a source.
+    native_search: bool,
+}
+
+impl SourceTracker {
+    pub fn clear(&mut self) {
+        self.sources.clear();
+        self.native_search = false;
+    }
+
+    /// Records the sources carried by a live event. A new task starts a new
+    /// set.
+    pub fn observe_event(&mut self, msg: &EventMsg) {
+        match msg {
+            EventMsg::TaskStarted => self.clear(),
+            EventMsg::WebSearchComplete(_) => self.record_native_search(),
+            EventMsg::CustomToolCallEnd(event) => {
+                if let Ok(output) = &event.result {
+                    self.record_tool_call(&event.tool_name, event.parameters.as_ref(), output);
+                }
+            }
+            EventMsg::McpToolCallEnd(event) if event.is_success() => {
+                self.record_tool_call(&event.invocation.tool, event.invocation.arguments.as_ref(), "");
+            }
+            _ => {}
+        }
+    }
+
+    pub fn record_native_search(&mut self) {
+        self.native_search = true;
+    }
+
+    /// Records a finished tool call. `web_search` results are read from its
+    /// JSON output; any other tool called with a `url` argument (MCP fetch
+    /// servers, for instance) contributes that URL.
+    pub fn record_tool_call(
+        &mut self,
+        tool_name: &str,
+        arguments: Option<&serde_json::Value>,
+        output: &str,
+    ) {
+        if tool_name == WEB_SEARCH_TOOL_NAME
+            && let Ok(value) = serde_json::from_str::<serde_json::Value>(output)
+            && let Some(results) = value.get("results").and_then(serde_json::Value::as_array)
+        {
+            for result in results {
+                if let Some(url) = result.get("url").and_then(serde_json::Value::as_str) {
+                    let title = result
+                        .get("title")
+                        .and_then(serde_json::Value::as_str)
+                        .filter(|title| !title.trim().is_empty())
+                        .map(str::to_owned);
+                    self.push(url, title);
+                }
+            }
+            return;
+        }
+        if let Some(url) = arguments
+            .and_then(|args| args.get("url"))
+            .and_then(serde_json::Value::as_str)
+        {
+            self.push(url, None);
+        }
+    }
+
+    fn push(&mut self, url: &str, title: Option<String>) {
+        if !is_web_url(url) {
+            return;
+        }
+        let key = normalize_url(url);
+        match self.sources.iter_mut().find(|source| normalize_url(&source.url) == key) {
+            Some(existing) => {
+                if existing.title.is_none() {
+                    existing.title = title;
+                }
+            }
+            None => self.sources.push(Source {
+                url: url.to_owned(),
+                title,
+            }),
+        }
+    }
+
+    /// The recorded sources `answer` links to, in order of first mention.
+    /// The answer's position in the list is its citation number.
+    pub fn cite(&self, answer: &str) -> Vec<Source> {
+        if self.sources.is_empty() && !self.native_search {
+            return Vec::new();
+        }
+        let mut cited: Vec<Source> = Vec::new();
+        for url in answer_links(answer) {
+            let key = normalize_url(url);
+            if cited.iter().any(|source| normalize_url(&source.url) == key) {
+                continue;
+            }
+            match self.sources.iter().find(|source| normalize_url(&source.url) == key) {
+                Some(source) => cited.push(source.clone()),
+                None if self.native_search => cited.push(Source {
+                    url: url.to_owned(),
+                    title: None,
+                }),
+                None => {}
+            }
+        }
+        cited
+    }
+}
+
+fn is_web_url(url: &str) -> bool {
+    url.starts_with("https://") || url.starts_with("http://")
+}
+
+/// Ignores the fragment and a trailing slash, which models add or drop
+/// freely when quoting a URL.
+fn normalize_url(url: &str) -> &str {
+    let url = url.split('#').next().unwrap_or(url);
+    url.strip_suffix('/').unwrap_or(url)
+}
+
+/// Every http(s) URL in `text`, bare or inside Markdown link syntax.
+fn answer_links(text: &str) -> Vec<&str> {
+    let mut links = Vec::new();
+    let mut rest = text;
+    while let Some(start) = ["https://", "http://"]
+        .iter()
+        .filter_map(|scheme| rest.find(scheme))
+        .min()
+    {
+        let candidate = &rest[start..];
+        let end = candidate
+            .find(|c: char| c.is_whitespace() || matches!(c, ')' | ']' | '>' | '<' | '"' | '\'' | '`'))
+            .unwrap_or(candidate.len());
+        let url = candidate[..end].trim_end_matches(['.', ',', ';', ':', '!', '?']);
+        if url.len() > "https://".len() {
+            links.push(url);
+        }
+        rest = &candidate[end.max(1)..];
+    }
+    links
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn source(url: &str, title: Option<&str>) -> Source {
+        Source {
+            url: url.to_owned(),
+            title: title.map(str::to_owned),
+        }
+    }
+
+    #[test]
+    fn cites_only_linked_search_results_in_answer_order() {
+        let mut tracker = SourceTracker::default();
+        let output = json!({
+            "provider": "brave",
+            "query": "tokio select",
+            "results": [
+                {"index": 1, "title": "tokio::select", "url": "https://docs.rs/tokio/latest/tokio/macro.select.html"},
+                {"index": 2, "title": "Unused", "url": "https://example.com/unused"},
+                {"index": 3, "title": "Tutorial", "url": "https://tokio.rs/tokio/tutorial/select"},
+            ],
+        });
+        tracker.record_tool_call(WEB_SEARCH_TOOL_NAME, None, &output.to_string());
+        tracker.record_tool_call("fetch", Some(&json!({"url": "https://blog.example/post"})), "");
+
+        let answer = "Use `select!` [1](https://tokio.rs/tokio/tutorial/select/), see \
+                      https://docs.rs/tokio/latest/tokio/macro.select.html#cancellation. \
+                      Also [the tutorial](https://tokio.rs/tokio/tutorial/select) and \
+                      <https://elsewhere.example/page>.";
+        assert_eq!(
+            tracker.cite(answer),
+            vec![
+                source("https://tokio.rs/tokio/tutorial/select", Some("Tutorial")),
+                source(
+                    "https://docs.rs/tokio/latest/tokio/macro.select.html",
+                    Some("tokio::select")
+                ),
+            ]
+        );
+        assert_eq!(
+            tracker.cite("Per https://blog.example/post, yes."),
+            vec![source("https://blog.example/post", None)]
+        );
+    }
+
+    #[test]
+    fn native_search_accepts_any_link_until_the_next_task() {
+        let mut tracker = SourceTracker::default();
+        assert_eq!(tracker.cite("See https://example.com."), Vec::new());
+
+        tracker.record_native_search();
+        assert_eq!(
+            tracker.cite("See https://example.com."),
+            vec![source("https://example.com", None)]
+        );
+
+        tracker.observe_event(&EventMsg::TaskStarted);
+        assert_eq!(tracker.cite("See https://example.com."), Vec::new());
+    }
+}
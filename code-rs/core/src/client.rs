@@ -66,8 +66,12 @@ use crate::error::ModelCapError;
 use crate::error::RetryLimitReachedError;
 use crate::error::UnexpectedResponseError;
 use crate::error::UsageLimitReachedError;
+use crate::provider_errors::ProviderErrorKind;
+use crate::provider_errors::classify_provider_error;
+use crate::provider_errors::provider_error;
+use crate::provider_errors::unexpected_status_error;
 use crate::flags::CODEX_RS_SSE_FIXTURE;
-use crate::model_family::{find_family_for_model, ModelFamily};
+use crate::model_family::{derive_default_model_family, find_family_for_model, ModelFamily};
 use crate::model_provider_info::ModelProviderInfo;
 use crate::model_provider_info::WireApi;
 use crate::openai_tools::create_tools_json_for_responses_api;
@@ -353,6 +357,49 @@ impl ModelClient {
         }
     }
 
+    /// A client for a single turn on another model and/or reasoning effort,
+    /// sharing this client's auth, provider and debug logger.
+    pub(crate) fn with_turn_overrides(
+        &self,
+        model: Option<&str>,
+        effort: Option<ReasoningEffortConfig>,
+    ) -> Self {
+        let mut config = (*self.config).clone();
+        let mut otel_event_manager = self.otel_event_manager.clone();
+        if let Some(model) = model {
+            let family = find_family_for_model(model)
+                .unwrap_or_else(|| derive_default_model_family(model));
+            if let Some(context_window) = family.context_window {
+                config.model_context_window = Some(context_window);
+            }
+            if let Some(max_output_tokens) = family.max_output_tokens {
+                config.model_max_output_tokens = Some(max_output_tokens);
+            }
+            otel_event_manager = otel_event_manager.map(|mgr| mgr.with_model(model, family.slug.as_str()));
+            config.model = model.to_owned();
+            config.model_family = family;
+        }
+        let effort = clamp_reasoning_effort_for_model(config.model.as_str(), effort.unwrap_or(self.effort));
+        let verbosity = clamp_text_verbosity_for_model(config.model.as_str(), self.verbosity);
+
+        Self {
+            config: Arc::new(config),
+            auth_manager: self.auth_manager.clone(),
+            otel_event_manager,
+            client: self.client.clone(),
+            provider: self.provider.clone(),
+            session_id: self.session_id,
+            effort,
+            summary: self.summary,
+            reasoning_summary_disabled: AtomicBool::new(false),
+            websockets_disabled: AtomicBool::new(
+                self.websockets_disabled.load(Ordering::Relaxed),
+            ),
+            verbosity,
+            debug_logger: Arc::clone(&self.debug_logger),
+        }
+    }
+
     /// Get the reasoning effort configuration
     pub fn get_reasoning_effort(&self) -> ReasoningEffortConfig {
         self.effort
@@ -478,7 +525,13 @@ impl ModelClient {
         });
         tools_config.web_search_allowed_domains.clone_from(&self.config.tools_web_search_allowed_domains);
         tools_config.web_search_external = self.config.tools_web_search_external;
+        tools_config.web_search_provider.clone_from(&self.config.web_search_provider);
         tools_config.search_tool = self.config.tools_search_tool;
+        tools_config.mcp_budget.clone_from(&self.config.tools_mcp_budget);
+        tools_config.docs_bundles.clone_from(&self.config.docs_bundles);
+        tools_config.project_memory = self.config.tools_project_memory;
+        tools_config.capabilities = self.config.tools_capabilities;
+        tools_config.scratch = self.config.tools_scratch;
         tools_config.repl = self.config.tools_repl;
         tools_config.repl_available_runtimes = self.config.repl_available_runtimes.clone();
 
@@ -698,7 +751,10 @@ impl ModelClient {
             .model_override
             .as_deref()
             .unwrap_or(self.config.model.as_str());
-        let effective_effort = clamp_reasoning_effort_for_model(request_model, self.effort);
+        let effective_effort = clamp_reasoning_effort_for_model(
+            request_model,
+            prompt.reasoning_effort_override.unwrap_or(self.effort),
+        );
         let request_family = prompt
             .model_family_override
             .clone()
@@ -1142,7 +1198,10 @@ impl ModelClient {
             .model_override
             .as_deref()
             .unwrap_or(self.config.model.as_str());
-        let effective_effort = clamp_reasoning_effort_for_model(request_model, self.effort);
+        let effective_effort = clamp_reasoning_effort_for_model(
+            request_model,
+            prompt.reasoning_effort_override.unwrap_or(self.effort),
+        );
         let request_family = prompt
             .model_family_override
             .clone()
@@ -1730,11 +1789,7 @@ impl ModelClient {
                             );
                             let _ = logger.end_request_log(&request_id);
                         }
-                        return Err(CodexErr::UnexpectedStatus(UnexpectedResponseError {
-                            status,
-                            body: body_text,
-                            request_id: None,
-                        }));
+                        return Err(unexpected_status_error(status, body_text, None));
                     }
 
                     if let Some(ErrorResponse { ref error }) = body
@@ -2059,11 +2114,7 @@ impl ModelClient {
             }
 
             if !status.is_success() {
-                return Err(CodexErr::UnexpectedStatus(UnexpectedResponseError {
-                    status,
-                    body,
-                    request_id: None,
-                }));
+                return Err(unexpected_status_error(status, body, None));
             }
 
             let CompactHistoryResponse { output } = serde_json::from_str(&body)?;
@@ -2139,6 +2190,15 @@ fn map_wrapped_websocket_error_event(event: WrappedWebsocketErrorEvent) -> Optio
             return Some(CodexErr::ServerOverloaded);
         }
 
+        if let Some(kind) = classify_provider_error(
+            Some(status),
+            &[error.code.as_deref(), error.r#type.as_deref()],
+        ) && kind != ProviderErrorKind::RateLimited
+        {
+            let message = error.message.unwrap_or_else(|| status.to_string());
+            return Some(provider_error(kind, message, None));
+        }
+
         serde_json::json!({
             "error": {
                 "type": error.r#type,
@@ -2821,12 +2881,19 @@ async fn process_sse<S>(
                                     response_error = Some(CodexErr::ServerOverloaded);
                                 } else {
                                     let retry_after = try_parse_retry_after(&error, Utc::now());
+                                    let kind = classify_provider_error(
+                                        None,
+                                        &[error.code.as_deref(), error.r#type.as_deref()],
+                                    );
                                     let message = error.message.unwrap_or_default();
-                                    response_error = Some(CodexErr::Stream(
-                                        message,
-                                        retry_after,
-                                        Some(request_id.clone()),
-                                    ));
+                                    response_error = Some(match kind {
+                                        Some(kind) => provider_error(kind, message, retry_after),
+                                        None => CodexErr::Stream(
+                                            message,
+                                            retry_after,
+                                            Some(request_id.clone()),
+                                        ),
+                                    });
                                 }
                             }
                             Err(e) => {
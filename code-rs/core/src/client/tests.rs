@@ -587,7 +587,7 @@ async fn error_when_error_event() {
     assert_eq!(events.len(), 1);
 
     match &events[0] {
-        Err(CodexErr::Stream(msg, Some(retry), _)) => {
+        Err(CodexErr::RateLimited(msg, Some(retry))) => {
             assert_eq!(
                 msg,
                 "Rate limit reached for gpt-5.1 in organization org-AAA on tokens per min (TPM): Limit 30000, Used 22999, Requested 12528. Please try again in 11.054s. Visit https://platform.openai.com/account/rate-limits to learn more."
@@ -67,6 +67,10 @@ pub struct Prompt {
     /// Whether to prepend the default developer instructions block.
     pub include_additional_instructions: bool,
 
+    /// Leave out the default developer prompt while still sending the
+    /// prepended messages, environment context and user instructions.
+    pub omit_default_developer_prompt: bool,
+
     /// Additional developer messages to insert immediately after the default
     /// fork instructions but before any environment or user context.
     pub prepend_developer_messages: Vec<String>,
@@ -79,6 +83,8 @@ pub struct Prompt {
 
     /// Optional per-request model family override matching `model_override`.
     pub model_family_override: Option<ModelFamily>,
+    /// Optional per-request reasoning effort, replacing the session's.
+    pub reasoning_effort_override: Option<ReasoningEffortConfig>,
     /// Optional the output schema for the model's response.
     pub output_schema: Option<Value>,
     /// Optional tag used to route debug logs into helper-specific directories.
@@ -101,10 +107,12 @@ impl Default for Prompt {
             status_items: Vec::new(),
             base_instructions_override: None,
             include_additional_instructions: true,
+            omit_default_developer_prompt: false,
             prepend_developer_messages: Vec::new(),
             text_format: None,
             model_override: None,
             model_family_override: None,
+            reasoning_effort_override: None,
             output_schema: None,
             log_tag: None,
             session_id_override: None,
@@ -127,7 +135,7 @@ impl Prompt {
         self.log_tag = Some(tag.into());
     }
 
-    fn additional_instructions(&self) -> Cow<'_, str> {
+    pub(crate) fn additional_instructions(&self) -> Cow<'_, str> {
         if let Some(custom) = &self.model_descriptions {
             Cow::Owned(PROMPT_CODER_TEMPLATE.replace("{MODEL_DESCRIPTIONS}", custom))
         } else {
@@ -135,7 +143,7 @@ impl Prompt {
         }
     }
 
-    fn get_formatted_user_instructions(&self) -> Option<ResponseItem> {
+    pub(crate) fn get_formatted_user_instructions(&self) -> Option<ResponseItem> {
         let instructions = self.user_instructions.as_ref()?;
         let directory = self
             .environment_context
@@ -152,7 +160,7 @@ impl Prompt {
         )
     }
 
-    fn get_formatted_environment_context(&self) -> Option<String> {
+    pub(crate) fn get_formatted_environment_context(&self) -> Option<String> {
         self.environment_context.as_ref().map(|ec| {
             let ec_str = serde_json::to_string_pretty(ec).unwrap_or_else(|_| format!("{ec:?}"));
             format!("{ENVIRONMENT_CONTEXT_START}{ec_str}{ENVIRONMENT_CONTEXT_END}")
@@ -163,11 +171,13 @@ impl Prompt {
         let mut input_with_instructions =
             Vec::with_capacity(self.input.len() + self.status_items.len() + 3);
         if self.include_additional_instructions {
-            let developer_text = self.additional_instructions().into_owned();
-            input_with_instructions.push(ResponseItem::Message {
-                id: None,
-                role: "developer".to_owned(),
-                content: vec![ContentItem::InputText { text: developer_text }], end_turn: None, phase: None});
+            if !self.omit_default_developer_prompt {
+                let developer_text = self.additional_instructions().into_owned();
+                input_with_instructions.push(ResponseItem::Message {
+                    id: None,
+                    role: "developer".to_owned(),
+                    content: vec![ContentItem::InputText { text: developer_text }], end_turn: None, phase: None});
+            }
             for message in &self.prepend_developer_messages {
                 let trimmed = message.trim();
                 if trimmed.is_empty() {
@@ -747,6 +757,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn omitting_default_developer_prompt_keeps_prepended_messages() {
+        let prompt = Prompt {
+            omit_default_developer_prompt: true,
+            prepend_developer_messages: vec!["Shell style".to_string()],
+            ..Prompt::default()
+        };
+
+        let formatted = prompt.get_formatted_input();
+        assert_eq!(formatted.len(), 1);
+        match &formatted[0] {
+            ResponseItem::Message { role, content, .. } => {
+                assert_eq!(role, "developer");
+                assert!(matches!(
+                    content.first(),
+                    Some(ContentItem::InputText { text }) if text == "Shell style"
+                ));
+            }
+            other => panic!("unexpected item: {other:?}"),
+        }
+    }
+
     #[test]
     fn serializes_text_verbosity_when_set() {
         let input: Vec<ResponseItem> = vec![];
@@ -27,4 +27,10 @@ impl CodexConversation {
     pub async fn next_event(&self) -> CodexResult<Event> {
         self.codex.next_event().await
     }
+
+    /// Like [`Self::next_event`], but returns every event already queued,
+    /// with consecutive stream deltas merged.
+    pub async fn next_event_batch(&self) -> CodexResult<Vec<Event>> {
+        self.codex.next_event_batch().await
+    }
 }
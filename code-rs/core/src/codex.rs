@@ -91,10 +91,12 @@ mod exec;
 pub(crate) mod exec_tool;
 mod hook_runtime;
 mod fs_utils;
+mod heartbeat;
 pub(crate) mod mcp_access;
 mod session;
 pub(crate) mod streaming;
 mod truncation;
+mod turn_baselines;
 
 pub use session::ApprovedCommandPattern;
 pub(crate) use session::McpAccessState;
@@ -229,6 +231,7 @@ fn to_proto_sandbox_policy(policy: SandboxPolicy) -> ProtoSandboxPolicy {
         SandboxPolicy::WorkspaceWrite {
             writable_roots,
             network_access,
+            network_allowlist: _,
             exclude_tmpdir_env_var,
             exclude_slash_tmp,
             allow_git_writes,
@@ -280,6 +283,8 @@ pub(crate) struct TurnContext {
     pub(crate) collaboration_mode: crate::protocol::CollaborationModeKind,
     pub(crate) is_review_mode: bool,
     pub(crate) text_format_override: Option<TextFormat>,
+    /// Prompt segment ids left out of this turn (`Op::SetDisabledPromptSegments`).
+    pub(crate) disabled_prompt_segments: Vec<String>,
     pub(crate) final_output_json_schema: Option<Value>,
 }
 
@@ -391,13 +396,12 @@ fn convert_call_tool_result_to_function_call_output_payload(
 }
 
 fn get_git_branch(cwd: &std::path::Path) -> Option<String> {
-    let head_path = cwd.join(".git/HEAD");
-    if let Ok(contents) = std::fs::read_to_string(&head_path)
-        && let Some(rest) = contents.trim().strip_prefix("ref: ")
-            && let Some(branch) = rest.trim().rsplit('/').next() {
-                return Some(branch.to_owned());
-            }
-    None
+    // Read HEAD on every call so branch switches made outside the session
+    // show up in the next status snapshot.
+    let head_path = crate::git_info::resolve_git_dir(cwd)?.join("HEAD");
+    let contents = std::fs::read_to_string(head_path).ok()?;
+    let reference = contents.trim().strip_prefix("ref: ")?.trim();
+    Some(reference.strip_prefix("refs/heads/").unwrap_or(reference).to_owned())
 }
 
 fn maybe_update_from_model_info<T: Copy + PartialEq>(
@@ -512,8 +516,15 @@ async fn build_turn_status_items_legacy(sess: &Session) -> Vec<ResponseItem> {
     let mut jar = EphemeralJar::new();
 
     // Collect environment context
-    let cwd = sess.cwd.to_string_lossy().into_owned();
-    let branch = get_git_branch(&sess.cwd).unwrap_or_else(|| "unknown".to_owned());
+    let redact_environment = sess.client.config().privacy.redact_environment;
+    #[cfg(feature = "browser-automation")]
+    let screenshots_allowed = sess.client.config().privacy.screenshots;
+    let (cwd, branch_line) = if redact_environment {
+        (redact_home_dir(&sess.cwd).to_string_lossy().into_owned(), String::new())
+    } else {
+        let branch = get_git_branch(&sess.cwd).unwrap_or_else(|| "unknown".to_owned());
+        (sess.cwd.to_string_lossy().into_owned(), format!("\n branch: {branch}"))
+    };
     let reasoning_effort = sess.client.get_reasoning_effort();
 
     // Build current system status (UI-only; not persisted)
@@ -522,8 +533,7 @@ async fn build_turn_status_items_legacy(sess: &Session) -> Vec<ResponseItem> {
         "== System Status ==
  [automatic message added by system]
 
- cwd: {cwd}
- branch: {branch}
+ cwd: {cwd}{branch_line}
  reasoning: {reasoning_effort:?}"
     );
     #[cfg(not(feature = "browser-automation"))]
@@ -531,8 +541,7 @@ async fn build_turn_status_items_legacy(sess: &Session) -> Vec<ResponseItem> {
         r#"== System Status ==
  [automatic message added by system]
 
- cwd: {cwd}
- branch: {branch}
+ cwd: {cwd}{branch_line}
  reasoning: {reasoning_effort:?}"#
     );
 
@@ -584,8 +593,15 @@ async fn build_turn_status_items_legacy(sess: &Session) -> Vec<ResponseItem> {
                 };
 
                 // Try to capture screenshot and compare with last one
-                let screenshot_status = match capture_browser_screenshot(sess).await {
-                    Ok((screenshot_path, _url)) => {
+                let capture = if screenshots_allowed && browser_screenshot_due(sess) {
+                    Some(capture_browser_screenshot(sess).await)
+                } else {
+                    None
+                };
+                let screenshot_status = match capture {
+                    None if !screenshots_allowed => BROWSER_SCREENSHOT_DISABLED_NOTE,
+                    None => BROWSER_SCREENSHOT_PAUSED_NOTE,
+                    Some(Ok((screenshot_path, _url))) => {
                         // Always update the UI with the latest screenshot, even if unchanged for LLM payload
                         // This ensures the user sees that a fresh capture occurred each turn.
                         add_pending_screenshot(sess, screenshot_path.clone(), url.clone());
@@ -645,7 +661,7 @@ async fn build_turn_status_items_legacy(sess: &Session) -> Vec<ResponseItem> {
                             " [Screenshot unchanged]"
                         }
                     }
-                    Err(err_msg) => {
+                    Some(Err(err_msg)) => {
                         // Include error message so LLM knows screenshot failed
                         format!(" [Screenshot unavailable: {err_msg}]").leak()
                     }
@@ -704,17 +720,34 @@ async fn build_turn_status_items_legacy(sess: &Session) -> Vec<ResponseItem> {
 
 async fn build_turn_status_items_v2(sess: &Session) -> Vec<ResponseItem> {
     let mut items = Vec::new();
-
-    let env_context = EnvironmentContext::new(
-        Some(sess.cwd.clone()),
-        Some(sess.approval_policy),
-        Some(sess.sandbox_policy.clone()),
-        Some(sess.user_shell.clone()),
-    );
+    let redact_environment = sess.client.config().privacy.redact_environment;
+    #[cfg(feature = "browser-automation")]
+    let screenshots_allowed = sess.client.config().privacy.screenshots;
+
+    let env_context = if redact_environment {
+        EnvironmentContext::new(
+            Some(redact_home_dir(&sess.cwd)),
+            Some(sess.get_approval_policy()),
+            Some(sess.get_sandbox_policy()),
+            None,
+        )
+    } else {
+        EnvironmentContext::new(
+            Some(sess.cwd.clone()),
+            Some(sess.get_approval_policy()),
+            Some(sess.get_sandbox_policy()),
+            Some(sess.user_shell.clone()),
+        )
+    };
+    let branch = if redact_environment {
+        None
+    } else {
+        get_git_branch(&sess.cwd)
+    };
 
     if let Some(mut env_items) = sess.maybe_emit_env_ctx_messages(
         &env_context,
-        get_git_branch(&sess.cwd),
+        branch,
         Some(format!("{:?}", sess.client.get_reasoning_effort())),
     ) {
         items.append(&mut env_items);
@@ -771,8 +804,15 @@ async fn build_turn_status_items_v2(sess: &Session) -> Vec<ResponseItem> {
 
             let mut screenshot_path = None;
 
-            match capture_browser_screenshot(sess).await {
-                Ok((path, _)) => {
+            let capture = if screenshots_allowed && browser_screenshot_due(sess) {
+                Some(capture_browser_screenshot(sess).await)
+            } else {
+                None
+            };
+            match capture {
+                None if !screenshots_allowed => trace!("env_ctx_v2: screenshots disabled by [privacy]"),
+                None => trace!("env_ctx_v2: screenshot skipped; no recent browser_* activity"),
+                Some(Ok((path, _))) => {
                     add_pending_screenshot(sess, path.clone(), url.clone());
                     let current_hash = crate::image_comparison::compute_image_hash(&path).ok();
                     let mut last_info =
@@ -787,7 +827,7 @@ async fn build_turn_status_items_v2(sess: &Session) -> Vec<ResponseItem> {
                         screenshot_path = Some(path);
                     }
                 }
-                Err(err_msg) => {
+                Some(Err(err_msg)) => {
                     trace!("env_ctx_v2: screenshot capture failed: {}", err_msg);
                 }
             }
@@ -836,6 +876,52 @@ async fn build_turn_status_items_v2(sess: &Session) -> Vec<ResponseItem> {
     items
 }
 
+/// Requests without a `browser_*` call after which per-attempt screenshots pause.
+#[cfg(feature = "browser-automation")]
+const BROWSER_SCREENSHOT_RECENT_REQUESTS: u64 = 3;
+
+#[cfg(feature = "browser-automation")]
+const BROWSER_SCREENSHOT_PAUSED_NOTE: &str =
+    " [Screenshot paused: no recent browser_* activity; run a browser_* tool to resume]";
+
+#[cfg(feature = "browser-automation")]
+const BROWSER_SCREENSHOT_DISABLED_NOTE: &str = " [Screenshots disabled by [privacy] settings]";
+
+/// `path` with the home directory replaced by `~`, for status items under
+/// `[privacy] redact_environment`.
+fn redact_home_dir(path: &Path) -> PathBuf {
+    match dirs::home_dir().and_then(|home| path.strip_prefix(home).ok().map(Path::to_path_buf)) {
+        Some(rest) => Path::new("~").join(rest),
+        None => path.to_path_buf(),
+    }
+}
+
+/// Screenshots cost tokens on every attempt, so only capture them while the
+/// model is actively driving the browser. The first capture is always taken so
+/// the model sees the page at least once after it opens.
+#[cfg(feature = "browser-automation")]
+fn browser_screenshot_due(sess: &Session) -> bool {
+    let has_prior_screenshot = lock_or_panic!(sess.last_screenshot_info).is_some();
+    screenshot_due_for_requests(
+        sess.browser_tool_last_request.load(Ordering::Relaxed),
+        sess.current_request_ordinal(),
+        has_prior_screenshot,
+    )
+}
+
+#[cfg(feature = "browser-automation")]
+fn screenshot_due_for_requests(
+    last_tool_request: u64,
+    current_request: u64,
+    has_prior_screenshot: bool,
+) -> bool {
+    if !has_prior_screenshot {
+        return true;
+    }
+    last_tool_request != 0
+        && current_request.saturating_sub(last_tool_request) <= BROWSER_SCREENSHOT_RECENT_REQUESTS
+}
+
 #[cfg(feature = "browser-automation")]
 fn should_include_browser_screenshot(
     last_info: &mut Option<(PathBuf, Vec<u8>, Vec<u8>)>,
@@ -908,6 +994,7 @@ use crate::exec::StdoutStream;
 use crate::exec::StreamOutput;
 use crate::exec::process_exec_tool_call;
 use crate::exec_env::create_env;
+use crate::exec_env::withhold_spawned_env;
 use crate::mcp_connection_manager::McpConnectionManager;
 use crate::model_family::{derive_default_model_family, find_family_for_model};
 use code_protocol::models::ContentItem;
@@ -938,11 +1025,13 @@ use crate::protocol::AgentStatusUpdateEvent;
 use crate::protocol::ApplyPatchApprovalRequestEvent;
 use crate::protocol::AskForApproval;
 use crate::protocol::BackgroundEventEvent;
+use crate::protocol::ErrorCode;
 use crate::protocol::ErrorEvent;
 use crate::protocol::Event;
 use crate::protocol::EventMsg;
 use crate::protocol::ListCustomPromptsResponseEvent;
 use crate::protocol::ListSkillsResponseEvent;
+use crate::protocol::PromptPreviewEvent;
 use crate::protocol::{EnvironmentContextDeltaEvent, EnvironmentContextFullEvent};
 use crate::protocol::ExecApprovalRequestEvent;
 use crate::protocol::ExecCommandBeginEvent;
@@ -959,6 +1048,7 @@ use crate::protocol::TokenUsageInfo;
 use crate::protocol::ReviewDecision;
 use crate::protocol::ValidationGroup;
 use crate::protocol::ReviewRequest;
+use crate::protocol::SandboxDeniedEvent;
 use crate::protocol::SandboxPolicy;
 use crate::protocol::SessionConfiguredEvent;
 use crate::protocol::Submission;
@@ -969,6 +1059,8 @@ use std::sync::OnceLock;
 use crate::protocol::{BrowserScreenshotUpdateEvent, BrowserSnapshotEvent};
 use tokio::sync::Notify;
 use crate::protocol::TurnDiffEvent;
+use crate::protocol::UserInputInterruptingEvent;
+use crate::protocol::UserInputQueuedEvent;
 use crate::rollout::RolloutRecorder;
 use crate::safety::SafetyCheck;
 use crate::safety::assess_command_safety;
@@ -1030,16 +1122,21 @@ impl Codex {
         let (tx_sub, rx_sub) = async_channel::unbounded();
         let (tx_event, rx_event) = async_channel::unbounded();
 
+        let skills_started = std::time::Instant::now();
         let skills_outcome = config.skills_enabled.then(|| load_skills(&config));
+        crate::startup_profile::record_since("skills scan (instructions)", skills_started);
         if let Some(outcome) = &skills_outcome {
             for err in &outcome.errors {
                 warn!("invalid skill {}: {}", err.path.display(), err.message);
             }
         }
 
-        let user_instructions = get_user_instructions(
-            &config,
-            skills_outcome.as_ref().map(|outcome| outcome.skills.as_slice()),
+        let user_instructions = crate::startup_profile::measure(
+            "user instructions",
+            get_user_instructions(
+                &config,
+                skills_outcome.as_ref().map(|outcome| outcome.skills.as_slice()),
+            ),
         )
         .await;
 
@@ -1068,6 +1165,7 @@ impl Codex {
                 dynamic_tools: config.dynamic_tools.clone(),
                 shell: config.shell.clone(),
                 shell_style_profiles: config.shell_style_profiles.clone(),
+                shell_env_set: None,
                 network: config.network.clone(),
                 tools_repl: config.tools_repl,
                 repl_default_runtime: config.repl_default_runtime,
@@ -1137,6 +1235,18 @@ impl Codex {
             .map_err(|_| CodexErr::InternalAgentDied)?;
         Ok(event)
     }
+
+    /// Waits for the next event, then drains events that are already queued
+    /// and merges consecutive deltas of the same stream. Order is preserved.
+    pub async fn next_event_batch(&self) -> CodexResult<Vec<Event>> {
+        let mut events = vec![self.next_event().await?];
+        while events.len() < crate::event_batch::MAX_EVENT_BATCH
+            && let Ok(event) = self.rx_event.try_recv()
+        {
+            events.push(event);
+        }
+        Ok(crate::event_batch::coalesce_stream_deltas(events))
+    }
 }
 
 #[cfg(test)]
@@ -1159,6 +1269,18 @@ mod tests {
         assert!(should_include_browser_screenshot(&mut last, &path, Some(hash_two)));
     }
 
+    #[cfg(feature = "browser-automation")]
+    #[test]
+    fn screenshot_capture_follows_recent_browser_use() {
+        // First capture always happens so the model sees the page once.
+        assert!(screenshot_due_for_requests(0, 5, false));
+        // Browser never driven by the model after that: stay paused.
+        assert!(!screenshot_due_for_requests(0, 5, true));
+        assert!(screenshot_due_for_requests(4, 5, true));
+        assert!(screenshot_due_for_requests(2, 5, true));
+        assert!(!screenshot_due_for_requests(1, 5, true));
+    }
+
     fn make_snapshot(cwd: &str) -> EnvironmentContextSnapshot {
         EnvironmentContextSnapshot {
             version: EnvironmentContextSnapshot::VERSION,
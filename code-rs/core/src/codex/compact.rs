@@ -13,6 +13,7 @@ use crate::error::CodexErr;
 use crate::error::RetryAfter;
 use crate::error::Result as CodexResult;
 use crate::protocol::AgentMessageEvent;
+use crate::protocol::ErrorCode;
 use crate::protocol::ErrorEvent;
 use crate::protocol::EventMsg;
 use code_protocol::protocol::CompactionCheckpointWarningEvent;
@@ -231,9 +232,7 @@ pub(super) async fn apply_emergency_compaction_fallback(
 
     let event = sess.make_event(
         sub_id,
-        EventMsg::Error(ErrorEvent {
-            message: message.clone(),
-        }),
+        EventMsg::Error(ErrorEvent::new(message.clone(), ErrorCode::ContextWindowExceeded)),
     );
     sess.send_event(event).await;
 
@@ -321,12 +320,12 @@ pub(super) async fn perform_compaction(
                     .unwrap_or_else(|| RetryAfter::from_duration(RetryAfter::DEFAULT_DELAY, now));
                 let mut message = format!("{limit_err} Auto-retrying");
                 message.push('…');
-                sess.notify_stream_error(&sub_id, message).await;
+                sess.notify_stream_error(&sub_id, ErrorCode::UsageLimitReached, message).await;
                 tokio::time::sleep(retry_after.delay).await;
                 retries = 0;
                 truncated_count = 0;
             }
-            Err(e) if is_context_overflow_error(&e) => {
+            Err(e) if e.is_context_window_exceeded() => {
                 if turn_input.len() > 1 && truncated_count < MAX_COMPACT_CONTEXT_OVERFLOW_TRIMS {
                     tracing::warn!(
                         "Context window exceeded while compacting; dropping oldest item ({} remaining)",
@@ -363,6 +362,7 @@ pub(super) async fn perform_compaction(
                     sess
                         .notify_stream_error(
                             &sub_id,
+                            e.error_code(),
                             format!(
                                 "stream error: {e}; retrying {retries}/{max_retries} in {delay:?}…"
                             ),
@@ -373,9 +373,7 @@ pub(super) async fn perform_compaction(
                 }
                 let event = sess.make_event(
                     &sub_id,
-                    EventMsg::Error(ErrorEvent {
-                        message: e.to_string(),
-                    }),
+                    EventMsg::Error(e.to_error_event(e.to_string())),
                 );
                 sess.send_event(event).await;
                 return Err(e);
@@ -490,12 +488,12 @@ async fn run_compact_task_inner_inline(
                     .unwrap_or_else(|| RetryAfter::from_duration(RetryAfter::DEFAULT_DELAY, now));
                 let mut message = format!("{limit_err} Auto-retrying");
                 message.push('…');
-                sess.notify_stream_error(&sub_id, message).await;
+                sess.notify_stream_error(&sub_id, ErrorCode::UsageLimitReached, message).await;
                 tokio::time::sleep(retry_after.delay).await;
                 retries = 0;
                 truncated_count = 0;
             }
-            Err(e) if is_context_overflow_error(&e) => {
+            Err(e) if e.is_context_window_exceeded() => {
                 if turn_input.len() > 1 && truncated_count < MAX_COMPACT_CONTEXT_OVERFLOW_TRIMS {
                     tracing::warn!(
                         "Context window exceeded while compacting; dropping oldest item ({} remaining)",
@@ -532,6 +530,7 @@ async fn run_compact_task_inner_inline(
                     sess
                         .notify_stream_error(
                             &sub_id,
+                            e.error_code(),
                             format!(
                                 "stream error: {e}; retrying {retries}/{max_retries} in {delay:?}…"
                             ),
@@ -542,9 +541,7 @@ async fn run_compact_task_inner_inline(
                 }
                 let event = sess.make_event(
                     &sub_id,
-                    EventMsg::Error(ErrorEvent {
-                        message: e.to_string(),
-                    }),
+                    EventMsg::Error(e.to_error_event(e.to_string())),
                 );
                 sess.send_event(event).await;
                 return Vec::new();
@@ -618,27 +615,6 @@ fn truncate_for_compact(text: String, max_bytes: usize) -> String {
     truncate_middle(&text, max_bytes).0
 }
 
-fn looks_like_context_overflow(message: &str) -> bool {
-    let lower = message.to_ascii_lowercase();
-    lower.contains("context_length_exceeded")
-        || lower.contains("context length exceeded")
-        || lower.contains("context window")
-            && (lower.contains("exceed")
-                || lower.contains("exceeded")
-                || lower.contains("full")
-                || lower.contains("too long"))
-        || lower.contains("maximum context length")
-        || lower.contains("exceeds the context window")
-}
-
-pub(super) fn is_context_overflow_error(err: &CodexErr) -> bool {
-    match err {
-        CodexErr::UnexpectedStatus(resp) => looks_like_context_overflow(&resp.body),
-        CodexErr::Stream(msg, _, _) => looks_like_context_overflow(msg),
-        _ => false,
-    }
-}
-
 pub fn sanitize_items_for_compact(items: Vec<ResponseItem>) -> Vec<ResponseItem> {
     items
         .into_iter()
@@ -2,7 +2,6 @@ use std::sync::Arc;
 
 use super::compact::{
     apply_emergency_compaction_fallback,
-    is_context_overflow_error,
     prune_orphan_tool_outputs,
     response_input_from_core_items,
     sanitize_items_for_compact,
@@ -15,7 +14,7 @@ use crate::error::CodexErr;
 use crate::error::Result as CodexResult;
 use crate::error::RetryAfter;
 use crate::protocol::AgentMessageEvent;
-use crate::protocol::ErrorEvent;
+use crate::protocol::ErrorCode;
 use crate::protocol::EventMsg;
 use crate::protocol::InputItem;
 use code_protocol::models::ResponseItem;
@@ -37,9 +36,7 @@ pub(super) async fn run_inline_remote_auto_compact_task(
         Err(err) => {
             let event = sess.make_event(
                 &sub_id,
-                EventMsg::Error(ErrorEvent {
-                    message: format!("remote compact failed: {err}"),
-                }),
+                EventMsg::Error(err.to_error_event(format!("remote compact failed: {err}"))),
             );
             sess.send_event(event).await;
             Vec::new()
@@ -64,9 +61,7 @@ pub(super) async fn run_remote_compact_task(
         Err(err) => {
             let event = sess.make_event(
                 &sub_id,
-                EventMsg::Error(ErrorEvent {
-                    message: err.to_string(),
-                }),
+                EventMsg::Error(err.to_error_event(err.to_string())),
             );
             sess.send_event(event).await;
             Err(err)
@@ -120,7 +115,7 @@ async fn run_remote_compact_task_inner(
                 }
                 break history;
             }
-            Err(err) if is_context_overflow_error(&err) => {
+            Err(err) if err.is_context_window_exceeded() => {
                 if turn_items.len() > 1
                     && truncated_count < MAX_REMOTE_COMPACT_CONTEXT_OVERFLOW_TRIMS
                 {
@@ -181,7 +176,7 @@ async fn run_remote_compact_task_inner(
                     .unwrap_or_else(|| RetryAfter::from_duration(RetryAfter::DEFAULT_DELAY, now));
                 let mut message = format!("{limit_err} Auto-retrying");
                 message.push('…');
-                sess.notify_stream_error(sub_id, message).await;
+                sess.notify_stream_error(sub_id, ErrorCode::UsageLimitReached, message).await;
                 tokio::time::sleep(retry_after.delay).await;
                 retries = 0;
             }
@@ -192,6 +187,7 @@ async fn run_remote_compact_task_inner(
                     sess
                         .notify_stream_error(
                             sub_id,
+                            err.error_code(),
                             format!(
                                 "remote compact error: {err}; retrying {retries}/{max_retries} in {delay:?}…"
                             ),
@@ -42,6 +42,14 @@ impl Session {
     /// Create a stamped Event with a per-turn sequence number.
     fn stamp_event(&self, sub_id: &str, msg: EventMsg) -> Event {
         let mut state = crate::codex::lock_or_panic!(self.state);
+        if self.heartbeat_interval.is_some() {
+            super::heartbeat::HeartbeatTracker::observe(
+                &mut state.heartbeat,
+                sub_id,
+                &msg,
+                std::time::Instant::now(),
+            );
+        }
         if state.event_seq_by_sub_id.len() > MAX_EVENT_SEQ_SUB_IDS {
             while state.event_seq_by_sub_id.len() > MAX_EVENT_SEQ_SUB_IDS {
                 let Some(old_key) = state.event_seq_by_sub_id.keys().next().cloned() else {
@@ -265,7 +265,15 @@ fn materialize_shell_script(user_shell: &crate::shell::Shell, mut params: ExecPa
     params
 }
 
-pub(super) fn maybe_run_with_user_profile(mut params: ExecParams, sess: &Session) -> ExecParams {
+pub(super) async fn maybe_run_with_user_profile(mut params: ExecParams, sess: &Session) -> ExecParams {
+    if sess.shell_environment_policy.use_snapshot
+        && let Some(command) = snapshot_invocation(&params, sess).await
+    {
+        params.shell_script = None;
+        params.command = command;
+        return params;
+    }
+
     let had_shell_script = params.shell_script.is_some();
     params = materialize_shell_script(&sess.user_shell, params);
 
@@ -283,6 +291,22 @@ pub(super) fn maybe_run_with_user_profile(mut params: ExecParams, sess: &Session
     params
 }
 
+/// Builds the command for calls that would otherwise source the user's rc
+/// file, sourcing the session's shell snapshot instead. Returns `None` when
+/// the call does not involve the rc file or no snapshot is available.
+async fn snapshot_invocation(params: &ExecParams, sess: &Session) -> Option<Vec<String>> {
+    let script = match params.shell_script.as_ref() {
+        Some(shell_script) if shell_script.source_user_rc => shell_script.command.clone(),
+        Some(_) => return None,
+        None if sess.shell_environment_policy.use_profile => {
+            crate::shell::script_for_command(&params.command)?
+        }
+        None => return None,
+    };
+    let snapshot = sess.shell_snapshot.snapshot_for(&sess.user_shell).await?;
+    sess.user_shell.format_snapshot_invocation(&script, &snapshot)
+}
+
 fn suppress_bash_job_control(command: &mut [String]) {
     let [program, flag, script] = command else {
         return;
@@ -564,7 +588,7 @@ impl Session {
         let ExecInvokeArgs { params, sandbox_type, sandbox_policy, sandbox_cwd, code_linux_sandbox_exe, stdout_stream } = exec_args;
         let tracking_command = params.command.clone();
         let dry_run_analysis = analyze_command(&tracking_command);
-        let params = maybe_run_with_user_profile(params, self);
+        let params = maybe_run_with_user_profile(params, self).await;
         let params_for_hooks = enable_hooks.then(|| params.clone());
 
         if enable_hooks
@@ -674,11 +698,13 @@ impl Session {
         let _ = self.tx_event.send(event).await;
     }
 
-    pub(super) async fn notify_stream_error(&self, sub_id: &str, message: impl Into<String>) {
-        let event = self.make_event(
-            sub_id,
-            EventMsg::Error(ErrorEvent { message: message.into() }),
-        );
+    pub(super) async fn notify_stream_error(
+        &self,
+        sub_id: &str,
+        code: ErrorCode,
+        message: impl Into<String>,
+    ) {
+        let event = self.make_event(sub_id, EventMsg::Error(ErrorEvent::new(message, code)));
         let _ = self.tx_event.send(event).await;
     }
 
@@ -687,8 +713,8 @@ impl Session {
         sandbox_permissions: code_protocol::models::SandboxPermissions,
     ) -> SandboxType {
         match assess_safety_for_untrusted_command(
-            self.approval_policy,
-            &self.sandbox_policy,
+            self.get_approval_policy(),
+            &self.get_sandbox_policy(),
             sandbox_permissions,
             false,
         ) {
@@ -838,10 +864,11 @@ impl Session {
         };
 
         let sandbox_type = self.resolve_internal_sandbox(SandboxPermissions::default());
+        let sandbox_policy = self.get_sandbox_policy();
         let exec_args = ExecInvokeArgs {
             params: exec_params,
             sandbox_type,
-            sandbox_policy: &self.sandbox_policy,
+            sandbox_policy: &sandbox_policy,
             sandbox_cwd: self.get_cwd(),
             code_linux_sandbox_exe: &self.code_linux_sandbox_exe,
             stdout_stream: None,
@@ -933,10 +960,11 @@ impl Session {
         };
 
         let sandbox_type = self.resolve_internal_sandbox(SandboxPermissions::default());
+        let sandbox_policy = self.get_sandbox_policy();
         let exec_args = ExecInvokeArgs {
             params: exec_params,
             sandbox_type,
-            sandbox_policy: &self.sandbox_policy,
+            sandbox_policy: &sandbox_policy,
             sandbox_cwd: self.get_cwd(),
             code_linux_sandbox_exe: &self.code_linux_sandbox_exe,
             stdout_stream: None,
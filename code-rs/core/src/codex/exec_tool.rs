@@ -9,6 +9,7 @@ use super::exec::{
 use super::fs_utils::{ensure_agent_dir, write_agent_file};
 use super::session::BackgroundExecState;
 use super::truncation::truncate_middle_bytes;
+use crate::config_types::ToolOutputCategory;
 use crate::tools::events::execute_custom_tool;
 use crate::tools::output_format::{format_exec_output_payload, format_exec_output_str};
 use code_protocol::models::FunctionCallOutputBody;
@@ -111,7 +112,7 @@ pub(crate) async fn handle_wait(
                         &ctx_inner.sub_id,
                         &ctx_inner.call_id,
                         &done,
-                        sess.tool_output_max_bytes,
+                        sess.tool_output_budget(ToolOutputCategory::Shell),
                     );
                     suppress_guard.disarm();
                     return ResponseInputItem::FunctionCallOutput {
@@ -273,7 +274,7 @@ pub(crate) async fn handle_wait(
                         &ctx_inner.sub_id,
                         &ctx_inner.call_id,
                         &done,
-                        sess.tool_output_max_bytes,
+                        sess.tool_output_budget(ToolOutputCategory::Shell),
                     );
                     suppress_guard.disarm();
                     ResponseInputItem::FunctionCallOutput {
@@ -450,12 +451,19 @@ pub(crate) fn to_exec_params(params: ShellToolCallParams, sess: &Session) -> Exe
         .timeout_ms
         .map(|ms| ms.max(MIN_SHELL_TIMEOUT_MS));
     let sandbox_permissions = params.sandbox_permissions.unwrap_or_default();
+    let mut env = create_env(&sess.shell_environment_policy);
+    withhold_spawned_env(
+        &mut env,
+        &sess.spawned_env,
+        &sess.shell_environment_policy.r#set,
+        &params.command,
+    );
     ExecParams {
         command: params.command,
         shell_script: None,
         cwd: sess.resolve_path(params.workdir.clone()),
         timeout_ms,
-        env: create_env(&sess.shell_environment_policy),
+        env,
         sandbox_permissions,
         additional_permissions: params.additional_permissions,
         justification: params.justification,
@@ -480,6 +488,7 @@ fn effective_sandbox_policy_for_exec(
     let crate::protocol::SandboxPolicy::WorkspaceWrite {
         writable_roots,
         network_access,
+        network_allowlist,
         exclude_tmpdir_env_var,
         exclude_slash_tmp,
         allow_git_writes,
@@ -506,6 +515,7 @@ fn effective_sandbox_policy_for_exec(
     crate::protocol::SandboxPolicy::WorkspaceWrite {
         writable_roots: effective_writable_roots,
         network_access: effective_network_access,
+        network_allowlist: network_allowlist.clone(),
         exclude_tmpdir_env_var: *exclude_tmpdir_env_var,
         exclude_slash_tmp: *exclude_slash_tmp,
         allow_git_writes: *allow_git_writes,
@@ -1538,6 +1548,28 @@ pub(crate) async fn handle_container_exec_with_params(
         MaybeApplyPatchVerified::NotApplyPatch => {}
     }
 
+    let turn_approval_policy = sess.get_approval_policy();
+    let turn_sandbox_policy = sess.get_sandbox_policy();
+
+    // Credentials a `[spawned_env.commands]` override released need a yes from
+    // the user; when nobody can be asked they stay withheld.
+    let released_env = crate::exec_env::released_spawned_env(
+        &params.env,
+        &sess.spawned_env,
+        &sess.shell_environment_policy.r#set,
+    );
+    let released_env_reason = if released_env.is_empty() {
+        None
+    } else if turn_approval_policy == AskForApproval::Never {
+        params.env.retain(|name, _| !released_env.contains(name));
+        None
+    } else {
+        Some(format!(
+            "Passes withheld environment variables: {}",
+            released_env.join(", ")
+        ))
+    };
+
     let safety = {
         let state = crate::codex::lock_or_panic!(sess.state);
         let mut command_safety_context =
@@ -1561,13 +1593,29 @@ pub(crate) async fn handle_container_exec_with_params(
         assess_command_safety(
             &params.command,
             safety_config,
-            sess.approval_policy,
-            &sess.sandbox_policy,
+            turn_approval_policy,
+            &turn_sandbox_policy,
             &state.approved_commands,
             params.sandbox_permissions,
             permissions_preapproved,
         )
     };
+    // After HEAD moved outside the session, the next write has to be
+    // confirmed even if the policy would run it unattended.
+    let git_head_reason = sess.pending_git_head_reconfirmation();
+    let safety = match safety {
+        SafetyCheck::AutoApprove { .. }
+            if git_head_reason.is_some()
+                && !crate::is_safe_command::is_known_safe_command(&params.command) =>
+        {
+            SafetyCheck::AskUser
+        }
+        SafetyCheck::AutoApprove {
+            user_explicitly_approved: false,
+            ..
+        } if released_env_reason.is_some() => SafetyCheck::AskUser,
+        safety => safety,
+    };
     let command_for_display = params.command.clone();
     let harness_summary_json: Option<String> = None;
 
@@ -1608,7 +1656,9 @@ pub(crate) async fn handle_container_exec_with_params(
                     approval_id: None,
                     command: params.command.clone(),
                     cwd: params.cwd.clone(),
-                    reason: params.justification.clone(),
+                    reason: git_head_reason
+                        .or(released_env_reason)
+                        .or_else(|| params.justification.clone()),
                     network_approval_context: None,
                     additional_permissions,
                 })
@@ -1643,6 +1693,7 @@ pub(crate) async fn handle_container_exec_with_params(
                     };
                 }
             }
+            sess.clear_git_head_reconfirmation();
             if params.sandbox_permissions.uses_additional_permissions() {
                 crate::safety::get_platform_sandbox().unwrap_or(SandboxType::None)
             } else {
@@ -1679,7 +1730,7 @@ pub(crate) async fn handle_container_exec_with_params(
         cwd: params.cwd.clone(),
         transcript_path: sess.hook_transcript_path(),
         model: sess.client.config().model.clone(),
-        permission_mode: crate::codex::hook_runtime::hook_permission_mode(sess.approval_policy),
+        permission_mode: crate::codex::hook_runtime::hook_permission_mode(turn_approval_policy),
         tool_name: "Bash".to_owned(),
         tool_use_id: call_id.clone(),
         command: display_label.clone(),
@@ -1704,7 +1755,7 @@ pub(crate) async fn handle_container_exec_with_params(
             },
         };
     }
-    let params = maybe_run_with_user_profile(params, sess);
+    let params = maybe_run_with_user_profile(params, sess).await;
 
     // ToolBefore hook for shell/container.exec commands
     let params_for_hooks = params.clone();
@@ -1758,6 +1809,20 @@ pub(crate) async fn handle_container_exec_with_params(
     let exec_ctx_for_task = exec_command_context.clone();
     let attempt_req_for_task = attempt_req;
 
+    // Set uncommitted work aside before git rewrites the working tree; the
+    // runner restores it once the command finishes.
+    let guard_stash = if sess.git_auto_stash && rewrites_git_worktree(&params.command) {
+        match code_git_tooling::stash_uncommitted(&params.cwd, &format!("before `{display_label}`")) {
+            Ok(stash) => stash,
+            Err(err) => {
+                warn!("auto-stash before `{display_label}` failed: {err}");
+                None
+            }
+        }
+    } else {
+        None
+    };
+
     // Emit BEGIN event using the normal path so the TUI shows a running cell
     sess
         .on_exec_command_begin(
@@ -1774,7 +1839,7 @@ pub(crate) async fn handle_container_exec_with_params(
     let sub_id_for_events = sub_id.clone();
     let call_id_for_events = call_id.clone();
     let sandbox_policy = effective_sandbox_policy_for_exec(
-        &sess.sandbox_policy,
+        &turn_sandbox_policy,
         params.sandbox_permissions,
         params.additional_permissions.as_ref(),
     );
@@ -1792,7 +1857,7 @@ pub(crate) async fn handle_container_exec_with_params(
     let backgrounded_task = backgrounded.clone();
     let suppress_event_flag_task = suppress_event_flag.clone();
     let display_label_task = display_label.clone();
-    let tool_output_max_bytes = sess.tool_output_max_bytes;
+    let shell_output_budget = sess.tool_output_budget(ToolOutputCategory::Shell);
     let managed_network_proxy = sess.managed_network_proxy();
     let network_approval = sess.network_approval();
     let zsh_fork_exec_config = compute_zsh_fork_exec_config(sess, sandbox_type, &params.command);
@@ -1888,8 +1953,9 @@ pub(crate) async fn handle_container_exec_with_params(
             }
         };
 
+        let sandbox_denied = matches!(&res, Err(CodexErr::Sandbox(SandboxErr::Denied { .. })));
         // Normalize to ExecToolCallOutput
-        let (out, exit_code) = match res {
+        let (mut out, exit_code) = match res {
             Ok(o) => { let exit = o.exit_code; (o, exit) },
             Err(CodexErr::Sandbox(SandboxErr::Timeout { output })) => (output.as_ref().clone(), 124),
             Err(e) => {
@@ -1908,6 +1974,10 @@ pub(crate) async fn handle_container_exec_with_params(
             }
         };
 
+        if let Some(stash) = guard_stash.as_ref() {
+            append_guard_stash_report(stash, &mut out);
+        }
+
         // Emit END event directly
         let end_msg = EventMsg::ExecCommandEnd(ExecCommandEndEvent {
             call_id: call_id_for_events.clone(),
@@ -1918,6 +1988,14 @@ pub(crate) async fn handle_container_exec_with_params(
         });
         let ev = Event { id: sub_id_for_events.clone(), event_seq: 0, msg: end_msg, order: Some(order_meta_for_end) };
         let _ = tx_event.send(ev).await;
+        if sandbox_denied {
+            let denied_msg = EventMsg::SandboxDenied(SandboxDeniedEvent {
+                call_id: call_id_for_events.clone(),
+                command: params.command.clone(),
+            });
+            let ev = Event { id: sub_id_for_events.clone(), event_seq: 0, msg: denied_msg, order: None };
+            let _ = tx_event.send(ev).await;
+        }
 
         // Store result for waiters
         {
@@ -1965,7 +2043,7 @@ pub(crate) async fn handle_container_exec_with_params(
                         &sub_id_for_events,
                         &call_id_for_events,
                         &full_body,
-                        tool_output_max_bytes,
+                        shell_output_budget,
                     );
                     let dev_text = format!("{header}\n\n{body}");
                     let _ = tx
@@ -2011,7 +2089,7 @@ pub(crate) async fn handle_container_exec_with_params(
                 &sub_id,
                 &call_id,
                 &done,
-                sess.tool_output_max_bytes,
+                sess.tool_output_budget(ToolOutputCategory::Shell),
             );
             if let Some(harness) = harness_summary_json.as_ref()
                 && !harness.is_empty() {
@@ -2725,12 +2803,14 @@ fn escalation_execution_for_trigger(
                 crate::protocol::SandboxPolicy::WorkspaceWrite {
                     writable_roots,
                     network_access,
+                    network_allowlist,
                     exclude_tmpdir_env_var,
                     exclude_slash_tmp,
                     allow_git_writes: _,
                 } => crate::protocol::SandboxPolicy::WorkspaceWrite {
                     writable_roots: writable_roots.clone(),
                     network_access: *network_access,
+                    network_allowlist: network_allowlist.clone(),
                     exclude_tmpdir_env_var: *exclude_tmpdir_env_var,
                     exclude_slash_tmp: *exclude_slash_tmp,
                     allow_git_writes: true,
@@ -2853,6 +2933,92 @@ fn is_git_write_command(argv: &[String]) -> bool {
     }
 }
 
+/// Whether `argv` runs a git command that replaces the working tree as a
+/// whole: a branch checkout or switch, a rebase, or a hard/merge/keep reset.
+/// Path-limited checkouts are left out; restoring around them would undo the
+/// very files they target.
+fn rewrites_git_worktree(argv: &[String]) -> bool {
+    let tokens: Vec<String> = match extract_shell_script_from_wrapper(argv) {
+        Some((_, script)) => shlex::split(&script)
+            .unwrap_or_else(|| script.split_whitespace().map(ToOwned::to_owned).collect()),
+        None => argv.to_vec(),
+    };
+    tokens
+        .split(|token| matches!(token.as_str(), "&&" | "||" | ";" | "|"))
+        .any(git_segment_rewrites_worktree)
+}
+
+fn git_segment_rewrites_worktree(segment: &[String]) -> bool {
+    let Some(git_index) = segment.iter().position(|token| {
+        std::path::Path::new(token)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name == "git")
+    }) else {
+        return false;
+    };
+    let mut rest = segment[git_index + 1..].iter().map(String::as_str);
+    let sub = loop {
+        match rest.next() {
+            Some("-C" | "-c" | "--git-dir" | "--work-tree") => {
+                rest.next();
+            }
+            Some(flag) if flag.starts_with('-') => {}
+            Some(sub) => break sub,
+            None => return false,
+        }
+    };
+    let args: Vec<&str> = rest.map(|arg| arg.trim_end_matches(';')).collect();
+    match sub {
+        "checkout" | "switch" => {
+            if args.contains(&"--") {
+                return false;
+            }
+            let positional: Vec<&str> = args
+                .iter()
+                .copied()
+                .filter(|arg| !arg.starts_with('-') || *arg == "-")
+                .collect();
+            let creates = args
+                .iter()
+                .any(|arg| matches!(*arg, "-b" | "-B" | "-c" | "-C" | "--orphan"));
+            // `checkout -b topic` keeps the tree; `checkout -b topic main` does not.
+            if creates {
+                positional.len() >= 2
+            } else {
+                positional.first().is_some_and(|first| *first != ".")
+            }
+        }
+        "rebase" => !args
+            .iter()
+            .any(|arg| matches!(*arg, "--continue" | "--abort" | "--skip" | "--quit")),
+        "reset" => args
+            .iter()
+            .any(|arg| matches!(*arg, "--hard" | "--merge" | "--keep")),
+        _ => false,
+    }
+}
+
+/// Restores an auto-stash after its command and appends the outcome to the
+/// output, so both the model and the transcript see what happened to the
+/// uncommitted changes.
+fn append_guard_stash_report(stash: &code_git_tooling::GuardStash, out: &mut ExecToolCallOutput) {
+    let report = match code_git_tooling::restore_guard_stash(stash) {
+        Ok(restore) => restore.describe(),
+        Err(err) => format!(
+            "Uncommitted changes were stashed before this command as \"{}\", but restoring them failed: {err}. Recover them with `git stash apply {}`.",
+            stash.message(),
+            stash.oid()
+        ),
+    };
+    for text in [&mut out.aggregated_output.text, &mut out.stderr.text] {
+        if !text.is_empty() && !text.ends_with('\n') {
+            text.push('\n');
+        }
+        let _ = writeln!(text, "[auto-stash] {report}");
+    }
+}
+
 fn extract_shell_script_from_wrapper(argv: &[String]) -> Option<(usize, String)> {
     // Return (index_of_script, script) if argv matches: <shell> (-lc|-c) <script>
     if argv.len() == 3 {
@@ -3526,6 +3692,7 @@ mod shell_zsh_fork_tests {
         let sandbox_policy = SandboxPolicy::WorkspaceWrite {
             writable_roots: Vec::new(),
             network_access: false,
+            network_allowlist: Vec::new(),
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
             allow_git_writes: true,
@@ -3543,6 +3710,7 @@ mod shell_zsh_fork_tests {
         let sandbox_policy = SandboxPolicy::WorkspaceWrite {
             writable_roots: Vec::new(),
             network_access: true,
+            network_allowlist: Vec::new(),
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
             allow_git_writes: false,
@@ -3560,6 +3728,7 @@ mod shell_zsh_fork_tests {
         let sandbox_policy = SandboxPolicy::WorkspaceWrite {
             writable_roots: Vec::new(),
             network_access: true,
+            network_allowlist: Vec::new(),
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
             allow_git_writes: true,
@@ -3569,6 +3738,25 @@ mod shell_zsh_fork_tests {
         assert_eq!(None, escalation_trigger_for_argv(&sandbox_policy, &argv));
     }
 
+    #[test]
+    fn rewrites_git_worktree_matches_tree_replacing_commands() {
+        let argv = |items: &[&str]| items.iter().map(ToString::to_string).collect::<Vec<_>>();
+        let script = |script: &str| argv(&["bash", "-lc", script]);
+
+        assert!(rewrites_git_worktree(&argv(&["git", "checkout", "main"])));
+        assert!(rewrites_git_worktree(&argv(&["git", "-C", "repo", "switch", "-"])));
+        assert!(rewrites_git_worktree(&script("git fetch && git rebase origin/main")));
+        assert!(rewrites_git_worktree(&script("git reset --hard HEAD~1")));
+        assert!(rewrites_git_worktree(&script("git checkout -b topic origin/main")));
+
+        assert!(!rewrites_git_worktree(&argv(&["git", "checkout", "--", "src/lib.rs"])));
+        assert!(!rewrites_git_worktree(&argv(&["git", "checkout", "-b", "topic"])));
+        assert!(!rewrites_git_worktree(&script("git checkout .")));
+        assert!(!rewrites_git_worktree(&script("git reset HEAD~1")));
+        assert!(!rewrites_git_worktree(&script("git rebase --continue")));
+        assert!(!rewrites_git_worktree(&script("git status && git log")));
+    }
+
     #[test]
     fn extract_shell_script_from_wrapper_parses_supported_shells() {
         let argv = vec!["zsh".to_string(), "-lc".to_string(), "echo hi".to_string()];
@@ -3598,6 +3786,7 @@ mod shell_zsh_fork_tests {
         let sandbox_policy = SandboxPolicy::WorkspaceWrite {
             writable_roots: Vec::new(),
             network_access: false,
+            network_allowlist: Vec::new(),
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
             allow_git_writes: true,
@@ -3629,6 +3818,7 @@ mod shell_zsh_fork_tests {
         let sandbox_policy = SandboxPolicy::WorkspaceWrite {
             writable_roots: Vec::new(),
             network_access: false,
+            network_allowlist: Vec::new(),
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
             allow_git_writes: false,
@@ -3744,6 +3934,7 @@ mod shell_zsh_fork_tests {
         let sandbox_policy = SandboxPolicy::WorkspaceWrite {
             writable_roots: Vec::new(),
             network_access: false,
+            network_allowlist: Vec::new(),
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
             allow_git_writes: true,
@@ -0,0 +1,193 @@
+//! Heartbeat events for turns that go quiet.
+//!
+//! Long tool calls, agent waits and slow provider responses can leave a turn
+//! without events for minutes. Every stamped event updates a small tracker
+//! with the turn's current phase; a session-wide ticker sends
+//! `EventMsg::Heartbeat` whenever the running turn has been quiet for the
+//! configured interval, so clients can tell "working" from "hung".
+
+use std::sync::Weak;
+use std::time::Duration;
+use std::time::Instant;
+
+use super::Session;
+use crate::protocol::EventMsg;
+use crate::protocol::HeartbeatEvent;
+use crate::protocol::HeartbeatPhase;
+
+const MAX_DETAIL_CHARS: usize = 80;
+
+pub(super) struct HeartbeatTracker {
+    sub_id: String,
+    turn_started: Instant,
+    last_event: Instant,
+    last_heartbeat: Option<Instant>,
+    phase: HeartbeatPhase,
+    detail: Option<String>,
+}
+
+impl HeartbeatTracker {
+    /// Updates `slot` for an event about to be sent for `sub_id`.
+    pub(super) fn observe(slot: &mut Option<Self>, sub_id: &str, msg: &EventMsg, now: Instant) {
+        match msg {
+            EventMsg::Heartbeat(_) => return,
+            EventMsg::TaskStarted => {
+                *slot = Some(Self {
+                    sub_id: sub_id.to_owned(),
+                    turn_started: now,
+                    last_event: now,
+                    last_heartbeat: None,
+                    phase: HeartbeatPhase::Model,
+                    detail: None,
+                });
+                return;
+            }
+            EventMsg::TaskComplete(_) | EventMsg::TurnAborted(_) | EventMsg::ShutdownComplete => {
+                if slot.as_ref().is_some_and(|tracker| tracker.sub_id == sub_id) {
+                    *slot = None;
+                }
+                return;
+            }
+            _ => {}
+        }
+        let Some(tracker) = slot.as_mut().filter(|tracker| tracker.sub_id == sub_id) else {
+            return;
+        };
+        tracker.last_event = now;
+        let (phase, detail) = match msg {
+            EventMsg::ExecCommandBegin(ev) => {
+                (HeartbeatPhase::ToolCall, Some(truncate_detail(&ev.command.join(" "))))
+            }
+            EventMsg::McpToolCallBegin(ev) => (
+                HeartbeatPhase::ToolCall,
+                Some(format!("{}/{}", ev.invocation.server, ev.invocation.tool)),
+            ),
+            EventMsg::CustomToolCallBegin(ev) => {
+                (HeartbeatPhase::ToolCall, Some(ev.tool_name.clone()))
+            }
+            EventMsg::ReplExecBegin(_) => (HeartbeatPhase::ToolCall, Some("repl".to_owned())),
+            EventMsg::WebSearchBegin(_) => {
+                (HeartbeatPhase::ToolCall, Some("web_search".to_owned()))
+            }
+            EventMsg::PatchApplyBegin(_) => {
+                (HeartbeatPhase::ToolCall, Some("apply_patch".to_owned()))
+            }
+            EventMsg::DynamicToolCallRequest(ev) => (HeartbeatPhase::ToolCall, Some(ev.tool.clone())),
+            EventMsg::ExecApprovalRequest(_)
+            | EventMsg::ApplyPatchApprovalRequest(_)
+            | EventMsg::RequestUserInput(_)
+            | EventMsg::RequestPermissions(_)
+            | EventMsg::ElicitationRequest(_) => (HeartbeatPhase::AwaitingUser, None),
+            // Output from a running call does not change what the turn waits on.
+            EventMsg::ExecCommandOutputDelta(_) | EventMsg::CustomToolCallUpdate(_) => return,
+            _ => (HeartbeatPhase::Model, None),
+        };
+        tracker.phase = phase;
+        tracker.detail = detail;
+    }
+
+    /// The heartbeat to send now, if the turn has been quiet for `interval`
+    /// since its last event or heartbeat.
+    pub(super) fn due(&mut self, interval: Duration, now: Instant) -> Option<(String, HeartbeatEvent)> {
+        let quiet_since = self
+            .last_heartbeat
+            .map_or(self.last_event, |heartbeat| heartbeat.max(self.last_event));
+        if now.duration_since(quiet_since) < interval {
+            return None;
+        }
+        self.last_heartbeat = Some(now);
+        Some((
+            self.sub_id.clone(),
+            HeartbeatEvent {
+                elapsed_ms: millis(now.duration_since(self.turn_started)),
+                idle_ms: millis(now.duration_since(self.last_event)),
+                phase: self.phase,
+                detail: self.detail.clone(),
+            },
+        ))
+    }
+}
+
+/// Runs until the session is dropped.
+pub(super) fn spawn_heartbeat_ticker(session: Weak<Session>, interval: Duration) {
+    let tick = (interval / 4).max(Duration::from_millis(250));
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(tick);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            let Some(sess) = session.upgrade() else {
+                return;
+            };
+            let due = {
+                let mut state = crate::codex::lock_or_panic!(sess.state);
+                state
+                    .heartbeat
+                    .as_mut()
+                    .and_then(|tracker| tracker.due(interval, Instant::now()))
+            };
+            if let Some((sub_id, heartbeat)) = due {
+                let event = sess.make_event(&sub_id, EventMsg::Heartbeat(heartbeat));
+                sess.send_event(event).await;
+            }
+        }
+    });
+}
+
+fn truncate_detail(text: &str) -> String {
+    if text.chars().count() <= MAX_DETAIL_CHARS {
+        return text.to_owned();
+    }
+    let truncated: String = text.chars().take(MAX_DETAIL_CHARS - 1).collect();
+    format!("{truncated}…")
+}
+
+fn millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::McpInvocation;
+    use crate::protocol::McpToolCallBeginEvent;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn reports_phase_once_per_quiet_interval() {
+        let start = Instant::now();
+        let interval = Duration::from_secs(10);
+        let mut slot = None;
+        HeartbeatTracker::observe(&mut slot, "sub-1", &EventMsg::TaskStarted, start);
+        let begin = EventMsg::McpToolCallBegin(McpToolCallBeginEvent {
+            call_id: "call-1".to_owned(),
+            parent_call_id: None,
+            invocation: McpInvocation {
+                server: "github".to_owned(),
+                tool: "search_code".to_owned(),
+                arguments: None,
+            },
+        });
+        HeartbeatTracker::observe(&mut slot, "sub-1", &begin, start + Duration::from_secs(2));
+        // Another submission finishing does not end this turn.
+        HeartbeatTracker::observe(&mut slot, "other", &EventMsg::ShutdownComplete, start);
+
+        let tracker = slot.as_mut().expect("tracker");
+        assert_eq!(tracker.due(interval, start + Duration::from_secs(11)), None);
+        let (sub_id, heartbeat) = tracker
+            .due(interval, start + Duration::from_secs(12))
+            .expect("heartbeat after 10s of silence");
+        assert_eq!(sub_id, "sub-1");
+        assert_eq!(
+            heartbeat,
+            HeartbeatEvent {
+                elapsed_ms: 12_000,
+                idle_ms: 10_000,
+                phase: HeartbeatPhase::ToolCall,
+                detail: Some("github/search_code".to_owned()),
+            }
+        );
+        assert_eq!(tracker.due(interval, start + Duration::from_secs(15)), None);
+        assert!(tracker.due(interval, start + Duration::from_secs(22)).is_some());
+    }
+}
@@ -1,5 +1,7 @@
 use super::*;
 use code_rmcp_client::ElicitationResponse;
+use crate::protocol::RequestBudgetCheckEvent;
+use crate::protocol::RequestBudgetDecision;
 use crate::protocol::TaskOriginKind;
 use serde_json::Value;
 use code_protocol::dynamic_tools::DynamicToolResponse;
@@ -135,6 +137,21 @@ impl EnvironmentContextStreamRegistry {
     }
 }
 
+/// Pending `Op::SetNextTurnOverrides`, applied to the next user turn.
+#[derive(Debug, Clone, Default)]
+pub(super) struct NextTurnOverrides {
+    pub(super) model: Option<String>,
+    pub(super) effort: Option<crate::config_types::ReasoningEffort>,
+    pub(super) sandbox: Option<SandboxPolicy>,
+    pub(super) approval: Option<AskForApproval>,
+}
+
+pub(super) struct TurnPolicyOverride {
+    sub_id: String,
+    approval_policy: Option<AskForApproval>,
+    sandbox_policy: Option<SandboxPolicy>,
+}
+
 #[derive(Default)]
 pub(super) struct State {
     pub(super) approved_commands: HashSet<ApprovedCommandPattern>,
@@ -143,14 +160,35 @@ pub(super) struct State {
     pub(super) pending_request_user_input: HashMap<String, oneshot::Sender<crate::protocol::RequestUserInputResponse>>,
     pub(super) pending_request_permissions: HashMap<String, PendingRequestPermissions>,
     pub(super) pending_dynamic_tools: HashMap<String, oneshot::Sender<DynamicToolResponse>>,
+    pub(super) pending_request_budget: HashMap<String, oneshot::Sender<RequestBudgetDecision>>,
     pub(super) pending_input: Vec<ResponseInputItem>,
     pub(super) pending_user_input: Vec<QueuedUserInput>,
     pub(super) history: ConversationHistory,
     pub(super) granted_permissions_by_turn: HashMap<String, code_protocol::models::PermissionProfile>,
     pub(super) granted_permissions_for_session: Option<code_protocol::models::PermissionProfile>,
+    /// Sandbox and approval policy of the running turn when
+    /// `Op::SetNextTurnOverrides` changed them.
+    pub(super) turn_policy_override: Option<TurnPolicyOverride>,
     /// Active MCP tool selection when `search_tool_bm25` gating is enabled.
     /// When `None`, no selection has been made yet for this session.
     pub(super) active_mcp_tool_selection: Option<Vec<String>>,
+    /// MCP tools called recently, most recent first. Scores tools under
+    /// `[tools.mcp_budget]`.
+    pub(super) recent_mcp_tools: VecDeque<String>,
+    /// MCP tools the model loaded with `tools_list_more`; every later request
+    /// includes them regardless of the budget.
+    pub(super) loaded_mcp_tools: Vec<String>,
+    /// MCP tools the latest request left out to stay within the budget.
+    pub(super) omitted_mcp_tools: Vec<String>,
+    /// Workspace snapshots taken as user turns started, oldest first.
+    pub(super) turn_baselines: VecDeque<super::turn_baselines::TurnBaseline>,
+    /// Tool names sent with the latest request.
+    pub(super) turn_tool_names: Vec<String>,
+    /// The static part of the `capabilities` output, keyed by the submission
+    /// it was built for. Cleared when the tool list changes.
+    pub(super) capabilities_cache: Option<(String, Value)>,
+    /// Phase and timing of the running turn for heartbeat events.
+    pub(super) heartbeat: Option<super::heartbeat::HeartbeatTracker>,
     /// Tracks which completed agents (by id) have already been returned to the
     /// model for a given batch when using `agent` with `action="wait"` and
     /// `return_all=false`.
@@ -165,6 +203,11 @@ pub(super) struct State {
     /// Scratchpad that buffers streamed items/deltas for the current HTTP attempt
     /// so we can seed retries without losing progress.
     pub(super) turn_scratchpad: Option<TurnScratchpad>,
+    /// Assistant text from dropped attempts that the next finalized assistant
+    /// message continues; stitched back so the transcript holds it once.
+    pub(super) retry_prefix: Option<String>,
+    /// Estimated output tokens regenerated because streams dropped mid-response
+    pub(super) retry_wasted_output_tokens: u64,
     /// Per-submission monotonic event sequence (resets at `TaskStarted`)
     pub(super) event_seq_by_sub_id: HashMap<String, u64>,
     /// Per-submission sequence used when synthesizing background `OrderMeta`.
@@ -248,6 +291,52 @@ pub(super) struct TurnScratchpad {
     pub(super) partial_assistant_text: String,
     /// Last reasoning summary fragment received via deltas (not yet finalized)
     pub(super) partial_reasoning_summary: String,
+    /// Full text of the in-flight assistant message, handed back to the model
+    /// as a prefix on retry. Dropped once it exceeds `RETRY_PREFIX_MAX_BYTES`.
+    pub(super) assistant_prefix: String,
+    pub(super) assistant_prefix_overflowed: bool,
+    /// Bytes streamed for the in-flight assistant message
+    pub(super) streamed_assistant_bytes: usize,
+    /// Bytes of reasoning summary streamed during this attempt
+    pub(super) streamed_reasoning_bytes: usize,
+}
+
+/// Largest in-flight assistant message we replay as a prefix on retry; longer
+/// partials fall back to the tail-only retry hint.
+pub(super) const RETRY_PREFIX_MAX_BYTES: usize = 32 * 1024;
+
+impl TurnScratchpad {
+    /// Assistant text already delivered to the user that a retry should
+    /// continue from, if it is small enough to replay.
+    pub(super) fn retry_prefix(&self) -> Option<&str> {
+        (!self.assistant_prefix_overflowed && !self.assistant_prefix.is_empty())
+            .then_some(self.assistant_prefix.as_str())
+    }
+
+    /// Rough output-token cost of streamed text that a retry will regenerate.
+    pub(super) fn wasted_output_tokens(&self) -> u64 {
+        let bytes = self.streamed_assistant_bytes + self.streamed_reasoning_bytes;
+        bytes.div_ceil(4) as u64
+    }
+}
+
+/// Joins a dropped attempt's partial assistant text with the retry's final
+/// message. When the model restarted from scratch instead of continuing, the
+/// retry already contains the prefix and is kept as-is.
+pub(super) fn stitch_retry_prefix(prefix: &str, item: ResponseItem) -> ResponseItem {
+    match item {
+        ResponseItem::Message { id, role, mut content, end_turn, phase } => {
+            if let Some(ContentItem::OutputText { text }) = content
+                .iter_mut()
+                .find(|c| matches!(c, ContentItem::OutputText { .. }))
+                && !text.starts_with(prefix)
+            {
+                text.insert_str(0, prefix);
+            }
+            ResponseItem::Message { id, role, content, end_turn, phase }
+        }
+        other => other,
+    }
 }
 
 #[derive(Clone)]
@@ -338,11 +427,14 @@ pub(super) fn is_connectivity_error(err: &CodexErr) -> bool {
 #[cfg(test)]
 mod tests {
     use super::is_connectivity_error;
+    use super::stitch_retry_prefix;
+    use super::TurnScratchpad;
     use super::{
         ApprovedCommandMatchKind,
         ApprovedCommandPattern,
     };
     use crate::error::CodexErr;
+    use code_protocol::models::{ContentItem, ResponseItem};
 
     #[test]
     fn context_overflow_transport_stream_is_not_connectivity() {
@@ -376,6 +468,53 @@ mod tests {
 
         assert!(pattern.matches(&["git status --short".to_string()]));
     }
+
+    fn assistant_message(text: &str) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: "assistant".to_string(),
+            content: vec![ContentItem::OutputText { text: text.to_string() }],
+            end_turn: None,
+            phase: None,
+        }
+    }
+
+    fn message_text(item: &ResponseItem) -> &str {
+        match item {
+            ResponseItem::Message { content, .. } => match content.first() {
+                Some(ContentItem::OutputText { text }) => text,
+                _ => "",
+            },
+            _ => "",
+        }
+    }
+
+    #[test]
+    fn retry_continuation_is_stitched_onto_prefix() {
+        let stitched = stitch_retry_prefix("Hello, wor", assistant_message("ld!"));
+        assert_eq!(message_text(&stitched), "Hello, world!");
+    }
+
+    #[test]
+    fn retry_that_repeats_prefix_is_not_duplicated() {
+        let stitched = stitch_retry_prefix("Hello, wor", assistant_message("Hello, world!"));
+        assert_eq!(message_text(&stitched), "Hello, world!");
+    }
+
+    #[test]
+    fn oversized_partial_is_not_replayed() {
+        let mut sp = TurnScratchpad {
+            assistant_prefix: "abc".to_string(),
+            streamed_assistant_bytes: 8,
+            streamed_reasoning_bytes: 4,
+            ..TurnScratchpad::default()
+        };
+        assert_eq!(sp.retry_prefix(), Some("abc"));
+        assert_eq!(sp.wasted_output_tokens(), 3);
+
+        sp.assistant_prefix_overflowed = true;
+        assert_eq!(sp.retry_prefix(), None);
+    }
 }
 
 #[derive(Debug)]
@@ -415,6 +554,7 @@ pub(crate) struct Session {
     pub(super) approval_policy: AskForApproval,
     pub(super) sandbox_policy: SandboxPolicy,
     pub(super) shell_environment_policy: ShellEnvironmentPolicy,
+    pub(super) spawned_env: crate::config_types::SpawnedEnvPolicy,
     pub(super) collaboration_mode: crate::protocol::CollaborationModeKind,
     pub(super) disable_response_storage: bool,
     pub(super) tools_config: ToolsConfig,
@@ -426,6 +566,8 @@ pub(crate) struct Session {
     pub(super) repl_handles: std::collections::HashMap<crate::config::ReplRuntimeKindToml, crate::tools::repl::ReplHandle>,
     pub(super) network_proxy: Option<crate::config::network_proxy_spec::StartedNetworkProxy>,
     pub(super) network_approval: Arc<crate::network_approval::NetworkApprovalService>,
+    #[cfg(feature = "managed-network-proxy")]
+    pub(super) network_egress: Option<Arc<crate::network_egress::NetworkEgressRecorder>>,
 
     /// Manager for external MCP servers/tools.
     pub(super) mcp_connection_manager: McpConnectionManager,
@@ -450,6 +592,9 @@ pub(crate) struct Session {
     pub(super) state: Mutex<State>,
     pub(super) code_linux_sandbox_exe: Option<PathBuf>,
     pub(super) user_shell: shell::Shell,
+    /// Captured rc environment reused by exec calls when
+    /// `shell_environment_policy.use_snapshot` is set.
+    pub(super) shell_snapshot: crate::shell_snapshot::ShellSnapshotCache,
     pub(super) dangerous_command_detection_enabled: bool,
     pub(super) safe_command_rules: crate::config_types::CommandSafetyRuleset,
     pub(super) dangerous_command_rules: crate::config_types::CommandSafetyRuleset,
@@ -460,21 +605,40 @@ pub(crate) struct Session {
     /// Track the last screenshot path and hash to detect changes
     #[cfg(feature = "browser-automation")]
     pub(super) last_screenshot_info: Mutex<LastScreenshotInfo>, // (path, phash, dhash)
+    /// Request ordinal of the most recent `browser_*` tool call (0 = never)
+    #[cfg(feature = "browser-automation")]
+    pub(super) browser_tool_last_request: AtomicU64,
     pub(super) time_budget: Mutex<Option<RunTimeBudget>>,
     pub(super) confirm_guard: ConfirmGuardRuntime,
+    /// Stash uncommitted changes around model-run git commands that rewrite
+    /// the working tree (`git_safety.auto_stash`).
+    pub(super) git_auto_stash: bool,
+    /// Branch and commit last seen in `cwd`, compared against HEAD changes
+    /// reported by the file watcher.
+    pub(super) git_head: Mutex<Option<crate::git_info::GitHeadState>>,
+    /// Set when HEAD changed outside the session; the next write command or
+    /// patch asks for approval with this reason.
+    pub(super) git_head_reconfirmation: Mutex<Option<String>>,
     pub(super) project_hooks: ProjectHooks,
     pub(super) project_commands: Vec<ProjectCommand>,
     pub(super) lifecycle_hooks: code_hooks::Hooks,
     pub(super) tool_output_max_bytes: usize,
+    pub(super) tool_output_limits: crate::config_types::ToolOutputLimits,
     pub(super) hook_guard: AtomicBool,
     pub(super) github: Arc<RwLock<crate::config_types::GithubConfig>>,
     pub(super) validation: Arc<RwLock<crate::config_types::ValidationConfig>>,
     pub(super) self_handle: Weak<Session>,
     pub(super) active_review: Mutex<Option<ReviewRequest>>,
     pub(super) next_turn_text_format: Mutex<Option<TextFormat>>,
+    pub(super) next_turn_overrides: Mutex<Option<NextTurnOverrides>>,
+    pub(super) next_turn_disabled_prompt_segments: Mutex<Vec<String>>,
+    pub(super) request_budget_negotiation: bool,
+    pub(super) heartbeat_interval: Option<std::time::Duration>,
     pub(super) env_ctx_v2: bool,
     pub(super) retention_config: crate::config_types::RetentionConfig,
     pub(super) model_descriptions: Option<String>,
+    /// Project notes from `.code/memory`, read once when the session starts.
+    pub(super) project_memory_context: Option<String>,
     pub(super) mcp_access: StdRwLock<McpAccessState>,
 }
 pub(super) struct HookGuard<'a> {
@@ -528,8 +692,13 @@ pub(crate) struct CommandApprovalRequest {
 }
 
 impl Session {
+    /// The approval policy of the running turn.
     pub(crate) fn get_approval_policy(&self) -> AskForApproval {
-        self.approval_policy
+        crate::codex::lock_or_panic!(self.state)
+            .turn_policy_override
+            .as_ref()
+            .and_then(|turn| turn.approval_policy)
+            .unwrap_or(self.approval_policy)
     }
 
     pub(crate) fn dangerous_command_detection_enabled(&self) -> bool {
@@ -599,6 +768,67 @@ impl Session {
         current.clone()
     }
 
+    pub(crate) fn mcp_tool_budget(&self) -> Option<&crate::config_types::McpToolBudgetConfig> {
+        self.tools_config.mcp_budget.as_ref()
+    }
+
+    pub(crate) fn note_mcp_tool_used(&self, tool_name: &str) {
+        let mut state = crate::codex::lock_or_panic!(self.state);
+        state
+            .recent_mcp_tools
+            .retain(|name| !name.eq_ignore_ascii_case(tool_name));
+        state.recent_mcp_tools.push_front(tool_name.to_owned());
+        state
+            .recent_mcp_tools
+            .truncate(crate::mcp::tool_budget::RECENT_MCP_TOOLS_LIMIT);
+    }
+
+    pub(crate) fn recent_mcp_tools_snapshot(&self) -> Vec<String> {
+        let state = crate::codex::lock_or_panic!(self.state);
+        state.recent_mcp_tools.iter().cloned().collect()
+    }
+
+    pub(crate) fn loaded_mcp_tools_snapshot(&self) -> Vec<String> {
+        let state = crate::codex::lock_or_panic!(self.state);
+        state.loaded_mcp_tools.clone()
+    }
+
+    pub(crate) fn omitted_mcp_tools_snapshot(&self) -> Vec<String> {
+        let state = crate::codex::lock_or_panic!(self.state);
+        state.omitted_mcp_tools.clone()
+    }
+
+    pub(crate) fn set_omitted_mcp_tools(&self, tools: Vec<String>) {
+        let mut state = crate::codex::lock_or_panic!(self.state);
+        state.omitted_mcp_tools = tools;
+    }
+
+    /// Moves `tools` from the omitted list into the loaded set, returning the
+    /// names that were loaded.
+    pub(crate) fn load_mcp_tools(&self, tools: &[String]) -> Vec<String> {
+        let mut state = crate::codex::lock_or_panic!(self.state);
+        let mut loaded = Vec::new();
+        for tool in tools {
+            let Some(idx) = state
+                .omitted_mcp_tools
+                .iter()
+                .position(|name| name.eq_ignore_ascii_case(tool))
+            else {
+                continue;
+            };
+            let name = state.omitted_mcp_tools.remove(idx);
+            if !state
+                .loaded_mcp_tools
+                .iter()
+                .any(|existing| existing.eq_ignore_ascii_case(&name))
+            {
+                state.loaded_mcp_tools.push(name.clone());
+            }
+            loaded.push(name);
+        }
+        loaded
+    }
+
     pub(crate) fn set_mcp_style_filters(
         &self,
         style: Option<crate::config_types::ShellScriptStyle>,
@@ -708,6 +938,21 @@ impl Session {
         self.tools_config.search_tool
     }
 
+    pub(crate) fn web_search_provider(&self) -> Option<&crate::web_search::WebSearchProvider> {
+        self.tools_config.web_search_provider.as_ref()
+    }
+
+    pub(crate) fn web_search_allowed_domains(&self) -> &[String] {
+        self.tools_config
+            .web_search_allowed_domains
+            .as_deref()
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn docs_bundles(&self) -> &[crate::docs_bundles::DocsBundle] {
+        &self.tools_config.docs_bundles
+    }
+
     pub(crate) fn repl_enabled(&self) -> bool {
         self.tools_config.repl
     }
@@ -740,6 +985,22 @@ impl Session {
         Arc::clone(&self.network_approval)
     }
 
+    /// Summary of the proxied connections tool calls made during `sub_id`,
+    /// when egress logging is on and there were any.
+    pub(crate) fn take_network_egress_summary(&self, sub_id: &str) -> Option<String> {
+        #[cfg(feature = "managed-network-proxy")]
+        {
+            self.network_egress
+                .as_ref()
+                .and_then(|recorder| recorder.take_turn_summary(sub_id))
+        }
+        #[cfg(not(feature = "managed-network-proxy"))]
+        {
+            let _ = sub_id;
+            None
+        }
+    }
+
     pub(crate) async fn repl_manager_for_runtime(
         &self,
         kind: crate::config::ReplRuntimeKindToml,
@@ -813,6 +1074,59 @@ impl Session {
             .and_then(|budget| budget.maybe_nudge(std::time::Instant::now()))
     }
 
+    /// Total and remaining run time when `max_run_seconds` is set.
+    pub(crate) fn time_budget_snapshot(&self) -> Option<(Duration, Duration)> {
+        let guard = crate::codex::lock_or_panic!(self.time_budget);
+        guard.as_ref().map(|budget| {
+            let remaining = budget
+                .deadline
+                .saturating_duration_since(std::time::Instant::now());
+            (budget.total, remaining)
+        })
+    }
+
+    /// Context window, auto-compact threshold and tool output cap for the
+    /// session's model.
+    pub(crate) fn model_limits(&self) -> Value {
+        serde_json::json!({
+            "context_window": self.client.get_model_context_window(),
+            "auto_compact_token_limit": self.client.get_auto_compact_token_limit(),
+            "tool_output_max_bytes": self.tool_output_max_bytes,
+        })
+    }
+
+    pub(crate) fn token_usage_info_snapshot(&self) -> Option<TokenUsageInfo> {
+        let state = crate::codex::lock_or_panic!(self.state);
+        state.token_usage_info.clone()
+    }
+
+    pub(crate) fn set_turn_tool_names(&self, names: Vec<String>) {
+        let mut state = crate::codex::lock_or_panic!(self.state);
+        if state.turn_tool_names != names {
+            state.turn_tool_names = names;
+            state.capabilities_cache = None;
+        }
+    }
+
+    pub(crate) fn turn_tool_names_snapshot(&self) -> Vec<String> {
+        let state = crate::codex::lock_or_panic!(self.state);
+        state.turn_tool_names.clone()
+    }
+
+    pub(crate) fn cached_capabilities(&self, sub_id: &str) -> Option<Value> {
+        let state = crate::codex::lock_or_panic!(self.state);
+        state
+            .capabilities_cache
+            .as_ref()
+            .filter(|(cached_for, _)| cached_for == sub_id)
+            .map(|(_, value)| value.clone())
+    }
+
+    pub(crate) fn store_capabilities(&self, sub_id: &str, value: Value) {
+        let mut state = crate::codex::lock_or_panic!(self.state);
+        state.capabilities_cache = Some((sub_id.to_owned(), value));
+    }
+
     pub(super) async fn apply_remote_model_overrides(&self, prompt: &mut Prompt) -> bool {
         let configured_model = self.client.get_model();
 
@@ -885,8 +1199,13 @@ impl Session {
         self.record_conversation_items(&[message]).await;
     }
 
-    pub(crate) fn get_sandbox_policy(&self) -> &SandboxPolicy {
-        &self.sandbox_policy
+    /// The sandbox policy of the running turn.
+    pub(crate) fn get_sandbox_policy(&self) -> SandboxPolicy {
+        crate::codex::lock_or_panic!(self.state)
+            .turn_policy_override
+            .as_ref()
+            .and_then(|turn| turn.sandbox_policy.clone())
+            .unwrap_or_else(|| self.sandbox_policy.clone())
     }
 
     pub(crate) fn session_uuid(&self) -> Uuid {
@@ -975,6 +1294,31 @@ impl Session {
         self.mcp_connection_manager.shutdown_all().await;
     }
 
+    /// Stops every child process the session owns (exec sessions, REPL
+    /// kernels, MCP servers, agents, the browser we launched) and waits for
+    /// them, so nothing outlives a shutdown. Unlike [`Self::abort`], which
+    /// fires these off in the background, this is awaited before the process
+    /// exits.
+    pub(crate) async fn shutdown_child_processes(&self) {
+        self.exec_command_manager.kill_all().await;
+        for handle in self.repl_handles.values() {
+            if let Some(js_mgr) = handle.manager_if_started() {
+                js_mgr.kill().await;
+            }
+        }
+        self.shutdown_mcp_clients().await;
+        let cancelled = AGENT_MANAGER.write().await.cancel_all();
+        if cancelled > 0 {
+            info!("cancelled {cancelled} running agent(s) on shutdown");
+        }
+        #[cfg(feature = "browser-automation")]
+        if let Some(browser_manager) = code_browser::global::get_browser_manager().await
+            && let Err(err) = browser_manager.stop().await
+        {
+            warn!("failed to stop browser on shutdown: {err}");
+        }
+    }
+
     pub(crate) async fn resolve_mcp_elicitation(
         &self,
         server_name: String,
@@ -1051,6 +1395,14 @@ impl Session {
         state.request_ordinal = state.request_ordinal.saturating_add(1);
     }
 
+    /// Remember that the model drove the browser during the current request so
+    /// per-attempt status capture keeps attaching screenshots.
+    #[cfg(feature = "browser-automation")]
+    pub(crate) fn note_browser_tool_use(&self) {
+        self.browser_tool_last_request
+            .store(self.current_request_ordinal(), Ordering::Relaxed);
+    }
+
     pub(super) fn turn_latency_request_scheduled(&self, attempt_req: u64, prompt: &Prompt) {
         let now = Instant::now();
         let gap_and_metrics = {
@@ -1073,6 +1425,7 @@ impl Session {
             attempt: attempt_req,
             gap_ms: gap.map(duration_to_millis),
             duration_ms: None,
+            first_token_ms: None,
             pending_input_count: metrics.pending_input_count as u64,
             pending_user_input_count: metrics.pending_user_input_count as u64,
             pending_background_execs: metrics.pending_background_execs as u64,
@@ -1096,6 +1449,7 @@ impl Session {
     pub(super) fn turn_latency_request_completed(
         &self,
         attempt_req: u64,
+        first_token_ms: Option<u64>,
         output_item_count: usize,
         token_usage: Option<&TokenUsage>,
     ) {
@@ -1128,6 +1482,7 @@ impl Session {
             attempt: attempt_req,
             gap_ms: None,
             duration_ms: duration.map(duration_to_millis),
+            first_token_ms,
             pending_input_count: metrics.pending_input_count as u64,
             pending_user_input_count: metrics.pending_user_input_count as u64,
             pending_background_execs: metrics.pending_background_execs as u64,
@@ -1148,7 +1503,12 @@ impl Session {
         self.emit_turn_latency(payload);
     }
 
-    pub(super) fn turn_latency_request_failed(&self, attempt_req: u64, note: Option<String>) {
+    pub(super) fn turn_latency_request_failed(
+        &self,
+        attempt_req: u64,
+        first_token_ms: Option<u64>,
+        note: Option<String>,
+    ) {
         let now = Instant::now();
         let (duration, prompt_counts, metrics) = {
             let mut state = crate::codex::lock_or_panic!(self.state);
@@ -1166,6 +1526,7 @@ impl Session {
             attempt: attempt_req,
             gap_ms: None,
             duration_ms: duration.map(duration_to_millis),
+            first_token_ms,
             pending_input_count: metrics.pending_input_count as u64,
             pending_user_input_count: metrics.pending_user_input_count as u64,
             pending_background_execs: metrics.pending_background_execs as u64,
@@ -1187,12 +1548,42 @@ impl Session {
     }
 
     fn emit_turn_latency(&self, payload: TurnLatencyPayload) {
+        self.record_latency_usage(&payload);
         if let Some(otel) = self.client.get_otel_event_manager() {
             otel.turn_latency_event(payload.clone());
         }
         self.client.log_turn_latency_debug(&payload);
     }
 
+    /// Adds a finished request to the per provider/model latency ledger read
+    /// by `code usage latency`.
+    fn record_latency_usage(&self, payload: &TurnLatencyPayload) {
+        let failed = match payload.phase {
+            TurnLatencyPhase::RequestScheduled => return,
+            TurnLatencyPhase::RequestCompleted => false,
+            TurnLatencyPhase::RequestFailed => true,
+        };
+        let Some(duration_ms) = payload.duration_ms else {
+            return;
+        };
+        let code_home = self.client.code_home().to_path_buf();
+        let sample = crate::latency_usage::LatencySample {
+            provider: self.client.config().model_provider_id.clone(),
+            model: self.client.get_model(),
+            first_token_ms: payload.first_token_ms,
+            duration_ms,
+            output_tokens: payload.token_usage_output_tokens,
+            failed,
+        };
+        spawn_usage_task(move || {
+            if let Err(err) =
+                crate::latency_usage::record_latency_sample(&code_home, &sample, Utc::now())
+            {
+                warn!("Failed to persist request latency: {err}");
+            }
+        });
+    }
+
     pub(super) fn scratchpad_push(
         &self,
         item: &ResponseItem,
@@ -1214,6 +1605,14 @@ impl Session {
         let mut state = crate::codex::lock_or_panic!(self.state);
         if let Some(sp) = &mut state.turn_scratchpad {
             sp.partial_assistant_text.push_str(delta);
+            sp.streamed_assistant_bytes = sp.streamed_assistant_bytes.saturating_add(delta.len());
+            if !sp.assistant_prefix_overflowed {
+                sp.assistant_prefix.push_str(delta);
+                if sp.assistant_prefix.len() > RETRY_PREFIX_MAX_BYTES {
+                    sp.assistant_prefix = String::new();
+                    sp.assistant_prefix_overflowed = true;
+                }
+            }
             // Keep memory bounded (ensure UTF-8 char boundary when trimming)
             if sp.partial_assistant_text.len() > 4000 {
                 let mut drain_up_to = sp.partial_assistant_text.len() - 4000;
@@ -1229,6 +1628,7 @@ impl Session {
         let mut state = crate::codex::lock_or_panic!(self.state);
         if let Some(sp) = &mut state.turn_scratchpad {
             sp.partial_reasoning_summary.push_str(delta);
+            sp.streamed_reasoning_bytes = sp.streamed_reasoning_bytes.saturating_add(delta.len());
             if sp.partial_reasoning_summary.len() > 4000 {
                 let mut drain_up_to = sp.partial_reasoning_summary.len() - 4000;
                 while !sp.partial_reasoning_summary.is_char_boundary(drain_up_to) {
@@ -1243,9 +1643,53 @@ impl Session {
         let mut state = crate::codex::lock_or_panic!(self.state);
         if let Some(sp) = &mut state.turn_scratchpad {
             sp.partial_assistant_text.clear();
+            sp.assistant_prefix.clear();
+            sp.assistant_prefix_overflowed = false;
+            sp.streamed_assistant_bytes = 0;
+        }
+    }
+
+    /// Carry a dropped attempt's partial assistant text into the next attempt
+    /// and add its regenerated output to the session's retry waste tally.
+    pub(super) fn note_dropped_attempt(&self, sp: &TurnScratchpad) {
+        let mut state = crate::codex::lock_or_panic!(self.state);
+        state.retry_wasted_output_tokens = state
+            .retry_wasted_output_tokens
+            .saturating_add(sp.wasted_output_tokens());
+        if let Some(prefix) = sp.retry_prefix() {
+            state
+                .retry_prefix
+                .get_or_insert_with(String::new)
+                .push_str(prefix);
         }
     }
 
+    /// Prepend carried-over retry text to the first assistant message that
+    /// finalizes after a dropped attempt.
+    pub(super) fn stitch_retry_prefix(&self, item: ResponseItem) -> ResponseItem {
+        if !matches!(&item, ResponseItem::Message { role, .. } if role == "assistant") {
+            return item;
+        }
+        let prefix = {
+            let mut state = crate::codex::lock_or_panic!(self.state);
+            state.retry_prefix.take()
+        };
+        match prefix {
+            Some(prefix) => stitch_retry_prefix(&prefix, item),
+            None => item,
+        }
+    }
+
+    pub(super) fn clear_retry_prefix(&self) {
+        let mut state = crate::codex::lock_or_panic!(self.state);
+        state.retry_prefix = None;
+    }
+
+    pub(super) fn take_retry_prefix(&self) -> Option<String> {
+        let mut state = crate::codex::lock_or_panic!(self.state);
+        state.retry_prefix.take()
+    }
+
     pub(super) fn take_scratchpad(&self) -> Option<TurnScratchpad> {
         let mut state = crate::codex::lock_or_panic!(self.state);
         state.turn_scratchpad.take()
@@ -1254,6 +1698,7 @@ impl Session {
     pub(super) fn clear_scratchpad(&self) {
         let mut state = crate::codex::lock_or_panic!(self.state);
         state.turn_scratchpad = None;
+        state.retry_prefix = None;
     }
 }
 
@@ -1338,15 +1783,68 @@ impl Session {
                 state.current_task.take();
             }
         state.granted_permissions_by_turn.remove(sub_id);
+        if state
+            .turn_policy_override
+            .as_ref()
+            .is_some_and(|turn| turn.sub_id == sub_id)
+        {
+            state.turn_policy_override = None;
+        }
     }
 
     pub fn has_running_task(&self) -> bool {
         crate::codex::lock_or_panic!(self.state).current_task.is_some()
     }
 
-    pub fn queue_user_input(&self, queued: QueuedUserInput) {
+    /// Records `head` as the last seen HEAD and returns the previous one.
+    pub(crate) fn replace_git_head(
+        &self,
+        head: crate::git_info::GitHeadState,
+    ) -> Option<crate::git_info::GitHeadState> {
+        crate::codex::lock_or_panic!(self.git_head).replace(head)
+    }
+
+    pub(crate) fn require_git_head_reconfirmation(&self, reason: String) {
+        *crate::codex::lock_or_panic!(self.git_head_reconfirmation) = Some(reason);
+    }
+
+    /// Approval reason for the next write while a HEAD change is unconfirmed.
+    /// Never-ask policies keep running unattended.
+    pub(crate) fn pending_git_head_reconfirmation(&self) -> Option<String> {
+        if self.get_approval_policy() == AskForApproval::Never {
+            return None;
+        }
+        crate::codex::lock_or_panic!(self.git_head_reconfirmation).clone()
+    }
+
+    pub(crate) fn clear_git_head_reconfirmation(&self) {
+        crate::codex::lock_or_panic!(self.git_head_reconfirmation).take();
+    }
+
+    /// Queues input for the running turn; returns how many inputs now wait.
+    pub fn queue_user_input(&self, queued: QueuedUserInput) -> usize {
         let mut state = crate::codex::lock_or_panic!(self.state);
         state.pending_user_input.push(queued);
+        state.pending_user_input.len()
+    }
+
+    pub(crate) fn queued_user_input_count(&self) -> usize {
+        crate::codex::lock_or_panic!(self.state).pending_user_input.len()
+    }
+
+    pub(crate) async fn notify_queued_user_input_delivered(
+        &self,
+        sub_id: &str,
+        submission_ids: Vec<String>,
+    ) {
+        let event = self.make_event(
+            sub_id,
+            EventMsg::QueuedUserInputDelivered(crate::protocol::QueuedUserInputDeliveredEvent {
+                submission_ids,
+                pending: self.queued_user_input_count(),
+            }),
+        );
+        self.send_event(event).await;
     }
 
     pub(super) fn notify_wait_interrupted(&self, reason: WaitInterruptReason) {
@@ -1503,7 +2001,43 @@ impl Session {
         &self,
         final_output_json_schema: Option<Value>,
     ) -> Arc<TurnContext> {
-        Arc::new(TurnContext {
+        Arc::new(self.build_turn_context(final_output_json_schema))
+    }
+
+    /// Turn context for the user turn `sub_id`, consuming any pending
+    /// `Op::SetNextTurnOverrides`. The overrides end with the turn.
+    pub(super) fn make_user_turn_context(
+        &self,
+        sub_id: &str,
+        final_output_json_schema: Option<Value>,
+    ) -> Arc<TurnContext> {
+        let mut turn_context = self.build_turn_context(final_output_json_schema);
+        let Some(overrides) = crate::codex::lock_or_panic!(self.next_turn_overrides).take() else {
+            return Arc::new(turn_context);
+        };
+        if overrides.model.is_some() || overrides.effort.is_some() {
+            turn_context.client = self
+                .client
+                .with_turn_overrides(overrides.model.as_deref(), overrides.effort);
+        }
+        if let Some(approval) = overrides.approval {
+            turn_context.approval_policy = approval;
+        }
+        if let Some(sandbox) = &overrides.sandbox {
+            turn_context.sandbox_policy = sandbox.clone();
+        }
+        if overrides.approval.is_some() || overrides.sandbox.is_some() {
+            crate::codex::lock_or_panic!(self.state).turn_policy_override = Some(TurnPolicyOverride {
+                sub_id: sub_id.to_owned(),
+                approval_policy: overrides.approval,
+                sandbox_policy: overrides.sandbox,
+            });
+        }
+        Arc::new(turn_context)
+    }
+
+    fn build_turn_context(&self, final_output_json_schema: Option<Value>) -> TurnContext {
+        TurnContext {
             client: self.client.clone(),
             cwd: self.cwd.clone(),
             base_instructions: self.base_instructions.clone(),
@@ -1516,8 +2050,11 @@ impl Session {
             collaboration_mode: self.collaboration_mode,
             is_review_mode: false,
             text_format_override: crate::codex::lock_or_panic!(self.next_turn_text_format).take(),
+            disabled_prompt_segments: std::mem::take(
+                &mut *crate::codex::lock_or_panic!(self.next_turn_disabled_prompt_segments),
+            ),
             final_output_json_schema,
-        })
+        }
     }
 
     pub(super) fn hooks_json(&self) -> &code_hooks::Hooks {
@@ -1785,21 +2322,96 @@ impl Session {
         }
     }
 
+    /// Announces a model request and waits for the client's decision.
+    /// Returns `None` when the turn is aborted first.
+    pub(crate) async fn request_budget_decision(
+        &self,
+        sub_id: &str,
+        check: RequestBudgetCheckEvent,
+    ) -> Option<RequestBudgetDecision> {
+        let (tx, rx) = oneshot::channel();
+        {
+            let mut state = crate::codex::lock_or_panic!(self.state);
+            state.pending_request_budget.insert(check.request_id.clone(), tx);
+        }
+        let event = self.make_event(sub_id, EventMsg::RequestBudgetCheck(check));
+        self.send_event(event).await;
+        rx.await.ok()
+    }
+
+    pub fn notify_request_budget_decision(&self, request_id: &str, decision: RequestBudgetDecision) {
+        let pending = {
+            let mut state = crate::codex::lock_or_panic!(self.state);
+            state.pending_request_budget.remove(request_id)
+        };
+        if let Some(tx) = pending {
+            let _ = tx.send(decision);
+        } else {
+            tracing::warn!("no pending request budget check found for request_id={request_id}");
+        }
+    }
+
     pub fn add_approved_command(&self, pattern: ApprovedCommandPattern) {
         let mut state = crate::codex::lock_or_panic!(self.state);
         state.approved_commands.insert(pattern);
     }
 
+    /// Drops approvals for `command` with `kind`; returns whether any existed.
+    pub fn remove_approved_command(
+        &self,
+        command: &[String],
+        kind: ApprovedCommandMatchKind,
+    ) -> bool {
+        let argv = crate::command_canonicalization::canonicalize_command_for_approval(command);
+        let mut state = crate::codex::lock_or_panic!(self.state);
+        let before = state.approved_commands.len();
+        state
+            .approved_commands
+            .retain(|pattern| !(pattern.argv() == argv.as_slice() && pattern.kind() == kind));
+        state.approved_commands.len() != before
+    }
+
     /// Records items to both the rollout and the chat completions/ZDR
     /// transcript, if enabled.
     pub(super) async fn record_conversation_items(&self, items: &[ResponseItem]) {
         debug!("Recording items for conversation: {items:?}");
-        self.record_state_snapshot(items).await;
+        let items = self.enforce_tool_output_budgets(items);
+        self.record_state_snapshot(&items).await;
 
-        crate::codex::lock_or_panic!(self.state).history.record_items(items);
+        crate::codex::lock_or_panic!(self.state)
+            .history
+            .record_items(items.iter());
 
     }
 
+    /// Byte budget for tool output of `category` kept in the history.
+    pub(super) fn tool_output_budget(
+        &self,
+        category: crate::config_types::ToolOutputCategory,
+    ) -> usize {
+        self.tool_output_limits
+            .budget(category, self.tool_output_max_bytes)
+    }
+
+    /// Caps tool outputs in `items` to their category budgets before they are
+    /// recorded, see [`crate::tool_output_budget`].
+    fn enforce_tool_output_budgets<'a>(
+        &self,
+        items: &'a [ResponseItem],
+    ) -> std::borrow::Cow<'a, [ResponseItem]> {
+        crate::tool_output_budget::enforce_budgets(
+            items,
+            &self.tool_output_limits,
+            self.tool_output_max_bytes,
+            |call_id| {
+                crate::codex::lock_or_panic!(self.state)
+                    .history
+                    .tool_call_name(call_id)
+            },
+            |name| self.mcp_connection_manager.parse_tool_name(name).is_some(),
+        )
+    }
+
     /// Clean up old screenshots and system status messages from conversation history
     /// This is called when a new user message arrives to keep history manageable
     pub(super) fn cleanup_old_status_items(&self) {
@@ -2581,7 +3193,9 @@ impl Session {
         state.pending_request_user_input.clear();
         state.pending_request_permissions.clear();
         state.pending_dynamic_tools.clear();
+        state.pending_request_budget.clear();
         state.granted_permissions_by_turn.clear();
+        state.turn_policy_override = None;
         // Do not clear `pending_input` here. When a user submits a new message
         // immediately after an interrupt, it may have been routed to
         // `pending_input` by an earlier code path. Clearing it would drop the
@@ -33,6 +33,7 @@ mod attempt_recovery;
 mod agent;
 mod env_context;
 mod mcp_convert;
+mod prompt_stack;
 mod submission;
 mod turn;
 #[cfg(test)]
@@ -47,6 +48,7 @@ use attempt_recovery::{
     inject_scratchpad_into_attempt_input,
     missing_tool_outputs_to_insert,
     reconcile_pending_tool_outputs,
+    salvage_partial_turn,
     should_inject_html_sanitizer_guardrails,
     should_inject_search_tool_developer_instructions,
 };
@@ -57,10 +59,7 @@ pub(super) use env_context::{
     parse_env_snapshot_from_response,
     process_rollout_env_item,
 };
-use mcp_convert::{
-    convert_mcp_resource_templates_by_server,
-    convert_mcp_resources_by_server,
-};
+use mcp_convert::mcp_list_tools_response;
 
 #[cfg(feature = "browser-automation")]
 pub(super) fn add_pending_screenshot(
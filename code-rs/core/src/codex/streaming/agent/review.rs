@@ -9,7 +9,7 @@ pub(super) async fn spawn_review_thread(
     sess: Arc<Session>,
     config: Arc<Config>,
     sub_id: String,
-    review_request: ReviewRequest,
+    mut review_request: ReviewRequest,
 ) {
     // Ensure any running task is stopped before starting the review flow.
     sess.notify_wait_interrupted(WaitInterruptReason::SessionAborted);
@@ -17,6 +17,31 @@ pub(super) async fn spawn_review_thread(
 
     let parent_turn_context = sess.make_turn_context();
 
+    // Resolve the reviewer profile; an unknown name falls back to the
+    // configured default rather than failing the review.
+    let profile = match crate::review_profiles::resolve(&config, review_request.profile.as_deref()) {
+        Ok(profile) => profile,
+        Err(name) => {
+            let event = sess.make_event(
+                &sub_id,
+                EventMsg::Warning(crate::protocol::WarningEvent {
+                    message: format!("Unknown review profile `{name}`; using the default reviewer."),
+                }),
+            );
+            sess.send_event(event).await;
+            crate::review_profiles::resolve(&config, None).unwrap_or_default()
+        }
+    };
+    review_request.profile = profile.map(|(name, _)| name.to_owned());
+    let review_guidelines = match profile {
+        Some((name, profile)) => format!(
+            "{}\n\n{}",
+            REVIEW_PROMPT.trim_end(),
+            crate::review_profiles::render_instructions(name, profile)
+        ),
+        None => REVIEW_PROMPT.to_owned(),
+    };
+
     // Determine model + family for review mode.
     let review_model = config.review_model.clone();
     let review_family = find_family_for_model(&review_model)
@@ -30,7 +55,7 @@ pub(super) async fn spawn_review_thread(
     review_config.model_reasoning_summary = ReasoningSummaryConfig::Detailed;
     review_config.model_text_verbosity = config.model_text_verbosity;
     review_config.user_instructions = None;
-    review_config.base_instructions = Some(REVIEW_PROMPT.to_owned());
+    review_config.base_instructions = Some(review_guidelines.clone());
     if let Some(cw) = review_family.context_window {
         review_config.model_context_window = Some(cw);
     }
@@ -75,7 +100,7 @@ pub(super) async fn spawn_review_thread(
     let review_turn_context = Arc::new(TurnContext {
         client: review_client,
         cwd: parent_turn_context.cwd.clone(),
-        base_instructions: Some(REVIEW_PROMPT.to_owned()),
+        base_instructions: Some(review_guidelines.clone()),
         user_instructions: None,
         demo_developer_message: review_demo_message,
         compact_prompt_override: parent_turn_context.compact_prompt_override.clone(),
@@ -85,12 +110,13 @@ pub(super) async fn spawn_review_thread(
         collaboration_mode: parent_turn_context.collaboration_mode,
         is_review_mode: true,
         text_format_override: None,
+        disabled_prompt_segments: Vec::new(),
         final_output_json_schema: None,
     });
 
     let review_prompt_text = format!(
         "{}\n\n---\n\nNow, here's your task: {}",
-        REVIEW_PROMPT.trim(),
+        review_guidelines.trim(),
         review_request.prompt.trim()
     );
     let review_input = vec![InputItem::Text {
@@ -113,7 +139,9 @@ pub(super) async fn exit_review_mode(
     task_sub_id: String,
     review_output: Option<ReviewOutputEvent>,
 ) {
-    let snapshot = capture_review_snapshot(&session).await;
+    let active_request = session.take_active_review();
+    let review_profile = active_request.and_then(|request| request.profile);
+    let snapshot = capture_review_snapshot(&session, review_profile).await;
     let event = session.make_event(
         &task_sub_id,
         EventMsg::ExitedReviewMode(ExitedReviewModeEvent {
@@ -123,8 +151,6 @@ pub(super) async fn exit_review_mode(
     );
     session.send_event(event).await;
 
-    let _active_request = session.take_active_review();
-
     let developer_text = match review_output.clone() {
         Some(output) => {
             let mut sections: Vec<String> = Vec::new();
@@ -175,12 +201,15 @@ pub(super) async fn exit_review_mode(
         .await;
 }
 
-async fn capture_review_snapshot(session: &Session) -> Option<ReviewSnapshotInfo> {
+async fn capture_review_snapshot(
+    session: &Session,
+    review_profile: Option<String>,
+) -> Option<ReviewSnapshotInfo> {
     let cwd = session.cwd.clone();
     let repo_root = crate::git_info::get_git_repo_root(&cwd);
     let branch = crate::git_info::current_branch_name(&cwd).await;
 
-    if repo_root.is_none() && branch.is_none() {
+    if repo_root.is_none() && branch.is_none() && review_profile.is_none() {
         return None;
     }
 
@@ -189,6 +218,7 @@ async fn capture_review_snapshot(session: &Session) -> Option<ReviewSnapshotInfo
         branch,
         worktree_path: Some(cwd),
         repo_root,
+        review_profile,
     })
 }
 
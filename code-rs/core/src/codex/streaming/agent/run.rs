@@ -51,6 +51,10 @@ pub(super) async fn run_agent(sess: Arc<Session>, turn_context: Arc<TurnContext>
         );
     }
 
+    if !pending_only_turn && !is_review_mode {
+        crate::codex::turn_baselines::capture_turn_baseline(&sess, &sub_id).await;
+    }
+
     let mut blocked_by_user_prompt_hook = false;
     let mut initial_response_item: Option<ResponseItem> = None;
 
@@ -119,6 +123,11 @@ pub(super) async fn run_agent(sess: Arc<Session>, turn_context: Arc<TurnContext>
     // Although from the perspective of codex.rs, TurnDiffTracker has the lifecycle of a Agent which contains
     // many turns, from the perspective of the user, it is a single turn.
     let mut turn_diff_tracker = TurnDiffTracker::new();
+    turn_diff_tracker.track_submodules(&turn_context.cwd);
+    let large_files = &turn_context.client.config().large_files;
+    if !large_files.include_in_diffs {
+        turn_diff_tracker.summarize_large_files(large_files.threshold_bytes());
+    }
 
     // Track if this is the first iteration - if so, include the initial input
     let mut first_iteration = true;
@@ -151,6 +160,12 @@ pub(super) async fn run_agent(sess: Arc<Session>, turn_context: Arc<TurnContext>
                 .map(ResponseItem::from)
                 .collect::<Vec<ResponseItem>>();
             if !queued_user_inputs.is_empty() {
+                let submission_ids = queued_user_inputs
+                    .iter()
+                    .map(|queued| queued.submission_id.clone())
+                    .collect();
+                sess.notify_queued_user_input_delivered(&sub_id, submission_ids)
+                    .await;
                 let mut queued_items = Vec::new();
                 for queued in queued_user_inputs {
                     let submission_id = queued.submission_id;
@@ -570,7 +585,7 @@ pub(super) async fn run_agent(sess: Arc<Session>, turn_context: Arc<TurnContext>
                 info!("Turn error: {e:#}");
                 let event = sess.make_event(
                     &sub_id,
-                    EventMsg::Error(ErrorEvent { message: e.to_string() }),
+                    EventMsg::Error(e.to_error_event(e.to_string())),
                 );
                 sess.tx_event.send(event).await.ok();
                 if is_review_mode && !review_exit_emitted {
@@ -599,6 +614,10 @@ pub(super) async fn run_agent(sess: Arc<Session>, turn_context: Arc<TurnContext>
 
     sess.revoke_deno_turn_permissions().await;
     sess.remove_task(&sub_id);
+    if let Some(message) = sess.take_network_egress_summary(&sub_id) {
+        let event = sess.make_event(&sub_id, EventMsg::BackgroundEvent(BackgroundEventEvent { message }));
+        sess.tx_event.send(event).await.ok();
+    }
     let event = sess.make_event(
         &sub_id,
         EventMsg::TaskComplete(TaskCompleteEvent {
@@ -630,6 +649,9 @@ pub(super) async fn run_agent(sess: Arc<Session>, turn_context: Arc<TurnContext>
             sess_clone.cleanup_old_status_items();
             let turn_context = sess_clone.make_turn_context();
             let submission_id = queued.submission_id;
+            sess_clone
+                .notify_queued_user_input_delivered(&submission_id, vec![submission_id.clone()])
+                .await;
             let items = queued.core_items;
             let agent = AgentTask::spawn(Arc::clone(&sess_clone), turn_context, submission_id, items, TaskOriginKind::QueuedUser, true);
             sess_clone.set_task(agent);
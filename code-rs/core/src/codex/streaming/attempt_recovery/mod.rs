@@ -1,6 +1,7 @@
 use super::*;
 
 mod instructions;
+mod salvage;
 mod scratchpad;
 mod tool_outputs;
 
@@ -10,6 +11,7 @@ pub(super) use instructions::{
     should_inject_html_sanitizer_guardrails,
     should_inject_search_tool_developer_instructions,
 };
+pub(super) use salvage::salvage_partial_turn;
 pub(super) use scratchpad::inject_scratchpad_into_attempt_input;
 pub(super) use tool_outputs::{missing_tool_outputs_to_insert, reconcile_pending_tool_outputs};
 
@@ -0,0 +1,183 @@
+use super::*;
+
+use crate::protocol::PartialToolCall;
+use crate::protocol::PartialTurnEvent;
+use code_protocol::models::ContentItem;
+
+/// Collects what a turn finished before failing for good, so the progress is
+/// kept instead of discarded. `dropped` holds what earlier attempts handed to
+/// their retries, `carried_prefix` the assistant text they streamed, and `sp`
+/// the last attempt's scratchpad.
+///
+/// Returns the history items to record (each completed tool call followed by
+/// its output, then the partial assistant message) and the event describing
+/// them, or `None` when nothing was finished.
+pub(in crate::codex::streaming) fn salvage_partial_turn(
+    dropped: &[ResponseItem],
+    carried_prefix: Option<String>,
+    sp: Option<TurnScratchpad>,
+    error: String,
+) -> Option<(Vec<ResponseItem>, PartialTurnEvent)> {
+    let mut calls: Vec<ResponseItem> = Vec::new();
+    let mut outputs: Vec<ResponseItem> = Vec::new();
+    let mut partial_message = carried_prefix.unwrap_or_default();
+
+    let mut sort = |item: ResponseItem| {
+        if tool_call_id(&item).is_some() {
+            calls.push(item);
+        } else if tool_output_call_id(&item).is_some() {
+            outputs.push(item);
+        }
+    };
+    dropped.iter().cloned().for_each(&mut sort);
+    if let Some(sp) = sp {
+        match sp.retry_prefix() {
+            Some(prefix) => partial_message.push_str(prefix),
+            None => partial_message.push_str(&sp.partial_assistant_text),
+        }
+        sp.items.into_iter().for_each(&mut sort);
+        sp.responses.into_iter().map(ResponseItem::from).for_each(&mut sort);
+    }
+
+    let mut items = Vec::new();
+    let mut tool_calls = Vec::new();
+    for call in calls {
+        let (Some(call_id), Some(name)) = (tool_call_id(&call), tool_call_name(&call)) else {
+            continue;
+        };
+        // Only calls that returned are completed, and each is kept once.
+        if tool_calls.iter().any(|seen: &PartialToolCall| seen.call_id == call_id) {
+            continue;
+        }
+        let call_outputs: Vec<ResponseItem> = outputs
+            .iter()
+            .filter(|output| tool_output_call_id(output) == Some(call_id))
+            .cloned()
+            .collect();
+        if call_outputs.is_empty() {
+            continue;
+        }
+        tool_calls.push(PartialToolCall {
+            call_id: call_id.to_owned(),
+            name: name.to_owned(),
+        });
+        items.push(call);
+        items.extend(call_outputs);
+    }
+
+    let partial_message = (!partial_message.trim().is_empty()).then_some(partial_message);
+    if let Some(text) = partial_message.as_ref() {
+        items.push(ResponseItem::Message {
+            id: None,
+            role: "assistant".to_owned(),
+            content: vec![ContentItem::OutputText { text: text.clone() }],
+            end_turn: None,
+            phase: None,
+        });
+    }
+    if items.is_empty() {
+        return None;
+    }
+    Some((
+        items,
+        PartialTurnEvent {
+            tool_calls,
+            partial_message,
+            error,
+        },
+    ))
+}
+
+fn tool_call_id(item: &ResponseItem) -> Option<&str> {
+    match item {
+        ResponseItem::FunctionCall { call_id, .. } | ResponseItem::CustomToolCall { call_id, .. } => {
+            Some(call_id)
+        }
+        ResponseItem::LocalShellCall { call_id, id, .. } => call_id.as_deref().or(id.as_deref()),
+        _ => None,
+    }
+}
+
+fn tool_call_name(item: &ResponseItem) -> Option<&str> {
+    match item {
+        ResponseItem::FunctionCall { name, .. } | ResponseItem::CustomToolCall { name, .. } => {
+            Some(name)
+        }
+        ResponseItem::LocalShellCall { .. } => Some("local_shell"),
+        _ => None,
+    }
+}
+
+fn tool_output_call_id(item: &ResponseItem) -> Option<&str> {
+    match item {
+        ResponseItem::FunctionCallOutput { call_id, .. }
+        | ResponseItem::CustomToolCallOutput { call_id, .. } => Some(call_id),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use code_protocol::models::FunctionCallOutputPayload;
+    use pretty_assertions::assert_eq;
+
+    fn function_call(call_id: &str) -> ResponseItem {
+        ResponseItem::FunctionCall {
+            id: None,
+            name: "shell".to_owned(),
+            namespace: None,
+            arguments: "{}".to_owned(),
+            call_id: call_id.to_owned(),
+        }
+    }
+
+    fn function_output(call_id: &str) -> ResponseItem {
+        ResponseItem::FunctionCallOutput {
+            call_id: call_id.to_owned(),
+            output: FunctionCallOutputPayload::from_text("ok".to_owned()),
+        }
+    }
+
+    #[test]
+    fn keeps_completed_calls_and_partial_text() {
+        // The first attempt's call came back through the retry input; the
+        // last attempt finished one call and started another.
+        let dropped = vec![function_call("c1"), function_output("c1")];
+        let sp = TurnScratchpad {
+            items: vec![function_call("c2"), function_call("c3")],
+            responses: vec![ResponseInputItem::FunctionCallOutput {
+                call_id: "c2".to_owned(),
+                output: FunctionCallOutputPayload::from_text("ok".to_owned()),
+            }],
+            partial_assistant_text: "so far".to_owned(),
+            assistant_prefix: "so far".to_owned(),
+            ..TurnScratchpad::default()
+        };
+
+        let (items, event) = salvage_partial_turn(
+            &dropped,
+            Some("Checked the logs, ".to_owned()),
+            Some(sp),
+            "stream disconnected".to_owned(),
+        )
+        .expect("progress to salvage");
+
+        assert_eq!(
+            event.tool_calls.iter().map(|call| call.call_id.as_str()).collect::<Vec<_>>(),
+            vec!["c1", "c2"]
+        );
+        assert_eq!(event.partial_message.as_deref(), Some("Checked the logs, so far"));
+        assert_eq!(items.len(), 5);
+        assert!(matches!(items.last(), Some(ResponseItem::Message { role, .. }) if role == "assistant"));
+    }
+
+    #[test]
+    fn nothing_to_salvage() {
+        let sp = TurnScratchpad {
+            items: vec![function_call("c1")],
+            ..TurnScratchpad::default()
+        };
+        assert_eq!(salvage_partial_turn(&[], None, Some(sp), "boom".to_owned()), None);
+    }
+}
@@ -41,12 +41,31 @@ pub(in crate::codex::streaming) fn inject_scratchpad_into_attempt_input(
         attempt_input.push(ResponseItem::from(resp));
     }
 
+    use code_protocol::models::ContentItem;
+
+    // Hand the already-delivered assistant text back as a prefix so the retry
+    // continues it instead of regenerating (and re-billing) it.
+    let retry_prefix = sp.retry_prefix().map(str::to_owned);
+    if let Some(prefix) = retry_prefix.as_ref() {
+        attempt_input.push(ResponseItem::Message {
+            id: None,
+            role: "assistant".to_owned(),
+            content: vec![ContentItem::OutputText { text: prefix.clone() }],
+            end_turn: None,
+            phase: None,
+        });
+    }
+
     // If we have partial deltas, include a short ephemeral hint so the model can resume.
     if !sp.partial_assistant_text.is_empty() || !sp.partial_reasoning_summary.is_empty() {
-        use code_protocol::models::ContentItem;
         let mut hint = String::from(
             "[EPHEMERAL:RETRY_HINT]\nPrevious attempt aborted mid-stream. Continue without repeating.\n",
         );
+        if retry_prefix.is_some() {
+            hint.push_str(
+                "The assistant message above was already shown to the user; continue it from exactly where it stops.\n",
+            );
+        }
         if !sp.partial_reasoning_summary.is_empty() {
             let s = &sp.partial_reasoning_summary;
             let start_idx = if s.chars().count() > crate::util::MAX_SNIPPET_CHARS {
@@ -60,7 +79,7 @@ pub(in crate::codex::streaming) fn inject_scratchpad_into_attempt_input(
             let tail = &s[start_idx..];
             let _ = write!(hint, "Last reasoning summary fragment:\n{tail}\n\n");
         }
-        if !sp.partial_assistant_text.is_empty() {
+        if retry_prefix.is_none() && !sp.partial_assistant_text.is_empty() {
             let s = &sp.partial_assistant_text;
             let start_idx = if s.chars().count() > crate::util::MAX_SNIPPET_CHARS {
                 s.char_indices()
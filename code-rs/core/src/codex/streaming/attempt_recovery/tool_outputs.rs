@@ -194,6 +194,10 @@ mod tool_call_id_tests {
             }],
             partial_assistant_text: String::new(),
             partial_reasoning_summary: String::new(),
+            assistant_prefix: String::new(),
+            assistant_prefix_overflowed: false,
+            streamed_assistant_bytes: 0,
+            streamed_reasoning_bytes: 0,
         };
 
         let mut attempt_input: Vec<ResponseItem> = Vec::new();
@@ -214,6 +218,36 @@ mod tool_call_id_tests {
         assert!(call_pos < output_pos, "tool call should precede output");
     }
 
+    #[test]
+    fn retry_scratchpad_replays_partial_assistant_text_as_prefix() {
+        use code_protocol::models::ContentItem;
+
+        let sp = TurnScratchpad {
+            partial_assistant_text: "Here is the pl".to_string(),
+            assistant_prefix: "Here is the pl".to_string(),
+            streamed_assistant_bytes: 14,
+            ..TurnScratchpad::default()
+        };
+
+        let mut attempt_input: Vec<ResponseItem> = Vec::new();
+        inject_scratchpad_into_attempt_input(&mut attempt_input, sp);
+
+        assert!(matches!(
+            attempt_input.first(),
+            Some(ResponseItem::Message { role, content, .. })
+                if role == "assistant"
+                    && matches!(content.first(), Some(ContentItem::OutputText { text }) if text == "Here is the pl")
+        ));
+        let Some(ResponseItem::Message { role, content, .. }) = attempt_input.get(1) else {
+            panic!("expected retry hint after prefix");
+        };
+        assert_eq!(role, "user");
+        let Some(ContentItem::InputText { text }) = content.first() else {
+            panic!("expected text hint");
+        };
+        assert!(!text.contains("Last assistant text fragment"));
+    }
+
     #[test]
     fn missing_tool_outputs_inserts_function_call_output_for_function_call() {
         let items = vec![ResponseItem::FunctionCall {
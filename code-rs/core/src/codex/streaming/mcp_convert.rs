@@ -2,6 +2,42 @@ use super::*;
 
 use std::collections::HashMap;
 
+/// The `McpListToolsResponse` payload for the session's current servers.
+pub(super) async fn mcp_list_tools_response(sess: &Session) -> McpListToolsResponseEvent {
+    let manager = &sess.mcp_connection_manager;
+    let tools = manager
+        .list_all_tools()
+        .into_iter()
+        .filter_map(|(name, tool)| {
+            let value = match serde_json::to_value(tool) {
+                Ok(value) => value,
+                Err(err) => {
+                    warn!("failed to serialize MCP tool {name}: {err}");
+                    return None;
+                }
+            };
+            match code_protocol::mcp::Tool::from_mcp_value(value) {
+                Ok(converted) => Some((name, converted)),
+                Err(err) => {
+                    warn!("failed to convert MCP tool {name}: {err}");
+                    None
+                }
+            }
+        })
+        .collect();
+    McpListToolsResponseEvent {
+        tools,
+        server_tools: Some(manager.list_tools_by_server()),
+        server_disabled_tools: Some(manager.list_disabled_tools_by_server()),
+        server_failures: Some(manager.list_server_failures()),
+        resources: convert_mcp_resources_by_server(manager.list_resources_by_server().await),
+        resource_templates: convert_mcp_resource_templates_by_server(
+            manager.list_resource_templates_by_server().await,
+        ),
+        auth_statuses: manager.list_auth_statuses().await,
+    }
+}
+
 pub(super) fn convert_mcp_resources_by_server(
     resources_by_server: HashMap<String, Vec<mcp_types::Resource>>,
 ) -> HashMap<String, Vec<code_protocol::mcp::Resource>> {
@@ -0,0 +1,212 @@
+//! The prompt stack sent ahead of the conversation: base instructions, the
+//! default developer prompt, prepended developer messages, environment
+//! context and user instructions.
+//!
+//! `run_turn` assembles it for every request and `Op::PreviewPrompt` reports
+//! it to the `/prompt` overlay. Segment ids let a frontend leave parts out of
+//! the next turn with `Op::SetDisabledPromptSegments`.
+
+use super::*;
+use crate::protocol::CollaborationModeKind;
+use crate::protocol::PromptSegment;
+
+pub(super) const DEVELOPER_PROMPT_SEGMENT: &str = "developer_prompt";
+pub(super) const ENVIRONMENT_CONTEXT_SEGMENT: &str = "environment_context";
+pub(super) const USER_INSTRUCTIONS_SEGMENT: &str = "user_instructions";
+pub(super) const MEMORIES_SEGMENT: &str = "developer:memories";
+const PROJECT_MEMORY_SEGMENT: &str = "developer:project_memory";
+const INSTRUCTIONS_SEGMENT: &str = "instructions";
+
+/// A developer message prepended to every request of a turn.
+pub(super) struct PrependedMessage {
+    pub(super) id: String,
+    pub(super) label: String,
+    pub(super) text: String,
+}
+
+/// Builds the developer messages that follow the default developer prompt.
+/// Previews pass `record_memory_usage = false` so that looking at the stack
+/// does not count as a use of the memories summary.
+pub(super) async fn prepended_developer_messages(
+    sess: &Session,
+    demo_developer_message: Option<&str>,
+    collaboration_mode: CollaborationModeKind,
+    cwd: &Path,
+    record_memory_usage: bool,
+) -> Vec<PrependedMessage> {
+    let mut messages = Vec::new();
+    let mut push = |id: String, label: String, text: &str| {
+        let text = text.trim();
+        if !text.is_empty() {
+            messages.push(PrependedMessage {
+                id,
+                label,
+                text: text.to_owned(),
+            });
+        }
+    };
+
+    if let Some(demo) = demo_developer_message {
+        push("developer:demo".to_owned(), "Demo message".to_owned(), demo);
+    }
+    push(
+        "developer:collaboration_mode".to_owned(),
+        "Collaboration mode".to_owned(),
+        &render_collaboration_mode_instructions(collaboration_mode),
+    );
+    if let Some(shell_style) = sess.user_shell.script_style() {
+        push(
+            "developer:shell_style".to_owned(),
+            "Shell style".to_owned(),
+            shell_style.developer_instruction(),
+        );
+    }
+    for (idx, message) in sess.shell_style_profile_messages.iter().enumerate() {
+        push(
+            format!("developer:shell_profile:{idx}"),
+            format!("Shell profile #{}", idx + 1),
+            message,
+        );
+    }
+    if sess.memories_config.use_memories {
+        let memory_context = {
+            let state = crate::codex::lock_or_panic!(sess.state);
+            crate::memories::current_context_from_runtime(
+                state.last_environment_snapshot.as_ref(),
+                sess.user_shell(),
+                cwd,
+            )
+        };
+        if let Some(memory_prompt) = crate::memories::build_memory_tool_developer_instructions(
+            sess.client.code_home(),
+            &memory_context,
+        )
+        .await
+        {
+            if record_memory_usage {
+                match crate::memories::record_memory_prompt_usage(
+                    sess.client.code_home(),
+                    &memory_prompt,
+                )
+                .await
+                {
+                    Ok(false) => {
+                        tracing::debug!(
+                            "memories prompt used canonical summary fallback; skipping epoch usage attribution"
+                        );
+                    }
+                    Ok(true) => {}
+                    Err(err) => {
+                        tracing::warn!("failed to record memories usage: {err}");
+                    }
+                }
+            }
+            push(
+                MEMORIES_SEGMENT.to_owned(),
+                "Memories".to_owned(),
+                &memory_prompt.instructions,
+            );
+        }
+    }
+    if let Some(notes) = sess.project_memory_context.as_deref() {
+        push(
+            PROJECT_MEMORY_SEGMENT.to_owned(),
+            "Project memory".to_owned(),
+            notes,
+        );
+    }
+    messages
+}
+
+/// The stack the next turn would send, with the segments in `disabled`
+/// marked as such. Per-request additions that depend on the turn's input
+/// or tools (HTML sanitizer guardrails, tool search instructions) are not
+/// included.
+pub(super) async fn preview_prompt_stack(sess: &Session, disabled: &[String]) -> Vec<PromptSegment> {
+    let prompt = Prompt {
+        user_instructions: sess.user_instructions.clone(),
+        environment_context: Some(EnvironmentContext::new(
+            Some(sess.cwd.clone()),
+            Some(sess.approval_policy),
+            Some(sess.sandbox_policy.clone()),
+            Some(sess.user_shell.clone()),
+        )),
+        base_instructions_override: sess.base_instructions.clone(),
+        model_descriptions: sess.model_descriptions.clone(),
+        ..Prompt::default()
+    };
+    let mut segments = vec![segment(
+        INSTRUCTIONS_SEGMENT,
+        "Base instructions",
+        "system",
+        &prompt.get_full_instructions(sess.client.default_model_family()),
+        false,
+        disabled,
+    )];
+    segments.push(segment(
+        DEVELOPER_PROMPT_SEGMENT,
+        "Developer prompt",
+        "developer",
+        &prompt.additional_instructions(),
+        true,
+        disabled,
+    ));
+    for message in prepended_developer_messages(
+        sess,
+        sess.demo_developer_message.as_deref(),
+        sess.collaboration_mode,
+        &sess.cwd,
+        false,
+    )
+    .await
+    {
+        segments.push(segment(&message.id, &message.label, "developer", &message.text, true, disabled));
+    }
+    if let Some(text) = prompt.get_formatted_environment_context() {
+        segments.push(segment(
+            ENVIRONMENT_CONTEXT_SEGMENT,
+            "Environment context",
+            "user",
+            &text,
+            true,
+            disabled,
+        ));
+    }
+    if let Some(ResponseItem::Message { content, .. }) = prompt.get_formatted_user_instructions() {
+        let text = content
+            .iter()
+            .filter_map(|item| match item {
+                ContentItem::InputText { text } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect::<String>();
+        segments.push(segment(
+            USER_INSTRUCTIONS_SEGMENT,
+            "User instructions",
+            "user",
+            &text,
+            true,
+            disabled,
+        ));
+    }
+    segments
+}
+
+fn segment(
+    id: &str,
+    label: &str,
+    role: &str,
+    text: &str,
+    optional: bool,
+    disabled: &[String],
+) -> PromptSegment {
+    PromptSegment {
+        id: id.to_owned(),
+        label: label.to_owned(),
+        role: role.to_owned(),
+        text: text.to_owned(),
+        tokens: text.len().div_ceil(4) as u64,
+        optional,
+        enabled: !optional || !disabled.iter().any(|disabled| disabled == id),
+    }
+}
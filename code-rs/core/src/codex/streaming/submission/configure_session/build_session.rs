@@ -108,64 +108,11 @@ impl Runner<'_> {
             manager
         };
 
-        // Probe REPL runtime health early so the client (and every per-turn
-        // tool rebuild) already knows which runtimes are available.
-        if config.tools_repl {
-            let mut available_runtimes = Vec::new();
-            for &kind in crate::config::ReplRuntimeKindToml::ALL {
-                if !config.is_repl_runtime_enabled(kind) {
-                    tracing::debug!(
-                        runtime = %kind,
-                        "repl runtime disabled by user — skipping"
-                    );
-                    continue;
-                }
-                let probe_handle = crate::tools::repl::ReplHandle::new(
-                    config.repl_runtime_config(kind),
-                );
-                match probe_handle.probe_health().await {
-                    Ok(version) => {
-                        tracing::info!(
-                            runtime = %kind,
-                            version = %version,
-                            "repl runtime available"
-                        );
-                        available_runtimes.push(kind);
-                    }
-                    Err(err) => {
-                        tracing::debug!(
-                            runtime = %kind,
-                            error = %err,
-                            "repl runtime not found — skipping"
-                        );
-                    }
-                }
-            }
-            if available_runtimes.is_empty() {
-                tracing::warn!("no repl runtimes available — disabling tool");
-                Arc::make_mut(&mut config).tools_repl = false;
-            }
-            Arc::make_mut(&mut config).repl_available_runtimes = available_runtimes;
-        } else {
-            // REPL disabled — ensure stale runtimes are cleared.
-            Arc::make_mut(&mut config).repl_available_runtimes = Vec::new();
-        }
-
-        // Wrap provided auth (if any) in a minimal AuthManager for client usage.
-        let client = ModelClient::new(
-            config.clone(),
-            self.auth_manager.clone(),
-            Some(otel_event_manager.clone()),
-            provider.clone(),
-            model_reasoning_effort,
-            model_reasoning_summary,
-            model_text_verbosity,
-            self.session_id,
-            debug_logger,
-        );
-
         // abort any current running session and clone its state
         let old_session = self.sess.take();
+        let cwd_changed = old_session
+            .as_ref()
+            .is_some_and(|sess_arc| sess_arc.cwd != cwd);
         let (mcp_allow_servers, mcp_deny_servers) = old_session
             .as_ref()
             .map(|sess_arc| sess_arc.session_mcp_overrides_snapshot())
@@ -216,16 +163,55 @@ impl Runner<'_> {
             drop(old_session_arc);
         }
 
-        let (mcp_connection_manager, failed_clients) = match McpConnectionManager::new(
-            config.code_home.clone(),
-            config.mcp_oauth_credentials_store_mode,
-            effective_mcp_servers,
-            excluded_tools,
-            self.tx_event.clone(),
-            approval_policy,
-        )
-        .await
-        {
+        // Before spawning any helpers, start tracking them and reap the ones a
+        // crashed session left behind.
+        crate::child_registry::init(&config.code_home);
+
+        // The slow startup subsystems do not depend on each other: launch MCP
+        // servers, probe REPL runtimes, open the memories store and discover
+        // hook config layers concurrently rather than one after another.
+        let lifecycle_hooks_enabled = config.lifecycle_hooks.enabled != Some(false);
+        let (
+            available_repl_runtimes,
+            mcp_manager_result,
+            (),
+            config_layer_folders_low_to_high,
+        ) = tokio::join!(
+            crate::startup_profile::measure("repl runtime probes", probe_repl_runtimes(&config)),
+            crate::startup_profile::measure(
+                "mcp servers",
+                McpConnectionManager::new(
+                    config.code_home.clone(),
+                    config.mcp_oauth_credentials_store_mode,
+                    effective_mcp_servers,
+                    excluded_tools,
+                    self.tx_event.clone(),
+                    approval_policy,
+                    config.spawned_env.clone(),
+                ),
+            ),
+            crate::startup_profile::measure("memories state", open_memories_state(&config)),
+            crate::startup_profile::measure(
+                "hook config layers",
+                hook_config_layer_folders(&config, &cwd, lifecycle_hooks_enabled),
+            ),
+        );
+
+        match available_repl_runtimes {
+            Some(available_runtimes) => {
+                if available_runtimes.is_empty() {
+                    tracing::warn!("no repl runtimes available — disabling tool");
+                    Arc::make_mut(&mut config).tools_repl = false;
+                }
+                Arc::make_mut(&mut config).repl_available_runtimes = available_runtimes;
+            }
+            None => {
+                // REPL disabled — ensure stale runtimes are cleared.
+                Arc::make_mut(&mut config).repl_available_runtimes = Vec::new();
+            }
+        }
+
+        let (mcp_connection_manager, failed_clients) = match mcp_manager_result {
             Ok((mgr, failures)) => (mgr, failures),
             Err(e) => {
                 let message = format!("Failed to create MCP connection manager: {e:#}");
@@ -235,6 +221,19 @@ impl Runner<'_> {
             }
         };
 
+        // Wrap provided auth (if any) in a minimal AuthManager for client usage.
+        let client = ModelClient::new(
+            config.clone(),
+            self.auth_manager.clone(),
+            Some(otel_event_manager.clone()),
+            provider.clone(),
+            model_reasoning_effort,
+            model_reasoning_summary,
+            model_text_verbosity,
+            self.session_id,
+            debug_logger,
+        );
+
         // Surface individual client start-up failures to the user.
         if !failed_clients.is_empty() {
             for (server_name, failure) in failed_clients {
@@ -246,6 +245,9 @@ impl Runner<'_> {
                     crate::protocol::McpServerFailurePhase::ListTools => {
                         format!("MCP server `{server_name}` failed to list tools: {detail}")
                     }
+                    crate::protocol::McpServerFailurePhase::CircuitOpen => {
+                        format!("MCP server `{server_name}` is not accepting calls: {detail}")
+                    }
                 };
                 error!("{message}");
                 mcp_connection_errors.push(message);
@@ -264,7 +266,13 @@ impl Runner<'_> {
         });
         tools_config.web_search_allowed_domains = config.tools_web_search_allowed_domains.clone();
         tools_config.web_search_external = config.tools_web_search_external;
+        tools_config.web_search_provider = config.web_search_provider.clone();
         tools_config.search_tool = config.tools_search_tool;
+        tools_config.mcp_budget = config.tools_mcp_budget.clone();
+        tools_config.docs_bundles = config.docs_bundles.clone();
+        tools_config.project_memory = config.tools_project_memory;
+        tools_config.capabilities = config.tools_capabilities;
+        tools_config.scratch = config.tools_scratch;
         tools_config.repl = config.tools_repl;
         tools_config.repl_available_runtimes = config.repl_available_runtimes.clone();
 
@@ -307,6 +315,17 @@ impl Runner<'_> {
         tools_config.set_agent_models(agent_models);
 
         let model_descriptions = model_guide_markdown_with_custom(&config.agents);
+        let project_memory_context = if config.tools_project_memory {
+            match crate::project_memory::load_notes(&cwd) {
+                Ok(notes) => crate::project_memory::render_session_context(&notes),
+                Err(err) => {
+                    warn!("failed to load project memory: {err}");
+                    None
+                }
+            }
+        } else {
+            None
+        };
         let remote_models_manager = self.auth_manager.as_ref().map(|mgr| {
             Arc::new(RemoteModelsManager::new(
                 Arc::clone(mgr),
@@ -337,12 +356,32 @@ impl Runner<'_> {
             )
         });
 
+        #[cfg(feature = "managed-network-proxy")]
+        let network_egress = config
+            .network_proxy
+            .as_ref()
+            .filter(|_| config.network.as_ref().is_none_or(|network| network.log_egress))
+            .and_then(|_| crate::config::log_dir(&config).ok())
+            .map(|log_dir| Arc::new(crate::network_egress::NetworkEgressRecorder::new(log_dir)));
+
         let network_proxy = {
             #[cfg(feature = "managed-network-proxy")]
             {
                 if let Some(spec) = config.network_proxy.as_ref() {
+                    let allowed_request_observer = network_egress.as_ref().map(|recorder| {
+                        crate::network_egress::build_allowed_request_observer(
+                            Arc::clone(recorder),
+                            Arc::clone(&network_approval),
+                        )
+                    });
                     match spec
-                        .start_proxy(&sandbox_policy, network_policy_decider, None, true)
+                        .start_proxy(
+                            &sandbox_policy,
+                            network_policy_decider,
+                            None,
+                            allowed_request_observer,
+                            true,
+                        )
                         .await
                     {
                         Ok(proxy) => Some(proxy),
@@ -365,46 +404,6 @@ impl Runner<'_> {
 
         let repl_default_runtime = config.repl_default_runtime;
 
-        if let Err(err) = crate::memories::open_memories_state(config.code_home.as_path()).await {
-            warn!("failed to initialize memories sqlite state: {err}");
-        }
-
-        let lifecycle_hooks_enabled = config.lifecycle_hooks.enabled != Some(false);
-        let config_layer_folders_low_to_high = if lifecycle_hooks_enabled {
-            match crate::config_loader::load_config_layers_state_with_cwd(
-                config.code_home.as_path(),
-                Some(cwd.as_path()),
-                &[],
-                crate::config_loader::LoaderOverrides::default(),
-            )
-            .await
-            {
-                Ok(stack) => stack
-                    .layers_low_to_high()
-                    .filter(|layer| layer.disabled_reason.is_none())
-                    .filter_map(|layer| match &layer.name {
-                        ConfigLayerSource::System { file }
-                        | ConfigLayerSource::User { file } => {
-                            file.as_path().parent().map(std::path::Path::to_path_buf)
-                        }
-                        ConfigLayerSource::Project { dot_codex_folder } => {
-                            Some(dot_codex_folder.as_path().to_path_buf())
-                        }
-                        ConfigLayerSource::Mdm { .. }
-                        | ConfigLayerSource::SessionFlags
-                        | ConfigLayerSource::LegacyManagedConfigTomlFromFile { .. }
-                        | ConfigLayerSource::LegacyManagedConfigTomlFromMdm => None,
-                    })
-                    .collect(),
-                Err(err) => {
-                    warn!("failed to load config layers for hooks.json discovery: {err}");
-                    Vec::new()
-                }
-            }
-        } else {
-            Vec::new()
-        };
-
         let (hooks_shell_program, hooks_shell_args) = match config
             .lifecycle_hooks
             .shell_program
@@ -447,6 +446,8 @@ impl Runner<'_> {
             shell_args: hooks_shell_args,
         });
 
+        let git_head = crate::git_info::head_state(&cwd).await;
+
         let mut new_session = Arc::new(Session {
             id: self.session_id,
             client,
@@ -475,6 +476,8 @@ impl Runner<'_> {
             },
             network_proxy,
             network_approval: Arc::clone(&network_approval),
+            #[cfg(feature = "managed-network-proxy")]
+            network_egress,
             tx_event: self.tx_event.clone(),
             user_instructions: effective_user_instructions,
             base_instructions,
@@ -484,6 +487,7 @@ impl Runner<'_> {
             approval_policy,
             sandbox_policy,
             shell_environment_policy: config.shell_environment_policy.clone(),
+            spawned_env: config.spawned_env.clone(),
             collaboration_mode,
             cwd,
             mcp_connection_manager,
@@ -497,6 +501,10 @@ impl Runner<'_> {
             code_linux_sandbox_exe: config.code_linux_sandbox_exe.clone(),
             disable_response_storage,
             user_shell: resolved_shell,
+            shell_snapshot: crate::shell_snapshot::ShellSnapshotCache::new(
+                &config.code_home,
+                self.session_id,
+            ),
             dangerous_command_detection_enabled: command_safety_profile
                 .dangerous_command_detection_enabled,
             safe_command_rules: command_safety_profile.safe_rules,
@@ -506,6 +514,8 @@ impl Runner<'_> {
             last_system_status: Mutex::new(None),
             #[cfg(feature = "browser-automation")]
             last_screenshot_info: Mutex::new(None),
+            #[cfg(feature = "browser-automation")]
+            browser_tool_last_request: AtomicU64::new(0),
             time_budget: Mutex::new(config.max_run_seconds.map(|secs| {
                 let total = Duration::from_secs(secs);
                 let deadline = config
@@ -514,19 +524,28 @@ impl Runner<'_> {
                 RunTimeBudget::new(deadline, total)
             })),
             confirm_guard: ConfirmGuardRuntime::from_config(&config.confirm_guard),
+            git_auto_stash: config.git_safety.auto_stash,
+            git_head: Mutex::new(git_head),
+            git_head_reconfirmation: Mutex::new(None),
             project_hooks: config.project_hooks.clone(),
             project_commands: config.project_commands.clone(),
             lifecycle_hooks,
             tool_output_max_bytes: config.tool_output_max_bytes,
+            tool_output_limits: config.tool_output_limits,
             hook_guard: AtomicBool::new(false),
             github: Arc::new(RwLock::new(config.github.clone())),
             validation: Arc::new(RwLock::new(config.validation.clone())),
             self_handle: Weak::new(),
             active_review: Mutex::new(None),
             next_turn_text_format: Mutex::new(None),
+            next_turn_overrides: Mutex::new(None),
+            next_turn_disabled_prompt_segments: Mutex::new(Vec::new()),
+            request_budget_negotiation: config.request_budget_negotiation,
+            heartbeat_interval: config.heartbeat_interval,
             env_ctx_v2: config.env_ctx_v2,
             retention_config: config.retention.clone(),
             model_descriptions,
+            project_memory_context,
             mcp_access: std::sync::RwLock::new(crate::codex::session::McpAccessState {
                 style: active_shell_style,
                 style_label: active_shell_style_label,
@@ -553,14 +572,18 @@ impl Runner<'_> {
             }
         }
         if let Some(sess_arc) = self.sess.as_ref() {
+            if let Some(interval) = sess_arc.heartbeat_interval {
+                crate::codex::heartbeat::spawn_heartbeat_ticker(Arc::downgrade(sess_arc), interval);
+            }
             if config.memories.generate_memories {
                 crate::memories::maybe_spawn_memory_refresh(
                     config.code_home.clone(),
                     config.memories.clone(),
                 );
             }
-            // Reset environment context tracker if shell changed
-            if shell_override_present {
+            // Reset environment context tracker if the shell or working
+            // directory changed so the next turn sends a full snapshot.
+            if shell_override_present || cwd_changed {
                 let mut st = crate::codex::lock_or_panic!(sess_arc.state);
                 st.environment_context_tracker = crate::environment_context::EnvironmentContextTracker::new();
             }
@@ -609,3 +632,101 @@ impl Runner<'_> {
         }
     }
 }
+
+/// Probes every enabled REPL runtime concurrently so the client (and every
+/// per-turn tool rebuild) already knows which runtimes are available.
+/// Returns `None` when the REPL tool is disabled.
+async fn probe_repl_runtimes(config: &Config) -> Option<Vec<crate::config::ReplRuntimeKindToml>> {
+    if !config.tools_repl {
+        return None;
+    }
+    let probes = crate::config::ReplRuntimeKindToml::ALL
+        .iter()
+        .copied()
+        .filter(|&kind| {
+            let enabled = config.is_repl_runtime_enabled(kind);
+            if !enabled {
+                tracing::debug!(
+                    runtime = %kind,
+                    "repl runtime disabled by user — skipping"
+                );
+            }
+            enabled
+        })
+        .map(|kind| async move {
+            let probe_handle = crate::tools::repl::ReplHandle::new(config.repl_runtime_config(kind));
+            match probe_handle.probe_health().await {
+                Ok(version) => {
+                    tracing::info!(
+                        runtime = %kind,
+                        version = %version,
+                        "repl runtime available"
+                    );
+                    Some(kind)
+                }
+                Err(err) => {
+                    tracing::debug!(
+                        runtime = %kind,
+                        error = %err,
+                        "repl runtime not found — skipping"
+                    );
+                    None
+                }
+            }
+        });
+    Some(
+        futures::future::join_all(probes)
+            .await
+            .into_iter()
+            .flatten()
+            .collect(),
+    )
+}
+
+async fn open_memories_state(config: &Config) {
+    if let Err(err) = crate::memories::open_memories_state(config.code_home.as_path()).await {
+        warn!("failed to initialize memories sqlite state: {err}");
+    }
+}
+
+/// Folders whose `hooks.json` participates in lifecycle hooks, lowest
+/// precedence first.
+async fn hook_config_layer_folders(
+    config: &Config,
+    cwd: &std::path::Path,
+    lifecycle_hooks_enabled: bool,
+) -> Vec<PathBuf> {
+    if !lifecycle_hooks_enabled {
+        return Vec::new();
+    }
+    match crate::config_loader::load_config_layers_state_with_cwd(
+        config.code_home.as_path(),
+        Some(cwd),
+        &[],
+        crate::config_loader::LoaderOverrides::default(),
+    )
+    .await
+    {
+        Ok(stack) => stack
+            .layers_low_to_high()
+            .filter(|layer| layer.disabled_reason.is_none())
+            .filter_map(|layer| match &layer.name {
+                ConfigLayerSource::System { file }
+                | ConfigLayerSource::User { file } => {
+                    file.as_path().parent().map(std::path::Path::to_path_buf)
+                }
+                ConfigLayerSource::Project { dot_codex_folder } => {
+                    Some(dot_codex_folder.as_path().to_path_buf())
+                }
+                ConfigLayerSource::Mdm { .. }
+                | ConfigLayerSource::SessionFlags
+                | ConfigLayerSource::LegacyManagedConfigTomlFromFile { .. }
+                | ConfigLayerSource::LegacyManagedConfigTomlFromMdm => None,
+            })
+            .collect(),
+        Err(err) => {
+            warn!("failed to load config layers for hooks.json discovery: {err}");
+            Vec::new()
+        }
+    }
+}
@@ -34,7 +34,10 @@ impl Runner<'_> {
             }),
         ))
         .chain(mcp_connection_errors.into_iter().map(|message| {
-            sess_arc.make_event(&submission_id, EventMsg::Error(ErrorEvent { message }))
+            sess_arc.make_event(
+                &submission_id,
+                EventMsg::Error(ErrorEvent::new(message, ErrorCode::McpServerFailed)),
+            )
         }));
 
         for event in events {
@@ -42,6 +45,7 @@ impl Runner<'_> {
                 error!("failed to send event: {e:?}");
             }
         }
+        super::super::mcp_device_login::spawn_device_logins(sess_arc, &submission_id);
 
         let replay_history_items_present = replay_history_items.is_some();
 
@@ -109,11 +113,14 @@ impl Runner<'_> {
             permission_mode: crate::codex::hook_runtime::hook_permission_mode(sess_arc.approval_policy),
             source: session_start_source,
         };
-        let outcome = crate::codex::hook_runtime::run_session_start_hooks(
-            sess_arc,
-            submission_id.as_str(),
-            &session_start_request,
-            Some(submission_id.clone()),
+        let outcome = crate::startup_profile::measure(
+            "session start hooks",
+            crate::codex::hook_runtime::run_session_start_hooks(
+                sess_arc,
+                submission_id.as_str(),
+                &session_start_request,
+                Some(submission_id.clone()),
+            ),
         )
         .await;
         crate::codex::hook_runtime::record_additional_contexts(sess_arc, outcome.additional_contexts)
@@ -122,10 +129,32 @@ impl Runner<'_> {
         sess_arc.run_session_hooks(ProjectHookEvent::SessionStart).await;
 
         // Initialize agent manager after SessionConfigured is sent
-        if self.agent_manager_initialized {
+        if !self.agent_manager_initialized {
+            let started = std::time::Instant::now();
+            self.init_agent_manager(&submission_id).await;
+            crate::startup_profile::record_since("agent manager", started);
+        }
+
+        self.emit_startup_profile(&submission_id).await;
+    }
+
+    async fn emit_startup_profile(&self, submission_id: &str) {
+        let Some(report) = crate::startup_profile::take_report() else {
+            return;
+        };
+        let Some(sess_arc) = self.sess.as_ref() else {
             return;
+        };
+        let event = sess_arc.make_event(
+            submission_id,
+            EventMsg::BackgroundEvent(BackgroundEventEvent { message: report }),
+        );
+        if let Err(e) = self.tx_event.send(event).await {
+            warn!("failed to send startup profile event: {e}");
         }
+    }
 
+    async fn init_agent_manager(&mut self, submission_id: &str) {
         let mut manager = AGENT_MANAGER.write().await;
         let (agent_tx, mut agent_rx) =
             tokio::sync::mpsc::unbounded_channel::<AgentStatusUpdatePayload>();
@@ -133,7 +162,7 @@ impl Runner<'_> {
         drop(manager);
 
         let Some(sess_for_agents) = self.sess.clone() else {
-            self.send_no_session_event(&submission_id).await;
+            self.send_no_session_event(submission_id).await;
             return;
         };
 
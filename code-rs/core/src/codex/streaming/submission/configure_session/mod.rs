@@ -74,6 +74,7 @@ pub(super) async fn handle_configure_session(
         dynamic_tools,
         shell: shell_override,
         shell_style_profiles,
+        shell_env_set,
         network,
         tools_repl,
         repl_default_runtime,
@@ -107,6 +108,7 @@ pub(super) async fn handle_configure_session(
         dynamic_tools,
         shell_override,
         shell_style_profiles,
+        shell_env_set,
         network,
         tools_repl,
         repl_default_runtime,
@@ -155,6 +157,7 @@ struct ConfigureSessionRequest {
     dynamic_tools: Vec<DynamicToolSpec>,
     shell_override: Option<ShellConfig>,
     shell_style_profiles: HashMap<String, ShellStyleProfileEntry>,
+    shell_env_set: Option<HashMap<String, String>>,
     network: Option<crate::config::NetworkProxySettingsToml>,
     tools_repl: bool,
     repl_default_runtime: crate::config::ReplRuntimeKindToml,
@@ -227,11 +230,11 @@ impl Runner<'_> {
     }
 
     async fn run(&mut self, req: ConfigureSessionRequest) -> ConfigureSessionControl {
-        let prepared = match self.prepare(req).await {
+        let prepared = match crate::startup_profile::measure("session prepare", self.prepare(req)).await {
             Ok(prepared) => prepared,
             Err(control) => return control,
         };
-        let built = self.build_session(prepared).await;
+        let built = crate::startup_profile::measure("session build", self.build_session(prepared)).await;
         self.emit(built).await;
         ConfigureSessionControl::Continue
     }
@@ -241,7 +244,7 @@ impl Runner<'_> {
         let event = Event {
             id: sub_id.to_owned(),
             event_seq: 0,
-            msg: EventMsg::Error(ErrorEvent { message }),
+            msg: EventMsg::Error(ErrorEvent::new(message, ErrorCode::InvalidConfig)),
             order: None,
         };
         if let Err(e) = self.tx_event.send(event).await {
@@ -266,9 +269,10 @@ impl Runner<'_> {
         let event = Event {
             id: sub_id.to_owned(),
             event_seq: 0,
-            msg: EventMsg::Error(ErrorEvent {
-                message: "No session initialized, expected 'ConfigureSession' as first Op".to_owned(),
-            }),
+            msg: EventMsg::Error(ErrorEvent::new(
+                "No session initialized, expected 'ConfigureSession' as first Op",
+                ErrorCode::NoSession,
+            )),
             order: None,
         };
         let _ = self.tx_event.send(event).await;
@@ -32,6 +32,7 @@ impl Runner<'_> {
             dynamic_tools,
             shell_override,
             shell_style_profiles,
+            shell_env_set,
             network,
             tools_repl,
             repl_default_runtime,
@@ -91,6 +92,9 @@ impl Runner<'_> {
         updated_config.disable_response_storage = disable_response_storage;
         updated_config.notify = notify.clone();
         updated_config.cwd = cwd.clone();
+        if let Some(set) = shell_env_set {
+            updated_config.shell_environment_policy.r#set = set;
+        }
         updated_config.dynamic_tools = dynamic_tools.clone();
         updated_config.network = network.clone();
         updated_config.tools_repl = tools_repl;
@@ -116,7 +120,11 @@ impl Runner<'_> {
         updated_config.network_proxy = {
             #[cfg(feature = "managed-network-proxy")]
             {
-                match updated_config.network.as_ref().filter(|net| net.enabled) {
+                let network = crate::network_allowlist::effective_network_settings(
+                    updated_config.network.as_ref(),
+                    updated_config.sandbox_policy.network_allowlist(),
+                );
+                match network.as_ref().filter(|net| net.enabled) {
                     Some(net) => {
                         match crate::config::network_proxy_spec::NetworkProxySpec::from_config(
                             net.to_network_proxy_config(),
@@ -142,6 +150,14 @@ impl Runner<'_> {
                     )
                     .await;
                 }
+                if !updated_config.sandbox_policy.network_allowlist().is_empty() {
+                    self.send_warning_event(
+                        &submission_id,
+                        "The sandbox network allowlist needs managed network mediation, which is not available in this build; network access stays blocked."
+                            .to_string(),
+                    )
+                    .await;
+                }
                 None
             }
         };
@@ -391,6 +407,7 @@ impl Runner<'_> {
             &updated_config.shell_style_profiles,
         );
 
+        let skills_started = std::time::Instant::now();
         let mut skills_outcome = updated_config.skills_enabled.then(|| {
             if shell_style_skill_roots.is_empty() {
                 load_skills(&updated_config)
@@ -401,6 +418,7 @@ impl Runner<'_> {
                 )
             }
         });
+        crate::startup_profile::record_since("skills scan", skills_started);
         if let Some(outcome) = &mut skills_outcome {
             for err in &outcome.errors {
                 warn!("invalid skill {}: {}", err.path.display(), err.message);
@@ -527,6 +545,8 @@ impl Runner<'_> {
                 .await
                 {
                     Ok(r) => Some(r),
+                    // `[privacy] transcripts = "none"`; nothing to warn about.
+                    Err(e) if e.kind() == std::io::ErrorKind::Unsupported => None,
                     Err(e) => {
                         warn!("failed to initialise rollout recorder: {e}");
                         None
@@ -535,6 +555,27 @@ impl Runner<'_> {
             }
         };
 
+        if let Some(days) = self.config.privacy.retention_days {
+            let code_home = self.config.code_home.clone();
+            let project_root = crate::git_info::get_git_repo_root(&self.config.cwd)
+                .unwrap_or_else(|| self.config.cwd.clone());
+            let current_session = self.session_id;
+            tokio::spawn(async move {
+                let cutoff = chrono::Utc::now() - chrono::Duration::days(i64::from(days));
+                match crate::session_catalog::SessionCatalog::new(code_home)
+                    .delete_sessions_older_than(&project_root, cutoff, Some(current_session))
+                    .await
+                {
+                    Ok(0) => {}
+                    Ok(removed) => tracing::info!(
+                        "[privacy] removed {removed} sessions idle for more than {days} days under {}",
+                        project_root.display()
+                    ),
+                    Err(err) => warn!("failed to apply [privacy] retention_days: {err:#}"),
+                }
+            });
+        }
+
         Ok(Prepared {
             submission_id,
             provider,
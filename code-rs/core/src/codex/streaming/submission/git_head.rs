@@ -0,0 +1,58 @@
+use super::*;
+
+/// Compares HEAD against the last state the session saw and, when it moved
+/// to another branch or was rewritten while no turn was running, warns the
+/// user and requires the next write to be confirmed.
+pub(super) async fn handle_git_head_change(sess: &Arc<Session>) {
+    // Wait for the rebase to finish; its last HEAD update fires another event.
+    if crate::git_info::resolve_git_dir(&sess.cwd)
+        .is_some_and(|git_dir| crate::git_info::rebase_in_progress(&git_dir))
+    {
+        return;
+    }
+    let Some(current) = crate::git_info::head_state(&sess.cwd).await else {
+        return;
+    };
+    let Some(previous) = sess.replace_git_head(current.clone()) else {
+        return;
+    };
+    // Changes made while a turn runs come from the turn's own commands.
+    if previous == current || sess.has_running_task() {
+        return;
+    }
+
+    let message = if previous.branch == current.branch {
+        if let (Some(old), Some(new)) = (previous.commit.as_deref(), current.commit.as_deref())
+            && crate::git_info::is_ancestor_commit(&sess.cwd, old, new).await
+        {
+            // New commits on the same branch keep existing baselines valid.
+            return;
+        }
+        format!(
+            "HEAD was rewritten outside this session (rebase or reset): {} is now {}.",
+            previous.describe(),
+            current.describe()
+        )
+    } else {
+        format!(
+            "HEAD changed outside this session: switched from {} to {}.",
+            previous.describe(),
+            current.describe()
+        )
+    };
+    sess.require_git_head_reconfirmation(format!(
+        "{message} Confirm before the agent keeps writing to this checkout."
+    ));
+
+    let event = sess.make_event(
+        INITIAL_SUBMIT_ID,
+        EventMsg::GitHeadChanged(crate::protocol::GitHeadChangedEvent {
+            previous_branch: previous.branch,
+            previous_commit: previous.commit,
+            branch: current.branch,
+            commit: current.commit,
+            message,
+        }),
+    );
+    sess.send_event(event).await;
+}
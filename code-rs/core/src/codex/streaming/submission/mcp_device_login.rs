@@ -0,0 +1,123 @@
+use super::*;
+use crate::config_types::McpServerConfig;
+use crate::config_types::McpServerTransportConfig;
+use crate::protocol::McpAuthPromptEvent;
+
+/// Starts an OAuth device login for every HTTP server that failed to start
+/// because it wants one. Each login runs in the background: the user gets an
+/// `McpAuthPrompt`, and the server is started once they approve.
+pub(super) fn spawn_device_logins(sess: &Arc<Session>, sub_id: &str) {
+    for (server_name, cfg) in sess.mcp_connection_manager.begin_device_logins() {
+        let sess = Arc::clone(sess);
+        let sub_id = sub_id.to_owned();
+        tokio::spawn(async move {
+            run_device_login(&sess, &sub_id, &server_name, &cfg).await;
+            sess.mcp_connection_manager
+                .finish_device_login(&server_name);
+        });
+    }
+}
+
+async fn run_device_login(
+    sess: &Arc<Session>,
+    sub_id: &str,
+    server_name: &str,
+    cfg: &McpServerConfig,
+) {
+    let McpServerTransportConfig::StreamableHttp {
+        url,
+        http_headers,
+        env_http_headers,
+        ..
+    } = &cfg.transport
+    else {
+        return;
+    };
+    let config = sess.client.config();
+    let started = code_rmcp_client::start_device_login(code_rmcp_client::DeviceLoginArgs {
+        code_home: &config.code_home,
+        server_name,
+        server_url: url,
+        store_mode: config.mcp_oauth_credentials_store_mode,
+        http_headers: http_headers.clone(),
+        env_http_headers: env_http_headers.clone(),
+        scopes: &[],
+        http_client_builder: crate::http_client::apply_outbound_network_settings(
+            reqwest::Client::builder(),
+        ),
+    })
+    .await;
+    let handle = match started {
+        Ok(Some(handle)) => handle,
+        // Failed for some other reason; the start-up error already says why.
+        Ok(None) => return,
+        Err(err) => {
+            send_login_error(
+                sess,
+                sub_id,
+                format!("MCP server `{server_name}` sign-in failed: {err:#}"),
+            )
+            .await;
+            return;
+        }
+    };
+
+    let prompt = handle.prompt();
+    let event = sess.make_event(
+        sub_id,
+        EventMsg::McpAuthPrompt(McpAuthPromptEvent {
+            server_name: server_name.to_owned(),
+            verification_uri: prompt.verification_uri.clone(),
+            verification_uri_complete: prompt.verification_uri_complete.clone(),
+            user_code: prompt.user_code.clone(),
+            expires_in_secs: prompt.expires_in.as_secs(),
+        }),
+    );
+    sess.send_event(event).await;
+
+    if let Err(err) = handle.wait().await {
+        send_login_error(
+            sess,
+            sub_id,
+            format!("MCP server `{server_name}` sign-in failed: {err:#}"),
+        )
+        .await;
+        return;
+    }
+    if let Err(err) = sess
+        .mcp_connection_manager
+        .ensure_server_started(server_name, cfg)
+        .await
+    {
+        send_login_error(
+            sess,
+            sub_id,
+            format!("MCP server `{server_name}` failed to start after sign-in: {err:#}"),
+        )
+        .await;
+        return;
+    }
+
+    let event = sess.make_event(
+        sub_id,
+        EventMsg::BackgroundEvent(BackgroundEventEvent {
+            message: format!(
+                "Signed in to MCP server `{server_name}`; its tools are now available."
+            ),
+        }),
+    );
+    sess.send_event(event).await;
+    let event = sess.make_event(
+        sub_id,
+        EventMsg::McpListToolsResponse(mcp_list_tools_response(sess).await),
+    );
+    sess.send_event(event).await;
+}
+
+async fn send_login_error(sess: &Session, sub_id: &str, message: String) {
+    let event = sess.make_event(
+        sub_id,
+        EventMsg::Error(ErrorEvent::new(message, ErrorCode::McpServerFailed)),
+    );
+    sess.send_event(event).await;
+}
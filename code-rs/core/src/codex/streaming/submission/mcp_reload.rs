@@ -0,0 +1,115 @@
+use super::*;
+use crate::config::ConfigBuilder;
+use crate::config::ConfigOverrides;
+use crate::mcp_connection_manager::McpReloadSummary;
+
+/// Handles `Op::ReloadMcpServers`: re-reads `mcp_servers` from config,
+/// applies the difference to the running servers and reports the result.
+pub(super) async fn reload_mcp_servers(sess: &Arc<Session>, sub_id: &str) {
+    let code_home = sess.client.config().code_home.clone();
+    let cwd = sess.get_cwd().to_path_buf();
+    let loaded = tokio::task::spawn_blocking(move || {
+        ConfigBuilder::new()
+            .with_code_home(code_home)
+            .with_overrides(ConfigOverrides {
+                cwd: Some(cwd),
+                ..Default::default()
+            })
+            .load()
+    })
+    .await;
+    let servers = match loaded {
+        Ok(Ok(config)) => config.mcp_servers,
+        Ok(Err(err)) => {
+            let message = format!("MCP reload failed to read config: {err}");
+            send_reload_message(sess, sub_id, message).await;
+            return;
+        }
+        Err(err) => {
+            let message = format!("MCP reload failed to read config: {err}");
+            send_reload_message(sess, sub_id, message).await;
+            return;
+        }
+    };
+
+    // Servers filtered out by the active shell style are only started on
+    // demand, as at session start.
+    let access = sess.mcp_access_snapshot();
+    let summary = sess
+        .mcp_connection_manager
+        .reload_servers(servers, |name| {
+            let name = name.to_ascii_lowercase();
+            (access.style_include_servers.is_empty()
+                || access.style_include_servers.contains(&name))
+                && !access.style_exclude_servers.contains(&name)
+        })
+        .await;
+
+    send_reload_message(sess, sub_id, reload_message(&summary)).await;
+    super::mcp_device_login::spawn_device_logins(sess, sub_id);
+    let event = sess.make_event(
+        sub_id,
+        EventMsg::McpListToolsResponse(mcp_list_tools_response(sess).await),
+    );
+    sess.send_event(event).await;
+}
+
+async fn send_reload_message(sess: &Session, sub_id: &str, message: String) {
+    let event = sess.make_event(
+        sub_id,
+        EventMsg::BackgroundEvent(BackgroundEventEvent { message }),
+    );
+    sess.send_event(event).await;
+}
+
+fn reload_message(summary: &McpReloadSummary) -> String {
+    if summary.is_empty() {
+        return "MCP servers reloaded: no changes.".to_owned();
+    }
+    let mut parts = Vec::new();
+    for (label, names) in [
+        ("started", &summary.added),
+        ("restarted", &summary.restarted),
+        ("stopped", &summary.removed),
+    ] {
+        if !names.is_empty() {
+            parts.push(format!("{label} {}", names.join(", ")));
+        }
+    }
+    let mut lines = vec![if parts.is_empty() {
+        "MCP servers reloaded.".to_owned()
+    } else {
+        format!("MCP servers reloaded: {}.", parts.join("; "))
+    }];
+    lines.extend(
+        summary
+            .failed
+            .iter()
+            .map(|(name, error)| format!("`{name}` failed to start: {error}")),
+    );
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn reload_message_lists_each_change() {
+        assert_eq!(
+            reload_message(&McpReloadSummary::default()),
+            "MCP servers reloaded: no changes."
+        );
+        let summary = McpReloadSummary {
+            added: vec!["docs".to_owned(), "search".to_owned()],
+            removed: vec!["old".to_owned()],
+            restarted: Vec::new(),
+            failed: vec![("broken".to_owned(), "command not found".to_owned())],
+        };
+        assert_eq!(
+            reload_message(&summary),
+            "MCP servers reloaded: started docs, search; stopped old.\n`broken` failed to start: command not found"
+        );
+    }
+}
@@ -1,6 +1,9 @@
 use super::*;
 
 mod configure_session;
+mod git_head;
+mod mcp_device_login;
+mod mcp_reload;
 mod skills;
 
 pub(in crate::codex) async fn submission_loop(
@@ -27,7 +30,10 @@ pub(in crate::codex) async fn submission_loop(
         let event = Event {
             id: sub_id,
             event_seq: 0,
-            msg: EventMsg::Error(ErrorEvent { message: "No session initialized, expected 'ConfigureSession' as first Op".to_owned() }),
+            msg: EventMsg::Error(ErrorEvent::new(
+                "No session initialized, expected 'ConfigureSession' as first Op",
+                ErrorCode::NoSession,
+            )),
             order: None,
         };
         tx_event.send(event).await.ok();
@@ -158,13 +164,23 @@ pub(in crate::codex) async fn submission_loop(
                 // This prevents token buildup from old screenshots/status messages
                 sess.cleanup_old_status_items();
 
+                if sess.has_running_task() {
+                    let event = sess.make_event(
+                        &sub.id,
+                        EventMsg::UserInputInterrupting(UserInputInterruptingEvent {
+                            pending: sess.queued_user_input_count(),
+                        }),
+                    );
+                    sess.send_event(event).await;
+                }
+
                 // Abort synchronously here to avoid a race that can kill the
                 // newly spawned agent if the async abort runs after set_task.
                 sess.notify_wait_interrupted(WaitInterruptReason::UserMessage);
                 sess.abort();
 
                 // Spawn a new agent for this user input.
-                let turn_context = sess.make_turn_context_with_schema(final_output_json_schema);
+                let turn_context = sess.make_user_turn_context(&sub.id, final_output_json_schema);
                 let agent = AgentTask::spawn(Arc::clone(sess), turn_context, sub.id.clone(), items, TaskOriginKind::User, true);
                 sess.set_task(agent);
             }
@@ -183,11 +199,16 @@ pub(in crate::codex) async fn submission_loop(
                         response_item,
                         core_items: items,
                     };
-                    sess.queue_user_input(queued);
+                    let pending = sess.queue_user_input(queued);
+                    let event = sess.make_event(
+                        &sub.id,
+                        EventMsg::UserInputQueued(UserInputQueuedEvent { pending }),
+                    );
+                    sess.send_event(event).await;
                 } else {
                     // No task running: treat this as immediate user input without aborting.
                     sess.cleanup_old_status_items();
-                    let turn_context = sess.make_turn_context();
+                    let turn_context = sess.make_user_turn_context(&sub.id, None);
                     let agent = AgentTask::spawn(Arc::clone(sess), turn_context, sub.id.clone(), items, TaskOriginKind::QueuedUser, true);
                     sess.set_task(agent);
                 }
@@ -248,24 +269,68 @@ pub(in crate::codex) async fn submission_loop(
                 };
                 sess.notify_dynamic_tool_response(&id, response);
             }
+            Op::RequestBudgetDecision { id, decision } => {
+                let Some(sess) = sess.as_ref() else {
+                    send_no_session_event(sub.id).await;
+                    continue;
+                };
+                sess.notify_request_budget_decision(&id, decision);
+            }
             Op::RegisterApprovedCommand {
                 command,
                 match_kind,
                 semantic_prefix,
+                persist,
             } => {
                 if command.is_empty() {
                     continue;
                 }
-                if let Some(sess) = sess.as_ref() {
-                    sess.add_approved_command(ApprovedCommandPattern::new(
-                        command,
-                        match_kind,
-                        semantic_prefix,
-                    ));
-                } else {
+                let Some(sess) = sess.as_ref() else {
                     send_no_session_event(sub.id).await;
+                    continue;
+                };
+                sess.add_approved_command(ApprovedCommandPattern::new(
+                    command.clone(),
+                    match_kind,
+                    semantic_prefix,
+                ));
+                if persist {
+                    let display = crate::util::strip_bash_lc_and_escape(&command);
+                    let saved =
+                        crate::approvals_store::add_approval(sess.get_cwd(), &command, match_kind);
+                    let message = match saved {
+                        Ok(_) => format!("Always allowing `{display}` in this project."),
+                        Err(err) => format!("Failed to save the approval for `{display}`: {err}"),
+                    };
+                    let event = sess.make_event(
+                        &sub.id,
+                        EventMsg::BackgroundEvent(BackgroundEventEvent { message }),
+                    );
+                    sess.send_event(event).await;
                 }
             }
+            Op::RevokeApprovedCommand { command, match_kind } => {
+                let Some(sess) = sess.as_ref() else {
+                    send_no_session_event(sub.id).await;
+                    continue;
+                };
+                let display = crate::util::strip_bash_lc_and_escape(&command);
+                let in_session = sess.remove_approved_command(&command, match_kind);
+                let revoked =
+                    crate::approvals_store::revoke_approval(sess.get_cwd(), &command, match_kind);
+                let message = match revoked {
+                    Ok(stored) if stored || in_session => {
+                        format!("Revoked the approval for `{display}`.")
+                    }
+                    Ok(_) => format!("No approval for `{display}` to revoke."),
+                    Err(err) => format!("Failed to revoke the approval for `{display}`: {err}"),
+                };
+                let event = sess.make_event(
+                    &sub.id,
+                    EventMsg::BackgroundEvent(BackgroundEventEvent { message }),
+                );
+                sess.send_event(event).await;
+            }
             Op::UpdateValidationTool { name, enable } => {
                 if let Some(sess) = sess.as_ref() {
                     sess.update_validation_tool(&name, enable);
@@ -365,52 +430,10 @@ pub(in crate::codex) async fn submission_loop(
                     continue;
                 };
 
-                let tools = sess
-                    .mcp_connection_manager
-                    .list_all_tools()
-                    .into_iter()
-                    .filter_map(|(name, tool)| {
-                        let value = match serde_json::to_value(tool) {
-                            Ok(value) => value,
-                            Err(err) => {
-                                warn!("failed to serialize MCP tool {name}: {err}");
-                                return None;
-                            }
-                        };
-                        match code_protocol::mcp::Tool::from_mcp_value(value) {
-                            Ok(converted) => Some((name, converted)),
-                            Err(err) => {
-                                warn!("failed to convert MCP tool {name}: {err}");
-                                None
-                            }
-                        }
-                    })
-                    .collect();
-                let server_tools = sess.mcp_connection_manager.list_tools_by_server();
-                let server_disabled_tools =
-                    sess.mcp_connection_manager.list_disabled_tools_by_server();
-                let server_failures = sess.mcp_connection_manager.list_server_failures();
-                let resources =
-                    convert_mcp_resources_by_server(sess.mcp_connection_manager.list_resources_by_server().await);
-                let resource_templates = convert_mcp_resource_templates_by_server(
-                    sess.mcp_connection_manager
-                        .list_resource_templates_by_server()
-                        .await,
-                );
-                let auth_statuses = sess.mcp_connection_manager.list_auth_statuses().await;
-
                 let event = Event {
                     id: sub.id.clone(),
                     event_seq: 0,
-                    msg: EventMsg::McpListToolsResponse(McpListToolsResponseEvent {
-                        tools,
-                        server_tools: Some(server_tools),
-                        server_disabled_tools: Some(server_disabled_tools),
-                        server_failures: Some(server_failures),
-                        resources,
-                        resource_templates,
-                        auth_statuses,
-                    }),
+                    msg: EventMsg::McpListToolsResponse(mcp_list_tools_response(&sess).await),
                     order: None,
                 };
 
@@ -426,52 +449,10 @@ pub(in crate::codex) async fn submission_loop(
 
                 sess.mcp_connection_manager.refresh_tools().await;
 
-                let tools = sess
-                    .mcp_connection_manager
-                    .list_all_tools()
-                    .into_iter()
-                    .filter_map(|(name, tool)| {
-                        let value = match serde_json::to_value(tool) {
-                            Ok(value) => value,
-                            Err(err) => {
-                                warn!("failed to serialize MCP tool {name}: {err}");
-                                return None;
-                            }
-                        };
-                        match code_protocol::mcp::Tool::from_mcp_value(value) {
-                            Ok(converted) => Some((name, converted)),
-                            Err(err) => {
-                                warn!("failed to convert MCP tool {name}: {err}");
-                                None
-                            }
-                        }
-                    })
-                    .collect();
-                let server_tools = sess.mcp_connection_manager.list_tools_by_server();
-                let server_disabled_tools =
-                    sess.mcp_connection_manager.list_disabled_tools_by_server();
-                let server_failures = sess.mcp_connection_manager.list_server_failures();
-                let resources =
-                    convert_mcp_resources_by_server(sess.mcp_connection_manager.list_resources_by_server().await);
-                let resource_templates = convert_mcp_resource_templates_by_server(
-                    sess.mcp_connection_manager
-                        .list_resource_templates_by_server()
-                        .await,
-                );
-                let auth_statuses = sess.mcp_connection_manager.list_auth_statuses().await;
-
                 let event = Event {
                     id: sub.id.clone(),
                     event_seq: 0,
-                    msg: EventMsg::McpListToolsResponse(McpListToolsResponseEvent {
-                        tools,
-                        server_tools: Some(server_tools),
-                        server_disabled_tools: Some(server_disabled_tools),
-                        server_failures: Some(server_failures),
-                        resources,
-                        resource_templates,
-                        auth_statuses,
-                    }),
+                    msg: EventMsg::McpListToolsResponse(mcp_list_tools_response(&sess).await),
                     order: None,
                 };
 
@@ -479,6 +460,17 @@ pub(in crate::codex) async fn submission_loop(
                     warn!("failed to send McpListToolsResponse event: {e}");
                 }
             }
+            Op::ReloadMcpServers => {
+                let Some(sess) = sess.as_ref() else {
+                    send_no_session_event(sub.id).await;
+                    continue;
+                };
+                // Starting servers can take a while; keep handling ops.
+                let sess = Arc::clone(sess);
+                tokio::spawn(async move {
+                    mcp_reload::reload_mcp_servers(&sess, &sub.id).await;
+                });
+            }
             Op::SetMcpToolEnabled {
                 server,
                 tool,
@@ -493,52 +485,10 @@ pub(in crate::codex) async fn submission_loop(
                     .set_tool_enabled(&server, &tool, enable)
                     .await;
 
-                let tools = sess
-                    .mcp_connection_manager
-                    .list_all_tools()
-                    .into_iter()
-                    .filter_map(|(name, tool)| {
-                        let value = match serde_json::to_value(tool) {
-                            Ok(value) => value,
-                            Err(err) => {
-                                warn!("failed to serialize MCP tool {name}: {err}");
-                                return None;
-                            }
-                        };
-                        match code_protocol::mcp::Tool::from_mcp_value(value) {
-                            Ok(converted) => Some((name, converted)),
-                            Err(err) => {
-                                warn!("failed to convert MCP tool {name}: {err}");
-                                None
-                            }
-                        }
-                    })
-                    .collect();
-                let server_tools = sess.mcp_connection_manager.list_tools_by_server();
-                let server_disabled_tools =
-                    sess.mcp_connection_manager.list_disabled_tools_by_server();
-                let server_failures = sess.mcp_connection_manager.list_server_failures();
-                let resources =
-                    convert_mcp_resources_by_server(sess.mcp_connection_manager.list_resources_by_server().await);
-                let resource_templates = convert_mcp_resource_templates_by_server(
-                    sess.mcp_connection_manager
-                        .list_resource_templates_by_server()
-                        .await,
-                );
-                let auth_statuses = sess.mcp_connection_manager.list_auth_statuses().await;
-
                 let event = Event {
                     id: sub.id.clone(),
                     event_seq: 0,
-                    msg: EventMsg::McpListToolsResponse(McpListToolsResponseEvent {
-                        tools,
-                        server_tools: Some(server_tools),
-                        server_disabled_tools: Some(server_disabled_tools),
-                        server_failures: Some(server_failures),
-                        resources,
-                        resource_templates,
-                        auth_statuses,
-                    }),
+                    msg: EventMsg::McpListToolsResponse(mcp_list_tools_response(&sess).await),
                     order: None,
                 };
 
@@ -676,6 +626,13 @@ pub(in crate::codex) async fn submission_loop(
                     sess.send_event(event).await;
                 }
             }
+            Op::RevertTurn { turn_id } => {
+                let Some(sess) = sess.as_ref() else {
+                    send_no_session_event(sub.id).await;
+                    continue;
+                };
+                crate::codex::turn_baselines::revert_turn(sess, &sub.id, turn_id).await;
+            }
             Op::Review { review_request } => {
                 let sess = if let Some(sess) = sess.as_ref() { Arc::clone(sess) } else {
                     send_no_session_event(sub.id).await;
@@ -692,6 +649,44 @@ pub(in crate::codex) async fn submission_loop(
                 };
                 *crate::codex::lock_or_panic!(sess_arc.next_turn_text_format) = Some(format);
             }
+            Op::SetNextTurnOverrides { model, effort, sandbox, approval } => {
+                let sess_arc = if let Some(sess) = sess.as_ref() { Arc::clone(sess) } else {
+                    send_no_session_event(sub.id).await;
+                    continue;
+                };
+                *crate::codex::lock_or_panic!(sess_arc.next_turn_overrides) =
+                    Some(crate::codex::session::NextTurnOverrides {
+                        model,
+                        effort,
+                        sandbox,
+                        approval,
+                    });
+            }
+            Op::PreviewPrompt => {
+                let sess = if let Some(sess) = sess.as_ref() { Arc::clone(sess) } else {
+                    send_no_session_event(sub.id).await;
+                    continue;
+                };
+
+                let disabled =
+                    crate::codex::lock_or_panic!(sess.next_turn_disabled_prompt_segments).clone();
+                let segments = prompt_stack::preview_prompt_stack(&sess, &disabled).await;
+                let event = Event {
+                    id: sub.id.clone(),
+                    event_seq: 0,
+                    msg: EventMsg::PromptPreview(PromptPreviewEvent { segments }),
+                    order: None,
+                };
+
+                sess.send_event(event).await;
+            }
+            Op::SetDisabledPromptSegments { segments } => {
+                let sess_arc = if let Some(sess) = sess.as_ref() { Arc::clone(sess) } else {
+                    send_no_session_event(sub.id).await;
+                    continue;
+                };
+                *crate::codex::lock_or_panic!(sess_arc.next_turn_disabled_prompt_segments) = segments;
+            }
             Op::Shutdown => {
                 info!("Shutting down Codex instance");
 
@@ -704,6 +699,10 @@ pub(in crate::codex) async fn submission_loop(
                     });
                 }
 
+                if let Some(ref sess_arc) = sess {
+                    sess_arc.shutdown_child_processes().await;
+                }
+
                 // Gracefully flush and shutdown rollout recorder on session end so tests
                 // that inspect the rollout file do not race with the background writer.
                 if let Some(ref sess_arc) = sess {
@@ -713,9 +712,10 @@ pub(in crate::codex) async fn submission_loop(
                             warn!("failed to shutdown rollout recorder: {e}");
                             let event = sess_arc.make_event(
                                 &sub.id,
-                                EventMsg::Error(ErrorEvent {
-                                    message: "Failed to shutdown rollout recorder".to_owned(),
-                                }),
+                                EventMsg::Error(ErrorEvent::new(
+                                    "Failed to shutdown rollout recorder",
+                                    ErrorCode::Internal,
+                                )),
                             );
                             if let Err(e) = tx_event.send(event).await {
                                 warn!("failed to send error message: {e:?}");
@@ -757,6 +757,15 @@ pub(in crate::codex) async fn submission_loop(
                             .await;
                         });
                     }
+                    Ok(crate::file_watcher::FileWatcherEvent::GitHeadChanged { .. }) => {
+                        let Some(sess_arc) = sess.as_ref() else {
+                            continue;
+                        };
+                        let sess_arc = Arc::clone(sess_arc);
+                        tokio::spawn(async move {
+                            git_head::handle_git_head_change(&sess_arc).await;
+                        });
+                    }
                     Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => {}
                     Err(tokio::sync::broadcast::error::RecvError::Closed) => {
                         warn!("file watcher channel closed; disabling");
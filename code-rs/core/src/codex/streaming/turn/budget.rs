@@ -0,0 +1,159 @@
+//! Request budget negotiation for embedding applications. With
+//! `Config::request_budget_negotiation` set, every model request is announced
+//! with `EventMsg::RequestBudgetCheck` before it is sent, and the turn waits
+//! for `Op::RequestBudgetDecision` to proceed, modify or veto it.
+
+use super::*;
+use crate::model_family::ModelFamily;
+use crate::protocol::RequestBudgetCheckEvent;
+use crate::protocol::RequestBudgetDecision;
+use crate::protocol::WarningEvent;
+
+/// What `run_turn` does with the request it was about to send.
+pub(super) enum BudgetOutcome {
+    Send,
+    Vetoed,
+    /// The turn was aborted while waiting for a decision.
+    Aborted,
+}
+
+pub(super) async fn negotiate_request_budget(
+    sess: &Session,
+    tc: &TurnContext,
+    sub_id: &str,
+    attempt_req: u64,
+    prompt: &mut Prompt,
+) -> BudgetOutcome {
+    if !sess.request_budget_negotiation {
+        return BudgetOutcome::Send;
+    }
+
+    let family = prompt
+        .model_family_override
+        .as_ref()
+        .unwrap_or_else(|| tc.client.default_model_family());
+    let event = RequestBudgetCheckEvent {
+        request_id: format!("{sub_id}:{attempt_req}"),
+        turn_id: sub_id.to_owned(),
+        model: prompt
+            .model_override
+            .clone()
+            .unwrap_or_else(|| tc.client.get_model()),
+        reasoning_effort: prompt
+            .reasoning_effort_override
+            .unwrap_or_else(|| tc.client.get_reasoning_effort()),
+        estimated_input_tokens: estimate_input_tokens(prompt, family),
+        tools: prompt.tools.iter().map(|tool| tool.name().to_owned()).collect(),
+    };
+
+    let Some(decision) = sess.request_budget_decision(sub_id, event).await else {
+        return BudgetOutcome::Aborted;
+    };
+    match decision {
+        RequestBudgetDecision::Proceed => BudgetOutcome::Send,
+        RequestBudgetDecision::Modify {
+            remove_tools,
+            reasoning_effort,
+        } => {
+            apply_modification(prompt, &remove_tools, reasoning_effort);
+            BudgetOutcome::Send
+        }
+        RequestBudgetDecision::Veto { reason } => {
+            let message = match reason {
+                Some(reason) => format!("Model request vetoed: {reason}"),
+                None => "Model request vetoed".to_owned(),
+            };
+            let event = sess.make_event(sub_id, EventMsg::Warning(WarningEvent { message }));
+            sess.send_event(event).await;
+            BudgetOutcome::Vetoed
+        }
+    }
+}
+
+fn apply_modification(
+    prompt: &mut Prompt,
+    remove_tools: &[String],
+    reasoning_effort: Option<ReasoningEffortConfig>,
+) {
+    if !remove_tools.is_empty() {
+        prompt
+            .tools
+            .retain(|tool| !remove_tools.iter().any(|name| name == tool.name()));
+    }
+    if reasoning_effort.is_some() {
+        prompt.reasoning_effort_override = reasoning_effort;
+    }
+}
+
+/// Instructions, input and tool definitions at four bytes per token.
+fn estimate_input_tokens(prompt: &Prompt, family: &ModelFamily) -> u64 {
+    let bytes = prompt.get_full_instructions(family).len()
+        + json_len(&prompt.get_formatted_input())
+        + json_len(&prompt.tools);
+    bytes.div_ceil(4) as u64
+}
+
+fn json_len<T: serde::Serialize>(value: &T) -> usize {
+    serde_json::to_string(value).map_or(0, |json| json.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::openai_tools::OpenAiTool;
+    use crate::openai_tools::ResponsesApiTool;
+    use pretty_assertions::assert_eq;
+
+    fn function_tool(name: &str) -> OpenAiTool {
+        OpenAiTool::Function(ResponsesApiTool {
+            name: name.to_string(),
+            description: String::new(),
+            strict: false,
+            parameters: crate::openai_tools::JsonSchema::Object {
+                properties: Default::default(),
+                required: None,
+                additional_properties: None,
+            },
+        })
+    }
+
+    #[test]
+    fn modification_strips_tools_and_lowers_effort() {
+        let mut prompt = Prompt {
+            tools: vec![
+                function_tool("shell"),
+                OpenAiTool::WebSearch(Default::default()),
+                function_tool("browser_open"),
+            ],
+            ..Prompt::default()
+        };
+
+        apply_modification(
+            &mut prompt,
+            &["web_search".to_owned(), "browser_open".to_owned()],
+            Some(ReasoningEffortConfig::Low),
+        );
+
+        let names: Vec<&str> = prompt.tools.iter().map(OpenAiTool::name).collect();
+        assert_eq!(names, vec!["shell"]);
+        assert_eq!(prompt.reasoning_effort_override, Some(ReasoningEffortConfig::Low));
+    }
+
+    #[test]
+    fn decisions_use_tagged_json() {
+        let decision: RequestBudgetDecision = serde_json::from_str(
+            r#"{"type":"modify","remove_tools":["web_search"],"reasoning_effort":"low"}"#,
+        )
+        .expect("modify decision");
+        assert_eq!(
+            decision,
+            RequestBudgetDecision::Modify {
+                remove_tools: vec!["web_search".to_owned()],
+                reasoning_effort: Some(ReasoningEffortConfig::Low),
+            }
+        );
+        let veto: RequestBudgetDecision =
+            serde_json::from_str(r#"{"type":"veto"}"#).expect("veto decision");
+        assert_eq!(veto, RequestBudgetDecision::Veto { reason: None });
+    }
+}
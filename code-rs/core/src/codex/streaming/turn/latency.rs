@@ -4,6 +4,8 @@ pub(super) struct TurnLatencyGuard<'a> {
     sess: &'a Session,
     attempt_req: u64,
     active: bool,
+    started: Instant,
+    first_token_ms: Option<u64>,
 }
 
 impl<'a> TurnLatencyGuard<'a> {
@@ -13,6 +15,25 @@ impl<'a> TurnLatencyGuard<'a> {
             sess,
             attempt_req,
             active: true,
+            started: Instant::now(),
+            first_token_ms: None,
+        }
+    }
+
+    /// Records time to first token on the first event carrying model output.
+    pub(super) fn observe_event(&mut self, event: &ResponseEvent) {
+        if self.first_token_ms.is_some() {
+            return;
+        }
+        if matches!(
+            event,
+            ResponseEvent::OutputItemDone { .. }
+                | ResponseEvent::OutputTextDelta { .. }
+                | ResponseEvent::ReasoningSummaryDelta { .. }
+                | ResponseEvent::ReasoningContentDelta { .. }
+                | ResponseEvent::WebSearchCallBegin { .. }
+        ) {
+            self.first_token_ms = Some(duration_to_millis(self.started.elapsed()));
         }
     }
 
@@ -26,6 +47,7 @@ impl<'a> TurnLatencyGuard<'a> {
         }
         self.sess.turn_latency_request_completed(
             self.attempt_req,
+            self.first_token_ms,
             output_item_count,
             token_usage,
         );
@@ -36,7 +58,8 @@ impl<'a> TurnLatencyGuard<'a> {
         if !self.active {
             return;
         }
-        self.sess.turn_latency_request_failed(self.attempt_req, note);
+        self.sess
+            .turn_latency_request_failed(self.attempt_req, self.first_token_ms, note);
         self.active = false;
     }
 }
@@ -46,6 +69,7 @@ impl Drop for TurnLatencyGuard<'_> {
         if self.active {
             self.sess.turn_latency_request_failed(
                 self.attempt_req,
+                self.first_token_ms,
                 Some("dropped_without_outcome".to_owned()),
             );
         }
@@ -2,6 +2,7 @@ use std::fmt::Write as _;
 
 use super::*;
 
+mod budget;
 mod handle_item;
 mod latency;
 mod stream;
@@ -31,8 +32,6 @@ pub(super) async fn run_turn(
 
     let mut retries = 0;
     let mut rate_limit_switch_state = RateLimitSwitchState::default();
-    let collaboration_mode_instructions =
-        render_collaboration_mode_instructions(tc.collaboration_mode);
     // Ensure we only auto-compact once per turn to avoid loops
     let mut did_auto_compact = false;
     // Keep the MCP allow guard alive for the entire turn so per-turn MCP
@@ -49,64 +48,25 @@ pub(super) async fn run_turn(
     )
     .await;
 
-    let mut base_prepend_developer_messages: Vec<String> = tc
-        .demo_developer_message
-        .clone()
-        .into_iter()
-        .collect();
-    let trimmed_mode_instructions = collaboration_mode_instructions.trim();
-    if !trimmed_mode_instructions.is_empty() {
-        base_prepend_developer_messages.push(trimmed_mode_instructions.to_owned());
-    }
-    if let Some(shell_style) = sess.user_shell.script_style() {
-        base_prepend_developer_messages.push(shell_style.developer_instruction().to_owned());
-    }
-    base_prepend_developer_messages.extend(
-        sess
-            .shell_style_profile_messages
-            .iter()
-            .filter_map(|message| {
-                let trimmed = message.trim();
-                (!trimmed.is_empty()).then(|| trimmed.to_owned())
-            }),
-    );
-    if sess.memories_config.use_memories {
-        let memory_context = {
-            let state = crate::codex::lock_or_panic!(sess.state);
-            crate::memories::current_context_from_runtime(
-                state.last_environment_snapshot.as_ref(),
-                sess.user_shell(),
-                &tc.cwd,
-            )
-        };
-        if let Some(memory_prompt) = crate::memories::build_memory_tool_developer_instructions(
-            sess.client.code_home(),
-            &memory_context,
-        )
-        .await
-        {
-            match crate::memories::record_memory_prompt_usage(
-                sess.client.code_home(),
-                &memory_prompt,
-            )
-            .await
-            {
-                Ok(false) => {
-                tracing::debug!(
-                    "memories prompt used canonical summary fallback; skipping epoch usage attribution"
-                );
-                }
-                Ok(true) => {}
-                Err(err) => {
-                    tracing::warn!("failed to record memories usage: {err}");
-                }
-            }
-            base_prepend_developer_messages.push(memory_prompt.instructions);
-        }
-    }
+    // Segments turned off from `/prompt` apply to this turn only.
+    let disabled_segments = &tc.disabled_prompt_segments;
+    let is_disabled = |id: &str| disabled_segments.iter().any(|disabled| disabled == id);
+    let base_prepend_developer_messages: Vec<String> = prompt_stack::prepended_developer_messages(
+        sess,
+        tc.demo_developer_message.as_deref(),
+        tc.collaboration_mode,
+        &tc.cwd,
+        !is_disabled(prompt_stack::MEMORIES_SEGMENT),
+    )
+    .await
+    .into_iter()
+    .filter(|message| !is_disabled(&message.id))
+    .map(|message| message.text)
+    .collect();
 
     let drain_scratchpad_into_attempt = |attempt_input: &mut Vec<ResponseItem>| {
         if let Some(sp) = sess.take_scratchpad() {
+            sess.note_dropped_attempt(&sp);
             inject_scratchpad_into_attempt_input(attempt_input, sp);
         }
     };
@@ -135,21 +95,28 @@ pub(super) async fn run_turn(
         let mut prompt = Prompt {
             input: attempt_input.clone(),
             store: !sess.disable_response_storage,
-            user_instructions: tc.user_instructions.clone(),
-            environment_context: Some(EnvironmentContext::new(
-                Some(tc.cwd.clone()),
-                Some(tc.approval_policy),
-                Some(tc.sandbox_policy.clone()),
-                Some(sess.user_shell.clone()),
-            )),
+            user_instructions: tc
+                .user_instructions
+                .clone()
+                .filter(|_| !is_disabled(prompt_stack::USER_INSTRUCTIONS_SEGMENT)),
+            environment_context: (!is_disabled(prompt_stack::ENVIRONMENT_CONTEXT_SEGMENT)).then(|| {
+                EnvironmentContext::new(
+                    Some(tc.cwd.clone()),
+                    Some(tc.approval_policy),
+                    Some(tc.sandbox_policy.clone()),
+                    Some(sess.user_shell.clone()),
+                )
+            }),
             tools: Vec::new(),
             status_items, // Include status items with this request
             base_instructions_override: tc.base_instructions.clone(),
             include_additional_instructions: true,
+            omit_default_developer_prompt: is_disabled(prompt_stack::DEVELOPER_PROMPT_SEGMENT),
             prepend_developer_messages,
             text_format: tc.text_format_override.clone(),
             model_override: None,
             model_family_override: None,
+            reasoning_effort_override: None,
             output_schema: tc.final_output_json_schema.clone(),
             log_tag: Some("codex/turn".to_owned()),
             session_id_override: None,
@@ -172,6 +139,7 @@ pub(super) async fn run_turn(
             &mcp_access,
             sub_id.as_str(),
         );
+        let mut omitted_mcp_tools = 0;
         let mcp_tools = if tools_config.search_tool {
             let selection = sess.mcp_tool_selection_snapshot().unwrap_or_default();
             if selection.is_empty() {
@@ -190,6 +158,17 @@ pub(super) async fn run_turn(
                 }
                 (!selected.is_empty()).then_some(selected)
             }
+        } else if let Some(budget) = tools_config.mcp_budget.as_ref() {
+            let budgeted = crate::mcp::tool_budget::apply_tool_budget(
+                allowed_mcp_tools,
+                budget,
+                &crate::mcp::tool_budget::latest_user_text(&attempt_input),
+                &sess.recent_mcp_tools_snapshot(),
+                &sess.loaded_mcp_tools_snapshot(),
+            );
+            omitted_mcp_tools = budgeted.omitted.len();
+            sess.set_omitted_mcp_tools(budgeted.omitted);
+            Some(budgeted.included)
         } else {
             Some(allowed_mcp_tools)
         };
@@ -200,6 +179,18 @@ pub(super) async fn run_turn(
             agents_active,
             sess.dynamic_tools.as_slice(),
         );
+        if omitted_mcp_tools > 0 {
+            prompt
+                .tools
+                .push(crate::openai_tools::create_tools_list_more_tool(omitted_mcp_tools));
+        }
+        sess.set_turn_tool_names(
+            prompt
+                .tools
+                .iter()
+                .map(|tool| tool.name().to_owned())
+                .collect(),
+        );
         if should_inject_search_tool_developer_instructions(&prompt.tools) {
             let search_tool_instructions = SEARCH_TOOL_DEVELOPER_INSTRUCTIONS.trim();
             if !search_tool_instructions.is_empty()
@@ -214,6 +205,12 @@ pub(super) async fn run_turn(
             }
         }
 
+        match budget::negotiate_request_budget(sess, tc, &sub_id, attempt_req, &mut prompt).await {
+            budget::BudgetOutcome::Send => {}
+            budget::BudgetOutcome::Vetoed => return Ok(Vec::new()),
+            budget::BudgetOutcome::Aborted => return Err(CodexErr::Interrupted),
+        }
+
         // Start a new scratchpad for this HTTP attempt
         sess.begin_attempt_scratchpad();
 
@@ -347,80 +344,69 @@ pub(super) async fn run_turn(
                     let _ = write!(retry_message, " at {eta}");
                 }
                 retry_message.push('…');
-                sess.notify_stream_error(&sub_id, retry_message).await;
+                sess
+                    .notify_stream_error(&sub_id, ErrorCode::UsageLimitReached, retry_message)
+                    .await;
                 drain_scratchpad_into_attempt(&mut attempt_input);
                 tokio::time::sleep(retry_after.delay).await;
                 retries = 0;
             }
             Err(CodexErr::UsageNotIncluded) => return Err(CodexErr::UsageNotIncluded),
             Err(CodexErr::QuotaExceeded) => return Err(CodexErr::QuotaExceeded),
+            Err(e @ CodexErr::ContentFiltered(_)) => return Err(e),
             Err(e) => {
                 // Detect context-window overflow and auto-run a compact summarization once
-                if !did_auto_compact
-                    && let CodexErr::Stream(msg, _maybe_delay, _req_id) = &e {
-                        let lower = msg.to_ascii_lowercase();
-                        let looks_like_context_overflow =
-                            lower.contains("exceeds the context window")
-                                || lower.contains("exceed the context window")
-                                || lower.contains("context length exceeded")
-                                || lower.contains("maximum context length")
-                                || (lower.contains("context window")
-                                    && (lower.contains("exceed")
-                                        || lower.contains("exceeded")
-                                        || lower.contains("full")
-                                        || lower.contains("too long")));
-
-                        if looks_like_context_overflow {
-                            did_auto_compact = true;
-                            sess
-                                .notify_stream_error(
-                                    &sub_id,
-                                    "Model hit context-window limit; running /compact and retrying…".to_owned(),
-                                )
-                                .await;
+                if !did_auto_compact && e.is_context_window_exceeded() {
+                        did_auto_compact = true;
+                        sess
+                            .notify_stream_error(
+                                &sub_id,
+                                ErrorCode::ContextWindowExceeded,
+                                "Model hit context-window limit; running /compact and retrying…".to_owned(),
+                            )
+                            .await;
 
-                            let previous_input_snapshot = input.clone();
-                            let compacted_history = if compact::should_use_remote_compact_task(sess) {
-                                run_inline_remote_auto_compact_task(
-                                    Arc::clone(sess),
-                                    Arc::clone(turn_context),
-                                    Vec::new(),
-                                )
-                                .await
-                            } else {
-                                compact::run_inline_auto_compact_task(
-                                    Arc::clone(sess),
-                                    Arc::clone(turn_context),
-                                )
-                                .await
-                            };
+                        let previous_input_snapshot = input.clone();
+                        let compacted_history = if compact::should_use_remote_compact_task(sess) {
+                            run_inline_remote_auto_compact_task(
+                                Arc::clone(sess),
+                                Arc::clone(turn_context),
+                                Vec::new(),
+                            )
+                            .await
+                        } else {
+                            compact::run_inline_auto_compact_task(
+                                Arc::clone(sess),
+                                Arc::clone(turn_context),
+                            )
+                            .await
+                        };
 
-                            // Reset any partial attempt state and rebuild the request payload using the
-                            // newly compacted history plus the current user turn items.
-                            sess.clear_scratchpad();
+                        // Reset any partial attempt state and rebuild the request payload using the
+                        // newly compacted history plus the current user turn items.
+                        sess.clear_scratchpad();
 
-                            if compacted_history.is_empty() {
-                                attempt_input = input.clone();
-                            } else {
-                                let mut rebuilt = compacted_history;
-                                if let Some(initial_item) = initial_user_item.clone() {
-                                    rebuilt.push(initial_item);
+                        if compacted_history.is_empty() {
+                            attempt_input = input.clone();
+                        } else {
+                            let mut rebuilt = compacted_history;
+                            if let Some(initial_item) = initial_user_item.clone() {
+                                rebuilt.push(initial_item);
+                            }
+                            if !pending_input_tail.is_empty() {
+                                let (missing_calls, filtered_outputs) =
+                                    reconcile_pending_tool_outputs(&pending_input_tail, &rebuilt, &previous_input_snapshot);
+                                if !missing_calls.is_empty() {
+                                    rebuilt.extend(missing_calls);
                                 }
-                                if !pending_input_tail.is_empty() {
-                                    let (missing_calls, filtered_outputs) =
-                                        reconcile_pending_tool_outputs(&pending_input_tail, &rebuilt, &previous_input_snapshot);
-                                    if !missing_calls.is_empty() {
-                                        rebuilt.extend(missing_calls);
-                                    }
-                                    if !filtered_outputs.is_empty() {
-                                        rebuilt.extend(filtered_outputs);
-                                    }
+                                if !filtered_outputs.is_empty() {
+                                    rebuilt.extend(filtered_outputs);
                                 }
-                                input = rebuilt.clone();
-                                attempt_input = rebuilt;
                             }
-                            continue;
+                            input = rebuilt.clone();
+                            attempt_input = rebuilt;
                         }
+                        continue;
                     }
 
                 // Use the configured provider-specific stream retry budget.
@@ -436,7 +422,7 @@ pub(super) async fn run_turn(
                     let wait_message = format!(
                         "Network unavailable; waiting to reconnect to {probe} ({e})"
                     );
-                    sess.notify_stream_error(&sub_id, wait_message).await;
+                    sess.notify_stream_error(&sub_id, e.error_code(), wait_message).await;
                     drain_scratchpad_into_attempt(&mut attempt_input);
                     wait_for_connectivity(&probe).await;
                     retries = 0;
@@ -446,7 +432,8 @@ pub(super) async fn run_turn(
                 if retries < max_retries {
                     retries += 1;
                     let (delay, retry_eta) = match e {
-                        CodexErr::Stream(_, Some(ref retry_after), _) => {
+                        CodexErr::Stream(_, Some(ref retry_after), _)
+                        | CodexErr::RateLimited(_, Some(ref retry_after)) => {
                             let eta = format_retry_eta(retry_after);
                             (retry_after.delay, eta)
                         }
@@ -467,7 +454,7 @@ pub(super) async fn run_turn(
                         let _ = write!(retry_message, " (next attempt at {eta})");
                     }
                     retry_message.push('…');
-                    sess.notify_stream_error(&sub_id, retry_message.clone()).await;
+                    sess.notify_stream_error(&sub_id, e.error_code(), retry_message.clone()).await;
                     // Pull any partial progress from this attempt and append to
                     // the next request's input so we do not lose tool progress
                     // or already-finalized items.
@@ -483,6 +470,18 @@ pub(super) async fn run_turn(
                         error = %e,
                         "stream disconnected - retries exhausted"
                     );
+                    // Keep the tool work and text this turn already finished.
+                    let dropped = attempt_input.get(input.len()..).unwrap_or_default();
+                    if let Some((items, event)) = salvage_partial_turn(
+                        dropped,
+                        sess.take_retry_prefix(),
+                        sess.take_scratchpad(),
+                        e.to_string(),
+                    ) {
+                        sess.record_conversation_items(&items).await;
+                        let event = sess.make_event(&sub_id, EventMsg::PartialTurn(event));
+                        sess.send_event(event).await;
+                    }
                     return Err(e);
                 }
             }
@@ -39,6 +39,7 @@ pub(super) async fn try_run_turn(
             sess
                 .notify_stream_error(
                     sub_id,
+                    e.error_code(),
                     format!("[transport] failed to start stream: {e}"),
                 )
                 .await;
@@ -74,6 +75,7 @@ pub(super) async fn try_run_turn(
                 return Err(e);
             }
         };
+        turn_latency_guard.observe_event(&event);
 
         match event {
             ResponseEvent::Created { .. } => {}
@@ -101,6 +103,7 @@ pub(super) async fn try_run_turn(
                         output_index,
                     });
                 } else {
+                    let item = sess.stitch_retry_prefix(item);
                     let response = handle_response_item(
                         sess,
                         turn_diff_tracker,
@@ -151,14 +154,20 @@ pub(super) async fn try_run_turn(
                 response_id: _,
                 token_usage,
             } => {
+                // A prefix not consumed by an assistant message this attempt
+                // must not leak into a later, unrelated message.
+                sess.clear_retry_prefix();
                 let (new_info, rate_limits, should_emit);
                 {
                     let mut state = crate::codex::lock_or_panic!(sess.state);
-                    let info = TokenUsageInfo::new_or_append(
+                    let mut info = TokenUsageInfo::new_or_append(
                         &state.token_usage_info,
                         &token_usage,
                         sess.client.get_model_context_window(),
                     );
+                    if let Some(info) = info.as_mut() {
+                        info.retry_wasted_output_tokens = state.retry_wasted_output_tokens;
+                    }
                     let limits = state.latest_rate_limits.clone();
                     let emit = info.is_some() || limits.is_some();
                     state.token_usage_info.clone_from(&info);
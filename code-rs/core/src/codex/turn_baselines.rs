@@ -0,0 +1,202 @@
+//! Workspace snapshots taken when user turns start, so `Op::RevertTurn` can
+//! put a turn's file changes back.
+
+use super::*;
+use code_git_tooling::CreateGhostCommitOptions;
+use code_git_tooling::GhostCommit;
+use code_git_tooling::create_ghost_commit;
+use code_git_tooling::diff_numstat;
+use code_git_tooling::restore_to_commit;
+use crate::protocol::RevertedFile;
+use crate::protocol::TurnRevertedEvent;
+
+/// Turns that can still be reverted; older snapshots are forgotten.
+const MAX_TURN_BASELINES: usize = 20;
+
+#[derive(Debug, Clone)]
+pub(super) struct TurnBaseline {
+    turn_id: String,
+    commit: GhostCommit,
+}
+
+/// Snapshots the workspace before the user turn `turn_id` changes anything.
+/// Does nothing outside a git repository or when
+/// `[ghost_commits] turn_snapshots` is off.
+pub(super) async fn capture_turn_baseline(sess: &Session, turn_id: &str) {
+    if !sess.client.config().ghost_commits.turn_snapshots {
+        return;
+    }
+    let cwd = sess.get_cwd().to_path_buf();
+    if crate::git_info::get_git_repo_root(&cwd).is_none() {
+        return;
+    }
+    let snapshot = tokio::task::spawn_blocking(move || {
+        create_ghost_commit(&CreateGhostCommitOptions::new(cwd.as_path()).message("code turn snapshot"))
+    })
+    .await;
+    let commit = match snapshot {
+        Ok(Ok(commit)) => commit,
+        Ok(Err(err)) => {
+            warn!("failed to snapshot the workspace for turn {turn_id}: {err}");
+            return;
+        }
+        Err(err) => {
+            warn!("failed to snapshot the workspace for turn {turn_id}: {err}");
+            return;
+        }
+    };
+    let mut state = crate::codex::lock_or_panic!(sess.state);
+    state.turn_baselines.push_back(TurnBaseline {
+        turn_id: turn_id.to_owned(),
+        commit,
+    });
+    while state.turn_baselines.len() > MAX_TURN_BASELINES {
+        state.turn_baselines.pop_front();
+    }
+}
+
+/// Handles `Op::RevertTurn`: restores the snapshot taken when `turn_id` (or
+/// the latest turn) started, reports the files put back, and notes the revert
+/// in the conversation so the model does not rely on the discarded edits.
+pub(super) async fn revert_turn(sess: &Arc<Session>, sub_id: &str, turn_id: Option<String>) {
+    let event = match revert_to_baseline(sess, turn_id.as_deref()).await {
+        Ok((turn_id, files)) => {
+            if !files.is_empty() {
+                let note = ResponseItem::Message {
+                    id: None,
+                    role: "developer".to_owned(),
+                    content: vec![ContentItem::InputText {
+                        text: history_note(&files),
+                    }],
+                    end_turn: None,
+                    phase: None,
+                };
+                sess.record_conversation_items(&[note]).await;
+            }
+            TurnRevertedEvent {
+                message: revert_message(&files),
+                turn_id: Some(turn_id),
+                success: true,
+                files,
+            }
+        }
+        Err(message) => TurnRevertedEvent {
+            turn_id,
+            success: false,
+            message,
+            files: Vec::new(),
+        },
+    };
+    let event = sess.make_event(sub_id, EventMsg::TurnReverted(event));
+    sess.send_event(event).await;
+}
+
+async fn revert_to_baseline(
+    sess: &Session,
+    turn_id: Option<&str>,
+) -> Result<(String, Vec<RevertedFile>), String> {
+    if sess.has_running_task() {
+        return Err("Wait for the running turn to finish before reverting it.".to_owned());
+    }
+    let (index, baseline) = {
+        let state = crate::codex::lock_or_panic!(sess.state);
+        let index = match turn_id {
+            Some(turn_id) => state
+                .turn_baselines
+                .iter()
+                .position(|baseline| baseline.turn_id == turn_id),
+            None => state.turn_baselines.len().checked_sub(1),
+        };
+        match index.and_then(|index| Some((index, state.turn_baselines.get(index)?.clone()))) {
+            Some(found) => found,
+            None if turn_id.is_some() => {
+                return Err("No workspace snapshot was taken for that turn.".to_owned());
+            }
+            None => return Err("No turn snapshots to revert yet.".to_owned()),
+        }
+    };
+
+    let cwd = sess.get_cwd().to_path_buf();
+    let baseline_id = baseline.commit.id().to_owned();
+    let restored = tokio::task::spawn_blocking(move || -> Result<Vec<RevertedFile>, String> {
+        // Snapshot the current state first; the diff against it lists what
+        // the revert puts back.
+        let current = create_ghost_commit(
+            &CreateGhostCommitOptions::new(cwd.as_path()).message("code pre-revert snapshot"),
+        )
+        .map_err(|err| format!("Failed to snapshot the workspace: {err}"))?;
+        let changes = diff_numstat(&cwd, &baseline_id, current.id())
+            .map_err(|err| format!("Failed to compare the workspace with the turn snapshot: {err}"))?;
+        restore_to_commit(&cwd, &baseline_id)
+            .map_err(|err| format!("Failed to restore workspace files: {err}"))?;
+
+        // Restoring leaves files the turns created; remove them too.
+        let repo_root = crate::git_info::get_git_repo_root(&cwd).unwrap_or_else(|| cwd.clone());
+        Ok(changes
+            .into_iter()
+            .map(|change| {
+                if change.new_file
+                    && let Err(err) = std::fs::remove_file(repo_root.join(&change.path))
+                    && err.kind() != std::io::ErrorKind::NotFound
+                {
+                    warn!("failed to remove {} while reverting: {err}", change.path);
+                }
+                RevertedFile {
+                    path: change.path,
+                    added: change.added,
+                    removed: change.removed,
+                    deleted: change.new_file,
+                }
+            })
+            .collect())
+    })
+    .await
+    .map_err(|err| format!("Failed to restore workspace files: {err}"))??;
+
+    // Snapshots of the reverted turns no longer match the workspace.
+    crate::codex::lock_or_panic!(sess.state)
+        .turn_baselines
+        .truncate(index);
+    Ok((baseline.turn_id, restored))
+}
+
+fn revert_message(files: &[RevertedFile]) -> String {
+    match files.len() {
+        0 => "The workspace already matches the start of that turn; nothing to revert.".to_owned(),
+        1 => format!("Reverted {}.", files[0].path),
+        count => format!("Reverted {count} files to how they were before the turn."),
+    }
+}
+
+fn history_note(files: &[RevertedFile]) -> String {
+    let paths: Vec<&str> = files.iter().map(|file| file.path.as_str()).collect();
+    format!(
+        "The user reverted the file changes made since an earlier turn started. These files are back to their earlier contents: {}. Re-read them before relying on edits made after that point.",
+        paths.join(", ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn file(path: &str) -> RevertedFile {
+        RevertedFile {
+            path: path.to_owned(),
+            added: Some(1),
+            removed: Some(0),
+            deleted: false,
+        }
+    }
+
+    #[test]
+    fn messages_name_a_single_file_and_count_several() {
+        assert_eq!(revert_message(&[file("src/lib.rs")]), "Reverted src/lib.rs.");
+        assert_eq!(
+            revert_message(&[file("a.rs"), file("b.rs")]),
+            "Reverted 2 files to how they were before the turn."
+        );
+        assert!(history_note(&[file("a.rs"), file("b.rs")]).contains("a.rs, b.rs"));
+    }
+}
@@ -3,6 +3,7 @@ use crate::protocol::ApprovedCommandMatchKind;
 use crate::config_profile::ConfigProfile;
 use crate::config_types::AgentConfig;
 use std::collections::HashMap;
+use std::collections::BTreeMap;
 use crate::config_types::AutoDriveSettings;
 use crate::config_types::AutoDriveModelRoutingEntry;
 use crate::config_types::AllowedCommand;
@@ -11,6 +12,7 @@ use crate::config_types::AuthCredentialsStoreMode;
 use crate::config_types::BrowserConfig;
 use crate::config_types::ClientTools;
 use crate::config_types::Notice;
+use crate::config_types::ReviewProfile;
 use crate::config_types::History;
 use crate::config_types::GithubConfig;
 use crate::config_types::ValidationConfig;
@@ -23,6 +25,9 @@ use crate::config_types::AppsSourcesToml;
 use crate::config_types::FeaturesToml;
 use crate::config_types::PluginsToml;
 use crate::config_types::resolve_memories_config;
+use crate::config_types::PrivacyConfig;
+use crate::config_types::PrivacyToml;
+use crate::config_types::resolve_privacy_config;
 use crate::config_types::Notifications;
 use crate::config_types::OtelConfig;
 use crate::config_types::OtelConfigToml;
@@ -30,12 +35,16 @@ use crate::config_types::OtelExporterKind;
 use crate::config_types::default_auto_drive_model_routing_entries;
 use crate::config_types::ProjectCommandConfig;
 use crate::config_types::ProjectHookConfig;
+use crate::config_types::SandboxPresetToml;
 use crate::config_types::SandboxWorkspaceWrite;
 use crate::config_types::ShellConfig;
 use crate::config_types::ShellPresetConfig;
 use crate::config_types::ShellStyleProfileEntry;
+use crate::config_types::EnvironmentVariablePattern;
 use crate::config_types::ShellEnvironmentPolicy;
 use crate::config_types::ShellEnvironmentPolicyToml;
+use crate::config_types::SpawnedEnvPolicy;
+use crate::config_types::SpawnedEnvToml;
 use crate::config_types::TextVerbosity;
 use crate::config_types::Tui;
 use crate::config_types::UriBasedFileOpener;
@@ -92,10 +101,13 @@ pub use sources::{
     add_project_allowed_command,
     find_code_home,
     list_mcp_servers,
+    list_project_trust,
     load_config_as_toml,
     load_global_mcp_servers,
     persist_model_selection,
     persist_shell,
+    project_trust_root,
+    remove_project_trust,
     resolve_code_path_for_read,
     set_all_shell_style_profiles,
     set_shell_style_profile_mcp_servers,
@@ -161,6 +173,7 @@ pub(crate) const DEFAULT_SUBAGENT_MAX_DEPTH: i32 = 1;
 /// the context window.
 pub(crate) const PROJECT_DOC_MAX_BYTES: usize = 32 * 1024; // 32 KiB
 pub(crate) const CONFIG_TOML_FILE: &str = "config.toml";
+const DEFAULT_HEARTBEAT_INTERVAL_SECS: u64 = 15;
 
 const DEFAULT_RESPONSES_ORIGINATOR_HEADER: &str = "code_cli_rs";
 
@@ -247,6 +260,12 @@ pub struct Config {
     /// Whether review should inherit the chat model instead of using a dedicated override.
     pub review_use_chat_model: bool,
 
+    /// Named reviewer personas from `[review_profiles.<name>]`.
+    pub review_profiles: HashMap<String, ReviewProfile>,
+
+    /// Reviewer profile applied when a review does not pick one explicitly.
+    pub review_profile: Option<String>,
+
     /// Model used to apply fixes during auto-resolve of `/review` flows.
     pub review_resolve_model: String,
 
@@ -339,6 +358,10 @@ pub struct Config {
 
     pub shell_environment_policy: ShellEnvironmentPolicy,
 
+    /// Parent environment variables withheld from exec tool calls and MCP
+    /// stdio servers.
+    pub spawned_env: SpawnedEnvPolicy,
+
     /// Shell configuration for command execution.
     /// If not set, the user's default shell is detected automatically.
     pub shell: Option<ShellConfig>,
@@ -446,6 +469,9 @@ pub struct Config {
     /// Definition for MCP servers that Codex can reach out to for tool calls.
     pub mcp_servers: HashMap<String, McpServerConfig>,
 
+    /// Enabled `[docs_bundles]` entries, searched by the `docs_search` tool.
+    pub docs_bundles: Vec<crate::docs_bundles::DocsBundle>,
+
     /// Optional ACP client tool identifiers supplied by the host IDE.
     pub experimental_client_tools: Option<ClientTools>,
 
@@ -464,6 +490,13 @@ pub struct Config {
     /// Maximum number of bytes of tool output to include in a model request.
     pub tool_output_max_bytes: usize,
 
+    /// Per-category overrides of `tool_output_max_bytes`.
+    pub tool_output_limits: crate::config_types::ToolOutputLimits,
+
+    /// How long a running turn may stay quiet before `EventMsg::Heartbeat`
+    /// is sent (and resent at the same interval). `None` disables heartbeats.
+    pub heartbeat_interval: Option<std::time::Duration>,
+
     /// Ordered list of fallback filenames to consider when loading project docs.
     pub project_doc_fallback_filenames: Vec<String>,
 
@@ -571,6 +604,17 @@ pub struct Config {
     pub tools_web_search_external: bool,
     /// Enable MCP tool discovery helper (`search_tool_bm25`).
     pub tools_search_tool: bool,
+    /// Enable project notes in `.code/memory` (`memory_put` /
+    /// `memory_search`), shown to the model at session start.
+    pub tools_project_memory: bool,
+    /// Enable the `capabilities` tool describing the session's sandbox,
+    /// approvals, tools and budgets.
+    pub tools_capabilities: bool,
+    /// Enable the `scratch` tool, a per-session directory outside the
+    /// workspace for temporary files.
+    pub tools_scratch: bool,
+    /// Per-request MCP tool schema budget (`[tools.mcp_budget]`).
+    pub tools_mcp_budget: Option<crate::config_types::McpToolBudgetConfig>,
     /// Enable the optional `repl` tool (off by default).
     pub tools_repl: bool,
     /// Per-runtime enabled flags.  `true` means the runtime will be probed
@@ -612,6 +656,9 @@ pub struct Config {
     pub repl_node_module_dirs: Vec<PathBuf>,
     /// Optional allow-list of domains for `web_search` `filters.allowed_domains`
     pub tools_web_search_allowed_domains: Option<Vec<String>>,
+    /// Non-native `web_search` backend from `[web_search]`; `None` uses the
+    /// model provider's tool.
+    pub web_search_provider: Option<crate::web_search::WebSearchProvider>,
     /// Experimental: enable streamable shell tool selection (off by default).
     pub use_experimental_streamable_shell_tool: bool,
     /// Enable the `image_view` tool that lets the agent attach local images.
@@ -629,6 +676,8 @@ pub struct Config {
     pub active_profile_memories: Option<MemoriesToml>,
     /// Raw memories override from the current project, if any.
     pub project_memories: Option<MemoriesToml>,
+    /// `[privacy]` settings with the current project's overrides applied.
+    pub privacy: PrivacyConfig,
     /// Experimental: enable JSON-based environment context snapshots and deltas (phase gated).
     pub env_ctx_v2: bool,
     /// Retention policy for `env_ctx_v2` timeline management (gated by `env_ctx_v2`).
@@ -656,6 +705,24 @@ pub struct Config {
     /// Validation harness configuration.
     pub validation: ValidationConfig,
 
+    /// Ghost commit retention.
+    pub ghost_commits: crate::config_types::GhostCommitsConfig,
+
+    /// Style guide for `exec --final-message-style concise`.
+    pub final_message_style: crate::config_types::FinalMessageStyleConfig,
+
+    /// Review settings (`[review]`).
+    pub review: crate::config_types::ReviewConfig,
+
+    /// Auto Review finding thresholds and path filters (`[auto_review]`).
+    pub auto_review: crate::config_types::AutoReviewConfig,
+
+    /// Handling of LFS, binary, and very large files in diffs.
+    pub large_files: crate::config_types::LargeFilesConfig,
+
+    /// Safety nets around model-run git commands.
+    pub git_safety: crate::config_types::GitSafetyConfig,
+
     /// Resolved subagent command configurations (including custom ones).
     /// If a command with name `plan|solve|code` exists here, it overrides
     /// the built-in defaults for that slash command.
@@ -687,6 +754,13 @@ pub struct Config {
     ///
     /// Set by exec; not loaded from config.toml.
     pub timeboxed_exec_mode: bool,
+
+    /// Announce every model request with `EventMsg::RequestBudgetCheck` and
+    /// wait for `Op::RequestBudgetDecision` before sending it.
+    ///
+    /// Set by embedding applications that answer those events; not loaded
+    /// from config.toml.
+    pub request_budget_negotiation: bool,
 }
 
 impl Config {
@@ -860,6 +934,13 @@ pub struct ConfigToml {
     #[serde(default)]
     pub review_use_chat_model: bool,
 
+    /// Named reviewer personas (strictness, focus areas, tone, checklist)
+    /// that parameterize the review prompt.
+    #[serde(default)]
+    pub review_profiles: HashMap<String, ReviewProfile>,
+    /// Reviewer profile used when `/review` does not pass `--profile`.
+    pub review_profile: Option<String>,
+
     /// Resolve model override used during auto-resolve for `/review`.
     pub review_resolve_model: Option<String>,
     /// Reasoning effort override used for the resolve model.
@@ -902,6 +983,11 @@ pub struct ConfigToml {
     #[serde(default)]
     pub shell_environment_policy: ShellEnvironmentPolicyToml,
 
+    /// Which parent environment variables reach exec tool calls and MCP
+    /// stdio servers.
+    #[serde(default)]
+    pub spawned_env: SpawnedEnvToml,
+
     /// Shell configuration for command execution.
     /// If not set, the user's default shell is detected automatically.
     pub shell: Option<ShellConfig>,
@@ -930,6 +1016,14 @@ pub struct ConfigToml {
     /// Sandbox configuration to apply if `sandbox` is `WorkspaceWrite`.
     pub sandbox_workspace_write: Option<SandboxWorkspaceWrite>,
 
+    /// Named sandbox policies that `sandbox_preset`, profiles, and
+    /// `--sandbox-preset` can refer to.
+    #[serde(default)]
+    pub sandbox_presets: HashMap<String, SandboxPresetToml>,
+
+    /// Default sandbox preset applied when neither a profile nor the CLI picks one.
+    pub sandbox_preset: Option<String>,
+
     /// Limits applied to tool-spawned processes (Linux cgroup v2 when available).
     ///
     /// Persisted under `[exec_limits]` in `config.toml`.
@@ -966,6 +1060,15 @@ pub struct ConfigToml {
     #[schemars(schema_with = "crate::config::schema::mcp_servers_schema")]
     pub mcp_servers: HashMap<String, McpServerConfig>,
 
+    /// Local documentation bundles exposed through the `docs_search` tool,
+    /// keyed by bundle name.
+    #[serde(default)]
+    pub docs_bundles: BTreeMap<String, crate::config_types::DocsBundleToml>,
+
+    /// Backend for the `web_search` tool.
+    #[serde(default)]
+    pub web_search: Option<crate::config_types::WebSearchToml>,
+
     /// Preferred store for MCP OAuth credentials (used by streamable HTTP MCP servers).
     ///
     /// - `auto` (default): keyring if available, otherwise file.
@@ -1001,6 +1104,15 @@ pub struct ConfigToml {
     /// Maximum number of bytes of tool output to include in a model request.
     pub tool_output_max_bytes: Option<usize>,
 
+    /// Per-category tool output budgets in bytes (`shell`, `file_read`, `mcp`,
+    /// `browser`); unset categories use `tool_output_max_bytes`.
+    #[serde(default)]
+    pub tool_output_limits: Option<crate::config_types::ToolOutputLimits>,
+
+    /// Seconds a running turn may stay quiet before a heartbeat event is
+    /// emitted (default: 15). Set to 0 to disable heartbeats.
+    pub heartbeat_interval_secs: Option<u64>,
+
     /// Ordered list of fallback filenames to look for when AGENTS.md is missing.
     pub project_doc_fallback_filenames: Option<Vec<String>>,
 
@@ -1135,6 +1247,11 @@ pub struct ConfigToml {
     /// Memory subsystem configuration.
     pub memories: Option<MemoriesToml>,
 
+    /// What is persisted or sent about each session: transcripts, status
+    /// item screenshots and environment details, and how long sessions are
+    /// kept.
+    pub privacy: Option<PrivacyToml>,
+
     /// When true, disables burst-paste detection for typed input entirely.
     /// All characters are inserted as they are received, and no buffering
     /// or placeholder replacement will occur for fast keypress bursts.
@@ -1146,6 +1263,29 @@ pub struct ConfigToml {
     /// Validation harness configuration.
     pub validation: Option<ValidationConfig>,
 
+    /// Ghost commit retention.
+    pub ghost_commits: Option<crate::config_types::GhostCommitsConfig>,
+
+    /// Style guide for `exec --final-message-style concise`.
+    pub final_message_style: Option<crate::config_types::FinalMessageStyleConfig>,
+
+    /// Review settings, including how background review worktrees are
+    /// checked out.
+    pub review: Option<crate::config_types::ReviewConfig>,
+
+    /// Priority and confidence thresholds a background Auto Review finding
+    /// must meet to count as an issue and trigger auto-resolve, and the paths
+    /// whose changes start a review.
+    pub auto_review: Option<crate::config_types::AutoReviewConfig>,
+
+    /// How Git LFS, binary, and very large files appear in turn diffs and
+    /// review scopes.
+    pub large_files: Option<crate::config_types::LargeFilesConfig>,
+
+    /// Safety nets around git commands the model runs, such as stashing
+    /// uncommitted changes before a checkout or reset.
+    pub git_safety: Option<crate::config_types::GitSafetyConfig>,
+
     /// Configuration for subagent commands (built-ins and custom).
     #[serde(default)]
     pub subagents: Option<crate::config_types::SubagentsToml>,
@@ -1193,6 +1333,8 @@ pub struct ProjectConfig {
     #[serde(default)]
     pub memories: Option<MemoriesToml>,
     #[serde(default)]
+    pub privacy: Option<PrivacyToml>,
+    #[serde(default)]
     pub always_allow_commands: Option<Vec<AllowedCommand>>,
     #[serde(default)]
     pub hooks: Vec<ProjectHookConfig>,
@@ -1454,6 +1596,26 @@ pub struct ToolsToml {
     #[serde(default)]
     pub search_tool: Option<bool>,
 
+    /// Keep project notes in `.code/memory` through the `memory_put` and
+    /// `memory_search` tools (off by default).
+    #[serde(default)]
+    pub project_memory: Option<bool>,
+
+    /// Let the model ask for its sandbox, approval policy, tools and budgets
+    /// through the `capabilities` tool (off by default).
+    #[serde(default)]
+    pub capabilities: Option<bool>,
+
+    /// Give the model a per-session scratch directory outside the workspace
+    /// through the `scratch` tool (off by default).
+    #[serde(default)]
+    pub scratch: Option<bool>,
+
+    /// Cap the MCP tool schemas sent per request, keeping the most relevant
+    /// ones and letting the model load the rest with `tools_list_more`.
+    #[serde(default)]
+    pub mcp_budget: Option<crate::config_types::McpToolBudgetConfig>,
+
     /// Enable the optional `repl` tool (off by default).
     #[serde(default)]
     pub repl: Option<bool>,
@@ -1625,6 +1787,32 @@ pub fn apply_exec_limits_settings(settings: &ExecLimitsToml) -> anyhow::Result<(
     Ok(())
 }
 
+/// Publishes the `[network]` proxy and CA settings to every HTTP client built
+/// from here on. An unusable `ca_bundle` is a hard error: silently falling
+/// back to the system roots would only resurface as TLS failures later.
+pub fn apply_outbound_network_settings(
+    network: Option<&NetworkProxySettingsToml>,
+) -> std::io::Result<()> {
+    let settings = network
+        .map(NetworkProxySettingsToml::outbound_network_settings)
+        .unwrap_or_default();
+    if let Some(path) = &settings.ca_bundle {
+        crate::http_client::validate_ca_bundle(path).map_err(|err| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("network.ca_bundle: {err}"),
+            )
+        })?;
+    }
+    #[cfg(feature = "browser-automation")]
+    code_browser::global::set_default_proxy(code_browser::global::DefaultProxy {
+        server: settings.chrome_proxy_server(),
+        bypass_list: settings.chrome_proxy_bypass_list(),
+    });
+    crate::http_client::set_outbound_network_settings(settings);
+    Ok(())
+}
+
 /// Returns the effective pids limit after applying user overrides.
 /// Falls back to auto when the override is set to Auto mode.
 pub fn exec_limits_effective_pids_max() -> Option<u64> {
@@ -1783,6 +1971,27 @@ pub struct NetworkProxySettingsToml {
 
     /// Allow binding loopback listeners from within mediated sessions.
     pub allow_local_binding: bool,
+
+    /// Record every destination host and port proxied tool calls connect to
+    /// in `network-egress.jsonl` and summarize them at the end of each turn.
+    pub log_egress: bool,
+
+    /// Upstream proxy for `http://` requests made by Code itself (model
+    /// providers, MCP HTTP servers, `web_fetch`, the internal browser).
+    /// Falls back to `HTTP_PROXY`.
+    pub http_proxy: Option<String>,
+
+    /// Upstream proxy for `https://` requests made by Code itself. Falls back
+    /// to `HTTPS_PROXY`, then to the HTTP proxy.
+    pub https_proxy: Option<String>,
+
+    /// Hosts, domain suffixes (`.corp.example`) and CIDR ranges reached
+    /// without the upstream proxy. Falls back to `NO_PROXY`.
+    pub no_proxy: Vec<String>,
+
+    /// PEM bundle of extra root certificates (e.g. a corporate TLS
+    /// inspection CA) trusted by every outbound HTTPS client.
+    pub ca_bundle: Option<PathBuf>,
 }
 
 impl Default for NetworkProxySettingsToml {
@@ -1802,11 +2011,25 @@ impl Default for NetworkProxySettingsToml {
             denied_domains: Vec::new(),
             allow_unix_sockets: Vec::new(),
             allow_local_binding: true,
+            log_egress: true,
+            http_proxy: None,
+            https_proxy: None,
+            no_proxy: Vec::new(),
+            ca_bundle: None,
         }
     }
 }
 
 impl NetworkProxySettingsToml {
+    pub fn outbound_network_settings(&self) -> crate::http_client::OutboundNetworkSettings {
+        crate::http_client::OutboundNetworkSettings {
+            http_proxy: self.http_proxy.clone(),
+            https_proxy: self.https_proxy.clone(),
+            no_proxy: self.no_proxy.clone(),
+            ca_bundle: self.ca_bundle.clone(),
+        }
+    }
+
     #[cfg(feature = "managed-network-proxy")]
     pub(crate) fn to_network_proxy_config(&self) -> code_network_proxy::NetworkProxyConfig {
         let mut cfg = code_network_proxy::NetworkProxyConfig::default();
@@ -1873,12 +2096,14 @@ impl ConfigToml {
                 Some(SandboxWorkspaceWrite {
                     writable_roots,
                     network_access,
+                    network_allowlist,
                     exclude_tmpdir_env_var,
                     exclude_slash_tmp,
                     allow_git_writes,
                 }) => SandboxPolicy::WorkspaceWrite {
                     writable_roots: writable_roots.clone(),
                     network_access: *network_access,
+                    network_allowlist: network_allowlist.clone(),
                     exclude_tmpdir_env_var: *exclude_tmpdir_env_var,
                     exclude_slash_tmp: *exclude_slash_tmp,
                     allow_git_writes: *allow_git_writes,
@@ -1944,6 +2169,7 @@ pub struct ConfigOverrides {
     pub cwd: Option<PathBuf>,
     pub approval_policy: Option<AskForApproval>,
     pub sandbox_mode: Option<SandboxMode>,
+    pub sandbox_preset: Option<String>,
     pub model_provider: Option<String>,
     pub config_profile: Option<String>,
     pub code_linux_sandbox_exe: Option<PathBuf>,
@@ -1982,6 +2208,7 @@ impl Config {
             cwd,
             approval_policy,
             sandbox_mode,
+            sandbox_preset,
             model_provider,
             config_profile: config_profile_key,
             code_linux_sandbox_exe,
@@ -2070,10 +2297,33 @@ impl Config {
             })?
             .clone();
 
+        // Sandbox preset precedence: CLI > profile > global config.toml.
+        let sandbox_preset = match sandbox_preset
+            .or_else(|| config_profile.sandbox_preset.clone())
+            .or_else(|| cfg.sandbox_preset.clone())
+        {
+            Some(name) => Some(cfg.sandbox_presets.remove(&name).ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!("sandbox preset `{name}` not found in [sandbox_presets]"),
+                )
+            })?),
+            None => None,
+        };
+
         // Capture workspace-write details early to avoid borrow after partial moves
-        let cfg_workspace = cfg.sandbox_workspace_write.clone();
+        let cfg_workspace = match &sandbox_preset {
+            Some(preset) => Some(preset.workspace_write()),
+            None => cfg.sandbox_workspace_write.clone(),
+        };
 
-        let shell_environment_policy = cfg.shell_environment_policy.into();
+        let mut shell_environment_policy: ShellEnvironmentPolicy = cfg.shell_environment_policy.into();
+        if let Some(allowlist) = sandbox_preset.as_ref().and_then(|preset| preset.env_allowlist.as_ref()) {
+            shell_environment_policy.include_only = allowlist
+                .iter()
+                .map(|pattern| EnvironmentVariablePattern::new_case_insensitive(pattern))
+                .collect();
+        }
 
         let resolved_cwd = {
             use std::env;
@@ -2105,8 +2355,9 @@ impl Config {
             .as_ref()
             .and_then(|m| m.get(&project_key));
         // Resolve sandbox mode with correct precedence:
-        // CLI override > per-project override > global config.toml > default
+        // CLI override > sandbox preset > per-project override > global config.toml > default
         let effective_sandbox_mode = sandbox_mode
+            .or_else(|| sandbox_preset.as_ref().and_then(|preset| preset.sandbox_mode))
             .or_else(|| project_override.and_then(|p| p.sandbox_mode))
             .or(cfg.sandbox_mode)
             .unwrap_or_default();
@@ -2116,12 +2367,14 @@ impl Config {
                 Some(SandboxWorkspaceWrite {
                     writable_roots,
                     network_access,
+                    network_allowlist,
                     exclude_tmpdir_env_var,
                     exclude_slash_tmp,
                     allow_git_writes,
                 }) => SandboxPolicy::WorkspaceWrite {
                     writable_roots,
                     network_access,
+                    network_allowlist,
                     exclude_tmpdir_env_var,
                     exclude_slash_tmp,
                     allow_git_writes,
@@ -2130,6 +2383,7 @@ impl Config {
             },
             SandboxMode::DangerFullAccess => SandboxPolicy::DangerFullAccess,
         };
+        crate::network_allowlist::validate_network_allowlist(sandbox_policy.network_allowlist())?;
         // Resolve approval policy with precedence:
         // CLI override > profile override > per-project override > global config.toml > default
         let effective_approval = approval_policy
@@ -2159,6 +2413,23 @@ impl Config {
                     ));
                 }
             }
+        // `.code/approvals.toml` is project-controlled, so only trusted
+        // projects get to pre-approve commands.
+        if cfg.is_cwd_trusted(&resolved_cwd) {
+            match crate::approvals_store::load_approvals(&resolved_cwd) {
+                Ok(stored) => {
+                    for approval in stored {
+                        let semantic = matches!(approval.match_kind, ApprovedCommandMatchKind::Prefix)
+                            .then(|| approval.argv.clone());
+                        let pattern = ApprovedCommandPattern::new(approval.argv, approval.match_kind, semantic);
+                        if !always_allow_commands.contains(&pattern) {
+                            always_allow_commands.push(pattern);
+                        }
+                    }
+                }
+                Err(err) => tracing::warn!("ignoring stored command approvals: {err}"),
+            }
+        }
 
         let project_hooks = project_override
             .map(|cfg| ProjectHooks::from_configs(&cfg.hooks, &resolved_cwd))
@@ -2180,6 +2451,22 @@ impl Config {
             .as_ref()
             .and_then(|t| t.search_tool)
             .unwrap_or(false);
+        let tools_project_memory = cfg
+            .tools
+            .as_ref()
+            .and_then(|t| t.project_memory)
+            .unwrap_or(false);
+        let tools_capabilities = cfg
+            .tools
+            .as_ref()
+            .and_then(|t| t.capabilities)
+            .unwrap_or(false);
+        let tools_scratch = cfg
+            .tools
+            .as_ref()
+            .and_then(|t| t.scratch)
+            .unwrap_or(false);
+        let tools_mcp_budget = cfg.tools.as_ref().and_then(|t| t.mcp_budget.clone());
         let tools_repl = cfg.tools.as_ref().and_then(|t| t.repl).unwrap_or(false);
         let repl_node_enabled = cfg.tools.as_ref().and_then(|t| t.repl_node_enabled).unwrap_or(true);
         let repl_deno_enabled = cfg.tools.as_ref().and_then(|t| t.repl_deno_enabled).unwrap_or(true);
@@ -2286,6 +2573,10 @@ impl Config {
             .tools
             .as_ref()
             .and_then(|t| t.web_search_allowed_domains.clone());
+        let web_search_provider = match config_profile.web_search.as_ref().or(cfg.web_search.as_ref()) {
+            Some(web_search) => crate::web_search::WebSearchProvider::from_toml(web_search)?,
+            None => None,
+        };
         // View Image tool is enabled by default; can be disabled in config or overrides.
         let include_view_image_tool_flag = include_view_image_tool
             .or_else(|| cfg.tools.as_ref().and_then(|t| t.view_image))
@@ -2294,13 +2585,13 @@ impl Config {
         let network_proxy = {
             #[cfg(feature = "managed-network-proxy")]
             {
-                cfg.network
-                    .as_ref()
-                    .filter(|net| net.enabled)
-                    .map(|net| {
-                        network_proxy_spec::NetworkProxySpec::from_config(net.to_network_proxy_config())
-                    })
-                    .transpose()?
+                crate::network_allowlist::effective_network_settings(
+                    cfg.network.as_ref(),
+                    sandbox_policy.network_allowlist(),
+                )
+                .filter(|net| net.enabled)
+                .map(|net| network_proxy_spec::NetworkProxySpec::from_config(net.to_network_proxy_config()))
+                .transpose()?
             }
             #[cfg(not(feature = "managed-network-proxy"))]
             {
@@ -2309,6 +2600,11 @@ impl Config {
                         "Managed network mediation is not available in this build; ignoring `[network]` settings."
                     );
                 }
+                if !sandbox_policy.network_allowlist().is_empty() {
+                    tracing::warn!(
+                        "The sandbox network allowlist needs managed network mediation, which is not available in this build; network access stays blocked."
+                    );
+                }
                 None
             }
         };
@@ -2333,6 +2629,10 @@ impl Config {
             memories.generate_memories = false;
             memories.use_memories = false;
         }
+        let privacy = resolve_privacy_config(
+            cfg.privacy.as_ref(),
+            project_override.and_then(|project| project.privacy.as_ref()),
+        );
 
         let env_ctx_v2_flag = *crate::flags::CTX_UI;
 
@@ -2478,6 +2778,17 @@ impl Config {
             }
         }
 
+        let review_profiles = cfg.review_profiles;
+        let review_profile = cfg.review_profile;
+        if let Some(name) = review_profile.as_deref()
+            && !review_profiles.contains_key(name)
+        {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("review_profile `{name}` is not defined under [review_profiles]"),
+            ));
+        }
+
         // Default review model when not set in config; allow CLI override to take precedence.
         let review_model = override_review_model
             .or_else(|| config_profile.review_model.clone())
@@ -2648,6 +2959,14 @@ impl Config {
             ));
         }
 
+        let auto_review = cfg.auto_review.unwrap_or_default();
+        if let Err(err) = auto_review.path_filter() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("invalid auto_review include/exclude glob: {err}"),
+            ));
+        }
+
         if auto_drive.model_routing_enabled
             && !auto_drive
                 .model_routing_entries
@@ -2688,6 +3007,7 @@ impl Config {
 
         let prevent_idle_sleep = tui_config.prevent_idle_sleep;
 
+        let docs_bundles = crate::docs_bundles::resolve_docs_bundles(&code_home, &cfg.docs_bundles);
         let config = Self {
             model,
             model_explicit,
@@ -2697,6 +3017,8 @@ impl Config {
             review_model,
             review_model_reasoning_effort,
             review_use_chat_model,
+            review_profiles,
+            review_profile,
             review_resolve_model,
             review_resolve_model_reasoning_effort,
             review_resolve_use_chat_model,
@@ -2724,6 +3046,7 @@ impl Config {
             project_commands,
             lifecycle_hooks: cfg.lifecycle_hooks.unwrap_or_default(),
             shell_environment_policy,
+            spawned_env: cfg.spawned_env.into(),
             shell: cfg.shell,
             zsh_path: cfg.zsh_path,
             main_execve_wrapper_exe: cfg.main_execve_wrapper_exe,
@@ -2745,6 +3068,7 @@ impl Config {
             personality_traits,
             compact_prompt_override,
             mcp_servers: cfg.mcp_servers,
+            docs_bundles,
             experimental_client_tools: cfg.experimental_client_tools.clone(),
             dynamic_tools: cfg.dynamic_tools.unwrap_or_default(),
             agents,
@@ -2753,6 +3077,11 @@ impl Config {
             tool_output_max_bytes: cfg
                 .tool_output_max_bytes
                 .unwrap_or(default_tool_output_max_bytes),
+            tool_output_limits: cfg.tool_output_limits.unwrap_or_default(),
+            heartbeat_interval: match cfg.heartbeat_interval_secs.unwrap_or(DEFAULT_HEARTBEAT_INTERVAL_SECS) {
+                0 => None,
+                secs => Some(std::time::Duration::from_secs(secs)),
+            },
             project_doc_fallback_filenames: cfg
                 .project_doc_fallback_filenames
                 .unwrap_or_default()
@@ -2823,6 +3152,10 @@ impl Config {
             tools_web_search_request,
             tools_web_search_external,
             tools_search_tool,
+            tools_project_memory,
+            tools_capabilities,
+            tools_scratch,
+            tools_mcp_budget,
             tools_repl,
             repl_node_enabled,
             repl_deno_enabled,
@@ -2839,6 +3172,7 @@ impl Config {
             repl_python_args,
             repl_node_module_dirs,
             tools_web_search_allowed_domains,
+            web_search_provider,
             // Honor upstream opt-in switch name for our experimental streamable shell tool.
             use_experimental_streamable_shell_tool: cfg
                 .experimental_use_exec_command_tool
@@ -2850,6 +3184,7 @@ impl Config {
             global_memories,
             active_profile_memories,
             project_memories,
+            privacy,
             env_ctx_v2: env_ctx_v2_flag,
             retention: crate::config_types::RetentionConfig::default(),
             responses_originator_header,
@@ -2860,6 +3195,12 @@ impl Config {
             api_key_fallback_on_all_accounts_limited,
             github: cfg.github.unwrap_or_default(),
             validation: cfg.validation.unwrap_or_default(),
+            ghost_commits: cfg.ghost_commits.unwrap_or_default(),
+            final_message_style: cfg.final_message_style.unwrap_or_default(),
+            review: cfg.review.unwrap_or_default(),
+            auto_review,
+            large_files: cfg.large_files.unwrap_or_default(),
+            git_safety: cfg.git_safety.unwrap_or_default(),
             subagent_commands: cfg
                 .subagents
                 .map(|s| s.commands)
@@ -2869,6 +3210,7 @@ impl Config {
             max_run_seconds: None,
             max_run_deadline: None,
             timeboxed_exec_mode: false,
+            request_budget_negotiation: false,
             // Surface TUI notifications preference from config when present.
             tui_notifications: tui_config.notifications,
             auto_drive_observer_cadence: cfg.auto_drive_observer_cadence.unwrap_or(5),
@@ -2888,6 +3230,7 @@ impl Config {
         };
         apply_exec_limits_settings(&config.exec_limits)
             .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err.to_string()))?;
+        apply_outbound_network_settings(config.network.as_ref())?;
         Ok(config)
     }
 
@@ -3031,6 +3374,7 @@ mod tests {
     use crate::config_types::HistoryPersistence;
     use crate::config_types::McpServerTransportConfig;
     use crate::config_types::Notifications;
+    use crate::config_types::ToolOutputCategory;
     use crate::model_provider_info::OPENAI_API_BASE_URL;
 
     use super::*;
@@ -3162,14 +3506,19 @@ persistence = "none"
             default_config.model_family.tool_output_max_bytes()
         );
 
-        let cfg = toml::from_str::<ConfigToml>("tool_output_max_bytes = 65536")
-            .or_panic("TOML should deserialize");
+        let cfg = toml::from_str::<ConfigToml>(
+            "tool_output_max_bytes = 65536\n[tool_output_limits]\nshell = 8192\n",
+        )
+        .or_panic("TOML should deserialize");
         let overridden = Config::load_from_base_config_with_overrides(
             cfg,
             ConfigOverrides::default(),
             code_home.path().to_path_buf(),
         )?;
         assert_eq!(overridden.tool_output_max_bytes, 65_536);
+        let limits = overridden.tool_output_limits;
+        assert_eq!(limits.budget(ToolOutputCategory::Shell, 65_536), 8_192);
+        assert_eq!(limits.budget(ToolOutputCategory::Mcp, 65_536), 65_536);
         Ok(())
     }
 
@@ -3290,6 +3639,7 @@ exclude_slash_tmp = true
             SandboxPolicy::WorkspaceWrite {
                 writable_roots: vec![PathBuf::from("/my/workspace")],
                 network_access: false,
+                network_allowlist: vec![],
                 exclude_tmpdir_env_var: true,
                 exclude_slash_tmp: true,
                 allow_git_writes: true,
@@ -4289,6 +4639,61 @@ model_verbosity = "high"
         Ok(())
     }
 
+    #[test]
+    fn sandbox_preset_from_profile_sets_policy_and_env_allowlist() -> std::io::Result<()> {
+        let fixture = create_test_fixture()?;
+        let mut cfg = fixture.cfg.clone();
+        let presets: ConfigToml = toml::from_str(
+            r#"
+sandbox_mode = "read-only"
+
+[sandbox_presets.ci]
+sandbox_mode = "workspace-write"
+writable_roots = ["/tmp/ci-cache"]
+network_access = true
+env_allowlist = ["PATH", "CI_*"]
+"#,
+        )
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+        cfg.sandbox_mode = presets.sandbox_mode;
+        cfg.sandbox_presets = presets.sandbox_presets;
+        cfg.profiles.entry("gpt3".to_string()).or_default().sandbox_preset = Some("ci".to_string());
+
+        let resolved = Config::load_from_base_config_with_overrides(
+            cfg.clone(),
+            ConfigOverrides {
+                cwd: Some(fixture.cwd()),
+                ..Default::default()
+            },
+            fixture.code_home(),
+        )?;
+        assert_eq!(
+            resolved.sandbox_policy,
+            SandboxPolicy::WorkspaceWrite {
+                writable_roots: vec![PathBuf::from("/tmp/ci-cache")],
+                network_access: true,
+                network_allowlist: vec![],
+                exclude_tmpdir_env_var: false,
+                exclude_slash_tmp: false,
+                allow_git_writes: true,
+            }
+        );
+        assert_eq!(resolved.shell_environment_policy.include_only.len(), 2);
+
+        let missing = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides {
+                cwd: Some(fixture.cwd()),
+                sandbox_preset: Some("nightly".to_string()),
+                ..Default::default()
+            },
+            fixture.code_home(),
+        )
+        .expect_err("unknown preset is rejected");
+        assert_eq!(missing.kind(), std::io::ErrorKind::NotFound);
+        Ok(())
+    }
+
     #[test]
     fn test_compact_prompt_override_file_populates_string() -> std::io::Result<()> {
         let fixture = create_test_fixture()?;
@@ -4661,6 +5066,40 @@ module_dirs = ["/nested/node_modules"]
         Ok(())
     }
 
+    #[test]
+    fn test_remove_project_trust_round_trips_with_list() -> anyhow::Result<()> {
+        let code_home = temp_dir_or_panic();
+        let trusted_dir = temp_dir_or_panic();
+        let other_dir = temp_dir_or_panic();
+
+        set_project_trusted(code_home.path(), trusted_dir.path())?;
+        set_project_access_mode(
+            code_home.path(),
+            other_dir.path(),
+            AskForApproval::OnRequest,
+            SandboxMode::WorkspaceWrite,
+        )?;
+        set_project_trusted(code_home.path(), other_dir.path())?;
+        assert_eq!(list_project_trust(code_home.path())?.len(), 2);
+
+        assert!(remove_project_trust(code_home.path(), trusted_dir.path())?);
+        assert!(!remove_project_trust(code_home.path(), trusted_dir.path())?);
+        assert!(remove_project_trust(code_home.path(), other_dir.path())?);
+        assert_eq!(list_project_trust(code_home.path())?, Vec::new());
+
+        // Other per-project settings survive losing trust.
+        let contents = std::fs::read_to_string(code_home.path().join(CONFIG_TOML_FILE))?;
+        let parsed: toml::Value = toml::from_str(&contents)?;
+        let projects = parsed
+            .get("projects")
+            .and_then(toml::Value::as_table)
+            .ok_or_else(|| anyhow::anyhow!("projects table missing"))?;
+        assert!(!projects.contains_key(trusted_dir.path().to_string_lossy().as_ref()));
+        assert!(projects.contains_key(other_dir.path().to_string_lossy().as_ref()));
+
+        Ok(())
+    }
+
     #[test]
     fn shell_style_profile_skill_mode_updates_existing_alias_key() -> anyhow::Result<()> {
         let code_home = temp_dir_or_panic();
@@ -69,11 +69,7 @@ impl ConfigBuilder {
             self.loader_overrides,
         )?;
 
-        let mut constrained_approval_policy = requirements.approval_policy;
-        constrained_approval_policy
-            .set(config.approval_policy)
-            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?;
-        config.approval_policy = constrained_approval_policy.value();
+        requirements.enforce(&mut config)?;
 
         // Merge enabled plugin-provided MCP servers into the effective runtime config.
         //
@@ -86,7 +82,7 @@ impl ConfigBuilder {
                 .unwrap_or_default();
         let plugin_manager = crate::plugins::PluginsManager::new(config.code_home.clone());
         for (name, cfg) in plugin_manager.effective_mcp_servers() {
-            if disabled_mcp_server_names.contains(&name) {
+            if disabled_mcp_server_names.contains(&name) || !requirements.allows_mcp_server(&name) {
                 continue;
             }
             config.mcp_servers.entry(name).or_insert(cfg);
@@ -1,6 +1,7 @@
 #[cfg(feature = "managed-network-proxy")]
 mod imp {
     use async_trait::async_trait;
+    use code_network_proxy::AllowedRequestObserver;
     use code_network_proxy::BlockedRequestObserver;
     use code_network_proxy::ConfigReloader;
     use code_network_proxy::ConfigState;
@@ -84,6 +85,7 @@ mod imp {
             sandbox_policy: &SandboxPolicy,
             policy_decider: Option<Arc<dyn NetworkPolicyDecider>>,
             blocked_request_observer: Option<Arc<dyn BlockedRequestObserver>>,
+            allowed_request_observer: Option<Arc<dyn AllowedRequestObserver>>,
             enable_network_approval_flow: bool,
         ) -> std::io::Result<StartedNetworkProxy> {
             let state =
@@ -115,6 +117,10 @@ mod imp {
                 builder = builder.blocked_request_observer_arc(blocked_request_observer);
             }
 
+            if let Some(allowed_request_observer) = allowed_request_observer {
+                builder = builder.allowed_request_observer_arc(allowed_request_observer);
+            }
+
             let proxy = builder.build().await.map_err(|err| {
                 std::io::Error::other(format!("failed to build network proxy: {err}"))
             })?;
@@ -149,6 +155,7 @@ mod imp {
             _sandbox_policy: &SandboxPolicy,
             _policy_decider: Option<()>,
             _blocked_request_observer: Option<()>,
+            _allowed_request_observer: Option<()>,
             _enable_network_approval_flow: bool,
         ) -> std::io::Result<StartedNetworkProxy> {
             Err(std::io::Error::new(
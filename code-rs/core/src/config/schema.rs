@@ -9,8 +9,21 @@ use schemars::schema::Schema;
 use schemars::schema::SchemaObject;
 use serde_json::Map;
 use serde_json::Value;
+use std::collections::HashSet;
 use std::path::Path;
 
+/// Extension keyword listing the config layers that may set a top-level key.
+pub const LAYERS_KEYWORD: &str = "x-code-layers";
+
+/// Config layers in precedence order, lowest first: `/etc/code/config.toml`,
+/// `CODE_HOME/config.toml`, trusted `.code/config.toml` files, the active
+/// `[profiles.<name>]` table, `-c key=value` overrides, and managed config.
+const CONFIG_LAYERS: [&str; 6] = ["system", "user", "project", "profile", "cli", "managed"];
+
+/// Keys only read from the system and user layers (trust decisions must not
+/// come from the project being trusted).
+const USER_ONLY_KEYS: [&str; 1] = ["projects"];
+
 /// Canonical upstream Codex schema, embedded for compatibility validation.
 pub fn codex_config_schema_json() -> &'static [u8] {
     include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/config.schema.codex.json"))
@@ -67,6 +80,41 @@ pub fn config_schema_json() -> anyhow::Result<Vec<u8>> {
     Ok(serde_json::to_vec_pretty(&value)?)
 }
 
+/// Render the config schema for editors and other external tooling: the
+/// upgraded schema with each top-level property annotated with the layers
+/// that may set it ([`LAYERS_KEYWORD`]).
+pub fn config_schema_with_layers_json() -> anyhow::Result<Vec<u8>> {
+    let mut value = serde_json::to_value(config_schema())?;
+    let profile_keys: HashSet<String> = value
+        .pointer("/definitions/ConfigProfile/properties")
+        .and_then(Value::as_object)
+        .map(|properties| properties.keys().cloned().collect())
+        .unwrap_or_default();
+    if let Some(properties) = value.get_mut("properties").and_then(Value::as_object_mut) {
+        for (key, property) in properties.iter_mut() {
+            let Some(property) = property.as_object_mut() else {
+                continue;
+            };
+            property.insert(
+                LAYERS_KEYWORD.to_owned(),
+                Value::from(config_layers_for_key(key, &profile_keys)),
+            );
+        }
+    }
+    let value = canonicalize(&value);
+    Ok(serde_json::to_vec_pretty(&value)?)
+}
+
+fn config_layers_for_key(key: &str, profile_keys: &HashSet<String>) -> Vec<&'static str> {
+    if USER_ONLY_KEYS.contains(&key) {
+        return vec!["system", "user"];
+    }
+    CONFIG_LAYERS
+        .into_iter()
+        .filter(|layer| *layer != "profile" || profile_keys.contains(key))
+        .collect()
+}
+
 /// Write the upgraded config schema fixture to disk.
 pub fn write_config_schema(out_path: &Path) -> anyhow::Result<()> {
     std::fs::write(out_path, config_schema_json()?)?;
@@ -96,5 +144,33 @@ mod tests {
             "config.schema.codex.json is stale — regenerate with UPDATE_SCHEMA=1 cargo test -p code-core --lib -- config::schema::tests::checked_in_codex_schema_matches_generated"
         );
     }
+
+    #[test]
+    fn layered_schema_annotates_every_top_level_key() {
+        let schema: Value = serde_json::from_slice(
+            &config_schema_with_layers_json().expect("schema generation"),
+        )
+        .expect("valid json");
+        let properties = schema["properties"].as_object().expect("properties");
+        assert!(
+            properties
+                .values()
+                .all(|property| property[LAYERS_KEYWORD].is_array()),
+            "every top-level key lists its layers"
+        );
+
+        let layers = |key: &str| -> Vec<String> {
+            serde_json::from_value(properties[key][LAYERS_KEYWORD].clone()).expect("layers")
+        };
+        assert_eq!(
+            layers("model"),
+            vec!["system", "user", "project", "profile", "cli", "managed"]
+        );
+        assert_eq!(
+            layers("mcp_servers"),
+            vec!["system", "user", "project", "cli", "managed"]
+        );
+        assert_eq!(layers("projects"), vec!["system", "user"]);
+    }
 }
 
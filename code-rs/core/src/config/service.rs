@@ -2,6 +2,8 @@ use crate::config::Config;
 use crate::config::ConfigBuilder;
 use crate::config::ConfigOverrides;
 use crate::config_loader::LoaderOverrides;
+use crate::config_loader::describe_layer_source;
+use crate::config_loader::toml_value_at_path;
 use crate::protocol::AskForApproval as CoreAskForApproval;
 use code_app_server_protocol::AskForApproval as V2AskForApproval;
 use code_app_server_protocol::Config as V2Config;
@@ -344,7 +346,7 @@ impl ConfigService {
                     continue;
                 }
 
-                let overriding_layer = format_layer_source_for_override(&origin.name);
+                let overriding_layer = describe_layer_source(&origin.name);
                 status = WriteStatus::OkOverridden;
                 overridden_metadata = Some(OverriddenMetadata {
                     message: format!("Value is overridden by {overriding_layer}."),
@@ -599,43 +601,6 @@ fn merge_toml_values(target: &mut TomlValue, incoming: TomlValue) {
     }
 }
 
-fn toml_value_at_path<'a>(root: &'a TomlValue, key_path: &str) -> Option<&'a TomlValue> {
-    let mut current = root;
-    for segment in key_path.split('.').filter(|segment| !segment.is_empty()) {
-        let table = current.as_table()?;
-        current = table.get(segment)?;
-    }
-    Some(current)
-}
-
-fn format_layer_source_for_override(source: &code_app_server_protocol::ConfigLayerSource) -> String {
-    match source {
-        code_app_server_protocol::ConfigLayerSource::Mdm { domain, key } => {
-            format!("MDM ({domain}:{key})")
-        }
-        code_app_server_protocol::ConfigLayerSource::System { file } => {
-            let path = file.as_ref().display();
-            format!("system config ({path})")
-        }
-        code_app_server_protocol::ConfigLayerSource::User { file } => {
-            let path = file.as_ref().display();
-            format!("user config ({path})")
-        }
-        code_app_server_protocol::ConfigLayerSource::Project { dot_codex_folder } => {
-            let path = dot_codex_folder.as_ref().display();
-            format!("project config ({path})")
-        }
-        code_app_server_protocol::ConfigLayerSource::SessionFlags => "session overrides".to_owned(),
-        code_app_server_protocol::ConfigLayerSource::LegacyManagedConfigTomlFromFile { file } => {
-            let path = file.as_ref().display();
-            format!("managed config ({path})")
-        }
-        code_app_server_protocol::ConfigLayerSource::LegacyManagedConfigTomlFromMdm => {
-            "MDM managed config".to_owned()
-        }
-    }
-}
-
 fn write_atomically(path: &Path, contents: &[u8]) -> std::io::Result<()> {
     let Some(parent) = path.parent() else {
         return Err(std::io::Error::new(
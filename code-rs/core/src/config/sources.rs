@@ -219,6 +219,15 @@ pub fn write_global_mcp_servers(
                 if let Some(depth) = config.scheduling.max_queue_depth {
                     sched_table["max_queue_depth"] = toml_edit::value(i64::from(depth));
                 }
+                if let Some(max) = config.scheduling.max_calls_per_minute {
+                    sched_table["max_calls_per_minute"] = toml_edit::value(i64::from(max));
+                }
+                if let Some(failures) = config.scheduling.circuit_breaker_failures {
+                    sched_table["circuit_breaker_failures"] = toml_edit::value(i64::from(failures));
+                }
+                if let Some(duration) = config.scheduling.circuit_breaker_cooldown_sec {
+                    sched_table["circuit_breaker_cooldown_sec"] = toml_edit::value(duration.as_secs_f64());
+                }
                 entry["scheduling"] = TomlItem::Table(sched_table);
             }
 
@@ -1260,6 +1269,66 @@ fn set_project_trusted_inner(doc: &mut DocumentMut, project_path: &Path) -> anyh
     Ok(())
 }
 
+/// Drop the `trust_level` of `project_path` from `CODEX_HOME/config.toml`,
+/// removing the project table when nothing else is left in it. Returns
+/// whether an entry was removed.
+pub fn remove_project_trust(code_home: &Path, project_path: &Path) -> anyhow::Result<bool> {
+    let config_path = code_home.join(CONFIG_TOML_FILE);
+    let read_path = resolve_code_path_for_read(code_home, Path::new(CONFIG_TOML_FILE));
+    let mut doc = read_config_doc(&read_path)?;
+
+    let project_key = project_path.to_string_lossy().into_owned();
+    let Some(projects_tbl) = doc
+        .get_mut("projects")
+        .and_then(TomlItem::as_table_like_mut)
+    else {
+        return Ok(false);
+    };
+    let Some(proj_tbl) = projects_tbl
+        .get_mut(project_key.as_str())
+        .and_then(TomlItem::as_table_like_mut)
+    else {
+        return Ok(false);
+    };
+    if proj_tbl.remove("trust_level").is_none() {
+        return Ok(false);
+    }
+    if proj_tbl.is_empty() {
+        projects_tbl.remove(project_key.as_str());
+    }
+
+    std::fs::create_dir_all(code_home)?;
+    let tmp_file = NamedTempFile::new_in(code_home)?;
+    std::fs::write(tmp_file.path(), doc.to_string())?;
+    tmp_file.persist(config_path)?;
+    Ok(true)
+}
+
+/// Projects with a `trust_level` in the system and user config, sorted by
+/// path.
+pub fn list_project_trust(code_home: &Path) -> std::io::Result<Vec<(PathBuf, String)>> {
+    let root_value = load_config_as_toml(code_home)?;
+    let Some(projects) = root_value.get("projects").and_then(TomlValue::as_table) else {
+        return Ok(Vec::new());
+    };
+    let mut entries: Vec<(PathBuf, String)> = projects
+        .iter()
+        .filter_map(|(path, project)| {
+            let level = project.get("trust_level")?.as_str()?;
+            Some((PathBuf::from(path), level.to_owned()))
+        })
+        .collect();
+    entries.sort();
+    Ok(entries)
+}
+
+/// Path whose trust applies to `cwd`: the main repository working directory
+/// when `cwd` is inside a git checkout (so worktrees share it), otherwise
+/// `cwd` itself.
+pub fn project_trust_root(cwd: &Path) -> PathBuf {
+    crate::git_info::resolve_root_git_project_for_trust(cwd).unwrap_or_else(|| cwd.to_path_buf())
+}
+
 /// Persist the selected TUI theme into `CODEX_HOME/config.toml` at `[tui.theme].name`.
 pub fn set_tui_theme_name(code_home: &Path, theme: ThemeName) -> anyhow::Result<()> {
     let config_path = code_home.join(CONFIG_TOML_FILE);
@@ -2557,6 +2626,23 @@ pub fn list_mcp_servers(code_home: &Path) -> anyhow::Result<McpServerListPair> {
                 scheduling.max_queue_depth = Some(depth);
             }
 
+            if let Some(max) = parse_u32_field(tbl, "max_calls_per_minute")? {
+                if max == 0 {
+                    return Err(anyhow::anyhow!("scheduling.max_calls_per_minute must be >= 1"));
+                }
+                scheduling.max_calls_per_minute = Some(max);
+            }
+            if let Some(failures) = parse_u32_field(tbl, "circuit_breaker_failures")? {
+                if failures == 0 {
+                    return Err(anyhow::anyhow!(
+                        "scheduling.circuit_breaker_failures must be >= 1"
+                    ));
+                }
+                scheduling.circuit_breaker_failures = Some(failures);
+            }
+            scheduling.circuit_breaker_cooldown_sec =
+                parse_duration_field(tbl, "circuit_breaker_cooldown_sec")?;
+
             return Ok(scheduling);
         }
 
@@ -2591,6 +2677,23 @@ pub fn list_mcp_servers(code_home: &Path) -> anyhow::Result<McpServerListPair> {
                 scheduling.max_queue_depth = Some(depth);
             }
 
+            if let Some(max) = parse_u32_inline_field(tbl, "max_calls_per_minute")? {
+                if max == 0 {
+                    return Err(anyhow::anyhow!("scheduling.max_calls_per_minute must be >= 1"));
+                }
+                scheduling.max_calls_per_minute = Some(max);
+            }
+            if let Some(failures) = parse_u32_inline_field(tbl, "circuit_breaker_failures")? {
+                if failures == 0 {
+                    return Err(anyhow::anyhow!(
+                        "scheduling.circuit_breaker_failures must be >= 1"
+                    ));
+                }
+                scheduling.circuit_breaker_failures = Some(failures);
+            }
+            scheduling.circuit_breaker_cooldown_sec =
+                parse_duration_inline_field(tbl, "circuit_breaker_cooldown_sec")?;
+
             return Ok(scheduling);
         }
 
@@ -2977,6 +3080,15 @@ pub fn add_mcp_server(
         if let Some(depth) = scheduling.max_queue_depth {
             sched_table["max_queue_depth"] = toml_edit::value(i64::from(depth));
         }
+        if let Some(max) = scheduling.max_calls_per_minute {
+            sched_table["max_calls_per_minute"] = toml_edit::value(i64::from(max));
+        }
+        if let Some(failures) = scheduling.circuit_breaker_failures {
+            sched_table["circuit_breaker_failures"] = toml_edit::value(i64::from(failures));
+        }
+        if let Some(duration) = scheduling.circuit_breaker_cooldown_sec {
+            sched_table["circuit_breaker_cooldown_sec"] = toml_edit::value(duration.as_secs_f64());
+        }
         server_tbl.insert("scheduling", TomlItem::Table(sched_table));
     }
 
@@ -3250,6 +3362,15 @@ pub fn set_mcp_server_scheduling(
         if let Some(depth) = scheduling.max_queue_depth {
             sched_table["max_queue_depth"] = toml_edit::value(i64::from(depth));
         }
+        if let Some(max) = scheduling.max_calls_per_minute {
+            sched_table["max_calls_per_minute"] = toml_edit::value(i64::from(max));
+        }
+        if let Some(failures) = scheduling.circuit_breaker_failures {
+            sched_table["circuit_breaker_failures"] = toml_edit::value(i64::from(failures));
+        }
+        if let Some(duration) = scheduling.circuit_breaker_cooldown_sec {
+            sched_table["circuit_breaker_cooldown_sec"] = toml_edit::value(duration.as_secs_f64());
+        }
         server_table["scheduling"] = TomlItem::Table(sched_table);
     }
 
@@ -1,18 +1,57 @@
+use crate::config::Config;
 use crate::config::Constrained;
 use crate::config::ConstraintError;
 use crate::protocol::AskForApproval;
+use crate::protocol::SandboxPolicy;
+use code_protocol::config_types::SandboxMode;
+use code_protocol::config_types::WebSearchMode;
 use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+/// Where a requirement came from, named in violation errors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum RequirementsSource {
+    RequirementsToml(PathBuf),
+    LegacyManagedConfig(PathBuf),
+    ManagedPreferences,
+}
+
+impl fmt::Display for RequirementsSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RequirementsToml(path) => write!(f, "requirements file {}", path.display()),
+            Self::LegacyManagedConfig(path) => write!(f, "managed config {}", path.display()),
+            Self::ManagedPreferences => f.write_str("MDM managed preferences"),
+        }
+    }
+}
 
 /// Normalized version of [`ConfigRequirementsToml`] after deserialization and normalization.
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) struct ConfigRequirements {
     pub(crate) approval_policy: Constrained<AskForApproval>,
+    pub(crate) sandbox_mode: Constrained<SandboxMode>,
+    pub(crate) web_search_mode: Constrained<WebSearchMode>,
+    /// Model provider ids that may be selected; `None` allows any.
+    pub(crate) model_providers: Option<Vec<String>>,
+    /// MCP server names that may be configured; `None` allows any.
+    pub(crate) mcp_servers: Option<Vec<String>>,
+    /// Layer that set each constrained key, keyed by config key.
+    pub(crate) sources: HashMap<&'static str, RequirementsSource>,
 }
 
 impl Default for ConfigRequirements {
     fn default() -> Self {
         Self {
             approval_policy: Constrained::allow_any_from_default(),
+            sandbox_mode: Constrained::allow_any_from_default(),
+            web_search_mode: Constrained::allow_any_from_default(),
+            model_providers: None,
+            mcp_servers: None,
+            sources: HashMap::new(),
         }
     }
 }
@@ -21,26 +60,150 @@ impl Default for ConfigRequirements {
 #[derive(Deserialize, Debug, Clone, Default, PartialEq)]
 pub(crate) struct ConfigRequirementsToml {
     pub allowed_approval_policies: Option<Vec<AskForApproval>>,
+    pub allowed_sandbox_modes: Option<Vec<SandboxMode>>,
+    pub allowed_web_search_modes: Option<Vec<WebSearchMode>>,
+    pub allowed_model_providers: Option<Vec<String>>,
+    pub allowed_mcp_servers: Option<Vec<String>>,
+}
+
+/// Constrains a field to `allowed`, starting from the default when it is
+/// allowed and from the first entry otherwise.
+fn constrain_to<T>(field: &str, allowed: Option<Vec<T>>) -> Result<Constrained<T>, ConstraintError>
+where
+    T: Default + Copy + PartialEq + Send + Sync + fmt::Debug + 'static,
+{
+    match allowed {
+        Some(values) => {
+            let default_value = T::default();
+            if values.contains(&default_value) {
+                Constrained::allow_values(default_value, values)
+            } else if let Some(first) = values.first() {
+                Constrained::allow_values(*first, values)
+            } else {
+                Err(ConstraintError::empty_field(field))
+            }
+        }
+        None => Ok(Constrained::allow_any_from_default()),
+    }
+}
+
+fn non_empty(field: &str, allowed: Option<Vec<String>>) -> Result<Option<Vec<String>>, ConstraintError> {
+    match allowed {
+        Some(values) if values.is_empty() => Err(ConstraintError::empty_field(field)),
+        other => Ok(other),
+    }
 }
 
 impl TryFrom<ConfigRequirementsToml> for ConfigRequirements {
     type Error = ConstraintError;
 
     fn try_from(toml: ConfigRequirementsToml) -> Result<Self, Self::Error> {
-        let approval_policy: Constrained<AskForApproval> = match toml.allowed_approval_policies {
-            Some(policies) => {
-                let default_value = AskForApproval::default();
-                if policies.contains(&default_value) {
-                    Constrained::allow_values(default_value, policies)?
-                } else if let Some(first) = policies.first() {
-                    Constrained::allow_values(*first, policies)?
-                } else {
-                    return Err(ConstraintError::empty_field("allowed_approval_policies"));
-                }
+        Ok(ConfigRequirements {
+            approval_policy: constrain_to("allowed_approval_policies", toml.allowed_approval_policies)?,
+            sandbox_mode: constrain_to("allowed_sandbox_modes", toml.allowed_sandbox_modes)?,
+            web_search_mode: constrain_to("allowed_web_search_modes", toml.allowed_web_search_modes)?,
+            model_providers: non_empty("allowed_model_providers", toml.allowed_model_providers)?,
+            mcp_servers: non_empty("allowed_mcp_servers", toml.allowed_mcp_servers)?,
+            sources: HashMap::new(),
+        })
+    }
+}
+
+impl ConfigRequirements {
+    /// Records `source` for every key `toml` constrains.
+    pub(crate) fn record_sources(&mut self, toml: &ConfigRequirementsToml, source: &RequirementsSource) {
+        let keys = [
+            ("approval_policy", toml.allowed_approval_policies.is_some()),
+            ("sandbox_mode", toml.allowed_sandbox_modes.is_some()),
+            ("tools.web_search", toml.allowed_web_search_modes.is_some()),
+            ("model_provider", toml.allowed_model_providers.is_some()),
+            ("mcp_servers", toml.allowed_mcp_servers.is_some()),
+        ];
+        for (key, constrained) in keys {
+            if constrained {
+                self.sources.insert(key, source.clone());
             }
-            None => Constrained::allow_any_from_default(),
+        }
+    }
+
+    fn violation(&self, key: &str, err: impl fmt::Display) -> io::Error {
+        let message = match self.sources.get(key) {
+            Some(source) => format!("`{key}` {err} (enforced by {source})"),
+            None => format!("`{key}` {err}"),
         };
-        Ok(ConfigRequirements { approval_policy })
+        io::Error::new(io::ErrorKind::InvalidInput, message)
+    }
+
+    /// Checks the resolved `config` against every requirement, failing on
+    /// the first value the managing layer does not allow.
+    pub(crate) fn enforce(&self, config: &mut Config) -> io::Result<()> {
+        let mut approval_policy = self.approval_policy.clone();
+        approval_policy
+            .set(config.approval_policy)
+            .map_err(|err| self.violation("approval_policy", err))?;
+        config.approval_policy = approval_policy.value();
+
+        self.sandbox_mode
+            .can_set(&sandbox_mode_for_policy(&config.sandbox_policy))
+            .map_err(|err| self.violation("sandbox_mode", err))?;
+
+        self.web_search_mode
+            .can_set(&web_search_mode_for(config))
+            .map_err(|err| self.violation("tools.web_search", err))?;
+
+        if let Some(allowed) = &self.model_providers
+            && !allowed.contains(&config.model_provider_id)
+        {
+            return Err(self.violation(
+                "model_provider",
+                ConstraintError::invalid_value(config.model_provider_id.as_str(), format!("{allowed:?}")),
+            ));
+        }
+
+        if let Some(allowed) = &self.mcp_servers {
+            let mut blocked: Vec<&String> = config
+                .mcp_servers
+                .keys()
+                .filter(|name| !allowed.contains(name))
+                .collect();
+            blocked.sort();
+            if let Some(name) = blocked.first() {
+                return Err(self.violation(
+                    "mcp_servers",
+                    ConstraintError::invalid_value(name.as_str(), format!("{allowed:?}")),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether an MCP server contributed outside of user config (e.g. by a
+    /// plugin) may be added.
+    pub(crate) fn allows_mcp_server(&self, name: &str) -> bool {
+        self.mcp_servers
+            .as_ref()
+            .is_none_or(|allowed| allowed.iter().any(|entry| entry == name))
+    }
+}
+
+fn sandbox_mode_for_policy(policy: &SandboxPolicy) -> SandboxMode {
+    match policy {
+        SandboxPolicy::ReadOnly => SandboxMode::ReadOnly,
+        SandboxPolicy::WorkspaceWrite { .. } => SandboxMode::WorkspaceWrite,
+        // Code applies no sandbox of its own under an external sandbox.
+        SandboxPolicy::DangerFullAccess | SandboxPolicy::ExternalSandbox { .. } => {
+            SandboxMode::DangerFullAccess
+        }
+    }
+}
+
+fn web_search_mode_for(config: &Config) -> WebSearchMode {
+    if !config.tools_web_search_request {
+        WebSearchMode::Disabled
+    } else if config.tools_web_search_external {
+        WebSearchMode::Live
+    } else {
+        WebSearchMode::Cached
     }
 }
 
@@ -54,5 +217,45 @@ impl TryFrom<ConfigRequirementsToml> for ConfigRequirements {
 #[derive(Deserialize, Debug, Clone, Default, PartialEq)]
 pub(crate) struct LegacyManagedConfigToml {
     pub(crate) approval_policy: Option<AskForApproval>,
+    pub(crate) sandbox_mode: Option<SandboxMode>,
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn empty_allowlists_are_rejected() {
+        let toml = ConfigRequirementsToml {
+            allowed_mcp_servers: Some(Vec::new()),
+            ..Default::default()
+        };
+        assert_eq!(
+            ConfigRequirements::try_from(toml).map(|_| ()),
+            Err(ConstraintError::empty_field("allowed_mcp_servers"))
+        );
+    }
+
+    #[test]
+    fn violations_name_the_managing_layer() {
+        let toml = ConfigRequirementsToml {
+            allowed_sandbox_modes: Some(vec![SandboxMode::ReadOnly, SandboxMode::WorkspaceWrite]),
+            ..Default::default()
+        };
+        let mut requirements = ConfigRequirements::try_from(toml.clone()).expect("valid requirements");
+        let source = RequirementsSource::RequirementsToml(PathBuf::from("/etc/code/requirements.toml"));
+        requirements.record_sources(&toml, &source);
+
+        let err = requirements
+            .sandbox_mode
+            .can_set(&sandbox_mode_for_policy(&SandboxPolicy::DangerFullAccess))
+            .map_err(|err| requirements.violation("sandbox_mode", err))
+            .expect_err("full access is not allowed");
+        assert_eq!(
+            err.to_string(),
+            "`sandbox_mode` value `DangerFullAccess` is not in the allowed set [ReadOnly, WorkspaceWrite] (enforced by requirements file /etc/code/requirements.toml)"
+        );
+        assert!(requirements.allows_mcp_server("anything"));
+    }
+}
@@ -0,0 +1,172 @@
+//! Per-layer provenance for a single config key, used by
+//! `code config explain`.
+
+use super::ConfigLayerStack;
+use code_app_server_protocol::ConfigLayerSource;
+use toml::Value as TomlValue;
+
+/// How one layer's value for a key relates to the effective value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LayerVerdict {
+    /// The layer does not set the key.
+    NotSet,
+    /// The layer sets the key but was skipped, with the loader's reason.
+    Disabled(String),
+    /// The layer's value is the effective one.
+    Winner,
+    /// The layer's table was merged key-by-key with other layers' tables.
+    Merged,
+    /// A higher layer replaced the layer's value.
+    Overridden,
+}
+
+/// One layer's view of a key.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayerContribution {
+    pub source: ConfigLayerSource,
+    pub value: Option<TomlValue>,
+    pub verdict: LayerVerdict,
+}
+
+/// Every layer's contribution to a key, ordered from lowest to highest
+/// precedence.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyExplanation {
+    pub key: String,
+    pub effective: Option<TomlValue>,
+    /// Layer that supplied the effective value, when it is not a merged table.
+    pub winner: Option<ConfigLayerSource>,
+    pub layers: Vec<LayerContribution>,
+}
+
+/// Looks up a dotted `key_path` (e.g. `tui.theme.name`) in `root`.
+pub fn toml_value_at_path<'a>(root: &'a TomlValue, key_path: &str) -> Option<&'a TomlValue> {
+    let mut current = root;
+    for segment in key_path.split('.').filter(|segment| !segment.is_empty()) {
+        let table = current.as_table()?;
+        current = table.get(segment)?;
+    }
+    Some(current)
+}
+
+/// Human-readable name for a config layer, including its file when it has one.
+pub fn describe_layer_source(source: &ConfigLayerSource) -> String {
+    match source {
+        ConfigLayerSource::Mdm { domain, key } => format!("MDM ({domain}:{key})"),
+        ConfigLayerSource::System { file } => {
+            let path = file.as_ref().display();
+            format!("system config ({path})")
+        }
+        ConfigLayerSource::User { file } => {
+            let path = file.as_ref().display();
+            format!("user config ({path})")
+        }
+        ConfigLayerSource::Project { dot_codex_folder } => {
+            let path = dot_codex_folder.as_ref().display();
+            format!("project config ({path})")
+        }
+        ConfigLayerSource::SessionFlags => "session overrides".to_owned(),
+        ConfigLayerSource::LegacyManagedConfigTomlFromFile { file } => {
+            let path = file.as_ref().display();
+            format!("managed config ({path})")
+        }
+        ConfigLayerSource::LegacyManagedConfigTomlFromMdm => "MDM managed config".to_owned(),
+    }
+}
+
+impl ConfigLayerStack {
+    /// Explains which layers set `key` and why the effective value won.
+    pub fn explain(&self, key: &str) -> KeyExplanation {
+        let effective_config = self.effective_config();
+        let effective = toml_value_at_path(&effective_config, key).cloned();
+        let merged_table = effective.as_ref().is_some_and(TomlValue::is_table);
+        let winner = if merged_table {
+            None
+        } else {
+            self.origins().remove(key).map(|origin| origin.name)
+        };
+
+        let layers = self
+            .layers_low_to_high()
+            .map(|layer| {
+                let value = toml_value_at_path(&layer.config, key).cloned();
+                let verdict = match (&value, &layer.disabled_reason) {
+                    (None, _) => LayerVerdict::NotSet,
+                    (Some(_), Some(reason)) => LayerVerdict::Disabled(reason.clone()),
+                    (Some(value), None) if merged_table && value.is_table() => LayerVerdict::Merged,
+                    (Some(_), None) if winner.as_ref() == Some(&layer.name) => LayerVerdict::Winner,
+                    (Some(_), None) => LayerVerdict::Overridden,
+                };
+                LayerContribution {
+                    source: layer.name.clone(),
+                    value,
+                    verdict,
+                }
+            })
+            .collect();
+
+        KeyExplanation {
+            key: key.to_owned(),
+            effective,
+            winner,
+            layers,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::ConfigLayerEntry;
+    use super::*;
+    use code_utils_absolute_path::AbsolutePathBuf;
+    use pretty_assertions::assert_eq;
+
+    fn layer(source: ConfigLayerSource, toml: &str) -> ConfigLayerEntry {
+        ConfigLayerEntry::new(source, toml::from_str(toml).expect("valid toml"))
+    }
+
+    #[test]
+    fn explains_winner_overrides_and_disabled_layers() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let user = ConfigLayerSource::User {
+            file: AbsolutePathBuf::from_absolute_path(tmp.path().join("config.toml")).expect("abs"),
+        };
+        let project = ConfigLayerSource::Project {
+            dot_codex_folder: AbsolutePathBuf::from_absolute_path(tmp.path().join(".code")).expect("abs"),
+        };
+        let stack = ConfigLayerStack::new(vec![
+            layer(user.clone(), "approval_policy = \"never\"\n[tui]\nalt = true\n"),
+            ConfigLayerEntry::new_disabled(
+                project,
+                toml::from_str("approval_policy = \"untrusted\"").expect("valid toml"),
+                "not trusted",
+            ),
+            layer(
+                ConfigLayerSource::SessionFlags,
+                "approval_policy = \"on-request\"\n[tui]\nmouse = false\n",
+            ),
+        ]);
+
+        let explained = stack.explain("approval_policy");
+        assert_eq!(explained.effective, Some(TomlValue::String("on-request".to_owned())));
+        assert_eq!(explained.winner, Some(ConfigLayerSource::SessionFlags));
+        let verdicts: Vec<LayerVerdict> = explained.layers.into_iter().map(|l| l.verdict).collect();
+        assert_eq!(
+            verdicts,
+            vec![
+                LayerVerdict::Overridden,
+                LayerVerdict::Disabled("not trusted".to_owned()),
+                LayerVerdict::Winner,
+            ]
+        );
+
+        let tui = stack.explain("tui");
+        assert_eq!(tui.winner, None);
+        let verdicts: Vec<LayerVerdict> = tui.layers.into_iter().map(|l| l.verdict).collect();
+        assert_eq!(
+            verdicts,
+            vec![LayerVerdict::Merged, LayerVerdict::NotSet, LayerVerdict::Merged]
+        );
+        assert_eq!(stack.explain("tui.alt").winner, Some(user));
+    }
+}
@@ -1,12 +1,15 @@
 mod config_requirements;
 mod diagnostics;
+mod explain;
 mod layer_io;
 mod macos;
+mod project_layers;
 
 use crate::config::CONFIG_TOML_FILE;
 use config_requirements::ConfigRequirements;
 use config_requirements::ConfigRequirementsToml;
 use config_requirements::LegacyManagedConfigToml;
+use config_requirements::RequirementsSource;
 use code_app_server_protocol::ConfigLayerMetadata;
 use code_app_server_protocol::ConfigLayerSource;
 use code_utils_absolute_path::AbsolutePathBuf;
@@ -15,6 +18,7 @@ use layer_io::read_config_from_path;
 use layer_io::requirements_default_path;
 use layer_io::system_config_default_path;
 use macos::load_managed_admin_config_layer;
+use project_layers::load_project_layers;
 use sha1::Digest;
 use sha1::Sha1;
 use std::collections::HashMap;
@@ -25,6 +29,12 @@ use tokio::runtime::{Builder as RuntimeBuilder, Handle};
 use toml::Value as TomlValue;
 use toml::map::Map;
 
+pub use explain::KeyExplanation;
+pub use explain::LayerContribution;
+pub use explain::LayerVerdict;
+pub use explain::describe_layer_source;
+pub use explain::toml_value_at_path;
+
 #[derive(Debug, Default, Clone)]
 pub struct LoaderOverrides {
     /// Optional override for the system config file path. When unset, defaults
@@ -70,6 +80,17 @@ impl ConfigLayerEntry {
     }
 }
 
+/// A `.code/` folder whose config layer is disabled because the project is
+/// not trusted, with the files in it that are being ignored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IgnoredProjectLayer {
+    pub dot_code_folder: PathBuf,
+    /// `config.toml` in the folder, when present.
+    pub config_file: Option<PathBuf>,
+    /// `hooks.json` (lifecycle hooks) in the folder, when present.
+    pub hooks_file: Option<PathBuf>,
+}
+
 #[derive(Debug, Clone)]
 pub struct ConfigLayerStack {
     layers_low_to_high: Vec<ConfigLayerEntry>,
@@ -99,6 +120,32 @@ impl ConfigLayerStack {
         merged
     }
 
+    /// Project layers skipped because the project is not trusted. Folders
+    /// without a `config.toml` or `hooks.json` are left out.
+    pub fn ignored_project_layers(&self) -> Vec<IgnoredProjectLayer> {
+        self.layers_low_to_high
+            .iter()
+            .filter(|layer| layer.disabled_reason.is_some())
+            .filter_map(|layer| match &layer.name {
+                ConfigLayerSource::Project { dot_codex_folder } => {
+                    let folder = dot_codex_folder.as_path();
+                    let existing = |name: &str| {
+                        let path = folder.join(name);
+                        path.is_file().then_some(path)
+                    };
+                    let config_file = existing(CONFIG_TOML_FILE);
+                    let hooks_file = existing("hooks.json");
+                    (config_file.is_some() || hooks_file.is_some()).then(|| IgnoredProjectLayer {
+                        dot_code_folder: folder.to_path_buf(),
+                        config_file,
+                        hooks_file,
+                    })
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
     pub fn origins(&self) -> HashMap<String, ConfigLayerMetadata> {
         let mut origins = HashMap::<String, ConfigLayerMetadata>::new();
         for layer in &self.layers_low_to_high {
@@ -393,7 +440,7 @@ async fn load_config_requirements_internal(
         requirements_path,
     } = overrides;
 
-    let (_managed_config_path, managed_config) =
+    let (managed_config_path, managed_config) =
         load_legacy_managed_config(code_home, managed_config_path).await?;
     let requirements_path = requirements_path.unwrap_or_else(|| requirements_default_path(code_home));
 
@@ -405,8 +452,14 @@ async fn load_config_requirements_internal(
                     format!("Failed to parse config requirements TOML: {err}"),
                 )
             })?;
-        ConfigRequirements::try_from(parsed)
-            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?
+        let mut requirements = ConfigRequirements::try_from(parsed.clone()).map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{}: {err}", requirements_path.display()),
+            )
+        })?;
+        requirements.record_sources(&parsed, &RequirementsSource::RequirementsToml(requirements_path));
+        requirements
     } else {
         ConfigRequirements::default()
     };
@@ -416,14 +469,19 @@ async fn load_config_requirements_internal(
         load_managed_admin_config_layer(managed_preferences_base64.as_deref()).await?;
 
     #[cfg(not(target_os = "macos"))]
-    let managed_preferences = None;
+    let managed_preferences: Option<TomlValue> = None;
 
-    // If multiple legacy layers specify approval_policy (e.g. both a managed_config
+    // If multiple legacy layers specify the same key (e.g. both a managed_config
     // file and macOS managed preferences), allow the later/higher-precedence layer
     // to override earlier ones.
     let mut legacy_approval_policy = None;
+    let mut legacy_sandbox_mode = None;
 
-    for legacy in [managed_config, managed_preferences].into_iter().flatten() {
+    let legacy_layers = [
+        managed_config.map(|value| (value, RequirementsSource::LegacyManagedConfig(managed_config_path))),
+        managed_preferences.map(|value| (value, RequirementsSource::ManagedPreferences)),
+    ];
+    for (legacy, source) in legacy_layers.into_iter().flatten() {
         let legacy: LegacyManagedConfigToml = legacy.try_into().map_err(|err: toml::de::Error| {
             io::Error::new(
                 io::ErrorKind::InvalidData,
@@ -432,13 +490,22 @@ async fn load_config_requirements_internal(
         })?;
 
         if let Some(approval_policy) = legacy.approval_policy {
-            legacy_approval_policy = Some(approval_policy);
+            legacy_approval_policy = Some((approval_policy, source.clone()));
+        }
+        if let Some(sandbox_mode) = legacy.sandbox_mode {
+            legacy_sandbox_mode = Some((sandbox_mode, source));
         }
     }
 
-    if let Some(approval_policy) = legacy_approval_policy {
+    if let Some((approval_policy, source)) = legacy_approval_policy {
         requirements.approval_policy.can_set(&approval_policy)?;
         requirements.approval_policy = crate::config::Constrained::allow_only(approval_policy);
+        requirements.sources.insert("approval_policy", source);
+    }
+    if let Some((sandbox_mode, source)) = legacy_sandbox_mode {
+        requirements.sandbox_mode.can_set(&sandbox_mode)?;
+        requirements.sandbox_mode = crate::config::Constrained::allow_only(sandbox_mode);
+        requirements.sources.insert("sandbox_mode", source);
     }
 
     Ok(requirements)
@@ -600,114 +667,6 @@ fn resolve_cwd_for_config_layers(cwd: &Path) -> io::Result<PathBuf> {
     Ok(std::fs::canonicalize(&base).unwrap_or(base))
 }
 
-async fn load_project_layers(
-    cwd: &Path,
-    code_home: &Path,
-    trusted: bool,
-) -> io::Result<Vec<ConfigLayerEntry>> {
-    let project_root = crate::git_info::resolve_root_git_project_for_trust(cwd)
-        .unwrap_or_else(|| cwd.to_path_buf());
-
-    let mut dirs = Vec::<PathBuf>::new();
-    for ancestor in cwd.ancestors() {
-        dirs.push(ancestor.to_path_buf());
-        if ancestor == project_root {
-            break;
-        }
-    }
-    dirs.reverse();
-
-    let code_home_normalized = std::fs::canonicalize(code_home).unwrap_or_else(|_| code_home.to_path_buf());
-
-    let mut layers = Vec::<ConfigLayerEntry>::new();
-    for dir in dirs {
-        let dot_code = dir.join(".code");
-        if !tokio::fs::metadata(&dot_code)
-            .await
-            .is_ok_and(|meta| meta.is_dir())
-        {
-            continue;
-        }
-
-        let dot_code_normalized =
-            std::fs::canonicalize(&dot_code).unwrap_or_else(|_| dot_code.clone());
-        if dot_code_normalized == code_home_normalized {
-            continue;
-        }
-
-        let config_file = dot_code.join(CONFIG_TOML_FILE);
-        let layer_source = ConfigLayerSource::Project {
-            dot_codex_folder: AbsolutePathBuf::from_absolute_path(&dot_code)?,
-        };
-
-        match tokio::fs::read_to_string(&config_file).await {
-            Ok(contents) => match toml::from_str::<TomlValue>(&contents) {
-                Ok(config) => {
-                    if trusted {
-                        layers.push(ConfigLayerEntry::new(layer_source, config));
-                    } else {
-                        layers.push(ConfigLayerEntry::new_disabled(
-                            layer_source,
-                            config,
-                            "Project directory is not trusted; ignoring project config layer.",
-                        ));
-                    }
-                }
-                Err(err) => {
-                    if trusted {
-                        return Err(io::Error::new(
-                            io::ErrorKind::InvalidData,
-                            format!(
-                                "Failed to parse project config file {}: {err}",
-                                config_file.display()
-                            ),
-                        ));
-                    }
-                    layers.push(ConfigLayerEntry::new_disabled(
-                        layer_source,
-                        default_empty_table(),
-                        format!(
-                            "Project directory is not trusted and project config could not be parsed (ignored): {err}"
-                        ),
-                    ));
-                }
-            },
-            Err(err) if err.kind() == io::ErrorKind::NotFound => {
-                // Record an empty layer entry when the folder exists, even if config.toml is missing.
-                if trusted {
-                    layers.push(ConfigLayerEntry::new(layer_source, default_empty_table()));
-                } else {
-                    layers.push(ConfigLayerEntry::new_disabled(
-                        layer_source,
-                        default_empty_table(),
-                        "Project directory is not trusted; ignoring project config layer.",
-                    ));
-                }
-            }
-            Err(err) => {
-                if trusted {
-                    return Err(io::Error::new(
-                        err.kind(),
-                        format!(
-                            "Failed to read project config file {}: {err}",
-                            config_file.display()
-                        ),
-                    ));
-                }
-                layers.push(ConfigLayerEntry::new_disabled(
-                    layer_source,
-                    default_empty_table(),
-                    format!(
-                        "Project directory is not trusted and project config could not be read (ignored): {err}"
-                    ),
-                ));
-            }
-        }
-    }
-
-    Ok(layers)
-}
-
 fn block_on_loader<F, T>(future: F) -> io::Result<T>
 where
     F: std::future::Future<Output = io::Result<T>> + Send + 'static,
@@ -814,6 +773,49 @@ extra = true
         assert!(table.is_empty(), "expected empty table when configs missing");
     }
 
+    #[tokio::test]
+    async fn requirements_record_their_managing_layer() {
+        let tmp = tempdir().expect("tempdir");
+        let requirements_path = tmp.path().join("requirements.toml");
+        let managed_path = tmp.path().join("managed_config.toml");
+        std::fs::write(
+            &requirements_path,
+            "allowed_model_providers = [\"openai\"]\nallowed_mcp_servers = [\"docs\"]\n",
+        )
+        .expect("write requirements");
+        std::fs::write(&managed_path, "sandbox_mode = \"read-only\"\n").expect("write managed config");
+
+        let overrides = LoaderOverrides {
+            system_config_path: Some(tmp.path().join("system_config.toml")),
+            managed_config_path: Some(managed_path.clone()),
+            requirements_path: Some(requirements_path.clone()),
+            #[cfg(target_os = "macos")]
+            managed_preferences_base64: None,
+        };
+        let requirements = load_config_requirements_internal(tmp.path(), overrides)
+            .await
+            .expect("load requirements");
+
+        assert_eq!(requirements.model_providers, Some(vec!["openai".to_string()]));
+        assert!(requirements.allows_mcp_server("docs"));
+        assert!(!requirements.allows_mcp_server("shell"));
+        assert!(
+            requirements
+                .sandbox_mode
+                .can_set(&code_protocol::config_types::SandboxMode::WorkspaceWrite)
+                .is_err()
+        );
+        assert_eq!(
+            requirements.sources.get("mcp_servers"),
+            Some(&RequirementsSource::RequirementsToml(requirements_path))
+        );
+        assert_eq!(
+            requirements.sources.get("sandbox_mode"),
+            Some(&RequirementsSource::LegacyManagedConfig(managed_path))
+        );
+        assert_eq!(requirements.sources.get("approval_policy"), None);
+    }
+
     #[cfg(target_os = "macos")]
     #[tokio::test]
     async fn managed_preferences_take_highest_precedence() {
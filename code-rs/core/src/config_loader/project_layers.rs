@@ -0,0 +1,336 @@
+use super::ConfigLayerEntry;
+use super::default_empty_table;
+use crate::config::CONFIG_TOML_FILE;
+use code_app_server_protocol::ConfigLayerSource;
+use code_utils_absolute_path::AbsolutePathBuf;
+use futures::future::join_all;
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use toml::Value as TomlValue;
+
+/// Upper bound on remembered canonical paths; the cache is reset rather than
+/// evicted piecemeal once it fills up.
+const CANONICAL_CACHE_MAX_ENTRIES: usize = 256;
+
+/// Canonical forms of `.code` folders and `CODE_HOME`, keyed by the path as
+/// first seen. Config is reloaded on every session start and on many
+/// settings changes, and each reload used to re-resolve the same symlinks.
+static CANONICAL_PATHS: LazyLock<Mutex<HashMap<PathBuf, PathBuf>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+async fn canonicalize_cached(path: &Path) -> PathBuf {
+    if let Ok(cache) = CANONICAL_PATHS.lock()
+        && let Some(hit) = cache.get(path)
+    {
+        return hit.clone();
+    }
+    match tokio::fs::canonicalize(path).await {
+        Ok(resolved) => {
+            if let Ok(mut cache) = CANONICAL_PATHS.lock() {
+                if cache.len() >= CANONICAL_CACHE_MAX_ENTRIES {
+                    cache.clear();
+                }
+                cache.insert(path.to_path_buf(), resolved.clone());
+            }
+            resolved
+        }
+        // Missing paths are not cached so a folder created later is picked up.
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+async fn is_dir(path: &Path) -> bool {
+    tokio::fs::metadata(path)
+        .await
+        .is_ok_and(|meta| meta.is_dir())
+}
+
+/// A `.code` folder found between the project root and cwd, with its config
+/// file already read.
+struct DotCodeDir {
+    dot_code: PathBuf,
+    normalized: PathBuf,
+    contents: io::Result<String>,
+}
+
+async fn read_dot_code_dir(dot_code: PathBuf) -> DotCodeDir {
+    let config_file = dot_code.join(CONFIG_TOML_FILE);
+    let (normalized, contents) = tokio::join!(
+        canonicalize_cached(&dot_code),
+        tokio::fs::read_to_string(&config_file),
+    );
+    DotCodeDir {
+        dot_code,
+        normalized,
+        contents,
+    }
+}
+
+/// Loads every `.code/config.toml` between the project root and `cwd`,
+/// ordered from the root down so deeper folders take precedence.
+///
+/// Ancestor probes, canonicalization and file reads are issued concurrently;
+/// the git root lookup (a subprocess) runs on the blocking pool alongside the
+/// probes instead of ahead of them. Layers are assembled in order afterwards,
+/// so errors surface exactly as they would from a serial walk.
+pub(super) async fn load_project_layers(
+    cwd: &Path,
+    code_home: &Path,
+    trusted: bool,
+) -> io::Result<Vec<ConfigLayerEntry>> {
+    let root_cwd = cwd.to_path_buf();
+    let root_lookup = tokio::task::spawn_blocking(move || {
+        crate::git_info::resolve_root_git_project_for_trust(&root_cwd)
+    });
+
+    let ancestors: Vec<PathBuf> = cwd.ancestors().map(Path::to_path_buf).collect();
+    let probes = join_all(ancestors.iter().map(|dir| async move {
+        let dot_code = dir.join(".code");
+        is_dir(&dot_code).await.then_some(dot_code)
+    }));
+    let (project_root, found, code_home_normalized) =
+        tokio::join!(root_lookup, probes, canonicalize_cached(code_home));
+    let project_root = project_root
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| cwd.to_path_buf());
+
+    // `ancestors` runs from cwd upwards; stop at the project root (inclusive).
+    let depth = ancestors
+        .iter()
+        .position(|dir| *dir == project_root)
+        .map_or(ancestors.len(), |idx| idx + 1);
+    let dot_code_dirs: Vec<PathBuf> = found
+        .into_iter()
+        .take(depth)
+        .flatten()
+        .rev()
+        .collect();
+
+    let dirs = join_all(dot_code_dirs.into_iter().map(read_dot_code_dir)).await;
+
+    let mut layers = Vec::<ConfigLayerEntry>::new();
+    for DotCodeDir {
+        dot_code,
+        normalized,
+        contents,
+    } in dirs
+    {
+        if normalized == code_home_normalized {
+            continue;
+        }
+
+        let config_file = dot_code.join(CONFIG_TOML_FILE);
+        let layer_source = ConfigLayerSource::Project {
+            dot_codex_folder: AbsolutePathBuf::from_absolute_path(&dot_code)?,
+        };
+
+        match contents {
+            Ok(contents) => match toml::from_str::<TomlValue>(&contents) {
+                Ok(config) => {
+                    if trusted {
+                        layers.push(ConfigLayerEntry::new(layer_source, config));
+                    } else {
+                        layers.push(ConfigLayerEntry::new_disabled(
+                            layer_source,
+                            config,
+                            "Project directory is not trusted; ignoring project config layer.",
+                        ));
+                    }
+                }
+                Err(err) => {
+                    if trusted {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            format!(
+                                "Failed to parse project config file {}: {err}",
+                                config_file.display()
+                            ),
+                        ));
+                    }
+                    layers.push(ConfigLayerEntry::new_disabled(
+                        layer_source,
+                        default_empty_table(),
+                        format!(
+                            "Project directory is not trusted and project config could not be parsed (ignored): {err}"
+                        ),
+                    ));
+                }
+            },
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                // Record an empty layer entry when the folder exists, even if config.toml is missing.
+                if trusted {
+                    layers.push(ConfigLayerEntry::new(layer_source, default_empty_table()));
+                } else {
+                    layers.push(ConfigLayerEntry::new_disabled(
+                        layer_source,
+                        default_empty_table(),
+                        "Project directory is not trusted; ignoring project config layer.",
+                    ));
+                }
+            }
+            Err(err) => {
+                if trusted {
+                    return Err(io::Error::new(
+                        err.kind(),
+                        format!(
+                            "Failed to read project config file {}: {err}",
+                            config_file.display()
+                        ),
+                    ));
+                }
+                layers.push(ConfigLayerEntry::new_disabled(
+                    layer_source,
+                    default_empty_table(),
+                    format!(
+                        "Project directory is not trusted and project config could not be read (ignored): {err}"
+                    ),
+                ));
+            }
+        }
+    }
+
+    Ok(layers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+    use tempfile::tempdir;
+
+    /// Builds `depth` nested directories under `base`, adding a `.code`
+    /// folder with a one-key config every `every` levels.
+    fn deep_tree(base: &Path, depth: usize, every: usize) -> PathBuf {
+        let mut dir = base.to_path_buf();
+        for level in 0..depth {
+            dir = dir.join(format!("d{level}"));
+            std::fs::create_dir_all(&dir).expect("create nested dir");
+            if level % every == 0 {
+                let dot_code = dir.join(".code");
+                std::fs::create_dir_all(&dot_code).expect("create .code");
+                std::fs::write(dot_code.join(CONFIG_TOML_FILE), format!("level = {level}\n"))
+                    .expect("write project config");
+            }
+        }
+        dir
+    }
+
+    fn layer_level(entry: &ConfigLayerEntry) -> Option<i64> {
+        entry.config.get("level").and_then(TomlValue::as_integer)
+    }
+
+    #[tokio::test]
+    async fn deep_layers_are_ordered_from_root_to_cwd() {
+        let tmp = tempdir().expect("tempdir");
+        let code_home = tmp.path().join("home");
+        std::fs::create_dir_all(&code_home).expect("create code home");
+        let cwd = deep_tree(tmp.path(), 12, 4);
+
+        let layers = load_project_layers(&cwd, &code_home, true)
+            .await
+            .expect("load project layers");
+
+        let levels: Vec<Option<i64>> = layers.iter().map(layer_level).collect();
+        assert_eq!(levels, vec![Some(0), Some(4), Some(8)]);
+        assert!(layers.iter().all(|layer| layer.disabled_reason.is_none()));
+    }
+
+    #[tokio::test]
+    async fn code_home_is_not_treated_as_a_project_layer() {
+        let tmp = tempdir().expect("tempdir");
+        let cwd = deep_tree(tmp.path(), 2, 1);
+        // Point CODE_HOME at the innermost `.code` folder.
+        let code_home = cwd.join(".code");
+
+        let layers = load_project_layers(&cwd, &code_home, true)
+            .await
+            .expect("load project layers");
+
+        let levels: Vec<Option<i64>> = layers.iter().map(layer_level).collect();
+        assert_eq!(levels, vec![Some(0)]);
+    }
+
+    #[tokio::test]
+    async fn untrusted_parse_errors_are_disabled_not_fatal() {
+        let tmp = tempdir().expect("tempdir");
+        let cwd = tmp.path().join("repo");
+        let dot_code = cwd.join(".code");
+        std::fs::create_dir_all(&dot_code).expect("create .code");
+        std::fs::write(dot_code.join(CONFIG_TOML_FILE), "not = [valid").expect("write config");
+
+        let layers = load_project_layers(&cwd, &tmp.path().join("home"), false)
+            .await
+            .expect("untrusted parse errors are ignored");
+        assert_eq!(layers.len(), 1);
+        assert!(layers[0].disabled_reason.is_some());
+
+        let err = load_project_layers(&cwd, &tmp.path().join("home"), true)
+            .await
+            .expect_err("trusted parse errors are fatal");
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[tokio::test]
+    async fn untrusted_layers_report_ignored_config_and_hooks() {
+        let tmp = tempdir().expect("tempdir");
+        let cwd = tmp.path().canonicalize().expect("canonical tempdir").join("repo");
+        let nested = cwd.join("pkg");
+        let root_code = cwd.join(".code");
+        let nested_code = nested.join(".code");
+        std::fs::create_dir_all(&root_code).expect("create root .code");
+        std::fs::create_dir_all(&nested_code).expect("create nested .code");
+        std::fs::write(root_code.join(CONFIG_TOML_FILE), "level = 0\n").expect("write config");
+        std::fs::write(nested_code.join("hooks.json"), "{}").expect("write hooks");
+        let empty = nested.join("empty");
+        std::fs::create_dir_all(empty.join(".code")).expect("create empty .code");
+        let status = std::process::Command::new("git")
+            .args(["init", "-q"])
+            .current_dir(&cwd)
+            .status()
+            .expect("git init");
+        assert!(status.success());
+
+        let layers = load_project_layers(&empty, &tmp.path().join("home"), false)
+            .await
+            .expect("load project layers");
+        let ignored = super::super::ConfigLayerStack::new(layers).ignored_project_layers();
+
+        assert_eq!(ignored.len(), 2);
+        assert_eq!(ignored[0].config_file, Some(root_code.join(CONFIG_TOML_FILE)));
+        assert_eq!(ignored[0].hooks_file, None);
+        assert_eq!(ignored[1].config_file, None);
+        assert_eq!(ignored[1].hooks_file, Some(nested_code.join("hooks.json")));
+    }
+
+    /// Prints load latency for a deep monorepo-style path. Run with
+    /// `CODE_CONFIG_PERF_NUMBERS=1 cargo test -p code-core deep_monorepo -- --nocapture`.
+    #[tokio::test(flavor = "multi_thread")]
+    async fn print_project_layer_cost_for_deep_monorepo_path() {
+        if std::env::var_os("CODE_CONFIG_PERF_NUMBERS").is_none() {
+            return;
+        }
+        let tmp = tempdir().expect("tempdir");
+        let code_home = tmp.path().join("home");
+        std::fs::create_dir_all(&code_home).expect("create code home");
+        let cwd = deep_tree(tmp.path(), 64, 8);
+
+        // Warm-up fills the canonicalization cache and the OS dentry cache.
+        let _ = load_project_layers(&cwd, &code_home, true).await;
+
+        let runs = 50u32;
+        let started = Instant::now();
+        for _ in 0..runs {
+            let layers = load_project_layers(&cwd, &code_home, true)
+                .await
+                .expect("load project layers");
+            assert_eq!(layers.len(), 8);
+        }
+        let avg_ms = started.elapsed().as_secs_f64() * 1_000.0 / f64::from(runs);
+        println!("config_loader_perf_numbers: depth=64 layers=8 runs={runs} avg_load_ms={avg_ms:.3}");
+    }
+}
@@ -13,6 +13,7 @@ use crate::config_types::Tone;
 use crate::personality_traits::PersonalityTraits;
 use crate::config_types::AppsToml;
 use crate::config_types::FeaturesToml;
+use crate::config_types::WebSearchToml;
 use crate::config_types::WindowsToml;
 use crate::protocol::AskForApproval;
 
@@ -33,6 +34,8 @@ pub struct ConfigProfile {
     /// [`ModelProviderInfo`](crate::model_provider_info::ModelProviderInfo) to use.
     pub model_provider: Option<String>,
     pub approval_policy: Option<AskForApproval>,
+    /// Name of a `[sandbox_presets.<name>]` entry to apply.
+    pub sandbox_preset: Option<String>,
     pub disable_response_storage: Option<bool>,
     pub model_reasoning_effort: Option<ReasoningEffort>,
     pub preferred_model_reasoning_effort: Option<ReasoningEffort>,
@@ -56,6 +59,8 @@ pub struct ConfigProfile {
     pub windows: Option<WindowsToml>,
     pub memories: Option<MemoriesToml>,
     pub chatgpt_base_url: Option<String>,
+    /// Replaces the top-level `[web_search]` table while this profile is active.
+    pub web_search: Option<WebSearchToml>,
     #[serde(default)]
     pub apps: Option<AppsToml>,
     pub features: Option<FeaturesToml>,
@@ -307,6 +307,117 @@ mod memories_tests {
     }
 }
 
+/// How much of each session is written to the rollout file under
+/// `CODE_HOME/sessions`.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TranscriptPersistence {
+    /// Everything the rollout normally keeps.
+    #[default]
+    Full,
+    /// Session metadata, user and assistant messages, and compaction
+    /// summaries; tool calls, command output and reasoning are dropped.
+    Summary,
+    /// Nothing is written, so the session cannot be resumed later.
+    None,
+}
+
+/// `[privacy]` in `config.toml` or under `[projects."<path>"]`.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
+pub struct PrivacyToml {
+    /// What is kept of each session transcript: `full`, `summary` or `none`.
+    pub transcripts: Option<TranscriptPersistence>,
+    /// Attach browser screenshots to turn status items.
+    pub screenshots: Option<bool>,
+    /// Leave the home directory, git branch and shell out of the environment
+    /// context sent with each turn.
+    pub redact_environment: Option<bool>,
+    /// Delete this project's saved sessions once they have been idle for
+    /// this many days.
+    pub retention_days: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct PrivacyConfig {
+    pub transcripts: TranscriptPersistence,
+    pub screenshots: bool,
+    pub redact_environment: bool,
+    pub retention_days: Option<u32>,
+}
+
+impl Default for PrivacyConfig {
+    fn default() -> Self {
+        Self {
+            transcripts: TranscriptPersistence::Full,
+            screenshots: true,
+            redact_environment: false,
+            retention_days: None,
+        }
+    }
+}
+
+impl PrivacyConfig {
+    pub fn apply_toml(&mut self, toml: &PrivacyToml) {
+        if let Some(value) = toml.transcripts {
+            self.transcripts = value;
+        }
+        if let Some(value) = toml.screenshots {
+            self.screenshots = value;
+        }
+        if let Some(value) = toml.redact_environment {
+            self.redact_environment = value;
+        }
+        if let Some(value) = toml.retention_days {
+            // Zero would delete the running session's own history.
+            self.retention_days = Some(value.max(1));
+        }
+    }
+}
+
+/// Project settings override the global ones key by key.
+pub fn resolve_privacy_config(
+    global: Option<&PrivacyToml>,
+    project: Option<&PrivacyToml>,
+) -> PrivacyConfig {
+    let mut resolved = PrivacyConfig::default();
+    if let Some(global) = global {
+        resolved.apply_toml(global);
+    }
+    if let Some(project) = project {
+        resolved.apply_toml(project);
+    }
+    resolved
+}
+
+#[cfg(test)]
+mod privacy_tests {
+    use super::PrivacyToml;
+    use super::TranscriptPersistence;
+    use super::resolve_privacy_config;
+
+    #[test]
+    fn project_privacy_overrides_global_per_key() {
+        let global = PrivacyToml {
+            transcripts: Some(TranscriptPersistence::Summary),
+            screenshots: Some(false),
+            retention_days: Some(30),
+            ..PrivacyToml::default()
+        };
+        let project = PrivacyToml {
+            transcripts: Some(TranscriptPersistence::None),
+            retention_days: Some(0),
+            ..PrivacyToml::default()
+        };
+
+        let resolved = resolve_privacy_config(Some(&global), Some(&project));
+
+        assert_eq!(resolved.transcripts, TranscriptPersistence::None);
+        assert!(!resolved.screenshots);
+        assert!(!resolved.redact_environment);
+        assert_eq!(resolved.retention_days, Some(1));
+    }
+}
+
 /// Determine where Code should store CLI auth credentials (the `auth.json` payload).
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
@@ -459,6 +570,21 @@ pub struct McpServerSchedulingToml {
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub max_queue_depth: Option<u32>,
+
+    /// Calls allowed to start in any 60-second window; later calls wait.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_calls_per_minute: Option<u32>,
+
+    /// Consecutive failed calls that open the circuit breaker. While open,
+    /// calls to the server fail immediately instead of waiting on it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub circuit_breaker_failures: Option<u32>,
+
+    /// How long the circuit stays open before one trial call is let through
+    /// (default: 30 seconds).
+    #[serde(default, with = "option_duration_secs", skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<f64>")]
+    pub circuit_breaker_cooldown_sec: Option<Duration>,
 }
 
 impl Default for McpServerSchedulingToml {
@@ -469,6 +595,9 @@ impl Default for McpServerSchedulingToml {
             min_interval_sec: None,
             queue_timeout_sec: None,
             max_queue_depth: None,
+            max_calls_per_minute: None,
+            circuit_breaker_failures: None,
+            circuit_breaker_cooldown_sec: None,
         }
     }
 }
@@ -664,6 +793,16 @@ impl<'de> Deserialize<'de> for McpServerConfig {
                 "scheduling.max_queue_depth must be >= 1",
             ));
         }
+        if scheduling.max_calls_per_minute == Some(0) {
+            return Err(SerdeError::custom(
+                "scheduling.max_calls_per_minute must be >= 1",
+            ));
+        }
+        if scheduling.circuit_breaker_failures == Some(0) {
+            return Err(SerdeError::custom(
+                "scheduling.circuit_breaker_failures must be >= 1",
+            ));
+        }
 
         // Normalize tool override keys (trim whitespace, drop empty entries).
         let mut tool_scheduling: BTreeMap<String, McpToolSchedulingOverrideToml> = BTreeMap::new();
@@ -1261,6 +1400,33 @@ pub struct McpToolId {
     pub tool_name: String,
 }
 
+/// Per-request cap on MCP tool schemas, configured under
+/// `[tools.mcp_budget]`. Tools that are left out can still be loaded by the
+/// model through `tools_list_more`.
+#[derive(Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+pub struct McpToolBudgetConfig {
+    /// Most MCP tool schemas sent with a single request.
+    pub max_tools: usize,
+
+    /// Score for the most recently used tool; older uses earn a
+    /// proportionally smaller share.
+    #[serde(default = "default_mcp_recent_use_weight")]
+    pub recent_use_weight: f32,
+
+    /// Score per word of a tool's name that also appears in the latest user
+    /// message.
+    #[serde(default = "default_mcp_name_match_weight")]
+    pub name_match_weight: f32,
+}
+
+const fn default_mcp_recent_use_weight() -> f32 {
+    2.0
+}
+
+const fn default_mcp_name_match_weight() -> f32 {
+    1.0
+}
+
 /// Configuration for external agent models
 #[derive(Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
@@ -1315,6 +1481,87 @@ fn default_true() -> bool {
     true
 }
 
+/// One entry under `[docs_bundles]`: a local documentation dump searched by
+/// the `docs_search` tool once `code docs index` has indexed it.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct DocsBundleToml {
+    /// Directory holding HTML, Markdown or plain-text documentation.
+    /// Relative paths are resolved against `CODE_HOME`.
+    pub path: PathBuf,
+
+    /// What the bundle covers (e.g. "Rust std and tokio API docs"); shown to
+    /// the model so it knows when to search it.
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// Set to false to keep the bundle configured but out of `docs_search`.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+/// Backend behind the `web_search` tool.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum WebSearchProviderKind {
+    /// The model provider's native `web_search` tool.
+    #[default]
+    Openai,
+    /// Bing Web Search API.
+    Bing,
+    /// Brave Search API.
+    Brave,
+    /// A SearXNG instance's JSON API.
+    Searxng,
+    /// An endpoint that returns `{"results": [{"title", "url", "snippet"}]}`.
+    Custom,
+}
+
+impl WebSearchProviderKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Openai => "openai",
+            Self::Bing => "bing",
+            Self::Brave => "brave",
+            Self::Searxng => "searxng",
+            Self::Custom => "custom",
+        }
+    }
+}
+
+/// `[web_search]`: where `web_search` queries go. Profiles may override it.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct WebSearchToml {
+    /// Search backend (default `openai`, the native tool).
+    #[serde(default)]
+    pub provider: WebSearchProviderKind,
+
+    /// API endpoint. Required for `searxng` (the instance base URL) and
+    /// `custom`; Bing and Brave default to their public APIs.
+    #[serde(default)]
+    pub endpoint: Option<String>,
+
+    /// Environment variable holding the API key. Defaults to
+    /// `BING_SEARCH_API_KEY` or `BRAVE_SEARCH_API_KEY`; for `custom` the key
+    /// is sent as a bearer token.
+    #[serde(default)]
+    pub api_key_env: Option<String>,
+
+    /// Results returned per query (default 5, at most 20).
+    #[serde(default)]
+    pub max_results: Option<usize>,
+
+    /// Requests allowed per minute; 0 disables the limit. Defaults to 100
+    /// for Bing, 60 for Brave and custom endpoints, 30 for SearXNG.
+    #[serde(default)]
+    pub requests_per_minute: Option<u32>,
+
+    /// Extra HTTP headers sent with every search request.
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+}
+
 /// GitHub integration settings.
 #[derive(Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
 pub struct GithubConfig {
@@ -1527,6 +1774,14 @@ fn default_settings_overlay_min_width() -> u16 {
     100
 }
 
+fn default_memory_soft_limit_mb() -> u64 {
+    4096
+}
+
+fn default_exec_output_fold_lines() -> usize {
+    40
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Default, JsonSchema)]
 #[repr(u8)]
 #[serde(rename_all = "kebab-case")]
@@ -2513,6 +2768,31 @@ pub struct Tui {
     #[serde(default = "default_true")]
     pub alternate_screen: bool,
 
+    /// Render for slow or high-latency terminals (for example over SSH).
+    /// Batches redraws, disables per-frame animations, and repaints streaming
+    /// answers only at paragraph boundaries. Also enabled by `--low-bandwidth`
+    /// or `CODE_TUI_LOW_BANDWIDTH=1`. Defaults to `false`.
+    #[serde(default)]
+    pub low_bandwidth: bool,
+
+    /// Resident memory (MiB) above which the TUI drops render caches and warns
+    /// that the session should be compacted. Set to `0` to disable.
+    /// Defaults to `4096`.
+    #[serde(default = "default_memory_soft_limit_mb")]
+    pub memory_soft_limit_mb: u64,
+
+    /// Command output longer than this many lines is folded to its first
+    /// lines when the command finishes; the fold hotkey expands it again.
+    /// Defaults to `40`.
+    #[serde(default = "default_exec_output_fold_lines")]
+    pub exec_output_fold_lines: usize,
+
+    /// Dock a live agents status panel to the right of the conversation
+    /// instead of only showing agents in the Ctrl+A overlay. F6 toggles it
+    /// for the session. Defaults to `false`.
+    #[serde(default)]
+    pub split_agents_panel: bool,
+
     /// Remember whether Auto Resolve is enabled for `/review` flows.
     #[serde(default = "default_true")]
     pub review_auto_resolve: bool,
@@ -2731,6 +3011,10 @@ impl Default for Tui {
             prevent_idle_sleep: false,
             upgrade_command: Vec::new(),
             alternate_screen: true,
+            low_bandwidth: false,
+            memory_soft_limit_mb: default_memory_soft_limit_mb(),
+            exec_output_fold_lines: default_exec_output_fold_lines(),
+            split_agents_panel: false,
             review_auto_resolve: true,
             auto_review_enabled: true,
             shell_presets: Vec::new(),
@@ -2760,6 +3044,291 @@ impl Tui {
     }
 }
 
+/// How readily a reviewer profile flags issues.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ReviewStrictness {
+    /// Only clear, high-impact bugs.
+    Lenient,
+    /// The default review guidelines.
+    Balanced,
+    /// Also lower-priority issues the author would still want to fix.
+    Strict,
+}
+
+/// Reviewer persona defined under `[review_profiles.<name>]` and selected
+/// with `/review --profile <name>` or `review_profile`.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
+pub struct ReviewProfile {
+    #[serde(default)]
+    pub strictness: Option<ReviewStrictness>,
+
+    /// Areas to prioritize, e.g. "security" or "error handling".
+    #[serde(default)]
+    pub focus_areas: Vec<String>,
+
+    /// Free-form guidance on how findings should be worded.
+    #[serde(default)]
+    pub tone: Option<String>,
+
+    /// Checks the reviewer must explicitly go through before its verdict.
+    #[serde(default)]
+    pub checklist: Vec<String>,
+}
+
+/// Background Auto Review settings, configured under `[auto_review]`.
+/// Findings outside the thresholds are still reported, but do not count as
+/// issues and are not passed to the auto-resolve loop.
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, JsonSchema)]
+pub struct AutoReviewConfig {
+    /// Least severe priority that counts (0 = P0, the most severe). Unset
+    /// counts every priority.
+    #[serde(default)]
+    pub min_priority: Option<i32>,
+
+    /// Minimum reviewer confidence (0.0-1.0) that counts. Unset counts
+    /// findings regardless of confidence.
+    #[serde(default)]
+    pub min_confidence: Option<f32>,
+
+    /// Globs, relative to the repository root, for the paths auto review
+    /// covers. A review only starts when a turn changes a covered path.
+    /// Empty covers every path.
+    #[serde(default)]
+    pub include: Vec<String>,
+
+    /// Globs for paths auto review ignores even when `include` matches them.
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+impl AutoReviewConfig {
+    pub fn has_threshold(&self) -> bool {
+        self.min_priority.is_some() || self.min_confidence.is_some()
+    }
+
+    pub fn has_path_filter(&self) -> bool {
+        !self.include.is_empty() || !self.exclude.is_empty()
+    }
+
+    /// Compiles `include` and `exclude`. Globs without a `/` match the file
+    /// name at any depth (`*.lock`); others match the whole path, where `*`
+    /// stays within one directory and `**` spans several.
+    pub fn path_filter(&self) -> Result<AutoReviewPathFilter, globset::Error> {
+        let include = if self.include.is_empty() {
+            None
+        } else {
+            Some(build_path_glob_set(&self.include)?)
+        };
+        Ok(AutoReviewPathFilter {
+            include,
+            exclude: build_path_glob_set(&self.exclude)?,
+        })
+    }
+
+    /// Whether a finding with this priority and confidence counts.
+    pub fn accepts(&self, priority: i32, confidence: f32) -> bool {
+        self.min_priority.is_none_or(|min| priority <= min)
+            && self.min_confidence.is_none_or(|min| confidence >= min)
+    }
+}
+
+/// Compiled `[auto_review]` include/exclude globs.
+#[derive(Debug, Clone)]
+pub struct AutoReviewPathFilter {
+    include: Option<globset::GlobSet>,
+    exclude: globset::GlobSet,
+}
+
+impl AutoReviewPathFilter {
+    /// Whether auto review covers `path`, given relative to the repository
+    /// root with `/` separators (as `git diff --name-only` prints it).
+    pub fn matches(&self, path: &str) -> bool {
+        self.include.as_ref().is_none_or(|include| include.is_match(path))
+            && !self.exclude.is_match(path)
+    }
+}
+
+fn build_path_glob_set(patterns: &[String]) -> Result<globset::GlobSet, globset::Error> {
+    let mut builder = globset::GlobSetBuilder::new();
+    for pattern in patterns {
+        let pattern = pattern.trim().trim_start_matches("./");
+        let pattern = if pattern.contains('/') {
+            pattern.trim_start_matches('/').to_owned()
+        } else {
+            format!("**/{pattern}")
+        };
+        builder.add(
+            globset::GlobBuilder::new(&pattern)
+                .literal_separator(true)
+                .build()?,
+        );
+    }
+    builder.build()
+}
+
+/// Review settings configured under `[review]`.
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+pub struct ReviewConfig {
+    /// Checkout of the worktrees background reviews run in.
+    #[serde(default)]
+    pub worktree: ReviewWorktreeConfig,
+}
+
+/// How background review worktrees are checked out, configured under
+/// `[review.worktree]`.
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+pub struct ReviewWorktreeConfig {
+    /// Check out only the directories touched by the reviewed changes plus
+    /// `context_paths` (sparse checkout). Meant for very large repositories.
+    #[serde(default)]
+    pub sparse: bool,
+
+    /// Directories, relative to the repository root, that sparse review
+    /// worktrees always include (e.g. shared build configuration).
+    #[serde(default)]
+    pub context_paths: Vec<String>,
+}
+
+/// Retention for ghost commits, the snapshots behind undo and auto review,
+/// configured under `[ghost_commits]`.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+pub struct GhostCommitsConfig {
+    /// Most recent snapshots kept per session (0 = unlimited).
+    #[serde(default = "default_ghost_keep_per_session")]
+    pub keep_per_session: usize,
+
+    /// Snapshots older than this many days are pruned (0 = never).
+    #[serde(default = "default_ghost_max_age_days")]
+    pub max_age_days: u64,
+
+    /// Apply the retention policy to the workspace repository when a session
+    /// ends.
+    #[serde(default = "default_true")]
+    pub cleanup_on_exit: bool,
+
+    /// Snapshot the workspace when each user turn starts so `Op::RevertTurn`
+    /// (`/undo-turn`) can put the turn's file changes back.
+    #[serde(default = "default_true")]
+    pub turn_snapshots: bool,
+}
+
+const fn default_ghost_keep_per_session() -> usize {
+    50
+}
+
+const fn default_ghost_max_age_days() -> u64 {
+    14
+}
+
+impl GhostCommitsConfig {
+    pub fn retention(&self) -> code_git_tooling::GhostRetention {
+        code_git_tooling::GhostRetention {
+            keep_per_session: (self.keep_per_session > 0).then_some(self.keep_per_session),
+            max_age: (self.max_age_days > 0)
+                .then(|| std::time::Duration::from_secs(self.max_age_days.saturating_mul(24 * 60 * 60))),
+        }
+    }
+}
+
+impl Default for GhostCommitsConfig {
+    fn default() -> Self {
+        Self {
+            keep_per_session: default_ghost_keep_per_session(),
+            max_age_days: default_ghost_max_age_days(),
+            cleanup_on_exit: true,
+            turn_snapshots: true,
+        }
+    }
+}
+
+/// Style guide for `code exec --final-message-style concise`, configured
+/// under `[final_message_style]`.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+pub struct FinalMessageStyleConfig {
+    /// Extra opening phrases to strip, matched case-insensitively at the
+    /// start of the message, on top of the built-in ones ("Sure!", "Great
+    /// question.", ...).
+    #[serde(default)]
+    pub strip_preambles: Vec<String>,
+
+    /// Drop a closing offer such as "Let me know if you need anything else."
+    #[serde(default = "default_true")]
+    pub strip_closing_offers: bool,
+
+    /// Most consecutive blank lines kept between paragraphs.
+    #[serde(default = "default_final_message_max_blank_lines")]
+    pub max_blank_lines: usize,
+}
+
+const fn default_final_message_max_blank_lines() -> usize {
+    1
+}
+
+impl Default for FinalMessageStyleConfig {
+    fn default() -> Self {
+        Self {
+            strip_preambles: Vec::new(),
+            strip_closing_offers: true,
+            max_blank_lines: default_final_message_max_blank_lines(),
+        }
+    }
+}
+
+/// How Git LFS, binary, and very large files show up in turn diffs and
+/// review scopes, configured under `[large_files]`.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+pub struct LargeFilesConfig {
+    /// Diff the contents of these files instead of replacing them with a
+    /// one-line notice (path, size, LFS pointer change).
+    #[serde(default)]
+    pub include_in_diffs: bool,
+
+    /// Text files larger than this many KiB are treated as large (0 = no
+    /// limit).
+    #[serde(default = "default_large_file_threshold_kib")]
+    pub threshold_kib: u64,
+}
+
+const fn default_large_file_threshold_kib() -> u64 {
+    1024
+}
+
+impl LargeFilesConfig {
+    pub fn threshold_bytes(&self) -> u64 {
+        if self.threshold_kib == 0 {
+            u64::MAX
+        } else {
+            self.threshold_kib.saturating_mul(1024)
+        }
+    }
+}
+
+impl Default for LargeFilesConfig {
+    fn default() -> Self {
+        Self {
+            include_in_diffs: false,
+            threshold_kib: default_large_file_threshold_kib(),
+        }
+    }
+}
+
+/// Safety nets around git commands run by the model, configured under
+/// `[git_safety]`.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+pub struct GitSafetyConfig {
+    /// Stash uncommitted changes before a model-run checkout, switch, rebase,
+    /// or hard reset, and restore them once the command finishes.
+    #[serde(default = "default_true")]
+    pub auto_stash: bool,
+}
+
+impl Default for GitSafetyConfig {
+    fn default() -> Self {
+        Self { auto_stash: true }
+    }
+}
+
 /// User acknowledgements for in-product notices (distinct from notifications).
 #[derive(Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
 pub struct Notice {
@@ -2844,7 +3413,7 @@ pub fn default_auto_drive_model_routing_entries() -> Vec<AutoDriveModelRoutingEn
 }
 
 /// Auto Drive behavioral defaults persisted via `config.toml`.
-#[derive(Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[derive(Deserialize, Debug, Clone, PartialEq, JsonSchema)]
 pub struct AutoDriveSettings {
     #[serde(default = "default_true")]
     pub review_enabled: bool,
@@ -2890,9 +3459,27 @@ pub struct AutoDriveSettings {
     #[serde(default)]
     pub auto_review_followup_attempts: AutoResolveAttemptLimit,
 
+    /// Which review findings the auto-resolve fix phase works on.
+    #[serde(default)]
+    pub auto_resolve_findings: AutoResolveFindingSelection,
+
+    /// Highest priority number to fix in `threshold`/`pick` mode (0 = P0,
+    /// the most severe). Unset fixes every priority.
+    #[serde(default)]
+    pub auto_resolve_max_priority: Option<i32>,
+
+    /// Minimum reviewer confidence (0.0-1.0) to fix in `threshold`/`pick`
+    /// mode. Unset fixes regardless of confidence.
+    #[serde(default)]
+    pub auto_resolve_min_confidence: Option<f32>,
+
     /// Maximum number of coordinator turns before stopping the session (0 = unlimited).
     #[serde(default = "default_auto_drive_coordinator_turn_cap")]
     pub coordinator_turn_cap: u32,
+
+    /// Checked after each turn; the run stops once any condition is met.
+    #[serde(default)]
+    pub stop_conditions: AutoDriveStopConditions,
 }
 
 impl Default for AutoDriveSettings {
@@ -2911,7 +3498,11 @@ impl Default for AutoDriveSettings {
             model_reasoning_effort: default_auto_drive_reasoning_effort(),
             auto_resolve_review_attempts: AutoResolveAttemptLimit::default(),
             auto_review_followup_attempts: AutoResolveAttemptLimit::default(),
+            auto_resolve_findings: AutoResolveFindingSelection::default(),
+            auto_resolve_max_priority: None,
+            auto_resolve_min_confidence: None,
             coordinator_turn_cap: default_auto_drive_coordinator_turn_cap(),
+            stop_conditions: AutoDriveStopConditions::default(),
         }
     }
 }
@@ -2920,6 +3511,52 @@ const fn default_auto_drive_coordinator_turn_cap() -> u32 {
     0
 }
 
+/// `[auto_drive.stop_conditions]`: user-defined "done" checks evaluated after
+/// every completed Auto Drive turn.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
+pub struct AutoDriveStopConditions {
+    /// Commands run in the session directory; met when the exit code matches.
+    #[serde(default)]
+    pub commands: Vec<AutoDriveCommandStopCondition>,
+
+    /// Files checked for a marker string.
+    #[serde(default)]
+    pub files: Vec<AutoDriveFileStopCondition>,
+}
+
+impl AutoDriveStopConditions {
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty() && self.files.is_empty()
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+pub struct AutoDriveCommandStopCondition {
+    /// Shell command to run, e.g. `cargo test`.
+    pub run: String,
+
+    /// Exit code that satisfies the condition.
+    #[serde(default)]
+    pub exit_code: i32,
+
+    /// Seconds before the command is killed and the condition counts as unmet.
+    #[serde(default = "default_stop_condition_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+const fn default_stop_condition_timeout_secs() -> u64 {
+    600
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+pub struct AutoDriveFileStopCondition {
+    /// File to read; relative paths resolve against the session directory.
+    pub path: PathBuf,
+
+    /// Marker text; the condition is met once the file contains it.
+    pub contains: String,
+}
+
 fn default_auto_drive_model() -> String {
     // Keep aligned with the coordinator's preferred model fallback.
     String::from("gpt-5.1")
@@ -2929,6 +3566,22 @@ const fn default_auto_drive_reasoning_effort() -> ReasoningEffort {
     ReasoningEffort::High
 }
 
+/// How auto-resolve picks the findings it passes to the fix phase. Findings
+/// left out are reported as acknowledged but not fixed.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum AutoResolveFindingSelection {
+    /// Fix every finding.
+    #[default]
+    All,
+    /// Fix findings that meet `auto_resolve_max_priority` and
+    /// `auto_resolve_min_confidence`.
+    Threshold,
+    /// Ask which findings to fix, pre-selecting those that meet the
+    /// threshold. Non-interactive runs behave like `threshold`.
+    Pick,
+}
+
 #[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 pub enum AutoDriveContinueMode {
@@ -3203,6 +3856,10 @@ pub struct SandboxWorkspaceWrite {
     pub writable_roots: Vec<PathBuf>,
     #[serde(default)]
     pub network_access: bool,
+    /// Hostnames (`crates.io`, `*.npmjs.org`) and CIDR ranges
+    /// (`10.20.0.0/16`) commands may reach when `network_access` is false.
+    #[serde(default)]
+    pub network_allowlist: Vec<String>,
     #[serde(default)]
     pub exclude_tmpdir_env_var: bool,
     #[serde(default)]
@@ -3213,6 +3870,45 @@ pub struct SandboxWorkspaceWrite {
     pub allow_git_writes: bool,
 }
 
+/// Reusable sandbox policy defined under `[sandbox_presets.<name>]` and
+/// selected with `sandbox_preset` (top level, profile, or `--sandbox-preset`).
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+pub struct SandboxPresetToml {
+    /// Sandbox mode the preset selects.
+    pub sandbox_mode: Option<code_protocol::config_types::SandboxMode>,
+    /// Extra writable roots in `workspace-write` mode.
+    #[serde(default)]
+    pub writable_roots: Vec<PathBuf>,
+    /// Allow outbound network access in `workspace-write` mode.
+    #[serde(default)]
+    pub network_access: bool,
+    /// Hosts and CIDR ranges reachable when `network_access` is false.
+    #[serde(default)]
+    pub network_allowlist: Vec<String>,
+    #[serde(default)]
+    pub exclude_tmpdir_env_var: bool,
+    #[serde(default)]
+    pub exclude_slash_tmp: bool,
+    #[serde(default = "crate::config_types::default_true_bool")]
+    pub allow_git_writes: bool,
+    /// Environment variable patterns passed to commands; replaces
+    /// `shell_environment_policy.include_only` when set.
+    pub env_allowlist: Option<Vec<String>>,
+}
+
+impl SandboxPresetToml {
+    pub(crate) fn workspace_write(&self) -> SandboxWorkspaceWrite {
+        SandboxWorkspaceWrite {
+            writable_roots: self.writable_roots.clone(),
+            network_access: self.network_access,
+            network_allowlist: self.network_allowlist.clone(),
+            exclude_tmpdir_env_var: self.exclude_tmpdir_env_var,
+            exclude_slash_tmp: self.exclude_slash_tmp,
+            allow_git_writes: self.allow_git_writes,
+        }
+    }
+}
+
 // Serde helper: default to true for `allow_git_writes` when omitted.
 pub(crate) const fn default_true_bool() -> bool { true }
 
@@ -3248,6 +3944,10 @@ pub struct ShellEnvironmentPolicyToml {
     pub include_only: Option<Vec<String>>,
 
     pub experimental_use_profile: Option<bool>,
+
+    /// Capture the rc file environment once per session and source that
+    /// snapshot for each command instead of the rc file itself.
+    pub experimental_use_snapshot: Option<bool>,
 }
 
 pub type EnvironmentVariablePattern = WildMatchPattern<'*', '?'>;
@@ -3279,6 +3979,10 @@ pub struct ShellEnvironmentPolicy {
 
     /// If true, the shell profile will be used to run the command.
     pub use_profile: bool,
+
+    /// If true, commands source a per-session snapshot of the rc file
+    /// environment rather than the rc file itself.
+    pub use_snapshot: bool,
 }
 
 impl Default for ShellEnvironmentPolicy {
@@ -3290,6 +3994,7 @@ impl Default for ShellEnvironmentPolicy {
             r#set: HashMap::new(),
             include_only: Vec::new(),
             use_profile: false,
+            use_snapshot: false,
         }
     }
 }
@@ -3313,6 +4018,7 @@ impl From<ShellEnvironmentPolicyToml> for ShellEnvironmentPolicy {
             .map(|s| EnvironmentVariablePattern::new_case_insensitive(&s))
             .collect();
         let use_profile = toml.experimental_use_profile.unwrap_or(false);
+        let use_snapshot = toml.experimental_use_snapshot.unwrap_or(false);
 
         Self {
             inherit,
@@ -3321,6 +4027,112 @@ impl From<ShellEnvironmentPolicyToml> for ShellEnvironmentPolicy {
             r#set,
             include_only,
             use_profile,
+            use_snapshot,
+        }
+    }
+}
+
+/// Credential-like variable names withheld from spawned processes when
+/// `[spawned_env].deny` is not set.
+pub const DEFAULT_SPAWNED_ENV_DENY: &[&str] = &[
+    "AWS_*",
+    "AZURE_CLIENT_SECRET",
+    "GOOGLE_APPLICATION_CREDENTIALS",
+    "*_TOKEN",
+    "*_API_KEY",
+    "*_SECRET",
+    "*_SECRET_KEY",
+    "*_ACCESS_KEY",
+    "*_PASSWORD",
+];
+
+/// `[spawned_env]`: which parent environment variables reach exec tool calls
+/// and MCP stdio servers.
+#[derive(Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+pub struct SpawnedEnvToml {
+    /// Name patterns (`*` and `?` wildcards, case-insensitive) withheld from
+    /// spawned processes. Defaults to common credential names such as
+    /// `AWS_*` and `*_TOKEN`; set to `[]` to withhold nothing.
+    pub deny: Option<Vec<String>>,
+
+    /// Patterns exempted from `deny`.
+    #[serde(default)]
+    pub allow: Vec<String>,
+
+    /// Parent variables forwarded to every MCP stdio server in addition to
+    /// the fixed baseline (`HOME`, `PATH`, ...). `deny` still applies.
+    #[serde(default)]
+    pub mcp_passthrough: Vec<String>,
+
+    /// Per-command exceptions to `deny`. Running a matching command with any
+    /// of these variables always asks for approval first.
+    #[serde(default)]
+    pub commands: Vec<SpawnedEnvCommandToml>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+pub struct SpawnedEnvCommandToml {
+    /// Program name (e.g. `aws`) that every command in the invocation must run.
+    pub command: String,
+
+    /// Withheld variables released to that program.
+    pub allow: Vec<String>,
+}
+
+/// Resolved form of [`SpawnedEnvToml`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpawnedEnvPolicy {
+    pub deny: Vec<EnvironmentVariablePattern>,
+    pub allow: Vec<EnvironmentVariablePattern>,
+    pub mcp_passthrough: Vec<EnvironmentVariablePattern>,
+    pub commands: Vec<SpawnedEnvCommandOverride>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpawnedEnvCommandOverride {
+    pub command: String,
+    pub allow: Vec<EnvironmentVariablePattern>,
+}
+
+fn env_patterns<S: AsRef<str>>(patterns: &[S]) -> Vec<EnvironmentVariablePattern> {
+    patterns
+        .iter()
+        .map(|pattern| EnvironmentVariablePattern::new_case_insensitive(pattern.as_ref()))
+        .collect()
+}
+
+impl SpawnedEnvPolicy {
+    /// Whether `name` is withheld unless a per-command override releases it.
+    pub fn withholds(&self, name: &str) -> bool {
+        self.deny.iter().any(|pattern| pattern.matches(name))
+            && !self.allow.iter().any(|pattern| pattern.matches(name))
+    }
+}
+
+impl Default for SpawnedEnvPolicy {
+    fn default() -> Self {
+        SpawnedEnvToml::default().into()
+    }
+}
+
+impl From<SpawnedEnvToml> for SpawnedEnvPolicy {
+    fn from(toml: SpawnedEnvToml) -> Self {
+        let deny = match toml.deny {
+            Some(deny) => env_patterns(&deny),
+            None => env_patterns(DEFAULT_SPAWNED_ENV_DENY),
+        };
+        Self {
+            deny,
+            allow: env_patterns(&toml.allow),
+            mcp_passthrough: env_patterns(&toml.mcp_passthrough),
+            commands: toml
+                .commands
+                .into_iter()
+                .map(|entry| SpawnedEnvCommandOverride {
+                    command: entry.command,
+                    allow: env_patterns(&entry.allow),
+                })
+                .collect(),
         }
     }
 }
@@ -3503,6 +4315,49 @@ pub struct ProjectCommandConfig {
     pub timeout_ms: Option<u64>,
 }
 
+/// Tool groups that can get their own output budget via
+/// `[tool_output_limits]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToolOutputCategory {
+    Shell,
+    FileRead,
+    Mcp,
+    Browser,
+    Other,
+}
+
+/// Per-category caps, in bytes, on tool output recorded in conversation
+/// history. Categories left unset use `tool_output_max_bytes`.
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, JsonSchema)]
+pub struct ToolOutputLimits {
+    /// Shell commands (`shell`, `exec_command`, `write_stdin`, `wait`).
+    #[serde(default)]
+    pub shell: Option<usize>,
+    /// File reads and searches (`read_file`, `list_dir`, `grep_files`).
+    #[serde(default)]
+    pub file_read: Option<usize>,
+    /// MCP tool results and resource reads.
+    #[serde(default)]
+    pub mcp: Option<usize>,
+    /// Browser actions and `web_fetch` extracts.
+    #[serde(default)]
+    pub browser: Option<usize>,
+}
+
+impl ToolOutputLimits {
+    /// Budget for `category`, falling back to `default` when unset.
+    pub fn budget(&self, category: ToolOutputCategory, default: usize) -> usize {
+        let limit = match category {
+            ToolOutputCategory::Shell => self.shell,
+            ToolOutputCategory::FileRead => self.file_read,
+            ToolOutputCategory::Mcp => self.mcp,
+            ToolOutputCategory::Browser => self.browser,
+            ToolOutputCategory::Other => None,
+        };
+        limit.unwrap_or(default)
+    }
+}
+
 /// Retention policy configuration for `env_ctx_v2` timeline management.
 #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
@@ -4023,4 +4878,28 @@ mod tests {
         .expect("should not fail to deserialize invalid theme config table");
         assert_eq!(cfg.tui.theme.name, ThemeName::LightPhoton);
     }
+
+    #[test]
+    fn auto_review_path_filter_matches_names_and_paths() {
+        let config = AutoReviewConfig {
+            include: vec!["src/**".to_string(), "Cargo.toml".to_string()],
+            exclude: vec!["**/generated/**".to_string(), "*.lock".to_string()],
+            ..Default::default()
+        };
+        let filter = config.path_filter().expect("valid globs");
+
+        assert!(filter.matches("src/main.rs"));
+        assert!(filter.matches("src/tui/app.rs"));
+        assert!(filter.matches("Cargo.toml"));
+        assert!(filter.matches("crates/core/Cargo.toml"));
+        assert!(!filter.matches("src/generated/schema.rs"));
+        assert!(!filter.matches("src/Cargo.lock"));
+        assert!(!filter.matches("docs/readme.md"));
+
+        let bad = AutoReviewConfig {
+            exclude: vec!["src/[".to_string()],
+            ..Default::default()
+        };
+        assert!(bad.path_filter().is_err());
+    }
 }
@@ -1,28 +1,42 @@
 use code_protocol::models::ResponseItem;
 
+mod interned;
+
+use interned::Interner;
+use interned::StoredItem;
+
 /// Transcript of conversation history
+///
+/// Items are kept in an interned form so repeated large strings (tool output,
+/// screenshots, re-read files) share one allocation across a long session.
 #[derive(Debug, Clone, Default)]
 pub(crate) struct ConversationHistory {
     /// The oldest items are at the beginning of the vector.
-    items: Vec<ResponseItem>,
+    items: Vec<StoredItem>,
+    interner: Interner,
 }
 
 impl ConversationHistory {
     pub(crate) fn new() -> Self {
-        Self { items: Vec::new() }
+        Self::default()
     }
 
     /// Returns a clone of the contents in the transcript.
     pub(crate) fn contents(&self) -> Vec<ResponseItem> {
-        self.items.clone()
+        self.items.iter().map(StoredItem::to_item).collect()
     }
 
     /// Drains and returns the contents of the transcript.
     ///
-    /// This avoids cloning large history items (e.g., base64 screenshots) when
-    /// callers need to re-apply retention policies.
+    /// Callers re-applying retention policies hand the result back through
+    /// `replace`/`replace_filtered`, which re-interns it.
     pub(crate) fn take_contents(&mut self) -> Vec<ResponseItem> {
-        std::mem::take(&mut self.items)
+        let items = std::mem::take(&mut self.items)
+            .iter()
+            .map(StoredItem::to_item)
+            .collect();
+        self.interner.clear();
+        items
     }
 
     /// `items` is ordered from oldest to newest.
@@ -36,27 +50,50 @@ impl ConversationHistory {
                 continue;
             }
 
-            self.items.push(item.clone());
+            self.items
+                .push(StoredItem::intern(item.clone(), &mut self.interner));
         }
     }
 
+    /// Name of the tool invoked by the call `call_id`, searching newest first.
+    pub(crate) fn tool_call_name(&self, call_id: &str) -> Option<String> {
+        self.items.iter().rev().find_map(|item| match item {
+            StoredItem::FunctionCall { name, call_id: id, .. }
+            | StoredItem::CustomToolCall { name, call_id: id, .. }
+                if id == call_id =>
+            {
+                Some(name.clone())
+            }
+            StoredItem::Plain(ResponseItem::LocalShellCall {
+                call_id: Some(id), ..
+            }) if id == call_id => Some("local_shell".to_owned()),
+            _ => None,
+        })
+    }
+
     /// Replace the entire history with a new set of items.
     /// Filtering is intentionally skipped here so compaction can preserve any
     /// non-standard items (e.g., bridge summaries) exactly as computed.
     pub(crate) fn replace(&mut self, items: Vec<ResponseItem>) {
-        self.items = items;
+        self.items.clear();
+        self.interner.prune();
+        self.items.reserve(items.len());
+        for item in items {
+            self.items.push(StoredItem::intern(item, &mut self.interner));
+        }
     }
 
     /// Replace the entire history, filtering out any items that should not be
     /// re-sent to the model.
     pub(crate) fn replace_filtered(&mut self, items: Vec<ResponseItem>) {
         self.items.clear();
+        self.interner.prune();
         self.items.reserve(items.len());
         for item in items {
             if !is_api_message(&item) {
                 continue;
             }
-            self.items.push(item);
+            self.items.push(StoredItem::intern(item, &mut self.interner));
         }
     }
 
@@ -0,0 +1,347 @@
+//! Compact storage for long-lived transcript items.
+//!
+//! Long sessions repeat the same large strings many times: identical tool
+//! outputs, re-read files, unchanged screenshots, repeated command banners.
+//! `ResponseItem` owns a fresh `String` for each of them, so the transcript
+//! keeps every copy alive. Here the bulky text fields are stored as shared
+//! `Arc<str>` handles deduplicated through an [`Interner`]; full
+//! `ResponseItem`s are rebuilt only when a request payload is assembled.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use code_protocol::models::ContentItem;
+use code_protocol::models::FunctionCallOutputBody;
+use code_protocol::models::FunctionCallOutputContentItem;
+use code_protocol::models::FunctionCallOutputPayload;
+use code_protocol::models::ImageDetail;
+use code_protocol::models::MessagePhase;
+use code_protocol::models::ResponseItem;
+
+/// Strings shorter than this are cheap to duplicate and rarely repeat
+/// verbatim, so they are stored without a lookup.
+const INTERN_MIN_BYTES: usize = 64;
+
+/// Deduplicating string table shared by every item in one transcript.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Interner {
+    strings: HashSet<Arc<str>>,
+}
+
+impl Interner {
+    pub(crate) fn intern(&mut self, value: String) -> Arc<str> {
+        if value.len() < INTERN_MIN_BYTES {
+            return Arc::from(value);
+        }
+        if let Some(existing) = self.strings.get(value.as_str()) {
+            return Arc::clone(existing);
+        }
+        let shared: Arc<str> = Arc::from(value);
+        self.strings.insert(Arc::clone(&shared));
+        shared
+    }
+
+    /// Drops strings no longer referenced by any stored item.
+    pub(crate) fn prune(&mut self) {
+        self.strings.retain(|s| Arc::strong_count(s) > 1);
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.strings.clear();
+    }
+
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        self.strings.len()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum StoredItem {
+    Message {
+        id: Option<String>,
+        role: String,
+        content: Vec<StoredContent>,
+        end_turn: Option<bool>,
+        phase: Option<MessagePhase>,
+    },
+    FunctionCall {
+        id: Option<String>,
+        name: String,
+        namespace: Option<String>,
+        arguments: Arc<str>,
+        call_id: String,
+    },
+    FunctionCallOutput {
+        call_id: String,
+        output: StoredOutput,
+    },
+    CustomToolCall {
+        id: Option<String>,
+        status: Option<String>,
+        call_id: String,
+        name: String,
+        input: Arc<str>,
+    },
+    CustomToolCallOutput {
+        call_id: String,
+        name: Option<String>,
+        output: StoredOutput,
+    },
+    /// Items that are small or rare enough to keep as-is.
+    Plain(ResponseItem),
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum StoredContent {
+    InputText(Arc<str>),
+    InputImage(Arc<str>),
+    OutputText(Arc<str>),
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct StoredOutput {
+    body: StoredBody,
+    success: Option<bool>,
+}
+
+#[derive(Debug, Clone)]
+enum StoredBody {
+    Text(Arc<str>),
+    ContentItems(Vec<StoredToolContent>),
+}
+
+#[derive(Debug, Clone)]
+enum StoredToolContent {
+    InputText(Arc<str>),
+    InputImage {
+        image_url: Arc<str>,
+        detail: Option<ImageDetail>,
+    },
+}
+
+impl StoredItem {
+    pub(crate) fn intern(item: ResponseItem, interner: &mut Interner) -> Self {
+        match item {
+            ResponseItem::Message { id, role, content, end_turn, phase } => Self::Message {
+                id,
+                role,
+                content: content
+                    .into_iter()
+                    .map(|c| StoredContent::intern(c, interner))
+                    .collect(),
+                end_turn,
+                phase,
+            },
+            ResponseItem::FunctionCall { id, name, namespace, arguments, call_id } => {
+                Self::FunctionCall {
+                    id,
+                    name,
+                    namespace,
+                    arguments: interner.intern(arguments),
+                    call_id,
+                }
+            }
+            ResponseItem::FunctionCallOutput { call_id, output } => Self::FunctionCallOutput {
+                call_id,
+                output: StoredOutput::intern(output, interner),
+            },
+            ResponseItem::CustomToolCall { id, status, call_id, name, input } => {
+                Self::CustomToolCall {
+                    id,
+                    status,
+                    call_id,
+                    name,
+                    input: interner.intern(input),
+                }
+            }
+            ResponseItem::CustomToolCallOutput { call_id, name, output } => {
+                Self::CustomToolCallOutput {
+                    call_id,
+                    name,
+                    output: StoredOutput::intern(output, interner),
+                }
+            }
+            other => Self::Plain(other),
+        }
+    }
+
+    pub(crate) fn to_item(&self) -> ResponseItem {
+        match self {
+            Self::Message { id, role, content, end_turn, phase } => ResponseItem::Message {
+                id: id.clone(),
+                role: role.clone(),
+                content: content.iter().map(StoredContent::to_item).collect(),
+                end_turn: *end_turn,
+                phase: phase.clone(),
+            },
+            Self::FunctionCall { id, name, namespace, arguments, call_id } => {
+                ResponseItem::FunctionCall {
+                    id: id.clone(),
+                    name: name.clone(),
+                    namespace: namespace.clone(),
+                    arguments: arguments.to_string(),
+                    call_id: call_id.clone(),
+                }
+            }
+            Self::FunctionCallOutput { call_id, output } => ResponseItem::FunctionCallOutput {
+                call_id: call_id.clone(),
+                output: output.to_payload(),
+            },
+            Self::CustomToolCall { id, status, call_id, name, input } => {
+                ResponseItem::CustomToolCall {
+                    id: id.clone(),
+                    status: status.clone(),
+                    call_id: call_id.clone(),
+                    name: name.clone(),
+                    input: input.to_string(),
+                }
+            }
+            Self::CustomToolCallOutput { call_id, name, output } => {
+                ResponseItem::CustomToolCallOutput {
+                    call_id: call_id.clone(),
+                    name: name.clone(),
+                    output: output.to_payload(),
+                }
+            }
+            Self::Plain(item) => item.clone(),
+        }
+    }
+}
+
+impl StoredContent {
+    fn intern(content: ContentItem, interner: &mut Interner) -> Self {
+        match content {
+            ContentItem::InputText { text } => Self::InputText(interner.intern(text)),
+            ContentItem::InputImage { image_url } => Self::InputImage(interner.intern(image_url)),
+            ContentItem::OutputText { text } => Self::OutputText(interner.intern(text)),
+        }
+    }
+
+    fn to_item(&self) -> ContentItem {
+        match self {
+            Self::InputText(text) => ContentItem::InputText { text: text.to_string() },
+            Self::InputImage(url) => ContentItem::InputImage { image_url: url.to_string() },
+            Self::OutputText(text) => ContentItem::OutputText { text: text.to_string() },
+        }
+    }
+}
+
+impl StoredOutput {
+    fn intern(payload: FunctionCallOutputPayload, interner: &mut Interner) -> Self {
+        let body = match payload.body {
+            FunctionCallOutputBody::Text(text) => StoredBody::Text(interner.intern(text)),
+            FunctionCallOutputBody::ContentItems(items) => StoredBody::ContentItems(
+                items
+                    .into_iter()
+                    .map(|item| match item {
+                        FunctionCallOutputContentItem::InputText { text } => {
+                            StoredToolContent::InputText(interner.intern(text))
+                        }
+                        FunctionCallOutputContentItem::InputImage { image_url, detail } => {
+                            StoredToolContent::InputImage {
+                                image_url: interner.intern(image_url),
+                                detail,
+                            }
+                        }
+                    })
+                    .collect(),
+            ),
+        };
+        Self { body, success: payload.success }
+    }
+
+    fn to_payload(&self) -> FunctionCallOutputPayload {
+        let body = match &self.body {
+            StoredBody::Text(text) => FunctionCallOutputBody::Text(text.to_string()),
+            StoredBody::ContentItems(items) => FunctionCallOutputBody::ContentItems(
+                items
+                    .iter()
+                    .map(|item| match item {
+                        StoredToolContent::InputText(text) => {
+                            FunctionCallOutputContentItem::InputText { text: text.to_string() }
+                        }
+                        StoredToolContent::InputImage { image_url, detail } => {
+                            FunctionCallOutputContentItem::InputImage {
+                                image_url: image_url.to_string(),
+                                detail: *detail,
+                            }
+                        }
+                    })
+                    .collect(),
+            ),
+        };
+        FunctionCallOutputPayload { body, success: self.success }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn output(call_id: &str, text: &str) -> ResponseItem {
+        ResponseItem::FunctionCallOutput {
+            call_id: call_id.to_string(),
+            output: FunctionCallOutputPayload::from_text(text.to_string()),
+        }
+    }
+
+    #[test]
+    fn repeated_outputs_share_one_allocation() {
+        let banner = "x".repeat(INTERN_MIN_BYTES * 4);
+        let mut interner = Interner::default();
+        let first = StoredItem::intern(output("a", &banner), &mut interner);
+        let second = StoredItem::intern(output("b", &banner), &mut interner);
+
+        let (
+            StoredItem::FunctionCallOutput { output: StoredOutput { body: StoredBody::Text(a), .. }, .. },
+            StoredItem::FunctionCallOutput { output: StoredOutput { body: StoredBody::Text(b), .. }, .. },
+        ) = (&first, &second)
+        else {
+            panic!("expected interned function outputs");
+        };
+        assert!(Arc::ptr_eq(a, b));
+        assert_eq!(interner.len(), 1);
+    }
+
+    #[test]
+    fn round_trip_preserves_items() {
+        let mut interner = Interner::default();
+        let items = vec![
+            ResponseItem::Message {
+                id: Some("m1".to_string()),
+                role: "assistant".to_string(),
+                content: vec![ContentItem::OutputText { text: "y".repeat(200) }],
+                end_turn: Some(true),
+                phase: None,
+            },
+            ResponseItem::CustomToolCall {
+                id: None,
+                status: Some("completed".to_string()),
+                call_id: "c1".to_string(),
+                name: "apply_patch".to_string(),
+                input: "*** Begin Patch\n*** End Patch".to_string(),
+            },
+            output("c1", "ok"),
+            ResponseItem::Other,
+        ];
+
+        let restored: Vec<ResponseItem> = items
+            .clone()
+            .into_iter()
+            .map(|item| StoredItem::intern(item, &mut interner).to_item())
+            .collect();
+        assert_eq!(restored, items);
+    }
+
+    #[test]
+    fn prune_drops_unreferenced_strings() {
+        let mut interner = Interner::default();
+        let item = StoredItem::intern(output("a", &"z".repeat(INTERN_MIN_BYTES)), &mut interner);
+        assert_eq!(interner.len(), 1);
+        drop(item);
+        interner.prune();
+        assert_eq!(interner.len(), 0);
+    }
+}
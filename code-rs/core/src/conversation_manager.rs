@@ -11,6 +11,9 @@ use crate::protocol::Event;
 use crate::protocol::EventMsg;
 use crate::protocol::SessionConfiguredEvent;
 use crate::rollout::RolloutRecorder;
+use crate::rollout::fork::ForkPoint;
+use crate::rollout::fork::fork_rollout_at;
+use crate::session_catalog::SessionCatalog;
 use code_protocol::ConversationId;
 use code_protocol::protocol::SessionSource;
 use code_protocol::models::ResponseItem;
@@ -160,6 +163,30 @@ impl ConversationManager {
         ids
     }
 
+    /// Fork the stored conversation `conversation_id` into a new one that
+    /// holds its history up to and including the event at `at_event_seq`
+    /// (all of it when `None`). The original conversation is left untouched,
+    /// so an alternate approach can be explored from that point.
+    pub async fn fork_conversation_at(
+        &self,
+        conversation_id: ConversationId,
+        at_event_seq: Option<ForkPoint>,
+        config: Config,
+    ) -> CodexResult<NewConversation> {
+        let catalog = SessionCatalog::new(config.code_home.clone());
+        let entry = catalog
+            .find_by_id(&conversation_id.to_string())
+            .await
+            .map_err(|err| CodexErr::Io(std::io::Error::other(format!("{err:#}"))))?
+            .ok_or_else(|| CodexErr::ConversationNotFound(conversation_id.into()))?;
+        let source_path = catalog.entry_rollout_path(&entry);
+        let forked_path = fork_rollout_at(&config, &source_path, at_event_seq.as_ref())
+            .await
+            .map_err(|err| CodexErr::Io(std::io::Error::other(format!("{err:#}"))))?;
+        self.resume_conversation_from_rollout(config, forked_path, self.auth_manager.clone())
+            .await
+    }
+
     /// Fork an existing conversation by dropping the last `drop_last_messages`
     /// user/assistant messages from its transcript and starting a new
     /// conversation with identical configuration (unless overridden by the
@@ -194,7 +194,7 @@ pub fn create_client(originator: &str) -> reqwest::Client {
     headers.insert("originator", originator_value);
     let ua = get_code_user_agent(Some(originator.value.as_str()));
 
-    let mut builder = crate::http_client::apply_extra_root_certificates(
+    let mut builder = crate::http_client::apply_outbound_network_settings(
         crate::http_client::with_chatgpt_cloudflare_cookie_store(reqwest::Client::builder()),
     )
         // Set UA via dedicated helper to avoid header validation pitfalls
@@ -0,0 +1,433 @@
+//! Local documentation bundles searched by the `docs_search` tool.
+//!
+//! A bundle is a directory of HTML, Markdown or plain-text documentation
+//! (e.g. a docs.rs or MDN dump) listed under `[docs_bundles]`. `code docs
+//! index` splits it into sections once, at install time, and stores them in
+//! `$CODE_HOME/docs_index/<name>.json`; sessions only load that file, so
+//! air-gapped machines get reference lookup without web search.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use bm25::Document;
+use bm25::Language;
+use bm25::SearchEngine;
+use bm25::SearchEngineBuilder;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::config_types::DocsBundleToml;
+
+const INDEX_DIR: &str = "docs_index";
+const INDEX_VERSION: u32 = 1;
+/// Sections longer than this are split at paragraph boundaries.
+const MAX_SECTION_CHARS: usize = 4_000;
+const SNIPPET_CHARS: usize = 1_200;
+/// Larger files are almost always generated search indexes, not prose.
+const MAX_FILE_BYTES: u64 = 4 * 1024 * 1024;
+const DOC_EXTENSIONS: &[&str] = &["html", "htm", "md", "markdown", "txt", "rst"];
+
+/// A configured, enabled bundle with its paths resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocsBundle {
+    pub name: String,
+    pub description: Option<String>,
+    pub root: PathBuf,
+    pub index_path: PathBuf,
+}
+
+/// Resolves `[docs_bundles]` entries; relative paths are taken from
+/// `code_home`. Disabled bundles are dropped.
+pub fn resolve_docs_bundles(
+    code_home: &Path,
+    bundles: &BTreeMap<String, DocsBundleToml>,
+) -> Vec<DocsBundle> {
+    bundles
+        .iter()
+        .filter(|(_, bundle)| bundle.enabled)
+        .map(|(name, bundle)| DocsBundle {
+            name: name.clone(),
+            description: bundle.description.clone(),
+            root: code_home.join(&bundle.path),
+            index_path: code_home.join(INDEX_DIR).join(format!("{name}.json")),
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DocsSection {
+    /// File the section came from, relative to the bundle root.
+    pub path: String,
+    pub title: String,
+    pub text: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct DocsIndexFile {
+    version: u32,
+    bundle: String,
+    root: PathBuf,
+    built_at: i64,
+    sections: Vec<DocsSection>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DocsIndexStats {
+    pub files: usize,
+    pub sections: usize,
+}
+
+/// Walks the bundle, splits every document into sections and writes the
+/// index file, replacing any previous one.
+pub fn build_docs_index(bundle: &DocsBundle) -> std::io::Result<DocsIndexStats> {
+    if !bundle.root.is_dir() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("{} is not a directory", bundle.root.display()),
+        ));
+    }
+    let mut files = Vec::new();
+    collect_doc_files(&bundle.root, &mut files)?;
+    files.sort();
+
+    let mut sections = Vec::new();
+    let mut indexed_files = 0;
+    for file in &files {
+        let Ok(bytes) = std::fs::read(file) else {
+            continue;
+        };
+        let contents = String::from_utf8_lossy(&bytes);
+        let relative = file
+            .strip_prefix(&bundle.root)
+            .unwrap_or(file)
+            .to_string_lossy()
+            .replace('\\', "/");
+        let is_html = file
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("html") || ext.eq_ignore_ascii_case("htm"));
+        let (page_title, markdown) = if is_html {
+            let Some(markdown) = html_to_markdown(&contents) else {
+                continue;
+            };
+            (html_title(&contents), markdown)
+        } else {
+            (None, contents.into_owned())
+        };
+        let page_title = page_title.unwrap_or_else(|| {
+            file.file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_else(|| relative.clone())
+        });
+        let before = sections.len();
+        sections.extend(
+            split_sections(&markdown, &page_title)
+                .into_iter()
+                .map(|(title, text)| DocsSection {
+                    path: relative.clone(),
+                    title,
+                    text,
+                }),
+        );
+        if sections.len() > before {
+            indexed_files += 1;
+        }
+    }
+
+    let stats = DocsIndexStats {
+        files: indexed_files,
+        sections: sections.len(),
+    };
+    let index = DocsIndexFile {
+        version: INDEX_VERSION,
+        bundle: bundle.name.clone(),
+        root: bundle.root.clone(),
+        built_at: chrono::Utc::now().timestamp(),
+        sections,
+    };
+    let json = serde_json::to_vec(&index).map_err(std::io::Error::other)?;
+    if let Some(parent) = bundle.index_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp_path = bundle.index_path.with_extension("json.tmp");
+    std::fs::write(&tmp_path, json)?;
+    std::fs::rename(&tmp_path, &bundle.index_path)?;
+    Ok(stats)
+}
+
+fn collect_doc_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            if !entry.file_name().to_string_lossy().starts_with('.') {
+                collect_doc_files(&path, files)?;
+            }
+        } else if file_type.is_file()
+            && entry.metadata().is_ok_and(|meta| meta.len() <= MAX_FILE_BYTES)
+            && path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| DOC_EXTENSIONS.iter().any(|known| ext.eq_ignore_ascii_case(known)))
+        {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn html_to_markdown(html: &str) -> Option<String> {
+    let options = htmd::options::Options {
+        heading_style: htmd::options::HeadingStyle::Atx,
+        code_block_style: htmd::options::CodeBlockStyle::Fenced,
+        ..Default::default()
+    };
+    htmd::HtmlToMarkdown::builder()
+        .options(options)
+        .skip_tags(vec!["script", "style", "nav", "header", "footer", "head"])
+        .build()
+        .convert(html)
+        .ok()
+}
+
+fn html_title(html: &str) -> Option<String> {
+    let lower = html.to_ascii_lowercase();
+    let start = lower.find("<title")?;
+    let open_end = start + lower[start..].find('>')? + 1;
+    let close = open_end + lower[open_end..].find("</title>")?;
+    let title = html[open_end..close].split_whitespace().collect::<Vec<_>>().join(" ");
+    (!title.is_empty()).then_some(title)
+}
+
+/// Splits Markdown at its headings into `(title, text)` sections. Headings
+/// inside fenced code blocks are ignored, and long sections are split
+/// further at blank lines.
+fn split_sections(markdown: &str, page_title: &str) -> Vec<(String, String)> {
+    let mut sections = Vec::new();
+    let mut heading: Option<String> = None;
+    let mut body = String::new();
+    let mut in_fence = false;
+
+    let mut flush = |heading: &Option<String>, body: &mut String| {
+        let text = body.trim();
+        if !text.is_empty() {
+            let title = match heading {
+                Some(heading) if heading != page_title => format!("{page_title} › {heading}"),
+                _ => page_title.to_owned(),
+            };
+            for chunk in split_long(text) {
+                sections.push((title.clone(), chunk));
+            }
+        }
+        body.clear();
+    };
+
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        }
+        let level = trimmed.chars().take_while(|ch| *ch == '#').count();
+        let is_heading = !in_fence
+            && (1..=6).contains(&level)
+            && trimmed[level..].starts_with(' ');
+        if is_heading {
+            flush(&heading, &mut body);
+            heading = Some(trimmed[level..].trim().trim_end_matches('#').trim().to_owned());
+        } else {
+            body.push_str(line);
+            body.push('\n');
+        }
+    }
+    flush(&heading, &mut body);
+    sections
+}
+
+fn split_long(text: &str) -> Vec<String> {
+    if text.len() <= MAX_SECTION_CHARS {
+        return vec![text.to_owned()];
+    }
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    for paragraph in text.split("\n\n") {
+        if !current.is_empty() && current.len() + paragraph.len() > MAX_SECTION_CHARS {
+            chunks.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push_str("\n\n");
+        }
+        current.push_str(paragraph);
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+struct LoadedIndex {
+    modified: SystemTime,
+    sections: Vec<DocsSection>,
+    engine: SearchEngine<usize>,
+}
+
+/// Indexes loaded by this process, reloaded when `code docs index` rewrites
+/// the file.
+static LOADED_INDEXES: LazyLock<Mutex<HashMap<PathBuf, Arc<LoadedIndex>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+fn load_index(bundle: &DocsBundle) -> Result<Arc<LoadedIndex>, String> {
+    let name = &bundle.name;
+    let modified = match std::fs::metadata(&bundle.index_path).and_then(|meta| meta.modified()) {
+        Ok(modified) => modified,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return Err(format!(
+                "docs bundle `{name}` has not been indexed; run `code docs index {name}`"
+            ));
+        }
+        Err(err) => return Err(format!("docs bundle `{name}`: {err}")),
+    };
+    if let Ok(loaded) = LOADED_INDEXES.lock()
+        && let Some(index) = loaded.get(&bundle.index_path)
+        && index.modified == modified
+    {
+        return Ok(Arc::clone(index));
+    }
+
+    let bytes = std::fs::read(&bundle.index_path).map_err(|err| format!("docs bundle `{name}`: {err}"))?;
+    let file: DocsIndexFile = serde_json::from_slice(&bytes)
+        .map_err(|err| format!("docs bundle `{name}` has a corrupt index ({err}); run `code docs index {name}`"))?;
+    if file.version != INDEX_VERSION {
+        return Err(format!(
+            "docs bundle `{name}` was indexed by a different version; run `code docs index {name}`"
+        ));
+    }
+    let documents: Vec<Document<usize>> = file
+        .sections
+        .iter()
+        .enumerate()
+        .map(|(idx, section)| Document::new(idx, format!("{}\n{}", section.title, section.text)))
+        .collect();
+    let engine = SearchEngineBuilder::<usize>::with_documents(Language::English, documents).build();
+    let index = Arc::new(LoadedIndex {
+        modified,
+        sections: file.sections,
+        engine,
+    });
+    if let Ok(mut loaded) = LOADED_INDEXES.lock() {
+        loaded.insert(bundle.index_path.clone(), Arc::clone(&index));
+    }
+    Ok(index)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocsHit {
+    pub bundle: String,
+    /// Absolute path of the source document.
+    pub path: PathBuf,
+    pub title: String,
+    pub snippet: String,
+    pub score: f32,
+}
+
+/// Searches `bundles` and returns the best `limit` hits across all of them,
+/// plus an error for each bundle that could not be searched.
+pub fn search_docs(bundles: &[DocsBundle], query: &str, limit: usize) -> (Vec<DocsHit>, Vec<String>) {
+    let mut hits = Vec::new();
+    let mut errors = Vec::new();
+    for bundle in bundles {
+        let index = match load_index(bundle) {
+            Ok(index) => index,
+            Err(err) => {
+                errors.push(err);
+                continue;
+            }
+        };
+        for result in index.engine.search(query, limit) {
+            let Some(section) = index.sections.get(result.document.id) else {
+                continue;
+            };
+            hits.push(DocsHit {
+                bundle: bundle.name.clone(),
+                path: bundle.root.join(&section.path),
+                title: section.title.clone(),
+                snippet: snippet(&section.text),
+                score: result.score,
+            });
+        }
+    }
+    hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+    hits.truncate(limit);
+    (hits, errors)
+}
+
+fn snippet(text: &str) -> String {
+    match text.char_indices().nth(SNIPPET_CHARS) {
+        Some((cut, _)) => format!("{}…", &text[..cut]),
+        None => text.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn splits_markdown_at_headings_outside_code_fences() {
+        let markdown = "intro\n# Vec\nA growable array.\n```\n# not a heading\n```\n## Capacity\nReserve space.\n";
+        let sections = split_sections(markdown, "std::vec");
+        assert_eq!(
+            sections,
+            vec![
+                ("std::vec".to_owned(), "intro".to_owned()),
+                (
+                    "std::vec › Vec".to_owned(),
+                    "A growable array.\n```\n# not a heading\n```".to_owned()
+                ),
+                ("std::vec › Capacity".to_owned(), "Reserve space.".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn indexes_and_searches_a_bundle() {
+        let code_home = tempfile::tempdir().expect("tempdir");
+        let root = code_home.path().join("docs/rust");
+        std::fs::create_dir_all(root.join("std")).expect("mkdir");
+        std::fs::write(
+            root.join("std/vec.html"),
+            "<html><head><title>Vec in std::vec</title></head><body><h2>Capacity</h2><p>Use reserve to grow the allocation.</p></body></html>",
+        )
+        .expect("write html");
+        std::fs::write(root.join("tokio.md"), "# Spawning\nUse tokio::spawn for tasks.\n").expect("write md");
+
+        let bundles = resolve_docs_bundles(
+            code_home.path(),
+            &BTreeMap::from([(
+                "rust".to_owned(),
+                DocsBundleToml {
+                    path: PathBuf::from("docs/rust"),
+                    description: None,
+                    enabled: true,
+                },
+            )]),
+        );
+        let (_, errors) = search_docs(&bundles, "reserve", 3);
+        assert_eq!(errors.len(), 1, "unindexed bundles report an error");
+
+        let stats = build_docs_index(&bundles[0]).expect("index bundle");
+        assert_eq!(stats, DocsIndexStats { files: 2, sections: 2 });
+
+        let (hits, errors) = search_docs(&bundles, "reserve allocation", 3);
+        assert!(errors.is_empty(), "{errors:?}");
+        assert_eq!(hits[0].title, "Vec in std::vec › Capacity");
+        assert_eq!(hits[0].path, root.join("std/vec.html"));
+    }
+}
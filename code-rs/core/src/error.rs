@@ -1,6 +1,9 @@
 use std::fmt::Write as _;
 
 use crate::exec::ExecToolCallOutput;
+use crate::protocol::CodexErrorInfo;
+use crate::protocol::ErrorCode;
+use crate::protocol::ErrorEvent;
 use chrono::{DateTime, Duration as ChronoDuration, Utc};
 use reqwest::StatusCode;
 use serde_json;
@@ -167,6 +170,20 @@ pub enum CodexErr {
     #[error("unsupported operation: {0}")]
     UnsupportedOperation(String),
 
+    /// The provider reported (with a structured error code) that the request
+    /// does not fit in the model's context window.
+    #[error("context window exceeded: {0}")]
+    ContextWindowExceeded(String),
+
+    /// The provider's content filter blocked the request or response.
+    #[error("blocked by the provider's content filter: {0}")]
+    ContentFiltered(String),
+
+    /// The provider rate-limited the request; retryable, optionally after the
+    /// delay it asked for.
+    #[error("rate limited: {0}")]
+    RateLimited(String, Option<RetryAfter>),
+
     // -----------------------------------------------------------------
     // Automatic conversions for common external error types
     // -----------------------------------------------------------------
@@ -392,6 +409,118 @@ impl CodexErr {
     pub fn downcast_ref<T: std::any::Any>(&self) -> Option<&T> {
         (self as &dyn std::any::Any).downcast_ref::<T>()
     }
+
+    /// Failure class reported to clients alongside the error message.
+    pub fn to_codex_error_info(&self) -> CodexErrorInfo {
+        if self.is_context_window_exceeded() {
+            return CodexErrorInfo::ContextWindowExceeded;
+        }
+        match self {
+            CodexErr::AuthRefreshPermanent(_) | CodexErr::EnvVar(_) => CodexErrorInfo::Unauthorized,
+            CodexErr::UnexpectedStatus(err) => match err.status {
+                StatusCode::UNAUTHORIZED => CodexErrorInfo::Unauthorized,
+                StatusCode::BAD_REQUEST => CodexErrorInfo::BadRequest,
+                _ => CodexErrorInfo::Other,
+            },
+            CodexErr::UsageLimitReached(_) | CodexErr::QuotaExceeded | CodexErr::UsageNotIncluded => {
+                CodexErrorInfo::UsageLimitExceeded
+            }
+            CodexErr::Sandbox(_) | CodexErr::LandlockSandboxExecutableNotProvided => {
+                CodexErrorInfo::SandboxError
+            }
+            CodexErr::ServerError(_) | CodexErr::InternalAgentDied => CodexErrorInfo::InternalServerError,
+            CodexErr::Stream(..) => CodexErrorInfo::ResponseStreamDisconnected { http_status_code: None },
+            CodexErr::RetryLimit(err) => CodexErrorInfo::ResponseTooManyFailedAttempts {
+                http_status_code: Some(err.status.as_u16()),
+            },
+            _ => CodexErrorInfo::Other,
+        }
+    }
+
+    /// Stable code for this failure; see `ErrorCode`.
+    pub fn error_code(&self) -> ErrorCode {
+        if self.is_context_window_exceeded() {
+            return ErrorCode::ContextWindowExceeded;
+        }
+        match self {
+            CodexErr::AuthRefreshPermanent(_) => ErrorCode::AuthExpired,
+            CodexErr::EnvVar(_) => ErrorCode::MissingCredentials,
+            CodexErr::UnexpectedStatus(err) => match err.status {
+                StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => ErrorCode::Unauthorized,
+                StatusCode::BAD_REQUEST => ErrorCode::BadRequest,
+                status if status.is_server_error() => ErrorCode::ServerError,
+                _ => ErrorCode::ProviderError,
+            },
+            CodexErr::UsageLimitReached(_) => ErrorCode::UsageLimitReached,
+            CodexErr::QuotaExceeded => ErrorCode::QuotaExceeded,
+            CodexErr::UsageNotIncluded => ErrorCode::UsageNotIncluded,
+            CodexErr::ModelCap(_) => ErrorCode::ModelCap,
+            CodexErr::ContextWindowExceeded(_) => ErrorCode::ContextWindowExceeded,
+            CodexErr::ContentFiltered(_) => ErrorCode::ContentFiltered,
+            CodexErr::RateLimited(..) => ErrorCode::RateLimited,
+            CodexErr::ServerError(_) => ErrorCode::ServerError,
+            CodexErr::ServerOverloaded => ErrorCode::ServerOverloaded,
+            CodexErr::Stream(..) => ErrorCode::StreamDisconnected,
+            CodexErr::RetryLimit(_) => ErrorCode::RetryLimitReached,
+            CodexErr::Reqwest(_) => ErrorCode::NetworkError,
+            CodexErr::Sandbox(err) => match err {
+                SandboxErr::Denied { .. } => ErrorCode::SandboxDenied,
+                SandboxErr::Timeout { .. } => ErrorCode::SandboxTimeout,
+                SandboxErr::Signal(_) => ErrorCode::SandboxSignal,
+                SandboxErr::OutOfMemory { .. } => ErrorCode::SandboxOutOfMemory,
+                _ => ErrorCode::SandboxSetupFailed,
+            },
+            #[cfg(target_os = "linux")]
+            CodexErr::LandlockRuleset(_) | CodexErr::LandlockPathFd(_) => {
+                ErrorCode::SandboxSetupFailed
+            }
+            CodexErr::LandlockSandboxExecutableNotProvided => ErrorCode::SandboxExecutableMissing,
+            CodexErr::Timeout => ErrorCode::CommandTimeout,
+            CodexErr::Spawn => ErrorCode::SpawnFailed,
+            CodexErr::UnsupportedOperation(_) => ErrorCode::UnsupportedOperation,
+            CodexErr::Interrupted => ErrorCode::Interrupted,
+            CodexErr::ConversationNotFound(_) => ErrorCode::ConversationNotFound,
+            CodexErr::SessionConfiguredNotFirstEvent
+            | CodexErr::InternalAgentDied
+            | CodexErr::Io(_)
+            | CodexErr::Json(_)
+            | CodexErr::TokioJoin(_) => ErrorCode::Internal,
+        }
+    }
+
+    /// `ErrorEvent` carrying `message` and this error's classification.
+    pub fn to_error_event(&self, message: String) -> ErrorEvent {
+        let code = self.error_code();
+        ErrorEvent {
+            message,
+            codex_error_info: Some(self.to_codex_error_info()),
+            code: Some(code),
+            category: Some(code.category()),
+        }
+    }
+
+    /// Structured provider codes are mapped to `ContextWindowExceeded` where
+    /// the error is built (see `provider_errors`); the message heuristics only
+    /// cover providers that report overflow in prose.
+    pub fn is_context_window_exceeded(&self) -> bool {
+        match self {
+            CodexErr::ContextWindowExceeded(_) => true,
+            CodexErr::UnexpectedStatus(resp) => looks_like_context_overflow(&resp.body),
+            CodexErr::Stream(msg, _, _) => looks_like_context_overflow(msg),
+            _ => false,
+        }
+    }
+}
+
+fn looks_like_context_overflow(message: &str) -> bool {
+    let lower = message.to_ascii_lowercase();
+    lower.contains("context_length_exceeded")
+        || lower.contains("context length exceeded")
+        || lower.contains("maximum context length")
+        || lower.contains("exceed the context window")
+        || lower.contains("exceeds the context window")
+        || (lower.contains("context window")
+            && (lower.contains("exceed") || lower.contains("full") || lower.contains("too long")))
 }
 
 pub fn get_error_message_ui(e: &CodexErr) -> String {
@@ -419,6 +548,52 @@ pub fn get_error_message_ui(e: &CodexErr) -> String {
 mod tests {
     use super::*;
     
+    #[test]
+    fn auth_failures_are_classified_as_unauthorized() {
+        let unauthorized = CodexErr::UnexpectedStatus(UnexpectedResponseError {
+            status: StatusCode::UNAUTHORIZED,
+            body: String::new(),
+            request_id: None,
+        });
+        assert_eq!(unauthorized.to_codex_error_info(), CodexErrorInfo::Unauthorized);
+        assert_eq!(
+            CodexErr::AuthRefreshPermanent("log in again".to_owned()).to_codex_error_info(),
+            CodexErrorInfo::Unauthorized
+        );
+        assert_eq!(CodexErr::Interrupted.to_codex_error_info(), CodexErrorInfo::Other);
+    }
+
+    #[test]
+    fn error_codes_carry_categories() {
+        use crate::protocol::ErrorCategory;
+
+        let overflow = CodexErr::Stream(
+            "Your input exceeds the context window of this model.".to_owned(),
+            None,
+            None,
+        );
+        assert_eq!(overflow.error_code(), ErrorCode::ContextWindowExceeded);
+        assert_eq!(overflow.to_codex_error_info(), CodexErrorInfo::ContextWindowExceeded);
+        assert_eq!(
+            CodexErr::Stream("connection reset".to_owned(), None, None).error_code(),
+            ErrorCode::StreamDisconnected
+        );
+
+        let event = CodexErr::AuthRefreshPermanent("log in again".to_owned())
+            .to_error_event("Authentication expired.".to_owned());
+        assert_eq!(event.code, Some(ErrorCode::AuthExpired));
+        assert_eq!(event.category, Some(ErrorCategory::Auth));
+        assert_eq!(
+            serde_json::to_value(&event).unwrap()["category"],
+            serde_json::json!("auth")
+        );
+        assert_eq!(CodexErr::Timeout.error_code().category(), ErrorCategory::Tool);
+        assert_eq!(
+            CodexErr::LandlockSandboxExecutableNotProvided.error_code().category(),
+            ErrorCategory::Config
+        );
+    }
+
     #[test]
     fn usage_limit_reached_error_formats_plus_plan() {
         let err = UsageLimitReachedError {
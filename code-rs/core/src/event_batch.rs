@@ -0,0 +1,186 @@
+//! Batched delivery of session events to front ends.
+//!
+//! During fast token streams the model produces many tiny deltas, and handing
+//! each one to the UI separately costs a channel hop, an ordering lookup and a
+//! stream commit per token. Front ends instead wait for one event, drain
+//! whatever else is already queued, and merge runs of consecutive deltas that
+//! belong to the same stream. Events are never reordered: a delta is only
+//! merged into the event directly before it.
+
+use crate::protocol::Event;
+use crate::protocol::EventMsg;
+use crate::protocol::OrderMeta;
+
+/// Upper bound on events drained into one batch, so a flooded channel still
+/// yields to the front end regularly.
+pub(crate) const MAX_EVENT_BATCH: usize = 256;
+
+/// Merges consecutive text deltas for the same stream, preserving order.
+///
+/// The merged event carries the `event_seq` and `order` of the last delta it
+/// absorbed, matching the state a consumer would reach after applying the
+/// deltas one by one.
+pub(crate) fn coalesce_stream_deltas(events: Vec<Event>) -> Vec<Event> {
+    let mut batch: Vec<Event> = Vec::with_capacity(events.len());
+    for event in events {
+        if let Some(prev) = batch.last_mut()
+            && merge_stream_delta(prev, &event)
+        {
+            continue;
+        }
+        batch.push(event);
+    }
+    batch
+}
+
+/// Appends `next` to `target` when both are text deltas of the same kind for
+/// the same stream, returning whether it did. On success `target` takes over
+/// `next`'s `event_seq` and `order`.
+pub fn merge_stream_delta(target: &mut Event, next: &Event) -> bool {
+    if target.id != next.id
+        || !same_output_item(target.order.as_ref(), next.order.as_ref())
+        || !append_delta(&mut target.msg, &next.msg)
+    {
+        return false;
+    }
+    target.event_seq = next.event_seq;
+    target.order = next.order.clone();
+    true
+}
+
+/// Text carried by a streaming delta event, or `None` for any other event.
+pub fn stream_delta_text(msg: &EventMsg) -> Option<&str> {
+    match msg {
+        EventMsg::AgentMessageDelta(ev) => Some(&ev.delta),
+        EventMsg::AgentReasoningDelta(ev) => Some(&ev.delta),
+        EventMsg::AgentReasoningRawContentDelta(ev) => Some(&ev.delta),
+        _ => None,
+    }
+}
+
+fn same_output_item(a: Option<&OrderMeta>, b: Option<&OrderMeta>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => {
+            a.request_ordinal == b.request_ordinal && a.output_index == b.output_index
+        }
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+/// Appends `next`'s text to `target` when both are deltas of the same kind.
+fn append_delta(target: &mut EventMsg, next: &EventMsg) -> bool {
+    match (target, next) {
+        (EventMsg::AgentMessageDelta(a), EventMsg::AgentMessageDelta(b)) => {
+            a.delta.push_str(&b.delta);
+        }
+        (EventMsg::AgentReasoningDelta(a), EventMsg::AgentReasoningDelta(b)) => {
+            a.delta.push_str(&b.delta);
+        }
+        (
+            EventMsg::AgentReasoningRawContentDelta(a),
+            EventMsg::AgentReasoningRawContentDelta(b),
+        ) => {
+            a.delta.push_str(&b.delta);
+        }
+        _ => return false,
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::AgentMessageDeltaEvent;
+    use crate::protocol::AgentReasoningDeltaEvent;
+    use pretty_assertions::assert_eq;
+
+    fn order(output_index: u32, sequence_number: u64) -> Option<OrderMeta> {
+        Some(OrderMeta {
+            request_ordinal: 1,
+            output_index: Some(output_index),
+            sequence_number: Some(sequence_number),
+        })
+    }
+
+    fn answer(id: &str, seq: u64, output_index: u32, delta: &str) -> Event {
+        Event {
+            id: id.to_string(),
+            event_seq: seq,
+            msg: EventMsg::AgentMessageDelta(AgentMessageDeltaEvent {
+                delta: delta.to_string(),
+            }),
+            order: order(output_index, seq),
+        }
+    }
+
+    fn reasoning(id: &str, seq: u64, delta: &str) -> Event {
+        Event {
+            id: id.to_string(),
+            event_seq: seq,
+            msg: EventMsg::AgentReasoningDelta(AgentReasoningDeltaEvent {
+                delta: delta.to_string(),
+            }),
+            order: order(0, seq),
+        }
+    }
+
+    fn summary(events: &[Event]) -> Vec<(String, u64, String)> {
+        events
+            .iter()
+            .map(|event| {
+                let text = match &event.msg {
+                    EventMsg::AgentMessageDelta(ev) => format!("answer:{}", ev.delta),
+                    EventMsg::AgentReasoningDelta(ev) => format!("reasoning:{}", ev.delta),
+                    other => format!("{other:?}"),
+                };
+                (event.id.clone(), event.event_seq, text)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn consecutive_deltas_merge_and_keep_last_sequence() {
+        let batch = coalesce_stream_deltas(vec![
+            answer("m1", 1, 0, "Hel"),
+            answer("m1", 2, 0, "lo, "),
+            answer("m1", 3, 0, "world"),
+        ]);
+
+        assert_eq!(
+            summary(&batch),
+            vec![("m1".to_string(), 3, "answer:Hello, world".to_string())]
+        );
+        assert_eq!(batch[0].order.as_ref().and_then(|o| o.sequence_number), Some(3));
+    }
+
+    #[test]
+    fn interleaved_events_stay_in_order() {
+        let batch = coalesce_stream_deltas(vec![
+            reasoning("r1", 1, "think"),
+            reasoning("r1", 2, "ing"),
+            answer("m1", 3, 1, "a"),
+            Event {
+                id: "m1".to_string(),
+                event_seq: 4,
+                msg: EventMsg::TaskStarted,
+                order: None,
+            },
+            answer("m1", 5, 1, "b"),
+            answer("m2", 6, 1, "c"),
+            answer("m2", 7, 2, "d"),
+        ]);
+
+        assert_eq!(
+            summary(&batch),
+            vec![
+                ("r1".to_string(), 2, "reasoning:thinking".to_string()),
+                ("m1".to_string(), 3, "answer:a".to_string()),
+                ("m1".to_string(), 4, "TaskStarted".to_string()),
+                ("m1".to_string(), 5, "answer:b".to_string()),
+                ("m2".to_string(), 6, "answer:c".to_string()),
+                ("m2".to_string(), 7, "answer:d".to_string()),
+            ]
+        );
+    }
+}
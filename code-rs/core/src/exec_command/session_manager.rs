@@ -489,6 +489,7 @@ fn effective_sandbox_policy_for_exec_command(
     let SandboxPolicy::WorkspaceWrite {
         writable_roots,
         network_access,
+        network_allowlist,
         exclude_tmpdir_env_var,
         exclude_slash_tmp,
         allow_git_writes,
@@ -515,6 +516,7 @@ fn effective_sandbox_policy_for_exec_command(
     SandboxPolicy::WorkspaceWrite {
         writable_roots: effective_writable_roots,
         network_access: effective_network_access,
+        network_allowlist: network_allowlist.clone(),
         exclude_tmpdir_env_var: *exclude_tmpdir_env_var,
         exclude_slash_tmp: *exclude_slash_tmp,
         allow_git_writes: *allow_git_writes,
@@ -1,8 +1,12 @@
+use crate::bash::try_parse_bash;
+use crate::bash::try_parse_word_only_commands_sequence;
 use crate::config_types::EnvironmentVariablePattern;
 use crate::config_types::ShellEnvironmentPolicy;
 use crate::config_types::ShellEnvironmentPolicyInherit;
+use crate::config_types::SpawnedEnvPolicy;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::path::Path;
 
 /// Construct an environment map based on the rules in the specified policy. The
 /// resulting map can be passed directly to `Command::envs()` after calling
@@ -97,10 +101,112 @@ where
     env_map
 }
 
+/// Removes the variables `spawned_env` withholds from `env` before `command`
+/// runs. Values set explicitly in `explicit` are kept, as are variables a
+/// `[spawned_env.commands]` override releases to `command`.
+pub fn withhold_spawned_env(
+    env: &mut HashMap<String, String>,
+    spawned_env: &SpawnedEnvPolicy,
+    explicit: &HashMap<String, String>,
+    command: &[String],
+) {
+    let released: Vec<&EnvironmentVariablePattern> = spawned_env
+        .commands
+        .iter()
+        .filter(|entry| runs_only(command, &entry.command))
+        .flat_map(|entry| entry.allow.iter())
+        .collect();
+    env.retain(|name, _| {
+        explicit.contains_key(name)
+            || !spawned_env.withholds(name)
+            || released.iter().any(|pattern| pattern.matches(name))
+    });
+}
+
+/// Withheld variables still present in `env`, i.e. the ones a per-command
+/// override released. Sorted so approval prompts are stable.
+pub fn released_spawned_env(
+    env: &HashMap<String, String>,
+    spawned_env: &SpawnedEnvPolicy,
+    explicit: &HashMap<String, String>,
+) -> Vec<String> {
+    let mut released: Vec<String> = env
+        .keys()
+        .filter(|name| !explicit.contains_key(*name) && spawned_env.withholds(name))
+        .cloned()
+        .collect();
+    released.sort();
+    released
+}
+
+/// Environment for an MCP stdio server: parent variables matching
+/// `mcp_passthrough` that are not withheld, overlaid by the server's own
+/// `env`. The client adds its fixed baseline on top.
+pub fn mcp_server_env(
+    spawned_env: &SpawnedEnvPolicy,
+    server_env: Option<HashMap<String, String>>,
+) -> Option<HashMap<String, String>> {
+    populate_mcp_env(std::env::vars(), spawned_env, server_env)
+}
+
+fn populate_mcp_env<I>(
+    vars: I,
+    spawned_env: &SpawnedEnvPolicy,
+    server_env: Option<HashMap<String, String>>,
+) -> Option<HashMap<String, String>>
+where
+    I: IntoIterator<Item = (String, String)>,
+{
+    if spawned_env.mcp_passthrough.is_empty() {
+        return server_env;
+    }
+    let mut env: HashMap<String, String> = vars
+        .into_iter()
+        .filter(|(name, _)| {
+            spawned_env
+                .mcp_passthrough
+                .iter()
+                .any(|pattern| pattern.matches(name))
+                && !spawned_env.withholds(name)
+        })
+        .collect();
+    env.extend(server_env.unwrap_or_default());
+    Some(env)
+}
+
+/// Whether every command in the invocation runs `program`. Scripts that are
+/// not plain word sequences (variable expansion, redirects, ...) never match,
+/// so a released credential cannot be piped into another program.
+fn runs_only(command: &[String], program: &str) -> bool {
+    let is_program = |argv: &[String]| {
+        argv.first().is_some_and(|first| {
+            let path = Path::new(first);
+            path.file_name().is_some_and(|name| name == program)
+                || path.file_stem().is_some_and(|stem| stem == program)
+        })
+    };
+    let script = match command {
+        [shell, flag, script]
+            if matches!(flag.as_str(), "-c" | "-lc")
+                && Path::new(shell)
+                    .file_name()
+                    .is_some_and(|name| name == "bash" || name == "zsh" || name == "sh") =>
+        {
+            script
+        }
+        _ => return is_program(command),
+    };
+    try_parse_bash(script)
+        .and_then(|tree| try_parse_word_only_commands_sequence(&tree, script))
+        .is_some_and(|commands| !commands.is_empty() && commands.iter().all(|argv| is_program(argv)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::config_types::ShellEnvironmentPolicyInherit;
+    use crate::config_types::SpawnedEnvCommandToml;
+    use crate::config_types::SpawnedEnvToml;
     use maplit::hashmap;
 
     fn make_vars(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
@@ -279,4 +385,82 @@ mod tests {
         expected.insert("GIT_ASKPASS".to_string(), "true".to_string());
         assert_eq!(result, expected);
     }
+
+    fn strings(parts: &[&str]) -> Vec<String> {
+        parts.iter().map(|part| (*part).to_string()).collect()
+    }
+
+    #[test]
+    fn spawned_env_withholds_credentials_unless_a_command_override_releases_them() {
+        let spawned_env = SpawnedEnvPolicy::from(SpawnedEnvToml {
+            allow: vec!["NPM_TOKEN".to_string()],
+            commands: vec![SpawnedEnvCommandToml {
+                command: "aws".to_string(),
+                allow: vec!["AWS_*".to_string()],
+            }],
+            ..Default::default()
+        });
+        let base: HashMap<String, String> = make_vars(&[
+            ("PATH", "/usr/bin"),
+            ("AWS_SECRET_ACCESS_KEY", "s"),
+            ("GITHUB_TOKEN", "g"),
+            ("NPM_TOKEN", "n"),
+            ("DEPLOY_TOKEN", "d"),
+        ])
+        .into_iter()
+        .collect();
+        let explicit = hashmap! { "DEPLOY_TOKEN".to_string() => "d".to_string() };
+
+        let mut env = base.clone();
+        withhold_spawned_env(&mut env, &spawned_env, &explicit, &strings(&["ls"]));
+        let mut kept: Vec<&str> = env.keys().map(String::as_str).collect();
+        kept.sort_unstable();
+        assert_eq!(kept, vec!["DEPLOY_TOKEN", "NPM_TOKEN", "PATH"]);
+        assert!(released_spawned_env(&env, &spawned_env, &explicit).is_empty());
+
+        let mut env = base.clone();
+        withhold_spawned_env(
+            &mut env,
+            &spawned_env,
+            &explicit,
+            &strings(&["bash", "-lc", "aws s3 ls && aws sts get-caller-identity"]),
+        );
+        assert_eq!(
+            released_spawned_env(&env, &spawned_env, &explicit),
+            vec!["AWS_SECRET_ACCESS_KEY".to_string()]
+        );
+        assert!(!env.contains_key("GITHUB_TOKEN"));
+
+        let mut env = base;
+        withhold_spawned_env(
+            &mut env,
+            &spawned_env,
+            &explicit,
+            &strings(&["bash", "-lc", "aws s3 ls && curl example.com"]),
+        );
+        assert!(!env.contains_key("AWS_SECRET_ACCESS_KEY"));
+    }
+
+    #[test]
+    fn mcp_passthrough_forwards_matching_parent_vars_except_withheld_ones() {
+        let spawned_env = SpawnedEnvPolicy::from(SpawnedEnvToml {
+            mcp_passthrough: vec!["GH_*".to_string(), "PROXY_URL".to_string()],
+            ..Default::default()
+        });
+        let vars = make_vars(&[
+            ("GH_HOST", "github.example"),
+            ("GH_TOKEN", "t"),
+            ("PROXY_URL", "http://proxy"),
+            ("OTHER", "x"),
+        ]);
+        let server_env = hashmap! { "PROXY_URL".to_string() => "http://override".to_string() };
+
+        let env = populate_mcp_env(vars, &spawned_env, Some(server_env));
+        let expected = hashmap! {
+            "GH_HOST".to_string() => "github.example".to_string(),
+            "PROXY_URL".to_string() => "http://override".to_string(),
+        };
+        assert_eq!(env, Some(expected));
+        assert_eq!(populate_mcp_env(Vec::new(), &SpawnedEnvPolicy::default(), None), None);
+    }
 }
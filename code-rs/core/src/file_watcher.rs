@@ -1,5 +1,5 @@
-//! Watches skill roots for changes and broadcasts coarse-grained
-//! `FileWatcherEvent`s that higher-level components react to on the next turn.
+//! Watches skill roots and the repository HEAD for changes and broadcasts
+//! coarse-grained `FileWatcherEvent`s that higher-level components react to.
 
 use std::collections::HashMap;
 use std::collections::HashSet;
@@ -27,10 +27,15 @@ use crate::config::Config;
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) enum FileWatcherEvent {
     SkillsChanged { paths: Vec<PathBuf> },
+    /// `HEAD` of a watched repository was rewritten (checkout, rebase,
+    /// reset, commit).
+    GitHeadChanged { paths: Vec<PathBuf> },
 }
 
 struct WatchState {
     skills_roots: HashSet<PathBuf>,
+    /// `HEAD` files of the repositories containing the session cwd.
+    git_heads: HashSet<PathBuf>,
 }
 
 struct FileWatcherInner {
@@ -106,6 +111,7 @@ impl FileWatcher {
         let (tx, _) = broadcast::channel(128);
         let state = Arc::new(RwLock::new(WatchState {
             skills_roots: HashSet::new(),
+            git_heads: HashSet::new(),
         }));
         let file_watcher = Self {
             inner: Some(Mutex::new(inner)),
@@ -122,6 +128,7 @@ impl FileWatcher {
             inner: None,
             state: Arc::new(RwLock::new(WatchState {
                 skills_roots: HashSet::new(),
+                git_heads: HashSet::new(),
             })),
             tx,
         }
@@ -135,6 +142,11 @@ impl FileWatcher {
         for root in crate::skills::loader::skill_root_paths_for_watcher(config) {
             self.register_skills_root(root);
         }
+        if let Some(git_dir) = crate::git_info::resolve_git_dir(&config.cwd) {
+            // Event paths are reported canonicalized on some platforms.
+            let git_dir = std::fs::canonicalize(&git_dir).unwrap_or(git_dir);
+            self.register_git_head(git_dir);
+        }
     }
 
     // Bridge `notify`'s callback-based events into the Tokio runtime and broadcast
@@ -149,10 +161,14 @@ impl FileWatcher {
             handle.spawn(async move {
                 let now = Instant::now();
                 let mut skills = ThrottledPaths::new(now);
+                let mut git_heads = ThrottledPaths::new(now);
 
                 loop {
                     let now = Instant::now();
-                    let next_deadline = skills.next_deadline(now);
+                    let next_deadline = match (skills.next_deadline(now), git_heads.next_deadline(now)) {
+                        (Some(a), Some(b)) => Some(a.min(b)),
+                        (a, b) => a.or(b),
+                    };
                     let timer_deadline =
                         next_deadline.unwrap_or_else(|| now + Duration::from_secs(60 * 60 * 24 * 365));
                     let timer = sleep_until(timer_deadline);
@@ -163,12 +179,17 @@ impl FileWatcher {
                             match res {
                                 Some(Ok(event)) => {
                                     let skills_paths = classify_event(&event, &state);
+                                    let head_paths = classify_git_head_event(&event, &state);
                                     let now = Instant::now();
                                     skills.add(skills_paths);
+                                    git_heads.add(head_paths);
 
                                     if let Some(paths) = skills.take_ready(now) {
                                         let _ = tx.send(FileWatcherEvent::SkillsChanged { paths });
                                     }
+                                    if let Some(paths) = git_heads.take_ready(now) {
+                                        let _ = tx.send(FileWatcherEvent::GitHeadChanged { paths });
+                                    }
                                 }
                                 Some(Err(err)) => {
                                     warn!("file watcher error: {err}");
@@ -179,6 +200,9 @@ impl FileWatcher {
                                     if let Some(paths) = skills.take_pending(now) {
                                         let _ = tx.send(FileWatcherEvent::SkillsChanged { paths });
                                     }
+                                    if let Some(paths) = git_heads.take_pending(now) {
+                                        let _ = tx.send(FileWatcherEvent::GitHeadChanged { paths });
+                                    }
                                     break;
                                 }
                             }
@@ -188,6 +212,9 @@ impl FileWatcher {
                             if let Some(paths) = skills.take_ready(now) {
                                 let _ = tx.send(FileWatcherEvent::SkillsChanged { paths });
                             }
+                            if let Some(paths) = git_heads.take_ready(now) {
+                                let _ = tx.send(FileWatcherEvent::GitHeadChanged { paths });
+                            }
                         }
                     }
                 }
@@ -208,6 +235,19 @@ impl FileWatcher {
         self.watch_path(root, RecursiveMode::Recursive);
     }
 
+    // Git replaces `HEAD` by renaming `HEAD.lock` over it, so watch the git
+    // directory itself rather than the file.
+    fn register_git_head(&self, git_dir: PathBuf) {
+        {
+            let mut state = match self.state.write() {
+                Ok(state) => state,
+                Err(err) => err.into_inner(),
+            };
+            state.git_heads.insert(git_dir.join("HEAD"));
+        }
+        self.watch_path(git_dir, RecursiveMode::NonRecursive);
+    }
+
     fn watch_path(&self, path: PathBuf, mode: RecursiveMode) {
         let Some(inner) = &self.inner else {
             return;
@@ -238,11 +278,15 @@ impl FileWatcher {
     }
 }
 
-fn classify_event(event: &Event, state: &RwLock<WatchState>) -> Vec<PathBuf> {
-    if !matches!(
+fn is_mutating_event(event: &Event) -> bool {
+    matches!(
         event.kind,
         EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
-    ) {
+    )
+}
+
+fn classify_event(event: &Event, state: &RwLock<WatchState>) -> Vec<PathBuf> {
+    if !is_mutating_event(event) {
         return Vec::new();
     }
 
@@ -264,6 +308,23 @@ fn classify_event(event: &Event, state: &RwLock<WatchState>) -> Vec<PathBuf> {
     skills_paths
 }
 
+fn classify_git_head_event(event: &Event, state: &RwLock<WatchState>) -> Vec<PathBuf> {
+    if !is_mutating_event(event) {
+        return Vec::new();
+    }
+
+    let state = match state.read() {
+        Ok(state) => state,
+        Err(err) => err.into_inner(),
+    };
+    event
+        .paths
+        .iter()
+        .filter(|path| state.git_heads.contains(*path))
+        .cloned()
+        .collect()
+}
+
 fn is_skills_path(path: &Path, roots: &HashSet<PathBuf>) -> bool {
     roots.iter().any(|root| path.starts_with(root))
 }
@@ -332,6 +393,7 @@ mod tests {
         let root = path("/tmp/skills");
         let state = RwLock::new(WatchState {
             skills_roots: HashSet::from([root.clone()]),
+            git_heads: HashSet::new(),
         });
         let event = notify_event(
             EventKind::Create(CreateKind::Any),
@@ -351,6 +413,7 @@ mod tests {
         let root_b = path("/tmp/workspace/.codex/skills");
         let state = RwLock::new(WatchState {
             skills_roots: HashSet::from([root_a.clone(), root_b.clone()]),
+            git_heads: HashSet::new(),
         });
         let event = notify_event(
             EventKind::Modify(ModifyKind::Any),
@@ -373,6 +436,7 @@ mod tests {
         let root = path("/tmp/skills");
         let state = RwLock::new(WatchState {
             skills_roots: HashSet::from([root.clone()]),
+            git_heads: HashSet::new(),
         });
         let path = root.join("demo/SKILL.md");
 
@@ -389,6 +453,35 @@ mod tests {
         assert_eq!(classify_event(&other_event, &state), Vec::<PathBuf>::new());
     }
 
+    #[test]
+    fn classify_git_head_event_matches_only_registered_head_files() {
+        let head = path("/tmp/repo/.git/HEAD");
+        let state = RwLock::new(WatchState {
+            skills_roots: HashSet::new(),
+            git_heads: HashSet::from([head.clone()]),
+        });
+        let event = notify_event(
+            EventKind::Modify(ModifyKind::Any),
+            vec![
+                path("/tmp/repo/.git/HEAD.lock"),
+                head.clone(),
+                path("/tmp/repo/.git/ORIG_HEAD"),
+            ],
+        );
+
+        assert_eq!(classify_git_head_event(&event, &state), vec![head.clone()]);
+        assert_eq!(classify_event(&event, &state), Vec::<PathBuf>::new());
+
+        let access_event = notify_event(
+            EventKind::Access(AccessKind::Open(AccessMode::Any)),
+            vec![head],
+        );
+        assert_eq!(
+            classify_git_head_event(&access_event, &state),
+            Vec::<PathBuf>::new()
+        );
+    }
+
     #[test]
     fn register_skills_root_dedupes_state_entries() {
         let watcher = FileWatcher::noop();
@@ -437,6 +530,7 @@ mod tests {
                 FileWatcherEvent::SkillsChanged { paths } => {
                     all_paths.extend(paths);
                 }
+                FileWatcherEvent::GitHeadChanged { .. } => {}
             }
         }
         all_paths.sort_unstable();
@@ -448,6 +542,7 @@ mod tests {
         let root = path("/tmp/skills");
         let state = RwLock::new(WatchState {
             skills_roots: HashSet::from([root.clone()]),
+            git_heads: HashSet::new(),
         });
         let event = notify_event(
             EventKind::Remove(RemoveKind::Any),
@@ -41,6 +41,58 @@ pub fn get_git_repo_root(base_dir: &Path) -> Option<PathBuf> {
     None
 }
 
+/// Resolve the git directory for `base_dir`, following the `gitdir:` entry of
+/// a `.git` file (linked worktrees and submodules). Like
+/// [`get_git_repo_root`], this does not run git.
+pub fn resolve_git_dir(base_dir: &Path) -> Option<PathBuf> {
+    let root = get_git_repo_root(base_dir)?;
+    let dot_git = root.join(".git");
+    if dot_git.is_dir() {
+        return Some(dot_git);
+    }
+    let contents = std::fs::read_to_string(&dot_git).ok()?;
+    let git_dir = contents
+        .lines()
+        .find_map(|line| line.strip_prefix("gitdir:"))?
+        .trim();
+    let git_dir = PathBuf::from(git_dir);
+    Some(if git_dir.is_absolute() {
+        git_dir
+    } else {
+        root.join(git_dir)
+    })
+}
+
+/// Returns `true` while a rebase started in `git_dir` is still being applied.
+pub fn rebase_in_progress(git_dir: &Path) -> bool {
+    git_dir.join("rebase-merge").exists() || git_dir.join("rebase-apply").exists()
+}
+
+/// Branch and commit checked out in a working tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitHeadState {
+    /// Checked out branch, or `None` when HEAD is detached.
+    pub branch: Option<String>,
+    /// Commit HEAD points at, or `None` on an unborn branch.
+    pub commit: Option<String>,
+}
+
+impl GitHeadState {
+    /// Short description such as ``branch `main` at 1a2b3c4``.
+    pub fn describe(&self) -> String {
+        let commit = self
+            .commit
+            .as_deref()
+            .map(|commit| commit.get(..7).unwrap_or(commit));
+        match (self.branch.as_deref(), commit) {
+            (Some(branch), Some(commit)) => format!("branch `{branch}` at {commit}"),
+            (Some(branch), None) => format!("branch `{branch}` (no commits)"),
+            (None, Some(commit)) => format!("detached HEAD at {commit}"),
+            (None, None) => "detached HEAD".to_owned(),
+        }
+    }
+}
+
 /// Timeout for git commands to prevent freezing on large repositories
 const GIT_COMMAND_TIMEOUT: TokioDuration = TokioDuration::from_secs(5);
 
@@ -555,6 +607,37 @@ pub async fn local_git_branches(cwd: &Path) -> Vec<String> {
     branches
 }
 
+/// Returns the branch and commit checked out in `cwd`, or `None` outside a
+/// git repository.
+pub async fn head_state(cwd: &Path) -> Option<GitHeadState> {
+    let is_git_repo = run_git_command_with_timeout(&["rev-parse", "--git-dir"], cwd)
+        .await?
+        .status
+        .success();
+    if !is_git_repo {
+        return None;
+    }
+
+    let (branch, commit) = tokio::join!(
+        current_branch_name(cwd),
+        run_git_command_with_timeout(&["rev-parse", "--verify", "--quiet", "HEAD"], cwd)
+    );
+    let commit = commit
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|commit| commit.trim().to_owned())
+        .filter(|commit| !commit.is_empty());
+    Some(GitHeadState { branch, commit })
+}
+
+/// Returns `true` when `ancestor` is reachable from `descendant`, i.e. HEAD
+/// only moved forward between the two commits.
+pub async fn is_ancestor_commit(cwd: &Path, ancestor: &str, descendant: &str) -> bool {
+    run_git_command_with_timeout(&["merge-base", "--is-ancestor", ancestor, descendant], cwd)
+        .await
+        .is_some_and(|output| output.status.success())
+}
+
 /// Returns the current checked out branch name.
 pub async fn current_branch_name(cwd: &Path) -> Option<String> {
     let out = run_git_command_with_timeout(&["branch", "--show-current"], cwd).await?;
@@ -1059,6 +1142,54 @@ mod tests {
         assert!(state.diff.contains("updated"));
     }
 
+    #[tokio::test]
+    async fn test_head_state_tracks_branch_switch_and_rewrite() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let repo_path = create_test_git_repo(&temp_dir).await;
+        assert_eq!(
+            resolve_git_dir(&repo_path.join("src")),
+            Some(repo_path.join(".git"))
+        );
+
+        let before = head_state(&repo_path).await.expect("head state");
+        let before_commit = before.commit.clone().expect("initial commit");
+        assert!(before.describe().contains(&before_commit[..7]));
+
+        Command::new("git")
+            .args(["checkout", "-q", "-b", "feature/head"])
+            .current_dir(&repo_path)
+            .output()
+            .await
+            .expect("git checkout");
+        fs::write(repo_path.join("test.txt"), "amended").unwrap();
+        Command::new("git")
+            .args(["commit", "-q", "-a", "--amend", "-m", "rewritten"])
+            .current_dir(&repo_path)
+            .output()
+            .await
+            .expect("git commit --amend");
+
+        let after = head_state(&repo_path).await.expect("head state");
+        assert_eq!(after.branch.as_deref(), Some("feature/head"));
+        let after_commit = after.commit.expect("amended commit");
+        assert_ne!(after_commit, before_commit);
+        assert!(!is_ancestor_commit(&repo_path, &before_commit, &after_commit).await);
+        assert!(!rebase_in_progress(&repo_path.join(".git")));
+    }
+
+    #[test]
+    fn test_resolve_git_dir_follows_gitdir_file() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let worktree = temp_dir.path().join("worktree");
+        fs::create_dir(&worktree).unwrap();
+        fs::write(worktree.join(".git"), "gitdir: ../main/.git/worktrees/wt\n").unwrap();
+
+        assert_eq!(
+            resolve_git_dir(&worktree),
+            Some(worktree.join("../main/.git/worktrees/wt"))
+        );
+    }
+
     #[test]
     fn test_git_info_serialization() {
         let git_info = GitInfo {
@@ -126,22 +126,27 @@ pub async fn get_git_root_from(cwd: &Path) -> Result<PathBuf, String> {
 /// view. If `base_ref` is `None`, the worktree is created from the current
 /// HEAD. When `base_ref` is set we always recreate the worktree directory to
 /// guarantee it reflects the requested snapshot.
-pub async fn setup_worktree(
-    git_root: &Path,
-    branch_id: &str,
-    base_ref: Option<&str>,
-) -> Result<(PathBuf, String), String> {
-    // Global location: ~/.code/working/<repo_name>/branches
+/// Directory holding agent and review worktrees for the repository at
+/// `git_root`: `~/.code/working/<repo_name>/branches`.
+pub fn branches_dir(git_root: &Path) -> PathBuf {
     let repo_name = git_root
         .file_name()
         .and_then(|s| s.to_str())
         .unwrap_or("repo");
-    let mut code_dir = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
-    code_dir = code_dir
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
         .join(".code")
         .join("working")
         .join(repo_name)
-        .join("branches");
+        .join("branches")
+}
+
+pub async fn setup_worktree(
+    git_root: &Path,
+    branch_id: &str,
+    base_ref: Option<&str>,
+) -> Result<(PathBuf, String), String> {
+    let code_dir = branches_dir(git_root);
     tokio::fs::create_dir_all(&code_dir)
         .await
         .map_err(|e| format!("Failed to create .code/branches directory: {e}"))?;
@@ -315,17 +320,7 @@ pub async fn prepare_reusable_worktree(
     base_ref: &str,
     keep_gitignored: bool,
 ) -> Result<PathBuf, String> {
-    // Global location: ~/.code/working/<repo_name>/branches
-    let repo_name = git_root
-        .file_name()
-        .and_then(|s| s.to_str())
-        .unwrap_or("repo");
-    let mut code_dir = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
-    code_dir = code_dir
-        .join(".code")
-        .join("working")
-        .join(repo_name)
-        .join("branches");
+    let code_dir = branches_dir(git_root);
     tokio::fs::create_dir_all(&code_dir)
         .await
         .map_err(|e| format!("Failed to create .code/branches directory: {e}"))?;
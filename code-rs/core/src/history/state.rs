@@ -4,6 +4,7 @@ use crate::protocol::{FileChange, RateLimitSnapshotEvent, TokenUsage};
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::{Duration, SystemTime};
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -457,6 +458,9 @@ pub enum TextEntity {
 pub struct MessageMetadata {
     pub citations: Vec<String>,
     pub token_usage: Option<TokenUsage>,
+    /// Web pages the answer links to, numbered by position.
+    #[serde(default)]
+    pub sources: Vec<crate::citations::Source>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -641,6 +645,10 @@ pub struct ExecRecord {
     pub env: Vec<(String, String)>,
     #[serde(default)]
     pub tags: Vec<String>,
+    /// Output fold state chosen by the user. `None` folds automatically once
+    /// the output exceeds the TUI's fold threshold.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_folded: Option<bool>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -678,10 +686,15 @@ pub enum ExecStatus {
     Error,
 }
 
+/// A contiguous slice of exec output starting at `offset` bytes into the
+/// stream.
+///
+/// `content` is shared so that cloning a record (which happens on every
+/// streamed delta) bumps reference counts instead of copying output bytes.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ExecStreamChunk {
     pub offset: usize,
-    pub content: String,
+    pub content: Arc<str>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -726,7 +739,7 @@ fn truncate_exec_stream(chunks: &mut Vec<ExecStreamChunk>, truncate_at: usize) {
             continue;
         }
         let keep = truncate_at.saturating_sub(last_start);
-        last.content.truncate(keep);
+        last.content = Arc::from(&last.content[..keep]);
         break;
     }
 }
@@ -735,8 +748,14 @@ fn append_exec_chunk(chunks: &mut Vec<ExecStreamChunk>, chunk: ExecStreamChunk)
     truncate_exec_stream(chunks, chunk.offset);
     if let Some(last) = chunks.last_mut() {
         let last_end = last.offset.saturating_add(last.content.len());
-        if chunk.offset == last_end {
-            last.content.push_str(&chunk.content);
+        // Only small neighbours are coalesced; large chunks keep their shared
+        // allocation so appending never re-copies output already retained.
+        let merged_len = last.content.len().saturating_add(chunk.content.len());
+        if chunk.offset == last_end && merged_len <= EXEC_STREAM_COALESCE_BYTES {
+            let mut merged = String::with_capacity(merged_len);
+            merged.push_str(&last.content);
+            merged.push_str(&chunk.content);
+            last.content = Arc::from(merged);
             prune_exec_stream(chunks, MAX_EXEC_STREAM_RETAINED_BYTES);
             return;
         }
@@ -776,7 +795,7 @@ fn prune_exec_stream(chunks: &mut Vec<ExecStreamChunk>, max_bytes: usize) {
         && let Some(first) = chunks.first_mut() {
             let drain = bytes_to_drop.min(first.content.len());
             first.offset = first.offset.saturating_add(drain);
-            first.content.drain(..drain);
+            first.content = Arc::from(&first.content[drain..]);
         }
 }
 
@@ -1103,6 +1122,10 @@ const EXEC_STREAM_BYTE_STEP: usize = 2 * 1024 * 1024;
 /// Older bytes are truncated from the front once this threshold is exceeded.
 pub const MAX_EXEC_STREAM_RETAINED_BYTES: usize = 32 * 1024 * 1024; // 32 MiB
 
+/// Adjacent exec chunks are merged only while the result stays below this
+/// size. Larger chunks are kept as separate shared slices.
+const EXEC_STREAM_COALESCE_BYTES: usize = 16 * 1024; // 16 KiB
+
 /// Global cap across *all* exec streams we keep in memory. When exceeded, we
 /// progressively trim the oldest exec records down to a small tail to keep RSS
 /// bounded during long Auto Drive runs with many noisy commands.
@@ -1401,6 +1424,9 @@ pub struct HistorySnapshot {
     pub order: Vec<OrderKeySnapshot>,
     #[serde(default)]
     pub order_debug: Vec<Option<String>>,
+    /// Cells the user bookmarked, in the order they were added.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bookmarks: Vec<HistoryId>,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -1413,6 +1439,8 @@ pub struct HistoryState {
     pub tool_call_lookup: HashMap<String, HistoryId>,
     #[serde(default)]
     pub stream_lookup: HashMap<String, HistoryId>,
+    #[serde(default)]
+    bookmarks: Vec<HistoryId>,
     #[serde(skip)]
     id_index: HashMap<HistoryId, usize>,
     #[serde(skip)]
@@ -1437,6 +1465,7 @@ impl HistoryState {
             exec_call_lookup: HashMap::new(),
             tool_call_lookup: HashMap::new(),
             stream_lookup: HashMap::new(),
+            bookmarks: Vec::new(),
             id_index: HashMap::new(),
             usage_tracker: HistoryUsageTracker::default(),
             exec_stream_retained_total_bytes: 0,
@@ -1592,6 +1621,7 @@ impl HistoryState {
             stream_lookup: self.stream_lookup.clone(),
             order: Vec::new(),
             order_debug: Vec::new(),
+            bookmarks: self.bookmarks.clone(),
         }
     }
 
@@ -1625,6 +1655,12 @@ impl HistoryState {
             self.rebuild_lookup_maps();
         }
         self.rebuild_id_index();
+        self.bookmarks = snapshot
+            .bookmarks
+            .iter()
+            .copied()
+            .filter(|id| self.id_index.contains_key(id))
+            .collect();
         self.usage_tracker.reset();
         for record in &self.records {
             self.usage_tracker.on_insert(record);
@@ -1741,6 +1777,35 @@ impl HistoryState {
         self.index_of(id).and_then(|idx| self.records.get_mut(idx))
     }
 
+    /// Adds or removes the bookmark on `id`, returning whether it is now
+    /// bookmarked. Ids without a record are never bookmarked.
+    pub fn toggle_bookmark(&mut self, id: HistoryId) -> bool {
+        if let Some(pos) = self.bookmarks.iter().position(|bookmark| *bookmark == id) {
+            self.bookmarks.remove(pos);
+            return false;
+        }
+        if self.index_of(id).is_none() {
+            return false;
+        }
+        self.bookmarks.push(id);
+        true
+    }
+
+    pub fn is_bookmarked(&self, id: HistoryId) -> bool {
+        self.bookmarks.contains(&id) && self.index_of(id).is_some()
+    }
+
+    /// Bookmarked ids in history order, skipping records that no longer exist.
+    pub fn bookmarks(&self) -> Vec<HistoryId> {
+        let mut ids: Vec<(usize, HistoryId)> = self
+            .bookmarks
+            .iter()
+            .filter_map(|id| self.index_of(*id).map(|idx| (idx, *id)))
+            .collect();
+        ids.sort_unstable_by_key(|(idx, _)| *idx);
+        ids.into_iter().map(|(_, id)| id).collect()
+    }
+
     fn register_record(&mut self, record: &HistoryRecord) {
         match record {
             HistoryRecord::Exec(state) => {
@@ -2099,6 +2164,7 @@ impl HistoryState {
                     working_dir,
                     env,
                     tags,
+                    output_folded: None,
                 };
                 self.apply_event(HistoryEvent::Insert {
                     index: insert_index,
@@ -2143,7 +2209,7 @@ impl HistoryState {
                                     &mut updated.stdout_chunks,
                                     ExecStreamChunk {
                                         offset,
-                                        content: tail,
+                                        content: Arc::from(tail),
                                     },
                                 );
                             }
@@ -2156,7 +2222,7 @@ impl HistoryState {
                                     &mut updated.stderr_chunks,
                                     ExecStreamChunk {
                                         offset,
-                                        content: tail,
+                                        content: Arc::from(tail),
                                     },
                                 );
                             }
@@ -2532,8 +2598,8 @@ mod tests {
                 assert_eq!(exec.wait_total, Some(Duration::from_secs(2)));
                 assert!(!exec.wait_active);
                 assert_eq!(exec.wait_notes.len(), 1);
-                assert_eq!(exec.stdout_chunks.last().map(|c| c.content.as_str()), Some("output"));
-                assert_eq!(exec.stderr_chunks.last().map(|c| c.content.as_str()), Some("warn"));
+                assert_eq!(exec.stdout_chunks.last().map(|c| c.content.as_ref()), Some("output"));
+                assert_eq!(exec.stderr_chunks.last().map(|c| c.content.as_ref()), Some("warn"));
             }
             other => panic!("expected exec record, got {other:?}"),
         }
@@ -2561,7 +2627,7 @@ mod tests {
         let exec_index = state.index_of(inserted_id).expect("exec index present");
         state.apply_domain_event(HistoryDomainEvent::UpdateExecStream {
             index: exec_index,
-            stdout_chunk: Some(ExecStreamChunk { offset: 0, content: oversized.clone() }),
+            stdout_chunk: Some(ExecStreamChunk { offset: 0, content: oversized.as_str().into() }),
             stderr_chunk: None,
         });
 
@@ -2587,6 +2653,48 @@ mod tests {
         assert_eq!(flattened, expected_tail);
     }
 
+    #[test]
+    fn exec_stream_shares_large_chunks_and_coalesces_small_ones() {
+        let mut state = HistoryState::new();
+        let inserted_id = match state.apply_domain_event(HistoryDomainEvent::StartExec {
+            index: state.records.len(),
+            call_id: Some("call-share".into()),
+            command: vec!["cat".into(), "big.log".into()],
+            parsed: Vec::new(),
+            action: ExecAction::Run,
+            started_at: SystemTime::UNIX_EPOCH,
+            working_dir: None,
+            env: Vec::new(),
+            tags: Vec::new(),
+        }) {
+            HistoryMutation::Inserted { id, .. } => id,
+            other => panic!("unexpected mutation: {other:?}"),
+        };
+        let exec_index = state.index_of(inserted_id).expect("exec index present");
+
+        let large: Arc<str> = Arc::from("y".repeat(EXEC_STREAM_COALESCE_BYTES * 2));
+        let mut offset = 0;
+        for content in [Arc::from("a\n"), Arc::from("b\n"), Arc::clone(&large), Arc::from("c\n")] {
+            let len = content.len();
+            state.apply_domain_event(HistoryDomainEvent::UpdateExecStream {
+                index: exec_index,
+                stdout_chunk: Some(ExecStreamChunk { offset, content }),
+                stderr_chunk: None,
+            });
+            offset += len;
+        }
+
+        let HistoryRecord::Exec(record) = state.record(inserted_id).expect("exec record") else {
+            panic!("expected exec record");
+        };
+        let contents: Vec<&str> = record.stdout_chunks.iter().map(|c| c.content.as_ref()).collect();
+        assert_eq!(contents, vec!["a\nb\n", large.as_ref(), "c\n"]);
+        assert!(Arc::ptr_eq(&record.stdout_chunks[1].content, &large));
+
+        let cloned = record.clone();
+        assert!(Arc::ptr_eq(&cloned.stdout_chunks[1].content, &large));
+    }
+
     #[test]
     fn finalize_assistant_updates_existing_records() {
         let mut state = HistoryState::new();
@@ -2652,6 +2760,7 @@ mod tests {
             stream_lookup: HashMap::new(),
             order: Vec::new(),
             order_debug: Vec::new(),
+            bookmarks: Vec::new(),
         };
 
         let mut state = HistoryState::new();
@@ -2697,6 +2806,7 @@ mod tests {
             stream_lookup: HashMap::new(),
             order: Vec::new(),
             order_debug: Vec::new(),
+            bookmarks: Vec::new(),
         };
 
         let mut state = HistoryState::new();
@@ -2763,6 +2873,32 @@ mod tests {
         assert_eq!(state.records[1].id(), second_id);
     }
 
+    #[test]
+    fn bookmarks_survive_snapshot_restore() {
+        let mut state = HistoryState::new();
+        let first_id = state.push(plain_message("first"));
+        let second_id = state.push(plain_message("second"));
+        let third_id = state.push(plain_message("third"));
+
+        assert!(state.toggle_bookmark(third_id));
+        assert!(state.toggle_bookmark(first_id));
+        assert!(state.toggle_bookmark(second_id));
+        assert!(!state.toggle_bookmark(second_id));
+        assert!(!state.toggle_bookmark(HistoryId(99)));
+        assert_eq!(state.bookmarks(), vec![first_id, third_id]);
+
+        let json = serde_json::to_string(&state.snapshot()).expect("serialize snapshot");
+        let snapshot: HistorySnapshot = serde_json::from_str(&json).expect("parse snapshot");
+        let mut restored = HistoryState::new();
+        restored.restore(&snapshot);
+        assert_eq!(restored.bookmarks(), vec![first_id, third_id]);
+        assert!(restored.is_bookmarked(third_id));
+
+        restored.remove(0);
+        assert_eq!(restored.bookmarks(), vec![third_id]);
+        assert!(!restored.is_bookmarked(first_id));
+    }
+
     #[test]
     fn truncate_after_removes_following_records() {
         let mut state = HistoryState::new();
@@ -2840,6 +2976,7 @@ mod tests {
             working_dir: None,
             env: Vec::new(),
             tags: Vec::new(),
+            output_folded: None,
         };
         let exec_id = state.push(HistoryRecord::Exec(exec_record));
         assert_ne!(exec_id, HistoryId::ZERO);
@@ -2895,6 +3032,7 @@ mod tests {
             working_dir: None,
             env: Vec::new(),
             tags: Vec::new(),
+            output_folded: None,
         }));
 
         let tool_id = state.push(HistoryRecord::ToolCall(ToolCallState {
@@ -2951,6 +3089,7 @@ mod tests {
                 reasoning_output_tokens: 1,
                 total_tokens: 16,
             }),
+            sources: Vec::new(),
         };
 
         let mut records: Vec<HistoryRecord> = Vec::new();
@@ -3072,6 +3211,7 @@ mod tests {
             working_dir: None,
             env: Vec::new(),
             tags: Vec::new(),
+            output_folded: None,
         }));
 
         records.push(HistoryRecord::AssistantStream(AssistantStreamState {
@@ -1,3 +1,4 @@
+use crate::config_types::GhostCommitsConfig;
 use crate::git_worktree;
 use crate::rollout::SESSIONS_SUBDIR;
 use fs2::FileExt;
@@ -13,6 +14,7 @@ use tracing::{debug, info, warn};
 
 const DEFAULT_SESSION_RETENTION_DAYS: i64 = 7;
 const DEFAULT_WORKTREE_RETENTION_DAYS: i64 = 3;
+const DEFAULT_SCRATCH_RETENTION_DAYS: i64 = 3;
 const DEFAULT_MIN_INTERVAL_HOURS: i64 = 6;
 const LOCK_FILE_NAME: &str = "cleanup.lock";
 const STATE_FILE_NAME: &str = "cleanup-state.json";
@@ -26,6 +28,8 @@ pub struct CleanupOutcome {
     pub worktree_files_removed: usize,
     pub worktree_bytes_reclaimed: u64,
     pub worktrees_skipped_active: usize,
+    pub scratch_dirs_removed: usize,
+    pub scratch_bytes_reclaimed: u64,
     pub errors: usize,
 }
 
@@ -33,6 +37,9 @@ pub struct CleanupOutcome {
 struct HousekeepingConfig {
     session_retention_days: Option<i64>,
     worktree_retention_days: Option<i64>,
+    scratch_retention_days: Option<i64>,
+    /// Where the `scratch` tool keeps per-session directories.
+    scratch_root: PathBuf,
     min_interval_hours: i64,
     disabled: bool,
 }
@@ -50,6 +57,10 @@ impl HousekeepingConfig {
             "CODE_CLEANUP_WORKTREE_RETENTION_DAYS",
             DEFAULT_WORKTREE_RETENTION_DAYS,
         );
+        let scratch_retention_days = parse_days_env(
+            "CODE_CLEANUP_SCRATCH_RETENTION_DAYS",
+            DEFAULT_SCRATCH_RETENTION_DAYS,
+        );
         let min_interval_hours = parse_positive_i64_env(
             "CODE_CLEANUP_MIN_INTERVAL_HOURS",
             DEFAULT_MIN_INTERVAL_HOURS,
@@ -58,6 +69,8 @@ impl HousekeepingConfig {
         Self {
             session_retention_days,
             worktree_retention_days,
+            scratch_retention_days,
+            scratch_root: crate::scratch::scratch_root(),
             min_interval_hours,
             disabled,
         }
@@ -128,13 +141,18 @@ pub fn run_housekeeping_if_due(code_home: &Path) -> io::Result<Option<CleanupOut
         );
     }
 
-    if outcome.session_days_removed > 0 || outcome.worktrees_removed > 0 {
+    if outcome.session_days_removed > 0
+        || outcome.worktrees_removed > 0
+        || outcome.scratch_dirs_removed > 0
+    {
         info!(
             sessions_pruned = outcome.session_days_removed,
             session_bytes_reclaimed = outcome.session_bytes_reclaimed,
             worktrees_pruned = outcome.worktrees_removed,
             worktree_bytes_reclaimed = outcome.worktree_bytes_reclaimed,
             skipped_active_worktrees = outcome.worktrees_skipped_active,
+            scratch_dirs_pruned = outcome.scratch_dirs_removed,
+            scratch_bytes_reclaimed = outcome.scratch_bytes_reclaimed,
             "code home housekeeping pruned stale artifacts"
         );
     } else {
@@ -144,6 +162,23 @@ pub fn run_housekeeping_if_due(code_home: &Path) -> io::Result<Option<CleanupOut
     Ok(Some(outcome))
 }
 
+/// Applies `[ghost_commits]` retention to the repository at `cwd` when a
+/// session ends. Outside a git repository this is a no-op.
+pub fn prune_ghost_commits_on_exit(cwd: &Path, settings: &GhostCommitsConfig) {
+    if !settings.cleanup_on_exit {
+        return;
+    }
+    match code_git_tooling::prune_ghost_refs(cwd, &settings.retention(), false) {
+        Ok(report) if !report.pruned.is_empty() => info!(
+            "pruned {} ghost commit ref(s), kept {}",
+            report.pruned.len(),
+            report.kept
+        ),
+        Ok(_) | Err(code_git_tooling::GitToolingError::NotAGitRepository { .. }) => {}
+        Err(err) => warn!("failed to prune ghost commits: {err}"),
+    }
+}
+
 fn perform_housekeeping(
     code_home: &Path,
     now: OffsetDateTime,
@@ -168,9 +203,81 @@ fn perform_housekeeping(
             outcome.errors += stats.errors;
         }
 
+    if let Some(days) = config.scratch_retention_days
+        && let Some(stats) = cleanup_scratch(&config.scratch_root, now, days) {
+            outcome.scratch_dirs_removed = stats.removed_dirs;
+            outcome.scratch_bytes_reclaimed = stats.reclaimed_bytes;
+            outcome.errors += stats.errors;
+        }
+
     Ok(outcome)
 }
 
+/// Removes session scratch directories in which nothing has changed for
+/// `retention_days`.
+fn cleanup_scratch(
+    scratch_root: &Path,
+    now: OffsetDateTime,
+    retention_days: i64,
+) -> Option<ScratchCleanupStats> {
+    if !scratch_root.is_dir() {
+        return None;
+    }
+
+    let mut stats = ScratchCleanupStats::default();
+    let retention = Duration::from_secs(retention_days.max(0) as u64 * 86_400);
+    let now_system: SystemTime = SystemTime::from(now);
+
+    for entry in list_dir_sorted(scratch_root) {
+        if !entry.file_type().is_ok_and(|ft| ft.is_dir()) {
+            continue;
+        }
+        let dir = entry.path();
+        let Some(modified) = newest_modification(&dir) else {
+            continue;
+        };
+        let age = now_system.duration_since(modified).unwrap_or(Duration::ZERO);
+        if age < retention {
+            continue;
+        }
+
+        let dir_stats = directory_stats(&dir);
+        match fs::remove_dir_all(&dir) {
+            Ok(()) => {
+                stats.removed_dirs += 1;
+                stats.reclaimed_bytes += dir_stats.bytes;
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => {
+                stats.errors += 1;
+                warn!("failed to remove scratch directory {:?}: {err}", dir);
+            }
+        }
+    }
+
+    Some(stats)
+}
+
+/// Latest modification time of `path` or anything below it.
+fn newest_modification(path: &Path) -> Option<SystemTime> {
+    let mut newest = fs::metadata(path).and_then(|meta| meta.modified()).ok()?;
+    let mut stack = vec![path.to_path_buf()];
+    while let Some(current) = stack.pop() {
+        let Ok(entries) = fs::read_dir(&current) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            if entry.file_type().is_ok_and(|ft| ft.is_dir()) {
+                stack.push(entry.path());
+            }
+            if let Ok(modified) = entry.metadata().and_then(|meta| meta.modified()) {
+                newest = newest.max(modified);
+            }
+        }
+    }
+    Some(newest)
+}
+
 fn cleanup_sessions(
     code_home: &Path,
     today: Date,
@@ -609,6 +716,13 @@ struct SessionCleanupStats {
     errors: usize,
 }
 
+#[derive(Default)]
+struct ScratchCleanupStats {
+    removed_dirs: usize,
+    reclaimed_bytes: u64,
+    errors: usize,
+}
+
 #[derive(Default)]
 struct WorktreeCleanupStats {
     removed_worktrees: usize,
@@ -791,6 +905,8 @@ mod tests {
         let config = HousekeepingConfig {
             session_retention_days: Some(7),
             worktree_retention_days: None,
+            scratch_retention_days: None,
+            scratch_root: code_home.join("scratch"),
             min_interval_hours: 1,
             disabled: false,
         };
@@ -816,6 +932,8 @@ mod tests {
         let config = HousekeepingConfig {
             session_retention_days: None,
             worktree_retention_days: Some(0),
+            scratch_retention_days: None,
+            scratch_root: code_home.join("scratch"),
             min_interval_hours: 1,
             disabled: false,
         };
@@ -844,6 +962,8 @@ mod tests {
         let config = HousekeepingConfig {
             session_retention_days: None,
             worktree_retention_days: Some(0),
+            scratch_retention_days: None,
+            scratch_root: code_home.join("scratch"),
             min_interval_hours: 1,
             disabled: false,
         };
@@ -891,6 +1011,8 @@ mod tests {
         let config = HousekeepingConfig {
             session_retention_days: None,
             worktree_retention_days: Some(0),
+            scratch_retention_days: None,
+            scratch_root: code_home.join("scratch"),
             min_interval_hours: 1,
             disabled: false,
         };
@@ -903,6 +1025,35 @@ mod tests {
         assert!(!branch_exists(&repo_dir, "code-branch-test"));
     }
 
+    #[test]
+    fn removes_idle_scratch_directories() {
+        let temp = TempDir::new().unwrap();
+        let code_home = temp.path();
+        let scratch_root = code_home.join("scratch");
+        let session_dir = scratch_root.join("session-a");
+        fs::create_dir_all(session_dir.join("analysis")).unwrap();
+        fs::write(session_dir.join("analysis/notes.md"), b"notes").unwrap();
+
+        let config = |days| HousekeepingConfig {
+            session_retention_days: None,
+            worktree_retention_days: None,
+            scratch_retention_days: Some(days),
+            scratch_root: scratch_root.clone(),
+            min_interval_hours: 1,
+            disabled: false,
+        };
+        let later = OffsetDateTime::now_utc() + time::Duration::days(10);
+
+        let outcome = perform_housekeeping(code_home, later, &config(30)).unwrap();
+        assert_eq!(outcome.scratch_dirs_removed, 0);
+        assert!(session_dir.exists());
+
+        let outcome = perform_housekeeping(code_home, later, &config(3)).unwrap();
+        assert_eq!(outcome.scratch_dirs_removed, 1);
+        assert_eq!(outcome.scratch_bytes_reclaimed, 5);
+        assert!(!session_dir.exists());
+    }
+
     fn run_git(repo_root: &Path, args: impl IntoIterator<Item = impl AsRef<std::ffi::OsStr>>) -> io::Result<()> {
         let output = std::process::Command::new("git")
             .current_dir(repo_root)
@@ -1,12 +1,150 @@
 use std::fs;
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::LazyLock;
+use std::sync::RwLock;
 
 use reqwest::cookie::CookieStore;
 use reqwest::cookie::Jar;
 use reqwest::header::HeaderValue;
 
+/// Proxy and CA settings from `[network]` that every outbound HTTP client
+/// honors: model providers, auth, MCP HTTP transports, `web_fetch` and the
+/// internal browser.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OutboundNetworkSettings {
+    /// Proxy for `http://` URLs.
+    pub http_proxy: Option<String>,
+    /// Proxy for `https://` URLs.
+    pub https_proxy: Option<String>,
+    /// Hosts, domains (`.corp.example`) and CIDR ranges that bypass the proxy.
+    pub no_proxy: Vec<String>,
+    /// PEM bundle of extra root certificates trusted alongside the system roots.
+    pub ca_bundle: Option<PathBuf>,
+}
+
+impl OutboundNetworkSettings {
+    /// Whether `[network]` sets any proxy option, in which case proxies are
+    /// resolved here instead of by each client library.
+    pub fn configures_proxy(&self) -> bool {
+        self.http_proxy.is_some() || self.https_proxy.is_some() || !self.no_proxy.is_empty()
+    }
+
+    /// Proxy for `http://` URLs: the configured one, else `HTTP_PROXY`.
+    pub fn effective_http_proxy(&self) -> Option<String> {
+        non_empty(self.http_proxy.clone()).or_else(|| env_value(&["HTTP_PROXY", "http_proxy"]))
+    }
+
+    /// Proxy for `https://` URLs: the configured one, else `HTTPS_PROXY`,
+    /// else the HTTP proxy.
+    pub fn effective_https_proxy(&self) -> Option<String> {
+        non_empty(self.https_proxy.clone())
+            .or_else(|| env_value(&["HTTPS_PROXY", "https_proxy"]))
+            .or_else(|| self.effective_http_proxy())
+    }
+
+    /// Comma-separated bypass list: the configured one, else `NO_PROXY`.
+    pub fn effective_no_proxy(&self) -> Option<String> {
+        let configured: Vec<&str> = self
+            .no_proxy
+            .iter()
+            .map(|entry| entry.trim())
+            .filter(|entry| !entry.is_empty())
+            .collect();
+        if configured.is_empty() {
+            env_value(&["NO_PROXY", "no_proxy"])
+        } else {
+            Some(configured.join(","))
+        }
+    }
+
+    /// Chrome `--proxy-server` value, or `None` to leave Chrome on its own
+    /// proxy detection.
+    pub fn chrome_proxy_server(&self) -> Option<String> {
+        if !self.configures_proxy() {
+            return None;
+        }
+        match (self.effective_http_proxy(), self.effective_https_proxy()) {
+            (Some(http), Some(https)) if http == https => Some(http),
+            (Some(http), Some(https)) => Some(format!("http={http};https={https}")),
+            (None, Some(https)) => Some(format!("https={https}")),
+            (Some(http), None) => Some(format!("http={http}")),
+            (None, None) => None,
+        }
+    }
+
+    /// Chrome `--proxy-bypass-list` value (semicolon-separated).
+    pub fn chrome_proxy_bypass_list(&self) -> Option<String> {
+        if !self.configures_proxy() {
+            return None;
+        }
+        self.effective_no_proxy().map(|list| list.replace(',', ";"))
+    }
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|value| !value.trim().is_empty())
+}
+
+fn env_value(keys: &[&str]) -> Option<String> {
+    keys.iter()
+        .find_map(|key| non_empty(std::env::var(key).ok()))
+}
+
+static OUTBOUND_NETWORK_SETTINGS: LazyLock<RwLock<OutboundNetworkSettings>> =
+    LazyLock::new(|| RwLock::new(OutboundNetworkSettings::default()));
+
+/// Replaces the process-wide outbound settings. Called whenever config is
+/// loaded; clients built afterwards pick up the new values.
+pub fn set_outbound_network_settings(settings: OutboundNetworkSettings) {
+    if let Ok(mut guard) = OUTBOUND_NETWORK_SETTINGS.write() {
+        *guard = settings;
+    }
+}
+
+pub fn outbound_network_settings() -> OutboundNetworkSettings {
+    OUTBOUND_NETWORK_SETTINGS
+        .read()
+        .map(|guard| guard.clone())
+        .unwrap_or_default()
+}
+
+/// Reads every certificate in a PEM bundle, or a single DER certificate.
+pub fn load_ca_bundle(path: &Path) -> std::io::Result<Vec<reqwest::Certificate>> {
+    let bytes = fs::read(path)?;
+    let certs = match reqwest::Certificate::from_pem_bundle(&bytes) {
+        Ok(certs) if !certs.is_empty() => certs,
+        _ => vec![reqwest::Certificate::from_der(&bytes).map_err(|err| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("{} is not a PEM or DER certificate bundle: {err}", path.display()),
+            )
+        })?],
+    };
+    Ok(certs)
+}
+
+/// Loads the bundle at `path` and checks that the TLS stack accepts every
+/// certificate in it, returning how many it holds. Parsing alone is lenient
+/// (DER input is not inspected until a client is built), so this builds a
+/// throwaway client.
+pub fn validate_ca_bundle(path: &Path) -> std::io::Result<usize> {
+    let certs = load_ca_bundle(path)?;
+    let count = certs.len();
+    certs
+        .into_iter()
+        .fold(reqwest::Client::builder(), reqwest::ClientBuilder::add_root_certificate)
+        .build()
+        .map_err(|err| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("{} contains an unusable certificate: {err}", path.display()),
+            )
+        })?;
+    Ok(count)
+}
+
 static SHARED_CHATGPT_CLOUDFLARE_COOKIE_STORE: LazyLock<Arc<ChatGptCloudflareCookieStore>> =
     LazyLock::new(|| Arc::new(ChatGptCloudflareCookieStore::default()));
 
@@ -116,12 +254,23 @@ fn is_allowed_cloudflare_cookie_name(name: &str) -> bool {
 }
 
 /// Build a `reqwest::ClientBuilder` with optional extra root certificates loaded from
-/// common environment variables (`SSL_CERT_FILE`, `REQUESTS_CA_BUNDLE`,
-/// `NODE_EXTRA_CA_CERTS`). This helps environments using corporate/mitm proxies
-/// whose CAs are distributed via system config or user-provided files.
+/// `network.ca_bundle` and common environment variables (`SSL_CERT_FILE`,
+/// `REQUESTS_CA_BUNDLE`, `NODE_EXTRA_CA_CERTS`). This helps environments using
+/// corporate/mitm proxies whose CAs are distributed via system config or
+/// user-provided files.
 pub fn apply_extra_root_certificates(
     mut builder: reqwest::ClientBuilder,
 ) -> reqwest::ClientBuilder {
+    // Validated when config is loaded, so a failure here means the file
+    // changed underneath us; the env fallbacks below still apply.
+    if let Some(path) = outbound_network_settings().ca_bundle
+        && let Ok(certs) = load_ca_bundle(&path)
+    {
+        for cert in certs {
+            builder = builder.add_root_certificate(cert);
+        }
+    }
+
     fn load_cert(path: PathBuf) -> Option<reqwest::Certificate> {
         if !path.exists() || !path.is_file() {
             return None;
@@ -162,14 +311,100 @@ pub fn apply_extra_root_certificates(
     builder
 }
 
+/// Routes `builder` through the proxies from `[network]`. Without any
+/// `[network]` proxy settings reqwest keeps reading the proxy environment
+/// variables itself.
+pub fn apply_outbound_proxy(builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+    let settings = outbound_network_settings();
+    if !settings.configures_proxy() {
+        return builder;
+    }
+    let no_proxy = settings
+        .effective_no_proxy()
+        .and_then(|list| reqwest::NoProxy::from_string(&list));
+    let mut builder = builder.no_proxy();
+    let proxies = [
+        settings.effective_http_proxy().map(|url| {
+            let proxy = reqwest::Proxy::http(url.as_str());
+            (url, proxy)
+        }),
+        settings.effective_https_proxy().map(|url| {
+            let proxy = reqwest::Proxy::https(url.as_str());
+            (url, proxy)
+        }),
+    ];
+    for (url, proxy) in proxies.into_iter().flatten() {
+        match proxy {
+            Ok(proxy) => builder = builder.proxy(proxy.no_proxy(no_proxy.clone())),
+            Err(err) => tracing::warn!("ignoring invalid proxy URL {url}: {err}"),
+        }
+    }
+    builder
+}
+
+/// Applies both the extra root certificates and the `[network]` proxies.
+pub fn apply_outbound_network_settings(builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+    apply_outbound_proxy(apply_extra_root_certificates(builder))
+}
+
 /// Build a reqwest Client with optional extra root certificates loaded from
 /// common environment variables. `CODEX_CA_CERTIFICATE` takes precedence over
 /// `SSL_CERT_FILE`, and other ecosystem-standard CA bundle variables continue
 /// to work as fallbacks.
 pub fn build_http_client() -> reqwest::Client {
-    apply_extra_root_certificates(with_chatgpt_cloudflare_cookie_store(
+    apply_outbound_network_settings(with_chatgpt_cloudflare_cookie_store(
         reqwest::Client::builder(),
     ))
     .build()
     .unwrap_or_else(|_| reqwest::Client::new())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn configured_no_proxy_entries_are_trimmed_and_joined() {
+        let settings = OutboundNetworkSettings {
+            no_proxy: vec![" localhost ".to_owned(), String::new(), ".corp.example".to_owned()],
+            ..OutboundNetworkSettings::default()
+        };
+        assert!(settings.configures_proxy());
+        assert_eq!(settings.effective_no_proxy().as_deref(), Some("localhost,.corp.example"));
+        assert!(!OutboundNetworkSettings::default().configures_proxy());
+        assert_eq!(
+            settings.chrome_proxy_bypass_list().as_deref(),
+            Some("localhost;.corp.example")
+        );
+    }
+
+    #[test]
+    fn chrome_proxy_server_uses_per_scheme_rules_only_when_they_differ() {
+        let same = OutboundNetworkSettings {
+            http_proxy: Some("http://proxy:8080".to_owned()),
+            https_proxy: Some("http://proxy:8080".to_owned()),
+            ..OutboundNetworkSettings::default()
+        };
+        assert_eq!(same.chrome_proxy_server().as_deref(), Some("http://proxy:8080"));
+
+        let split = OutboundNetworkSettings {
+            https_proxy: Some("http://secure:3128".to_owned()),
+            ..same
+        };
+        assert_eq!(
+            split.chrome_proxy_server().as_deref(),
+            Some("http=http://proxy:8080;https=http://secure:3128")
+        );
+    }
+
+    #[test]
+    fn ca_bundle_rejects_non_certificates() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("bundle.pem");
+        std::fs::write(&path, "not a certificate").expect("write bundle");
+        let err = validate_ca_bundle(&path).expect_err("garbage is not a bundle");
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert!(validate_ca_bundle(&dir.path().join("missing.pem")).is_err());
+    }
+}
@@ -0,0 +1,309 @@
+//! Per provider/model request latency, kept next to the token usage files in
+//! `CODE_HOME/usage/latency.json`.
+//!
+//! Every model request records its time to first token, and completed ones
+//! also record how fast output streamed after that. Samples are grouped into
+//! daily buckets per provider and model so `code usage latency` can compare
+//! providers and spot regressions against an earlier window.
+
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use fs2::FileExt;
+use serde::{Deserialize, Serialize};
+
+const LATENCY_VERSION: u32 = 1;
+const LATENCY_FILE: &str = "latency.json";
+const RETENTION_DAYS: i64 = 90;
+/// First-token samples kept per bucket; later requests still count toward
+/// the totals.
+const MAX_SAMPLES_PER_BUCKET: usize = 2_000;
+
+/// One model request, as recorded when it completes or fails.
+#[derive(Debug, Clone)]
+pub struct LatencySample {
+    pub provider: String,
+    pub model: String,
+    /// Time from sending the request to the first streamed output.
+    pub first_token_ms: Option<u64>,
+    /// Time from sending the request to its end.
+    pub duration_ms: u64,
+    pub output_tokens: Option<u64>,
+    pub failed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LatencyBucket {
+    day: NaiveDate,
+    provider: String,
+    model: String,
+    #[serde(default)]
+    requests: u64,
+    #[serde(default)]
+    failures: u64,
+    #[serde(default)]
+    first_token_ms: Vec<u64>,
+    #[serde(default)]
+    output_tokens: u64,
+    /// Streaming time after the first token for the requests in
+    /// `output_tokens`.
+    #[serde(default)]
+    generation_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LatencyData {
+    version: u32,
+    #[serde(default)]
+    buckets: Vec<LatencyBucket>,
+}
+
+impl Default for LatencyData {
+    fn default() -> Self {
+        Self {
+            version: LATENCY_VERSION,
+            buckets: Vec::new(),
+        }
+    }
+}
+
+impl LatencyData {
+    fn record(&mut self, sample: &LatencySample, day: NaiveDate) {
+        let idx = match self.buckets.iter().position(|bucket| {
+            bucket.day == day && bucket.provider == sample.provider && bucket.model == sample.model
+        }) {
+            Some(idx) => idx,
+            None => {
+                self.buckets.push(LatencyBucket {
+                    day,
+                    provider: sample.provider.clone(),
+                    model: sample.model.clone(),
+                    requests: 0,
+                    failures: 0,
+                    first_token_ms: Vec::new(),
+                    output_tokens: 0,
+                    generation_ms: 0,
+                });
+                self.buckets.len() - 1
+            }
+        };
+        let bucket = &mut self.buckets[idx];
+        bucket.requests = bucket.requests.saturating_add(1);
+        if sample.failed {
+            bucket.failures = bucket.failures.saturating_add(1);
+        }
+        if let Some(first_token_ms) = sample.first_token_ms {
+            if bucket.first_token_ms.len() < MAX_SAMPLES_PER_BUCKET {
+                bucket.first_token_ms.push(first_token_ms);
+            }
+            if !sample.failed
+                && let Some(output_tokens) = sample.output_tokens
+                && sample.duration_ms > first_token_ms
+            {
+                bucket.output_tokens = bucket.output_tokens.saturating_add(output_tokens);
+                bucket.generation_ms = bucket
+                    .generation_ms
+                    .saturating_add(sample.duration_ms - first_token_ms);
+            }
+        }
+    }
+
+    fn prune(&mut self, today: NaiveDate) {
+        let cutoff = today - Duration::days(RETENTION_DAYS);
+        self.buckets.retain(|bucket| bucket.day >= cutoff);
+    }
+}
+
+/// Latency for one provider and model over a range of days.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LatencySummary {
+    pub provider: String,
+    pub model: String,
+    pub requests: u64,
+    pub failures: u64,
+    pub first_token_p50_ms: Option<u64>,
+    pub first_token_p90_ms: Option<u64>,
+    pub first_token_p99_ms: Option<u64>,
+    /// Output tokens per second after the first token.
+    pub tokens_per_sec: Option<f64>,
+    /// Share (0.0-1.0) of first-token samples at or under the SLO passed to
+    /// [`summarize_latency`].
+    pub within_slo: Option<f64>,
+}
+
+fn latency_file_path(code_home: &Path) -> PathBuf {
+    code_home.join("usage").join(LATENCY_FILE)
+}
+
+pub fn record_latency_sample(
+    code_home: &Path,
+    sample: &LatencySample,
+    observed_at: DateTime<Utc>,
+) -> std::io::Result<()> {
+    let path = latency_file_path(code_home);
+    let Some(dir) = path.parent() else {
+        return Ok(());
+    };
+    fs::create_dir_all(dir)?;
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(&path)?;
+    file.lock_exclusive()?;
+
+    let mut contents = String::new();
+    file.seek(SeekFrom::Start(0))?;
+    // We need read_to_string on the locked file handle, not fs::read_to_string.
+    #[allow(clippy::verbose_file_reads)]
+    file.read_to_string(&mut contents)?;
+    let mut data = serde_json::from_str::<LatencyData>(&contents).unwrap_or_default();
+    data.version = LATENCY_VERSION;
+
+    let today = observed_at.date_naive();
+    data.record(sample, today);
+    data.prune(today);
+
+    let json = serde_json::to_string_pretty(&data)?;
+    let tmp_path = dir.join(format!("{LATENCY_FILE}.tmp"));
+    {
+        let mut tmp = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&tmp_path)?;
+        tmp.write_all(json.as_bytes())?;
+        tmp.sync_all()?;
+    }
+    if let Err(err) = fs::rename(&tmp_path, &path) {
+        let _ = fs::remove_file(&tmp_path);
+        file.unlock()?;
+        return Err(err);
+    }
+    file.unlock()?;
+    Ok(())
+}
+
+/// Summaries per provider and model for the days in `from..to`, sorted by
+/// provider then model. `slo_ms` fills in [`LatencySummary::within_slo`].
+pub fn summarize_latency(
+    code_home: &Path,
+    from: NaiveDate,
+    to: NaiveDate,
+    slo_ms: Option<u64>,
+) -> std::io::Result<Vec<LatencySummary>> {
+    let path = latency_file_path(code_home);
+    let data = match fs::read_to_string(&path) {
+        Ok(contents) => serde_json::from_str::<LatencyData>(&contents).unwrap_or_default(),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+
+    let mut grouped: BTreeMap<(&str, &str), Vec<&LatencyBucket>> = BTreeMap::new();
+    for bucket in data.buckets.iter().filter(|bucket| bucket.day >= from && bucket.day < to) {
+        grouped
+            .entry((bucket.provider.as_str(), bucket.model.as_str()))
+            .or_default()
+            .push(bucket);
+    }
+
+    Ok(grouped
+        .into_iter()
+        .map(|((provider, model), buckets)| {
+            let mut samples: Vec<u64> = buckets
+                .iter()
+                .flat_map(|bucket| bucket.first_token_ms.iter().copied())
+                .collect();
+            samples.sort_unstable();
+            let output_tokens: u64 = buckets.iter().map(|bucket| bucket.output_tokens).sum();
+            let generation_ms: u64 = buckets.iter().map(|bucket| bucket.generation_ms).sum();
+            let within_slo = slo_ms.filter(|_| !samples.is_empty()).map(|slo| {
+                let within = samples.partition_point(|ms| *ms <= slo);
+                within as f64 / samples.len() as f64
+            });
+            LatencySummary {
+                provider: provider.to_owned(),
+                model: model.to_owned(),
+                requests: buckets.iter().map(|bucket| bucket.requests).sum(),
+                failures: buckets.iter().map(|bucket| bucket.failures).sum(),
+                first_token_p50_ms: percentile(&samples, 50),
+                first_token_p90_ms: percentile(&samples, 90),
+                first_token_p99_ms: percentile(&samples, 99),
+                tokens_per_sec: (generation_ms > 0)
+                    .then(|| output_tokens as f64 * 1000.0 / generation_ms as f64),
+                within_slo,
+            }
+        })
+        .collect())
+}
+
+/// Nearest-rank percentile of sorted samples.
+fn percentile(sorted: &[u64], pct: usize) -> Option<u64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (sorted.len() * pct).div_ceil(100).max(1);
+    sorted.get(rank - 1).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn sample(model: &str, first_token_ms: u64, failed: bool) -> LatencySample {
+        LatencySample {
+            provider: "openai".to_owned(),
+            model: model.to_owned(),
+            first_token_ms: Some(first_token_ms),
+            duration_ms: first_token_ms + 2_000,
+            output_tokens: Some(100),
+            failed,
+        }
+    }
+
+    #[test]
+    fn summarizes_per_model_within_the_window() {
+        let home = TempDir::new().expect("tempdir");
+        let now = Utc::now();
+        let today = now.date_naive();
+        for ms in [400, 600, 800, 1_000] {
+            record_latency_sample(home.path(), &sample("gpt-5", ms, false), now).expect("record");
+        }
+        record_latency_sample(home.path(), &sample("gpt-5", 3_000, true), now).expect("record");
+        record_latency_sample(
+            home.path(),
+            &sample("gpt-5-mini", 200, false),
+            now - Duration::days(10),
+        )
+        .expect("record");
+
+        let summaries = summarize_latency(
+            home.path(),
+            today - Duration::days(6),
+            today + Duration::days(1),
+            Some(1_000),
+        )
+        .expect("summarize");
+
+        assert_eq!(
+            summaries,
+            vec![LatencySummary {
+                provider: "openai".to_owned(),
+                model: "gpt-5".to_owned(),
+                requests: 5,
+                failures: 1,
+                first_token_p50_ms: Some(800),
+                first_token_p90_ms: Some(3_000),
+                first_token_p99_ms: Some(3_000),
+                tokens_per_sec: Some(50.0),
+                within_slo: Some(0.8),
+            }]
+        );
+    }
+}
@@ -7,6 +7,7 @@
 #![deny(clippy::print_stdout, clippy::print_stderr)]
 
 mod apply_patch;
+pub mod approvals_store;
 mod fs_sanitize;
 pub mod auth;
 pub mod auth_accounts;
@@ -18,6 +19,8 @@ pub use account_switching::{
 pub mod bash;
 mod auto_drive_pid;
 mod chat_completions;
+mod child_registry;
+pub mod citations;
 mod client;
 mod client_common;
 pub mod codex;
@@ -40,9 +43,11 @@ mod conversation_history;
 pub mod acp;
 pub mod custom_prompts;
 pub mod debug_logger;
+pub mod docs_bundles;
 pub mod review_coord;
 pub mod env;
 mod environment_context;
+pub mod event_batch;
 mod reasoning;
 pub mod retention;
 pub(crate) mod telemetry;
@@ -58,13 +63,17 @@ pub use auto_drive_pid::{AutoDriveMode, AutoDrivePidFile};
 pub mod error;
 pub mod exec;
 pub mod managed_network_proxy_api;
+pub(crate) mod network_allowlist;
 pub(crate) mod network_approval;
+#[cfg(feature = "managed-network-proxy")]
+pub(crate) mod network_egress;
 mod exec_command;
 pub mod exec_env;
 pub mod external_agent_config;
 mod flags;
 pub mod git_info;
 pub mod landlock;
+pub mod latency_usage;
 pub mod http_client;
 pub(crate) mod housekeeping;
 pub mod mcp_connection_manager;
@@ -74,6 +83,7 @@ pub mod apps_sources;
 mod mcp_tool_call;
 mod memories;
 mod model_provider_info;
+mod provider_errors;
 pub mod remote_models;
 // Remote model discovery caches its own on-disk state within the module.
 mod cgroup;
@@ -90,6 +100,7 @@ pub mod slash_commands;
 pub mod parse_command;
 pub mod history;
 mod mentions;
+mod tool_output_budget;
 mod truncate;
 mod user_instructions;
 pub mod plugins;
@@ -145,6 +156,7 @@ pub mod secrets_resolver;
 #[cfg(test)]
 mod event_mapping;
 pub mod review_format;
+pub mod review_profiles;
 #[cfg(test)]
 mod prompt_assembly_tests;
 pub use code_protocol::protocol::InitialHistory;
@@ -163,13 +175,19 @@ mod patch_harness;
 pub mod plan_tool;
 pub mod project_doc;
 pub mod project_features;
+pub mod project_memory;
 mod rollout;
 pub(crate) mod safety;
+pub(crate) mod scratch;
 pub mod session_catalog;
+pub mod session_handoff;
+pub mod session_templates;
 pub mod seatbelt;
 pub mod shell;
+mod shell_snapshot;
 pub mod sandboxing;
 pub mod spawn;
+pub mod startup_profile;
 pub(crate) mod terminal;
 pub mod otel_init;
 mod text_encoding;
@@ -182,7 +200,9 @@ pub use rollout::INTERACTIVE_SESSION_SOURCES;
 pub use rollout::RolloutRecorder;
 pub use rollout::SESSIONS_SUBDIR;
 pub use rollout::SessionMeta;
+pub use rollout::fork::ForkPoint;
 pub use rollout::fork::fork_rollout;
+pub use rollout::fork::fork_rollout_at;
 pub use rollout::list::find_conversation_path_by_id_str;
 pub use rollout::list::ConversationItem;
 pub use rollout::list::ConversationsPage;
@@ -195,6 +215,7 @@ pub use text_encoding::bytes_to_string_smart;
 pub use session_catalog::SessionQuery;
 mod user_notification;
 pub mod util;
+pub mod web_search;
 pub mod windows_sandbox;
 
 pub use apply_patch::CODEX_APPLY_PATCH_ARG1;
@@ -204,6 +225,7 @@ pub use agent_tool::external_agent_command_exists;
 pub use agent_tool::smoke_test_agent_blocking;
 pub use agent_tool::split_command_and_args;
 pub use safety::get_platform_sandbox;
+pub use housekeeping::prune_ghost_commits_on_exit;
 pub use housekeeping::run_housekeeping_if_due;
 pub use housekeeping::CleanupOutcome;
 // Use our internal protocol module for crate-internal types and helpers.
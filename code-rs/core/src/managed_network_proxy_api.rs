@@ -1,5 +1,7 @@
+use std::collections::BTreeSet;
 use std::collections::HashMap;
 use std::net::SocketAddr;
+use url::Url;
 
 #[cfg(feature = "managed-network-proxy")]
 #[derive(Clone, Debug)]
@@ -96,3 +98,53 @@ pub(crate) fn has_proxy_url_env_vars(env: &HashMap<String, String>) -> bool {
         .iter()
         .any(|key| proxy_url_env_value(env, key).is_some_and(|value| !value.trim().is_empty()))
 }
+
+fn is_loopback_host(host: &str) -> bool {
+    host.eq_ignore_ascii_case("localhost") || host == "127.0.0.1" || host == "::1"
+}
+
+fn proxy_scheme_default_port(scheme: &str) -> u16 {
+    match scheme {
+        "https" => 443,
+        "socks5" | "socks5h" | "socks4" | "socks4a" => 1080,
+        _ => 80,
+    }
+}
+
+/// Ports of the loopback proxy endpoints named in `env`'s proxy variables.
+/// The sandboxes limit outbound connections to these when commands are
+/// routed through the managed proxy.
+pub fn proxy_loopback_ports_from_env(env: &HashMap<String, String>) -> Vec<u16> {
+    let mut ports = BTreeSet::new();
+    for key in PROXY_URL_ENV_KEYS {
+        let Some(proxy_url) = proxy_url_env_value(env, key) else {
+            continue;
+        };
+        let trimmed = proxy_url.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        let candidate = if trimmed.contains("://") {
+            trimmed.to_owned()
+        } else {
+            format!("http://{trimmed}")
+        };
+        let Ok(parsed) = Url::parse(&candidate) else {
+            continue;
+        };
+        let Some(host) = parsed.host_str() else {
+            continue;
+        };
+        if !is_loopback_host(host) {
+            continue;
+        }
+
+        let scheme = parsed.scheme().to_ascii_lowercase();
+        let port = parsed
+            .port()
+            .unwrap_or_else(|| proxy_scheme_default_port(scheme.as_str()));
+        ports.insert(port);
+    }
+    ports.into_iter().collect()
+}
@@ -1,3 +1,4 @@
 pub(crate) mod ids;
 pub(crate) mod policy;
 pub(crate) mod skill_dependencies;
+pub(crate) mod tool_budget;
@@ -228,6 +228,7 @@ fn server_failed_warning(skills: &str, server: &str, failure: &McpServerFailure)
     let phase = match failure.phase {
         McpServerFailurePhase::Start => "start",
         McpServerFailurePhase::ListTools => "list tools",
+        McpServerFailurePhase::CircuitOpen => "answer recent tool calls",
     };
     format!(
         "{skills} require MCP server `{server}`, but it failed to {phase}: {}. Open Settings -> MCP for details (or run `/mcp status`).",
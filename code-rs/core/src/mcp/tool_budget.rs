@@ -0,0 +1,159 @@
+//! Per-request budget for MCP tool schemas (`[tools.mcp_budget]`).
+//!
+//! Sessions with many MCP servers can spend a large share of the context on
+//! tool schemas. With a budget set, each request carries only the
+//! highest-scoring tools: recent use and name overlap with the latest user
+//! message earn points, and tools the model loaded through `tools_list_more`
+//! are always kept. Everything else is listed as omitted so the model can
+//! load it on demand.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use code_protocol::models::ContentItem;
+use code_protocol::models::ResponseItem;
+
+use crate::config_types::McpToolBudgetConfig;
+
+/// How many recently used MCP tools the session remembers for scoring.
+pub(crate) const RECENT_MCP_TOOLS_LIMIT: usize = 32;
+
+pub(crate) struct BudgetedTools {
+    pub(crate) included: HashMap<String, mcp_types::Tool>,
+    /// Qualified names left out of this request, sorted.
+    pub(crate) omitted: Vec<String>,
+}
+
+/// Keeps `loaded` tools plus the best-scoring others up to `max_tools`.
+/// `recent` is ordered most recent first; ties fall back to name order so
+/// the selection is stable between requests.
+pub(crate) fn apply_tool_budget(
+    tools: HashMap<String, mcp_types::Tool>,
+    budget: &McpToolBudgetConfig,
+    prompt_text: &str,
+    recent: &[String],
+    loaded: &[String],
+) -> BudgetedTools {
+    let prompt_words = words(prompt_text);
+    let mut included = HashMap::new();
+    let mut candidates: Vec<(f32, String, mcp_types::Tool)> = Vec::new();
+    for (name, tool) in tools {
+        if loaded.iter().any(|loaded| loaded.eq_ignore_ascii_case(&name)) {
+            included.insert(name, tool);
+            continue;
+        }
+        let recent_score = recent
+            .iter()
+            .position(|used| used.eq_ignore_ascii_case(&name))
+            .map_or(0.0, |rank| budget.recent_use_weight / (rank + 1) as f32);
+        let name_matches = words(&name).intersection(&prompt_words).count();
+        let score = recent_score + budget.name_match_weight * name_matches as f32;
+        candidates.push((score, name, tool));
+    }
+
+    candidates.sort_by(|(score_a, name_a, _), (score_b, name_b, _)| {
+        score_b
+            .partial_cmp(score_a)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| name_a.cmp(name_b))
+    });
+    let slots = budget.max_tools.saturating_sub(included.len());
+    let mut omitted = Vec::new();
+    for (idx, (_, name, tool)) in candidates.into_iter().enumerate() {
+        if idx < slots {
+            included.insert(name, tool);
+        } else {
+            omitted.push(name);
+        }
+    }
+    omitted.sort();
+    BudgetedTools { included, omitted }
+}
+
+/// Text of the most recent user message in the request input.
+pub(crate) fn latest_user_text(input: &[ResponseItem]) -> String {
+    input
+        .iter()
+        .rev()
+        .find_map(|item| match item {
+            ResponseItem::Message { role, content, .. } if role == "user" => Some(
+                content
+                    .iter()
+                    .filter_map(|entry| match entry {
+                        ContentItem::InputText { text } => Some(text.as_str()),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            ),
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
+/// Lowercase words of at least three characters, split on anything that is
+/// not alphanumeric (so `github__list_pull_requests` yields `github`, `list`,
+/// `pull`, `requests`).
+pub(crate) fn words(text: &str) -> HashSet<String> {
+    text.split(|ch: char| !ch.is_alphanumeric())
+        .filter(|word| word.chars().count() >= 3)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn tool(name: &str) -> mcp_types::Tool {
+        mcp_types::Tool {
+            name: name.to_owned(),
+            input_schema: mcp_types::ToolInputSchema {
+                properties: None,
+                required: None,
+                r#type: "object".to_owned(),
+            },
+            output_schema: None,
+            title: None,
+            annotations: None,
+            description: None,
+        }
+    }
+
+    #[test]
+    fn keeps_loaded_then_recent_and_matching_tools() {
+        let tools: HashMap<String, mcp_types::Tool> = [
+            "github__list_issues",
+            "github__create_pull_request",
+            "jira__search",
+            "slack__post_message",
+            "sentry__get_issue",
+        ]
+        .into_iter()
+        .map(|name| (name.to_owned(), tool(name)))
+        .collect();
+        let budget = McpToolBudgetConfig {
+            max_tools: 3,
+            recent_use_weight: 2.0,
+            name_match_weight: 1.0,
+        };
+
+        let budgeted = apply_tool_budget(
+            tools,
+            &budget,
+            "Open a pull request for this fix",
+            &["slack__post_message".to_owned()],
+            &["jira__search".to_owned()],
+        );
+
+        let mut included: Vec<&str> = budgeted.included.keys().map(String::as_str).collect();
+        included.sort_unstable();
+        assert_eq!(
+            included,
+            vec!["github__create_pull_request", "jira__search", "slack__post_message"]
+        );
+        assert_eq!(budgeted.omitted, vec!["github__list_issues", "sentry__get_issue"]);
+    }
+}
@@ -46,12 +46,20 @@ use tokio::task::JoinSet;
 use tracing::info;
 use tracing::warn;
 
+use crate::child_registry;
+use crate::child_registry::ChildKind;
+use crate::child_registry::ChildRegistration;
 use crate::config_types::McpServerConfig;
 use crate::config_types::McpDispatchMode;
 use crate::config_types::McpServerSchedulingToml;
 use crate::config_types::McpServerTransportConfig;
 use crate::config_types::McpToolSchedulingOverrideToml;
-use code_mcp_call_limiter::{McpCallLimiter, acquire_and_schedule};
+use crate::config_types::SpawnedEnvPolicy;
+use crate::exec_env::mcp_server_env;
+use code_mcp_call_limiter::CircuitBreakerConfig;
+use code_mcp_call_limiter::McpCallLimiter;
+use code_mcp_call_limiter::McpServerLimits;
+use code_mcp_call_limiter::acquire_and_schedule;
 use crate::protocol::{AskForApproval, Event, EventMsg, McpServerFailure, McpServerFailurePhase};
 
 /// Delimiter used to separate the server name from the tool name in a fully
@@ -132,9 +140,52 @@ fn resolve_streamable_http_bearer_token(
 /// Default timeout for initializing MCP server & initially listing tools.
 const DEFAULT_STARTUP_TIMEOUT: Duration = Duration::from_secs(10);
 
+/// Cooldown before a trial call when `circuit_breaker_cooldown_sec` is unset.
+const DEFAULT_CIRCUIT_BREAKER_COOLDOWN: Duration = Duration::from_secs(30);
+
 /// Map that holds startup or tool-list errors for MCP servers.
 pub type ClientStartErrors = HashMap<String, McpServerFailure>;
 
+/// Servers touched by [`McpConnectionManager::reload_servers`], sorted by name.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct McpReloadSummary {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub restarted: Vec<String>,
+    /// Server name and the error it failed to start with.
+    pub failed: Vec<(String, String)>,
+}
+
+impl McpReloadSummary {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.restarted.is_empty()
+            && self.failed.is_empty()
+    }
+}
+
+fn server_call_limiter(scheduling: &McpServerSchedulingToml) -> Arc<McpCallLimiter> {
+    let circuit_breaker = scheduling
+        .circuit_breaker_failures
+        .map(|failure_threshold| CircuitBreakerConfig {
+            failure_threshold,
+            cooldown: scheduling
+                .circuit_breaker_cooldown_sec
+                .unwrap_or(DEFAULT_CIRCUIT_BREAKER_COOLDOWN),
+        });
+    McpCallLimiter::new_for_server(
+        scheduling.max_concurrent,
+        scheduling.min_interval_sec,
+        scheduling.queue_timeout_sec,
+        scheduling.max_queue_depth,
+        McpServerLimits {
+            max_calls_per_minute: scheduling.max_calls_per_minute,
+            circuit_breaker,
+        },
+    )
+}
+
 fn qualify_tools(tools: Vec<ToolInfo>) -> HashMap<String, ToolInfo> {
     let mut used_names = HashSet::new();
     let mut seen_raw_names = HashSet::new();
@@ -278,6 +329,9 @@ struct ManagedClient {
     client: McpClientAdapter,
     startup_timeout: Duration,
     tool_timeout: Option<Duration>,
+    /// Keeps a stdio server's process in the child registry while any clone
+    /// of this client is alive.
+    _registration: Option<Arc<ChildRegistration>>,
 }
 
 #[derive(Clone)]
@@ -323,19 +377,40 @@ impl McpClientAdapter {
             bearer_token,
             http_headers,
             env_http_headers,
-            code_home: _code_home,
-            server_name: _server_name,
-            oauth_store_mode: _oauth_store_mode,
+            code_home,
+            server_name,
+            oauth_store_mode,
             params,
             startup_timeout,
             send_elicitation,
         } = args;
+        // Without a configured token, use the one stored by an OAuth login.
+        let bearer_token = match bearer_token {
+            Some(token) => Some(token),
+            None => code_rmcp_client::load_oauth_access_token(
+                code_rmcp_client::OAuthAccessTokenArgs {
+                    code_home: &code_home,
+                    server_name,
+                    url: &url,
+                    store_mode: oauth_store_mode,
+                    http_client_builder: crate::http_client::apply_outbound_network_settings(
+                        reqwest::Client::builder(),
+                    ),
+                },
+            )
+            .await
+            .unwrap_or_else(|err| {
+                warn!("failed to read stored OAuth tokens for MCP server `{server_name}`: {err:#}");
+                None
+            }),
+        };
         let client = Arc::new(RmcpClient::new_streamable_http_client(
             url,
             bearer_token,
             None,
             http_headers,
             env_http_headers,
+            crate::http_client::apply_outbound_network_settings(reqwest::Client::builder()),
         )?);
         client
             .initialize(params, Some(startup_timeout), send_elicitation)
@@ -343,6 +418,13 @@ impl McpClientAdapter {
         Ok(McpClientAdapter::Rmcp(client))
     }
 
+    fn register_process(&self, server_name: &str) -> Option<Arc<ChildRegistration>> {
+        let pid = match self {
+            McpClientAdapter::Rmcp(client) => client.process_id(),
+        };
+        child_registry::register(ChildKind::McpServer, pid, server_name).map(Arc::new)
+    }
+
     async fn list_tools(
         &self,
         params: Option<mcp_types::ListToolsRequestParams>,
@@ -408,9 +490,14 @@ pub struct McpConnectionManager {
     /// Directory containing all Code state (used for MCP OAuth token storage).
     code_home: PathBuf,
     mcp_oauth_credentials_store_mode: OAuthCredentialsStoreMode,
+    /// Decides which parent environment variables stdio servers inherit.
+    spawned_env: SpawnedEnvPolicy,
     tx_event: Sender<Event>,
     elicitation_requests: ElicitationRequestManager,
     server_transports: StdRwLock<HashMap<String, McpServerTransportConfig>>,
+    /// Configuration each server was last started from, so a reload can tell
+    /// which servers changed.
+    server_configs: StdRwLock<HashMap<String, McpServerConfig>>,
     server_scheduling: StdRwLock<HashMap<String, McpServerSchedulingToml>>,
     tool_scheduling: StdRwLock<HashMap<(String, String), McpToolSchedulingOverrideToml>>,
     server_limiters: StdRwLock<HashMap<String, Arc<McpCallLimiter>>>,
@@ -427,6 +514,8 @@ pub struct McpConnectionManager {
     excluded_tools: StdRwLock<HashSet<(String, String)>>,
     server_names: StdRwLock<Vec<String>>,
     failures: StdRwLock<HashMap<String, McpServerFailure>>,
+    /// Servers waiting on an OAuth device login.
+    device_logins: StdMutex<HashSet<String>>,
 }
 
 impl Default for McpConnectionManager {
@@ -435,9 +524,11 @@ impl Default for McpConnectionManager {
         Self {
             code_home: PathBuf::new(),
             mcp_oauth_credentials_store_mode: OAuthCredentialsStoreMode::default(),
+            spawned_env: SpawnedEnvPolicy::default(),
             tx_event,
             elicitation_requests: ElicitationRequestManager::default(),
             server_transports: StdRwLock::new(HashMap::new()),
+            server_configs: StdRwLock::new(HashMap::new()),
             server_scheduling: StdRwLock::new(HashMap::new()),
             tool_scheduling: StdRwLock::new(HashMap::new()),
             server_limiters: StdRwLock::new(HashMap::new()),
@@ -447,6 +538,7 @@ impl Default for McpConnectionManager {
             excluded_tools: StdRwLock::new(HashSet::new()),
             server_names: StdRwLock::new(Vec::new()),
             failures: StdRwLock::new(HashMap::new()),
+            device_logins: StdMutex::new(HashSet::new()),
         }
     }
 }
@@ -467,6 +559,7 @@ impl McpConnectionManager {
         excluded_tools: HashSet<(String, String)>,
         tx_event: Sender<Event>,
         approval_policy: AskForApproval,
+        spawned_env: SpawnedEnvPolicy,
     ) -> Result<(Self, ClientStartErrors)> {
         // Early exit if no servers are configured.
         if mcp_servers.is_empty() {
@@ -475,9 +568,11 @@ impl McpConnectionManager {
                 Self {
                     code_home,
                     mcp_oauth_credentials_store_mode,
+                    spawned_env,
                     tx_event,
                     elicitation_requests,
                     server_transports: StdRwLock::new(HashMap::new()),
+                    server_configs: StdRwLock::new(HashMap::new()),
                     server_scheduling: StdRwLock::new(HashMap::new()),
                     tool_scheduling: StdRwLock::new(HashMap::new()),
                     server_limiters: StdRwLock::new(HashMap::new()),
@@ -487,6 +582,7 @@ impl McpConnectionManager {
                     excluded_tools: StdRwLock::new(excluded_tools),
                     server_names: StdRwLock::new(Vec::new()),
                     failures: StdRwLock::new(HashMap::new()),
+                    device_logins: StdMutex::new(HashSet::new()),
                 },
                 ClientStartErrors::default(),
             ));
@@ -497,6 +593,7 @@ impl McpConnectionManager {
         let mut errors = ClientStartErrors::new();
         let elicitation_requests = ElicitationRequestManager::new(approval_policy);
         let mut server_transports = HashMap::with_capacity(mcp_servers.len());
+        let mut server_configs = HashMap::with_capacity(mcp_servers.len());
         let mut server_scheduling: HashMap<String, McpServerSchedulingToml> =
             HashMap::with_capacity(mcp_servers.len());
         let mut tool_scheduling: HashMap<(String, String), McpToolSchedulingOverrideToml> =
@@ -519,16 +616,9 @@ impl McpConnectionManager {
             }
 
             server_transports.insert(server_name.clone(), cfg.transport.clone());
+            server_configs.insert(server_name.clone(), cfg.clone());
             server_scheduling.insert(server_name.clone(), cfg.scheduling.clone());
-            server_limiters.insert(
-                server_name.clone(),
-                McpCallLimiter::new(
-                    cfg.scheduling.max_concurrent,
-                    cfg.scheduling.min_interval_sec,
-                    cfg.scheduling.queue_timeout_sec,
-                    cfg.scheduling.max_queue_depth,
-                ),
-            );
+            server_limiters.insert(server_name.clone(), server_call_limiter(&cfg.scheduling));
             for (tool_name, override_cfg) in &cfg.tool_scheduling {
                 if override_cfg.is_empty() {
                     continue;
@@ -554,6 +644,7 @@ impl McpConnectionManager {
             let oauth_store_mode = mcp_oauth_credentials_store_mode;
             let tx_event_for_server = tx_event.clone();
             let elicitation_requests_for_server = elicitation_requests.clone();
+            let spawned_env_for_server = spawned_env.clone();
 
             join_set.spawn(async move {
                 let McpServerConfig { transport, .. } = cfg;
@@ -592,7 +683,7 @@ impl McpConnectionManager {
                         McpClientAdapter::new_stdio_client(
                             command_os,
                             args_os,
-                            env,
+                            mcp_server_env(&spawned_env_for_server, env),
                             params.clone(),
                             startup_timeout,
                             send_elicitation,
@@ -665,12 +756,14 @@ impl McpConnectionManager {
 
             match client_res {
                 Ok((client, startup_timeout)) => {
+                    let registration = client.register_process(&server_name);
                     clients.insert(
                         server_name,
                         ManagedClient {
                             client,
                             startup_timeout,
                             tool_timeout,
+                            _registration: registration,
                         },
                     );
                 }
@@ -699,9 +792,11 @@ impl McpConnectionManager {
             Self {
             code_home,
             mcp_oauth_credentials_store_mode,
+            spawned_env,
             tx_event,
             elicitation_requests,
             server_transports: StdRwLock::new(server_transports),
+            server_configs: StdRwLock::new(server_configs),
             server_scheduling: StdRwLock::new(server_scheduling),
             tool_scheduling: StdRwLock::new(tool_scheduling),
             server_limiters: StdRwLock::new(server_limiters),
@@ -711,6 +806,7 @@ impl McpConnectionManager {
             excluded_tools: StdRwLock::new(excluded_tools),
             server_names: StdRwLock::new(server_names),
             failures: StdRwLock::new(failures),
+            device_logins: StdMutex::new(HashSet::new()),
         },
             errors,
         ))
@@ -842,6 +938,30 @@ impl McpConnectionManager {
         }
     }
 
+    fn server_configs_read(
+        &self,
+    ) -> std::sync::RwLockReadGuard<'_, HashMap<String, McpServerConfig>> {
+        match self.server_configs.read() {
+            Ok(guard) => guard,
+            Err(poisoned) => {
+                warn!("MCP server configs lock poisoned; recovering inner state");
+                poisoned.into_inner()
+            }
+        }
+    }
+
+    fn server_configs_write(
+        &self,
+    ) -> std::sync::RwLockWriteGuard<'_, HashMap<String, McpServerConfig>> {
+        match self.server_configs.write() {
+            Ok(guard) => guard,
+            Err(poisoned) => {
+                warn!("MCP server configs lock poisoned; recovering inner state");
+                poisoned.into_inner()
+            }
+        }
+    }
+
     fn server_names_read(&self) -> std::sync::RwLockReadGuard<'_, Vec<String>> {
         match self.server_names.read() {
             Ok(guard) => guard,
@@ -928,6 +1048,48 @@ impl McpConnectionManager {
         }
     }
 
+    /// HTTP servers that failed to start and have no configured credentials,
+    /// so an OAuth device login may get them going. Servers already waiting
+    /// on a login are skipped; the rest are marked as waiting until
+    /// [`Self::finish_device_login`].
+    pub(crate) fn begin_device_logins(&self) -> Vec<(String, McpServerConfig)> {
+        let failed: Vec<String> = self
+            .failures_read()
+            .iter()
+            .filter(|(_, failure)| failure.phase == McpServerFailurePhase::Start)
+            .map(|(name, _)| name.clone())
+            .collect();
+        let configs = self.server_configs_read();
+        let mut in_flight = self
+            .device_logins
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let mut candidates: Vec<(String, McpServerConfig)> = failed
+            .into_iter()
+            .filter_map(|name| {
+                let cfg = configs.get(&name)?;
+                let McpServerTransportConfig::StreamableHttp {
+                    bearer_token: None,
+                    bearer_token_env_var: None,
+                    ..
+                } = &cfg.transport
+                else {
+                    return None;
+                };
+                in_flight.insert(name.clone()).then(|| (name, cfg.clone()))
+            })
+            .collect();
+        candidates.sort_by(|a, b| a.0.cmp(&b.0));
+        candidates
+    }
+
+    pub(crate) fn finish_device_login(&self, server_name: &str) {
+        self.device_logins
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .remove(server_name);
+    }
+
     /// Returns a single map that contains **all** tools. Each key is the
     /// fully-qualified name for the tool.
     pub fn list_all_tools(&self) -> HashMap<String, Tool> {
@@ -1191,8 +1353,20 @@ impl McpConnectionManager {
         disabled_by_server
     }
 
+    /// Start-up failures, plus running servers whose circuit breaker is open.
     pub fn list_server_failures(&self) -> HashMap<String, McpServerFailure> {
-        self.failures_read().clone()
+        let mut failures = self.failures_read().clone();
+        for (server_name, limiter) in self.server_limiters_read().iter() {
+            if let Some(status) = limiter.circuit_status() {
+                failures
+                    .entry(server_name.clone())
+                    .or_insert_with(|| McpServerFailure {
+                        phase: McpServerFailurePhase::CircuitOpen,
+                        message: status.describe(),
+                    });
+            }
+        }
+        failures
     }
 
     pub async fn list_auth_statuses(&self) -> HashMap<String, McpAuthStatus> {
@@ -1275,12 +1449,7 @@ impl McpConnectionManager {
             scheduling.insert(server_name.to_owned(), cfg.scheduling.clone());
         }
         {
-            let limiter = McpCallLimiter::new(
-                cfg.scheduling.max_concurrent,
-                cfg.scheduling.min_interval_sec,
-                cfg.scheduling.queue_timeout_sec,
-                cfg.scheduling.max_queue_depth,
-            );
+            let limiter = server_call_limiter(&cfg.scheduling);
             let mut limiters = self.server_limiters_write();
             limiters.insert(server_name.to_owned(), limiter);
         }
@@ -1330,6 +1499,10 @@ impl McpConnectionManager {
             let mut transports = self.server_transports_write();
             transports.insert(server_name.to_owned(), transport.clone());
         }
+        {
+            let mut configs = self.server_configs_write();
+            configs.insert(server_name.to_owned(), cfg.clone());
+        }
 
         let code_home = self.code_home.clone();
         let oauth_store_mode = self.mcp_oauth_credentials_store_mode;
@@ -1344,7 +1517,7 @@ impl McpConnectionManager {
                 McpClientAdapter::new_stdio_client(
                     command_os,
                     args_os,
-                    env,
+                    mcp_server_env(&self.spawned_env, env),
                     params,
                     startup_timeout,
                     send_elicitation,
@@ -1383,10 +1556,12 @@ impl McpConnectionManager {
             }
         };
 
+        let registration = client.register_process(server_name);
         let managed = ManagedClient {
             client,
             startup_timeout,
             tool_timeout,
+            _registration: registration,
         };
 
         let inserted = {
@@ -1445,15 +1620,7 @@ impl McpConnectionManager {
             .server_limiters_read()
             .get(server)
             .cloned()
-            .unwrap_or_else(|| {
-                let default = McpServerSchedulingToml::default();
-                McpCallLimiter::new(
-                    default.max_concurrent,
-                    default.min_interval_sec,
-                    default.queue_timeout_sec,
-                    default.max_queue_depth,
-                )
-            });
+            .unwrap_or_else(|| server_call_limiter(&McpServerSchedulingToml::default()));
         let tool_limiter = self
             .tool_limiters_read()
             .get(&(server.to_owned(), tool.to_owned()))
@@ -1468,13 +1635,14 @@ impl McpConnectionManager {
             (managed.client.clone(), timeout)
         };
 
-        let (_server_guard, _tool_guard) =
+        let (mut server_guard, _tool_guard) =
             acquire_and_schedule(&server_limiter, tool_limiter.as_ref()).await?;
 
-        client
-            .call_tool(tool.to_owned(), arguments, timeout)
-            .await
-            .with_context(|| format!("tool call failed for `{server}/{tool}`"))
+        // Only transport failures and timeouts count against the circuit
+        // breaker; a tool reporting `is_error` still got an answer.
+        let result = client.call_tool(tool.to_owned(), arguments, timeout).await;
+        server_guard.record_outcome(result.is_ok());
+        result.with_context(|| format!("tool call failed for `{server}/{tool}`"))
     }
 
     pub fn server_dispatch_mode(&self, server: &str) -> McpDispatchMode {
@@ -1487,15 +1655,8 @@ impl McpConnectionManager {
     pub fn set_server_scheduling(&self, server: &str, scheduling: McpServerSchedulingToml) {
         self.server_scheduling_write()
             .insert(server.to_owned(), scheduling.clone());
-        self.server_limiters_write().insert(
-            server.to_owned(),
-            McpCallLimiter::new(
-                scheduling.max_concurrent,
-                scheduling.min_interval_sec,
-                scheduling.queue_timeout_sec,
-                scheduling.max_queue_depth,
-            ),
-        );
+        self.server_limiters_write()
+            .insert(server.to_owned(), server_call_limiter(&scheduling));
 
         // Keep per-tool limiters that inherit max_concurrent in sync with the
         // server default when the server scheduling changes.
@@ -1570,6 +1731,103 @@ impl McpConnectionManager {
         }
     }
 
+    /// Shut a server down and forget its configuration, scheduling and
+    /// tools. Returns `false` when no server by that name was known.
+    pub async fn stop_server(&self, server_name: &str) -> bool {
+        let managed = self.clients.write().await.remove(server_name);
+        let known = self.server_configs_write().remove(server_name).is_some();
+        self.server_transports_write().remove(server_name);
+        self.server_scheduling_write().remove(server_name);
+        self.server_limiters_write().remove(server_name);
+        self.tool_scheduling_write()
+            .retain(|(server, _), _| server != server_name);
+        self.tool_limiters_write()
+            .retain(|(server, _), _| server != server_name);
+        self.excluded_tools_write()
+            .retain(|(server, _)| server != server_name);
+        self.server_names_write().retain(|name| name != server_name);
+        self.failures_write().remove(server_name);
+        let running = managed.is_some();
+        if let Some(managed) = managed {
+            managed.shutdown().await;
+        }
+        self.refresh_tools().await;
+        running || known
+    }
+
+    /// Bring the running servers in line with `servers`, the `mcp_servers`
+    /// map of a freshly loaded config: servers no longer configured are shut
+    /// down, servers whose configuration changed are restarted, and new
+    /// servers (or ones that failed to start) are started when `eager`
+    /// accepts their name. Apps connector servers are not part of the config
+    /// and are left alone.
+    pub async fn reload_servers(
+        &self,
+        servers: HashMap<String, McpServerConfig>,
+        eager: impl Fn(&str) -> bool,
+    ) -> McpReloadSummary {
+        let mut summary = McpReloadSummary::default();
+        let previous = self.server_configs_read().clone();
+        let running: HashSet<String> = self.clients.read().await.keys().cloned().collect();
+
+        let mut stale: Vec<&String> = previous
+            .keys()
+            .filter(|name| {
+                !name.starts_with(crate::apps_sources::CODEX_APPS_SERVER_PREFIX)
+                    && !servers.contains_key(name.as_str())
+            })
+            .collect();
+        stale.sort();
+        for name in stale {
+            self.stop_server(name).await;
+            summary.removed.push(name.clone());
+        }
+
+        let mut configured: Vec<(&String, &McpServerConfig)> = servers.iter().collect();
+        configured.sort_by(|a, b| a.0.cmp(b.0));
+        let mut start_failures = Vec::new();
+        for (name, cfg) in configured {
+            let is_running = running.contains(name);
+            if is_running && previous.get(name) == Some(cfg) {
+                continue;
+            }
+            if !is_running && !eager(name) {
+                continue;
+            }
+            if is_running {
+                self.stop_server(name).await;
+            }
+            self.excluded_tools_write().extend(
+                cfg.disabled_tools
+                    .iter()
+                    .map(|tool| (name.clone(), tool.clone())),
+            );
+            match self.ensure_server_started(name, cfg).await {
+                Ok(_) if is_running => summary.restarted.push(name.clone()),
+                Ok(_) => summary.added.push(name.clone()),
+                Err(err) => {
+                    summary.failed.push((name.clone(), format!("{err:#}")));
+                    start_failures.push((name.clone(), format!("server '{name}': {err:#}")));
+                }
+            }
+        }
+
+        self.refresh_tools().await;
+        // Refreshing rebuilds the failure map from tool listing; keep the
+        // servers that did not start visible in it too.
+        let mut failures = self.failures_write();
+        for (name, message) in start_failures {
+            failures.insert(
+                name,
+                McpServerFailure {
+                    phase: McpServerFailurePhase::Start,
+                    message,
+                },
+            );
+        }
+        summary
+    }
+
     pub async fn refresh_tools(&self) {
         let clients_snapshot = {
             let clients = self.clients.read().await;
@@ -1820,6 +2078,7 @@ mod tests {
             HashSet::new(),
             tx_event,
             AskForApproval::OnRequest,
+            SpawnedEnvPolicy::default(),
         )
             .await
             .expect("manager creation should succeed even when servers fail");
@@ -1835,4 +2094,98 @@ mod tests {
             "error should include the missing command, got: {msg}"
         );
     }
+
+    #[tokio::test]
+    async fn device_logins_cover_failed_http_servers_once() {
+        let server = |transport| McpServerConfig {
+            transport,
+            startup_timeout_sec: Some(Duration::from_secs(2)),
+            tool_timeout_sec: None,
+            disabled_tools: Vec::new(),
+            scheduling: crate::config_types::McpServerSchedulingToml::default(),
+            tool_scheduling: std::collections::BTreeMap::new(),
+        };
+        let http = |bearer_token: Option<&str>| McpServerTransportConfig::StreamableHttp {
+            url: "http://127.0.0.1:1/mcp".to_string(),
+            bearer_token: bearer_token.map(str::to_string),
+            oauth_resource: None,
+            bearer_token_env_var: None,
+            http_headers: None,
+            env_http_headers: None,
+        };
+        let servers = HashMap::from([
+            ("docs".to_string(), server(http(None))),
+            ("tokened".to_string(), server(http(Some("secret")))),
+            (
+                "local".to_string(),
+                server(McpServerTransportConfig::Stdio {
+                    command: "nonexistent-cmd".to_string(),
+                    args: Vec::new(),
+                    env: None,
+                }),
+            ),
+        ]);
+
+        let code_home = tempfile::tempdir().expect("code home");
+        let (tx_event, _rx_event) = async_channel::unbounded();
+        let (manager, errors) = McpConnectionManager::new(
+            code_home.path().to_path_buf(),
+            OAuthCredentialsStoreMode::File,
+            servers,
+            HashSet::new(),
+            tx_event,
+            AskForApproval::OnRequest,
+            SpawnedEnvPolicy::default(),
+        )
+        .await
+        .expect("manager creation should succeed even when servers fail");
+        assert_eq!(errors.len(), 3);
+
+        let names = |candidates: Vec<(String, McpServerConfig)>| {
+            candidates.into_iter().map(|(name, _)| name).collect::<Vec<_>>()
+        };
+        assert_eq!(names(manager.begin_device_logins()), vec!["docs"]);
+        assert!(manager.begin_device_logins().is_empty());
+        manager.finish_device_login("docs");
+        assert_eq!(names(manager.begin_device_logins()), vec!["docs"]);
+    }
+
+    #[tokio::test]
+    async fn open_circuit_is_reported_as_a_server_failure() {
+        let code_home = tempfile::tempdir().expect("code home");
+        let (tx_event, _rx_event) = async_channel::unbounded();
+        let (manager, _errors) = McpConnectionManager::new(
+            code_home.path().to_path_buf(),
+            OAuthCredentialsStoreMode::File,
+            HashMap::new(),
+            HashSet::new(),
+            tx_event,
+            AskForApproval::OnRequest,
+            SpawnedEnvPolicy::default(),
+        )
+        .await
+        .expect("manager");
+        manager.set_server_scheduling(
+            "docs",
+            McpServerSchedulingToml {
+                circuit_breaker_failures: Some(1),
+                ..McpServerSchedulingToml::default()
+            },
+        );
+        assert!(manager.list_server_failures().is_empty());
+
+        let limiter = manager
+            .server_limiters_read()
+            .get("docs")
+            .cloned()
+            .expect("limiter");
+        let (mut guard, _tool) = acquire_and_schedule(&limiter, None).await.expect("admitted");
+        guard.record_outcome(false);
+        drop(guard);
+
+        let failures = manager.list_server_failures();
+        let failure = failures.get("docs").expect("circuit open");
+        assert_eq!(failure.phase, McpServerFailurePhase::CircuitOpen);
+        assert!(failure.message.starts_with("circuit open after 1 consecutive failed calls"));
+    }
 }
@@ -86,6 +86,7 @@ pub fn format_failure_summary(failure: &McpServerFailure) -> String {
     match failure.phase {
         McpServerFailurePhase::Start => format!("Failed to start: {message}"),
         McpServerFailurePhase::ListTools => format!("Failed to list tools: {message}"),
+        McpServerFailurePhase::CircuitOpen => format!("Calls paused: {message}"),
     }
 }
 
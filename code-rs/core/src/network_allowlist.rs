@@ -0,0 +1,158 @@
+//! `network_allowlist` entries of a `workspace-write` sandbox policy.
+//!
+//! Seatbelt and seccomp cannot filter connections by hostname, so the
+//! allowlist is enforced by the managed network proxy: its entries become the
+//! proxy's allowed domains, and the platform sandbox only lets commands
+//! connect to the proxy's loopback ports.
+
+use std::net::IpAddr;
+
+use crate::config::NetworkProxySettingsToml;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum NetworkAllowEntry {
+    /// A hostname, optionally with a leading `*.` or `**.` wildcard.
+    Host(String),
+    /// An address range; a bare IP address is a range of one.
+    Cidr { addr: IpAddr, prefix: u8 },
+}
+
+impl NetworkAllowEntry {
+    pub(crate) fn parse(entry: &str) -> Result<Self, String> {
+        let entry = entry.trim();
+        if entry.is_empty() {
+            return Err("empty entry".to_owned());
+        }
+        if let Some((addr, prefix)) = entry.split_once('/') {
+            let addr: IpAddr = addr
+                .parse()
+                .map_err(|_| format!("`{entry}` is not a valid CIDR range"))?;
+            let max_prefix = if addr.is_ipv4() { 32 } else { 128 };
+            let prefix = prefix
+                .parse::<u8>()
+                .ok()
+                .filter(|prefix| *prefix <= max_prefix)
+                .ok_or_else(|| format!("`{entry}` has an invalid prefix length"))?;
+            return Ok(Self::Cidr { addr, prefix });
+        }
+        if let Ok(addr) = entry.trim_start_matches('[').trim_end_matches(']').parse::<IpAddr>() {
+            let prefix = if addr.is_ipv4() { 32 } else { 128 };
+            return Ok(Self::Cidr { addr, prefix });
+        }
+
+        let host = entry.trim_end_matches('.').to_ascii_lowercase();
+        let name = host
+            .strip_prefix("**.")
+            .or_else(|| host.strip_prefix("*."))
+            .unwrap_or(&host);
+        let valid = !name.is_empty()
+            && name.split('.').all(|label| {
+                !label.is_empty()
+                    && !label.starts_with('-')
+                    && !label.ends_with('-')
+                    && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            });
+        if !valid {
+            return Err(format!(
+                "`{entry}` is not a hostname or CIDR range (ports and URLs are not supported)"
+            ));
+        }
+        Ok(Self::Host(host))
+    }
+
+    /// The managed proxy's `allowed_domains` pattern. A bare hostname also
+    /// admits its subdomains, so `crates.io` covers `index.crates.io`.
+    fn proxy_pattern(&self) -> String {
+        match self {
+            Self::Host(host) if host.starts_with('*') => host.clone(),
+            Self::Host(host) => format!("**.{host}"),
+            Self::Cidr { addr, prefix } => format!("{addr}/{prefix}"),
+        }
+    }
+}
+
+/// Rejects allowlist entries that are neither hostnames nor CIDR ranges.
+pub(crate) fn validate_network_allowlist(entries: &[String]) -> std::io::Result<()> {
+    for entry in entries {
+        NetworkAllowEntry::parse(entry).map_err(|err| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("sandbox_workspace_write.network_allowlist: {err}"),
+            )
+        })?;
+    }
+    Ok(())
+}
+
+/// The `[network]` settings the managed proxy starts with. A non-empty
+/// allowlist turns the proxy on and adds its entries to `allowed_domains`.
+pub(crate) fn effective_network_settings(
+    network: Option<&NetworkProxySettingsToml>,
+    allowlist: &[String],
+) -> Option<NetworkProxySettingsToml> {
+    if allowlist.is_empty() {
+        return network.cloned();
+    }
+    let mut settings = network.cloned().unwrap_or_default();
+    settings.enabled = true;
+    for entry in allowlist {
+        let Ok(entry) = NetworkAllowEntry::parse(entry) else {
+            continue;
+        };
+        let pattern = entry.proxy_pattern();
+        if !settings.allowed_domains.contains(&pattern) {
+            settings.allowed_domains.push(pattern);
+        }
+    }
+    Some(settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_hosts_and_ranges() {
+        assert_eq!(
+            NetworkAllowEntry::parse("Crates.IO."),
+            Ok(NetworkAllowEntry::Host("crates.io".to_owned()))
+        );
+        assert_eq!(
+            NetworkAllowEntry::parse("10.20.0.0/16"),
+            Ok(NetworkAllowEntry::Cidr {
+                addr: "10.20.0.0".parse().unwrap(),
+                prefix: 16,
+            })
+        );
+        assert_eq!(
+            NetworkAllowEntry::parse("::1"),
+            Ok(NetworkAllowEntry::Cidr {
+                addr: "::1".parse().unwrap(),
+                prefix: 128,
+            })
+        );
+        assert!(NetworkAllowEntry::parse("10.0.0.0/33").is_err());
+        assert!(NetworkAllowEntry::parse("https://registry.npmjs.org").is_err());
+        assert!(NetworkAllowEntry::parse("crates.io:443").is_err());
+    }
+
+    #[test]
+    fn allowlist_enables_the_proxy_with_its_entries() {
+        let settings = effective_network_settings(
+            None,
+            &[
+                "crates.io".to_owned(),
+                "*.npmjs.org".to_owned(),
+                "192.168.1.0/24".to_owned(),
+            ],
+        )
+        .expect("settings");
+        assert!(settings.enabled);
+        assert_eq!(
+            settings.allowed_domains,
+            vec!["**.crates.io", "*.npmjs.org", "192.168.1.0/24"]
+        );
+        assert_eq!(effective_network_settings(None, &[]), None);
+    }
+}
@@ -98,13 +98,10 @@ mod imp {
             attempts.remove(attempt_id);
         }
 
-        async fn resolve_attempt_for_request(
-            &self,
-            request: &NetworkPolicyRequest,
-        ) -> Option<Arc<NetworkApprovalAttempt>> {
+        async fn resolve_attempt(&self, attempt_id: Option<&str>) -> Option<Arc<NetworkApprovalAttempt>> {
             let attempts = self.attempts.lock().await;
 
-            if let Some(attempt_id) = request.attempt_id.as_deref() {
+            if let Some(attempt_id) = attempt_id {
                 return attempts.get(attempt_id).cloned();
             }
 
@@ -115,6 +112,20 @@ mod imp {
             None
         }
 
+        async fn resolve_attempt_for_request(
+            &self,
+            request: &NetworkPolicyRequest,
+        ) -> Option<Arc<NetworkApprovalAttempt>> {
+            self.resolve_attempt(request.attempt_id.as_deref()).await
+        }
+
+        /// Returns the `(turn_id, call_id)` of the tool call that owns a
+        /// proxied connection.
+        pub(crate) async fn attempt_origin(&self, attempt_id: Option<&str>) -> Option<(String, String)> {
+            let attempt = self.resolve_attempt(attempt_id).await?;
+            Some((attempt.turn_id.clone(), attempt.call_id.clone()))
+        }
+
         pub(crate) async fn handle_policy_request(
             &self,
             session: &Session,
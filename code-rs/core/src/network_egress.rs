@@ -0,0 +1,167 @@
+//! Egress log for tool-call processes routed through the managed network
+//! proxy.
+//!
+//! Every connection the proxy lets through is appended to
+//! `network-egress.jsonl` in the debug log directory and counted against the
+//! turn that launched the process, so the turn can end with a summary of the
+//! hosts it talked to.
+
+use code_network_proxy::AllowedRequest;
+use code_network_proxy::AllowedRequestObserver;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use tokio::io::AsyncWriteExt;
+
+use crate::network_approval::NetworkApprovalService;
+
+const EGRESS_LOG_FILENAME: &str = "network-egress.jsonl";
+
+/// Most destinations named in a turn summary before the rest are elided.
+const MAX_SUMMARY_DESTINATIONS: usize = 8;
+
+#[derive(Serialize)]
+struct EgressLogEntry<'a> {
+    timestamp: i64,
+    turn_id: Option<&'a str>,
+    call_id: Option<&'a str>,
+    host: &'a str,
+    port: u16,
+    protocol: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    method: Option<&'a str>,
+}
+
+#[derive(Debug)]
+pub(crate) struct NetworkEgressRecorder {
+    log_path: PathBuf,
+    turns: Mutex<HashMap<String, BTreeMap<(String, u16), u64>>>,
+}
+
+impl NetworkEgressRecorder {
+    pub(crate) fn new(log_dir: PathBuf) -> Self {
+        if let Err(err) = std::fs::create_dir_all(&log_dir) {
+            tracing::warn!("failed to create network egress log dir {}: {err}", log_dir.display());
+        }
+        Self {
+            log_path: log_dir.join(EGRESS_LOG_FILENAME),
+            turns: Mutex::new(HashMap::new()),
+        }
+    }
+
+    async fn record(&self, request: &AllowedRequest, origin: Option<(String, String)>) {
+        let (turn_id, call_id) = match &origin {
+            Some((turn_id, call_id)) => (Some(turn_id.as_str()), Some(call_id.as_str())),
+            None => (None, None),
+        };
+        if let Some(turn_id) = turn_id
+            && let Ok(mut turns) = self.turns.lock()
+        {
+            *turns
+                .entry(turn_id.to_owned())
+                .or_default()
+                .entry((request.host.clone(), request.port))
+                .or_default() += 1;
+        }
+
+        let entry = EgressLogEntry {
+            timestamp: request.timestamp,
+            turn_id,
+            call_id,
+            host: &request.host,
+            port: request.port,
+            protocol: &request.protocol,
+            method: request.method.as_deref(),
+        };
+        let mut line = match serde_json::to_string(&entry) {
+            Ok(line) => line,
+            Err(err) => {
+                tracing::warn!("failed to serialize network egress entry: {err}");
+                return;
+            }
+        };
+        line.push('\n');
+        let file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.log_path)
+            .await;
+        let result = match file {
+            Ok(mut file) => file.write_all(line.as_bytes()).await,
+            Err(err) => Err(err),
+        };
+        if let Err(err) = result {
+            tracing::warn!("failed to append to {}: {err}", self.log_path.display());
+        }
+    }
+
+    /// Removes the destinations recorded for `turn_id` and describes them,
+    /// or returns `None` when the turn made no proxied connections.
+    pub(crate) fn take_turn_summary(&self, turn_id: &str) -> Option<String> {
+        let destinations = self.turns.lock().ok()?.remove(turn_id)?;
+        format_turn_summary(&destinations)
+    }
+}
+
+pub(crate) fn build_allowed_request_observer(
+    recorder: Arc<NetworkEgressRecorder>,
+    network_approval: Arc<NetworkApprovalService>,
+) -> Arc<dyn AllowedRequestObserver> {
+    Arc::new(move |request: AllowedRequest| {
+        let recorder = Arc::clone(&recorder);
+        let network_approval = Arc::clone(&network_approval);
+        async move {
+            let origin = network_approval
+                .attempt_origin(request.attempt_id.as_deref())
+                .await;
+            recorder.record(&request, origin).await;
+        }
+    })
+}
+
+fn format_turn_summary(destinations: &BTreeMap<(String, u16), u64>) -> Option<String> {
+    if destinations.is_empty() {
+        return None;
+    }
+    let mut parts: Vec<String> = destinations
+        .iter()
+        .take(MAX_SUMMARY_DESTINATIONS)
+        .map(|((host, port), count)| match count {
+            1 => format!("{host}:{port}"),
+            _ => format!("{host}:{port} ({count})"),
+        })
+        .collect();
+    let hidden = destinations.len().saturating_sub(MAX_SUMMARY_DESTINATIONS);
+    if hidden > 0 {
+        parts.push(format!("{hidden} more"));
+    }
+    Some(format!("Network egress this turn: {}", parts.join(", ")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn summary_counts_repeat_destinations_and_elides_the_tail() {
+        assert_eq!(format_turn_summary(&BTreeMap::new()), None);
+
+        let mut destinations = BTreeMap::new();
+        destinations.insert(("api.github.com".to_owned(), 443), 3);
+        destinations.insert(("pypi.org".to_owned(), 443), 1);
+        assert_eq!(
+            format_turn_summary(&destinations).as_deref(),
+            Some("Network egress this turn: api.github.com:443 (3), pypi.org:443")
+        );
+
+        for index in 0..MAX_SUMMARY_DESTINATIONS {
+            destinations.insert((format!("host{index}.example"), 80), 1);
+        }
+        let summary = format_turn_summary(&destinations).unwrap_or_default();
+        assert!(summary.ends_with(", 2 more"), "{summary}");
+    }
+}
@@ -17,9 +17,16 @@ mod tests;
 const SEARCH_TOOL_DESCRIPTION_TEMPLATE: &str =
     include_str!("../templates/search_tool/tool_description.md");
 pub(crate) const SEARCH_TOOL_BM25_TOOL_NAME: &str = "search_tool_bm25";
+pub(crate) const TOOLS_LIST_MORE_TOOL_NAME: &str = "tools_list_more";
 pub(crate) const READ_FILE_TOOL_NAME: &str = "read_file";
 pub(crate) const LIST_DIR_TOOL_NAME: &str = "list_dir";
 pub(crate) const GREP_FILES_TOOL_NAME: &str = "grep_files";
+pub(crate) const DOCS_SEARCH_TOOL_NAME: &str = "docs_search";
+pub(crate) const MEMORY_PUT_TOOL_NAME: &str = "memory_put";
+pub(crate) const MEMORY_SEARCH_TOOL_NAME: &str = "memory_search";
+pub(crate) const CAPABILITIES_TOOL_NAME: &str = "capabilities";
+pub(crate) const SCRATCH_TOOL_NAME: &str = "scratch";
+pub(crate) const WEB_SEARCH_TOOL_NAME: &str = "web_search";
 pub(crate) const REPL_TOOL_NAME: &str = "repl";
 pub(crate) const REPL_RESET_TOOL_NAME: &str = "repl_reset";
 
@@ -57,6 +64,7 @@ pub(crate) fn runtime_from_repl_reset_tool_name(name: &str) -> Option<crate::con
 }
 
 pub use registry::get_openai_tools;
+pub(crate) use builtin_tools::create_tools_list_more_tool;
 pub use tools_json::create_tools_json_for_responses_api;
 pub(crate) use tools_json::create_tools_json_for_chat_completions_api;
 pub(crate) use json_schema::JsonSchema;
@@ -30,12 +30,19 @@ pub(super) fn create_browser_tool(browser_enabled: bool) -> OpenAiTool {
             "history",
             "inspect",
             "inspect_selector",
+            "dom",
             "console",
-            "targets",
-            "switch_target",
+            "har",
+            "downloads",
+            "mock",
+            "mock_list",
+            "mock_remove",
+            "mock_clear",
+            "tab_list",
+            "tab_open",
+            "tab_switch",
+            "tab_close",
             "activate_target",
-            "new_tab",
-            "close_target",
             "screenshot",
             "cookies_get",
             "cookies_set",
@@ -61,7 +68,7 @@ pub(super) fn create_browser_tool(browser_enabled: bool) -> OpenAiTool {
         "url".to_owned(),
         JsonSchema::String {
             description: Some(
-                "For action=open/fetch/new_tab: URL to navigate to or retrieve (e.g., https://example.com).".to_owned(),
+                "For action=open/fetch/tab_open: URL to navigate to or retrieve (e.g., https://example.com).".to_owned(),
             ),
             allowed_values: None,
         },
@@ -235,11 +242,94 @@ pub(super) fn create_browser_tool(browser_enabled: bool) -> OpenAiTool {
             ),
         },
     );
+    properties.insert(
+        "max_depth".to_owned(),
+        JsonSchema::Number {
+            description: Some(
+                "For action=dom: deepest outline level to include (default 30).".to_owned(),
+            ),
+        },
+    );
+    properties.insert(
+        "max_chars".to_owned(),
+        JsonSchema::Number {
+            description: Some(
+                "For action=dom: size limit of the outline in characters (default 20000).".to_owned(),
+            ),
+        },
+    );
+    properties.insert(
+        "bounds".to_owned(),
+        JsonSchema::Boolean {
+            description: Some(
+                "For action=dom: include each node's bounding box as @x,y wxh in viewport CSS pixels (default true).".to_owned(),
+            ),
+        },
+    );
+    properties.insert(
+        "path".to_owned(),
+        JsonSchema::String {
+            description: Some(
+                "For action=har: where to write the HAR file (relative to the working directory). Defaults to the session scratch directory.".to_owned(),
+            ),
+            allowed_values: None,
+        },
+    );
+    properties.insert(
+        "url_pattern".to_owned(),
+        JsonSchema::String {
+            description: Some(
+                "For action=mock/mock_remove: URL pattern of the requests to stub; '*' matches any characters, '?' one character (e.g., '*/api/users*').".to_owned(),
+            ),
+            allowed_values: None,
+        },
+    );
+    properties.insert(
+        "fixture".to_owned(),
+        JsonSchema::String {
+            description: Some(
+                "For action=mock: file whose contents are the response body (relative to the working directory); the content type follows its extension.".to_owned(),
+            ),
+            allowed_values: None,
+        },
+    );
+    properties.insert(
+        "body".to_owned(),
+        JsonSchema::String {
+            description: Some(
+                "For action=mock: inline response body, used when no fixture is given.".to_owned(),
+            ),
+            allowed_values: None,
+        },
+    );
+    properties.insert(
+        "status".to_owned(),
+        JsonSchema::Number {
+            description: Some("For action=mock: HTTP status of the stubbed response (default 200).".to_owned()),
+        },
+    );
+    properties.insert(
+        "headers".to_owned(),
+        JsonSchema::Object {
+            properties: BTreeMap::new(),
+            required: None,
+            additional_properties: Some(true.into()),
+        },
+    );
+    properties.insert(
+        "http_method".to_owned(),
+        JsonSchema::String {
+            description: Some(
+                "For action=mock: only stub requests with this HTTP method (e.g., 'POST'); any method when omitted.".to_owned(),
+            ),
+            allowed_values: None,
+        },
+    );
     properties.insert(
         "target_id".to_owned(),
         JsonSchema::String {
             description: Some(
-                "For action=switch_target/activate_target/close_target: CDP targetId of the tab to control (from action=targets).".to_owned(),
+                "For action=tab_switch/tab_close/activate_target: CDP targetId of the tab to control (from action=tab_list).".to_owned(),
             ),
             allowed_values: None,
         },
@@ -248,7 +338,7 @@ pub(super) fn create_browser_tool(browser_enabled: bool) -> OpenAiTool {
         "index".to_owned(),
         JsonSchema::Number {
             description: Some(
-                "For action=switch_target/activate_target/close_target: 1-based index from action=targets output.".to_owned(),
+                "For action=tab_switch/tab_close/activate_target: 1-based index from action=tab_list output.".to_owned(),
             ),
         },
     );
@@ -349,14 +439,14 @@ pub(super) fn create_browser_tool(browser_enabled: bool) -> OpenAiTool {
         "clear".to_owned(),
         JsonSchema::Boolean {
             description: Some(
-                "For action=storage_set: if true, clears storage before setting items.".to_owned(),
+                "For action=storage_set: if true, clears storage before setting items. For action=har: if true, clears the captured traffic after exporting.".to_owned(),
             ),
         },
     );
 
     OpenAiTool::Function(ResponsesApiTool {
         name: "browser".to_owned(),
-        description: "Unified browser controller for navigation, interaction, console access, DevTools commands, and one-shot fetches. Use action=targets/switch_target to select a tab, then action=click/type/javascript/cdp for interactions.".to_owned(),
+        description: "Unified browser controller for navigation, interaction, console access, page structure (action=dom returns a compact text outline of the accessibility tree with roles, names, values and bounding boxes; prefer it over screenshots for reading a page), network capture (action=har exports a HAR file and lists failed requests), file downloads (action=downloads lists downloaded files and where they were saved), request mocking (action=mock answers requests matching url_pattern with a local fixture so front-end code can be tested against deterministic API responses; mock_list/mock_remove/mock_clear manage them), DevTools commands, and one-shot fetches. Use action=tab_list/tab_switch to pick a tab (tab_open/tab_close open and close them), then action=click/type/javascript/cdp for interactions.".to_owned(),
         strict: false,
         parameters: JsonSchema::Object {
             properties,
@@ -6,13 +6,20 @@ use super::json_schema::JsonSchema;
 use super::types::{FreeformTool, FreeformToolFormat, OpenAiTool, ResponsesApiTool};
 use super::{
     create_additional_permissions_schema,
+    CAPABILITIES_TOOL_NAME,
+    SCRATCH_TOOL_NAME,
+    DOCS_SEARCH_TOOL_NAME,
+    WEB_SEARCH_TOOL_NAME,
     GREP_FILES_TOOL_NAME,
     REPL_RESET_TOOL_NAME,
     REPL_TOOL_NAME,
     LIST_DIR_TOOL_NAME,
+    MEMORY_PUT_TOOL_NAME,
+    MEMORY_SEARCH_TOOL_NAME,
     READ_FILE_TOOL_NAME,
     SEARCH_TOOL_BM25_TOOL_NAME,
     SEARCH_TOOL_DESCRIPTION_TEMPLATE,
+    TOOLS_LIST_MORE_TOOL_NAME,
 };
 
 pub(super) fn create_shell_tool() -> OpenAiTool {
@@ -294,6 +301,242 @@ pub(super) fn create_search_tool_bm25_tool() -> OpenAiTool {
     })
 }
 
+/// Offered when `[tools.mcp_budget]` left `omitted` MCP tools out of the
+/// request.
+pub(crate) fn create_tools_list_more_tool(omitted: usize) -> OpenAiTool {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "names".to_owned(),
+        JsonSchema::Array {
+            items: Box::new(JsonSchema::String {
+                description: None,
+                allowed_values: None,
+            }),
+            description: Some("Exact MCP tool names to load.".to_owned()),
+        },
+    );
+    properties.insert(
+        "query".to_owned(),
+        JsonSchema::String {
+            description: Some(
+                "Words describing the capability you need; the best matching tools are loaded."
+                    .to_owned(),
+            ),
+            allowed_values: None,
+        },
+    );
+    properties.insert(
+        "limit".to_owned(),
+        JsonSchema::Number {
+            description: Some(
+                "Maximum number of tools to load for `query` (defaults to 8).".to_owned(),
+            ),
+        },
+    );
+
+    OpenAiTool::Function(ResponsesApiTool {
+        name: TOOLS_LIST_MORE_TOOL_NAME.to_owned(),
+        description: format!(
+            "{omitted} MCP tool(s) are not loaded in this request to save context. Call with no arguments to list them, or pass `names` or `query` to load tools; loaded tools are available from your next step."
+        ),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: None,
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+pub(super) fn create_docs_search_tool(bundles: &[crate::docs_bundles::DocsBundle]) -> OpenAiTool {
+    let mut description = "Search locally installed reference documentation. Prefer this over web search for API and library lookups; results include the source file, which read_file can open for the full page.\n\nAvailable bundles:".to_owned();
+    for bundle in bundles {
+        description.push_str("\n- ");
+        description.push_str(&bundle.name);
+        if let Some(about) = bundle.description.as_deref().map(str::trim).filter(|about| !about.is_empty()) {
+            description.push_str(": ");
+            description.push_str(about);
+        }
+    }
+
+    let properties = BTreeMap::from([
+        (
+            "query".to_owned(),
+            JsonSchema::String {
+                description: Some("Keywords to search for, e.g. an API name or error message.".to_owned()),
+                allowed_values: None,
+            },
+        ),
+        (
+            "bundle".to_owned(),
+            JsonSchema::String {
+                description: Some("Optional bundle to search. Defaults to all bundles.".to_owned()),
+                allowed_values: Some(bundles.iter().map(|bundle| bundle.name.clone()).collect()),
+            },
+        ),
+        (
+            "limit".to_owned(),
+            JsonSchema::Number {
+                description: Some("Maximum number of sections to return (defaults to 5).".to_owned()),
+            },
+        ),
+    ]);
+
+    OpenAiTool::Function(ResponsesApiTool {
+        name: DOCS_SEARCH_TOOL_NAME.to_owned(),
+        description,
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["query".to_owned()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+pub(super) fn create_memory_put_tool() -> OpenAiTool {
+    let properties = BTreeMap::from([
+        (
+            "key".to_owned(),
+            JsonSchema::String {
+                description: Some("Short, stable name for the note, e.g. `test-command`. Putting an existing key replaces that note.".to_owned()),
+                allowed_values: None,
+            },
+        ),
+        (
+            "text".to_owned(),
+            JsonSchema::String {
+                description: Some("The note. Pass an empty string to remove the note stored under `key`.".to_owned()),
+                allowed_values: None,
+            },
+        ),
+        (
+            "tags".to_owned(),
+            JsonSchema::Array {
+                items: Box::new(JsonSchema::String {
+                    description: None,
+                    allowed_values: None,
+                }),
+                description: Some("Optional topics that help later searches.".to_owned()),
+            },
+        ),
+    ]);
+
+    OpenAiTool::Function(ResponsesApiTool {
+        name: MEMORY_PUT_TOOL_NAME.to_owned(),
+        description: "Save a note about this project for future sessions: build and test commands, conventions, decisions and pitfalls worth remembering. Notes are shared with everyone working in the repository, so keep them factual and free of secrets.".to_owned(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["key".to_owned(), "text".to_owned()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+pub(super) fn create_memory_search_tool() -> OpenAiTool {
+    let properties = BTreeMap::from([
+        (
+            "query".to_owned(),
+            JsonSchema::String {
+                description: Some("A note key or keywords to look for.".to_owned()),
+                allowed_values: None,
+            },
+        ),
+        (
+            "limit".to_owned(),
+            JsonSchema::Number {
+                description: Some("Maximum number of notes to return (defaults to 5).".to_owned()),
+            },
+        ),
+    ]);
+
+    OpenAiTool::Function(ResponsesApiTool {
+        name: MEMORY_SEARCH_TOOL_NAME.to_owned(),
+        description: "Search the notes saved about this project in earlier sessions with memory_put.".to_owned(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["query".to_owned()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+pub(super) fn create_capabilities_tool() -> OpenAiTool {
+    OpenAiTool::Function(ResponsesApiTool {
+        name: CAPABILITIES_TOOL_NAME.to_owned(),
+        description: "Describe this session as JSON: the sandbox (writable roots, network), the approval policy, the tools available in this request, context and output limits, and the time left before the run deadline. Call it before planning work that depends on these instead of finding out through failed tool calls.".to_owned(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties: BTreeMap::new(),
+            required: None,
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+pub(super) fn create_scratch_tool() -> OpenAiTool {
+    let properties = BTreeMap::from([
+        (
+            "action".to_owned(),
+            JsonSchema::String {
+                description: Some("`path` returns the scratch directory, or a file path inside it when `name` is given; `list` shows the files in it; `clear` deletes them.".to_owned()),
+                allowed_values: Some(vec!["path".to_owned(), "list".to_owned(), "clear".to_owned()]),
+            },
+        ),
+        (
+            "name".to_owned(),
+            JsonSchema::String {
+                description: Some("For `path`: a relative file name such as `analysis/deps.txt`. Parent directories are created.".to_owned()),
+                allowed_values: None,
+            },
+        ),
+    ]);
+
+    OpenAiTool::Function(ResponsesApiTool {
+        name: SCRATCH_TOOL_NAME.to_owned(),
+        description: "Manage this session's scratch directory, which lives outside the workspace. Write temporary analysis files, logs and one-off scripts there instead of the repository so they do not end up in diffs or reviews. Files are removed automatically some days after the session goes idle.".to_owned(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["action".to_owned()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+/// `web_search` as a function tool, used when `[web_search]` selects a
+/// backend other than the provider's native tool.
+pub(super) fn create_web_search_function_tool() -> OpenAiTool {
+    let properties = BTreeMap::from([
+        (
+            "query".to_owned(),
+            JsonSchema::String {
+                description: Some("Search query.".to_owned()),
+                allowed_values: None,
+            },
+        ),
+        (
+            "count".to_owned(),
+            JsonSchema::Number {
+                description: Some("Number of results to return (defaults to the configured maximum).".to_owned()),
+            },
+        ),
+    ]);
+
+    OpenAiTool::Function(ResponsesApiTool {
+        name: WEB_SEARCH_TOOL_NAME.to_owned(),
+        description: "Search the web. Returns numbered results with title, URL, snippet and publication date when known. Cite results you rely on with their URL, e.g. [1](https://example.com).".to_owned(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["query".to_owned()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
 pub(super) fn create_list_mcp_resources_tool() -> OpenAiTool {
     let properties = BTreeMap::from([
         (
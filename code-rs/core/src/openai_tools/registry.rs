@@ -71,6 +71,19 @@ pub fn get_openai_tools(
     if config.search_tool {
         tools.push(builtin_tools::create_search_tool_bm25_tool());
     }
+    if !config.docs_bundles.is_empty() {
+        tools.push(builtin_tools::create_docs_search_tool(&config.docs_bundles));
+    }
+    if config.project_memory {
+        tools.push(builtin_tools::create_memory_put_tool());
+        tools.push(builtin_tools::create_memory_search_tool());
+    }
+    if config.capabilities {
+        tools.push(builtin_tools::create_capabilities_tool());
+    }
+    if config.scratch {
+        tools.push(builtin_tools::create_scratch_tool());
+    }
     if config.repl {
         tools.push(builtin_tools::create_repl_tool());
         tools.push(builtin_tools::create_repl_reset_tool());
@@ -94,6 +107,11 @@ pub fn get_openai_tools(
 
     if config.web_search_request {
         let tool = match &config.web_search_allowed_domains {
+            // A configured `[web_search]` backend runs the search locally
+            // and applies `allowed_domains` to its results itself.
+            _ if config.web_search_provider.is_some() => {
+                builtin_tools::create_web_search_function_tool()
+            }
             Some(domains) if !domains.is_empty() => OpenAiTool::WebSearch(WebSearchTool {
                 external_web_access: Some(config.web_search_external),
                 filters: Some(WebSearchFilters {
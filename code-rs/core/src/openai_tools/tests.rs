@@ -976,6 +976,7 @@
         let sandbox_policy = SandboxPolicy::WorkspaceWrite {
             writable_roots: vec!["workspace".into()],
             network_access: false,
+            network_allowlist: vec![],
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
             allow_git_writes: true,
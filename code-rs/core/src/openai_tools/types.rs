@@ -45,6 +45,18 @@ pub enum OpenAiTool {
     Freeform(FreeformTool),
 }
 
+impl OpenAiTool {
+    /// The name the model calls the tool by.
+    pub(crate) fn name(&self) -> &str {
+        match self {
+            OpenAiTool::Function(tool) => &tool.name,
+            OpenAiTool::LocalShell {} => "local_shell",
+            OpenAiTool::WebSearch(_) => "web_search",
+            OpenAiTool::Freeform(tool) => &tool.name,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, PartialEq, Default)]
 pub struct WebSearchTool {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -0,0 +1,287 @@
+//! Project-scoped notes the assistant keeps across sessions, stored in
+//! `.code/memory/notes.json` at the repository root (or the working
+//! directory outside a repository).
+//!
+//! Notes are written and looked up through the `memory_put` and
+//! `memory_search` tools when `[tools] project_memory` is enabled, and the
+//! most recent ones are added to the prompt when a session starts. Unlike
+//! the global memories under `CODE_HOME`, they travel with the project.
+
+use std::cmp::Reverse;
+use std::fs::{self, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use fs2::FileExt;
+use serde::{Deserialize, Serialize};
+
+const NOTES_VERSION: u32 = 1;
+const NOTES_FILE: &str = "notes.json";
+pub const MAX_KEY_CHARS: usize = 100;
+pub const MAX_NOTE_CHARS: usize = 4_000;
+/// Budget for the notes added to the prompt at session start.
+const MAX_CONTEXT_BYTES: usize = 6_000;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MemoryNote {
+    pub key: String,
+    pub text: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct NotesData {
+    version: u32,
+    #[serde(default)]
+    notes: Vec<MemoryNote>,
+}
+
+impl Default for NotesData {
+    fn default() -> Self {
+        Self {
+            version: NOTES_VERSION,
+            notes: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PutOutcome {
+    Created,
+    Updated,
+    Removed,
+    /// Removal of a key that was not stored.
+    Missing,
+}
+
+/// `.code/memory` for the project containing `cwd`.
+pub fn project_memory_dir(cwd: &Path) -> PathBuf {
+    crate::git_info::get_git_repo_root(cwd)
+        .unwrap_or_else(|| cwd.to_path_buf())
+        .join(".code")
+        .join("memory")
+}
+
+fn notes_path(cwd: &Path) -> PathBuf {
+    project_memory_dir(cwd).join(NOTES_FILE)
+}
+
+/// Stored notes, most recently updated first.
+pub fn load_notes(cwd: &Path) -> std::io::Result<Vec<MemoryNote>> {
+    let mut notes = match fs::read_to_string(notes_path(cwd)) {
+        Ok(contents) => serde_json::from_str::<NotesData>(&contents)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?
+            .notes,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(err) => return Err(err),
+    };
+    notes.sort_by_key(|note| Reverse(note.updated_at));
+    Ok(notes)
+}
+
+/// Creates or replaces the note stored under `key`; an empty `text` removes
+/// it. Keys compare case-insensitively.
+pub fn put_note(
+    cwd: &Path,
+    key: &str,
+    text: &str,
+    tags: Vec<String>,
+    now: DateTime<Utc>,
+) -> std::io::Result<PutOutcome> {
+    let key = key.trim();
+    let text = text.trim();
+    if key.is_empty() {
+        return Err(invalid_input("key must not be empty"));
+    }
+    if key.chars().count() > MAX_KEY_CHARS {
+        return Err(invalid_input(format!("key is longer than {MAX_KEY_CHARS} characters")));
+    }
+    if text.chars().count() > MAX_NOTE_CHARS {
+        return Err(invalid_input(format!("text is longer than {MAX_NOTE_CHARS} characters")));
+    }
+
+    let path = notes_path(cwd);
+    let Some(dir) = path.parent() else {
+        return Err(invalid_input("no directory for project memory"));
+    };
+    fs::create_dir_all(dir)?;
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(&path)?;
+    file.lock_exclusive()?;
+
+    let mut contents = String::new();
+    file.seek(SeekFrom::Start(0))?;
+    // We need read_to_string on the locked file handle, not fs::read_to_string.
+    #[allow(clippy::verbose_file_reads)]
+    file.read_to_string(&mut contents)?;
+    // A file that no longer parses is left alone rather than overwritten.
+    let mut data = if contents.trim().is_empty() {
+        NotesData::default()
+    } else {
+        match serde_json::from_str::<NotesData>(&contents) {
+            Ok(data) => data,
+            Err(err) => {
+                file.unlock()?;
+                return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, err));
+            }
+        }
+    };
+    data.version = NOTES_VERSION;
+
+    let existing = data
+        .notes
+        .iter()
+        .position(|note| note.key.eq_ignore_ascii_case(key));
+    let outcome = match (existing, text.is_empty()) {
+        (Some(idx), true) => {
+            data.notes.remove(idx);
+            PutOutcome::Removed
+        }
+        (None, true) => PutOutcome::Missing,
+        (Some(idx), false) => {
+            let note = &mut data.notes[idx];
+            text.clone_into(&mut note.text);
+            note.tags = tags;
+            note.updated_at = now;
+            PutOutcome::Updated
+        }
+        (None, false) => {
+            data.notes.push(MemoryNote {
+                key: key.to_owned(),
+                text: text.to_owned(),
+                tags,
+                updated_at: now,
+            });
+            PutOutcome::Created
+        }
+    };
+    if outcome == PutOutcome::Missing {
+        file.unlock()?;
+        return Ok(outcome);
+    }
+
+    let json = serde_json::to_string_pretty(&data)?;
+    let tmp_path = dir.join(format!("{NOTES_FILE}.tmp"));
+    {
+        let mut tmp = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&tmp_path)?;
+        tmp.write_all(json.as_bytes())?;
+        tmp.sync_all()?;
+    }
+    if let Err(err) = fs::rename(&tmp_path, &path) {
+        let _ = fs::remove_file(&tmp_path);
+        file.unlock()?;
+        return Err(err);
+    }
+    file.unlock()?;
+    Ok(outcome)
+}
+
+/// Notes matching `query`, best first. An exact key match ranks above
+/// everything else; the rest are ranked by the query words found in the key,
+/// tags and text, then by recency.
+pub fn search_notes<'a>(notes: &'a [MemoryNote], query: &str, limit: usize) -> Vec<&'a MemoryNote> {
+    let query = query.trim();
+    let query_words = crate::mcp::tool_budget::words(query);
+    let mut ranked: Vec<(usize, &MemoryNote)> = notes
+        .iter()
+        .filter_map(|note| {
+            if note.key.eq_ignore_ascii_case(query) {
+                return Some((usize::MAX, note));
+            }
+            let text = format!("{} {} {}", note.key, note.tags.join(" "), note.text);
+            let score = crate::mcp::tool_budget::words(&text)
+                .intersection(&query_words)
+                .count();
+            (score > 0).then_some((score, note))
+        })
+        .collect();
+    ranked.sort_by(|(score_a, note_a), (score_b, note_b)| {
+        score_b
+            .cmp(score_a)
+            .then_with(|| note_b.updated_at.cmp(&note_a.updated_at))
+    });
+    ranked.into_iter().take(limit).map(|(_, note)| note).collect()
+}
+
+/// The developer message listing the project's notes at session start,
+/// most recent first, or `None` when there are none.
+pub fn render_session_context(notes: &[MemoryNote]) -> Option<String> {
+    if notes.is_empty() {
+        return None;
+    }
+    let mut out = "## Project memory\nNotes saved in earlier sessions on this project with `memory_put`. Use `memory_search` for details, and keep notes current with `memory_put` (empty text removes a note).\n".to_owned();
+    let mut omitted = 0usize;
+    for note in notes {
+        let entry = if note.tags.is_empty() {
+            format!("\n- {}: {}", note.key, note.text)
+        } else {
+            format!("\n- {} [{}]: {}", note.key, note.tags.join(", "), note.text)
+        };
+        if out.len() + entry.len() > MAX_CONTEXT_BYTES {
+            omitted += 1;
+            continue;
+        }
+        out.push_str(&entry);
+    }
+    if omitted > 0 {
+        out.push_str(&format!("\n\n({omitted} older notes omitted; search for them by topic.)"));
+    }
+    Some(out)
+}
+
+fn invalid_input(message: impl Into<String>) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidInput, message.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn put_updates_removes_and_searches_notes() {
+        let project = TempDir::new().expect("tempdir");
+        let cwd = project.path();
+        let now = Utc::now();
+        let put = |key: &str, text: &str, at| put_note(cwd, key, text, Vec::new(), at).expect("put");
+
+        assert_eq!(put("build", "Run `just build` before tests.", now), PutOutcome::Created);
+        assert_eq!(
+            put("release", "Releases are cut from the main branch.", now + Duration::seconds(1)),
+            PutOutcome::Created
+        );
+        assert_eq!(
+            put("Build", "Run `just build-all` before tests.", now + Duration::seconds(2)),
+            PutOutcome::Updated
+        );
+
+        let notes = load_notes(cwd).expect("load");
+        assert_eq!(
+            notes.iter().map(|note| note.key.as_str()).collect::<Vec<_>>(),
+            vec!["build", "release"]
+        );
+        assert_eq!(notes[0].text, "Run `just build-all` before tests.");
+
+        let hits = search_notes(&notes, "which branch for releases", 5);
+        assert_eq!(hits.iter().map(|note| note.key.as_str()).collect::<Vec<_>>(), vec!["release"]);
+        assert_eq!(search_notes(&notes, "BUILD", 5)[0].key, "build");
+
+        assert_eq!(put("release", "", now), PutOutcome::Removed);
+        assert_eq!(put("release", "", now), PutOutcome::Missing);
+        let context = render_session_context(&load_notes(cwd).expect("load")).expect("context");
+        assert!(context.ends_with("- build: Run `just build-all` before tests."));
+    }
+}
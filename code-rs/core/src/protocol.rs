@@ -46,6 +46,7 @@ pub use code_protocol::protocol::ReviewFinding;
 pub use code_protocol::protocol::ReviewLineRange;
 pub use code_protocol::protocol::ReviewOutputEvent;
 pub use code_protocol::protocol::{ReviewContextMetadata, ReviewRequest};
+pub use code_protocol::protocol::CodexErrorInfo;
 pub use code_protocol::protocol::GitInfo;
 pub use code_protocol::protocol::ImageGenerationBeginEvent;
 pub use code_protocol::protocol::ImageGenerationEndEvent;
@@ -154,6 +155,10 @@ pub struct ConfigureSessionOp {
     pub shell: Option<ShellConfig>,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub shell_style_profiles: HashMap<String, ShellStyleProfileEntry>,
+    /// Replaces `shell_environment_policy.set` for commands run in this
+    /// session (`/env`); `None` keeps the configured variables.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shell_env_set: Option<HashMap<String, String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub network: Option<crate::config::NetworkProxySettingsToml>,
@@ -215,6 +220,20 @@ pub enum Op {
         format: TextFormat,
     },
 
+    /// Run the next user turn with a different model, reasoning effort,
+    /// sandbox or approval policy. Unset fields keep the session's values,
+    /// and the session configuration is unchanged once that turn ends.
+    SetNextTurnOverrides {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        model: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        effort: Option<ReasoningEffortConfig>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        sandbox: Option<SandboxPolicy>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        approval: Option<AskForApproval>,
+    },
+
     /// Approve a command execution
     ExecApproval {
         /// The id of the submission we are approving
@@ -233,6 +252,17 @@ pub enum Op {
         #[serde(skip_serializing_if = "Option::is_none")]
         #[serde(default)]
         semantic_prefix: Option<Vec<String>>,
+        /// Also save the pattern to the project's `.code/approvals.toml` so
+        /// later sessions start with it approved.
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        persist: bool,
+    },
+
+    /// Drop a command pattern from the session's approvals and from the
+    /// project's `.code/approvals.toml`.
+    RevokeApprovedCommand {
+        command: Vec<String>,
+        match_kind: ApprovedCommandMatchKind,
     },
 
     /// Approve a code patch
@@ -279,6 +309,14 @@ pub enum Op {
         response: DynamicToolResponse,
     },
 
+    /// Answer an `EventMsg::RequestBudgetCheck`. Only sent by clients that
+    /// enabled `Config::request_budget_negotiation`.
+    RequestBudgetDecision {
+        /// `request_id` from the check being answered.
+        id: String,
+        decision: RequestBudgetDecision,
+    },
+
     /// Update a specific validation tool toggle for the session.
     UpdateValidationTool {
         name: String,
@@ -327,6 +365,12 @@ pub enum Op {
     /// Reply is delivered via `EventMsg::McpListToolsResponse`.
     RefreshMcpTools,
 
+    /// Re-read `mcp_servers` from config and apply the difference without a
+    /// new session: removed servers are shut down, changed ones restarted and
+    /// new ones started. A summary arrives as `EventMsg::BackgroundEvent`,
+    /// followed by `EventMsg::McpListToolsResponse`.
+    ReloadMcpServers,
+
     /// Update runtime MCP tool enablement for a specific server/tool pair.
     SetMcpToolEnabled {
         server: String,
@@ -355,10 +399,28 @@ pub enum Op {
     /// Reply is delivered via `EventMsg::ListSkillsResponse`.
     ListSkills,
 
+    /// Request the system/developer prompt stack the next turn would send.
+    /// Reply is delivered via `EventMsg::PromptPreview`.
+    PreviewPrompt,
+
+    /// Leave the listed prompt segments (by `PromptSegment::id`) out of the
+    /// next turn only. An empty list re-enables everything.
+    SetDisabledPromptSegments {
+        segments: Vec<String>,
+    },
+
     /// Request the agent to summarize the current conversation context.
     /// The agent will use its existing context (either conversation history or previous response id)
     /// to generate a summary which will be returned as an `AgentMessage` event.
     Compact,
+    /// Restore the workspace files to how they were before the user turn
+    /// `turn_id` (the submission id of its `UserInput`) started, discarding
+    /// the changes of that turn and every later one. `None` reverts the most
+    /// recent turn. Replies with `EventMsg::TurnReverted`.
+    RevertTurn {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        turn_id: Option<String>,
+    },
     /// Request the agent to perform a dedicated code review.
     Review { review_request: ReviewRequest },
     /// Request to shut down codex instance.
@@ -493,6 +555,12 @@ pub enum SandboxPolicy {
         #[serde(default)]
         network_access: bool,
 
+        /// Hostnames and CIDR ranges reachable when `network_access` is
+        /// `false`. Connections go through the managed network proxy, and the
+        /// sandbox only lets commands reach the proxy.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        network_allowlist: Vec<String>,
+
         /// When set to `true`, will NOT include the per-user `TMPDIR`
         /// environment variable among the default writable roots. Defaults to
         /// `false`.
@@ -559,6 +627,7 @@ impl SandboxPolicy {
         SandboxPolicy::WorkspaceWrite {
             writable_roots: vec![],
             network_access: false,
+            network_allowlist: vec![],
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
             allow_git_writes: true,
@@ -586,6 +655,19 @@ impl SandboxPolicy {
         }
     }
 
+    /// Hosts and CIDR ranges commands may reach through the managed network
+    /// proxy. Empty unless the policy restricts the network.
+    pub fn network_allowlist(&self) -> &[String] {
+        match self {
+            SandboxPolicy::WorkspaceWrite {
+                network_access: false,
+                network_allowlist,
+                ..
+            } => network_allowlist,
+            _ => &[],
+        }
+    }
+
     /// Returns the list of writable roots (tailored to the current working
     /// directory) together with subpaths that should remain read‑only under
     /// each writable root.
@@ -599,6 +681,7 @@ impl SandboxPolicy {
                 exclude_slash_tmp,
                 allow_git_writes,
                 network_access: _,
+                network_allowlist: _,
             } => {
                 // Start from explicitly configured writable roots.
                 let mut roots: Vec<PathBuf> = writable_roots.clone();
@@ -710,6 +793,10 @@ pub fn event_msg_to_protocol(msg: &EventMsg) -> Option<code_protocol::protocol::
     match msg {
         EventMsg::ReplayHistory(_) => None,
         EventMsg::TaskLifecycle(_) => None,
+        EventMsg::PromptPreview(_) => None,
+        EventMsg::RequestBudgetCheck(_) => None,
+        EventMsg::Heartbeat(_) => None,
+        EventMsg::PartialTurn(_) => None,
         EventMsg::TokenCount(payload) => {
             let info = convert_value(&payload.info)?;
             let rate_limits = payload
@@ -911,6 +998,10 @@ pub enum EventMsg {
     /// Non-fatal warning surfaced to the user.
     Warning(WarningEvent),
 
+    /// Progress kept from a turn that failed after exhausting its retries.
+    /// Precedes the turn's `Error`.
+    PartialTurn(PartialTurnEvent),
+
     /// Agent has started a task
     TaskStarted,
 
@@ -928,6 +1019,10 @@ pub enum EventMsg {
     /// Auto Context is evaluating whether to compact before the next turn.
     AutoContextCheck(AutoContextCheckEvent),
 
+    /// The running turn has been quiet for the heartbeat interval; repeats
+    /// until another event arrives. Not recorded in the rollout.
+    Heartbeat(HeartbeatEvent),
+
     /// Agent text output message
     AgentMessage(AgentMessageEvent),
 
@@ -1002,6 +1097,9 @@ pub enum EventMsg {
 
     ExecCommandEnd(ExecCommandEndEvent),
 
+    /// The sandbox blocked a command; follows its `ExecCommandEnd`.
+    SandboxDenied(SandboxDeniedEvent),
+
     ExecApprovalRequest(ExecApprovalRequestEvent),
 
     /// MCP server requested an elicitation response (form/url input).
@@ -1013,10 +1111,34 @@ pub enum EventMsg {
 
     DynamicToolCallRequest(DynamicToolCallRequest),
 
+    /// A model request is about to be sent; the turn waits for
+    /// `Op::RequestBudgetDecision`. Only emitted when
+    /// `Config::request_budget_negotiation` is set.
+    RequestBudgetCheck(RequestBudgetCheckEvent),
+
     ApplyPatchApprovalRequest(ApplyPatchApprovalRequestEvent),
 
     BackgroundEvent(BackgroundEventEvent),
 
+    /// HEAD of the session repository moved to another branch or was
+    /// rewritten (rebase, reset) outside the session.
+    GitHeadChanged(GitHeadChangedEvent),
+
+    /// Result of `Op::RevertTurn`.
+    TurnReverted(TurnRevertedEvent),
+
+    /// `Op::QueueUserInput` arrived while a turn was running and waits for
+    /// that turn's next model request. The event id is the queued submission.
+    UserInputQueued(UserInputQueuedEvent),
+
+    /// Queued user inputs were handed to the model, either in the running
+    /// turn or as the turn started after it.
+    QueuedUserInputDelivered(QueuedUserInputDeliveredEvent),
+
+    /// `Op::UserInput` arrived while a turn was running; that turn is
+    /// interrupted and a new one starts with the input.
+    UserInputInterrupting(UserInputInterruptingEvent),
+
     /// Notification that the agent is about to apply a code patch. Mirrors
     /// `ExecCommandBegin` so front‑ends can show progress indicators.
     PatchApplyBegin(PatchApplyBeginEvent),
@@ -1032,12 +1154,19 @@ pub enum EventMsg {
     /// List of MCP tools available to the agent.
     McpListToolsResponse(McpListToolsResponseEvent),
 
+    /// An HTTP MCP server needs an OAuth sign-in: the user should open the
+    /// URL and enter the code. The server starts once they approve.
+    McpAuthPrompt(McpAuthPromptEvent),
+
     /// List of custom prompts available to the agent.
     ListCustomPromptsResponse(ListCustomPromptsResponseEvent),
 
     /// List of skills available to the agent.
     ListSkillsResponse(ListSkillsResponseEvent),
 
+    /// Response to `PreviewPrompt`.
+    PromptPreview(PromptPreviewEvent),
+
     PlanUpdate(UpdatePlanArgs),
 
     /// Browser screenshot has been captured and is ready for display
@@ -1075,9 +1204,187 @@ pub enum EventMsg {
 
 // Individual event payload types matching each `EventMsg` variant.
 
+/// One block of the prompt stack sent ahead of the conversation.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PromptSegment {
+    /// Stable id accepted by `Op::SetDisabledPromptSegments`, e.g.
+    /// `user_instructions` or `developer:memories`.
+    pub id: String,
+    pub label: String,
+    /// `system`, `developer` or `user`.
+    pub role: String,
+    pub text: String,
+    /// Estimated at four bytes per token.
+    pub tokens: u64,
+    /// The base instructions cannot be left out; everything else can.
+    pub optional: bool,
+    /// False when the segment is disabled for the next turn.
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PromptPreviewEvent {
+    /// Segments in the order they are sent.
+    pub segments: Vec<PromptSegment>,
+}
+
+/// The model request a turn is about to send.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RequestBudgetCheckEvent {
+    /// Id to answer with in `Op::RequestBudgetDecision`.
+    pub request_id: String,
+    pub turn_id: String,
+    pub model: String,
+    pub reasoning_effort: ReasoningEffortConfig,
+    /// Instructions, input and tool definitions, estimated at four bytes per
+    /// token.
+    pub estimated_input_tokens: u64,
+    /// Names of the tools the request offers the model.
+    pub tools: Vec<String>,
+}
+
+/// How an embedding client wants a planned model request handled.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RequestBudgetDecision {
+    /// Send the request as planned.
+    Proceed,
+    /// Send the request with changes.
+    Modify {
+        /// Tools to drop from the request.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        remove_tools: Vec<String>,
+        /// Replaces the reasoning effort for this request.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reasoning_effort: Option<ReasoningEffortConfig>,
+    },
+    /// Do not send the request; the turn ends with a warning carrying
+    /// `reason`.
+    Veto {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reason: Option<String>,
+    },
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ErrorEvent {
     pub message: String,
+    /// Failure class, when the error came from a classified `CodexErr`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub codex_error_info: Option<CodexErrorInfo>,
+    /// Stable identifier for the failure; clients should branch on this
+    /// rather than on `message`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<ErrorCode>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<ErrorCategory>,
+}
+
+impl ErrorEvent {
+    pub fn new(message: impl Into<String>, code: ErrorCode) -> Self {
+        Self {
+            message: message.into(),
+            codex_error_info: None,
+            code: Some(code),
+            category: Some(code.category()),
+        }
+    }
+}
+
+/// Where a failure came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCategory {
+    Auth,
+    Sandbox,
+    Provider,
+    Tool,
+    Config,
+    Internal,
+}
+
+/// Machine-readable failure codes. These are part of the wire format: add new
+/// codes rather than renaming existing ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// The provider rejected the credentials.
+    Unauthorized,
+    /// Stored credentials expired and could not be refreshed.
+    AuthExpired,
+    /// A required API key environment variable is not set.
+    MissingCredentials,
+    ContextWindowExceeded,
+    UsageLimitReached,
+    QuotaExceeded,
+    UsageNotIncluded,
+    ModelCap,
+    /// The provider rate-limited the request.
+    RateLimited,
+    /// The provider's content filter blocked the request or response.
+    ContentFiltered,
+    BadRequest,
+    /// Any other unexpected HTTP status from the provider.
+    ProviderError,
+    ServerError,
+    ServerOverloaded,
+    StreamDisconnected,
+    RetryLimitReached,
+    NetworkError,
+    SandboxDenied,
+    SandboxTimeout,
+    SandboxSignal,
+    SandboxOutOfMemory,
+    SandboxSetupFailed,
+    CommandTimeout,
+    SpawnFailed,
+    McpServerFailed,
+    InvalidConfig,
+    SandboxExecutableMissing,
+    UnsupportedOperation,
+    Interrupted,
+    ConversationNotFound,
+    NoSession,
+    Internal,
+}
+
+impl ErrorCode {
+    pub fn category(self) -> ErrorCategory {
+        match self {
+            ErrorCode::Unauthorized | ErrorCode::AuthExpired | ErrorCode::MissingCredentials => {
+                ErrorCategory::Auth
+            }
+            ErrorCode::ContextWindowExceeded
+            | ErrorCode::UsageLimitReached
+            | ErrorCode::QuotaExceeded
+            | ErrorCode::UsageNotIncluded
+            | ErrorCode::ModelCap
+            | ErrorCode::RateLimited
+            | ErrorCode::ContentFiltered
+            | ErrorCode::BadRequest
+            | ErrorCode::ProviderError
+            | ErrorCode::ServerError
+            | ErrorCode::ServerOverloaded
+            | ErrorCode::StreamDisconnected
+            | ErrorCode::RetryLimitReached
+            | ErrorCode::NetworkError => ErrorCategory::Provider,
+            ErrorCode::SandboxDenied
+            | ErrorCode::SandboxTimeout
+            | ErrorCode::SandboxSignal
+            | ErrorCode::SandboxOutOfMemory
+            | ErrorCode::SandboxSetupFailed => ErrorCategory::Sandbox,
+            ErrorCode::CommandTimeout | ErrorCode::SpawnFailed | ErrorCode::McpServerFailed => {
+                ErrorCategory::Tool
+            }
+            ErrorCode::InvalidConfig
+            | ErrorCode::SandboxExecutableMissing
+            | ErrorCode::UnsupportedOperation => ErrorCategory::Config,
+            ErrorCode::Interrupted
+            | ErrorCode::ConversationNotFound
+            | ErrorCode::NoSession
+            | ErrorCode::Internal => ErrorCategory::Internal,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -1085,6 +1392,26 @@ pub struct WarningEvent {
     pub message: String,
 }
 
+/// What a failed turn finished before it failed. The completed tool calls
+/// and the partial assistant text are also kept in the conversation history,
+/// so the next turn can pick up from them.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PartialTurnEvent {
+    /// Tool calls that returned output before the failure.
+    pub tool_calls: Vec<PartialToolCall>,
+    /// Assistant text streamed before the failure.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partial_message: Option<String>,
+    /// The error that ended the turn.
+    pub error: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PartialToolCall {
+    pub call_id: String,
+    pub name: String,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct TaskCompleteEvent {
     pub last_agent_message: Option<String>,
@@ -1201,6 +1528,13 @@ pub struct TokenUsageInfo {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub latest_response_model: Option<String>,
     pub model_context_window: Option<u64>,
+    /// Estimated output tokens regenerated because streams dropped mid-response.
+    #[serde(default, skip_serializing_if = "is_zero_u64")]
+    pub retry_wasted_output_tokens: u64,
+}
+
+fn is_zero_u64(value: &u64) -> bool {
+    *value == 0
 }
 
 impl TokenUsageInfo {
@@ -1221,6 +1555,7 @@ impl TokenUsageInfo {
                 requested_model: None,
                 latest_response_model: None,
                 model_context_window,
+                retry_wasted_output_tokens: 0,
             },
         };
 
@@ -1494,6 +1829,13 @@ pub struct ExecCommandEndEvent {
     pub duration: Duration,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SandboxDeniedEvent {
+    /// Identifier of the denied command's `ExecCommandBegin`.
+    pub call_id: String,
+    pub command: Vec<String>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecOutputStream {
@@ -1583,6 +1925,100 @@ pub struct BackgroundEventEvent {
     pub message: String,
 }
 
+/// What a quiet turn is waiting on.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HeartbeatPhase {
+    /// Waiting for the model's response.
+    Model,
+    /// A tool call or command is running.
+    ToolCall,
+    /// Waiting for the user to answer an approval or input request.
+    AwaitingUser,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct HeartbeatEvent {
+    /// Milliseconds since the turn started.
+    pub elapsed_ms: u64,
+    /// Milliseconds since the turn's last event other than a heartbeat.
+    pub idle_ms: u64,
+    pub phase: HeartbeatPhase,
+    /// The command or tool the turn is waiting on in the `tool_call` phase.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GitHeadChangedEvent {
+    pub previous_branch: Option<String>,
+    pub previous_commit: Option<String>,
+    pub branch: Option<String>,
+    pub commit: Option<String>,
+    /// Human-readable summary of the change.
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TurnRevertedEvent {
+    /// The turn whose starting state was restored, when one was found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub turn_id: Option<String>,
+    pub success: bool,
+    /// Human-readable summary of the outcome.
+    pub message: String,
+    /// Files put back, relative to the repository root.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<RevertedFile>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct McpAuthPromptEvent {
+    pub server_name: String,
+    pub verification_uri: String,
+    /// `verification_uri` with the code already filled in, when the
+    /// authorization server provides one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verification_uri_complete: Option<String>,
+    pub user_code: String,
+    /// Seconds until the code expires.
+    pub expires_in_secs: u64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct UserInputQueuedEvent {
+    /// Queued inputs waiting for the model, this one included.
+    pub pending: usize,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct QueuedUserInputDeliveredEvent {
+    /// Submission ids of the delivered `Op::QueueUserInput` inputs.
+    pub submission_ids: Vec<String>,
+    /// Queued inputs still waiting.
+    pub pending: usize,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct UserInputInterruptingEvent {
+    /// Queued inputs carried over to the new turn.
+    pub pending: usize,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RevertedFile {
+    pub path: String,
+    /// Lines the reverted turns had added; `None` for binary files.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub added: Option<u64>,
+    /// Lines the reverted turns had removed; `None` for binary files.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub removed: Option<u64>,
+    /// The reverted turns created the file, so reverting deleted it.
+    #[serde(default)]
+    pub deleted: bool,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct PatchApplyBeginEvent {
     /// Identifier so this can be paired with the `PatchApplyEnd` event.
@@ -1871,6 +2307,7 @@ mod tests {
             dynamic_tools: Vec::new(),
             shell: None,
             shell_style_profiles: HashMap::new(),
+            shell_env_set: None,
             network: None,
             tools_repl: true,
             repl_default_runtime: crate::config::ReplRuntimeKindToml::Node,
@@ -0,0 +1,160 @@
+//! Structured classification of provider error payloads.
+//!
+//! Providers report context overflow, rate limiting and content filtering
+//! with machine-readable codes; this table maps them to dedicated `CodexErr`
+//! variants so callers do not have to pattern-match on English messages.
+//! `CodexErr::is_context_window_exceeded` still falls back to the message
+//! heuristics for providers that only send prose.
+
+use reqwest::StatusCode;
+use serde_json::Value;
+
+use crate::error::CodexErr;
+use crate::error::RetryAfter;
+use crate::error::UnexpectedResponseError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ProviderErrorKind {
+    ContextWindowExceeded,
+    RateLimited,
+    ContentFiltered,
+}
+
+/// `error.code` / `error.type` values, by provider:
+/// - OpenAI and Azure OpenAI: `context_length_exceeded`, `rate_limit_exceeded`,
+///   `content_filter` (Azure) and `content_policy_violation` / `invalid_prompt`
+///   (OpenAI).
+/// - Anthropic: `request_too_large`, `rate_limit_error`.
+/// - Gemini (`error.status`): `RESOURCE_EXHAUSTED`.
+const CONTEXT_WINDOW_CODES: &[&str] = &["context_length_exceeded", "request_too_large"];
+const RATE_LIMIT_CODES: &[&str] =
+    &["rate_limit_exceeded", "rate_limit_error", "RESOURCE_EXHAUSTED"];
+const CONTENT_FILTER_CODES: &[&str] =
+    &["content_filter", "content_policy_violation", "invalid_prompt"];
+
+/// Classifies one provider error from its HTTP status (when there is one)
+/// and its `code`, `type` or `status` fields.
+pub(crate) fn classify_provider_error(
+    status: Option<StatusCode>,
+    codes: &[Option<&str>],
+) -> Option<ProviderErrorKind> {
+    let has_code = |table: &[&str]| codes.iter().flatten().any(|code| table.contains(code));
+    if has_code(CONTEXT_WINDOW_CODES) || status == Some(StatusCode::PAYLOAD_TOO_LARGE) {
+        Some(ProviderErrorKind::ContextWindowExceeded)
+    } else if has_code(CONTENT_FILTER_CODES) {
+        Some(ProviderErrorKind::ContentFiltered)
+    } else if has_code(RATE_LIMIT_CODES) || status == Some(StatusCode::TOO_MANY_REQUESTS) {
+        Some(ProviderErrorKind::RateLimited)
+    } else {
+        None
+    }
+}
+
+/// Classifies a raw HTTP error body. Accepts the `{"error": {...}}` shape used
+/// by OpenAI-compatible APIs and Anthropic, and Gemini's list-wrapped form.
+pub(crate) fn classify_error_body(
+    status: Option<StatusCode>,
+    body: &str,
+) -> Option<(ProviderErrorKind, String)> {
+    let value: Value = serde_json::from_str(body).ok()?;
+    let value = match value {
+        Value::Array(mut items) if !items.is_empty() => items.swap_remove(0),
+        value => value,
+    };
+    let error = value.get("error")?;
+    let field = |name: &str| error.get(name).and_then(Value::as_str);
+    let kind = classify_provider_error(status, &[field("code"), field("type"), field("status")])?;
+    let message = field("message").map_or_else(|| body.to_owned(), str::to_owned);
+    Some((kind, message))
+}
+
+/// Builds the error for a provider failure that already has a kind.
+pub(crate) fn provider_error(
+    kind: ProviderErrorKind,
+    message: String,
+    retry_after: Option<RetryAfter>,
+) -> CodexErr {
+    match kind {
+        ProviderErrorKind::ContextWindowExceeded => CodexErr::ContextWindowExceeded(message),
+        ProviderErrorKind::ContentFiltered => CodexErr::ContentFiltered(message),
+        ProviderErrorKind::RateLimited => CodexErr::RateLimited(message, retry_after),
+    }
+}
+
+/// `CodexErr::UnexpectedStatus`, unless the body says the request overflowed
+/// the context window or was blocked by a content filter. Rate limits are left
+/// to the callers' retry handling.
+pub(crate) fn unexpected_status_error(
+    status: StatusCode,
+    body: String,
+    request_id: Option<String>,
+) -> CodexErr {
+    let classified = classify_error_body(Some(status), &body).or_else(|| {
+        (status == StatusCode::PAYLOAD_TOO_LARGE)
+            .then(|| (ProviderErrorKind::ContextWindowExceeded, body.clone()))
+    });
+    match classified {
+        Some((kind, message)) if kind != ProviderErrorKind::RateLimited => {
+            provider_error(kind, message, None)
+        }
+        _ => CodexErr::UnexpectedStatus(UnexpectedResponseError {
+            status,
+            body,
+            request_id,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn classifies_provider_error_bodies() {
+        let openai = r#"{"error":{"message":"This model's maximum context length is 128000 tokens.","type":"invalid_request_error","code":"context_length_exceeded"}}"#;
+        assert_eq!(
+            classify_error_body(Some(StatusCode::BAD_REQUEST), openai),
+            Some((
+                ProviderErrorKind::ContextWindowExceeded,
+                "This model's maximum context length is 128000 tokens.".to_owned()
+            ))
+        );
+
+        let azure = r#"{"error":{"message":"filtered","code":"content_filter"}}"#;
+        assert_eq!(
+            classify_error_body(Some(StatusCode::BAD_REQUEST), azure).map(|(kind, _)| kind),
+            Some(ProviderErrorKind::ContentFiltered)
+        );
+
+        let anthropic = r#"{"type":"error","error":{"type":"rate_limit_error","message":"slow down"}}"#;
+        assert_eq!(
+            classify_error_body(None, anthropic).map(|(kind, _)| kind),
+            Some(ProviderErrorKind::RateLimited)
+        );
+
+        let gemini = r#"[{"error":{"code":429,"message":"quota","status":"RESOURCE_EXHAUSTED"}}]"#;
+        assert_eq!(
+            classify_error_body(None, gemini).map(|(kind, _)| kind),
+            Some(ProviderErrorKind::RateLimited)
+        );
+
+        let unknown = r#"{"error":{"message":"Unknown parameter: 'input[0].metadata'","code":"unknown_parameter"}}"#;
+        assert_eq!(classify_error_body(Some(StatusCode::BAD_REQUEST), unknown), None);
+        assert_eq!(classify_error_body(Some(StatusCode::BAD_REQUEST), "not json"), None);
+    }
+
+    #[test]
+    fn unexpected_status_keeps_unclassified_bodies() {
+        let err = unexpected_status_error(
+            StatusCode::BAD_REQUEST,
+            r#"{"error":{"message":"too long","code":"context_length_exceeded"}}"#.to_owned(),
+            None,
+        );
+        assert!(matches!(err, CodexErr::ContextWindowExceeded(ref message) if message == "too long"));
+        assert!(err.is_context_window_exceeded());
+
+        let err = unexpected_status_error(StatusCode::NOT_FOUND, "missing".to_owned(), None);
+        assert!(matches!(err, CodexErr::UnexpectedStatus(_)));
+    }
+}
@@ -156,8 +156,9 @@ pub fn read_lock_info(scope: Option<&Path>) -> Option<ReviewLockInfo> {
     serde_json::from_str(&buf).ok()
 }
 
+/// Whether a process with `pid` is still running.
 #[cfg(unix)]
-fn pid_alive(pid: u32) -> bool {
+pub fn pid_alive(pid: u32) -> bool {
     // Safety: kill with signal 0 performs permission/aliveness check only
     let res = unsafe { libc::kill(pid as libc::pid_t, 0) };
     if res == 0 {
@@ -172,7 +173,7 @@ fn pid_alive(pid: u32) -> bool {
 }
 
 #[cfg(not(unix))]
-fn pid_alive(_pid: u32) -> bool {
+pub fn pid_alive(_pid: u32) -> bool {
     // Best-effort: assume alive to avoid clobbering valid locks on non-Unix platforms
     true
 }
@@ -0,0 +1,123 @@
+//! Reviewer personas defined under `[review_profiles.<name>]`.
+//!
+//! A profile parameterizes the review prompt: it adjusts how strict the
+//! reviewer is, which areas it prioritizes, how findings are worded, and
+//! which checks it must explicitly go through. Front ends select one with
+//! `/review --profile <name>`; otherwise the configured `review_profile`
+//! applies.
+
+use std::fmt::Write as _;
+
+use crate::config::Config;
+use crate::config_types::ReviewProfile;
+use crate::config_types::ReviewStrictness;
+
+/// Splits a leading `--profile <name>` / `--profile=<name>` off `/review`
+/// arguments, returning the profile name and the remaining arguments.
+pub fn split_profile_flag(args: &str) -> (Option<String>, String) {
+    let trimmed = args.trim_start();
+    let Some(rest) = trimmed.strip_prefix("--profile") else {
+        return (None, args.trim().to_owned());
+    };
+    let (name, remainder) = if let Some(value) = rest.strip_prefix('=') {
+        value.split_once(char::is_whitespace).unwrap_or((value, ""))
+    } else if rest.starts_with(char::is_whitespace) {
+        let rest = rest.trim_start();
+        rest.split_once(char::is_whitespace).unwrap_or((rest, ""))
+    } else {
+        // Something like `--profiles`; not our flag.
+        return (None, args.trim().to_owned());
+    };
+    let name = name.trim();
+    ((!name.is_empty()).then(|| name.to_owned()), remainder.trim().to_owned())
+}
+
+/// Picks the profile for a review: the requested one if given, else the
+/// configured default. Returns `Err` with the name when it is not defined.
+pub fn resolve<'a>(
+    config: &'a Config,
+    requested: Option<&str>,
+) -> Result<Option<(&'a str, &'a ReviewProfile)>, String> {
+    let Some(name) = requested.or(config.review_profile.as_deref()) else {
+        return Ok(None);
+    };
+    config
+        .review_profiles
+        .get_key_value(name)
+        .map(|(name, profile)| Some((name.as_str(), profile)))
+        .ok_or_else(|| name.to_owned())
+}
+
+/// Renders the prompt section appended to the review guidelines.
+pub fn render_instructions(name: &str, profile: &ReviewProfile) -> String {
+    let mut out = format!("## Reviewer profile: {name}\n");
+    match profile.strictness {
+        Some(ReviewStrictness::Lenient) => out.push_str(
+            "\nStrictness: lenient. Only flag clear, high-impact bugs; skip P2/P3 style and nice-to-have findings.\n",
+        ),
+        Some(ReviewStrictness::Strict) => out.push_str(
+            "\nStrictness: strict. In addition to bugs, flag lower-priority issues the author would still want to fix before merge.\n",
+        ),
+        Some(ReviewStrictness::Balanced) | None => {}
+    }
+    if !profile.focus_areas.is_empty() {
+        out.push_str("\nPrioritize these areas:\n");
+        for area in &profile.focus_areas {
+            let _ = writeln!(out, "- {area}");
+        }
+    }
+    if let Some(tone) = profile.tone.as_deref().map(str::trim).filter(|tone| !tone.is_empty()) {
+        let _ = writeln!(out, "\nTone for comments: {tone}");
+    }
+    if !profile.checklist.is_empty() {
+        out.push_str(
+            "\nBefore giving your verdict, go through each check below and report any that fail as findings:\n",
+        );
+        for item in &profile.checklist {
+            let _ = writeln!(out, "- [ ] {item}");
+        }
+    }
+    out.trim_end().to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn splits_profile_flag_forms() {
+        assert_eq!(
+            split_profile_flag("--profile security focus on auth"),
+            (Some("security".to_owned()), "focus on auth".to_owned())
+        );
+        assert_eq!(
+            split_profile_flag("--profile=perf"),
+            (Some("perf".to_owned()), String::new())
+        );
+        assert_eq!(
+            split_profile_flag("check the parser --profile security"),
+            (None, "check the parser --profile security".to_owned())
+        );
+        assert_eq!(split_profile_flag("--profiles"), (None, "--profiles".to_owned()));
+    }
+
+    #[test]
+    fn renders_only_configured_sections() {
+        let profile = ReviewProfile {
+            strictness: Some(ReviewStrictness::Strict),
+            focus_areas: vec!["authentication".to_owned()],
+            tone: None,
+            checklist: vec!["Inputs are validated".to_owned()],
+        };
+        let text = render_instructions("security", &profile);
+        assert!(text.starts_with("## Reviewer profile: security"));
+        assert!(text.contains("Strictness: strict."));
+        assert!(text.contains("- authentication"));
+        assert!(!text.contains("Tone"));
+        assert!(text.ends_with("- [ ] Inputs are validated"));
+
+        let plain = render_instructions("default", &ReviewProfile::default());
+        assert_eq!(plain, "## Reviewer profile: default");
+    }
+}
@@ -1,10 +1,13 @@
 use anyhow::anyhow;
 use anyhow::Context;
 use anyhow::Result;
+use std::fmt;
 use std::path::Path;
 use std::path::PathBuf;
+use std::str::FromStr;
 
 use crate::config::Config;
+use crate::rollout::recorder::RecordedRolloutLine;
 use crate::rollout::recorder::RolloutRecorderParams;
 use crate::rollout::RolloutRecorder;
 use code_protocol::ConversationId;
@@ -12,7 +15,56 @@ use code_protocol::ThreadId;
 use code_protocol::protocol::InitialHistory;
 use code_protocol::protocol::RolloutItem;
 
+/// Where to cut a conversation when forking it: right after the recorded
+/// event `event_seq` of submission `turn`. Sequence numbers restart with every
+/// submission, so `turn` defaults to the last one in the rollout.
+///
+/// Parsed from `SEQ` or `SUBMISSION_ID:SEQ`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForkPoint {
+    pub turn: Option<String>,
+    pub event_seq: u64,
+}
+
+impl FromStr for ForkPoint {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        let value = value.trim();
+        let (turn, seq) = match value.rsplit_once(':') {
+            Some((turn, seq)) if !turn.trim().is_empty() => (Some(turn.trim().to_owned()), seq),
+            Some((_, seq)) => (None, seq),
+            None => (None, value),
+        };
+        let event_seq = seq
+            .trim()
+            .parse::<u64>()
+            .map_err(|_| format!("expected SEQ or SUBMISSION_ID:SEQ, got `{value}`"))?;
+        Ok(Self { turn, event_seq })
+    }
+}
+
+impl fmt::Display for ForkPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.turn {
+            Some(turn) => write!(f, "{turn}:{}", self.event_seq),
+            None => write!(f, "{}", self.event_seq),
+        }
+    }
+}
+
 pub async fn fork_rollout(config: &Config, source_rollout: &Path) -> Result<PathBuf> {
+    fork_rollout_at(config, source_rollout, None).await
+}
+
+/// Copies `source_rollout` into a new conversation and returns the new
+/// rollout path. With `at`, only the lines up to and including that event
+/// are copied; otherwise the whole conversation is.
+pub async fn fork_rollout_at(
+    config: &Config,
+    source_rollout: &Path,
+    at: Option<&ForkPoint>,
+) -> Result<PathBuf> {
     let history = RolloutRecorder::get_rollout_history(source_rollout)
         .await
         .with_context(|| format!("failed to read rollout history from {}", source_rollout.display()))?;
@@ -41,7 +93,15 @@ pub async fn fork_rollout(config: &Config, source_rollout: &Path) -> Result<Path
     .await
     .context("failed to create rollout recorder for fork")?;
 
-    let mut items = history.get_rollout_items();
+    let mut items = match at {
+        None => history.get_rollout_items(),
+        Some(point) => {
+            let lines = RolloutRecorder::get_recorded_rollout_lines(source_rollout)
+                .await
+                .with_context(|| format!("failed to read {}", source_rollout.display()))?;
+            items_through(lines, point)?
+        }
+    };
     // The new rollout recorder writes its own SessionMeta; avoid duplicating it.
     items.retain(|item| !matches!(item, RolloutItem::SessionMeta(_)));
 
@@ -58,6 +118,12 @@ pub async fn fork_rollout(config: &Config, source_rollout: &Path) -> Result<Path
         .await
         .context("failed to flush fork rollout file")?;
 
+    // The UI snapshot shows the whole source conversation, so a fork cut
+    // short rebuilds its history from the rollout instead.
+    if at.is_some() {
+        return Ok(recorder.rollout_path);
+    }
+
     // Best-effort: copy any existing snapshot.json so resume is instant.
     let source_snapshot = source_rollout.with_extension("snapshot.json");
     let fork_snapshot = recorder.rollout_path.with_extension("snapshot.json");
@@ -76,6 +142,49 @@ pub async fn fork_rollout(config: &Config, source_rollout: &Path) -> Result<Path
     Ok(recorder.rollout_path)
 }
 
+/// The rollout items up to and including the event at `point`.
+pub(crate) fn items_through(
+    lines: Vec<RecordedRolloutLine>,
+    point: &ForkPoint,
+) -> Result<Vec<RolloutItem>> {
+    let event_at = |line: &RecordedRolloutLine| match &line.item {
+        RolloutItem::Event(event) => Some((event.id.clone(), event.event_seq)),
+        _ => None,
+    };
+    let Some(turn) = point
+        .turn
+        .clone()
+        .or_else(|| lines.iter().rev().find_map(|line| event_at(line).map(|(id, _)| id)))
+    else {
+        anyhow::bail!("the conversation has no recorded events to fork at");
+    };
+
+    let Some(stop) = lines
+        .iter()
+        .position(|line| event_at(line) == Some((turn.clone(), point.event_seq)))
+    else {
+        let last = lines
+            .iter()
+            .filter_map(event_at)
+            .filter(|(id, _)| *id == turn)
+            .map(|(_, seq)| seq)
+            .max();
+        match last {
+            Some(last) => anyhow::bail!(
+                "turn {turn} has no event with seq {} (its last seq is {last})",
+                point.event_seq
+            ),
+            None => anyhow::bail!("no recorded events for turn {turn}"),
+        }
+    };
+
+    Ok(lines
+        .into_iter()
+        .take(stop + 1)
+        .map(|line| line.item)
+        .collect())
+}
+
 fn source_thread_id(history: &InitialHistory) -> Option<ThreadId> {
     match history {
         InitialHistory::New => None,
@@ -50,3 +50,35 @@ pub(crate) fn should_persist_event_msg(ev: &EventMsg) -> bool {
             | EventMsg::AgentReasoningRawContentDelta(_)
     )
 }
+
+/// Whether `item` is kept when `[privacy] transcripts = "summary"`: what is
+/// needed to list and resume the conversation, without tool calls, command
+/// output or reasoning.
+pub(crate) fn should_persist_summary_item(item: &RolloutItem) -> bool {
+    match item {
+        RolloutItem::SessionMeta(_)
+        | RolloutItem::Compacted(_)
+        | RolloutItem::TurnContext(_) => true,
+        RolloutItem::ResponseItem(ResponseItem::Message { role, .. }) => {
+            role == "user" || role == "assistant"
+        }
+        RolloutItem::ResponseItem(ResponseItem::CompactionSummary { .. }) => true,
+        RolloutItem::ResponseItem(_) => false,
+        RolloutItem::Event(ev) => event_msg_from_protocol(&ev.msg)
+            .is_some_and(|msg| is_summary_event(&msg)),
+        RolloutItem::EventMsg(msg) => event_msg_from_protocol(msg)
+            .is_some_and(|event| is_summary_event(&event)),
+    }
+}
+
+fn is_summary_event(ev: &EventMsg) -> bool {
+    matches!(
+        ev,
+        EventMsg::UserMessage(_)
+            | EventMsg::AgentMessage(_)
+            | EventMsg::TaskStarted
+            | EventMsg::TaskComplete(_)
+            | EventMsg::TurnAborted(_)
+            | EventMsg::Error(_)
+    )
+}
@@ -28,8 +28,9 @@ use super::SESSIONS_SUBDIR;
 use super::list::get_conversations;
 use super::list::ConversationsPage;
 use super::list::Cursor;
-use super::policy::{should_persist_response_item, should_persist_rollout_item};
+use super::policy::{should_persist_response_item, should_persist_rollout_item, should_persist_summary_item};
 use crate::config::Config;
+use crate::config_types::TranscriptPersistence;
 use crate::default_client::DEFAULT_ORIGINATOR;
 use crate::git_info::collect_git_info;
 use crate::history::HistorySnapshot;
@@ -151,7 +152,17 @@ impl RolloutRecorder {
     /// Attempt to create a new [`RolloutRecorder`]. If the sessions directory
     /// cannot be created or the rollout file cannot be opened we return the
     /// error so the caller can decide whether to disable persistence.
+    ///
+    /// Fails with [`std::io::ErrorKind::Unsupported`] when `[privacy]
+    /// transcripts = "none"`, before anything is written.
     pub async fn new(config: &Config, params: RolloutRecorderParams) -> std::io::Result<Self> {
+        let transcripts = config.privacy.transcripts;
+        if transcripts == TranscriptPersistence::None {
+            return Err(IoError::new(
+                std::io::ErrorKind::Unsupported,
+                "session transcripts are disabled by [privacy] transcripts = \"none\"",
+            ));
+        }
         let (file, rollout_path, meta) = match params {
             RolloutRecorderParams::Create {
                 conversation_id,
@@ -234,6 +245,7 @@ impl RolloutRecorder {
             cwd,
             snapshot_path,
             catalog_state,
+            transcripts,
         ));
 
         Ok(Self { tx, rollout_path })
@@ -565,6 +577,7 @@ async fn rollout_writer(
     cwd: std::path::PathBuf,
     snapshot_path: PathBuf,
     mut catalog_state: Option<CatalogUpdateState>,
+    transcripts: TranscriptPersistence,
 ) -> std::io::Result<()> {
     let mut writer = JsonlWriter { file };
 
@@ -602,7 +615,10 @@ async fn rollout_writer(
         match cmd {
             RolloutCmd::AddItems(items) => {
                 for item in items {
-                    if should_persist_rollout_item(&item) {
+                    let keep = should_persist_rollout_item(&item)
+                        && (transcripts == TranscriptPersistence::Full
+                            || should_persist_summary_item(&item));
+                    if keep {
                         let (timestamp, _) = writer.write_rollout_item(item).await?;
                         if let Some(ref mut state) = catalog_state {
                             state.last_timestamp = timestamp;
@@ -623,6 +639,9 @@ async fn rollout_writer(
                         warn!("failed to update session catalog after AddItems: {err}");
                     }
             }
+            // The UI history snapshot holds the full transcript, so summary
+            // mode does not keep one.
+            RolloutCmd::SetSnapshot(_) if transcripts != TranscriptPersistence::Full => {}
             RolloutCmd::SetSnapshot(snapshot) => {
                 if let Err(err) = write_snapshot(&snapshot_path, &snapshot).await {
                     warn!("failed to persist history snapshot: {err}");
@@ -830,3 +830,50 @@ async fn test_source_filter_excludes_non_matching_sessions() {
         path.ends_with("rollout-2025-08-01T10-00-00-00000000-0000-0000-0000-00000000004d.jsonl")
     }));
 }
+
+#[test]
+fn fork_point_cuts_after_the_matching_event() {
+    use crate::rollout::fork::{ForkPoint, items_through};
+    use crate::rollout::recorder::RecordedRolloutLine;
+
+    let event = |turn: &str, seq: u64, message: &str| RolloutItem::Event(RecordedEvent {
+        id: turn.to_string(),
+        event_seq: seq,
+        order: None,
+        msg: ProtoEventMsg::UserMessage(UserMessageEvent {
+            message: message.to_string(),
+            images: None,
+            local_images: vec![],
+            text_elements: vec![],
+        }),
+    });
+    let items = [
+        event("1", 0, "first"),
+        event("1", 1, "first again"),
+        event("2", 0, "second"),
+        event("2", 1, "second again"),
+    ];
+    let lines = || {
+        items
+            .iter()
+            .enumerate()
+            .map(|(ordinal, item)| RecordedRolloutLine {
+                ordinal: ordinal as i64,
+                timestamp: "2025-10-06T09:00:01.000Z".to_string(),
+                item: item.clone(),
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let point: ForkPoint = "1:1".parse().unwrap();
+    assert_eq!(items_through(lines(), &point).unwrap().len(), 2);
+
+    // Without a submission id the sequence number refers to the last turn.
+    let point: ForkPoint = "0".parse().unwrap();
+    assert_eq!(point.turn, None);
+    assert_eq!(items_through(lines(), &point).unwrap().len(), 3);
+
+    let err = items_through(lines(), &"2:7".parse().unwrap()).unwrap_err();
+    assert_eq!(err.to_string(), "turn 2 has no event with seq 7 (its last seq is 1)");
+    assert!("next".parse::<ForkPoint>().is_err());
+}
@@ -50,9 +50,12 @@ pub fn protocol_policy_from_local(policy: &LocalSandboxPolicy) -> ProtocolSandbo
     match policy {
         LocalSandboxPolicy::DangerFullAccess => ProtocolSandboxPolicy::DangerFullAccess,
         LocalSandboxPolicy::ReadOnly => ProtocolSandboxPolicy::ReadOnly,
+        // The protocol policy has no allowlist; dropping it keeps the
+        // network closed.
         LocalSandboxPolicy::WorkspaceWrite {
             writable_roots,
             network_access,
+            network_allowlist: _,
             exclude_tmpdir_env_var,
             exclude_slash_tmp,
             allow_git_writes,
@@ -86,6 +89,7 @@ pub fn local_policy_from_protocol(policy: &ProtocolSandboxPolicy) -> LocalSandbo
                 .map(|path| path.as_ref().to_path_buf())
                 .collect(),
             network_access: *network_access,
+            network_allowlist: Vec::new(),
             exclude_tmpdir_env_var: *exclude_tmpdir_env_var,
             exclude_slash_tmp: *exclude_slash_tmp,
             allow_git_writes: *allow_git_writes,
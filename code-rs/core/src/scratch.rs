@@ -0,0 +1,128 @@
+//! Per-session scratch directories for temporary analysis files, handed out
+//! by the `scratch` tool.
+//!
+//! They live under `<system temp dir>/code-scratch/<session id>`, outside the
+//! workspace, so throwaway files never show up in diffs or reviews. The
+//! workspace-write sandbox already allows writes to the temp directory.
+//! Housekeeping removes directories that have been idle for longer than the
+//! scratch retention.
+
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+const SCRATCH_DIR: &str = "code-scratch";
+/// Entries listed by [`list_scratch`].
+const MAX_LISTED_ENTRIES: usize = 200;
+
+/// Directory that holds every session's scratch directory.
+pub fn scratch_root() -> PathBuf {
+    std::env::temp_dir().join(SCRATCH_DIR)
+}
+
+/// Creates the scratch directory for `session_id` if needed and returns it.
+pub fn ensure_scratch_dir(root: &Path, session_id: impl Display) -> io::Result<PathBuf> {
+    let dir = root.join(session_id.to_string());
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// Absolute path for `name` inside `dir`, creating its parent directories.
+/// `name` must be relative and may not leave the scratch directory.
+pub fn scratch_path(dir: &Path, name: &str) -> io::Result<PathBuf> {
+    let relative = Path::new(name.trim());
+    let inside = relative
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+    if relative.as_os_str().is_empty() || !inside {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("`{name}` must be a relative path inside the scratch directory"),
+        ));
+    }
+    let path = dir.join(relative);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    Ok(path)
+}
+
+/// Files in `dir` as `(relative path, size in bytes)`, sorted by path and
+/// capped at a few hundred entries.
+pub fn list_scratch(dir: &Path) -> io::Result<Vec<(String, u64)>> {
+    let mut files = Vec::new();
+    let mut stack = vec![dir.to_path_buf()];
+    while let Some(current) = stack.pop() {
+        for entry in fs::read_dir(&current)? {
+            let entry = entry?;
+            let file_type = entry.file_type()?;
+            if file_type.is_dir() {
+                stack.push(entry.path());
+            } else {
+                let path = entry.path();
+                let relative = path
+                    .strip_prefix(dir)
+                    .unwrap_or(&path)
+                    .to_string_lossy()
+                    .replace('\\', "/");
+                files.push((relative, entry.metadata()?.len()));
+            }
+        }
+    }
+    files.sort();
+    files.truncate(MAX_LISTED_ENTRIES);
+    Ok(files)
+}
+
+/// Removes everything in `dir`, keeping the directory itself, and returns how
+/// many top-level entries were removed.
+pub fn clear_scratch(dir: &Path) -> io::Result<usize> {
+    let mut removed = 0;
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            fs::remove_dir_all(entry.path())?;
+        } else {
+            fs::remove_file(entry.path())?;
+        }
+        removed += 1;
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn paths_stay_inside_the_session_directory() {
+        let root = TempDir::new().expect("tempdir");
+        let dir = ensure_scratch_dir(root.path(), "session-1").expect("dir");
+        assert_eq!(dir, root.path().join("session-1"));
+
+        let notes = scratch_path(&dir, "analysis/notes.md").expect("path");
+        assert_eq!(notes, dir.join("analysis/notes.md"));
+        assert!(dir.join("analysis").is_dir());
+        for bad in ["", "../outside.txt", "/etc/passwd", "a/../../b"] {
+            assert!(scratch_path(&dir, bad).is_err(), "{bad} should be rejected");
+        }
+
+        fs::write(&notes, "hello").expect("write");
+        fs::write(dir.join("out.json"), "{}").expect("write");
+        assert_eq!(
+            list_scratch(&dir).expect("list"),
+            vec![
+                ("analysis/notes.md".to_owned(), 5),
+                ("out.json".to_owned(), 2)
+            ]
+        );
+        assert_eq!(clear_scratch(&dir).expect("clear"), 2);
+        assert!(dir.is_dir());
+        assert!(list_scratch(&dir).expect("list").is_empty());
+    }
+}
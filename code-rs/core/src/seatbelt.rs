@@ -1,15 +1,12 @@
 use std::fmt::Write as _;
 
 use crate::managed_network_proxy_api::ALLOW_LOCAL_BINDING_ENV_KEY;
-use crate::managed_network_proxy_api::PROXY_URL_ENV_KEYS;
 use crate::managed_network_proxy_api::has_proxy_url_env_vars;
-use crate::managed_network_proxy_api::proxy_url_env_value;
-use std::collections::BTreeSet;
+use crate::managed_network_proxy_api::proxy_loopback_ports_from_env;
 use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
 use tokio::process::Child;
-use url::Url;
 
 use crate::protocol::SandboxPolicy;
 use crate::spawn::CODEX_SANDBOX_ENV_VAR;
@@ -79,53 +76,6 @@ pub async fn spawn_command_under_seatbelt(
     .await
 }
 
-fn is_loopback_host(host: &str) -> bool {
-    host.eq_ignore_ascii_case("localhost") || host == "127.0.0.1" || host == "::1"
-}
-
-fn proxy_scheme_default_port(scheme: &str) -> u16 {
-    match scheme {
-        "https" => 443,
-        "socks5" | "socks5h" | "socks4" | "socks4a" => 1080,
-        _ => 80,
-    }
-}
-
-fn proxy_loopback_ports_from_env(env: &HashMap<String, String>) -> Vec<u16> {
-    let mut ports = BTreeSet::new();
-    for key in PROXY_URL_ENV_KEYS {
-        let Some(proxy_url) = proxy_url_env_value(env, key) else {
-            continue;
-        };
-        let trimmed = proxy_url.trim();
-        if trimmed.is_empty() {
-            continue;
-        }
-
-        let candidate = if trimmed.contains("://") {
-            trimmed.to_owned()
-        } else {
-            format!("http://{trimmed}")
-        };
-        let Ok(parsed) = Url::parse(&candidate) else {
-            continue;
-        };
-        let Some(host) = parsed.host_str() else {
-            continue;
-        };
-        if !is_loopback_host(host) {
-            continue;
-        }
-
-        let scheme = parsed.scheme().to_ascii_lowercase();
-        let port = parsed
-            .port()
-            .unwrap_or_else(|| proxy_scheme_default_port(scheme.as_str()));
-        ports.insert(port);
-    }
-    ports.into_iter().collect()
-}
-
 fn local_binding_enabled(env: &HashMap<String, String>) -> bool {
     env.get(ALLOW_LOCAL_BINDING_ENV_KEY).is_some_and(|value| {
         let trimmed = value.trim();
@@ -142,6 +92,14 @@ fn dynamic_network_policy(
     if !ports.is_empty() {
         let mut policy =
             String::from("; allow outbound access only to configured loopback proxy endpoints\n");
+        let allowlist = sandbox_policy.network_allowlist();
+        if !allowlist.is_empty() {
+            let _ = writeln!(
+                policy,
+                "; the proxy admits the {} network_allowlist entries",
+                allowlist.len()
+            );
+        }
         if local_binding_enabled(env) {
             policy.push_str("; allow localhost-only binding and loopback traffic\n");
             policy.push_str("(allow network-bind (local ip \"localhost:*\"))\n");
@@ -169,6 +127,8 @@ fn dynamic_network_policy(
         return String::new();
     }
 
+    // A network allowlist is only enforced through the proxy, so without
+    // proxy endpoints the network stays closed.
     if sandbox_policy.has_full_network_access() {
         format!("(allow network-outbound)\n(allow network-inbound)\n{MACOS_SEATBELT_NETWORK_POLICY}")
     } else {
@@ -333,6 +293,7 @@ mod tests {
         let policy = SandboxPolicy::WorkspaceWrite {
             writable_roots: vec![root_with_git, root_without_git],
             network_access: false,
+            network_allowlist: vec![],
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: true,
             allow_git_writes: false,
@@ -412,6 +373,7 @@ mod tests {
         let policy = SandboxPolicy::WorkspaceWrite {
             writable_roots: vec![],
             network_access: false,
+            network_allowlist: vec![],
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
             allow_git_writes: false,
@@ -526,6 +488,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn network_allowlist_only_reaches_the_loopback_proxy() {
+        let policy = SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![],
+            network_access: false,
+            network_allowlist: vec!["crates.io".to_string(), "10.20.0.0/16".to_string()],
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+            allow_git_writes: true,
+        };
+        let cwd = Path::new("/tmp");
+        let command = vec!["/usr/bin/true".to_string()];
+
+        let mut env = std::collections::HashMap::new();
+        let args = super::build_seatbelt_args(command.clone(), &policy, cwd, false, &env);
+        assert!(
+            !args[1].contains("network-outbound"),
+            "an allowlist without proxy endpoints must not open the network"
+        );
+
+        env.insert("HTTPS_PROXY".to_string(), "http://127.0.0.1:3128".to_string());
+        let args = super::build_seatbelt_args(command, &policy, cwd, true, &env);
+        assert!(args[1].contains("(allow network-outbound (remote ip \"localhost:3128\"))"));
+        assert!(args[1].contains("; the proxy admits the 2 network_allowlist entries"));
+        assert!(!args[1].contains("(allow network-outbound)\n"));
+    }
+
     struct PopulatedTmp {
         root_with_git: PathBuf,
         root_without_git: PathBuf,
@@ -5,6 +5,7 @@ use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
 use code_protocol::protocol::SessionSource;
 use once_cell::sync::OnceCell;
 use tokio::task;
@@ -375,6 +376,53 @@ impl SessionCatalog {
         Ok(true)
     }
 
+    /// Delete every session recorded under `project_root` (archived ones
+    /// included) whose last event is older than `cutoff`, together with its
+    /// snapshot, except `keep`. Returns how many sessions were removed.
+    pub async fn delete_sessions_older_than(
+        &self,
+        project_root: &Path,
+        cutoff: DateTime<Utc>,
+        keep: Option<Uuid>,
+    ) -> Result<usize> {
+        let mut catalog = self.load_inner().await?;
+        let expired: Vec<SessionIndexEntry> = catalog
+            .all_ordered()
+            .into_iter()
+            .filter(|entry| Some(entry.session_id) != keep)
+            .filter(|entry| entry.cwd_real.starts_with(project_root))
+            .filter(|entry| {
+                DateTime::parse_from_rfc3339(&entry.last_event_at)
+                    .is_ok_and(|last| last.with_timezone(&Utc) < cutoff)
+            })
+            .cloned()
+            .collect();
+        if expired.is_empty() {
+            return Ok(0);
+        }
+
+        for entry in &expired {
+            let rollout_path = self.entry_rollout_path(entry);
+            for path in [rollout_path.with_extension("snapshot.json"), rollout_path] {
+                match tokio::fs::remove_file(&path).await {
+                    Ok(()) => {}
+                    Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                    Err(err) => {
+                        return Err(err)
+                            .with_context(|| format!("failed to delete {}", path.display()));
+                    }
+                }
+            }
+            catalog
+                .remove(&entry.session_id)
+                .context("failed to remove catalog entry")?;
+        }
+
+        let mut guard = self.cache.lock().await;
+        *guard = Some(catalog);
+        Ok(expired.len())
+    }
+
     async fn load_inner(&self) -> Result<rollout_catalog::SessionCatalog> {
         {
             let mut guard = self.cache.lock().await;
@@ -0,0 +1,86 @@
+//! Hand-off summaries written by `code exec --handoff` at the end of a
+//! headless run and shown pinned at the top of the conversation the next time
+//! that session is resumed in the TUI.
+//!
+//! Summaries live in `$CODE_HOME/handoffs/<session id>.md`. The TUI takes the
+//! file when it shows the summary, so a hand-off is only pinned once.
+
+use std::fmt::Display;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+const HANDOFFS_DIR: &str = "handoffs";
+
+/// Follow-up turn that asks the model for the hand-off summary.
+pub const HANDOFF_PROMPT: &str = "This headless run is over and a person will pick up the work \
+interactively. Write a hand-off summary for them in Markdown with exactly these sections:\n\
+\n\
+## State of work\nWhat was done and what state the workspace is in (files changed, commands \
+that pass or fail).\n\
+\n\
+## Open problems\nAnything unfinished, broken, or uncertain, with the relevant files or errors.\n\
+\n\
+## Next steps\nA short ordered list of what to do next.\n\
+\n\
+Be concise and specific. Do not run any more commands or change any files.";
+
+/// Where the hand-off for `session_id` is stored.
+pub fn handoff_path(code_home: &Path, session_id: impl Display) -> PathBuf {
+    code_home
+        .join(HANDOFFS_DIR)
+        .join(format!("{session_id}.md"))
+}
+
+/// Stores `summary` as the hand-off for `session_id`, replacing an earlier
+/// one, and returns its path.
+pub fn save_handoff(
+    code_home: &Path,
+    session_id: impl Display,
+    summary: &str,
+) -> std::io::Result<PathBuf> {
+    let path = handoff_path(code_home, session_id);
+    let Some(dir) = path.parent() else {
+        return Err(std::io::Error::other("no directory for hand-off summaries"));
+    };
+    fs::create_dir_all(dir)?;
+    let tmp = tempfile::NamedTempFile::new_in(dir)?;
+    fs::write(tmp.path(), format!("{}\n", summary.trim()))?;
+    tmp.persist(&path).map_err(|err| err.error)?;
+    Ok(path)
+}
+
+/// Reads and removes the hand-off for `session_id`, if there is one.
+pub fn take_handoff(code_home: &Path, session_id: impl Display) -> std::io::Result<Option<String>> {
+    let path = handoff_path(code_home, session_id);
+    let summary = match fs::read_to_string(&path) {
+        Ok(summary) => summary,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err),
+    };
+    fs::remove_file(&path)?;
+    let summary = summary.trim();
+    Ok((!summary.is_empty()).then(|| summary.to_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn saved_handoff_is_taken_once() {
+        let code_home = TempDir::new().expect("tempdir");
+        let home = code_home.path();
+        assert_eq!(take_handoff(home, "abc").expect("missing"), None);
+
+        let path = save_handoff(home, "abc", "## State of work\nDone.\n\n").expect("save");
+        assert_eq!(path, home.join("handoffs").join("abc.md"));
+        assert_eq!(
+            take_handoff(home, "abc").expect("take").as_deref(),
+            Some("## State of work\nDone.")
+        );
+        assert_eq!(take_handoff(home, "abc").expect("taken"), None);
+    }
+}
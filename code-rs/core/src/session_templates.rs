@@ -0,0 +1,440 @@
+//! Conversation templates for recurring kinds of work, defined in
+//! `.code/templates/<name>.toml` at the repository root (or the working
+//! directory outside a repository) and started with `code new --template`.
+//!
+//! A template names the files, repository map sections and earlier session
+//! summaries to attach, plus instructions for the model. They are rendered
+//! into a context pack that joins the session's user instructions.
+
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+use code_file_search::CodeIgnore;
+use globset::Glob;
+use serde::Deserialize;
+use walkdir::WalkDir;
+
+use crate::util::truncate_on_char_boundary;
+
+const TEMPLATE_EXTENSION: &str = "toml";
+/// Per-file cap for attached files and session summaries.
+const MAX_ITEM_BYTES: usize = 16_000;
+/// Cap for the whole pack; items past it are listed as omitted.
+const MAX_PACK_BYTES: usize = 120_000;
+/// Files a single glob may attach.
+const MAX_GLOB_MATCHES: usize = 20;
+/// Entries listed per repository map section.
+const MAX_MAP_ENTRIES: usize = 200;
+const MAP_DEPTH: usize = 3;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SessionTemplate {
+    #[serde(skip)]
+    pub name: String,
+    #[serde(skip)]
+    pub path: PathBuf,
+    /// One line shown by `code new --list`.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Instructions for the model at the top of the pack.
+    #[serde(default)]
+    pub instructions: Option<String>,
+    /// First message sent when `code new` is given no prompt.
+    #[serde(default)]
+    pub prompt: Option<String>,
+    /// Files to attach, relative to the project root; globs are allowed and
+    /// skip paths covered by a `.codeignore`.
+    #[serde(default)]
+    pub files: Vec<String>,
+    /// Directories whose layout is listed, relative to the project root,
+    /// leaving out `.codeignore`d paths.
+    #[serde(default)]
+    pub repo_map: Vec<String>,
+    /// Summaries of earlier sessions to include.
+    #[serde(default)]
+    pub sessions: TemplateSessions,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TemplateSessions {
+    /// Most recent matching summaries to include (0 = none).
+    #[serde(default)]
+    pub limit: usize,
+    /// Only summaries mentioning one of these words (case-insensitive); all
+    /// summaries qualify when empty.
+    #[serde(default)]
+    pub keywords: Vec<String>,
+}
+
+/// `.code/templates` for the project containing `cwd`.
+pub fn templates_dir(cwd: &Path) -> PathBuf {
+    project_root(cwd).join(".code").join("templates")
+}
+
+fn project_root(cwd: &Path) -> PathBuf {
+    crate::git_info::get_git_repo_root(cwd).unwrap_or_else(|| cwd.to_path_buf())
+}
+
+/// Templates defined for the project, sorted by name. Files that fail to
+/// parse are reported as errors rather than skipped.
+pub fn list_templates(cwd: &Path) -> std::io::Result<Vec<SessionTemplate>> {
+    let dir = templates_dir(cwd);
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    let mut templates = Vec::new();
+    for entry in entries.filter_map(Result::ok) {
+        let path = entry.path();
+        let is_template = path.is_file()
+            && path
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case(TEMPLATE_EXTENSION));
+        if !is_template {
+            continue;
+        }
+        if let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) {
+            templates.push(parse_template(name, &path)?);
+        }
+    }
+    templates.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(templates)
+}
+
+/// Loads `.code/templates/<name>.toml`.
+pub fn load_template(cwd: &Path, name: &str) -> std::io::Result<SessionTemplate> {
+    let valid_name = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        && !name.starts_with('.');
+    if !valid_name {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("`{name}` is not a valid template name"),
+        ));
+    }
+    let path = templates_dir(cwd).join(format!("{name}.{TEMPLATE_EXTENSION}"));
+    if !path.is_file() {
+        let available: Vec<String> = list_templates(cwd)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|template| template.name)
+            .collect();
+        let hint = if available.is_empty() {
+            format!("no templates are defined in {}", templates_dir(cwd).display())
+        } else {
+            format!("available: {}", available.join(", "))
+        };
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("template `{name}` not found ({hint})"),
+        ));
+    }
+    parse_template(name, &path)
+}
+
+fn parse_template(name: &str, path: &Path) -> std::io::Result<SessionTemplate> {
+    let contents = fs::read_to_string(path)?;
+    let mut template: SessionTemplate = toml::from_str(&contents).map_err(|err| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("{}: {err}", path.display()),
+        )
+    })?;
+    name.clone_into(&mut template.name);
+    template.path = path.to_path_buf();
+    Ok(template)
+}
+
+/// Renders the template's context pack: instructions, attached files, the
+/// repository map and related session summaries, within a size budget.
+/// Missing files are noted in the pack instead of failing the session.
+pub fn build_context_pack(template: &SessionTemplate, cwd: &Path, code_home: &Path) -> String {
+    let root = project_root(cwd);
+    let mut pack = PackWriter::default();
+    pack.push(format!("# Session template: {}\n", template.name));
+    if let Some(instructions) = template.instructions.as_deref().map(str::trim)
+        && !instructions.is_empty()
+    {
+        pack.push(format!("\n{instructions}\n"));
+    }
+
+    let files = resolve_files(&root, &template.files);
+    if !files.is_empty() {
+        pack.push("\n## Attached files\n".to_owned());
+    }
+    for file in files {
+        match file {
+            Ok(path) => {
+                let rel = relative_display(&root, &path);
+                match fs::read_to_string(&path) {
+                    Ok(contents) => {
+                        let text = format!("\n### {rel}\n```\n{}\n```\n", cap(&contents));
+                        pack.push_item(&rel, text);
+                    }
+                    Err(err) => pack.push(format!("\n### {rel}\n(unreadable: {err})\n")),
+                }
+            }
+            Err(missing) => {
+                pack.push(format!("\n### {missing}\n(no matching files in the project)\n"));
+            }
+        }
+    }
+
+    if !template.repo_map.is_empty() {
+        pack.push("\n## Repository map\n".to_owned());
+    }
+    for section in &template.repo_map {
+        let listing = if inside_project(section) {
+            repo_map_section(&root, &root.join(section))
+        } else {
+            "(directory not found)\n".to_owned()
+        };
+        pack.push_item(section, format!("\n### {section}\n{listing}"));
+    }
+
+    let summaries = related_session_summaries(code_home, &template.sessions);
+    if !summaries.is_empty() {
+        pack.push("\n## Related earlier sessions\n".to_owned());
+    }
+    for (slug, summary) in summaries {
+        pack.push_item(&slug, format!("\n### {slug}\n{}\n", cap(summary.trim())));
+    }
+    pack.finish()
+}
+
+#[derive(Default)]
+struct PackWriter {
+    out: String,
+    omitted: Vec<String>,
+}
+
+impl PackWriter {
+    fn push(&mut self, text: String) {
+        self.out.push_str(&text);
+    }
+
+    /// Adds an item unless it would push the pack over its budget.
+    fn push_item(&mut self, label: &str, text: String) {
+        if self.out.len() + text.len() > MAX_PACK_BYTES {
+            self.omitted.push(label.to_owned());
+        } else {
+            self.out.push_str(&text);
+        }
+    }
+
+    fn finish(mut self) -> String {
+        if !self.omitted.is_empty() {
+            self.out.push_str(&format!(
+                "\n(Omitted to stay within the context budget: {}. Read them directly if needed.)\n",
+                self.omitted.join(", ")
+            ));
+        }
+        self.out
+    }
+}
+
+fn cap(text: &str) -> String {
+    if text.len() <= MAX_ITEM_BYTES {
+        return text.to_owned();
+    }
+    format!(
+        "{}\n[... truncated; {} bytes total]",
+        truncate_on_char_boundary(text, MAX_ITEM_BYTES),
+        text.len()
+    )
+}
+
+fn relative_display(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+/// Entries are relative to the project root and may not leave it.
+fn inside_project(entry: &str) -> bool {
+    let path = Path::new(entry);
+    !path.is_absolute()
+        && path
+            .components()
+            .all(|component| !matches!(component, std::path::Component::ParentDir))
+}
+
+fn is_glob(pattern: &str) -> bool {
+    pattern.contains(['*', '?', '[', '{'])
+}
+
+/// Each entry's files in order; `Err` carries an entry that matched nothing.
+fn resolve_files(root: &Path, entries: &[String]) -> Vec<Result<PathBuf, String>> {
+    let mut out: Vec<Result<PathBuf, String>> = Vec::new();
+    for entry in entries {
+        if !inside_project(entry) {
+            out.push(Err(entry.clone()));
+            continue;
+        }
+        let mut matched: Vec<PathBuf> = if is_glob(entry) {
+            let Ok(glob) = Glob::new(entry) else {
+                out.push(Err(entry.clone()));
+                continue;
+            };
+            let matcher = glob.compile_matcher();
+            let codeignore = CodeIgnore::new(root);
+            WalkDir::new(root)
+                .into_iter()
+                .filter_entry(|dir_entry| keep_entry(&codeignore, dir_entry))
+                .filter_map(Result::ok)
+                .filter(|dir_entry| dir_entry.file_type().is_file())
+                .map(walkdir::DirEntry::into_path)
+                .filter(|path| matcher.is_match(path.strip_prefix(root).unwrap_or(path)))
+                .take(MAX_GLOB_MATCHES)
+                .collect()
+        } else {
+            let path = root.join(entry);
+            if path.is_file() { vec![path] } else { Vec::new() }
+        };
+        if matched.is_empty() {
+            out.push(Err(entry.clone()));
+            continue;
+        }
+        matched.sort();
+        for path in matched {
+            if !out.iter().any(|existing| existing.as_ref() == Ok(&path)) {
+                out.push(Ok(path));
+            }
+        }
+    }
+    out
+}
+
+/// Skips `.git` and anything a `.codeignore` covers; files named explicitly
+/// in a template are still attached.
+fn keep_entry(codeignore: &CodeIgnore, entry: &walkdir::DirEntry) -> bool {
+    entry.file_name() != ".git" && !codeignore.is_ignored(entry.path(), entry.file_type().is_dir())
+}
+
+fn repo_map_section(root: &Path, dir: &Path) -> String {
+    if !dir.is_dir() {
+        return "(directory not found)\n".to_owned();
+    }
+    let mut lines = Vec::new();
+    let mut total = 0usize;
+    let codeignore = CodeIgnore::new(root);
+    let walker = WalkDir::new(dir)
+        .min_depth(1)
+        .max_depth(MAP_DEPTH)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| keep_entry(&codeignore, entry));
+    for entry in walker.filter_map(Result::ok) {
+        total += 1;
+        if lines.len() >= MAX_MAP_ENTRIES {
+            continue;
+        }
+        let suffix = if entry.file_type().is_dir() { "/" } else { "" };
+        lines.push(format!("- {}{suffix}", relative_display(root, entry.path())));
+    }
+    if total > lines.len() {
+        lines.push(format!("- ... {} more entries", total - lines.len()));
+    }
+    format!("{}\n", lines.join("\n"))
+}
+
+/// The newest session summaries matching `sessions`, as `(slug, summary)`.
+fn related_session_summaries(code_home: &Path, sessions: &TemplateSessions) -> Vec<(String, String)> {
+    if sessions.limit == 0 {
+        return Vec::new();
+    }
+    let keywords: Vec<String> = sessions
+        .keywords
+        .iter()
+        .map(|keyword| keyword.trim().to_lowercase())
+        .filter(|keyword| !keyword.is_empty())
+        .collect();
+    let entries = match crate::memories::list_rollout_summaries(code_home) {
+        Ok(entries) => entries,
+        Err(err) => {
+            tracing::warn!("failed to list session summaries for a template: {err}");
+            return Vec::new();
+        }
+    };
+    entries
+        .into_iter()
+        .filter_map(|entry| {
+            let summary = crate::memories::read_rollout_summary(code_home, &entry.slug).ok()??;
+            let lower = summary.to_lowercase();
+            let related = keywords.is_empty() || keywords.iter().any(|keyword| lower.contains(keyword));
+            related.then_some((entry.slug, summary))
+        })
+        .take(sessions.limit)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn loads_templates_and_builds_the_pack() {
+        let project = TempDir::new().expect("tempdir");
+        let code_home = TempDir::new().expect("code home");
+        let root = project.path();
+        fs::create_dir_all(root.join(".code/templates")).expect("templates dir");
+        fs::create_dir_all(root.join("api/src")).expect("src dir");
+        fs::write(root.join("api/src/routes.rs"), "fn routes() {}").expect("routes");
+        fs::write(root.join("api/src/db.rs"), "fn db() {}").expect("db");
+        fs::write(
+            root.join(".code/templates/backend-bugfix.toml"),
+            r#"
+description = "Fix a backend bug"
+instructions = "Reproduce the bug with a failing test first."
+files = ["api/src/*.rs", "docs/missing.md"]
+repo_map = ["api"]
+"#,
+        )
+        .expect("template");
+
+        let templates = list_templates(root).expect("list");
+        assert_eq!(
+            templates.iter().map(|template| template.name.as_str()).collect::<Vec<_>>(),
+            vec!["backend-bugfix"]
+        );
+        let template = load_template(root, "backend-bugfix").expect("load");
+        assert_eq!(template.description.as_deref(), Some("Fix a backend bug"));
+
+        let pack = build_context_pack(&template, root, code_home.path());
+        assert!(pack.starts_with(
+            "# Session template: backend-bugfix\n\nReproduce the bug with a failing test first.\n"
+        ));
+        assert!(pack.contains("### api/src/db.rs\n```\nfn db() {}\n```"));
+        assert!(pack.contains("### api/src/routes.rs\n```\nfn routes() {}\n```"));
+        assert!(pack.contains("### docs/missing.md\n(no matching files in the project)"));
+        assert!(!inside_project("../outside.rs"));
+        assert!(pack.contains("### api\n- api/src/\n- api/src/db.rs\n- api/src/routes.rs\n"));
+
+        let err = load_template(root, "frontend").expect_err("missing template");
+        assert!(err.to_string().contains("available: backend-bugfix"));
+        assert!(load_template(root, "../secrets").is_err());
+    }
+
+    #[test]
+    fn codeignored_paths_stay_out_of_globs_and_the_map() {
+        let project = TempDir::new().expect("tempdir");
+        let root = project.path();
+        fs::create_dir_all(root.join("api/generated")).expect("generated dir");
+        fs::write(root.join("api/lib.rs"), "fn lib() {}").expect("lib");
+        fs::write(root.join("api/generated/client.rs"), "fn client() {}").expect("client");
+        fs::write(root.join(".codeignore"), "generated/\n").expect("codeignore");
+
+        let files = resolve_files(root, &["api/**/*.rs".to_owned()]);
+        assert_eq!(files, vec![Ok(root.join("api/lib.rs"))]);
+        assert_eq!(repo_map_section(root, &root.join("api")), "- api/lib.rs\n");
+    }
+}
@@ -191,6 +191,36 @@ impl Shell {
         }
     }
 
+    /// The rc file sourced before commands, for shells that have one.
+    pub(crate) fn rc_path(&self) -> Option<&str> {
+        match self {
+            Shell::Zsh(zsh) => Some(&zsh.zshrc_path),
+            Shell::Bash(bash) => Some(&bash.bashrc_path),
+            Shell::PowerShell(_) | Shell::Generic(_) | Shell::Unknown => None,
+        }
+    }
+
+    /// Runs `script` after sourcing a captured environment snapshot (see
+    /// `shell_snapshot`) in a plain, non-login shell. Only Bash and Zsh
+    /// produce snapshots.
+    pub(crate) fn format_snapshot_invocation(
+        &self,
+        script: &str,
+        snapshot: &std::path::Path,
+    ) -> Option<Vec<String>> {
+        let shell_path = match self {
+            Shell::Zsh(zsh) => &zsh.shell_path,
+            Shell::Bash(bash) => &bash.shell_path,
+            Shell::PowerShell(_) | Shell::Generic(_) | Shell::Unknown => return None,
+        };
+        let snapshot = shlex::try_quote(snapshot.to_str()?).ok()?;
+        Some(vec![
+            shell_path.clone(),
+            "-c".to_owned(),
+            format_command_with_rc(&snapshot, script),
+        ])
+    }
+
     pub fn name(&self) -> Option<String> {
         match self {
             Shell::Zsh(zsh) => std::path::Path::new(&zsh.shell_path)
@@ -266,8 +296,7 @@ fn format_shell_invocation_with_rc(
     shell_path: &str,
     rc_path: &str,
 ) -> Option<Vec<String>> {
-    let joined = extract_script_argument(command)
-        .or_else(|| shlex::try_join(command.iter().map(String::as_str)).ok())?;
+    let joined = script_for_command(command)?;
 
     let rc_command = if std::path::Path::new(rc_path).exists() {
         format!("source {rc_path} && ({joined})")
@@ -278,6 +307,13 @@ fn format_shell_invocation_with_rc(
     Some(vec![shell_path.to_owned(), "-lc".to_owned(), rc_command])
 }
 
+/// The script a default-shell invocation would run for `command`: the
+/// `-c`/`-lc` argument of a shell wrapper, or the argv joined for a shell.
+pub(crate) fn script_for_command(command: &[String]) -> Option<String> {
+    extract_script_argument(command)
+        .or_else(|| shlex::try_join(command.iter().map(String::as_str)).ok())
+}
+
 /// Extract the script text from a shell invocation of the form
 /// `[shell, "-c"|"-lc", script]` where `shell` is any recognized shell
 /// executable.
@@ -602,6 +638,28 @@ mod tests_common {
         );
     }
 
+    #[test]
+    fn snapshot_invocation_sources_quoted_snapshot_without_login() {
+        let shell = Shell::Bash(BashShell {
+            shell_path: "/bin/bash".to_string(),
+            bashrc_path: "/home/u/.bashrc".to_string(),
+        });
+
+        let invocation = shell.format_snapshot_invocation(
+            "echo hi",
+            std::path::Path::new("/tmp/snap shot.sh"),
+        );
+
+        assert_eq!(
+            invocation,
+            Some(vec![
+                "/bin/bash".to_string(),
+                "-c".to_string(),
+                "source '/tmp/snap shot.sh' && (echo hi)".to_string(),
+            ])
+        );
+    }
+
     #[test]
     fn generic_cmd_shell_unwraps_shell_wrappers_before_c() {
         let shell = Shell::Generic(GenericShell {
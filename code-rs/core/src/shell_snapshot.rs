@@ -0,0 +1,220 @@
+//! Per-session capture of the user's interactive shell environment.
+//!
+//! With `shell_environment_policy.experimental_use_snapshot`, the first exec
+//! call that would source `~/.bashrc` / `~/.zshrc` instead runs the rc file
+//! once in a login shell and dumps the resulting exports, aliases and
+//! functions to a script under `CODE_HOME/shell_snapshots`. Later commands
+//! source that script with a plain `-c` shell, skipping login and rc
+//! processing. The snapshot is re-captured whenever the rc file's size or
+//! modification time changes, and removed when the session ends.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use tokio::sync::Mutex;
+use tracing::debug;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::shell::Shell;
+
+/// Upper bound on how long capturing a snapshot may take before we fall back
+/// to sourcing the rc file directly.
+const SNAPSHOT_CAPTURE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Identifies one version of an rc file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct RcStamp {
+    len: u64,
+    modified: Option<SystemTime>,
+}
+
+impl RcStamp {
+    fn read(rc_path: &Path) -> Option<Self> {
+        let meta = std::fs::metadata(rc_path).ok()?;
+        Some(Self {
+            len: meta.len(),
+            modified: meta.modified().ok(),
+        })
+    }
+}
+
+#[derive(Debug)]
+enum SnapshotState {
+    Captured { path: PathBuf, stamp: RcStamp },
+    /// Capture failed for this rc version; don't retry until it changes.
+    Failed { stamp: RcStamp },
+}
+
+#[derive(Debug)]
+pub(crate) struct ShellSnapshotCache {
+    path: PathBuf,
+    state: Mutex<Option<SnapshotState>>,
+}
+
+impl ShellSnapshotCache {
+    pub(crate) fn new(code_home: &Path, session_id: Uuid) -> Self {
+        Self {
+            path: code_home
+                .join("shell_snapshots")
+                .join(format!("{session_id}.sh")),
+            state: Mutex::new(None),
+        }
+    }
+
+    /// Returns the snapshot to source for `shell`, capturing it first if
+    /// there is none yet or the rc file changed since the last capture.
+    /// `None` means the caller should fall back to sourcing the rc file.
+    pub(crate) async fn snapshot_for(&self, shell: &Shell) -> Option<PathBuf> {
+        let rc_path = Path::new(shell.rc_path()?);
+        let stamp = RcStamp::read(rc_path)?;
+
+        let mut state = self.state.lock().await;
+        match state.as_ref() {
+            Some(SnapshotState::Captured { path, stamp: captured })
+                if *captured == stamp && path.exists() =>
+            {
+                return Some(path.clone());
+            }
+            Some(SnapshotState::Failed { stamp: failed }) if *failed == stamp => return None,
+            _ => {}
+        }
+
+        match capture_snapshot(shell, rc_path, &self.path).await {
+            Ok(()) => {
+                debug!("captured shell snapshot at {}", self.path.display());
+                *state = Some(SnapshotState::Captured {
+                    path: self.path.clone(),
+                    stamp,
+                });
+                Some(self.path.clone())
+            }
+            Err(err) => {
+                warn!("shell snapshot capture failed; sourcing rc file instead: {err}");
+                *state = Some(SnapshotState::Failed { stamp });
+                None
+            }
+        }
+    }
+}
+
+impl Drop for ShellSnapshotCache {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Script run inside a login shell after sourcing the rc file. Readonly
+/// variables are skipped because re-declaring them in the command shell
+/// would fail.
+fn dump_script(shell: &Shell, rc_path: &Path) -> Option<String> {
+    let rc = shlex::try_quote(rc_path.to_str()?).ok()?;
+    let dump = match shell {
+        Shell::Bash(_) => {
+            "export -p | grep -v '^declare -[a-zA-Z]*r'; echo 'shopt -s expand_aliases'; alias -p; declare -f"
+        }
+        Shell::Zsh(_) => "export -p | grep -v '^typeset -[a-zA-Z]*r'; alias -L; typeset -f",
+        _ => return None,
+    };
+    Some(format!("source {rc} >/dev/null 2>&1 </dev/null; {{ {dump}; }} 2>/dev/null"))
+}
+
+async fn capture_snapshot(shell: &Shell, rc_path: &Path, dest: &Path) -> std::io::Result<()> {
+    let (Some(program), Some(script)) = (shell.shell_command_path(), dump_script(shell, rc_path)) else {
+        return Err(std::io::Error::other("shell does not support snapshots"));
+    };
+    let child = tokio::process::Command::new(program)
+        .arg("-lc")
+        .arg(script)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()?;
+    let output = tokio::time::timeout(SNAPSHOT_CAPTURE_TIMEOUT, child.wait_with_output())
+        .await
+        .map_err(|_| std::io::Error::other("timed out capturing shell snapshot"))??;
+    if !output.status.success() {
+        return Err(std::io::Error::other(format!(
+            "snapshot shell exited with {}",
+            output.status
+        )));
+    }
+
+    if let Some(parent) = dest.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let mut contents = format!("# Shell snapshot of {}\n", rc_path.display()).into_bytes();
+    contents.extend_from_slice(&output.stdout);
+    tokio::fs::write(dest, contents).await
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::shell::BashShell;
+    use tempfile::tempdir;
+
+    fn bash_with_rc(rc_path: &Path) -> Option<Shell> {
+        let bash = which::which("bash").ok()?;
+        Some(Shell::Bash(BashShell {
+            shell_path: bash.to_string_lossy().into_owned(),
+            bashrc_path: rc_path.to_string_lossy().into_owned(),
+        }))
+    }
+
+    #[tokio::test]
+    async fn snapshot_is_reused_until_rc_changes() {
+        let tmp = tempdir().expect("tempdir");
+        let rc_path = tmp.path().join(".bashrc");
+        std::fs::write(&rc_path, "export SNAPSHOT_PROBE=one\n").expect("write rc");
+        let Some(shell) = bash_with_rc(&rc_path) else {
+            return;
+        };
+        let cache = ShellSnapshotCache::new(tmp.path(), Uuid::new_v4());
+
+        let first = cache.snapshot_for(&shell).await.expect("snapshot captured");
+        let contents = std::fs::read_to_string(&first).expect("read snapshot");
+        assert!(contents.contains("SNAPSHOT_PROBE"), "{contents}");
+
+        // Same rc: the existing file is reused, so a marker we append survives.
+        std::fs::write(&first, format!("{contents}# marker\n")).expect("mark snapshot");
+        let again = cache.snapshot_for(&shell).await.expect("snapshot reused");
+        assert!(std::fs::read_to_string(&again).expect("read").contains("# marker"));
+
+        // Editing the rc (different length) triggers a fresh capture.
+        std::fs::write(&rc_path, "export SNAPSHOT_PROBE=second-value\n").expect("rewrite rc");
+        let refreshed = cache.snapshot_for(&shell).await.expect("snapshot recaptured");
+        let contents = std::fs::read_to_string(&refreshed).expect("read snapshot");
+        assert!(contents.contains("second-value"), "{contents}");
+        assert!(!contents.contains("# marker"));
+    }
+
+    #[tokio::test]
+    async fn missing_rc_means_no_snapshot() {
+        let tmp = tempdir().expect("tempdir");
+        let Some(shell) = bash_with_rc(&tmp.path().join("missing")) else {
+            return;
+        };
+        let cache = ShellSnapshotCache::new(tmp.path(), Uuid::new_v4());
+        assert_eq!(cache.snapshot_for(&shell).await, None);
+    }
+
+    #[tokio::test]
+    async fn snapshot_file_is_removed_with_the_cache() {
+        let tmp = tempdir().expect("tempdir");
+        let rc_path = tmp.path().join(".bashrc");
+        std::fs::write(&rc_path, "alias ll='ls -l'\n").expect("write rc");
+        let Some(shell) = bash_with_rc(&rc_path) else {
+            return;
+        };
+        let cache = ShellSnapshotCache::new(tmp.path(), Uuid::new_v4());
+        let path = cache.snapshot_for(&shell).await.expect("snapshot captured");
+        assert!(path.exists());
+        drop(cache);
+        assert!(!path.exists());
+    }
+}
@@ -3,9 +3,12 @@ use crate::agent_defaults::enabled_agent_model_specs;
 use crate::config_types::AgentConfig;
 use crate::config_types::SubagentCommandConfig;
 use crate::external_agent_command_exists;
+use crate::protocol::ApprovedCommandMatchKind;
+use std::path::Path;
+use std::path::PathBuf;
 
 // NOTE: These are the prompt formatters for the prompt‑expanding slash commands
-// (/plan, /solve, /code). If you add or change a slash command, please update
+// (/plan, /solve, /code, /repro). If you add or change a slash command, please update
 // the user documentation in `docs/slash-commands.md` so the list stays in sync
 // with the UI and behavior.
 
@@ -215,6 +218,196 @@ pub fn format_code_command(
     res.prompt
 }
 
+/// Directory, relative to the repository root, that `/repro` commits
+/// reproduction scripts to.
+pub const REPRO_DIR: &str = "repro";
+
+/// Format the /repro command into a prompt for the LLM. Unlike the
+/// multi-agent commands this runs as a single, step-by-step flow: the turn is
+/// only finished once the script has been run and seen to fail.
+pub fn format_repro_command(issue: &str) -> String {
+    format!(
+        "Please perform /repro using the <instructions> and <issue> below.
+<instructions>
+    Produce a minimal script that reproduces the issue. Do not fix the issue itself.
+
+    1. Understand the issue. If it names a failing test, run that test first and note how it fails.
+    2. Write the smallest standalone script that triggers the issue as `{REPRO_DIR}/<short-name>` in the repository root, using the project's own language and tooling. The script must exit non-zero while the issue is present and exit zero once it is fixed, and print what it expected and what it got. Start it with a comment describing the issue and how to run it.
+    3. Run the script. It must fail, and fail because of the issue: a syntax error, missing dependency or unrelated crash does not count. Adjust the script and run it again until it does. Trim anything the failure does not need.
+    4. If you cannot make it fail after a few honest attempts, stop, delete the script and explain what you tried instead of committing.
+    5. Commit only the script: `git add {REPRO_DIR}/<short-name> && git commit -m \"Add reproduction for <issue summary>\"`. Leave any other changes uncommitted.
+    6. Finish with the script path, the command that runs it, and the failing output you observed.
+</instructions>
+<issue>
+    {issue}
+</issue>"
+    )
+}
+
+/// Directory, relative to the repository root, where `/docsync` writes its patches.
+pub const DOCSYNC_DIR: &str = ".code/docsync";
+
+/// Prompt for `/docsync`: bring documentation in line with the pending code
+/// changes and hand the edits back as a patch instead of applying them.
+pub fn format_docsync_command(focus: &str) -> String {
+    let focus = if focus.trim().is_empty() {
+        "All documentation affected by the changes.".to_owned()
+    } else {
+        focus.trim().to_owned()
+    };
+    format!(
+        "Please perform /docsync using the <instructions> and <focus> below.
+<instructions>
+    Update the documentation affected by the current code changes and deliver the updates as a separate patch. Do not change any code.
+
+    1. Find the changes to document: the uncommitted changes (`git diff HEAD`), or the last commit (`git show HEAD`) if the working tree is clean.
+    2. List what they leave stale: doc comments and docstrings on changed or removed items, module-level docs, README sections, docs/ pages, usage text and examples that mention changed names, flags, config keys or behavior. Only edit tracked files.
+    3. Snapshot the current tree before editing: `base=$(git stash create); base=${{base:-HEAD}}`. This does not touch the working tree.
+    4. Make the documentation edits. Keep each file's existing tone and format, and describe behavior as the code now implements it.
+    5. Save them as a patch and take them back out of the working tree, leaving the code changes untouched: `mkdir -p {DOCSYNC_DIR} && git diff \"$base\" > {DOCSYNC_DIR}/<timestamp>.patch && git apply -R {DOCSYNC_DIR}/<timestamp>.patch`. Do not commit.
+    6. Finish with the patch path, a one-line summary per documentation change, and the command to accept it: `git apply {DOCSYNC_DIR}/<timestamp>.patch`. If nothing needed updating, say so and write no patch.
+</instructions>
+<focus>
+    {focus}
+</focus>"
+    )
+}
+
+/// Splits `/migrate` arguments into the dependency and its target version.
+pub fn parse_migrate_args(args: &str) -> Result<(String, String), String> {
+    let mut parts = args.split_whitespace();
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(dependency), Some(version), None) => Ok((dependency.to_owned(), version.to_owned())),
+        _ => Err(
+            "Error: /migrate requires a dependency and a target version. Usage: /migrate <dependency> <version>"
+                .to_owned(),
+        ),
+    }
+}
+
+/// Auto Drive goal for `/migrate`: upgrade one dependency in phases, keeping
+/// the build and tests green between batches.
+pub fn format_migrate_goal(dependency: &str, version: &str) -> String {
+    format!(
+        "Migrate this project to {dependency} {version}. Work in the phases below and do not start a phase before the previous one is done.
+
+1. Inventory: find the version in use and every place that depends on {dependency}: manifests and lockfiles, imports, APIs called, configuration, build scripts and CI. Read the upstream changelog and migration guide for every release between the current version and {version}, and note each breaking change that affects a usage you found. Record the inventory with the plan tool, one step per affected module.
+2. Plan: for each module, decide the concrete changes and order the modules so the project builds after each one (shared code and the dependency bump itself first). Update the plan with one step per batch. Stop and report if {version} cannot be adopted (for example a required platform or peer dependency is missing).
+3. Execute: migrate one batch at a time. After each batch, run the project's build and its tests for the touched modules, and fix failures before moving on; never disable or skip tests or the validation checks to get past a failure. Mark the batch's plan step complete.
+4. Verify: run the full test suite, then summarize what changed per module, any deprecations left behind and any behavior changes to double-check."
+    )
+}
+
+/// Resolves the `/cd` argument against the session's working directory.
+/// `~` expands to the home directory; the target must be an existing
+/// directory and is returned canonicalized.
+pub fn resolve_cd_target(cwd: &Path, arg: &str) -> Result<PathBuf, String> {
+    let arg = arg.trim();
+    if arg.is_empty() {
+        return Err("Error: /cd requires a directory. Usage: /cd <path>".to_owned());
+    }
+    let expanded = match arg.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => {
+            let Some(home) = dirs::home_dir() else {
+                return Err("Error: /cd could not determine the home directory".to_owned());
+            };
+            home.join(rest.trim_start_matches('/'))
+        }
+        _ => PathBuf::from(arg),
+    };
+    let target = cwd.join(expanded);
+    if !target.is_dir() {
+        return Err(format!("Error: /cd target is not a directory: {}", target.display()));
+    }
+    dunce::canonicalize(&target)
+        .map_err(|err| format!("Error: /cd could not resolve {}: {err}", target.display()))
+}
+
+/// Parsed `/env` arguments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnvCommand {
+    List,
+    Set { name: String, value: String },
+    Unset { name: String },
+}
+
+/// Parses `/env`, `/env set NAME=value` and `/env unset NAME`.
+pub fn parse_env_command(args: &str) -> Result<EnvCommand, String> {
+    const USAGE: &str = "Usage: /env [set NAME=value | unset NAME]";
+    let args = args.trim();
+    let (action, rest) = args.split_once(char::is_whitespace).unwrap_or((args, ""));
+    let rest = rest.trim();
+    let valid_name = |name: &str| {
+        !name.is_empty()
+            && !name.starts_with(|c: char| c.is_ascii_digit())
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    };
+    match action {
+        "" | "list" if rest.is_empty() => Ok(EnvCommand::List),
+        "set" => {
+            let Some((name, value)) = rest.split_once('=') else {
+                return Err(format!("Error: /env set expects NAME=value. {USAGE}"));
+            };
+            let name = name.trim();
+            if !valid_name(name) {
+                return Err(format!("Error: invalid environment variable name `{name}`. {USAGE}"));
+            }
+            Ok(EnvCommand::Set {
+                name: name.to_owned(),
+                value: value.to_owned(),
+            })
+        }
+        "unset" if valid_name(rest) => Ok(EnvCommand::Unset {
+            name: rest.to_owned(),
+        }),
+        _ => Err(format!("Error: unrecognized /env arguments. {USAGE}")),
+    }
+}
+
+/// Parsed `/approvals` arguments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApprovalsCommand {
+    List,
+    Add {
+        command: Vec<String>,
+        match_kind: ApprovedCommandMatchKind,
+    },
+    /// 1-based position in the `/approvals` listing.
+    Revoke { index: usize },
+}
+
+/// Parses `/approvals`, `/approvals add [--prefix] <command>` and
+/// `/approvals revoke <N>`.
+pub fn parse_approvals_command(args: &str) -> Result<ApprovalsCommand, String> {
+    const USAGE: &str = "Usage: /approvals [add [--prefix] <command> | revoke <N>]";
+    let args = args.trim();
+    let (action, rest) = args.split_once(char::is_whitespace).unwrap_or((args, ""));
+    let rest = rest.trim();
+    match action {
+        "" | "list" if rest.is_empty() => Ok(ApprovalsCommand::List),
+        "add" => {
+            let (match_kind, rest) = match rest.strip_prefix("--prefix") {
+                Some(command) if command.is_empty() || command.starts_with(char::is_whitespace) => {
+                    (ApprovedCommandMatchKind::Prefix, command.trim_start())
+                }
+                _ => (ApprovedCommandMatchKind::Exact, rest),
+            };
+            let Some(command) = shlex::split(rest).filter(|argv| !argv.is_empty()) else {
+                return Err(format!("Error: /approvals add expects a command. {USAGE}"));
+            };
+            Ok(ApprovalsCommand::Add {
+                command,
+                match_kind,
+            })
+        }
+        "revoke" => match rest.parse::<usize>() {
+            Ok(index) if index > 0 => Ok(ApprovalsCommand::Revoke { index }),
+            _ => Err(format!("Error: /approvals revoke expects a number from the list. {USAGE}")),
+        },
+        _ => Err(format!("Error: unrecognized /approvals arguments. {USAGE}")),
+    }
+}
+
 /// Parse a slash command and return the formatted prompt
 pub fn handle_slash_command(input: &str, agents: Option<&[AgentConfig]>) -> Option<String> {
     let input = input.trim();
@@ -253,6 +446,17 @@ pub fn handle_slash_command(input: &str, agents: Option<&[AgentConfig]>) -> Opti
                 Some(format_code_command(&args, None, agents))
             }
         }
+        "/repro" => {
+            if args.is_empty() {
+                Some(
+                    "Error: /repro requires a failing test or bug description. Usage: /repro <issue>"
+                        .to_owned(),
+                )
+            } else {
+                Some(format_repro_command(&args))
+            }
+        }
+        "/docsync" => Some(format_docsync_command(&args)),
         _ => None,
     }
 }
@@ -357,6 +561,33 @@ mod tests {
         let code_prompt = result.unwrap();
         assert!(code_prompt.contains("Complete a coding task"));
 
+        // Test /repro command
+        let result = handle_slash_command("/repro cargo test parses_empty_config panics", None);
+        let repro_prompt = result.unwrap();
+        assert!(repro_prompt.contains("`repro/<short-name>`"));
+        assert!(repro_prompt.contains("cargo test parses_empty_config panics"));
+        assert!(!repro_prompt.contains("agent {"));
+        assert!(handle_slash_command("/repro", None).unwrap().contains("Error"));
+
+        // /migrate starts Auto Drive instead of expanding a prompt
+        assert_eq!(handle_slash_command("/migrate tokio 1.40", None), None);
+        assert_eq!(
+            parse_migrate_args("  tokio\t1.40 "),
+            Ok(("tokio".to_owned(), "1.40".to_owned()))
+        );
+        assert!(parse_migrate_args("tokio").is_err());
+        assert!(parse_migrate_args("tokio 1.40 now").is_err());
+        let goal = format_migrate_goal("tokio", "1.40");
+        assert!(goal.starts_with("Migrate this project to tokio 1.40."));
+        assert!(goal.contains("between the current version and 1.40"));
+
+        // Test /docsync command, which takes an optional focus
+        let docsync_prompt = handle_slash_command("/docsync", None).unwrap();
+        assert!(docsync_prompt.contains("git diff \"$base\" > .code/docsync/<timestamp>.patch"));
+        assert!(docsync_prompt.contains("All documentation affected by the changes."));
+        let docsync_prompt = handle_slash_command("/docsync the README", None).unwrap();
+        assert!(docsync_prompt.contains("    the README\n</focus>"));
+
         // Test invalid command
         let result = handle_slash_command("/invalid test", None);
         assert!(result.is_none());
@@ -408,4 +639,70 @@ mod tests {
         assert!(prompt.contains("code-gpt-5.4"));
         assert!(!prompt.contains("test-gemini"));
     }
+
+    #[test]
+    fn cd_resolves_relative_directories_only() {
+        let root = tempdir().expect("temp dir");
+        std::fs::create_dir(root.path().join("sibling")).expect("create sibling");
+        std::fs::write(root.path().join("file.txt"), "").expect("create file");
+        let cwd = root.path().join("sibling");
+
+        let resolved = resolve_cd_target(&cwd, "..").expect("parent resolves");
+        assert_eq!(resolved, dunce::canonicalize(root.path()).expect("canonical root"));
+        assert!(resolve_cd_target(&cwd, "../file.txt").is_err());
+        assert!(resolve_cd_target(&cwd, "missing").is_err());
+        assert!(resolve_cd_target(&cwd, " ").is_err());
+    }
+
+    #[test]
+    fn env_command_parses_set_unset_and_list() {
+        assert_eq!(parse_env_command(""), Ok(EnvCommand::List));
+        assert_eq!(
+            parse_env_command("set RUST_LOG=debug=x"),
+            Ok(EnvCommand::Set {
+                name: "RUST_LOG".to_owned(),
+                value: "debug=x".to_owned(),
+            })
+        );
+        assert_eq!(
+            parse_env_command("unset RUST_LOG"),
+            Ok(EnvCommand::Unset {
+                name: "RUST_LOG".to_owned(),
+            })
+        );
+        assert!(parse_env_command("set 1BAD=x").is_err());
+        assert!(parse_env_command("set NOVALUE").is_err());
+        assert!(parse_env_command("unset").is_err());
+    }
+
+    #[test]
+    fn approvals_command_parses_add_and_revoke() {
+        assert_eq!(parse_approvals_command(" list "), Ok(ApprovalsCommand::List));
+        assert_eq!(
+            parse_approvals_command("add --prefix cargo test -p 'code core'"),
+            Ok(ApprovalsCommand::Add {
+                command: vec![
+                    "cargo".to_owned(),
+                    "test".to_owned(),
+                    "-p".to_owned(),
+                    "code core".to_owned(),
+                ],
+                match_kind: ApprovedCommandMatchKind::Prefix,
+            })
+        );
+        assert_eq!(
+            parse_approvals_command("add --prefixed"),
+            Ok(ApprovalsCommand::Add {
+                command: vec!["--prefixed".to_owned()],
+                match_kind: ApprovedCommandMatchKind::Exact,
+            })
+        );
+        assert_eq!(
+            parse_approvals_command("revoke 2"),
+            Ok(ApprovalsCommand::Revoke { index: 2 })
+        );
+        assert!(parse_approvals_command("add --prefix").is_err());
+        assert!(parse_approvals_command("revoke 0").is_err());
+        assert!(parse_approvals_command("revoke all").is_err());
+    }
 }
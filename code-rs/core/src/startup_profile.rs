@@ -0,0 +1,109 @@
+//! Opt-in timing of session startup, enabled by `--profile-startup`.
+//!
+//! Front ends call [`enable`] before loading config, subsystems record how
+//! long their initialization took, and the session emits the collected
+//! report once the first session is configured. Recording is a no-op while
+//! profiling is disabled, so instrumented call sites cost one atomic load.
+
+use std::fmt::Write as _;
+use std::future::Future;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+static SPANS: LazyLock<Mutex<Vec<StartupSpan>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StartupSpan {
+    pub subsystem: &'static str,
+    pub elapsed: Duration,
+}
+
+pub fn enable() {
+    ENABLED.store(true, Ordering::Release);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Acquire)
+}
+
+pub fn record(subsystem: &'static str, elapsed: Duration) {
+    if !is_enabled() {
+        return;
+    }
+    if let Ok(mut spans) = SPANS.lock() {
+        spans.push(StartupSpan { subsystem, elapsed });
+    }
+}
+
+/// Records the time elapsed since `started` under `subsystem`.
+pub fn record_since(subsystem: &'static str, started: Instant) {
+    record(subsystem, started.elapsed());
+}
+
+/// Awaits `fut` and records how long it took.
+pub async fn measure<F: Future>(subsystem: &'static str, fut: F) -> F::Output {
+    let started = Instant::now();
+    let output = fut.await;
+    record_since(subsystem, started);
+    output
+}
+
+/// Drains the recorded spans into a printable report and stops recording,
+/// so later session reconfigurations do not produce another report.
+pub fn take_report() -> Option<String> {
+    if !ENABLED.swap(false, Ordering::AcqRel) {
+        return None;
+    }
+    let spans = SPANS
+        .lock()
+        .map(|mut spans| std::mem::take(&mut *spans))
+        .unwrap_or_default();
+    Some(format_report(&spans))
+}
+
+fn format_report(spans: &[StartupSpan]) -> String {
+    let mut report = String::from("Startup profile");
+    if spans.is_empty() {
+        report.push_str(": no subsystems recorded");
+        return report;
+    }
+    let width = spans
+        .iter()
+        .map(|span| span.subsystem.len())
+        .max()
+        .unwrap_or(0);
+    for span in spans {
+        let millis = span.elapsed.as_secs_f64() * 1_000.0;
+        let _ = write!(report, "\n  {:<width$}  {millis:>8.1} ms", span.subsystem);
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn report_aligns_subsystems_in_recorded_order() {
+        let report = format_report(&[
+            StartupSpan { subsystem: "config", elapsed: Duration::from_millis(12) },
+            StartupSpan { subsystem: "mcp servers", elapsed: Duration::from_micros(250_500) },
+        ]);
+        assert_eq!(
+            report,
+            "Startup profile\n  config           12.0 ms\n  mcp servers     250.5 ms"
+        );
+    }
+
+    #[test]
+    fn empty_report_says_so() {
+        assert_eq!(format_report(&[]), "Startup profile: no subsystems recorded");
+    }
+}
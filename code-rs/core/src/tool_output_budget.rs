@@ -0,0 +1,252 @@
+//! Per-category budgets for tool output recorded in conversation history.
+//!
+//! Every tool output is capped before it enters the history, using the budget
+//! of its tool's category (`[tool_output_limits]`) or `tool_output_max_bytes`
+//! when that category has none, so one noisy tool cannot crowd the rest of a
+//! turn out of the context window. Shell commands already save oversized
+//! output to a file at their budget; this pass only trims what slips past.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use code_protocol::models::FunctionCallOutputBody;
+use code_protocol::models::FunctionCallOutputContentItem;
+use code_protocol::models::FunctionCallOutputPayload;
+use code_protocol::models::ResponseItem;
+
+use crate::config_types::ToolOutputCategory;
+use crate::config_types::ToolOutputLimits;
+use crate::truncate::truncate_middle;
+
+/// Category of the tool called `name`. `is_mcp` tells whether the name
+/// belongs to a tool served by an MCP server.
+fn category_for_tool(name: &str, is_mcp: bool) -> ToolOutputCategory {
+    match name {
+        "shell" | "container.exec" | "local_shell" | "wait" | "gh_run_wait" => {
+            ToolOutputCategory::Shell
+        }
+        crate::exec_command::EXEC_COMMAND_TOOL_NAME
+        | crate::exec_command::WRITE_STDIN_TOOL_NAME => ToolOutputCategory::Shell,
+        crate::openai_tools::READ_FILE_TOOL_NAME
+        | crate::openai_tools::LIST_DIR_TOOL_NAME
+        | crate::openai_tools::GREP_FILES_TOOL_NAME => ToolOutputCategory::FileRead,
+        "list_mcp_resources" | "list_mcp_resource_templates" | "read_mcp_resource" => {
+            ToolOutputCategory::Mcp
+        }
+        "browser" | "web_fetch" => ToolOutputCategory::Browser,
+        _ if is_mcp => ToolOutputCategory::Mcp,
+        _ => ToolOutputCategory::Other,
+    }
+}
+
+/// Caps the tool outputs in `items`. Tool names come from the calls in the
+/// same batch, then from `earlier_call_name` for calls recorded before it.
+pub(crate) fn enforce_budgets<'a>(
+    items: &'a [ResponseItem],
+    limits: &ToolOutputLimits,
+    default_budget: usize,
+    earlier_call_name: impl Fn(&str) -> Option<String>,
+    is_mcp: impl Fn(&str) -> bool,
+) -> Cow<'a, [ResponseItem]> {
+    let mut names: HashMap<&str, &str> = HashMap::new();
+    let mut capped: Option<Vec<ResponseItem>> = None;
+    for (index, item) in items.iter().enumerate() {
+        let (call_id, output) = match item {
+            ResponseItem::FunctionCall { name, call_id, .. }
+            | ResponseItem::CustomToolCall { name, call_id, .. } => {
+                names.insert(call_id, name);
+                continue;
+            }
+            ResponseItem::LocalShellCall {
+                call_id: Some(call_id),
+                ..
+            } => {
+                names.insert(call_id, "local_shell");
+                continue;
+            }
+            ResponseItem::FunctionCallOutput { call_id, output }
+            | ResponseItem::CustomToolCallOutput {
+                call_id, output, ..
+            } => (call_id, output),
+            _ => continue,
+        };
+        let category = match names.get(call_id.as_str()) {
+            Some(name) => category_for_tool(name, is_mcp(name)),
+            None => earlier_call_name(call_id).map_or(ToolOutputCategory::Other, |name| {
+                category_for_tool(&name, is_mcp(&name))
+            }),
+        };
+        let budget = limits.budget(category, default_budget);
+        let Some(output) = cap_output(output, budget) else {
+            continue;
+        };
+        let capped = capped.get_or_insert_with(|| items.to_vec());
+        match &mut capped[index] {
+            ResponseItem::FunctionCallOutput { output: slot, .. }
+            | ResponseItem::CustomToolCallOutput { output: slot, .. } => *slot = output,
+            _ => {}
+        }
+    }
+    match capped {
+        Some(items) => Cow::Owned(items),
+        None => Cow::Borrowed(items),
+    }
+}
+
+/// `output` trimmed to `budget` bytes of text, or `None` if it already fits.
+/// Images are left alone.
+fn cap_output(
+    output: &FunctionCallOutputPayload,
+    budget: usize,
+) -> Option<FunctionCallOutputPayload> {
+    let body = match &output.body {
+        FunctionCallOutputBody::Text(text) => FunctionCallOutputBody::Text(cap_text(text, budget)?),
+        FunctionCallOutputBody::ContentItems(items) => {
+            let text_len: usize = items
+                .iter()
+                .map(|item| match item {
+                    FunctionCallOutputContentItem::InputText { text } => text.len(),
+                    FunctionCallOutputContentItem::InputImage { .. } => 0,
+                })
+                .sum();
+            if text_len <= budget {
+                return None;
+            }
+            let mut remaining = budget;
+            let items = items
+                .iter()
+                .map(|item| match item {
+                    FunctionCallOutputContentItem::InputText { text } => {
+                        let text = cap_text(text, remaining).unwrap_or_else(|| text.clone());
+                        remaining = remaining.saturating_sub(text.len());
+                        FunctionCallOutputContentItem::InputText { text }
+                    }
+                    image => image.clone(),
+                })
+                .collect();
+            FunctionCallOutputBody::ContentItems(items)
+        }
+    };
+    Some(FunctionCallOutputPayload {
+        body,
+        success: output.success,
+    })
+}
+
+/// Shell results are JSON with the command output under `output`; trim that
+/// field so the payload stays valid JSON. Anything else is trimmed as text.
+fn cap_text(text: &str, budget: usize) -> Option<String> {
+    if text.len() <= budget {
+        return None;
+    }
+    if let Ok(serde_json::Value::Object(mut payload)) = serde_json::from_str(text)
+        && let Some(serde_json::Value::String(inner)) = payload.get("output")
+    {
+        let overhead = text.len().saturating_sub(inner.len());
+        let (trimmed, _) = truncate_middle(inner, budget.saturating_sub(overhead));
+        payload.insert("output".to_owned(), serde_json::Value::String(trimmed));
+        return Some(serde_json::Value::Object(payload).to_string());
+    }
+    Some(truncate_middle(text, budget).0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn call(name: &str, call_id: &str) -> ResponseItem {
+        ResponseItem::FunctionCall {
+            id: None,
+            name: name.to_owned(),
+            namespace: None,
+            arguments: "{}".to_owned(),
+            call_id: call_id.to_owned(),
+        }
+    }
+
+    fn output(call_id: &str, text: String) -> ResponseItem {
+        ResponseItem::FunctionCallOutput {
+            call_id: call_id.to_owned(),
+            output: FunctionCallOutputPayload {
+                body: FunctionCallOutputBody::Text(text),
+                success: Some(true),
+            },
+        }
+    }
+
+    fn output_text(item: &ResponseItem) -> String {
+        match item {
+            ResponseItem::FunctionCallOutput { output, .. } => {
+                output.body.to_text().unwrap_or_default()
+            }
+            other => panic!("unexpected item {other:?}"),
+        }
+    }
+
+    #[test]
+    fn each_category_gets_its_own_budget() {
+        let limits = ToolOutputLimits {
+            browser: Some(200),
+            ..ToolOutputLimits::default()
+        };
+        let page = "page line\n".repeat(100);
+        let items = vec![
+            call("browser", "b1"),
+            output("b1", page.clone()),
+            call("read_file", "r1"),
+            output("r1", page.clone()),
+            output("m1", page.clone()),
+        ];
+        let capped = enforce_budgets(
+            &items,
+            &limits,
+            4_096,
+            |call_id| (call_id == "m1").then(|| "docs__search".to_owned()),
+            |name| name.contains("__"),
+        );
+
+        let browser = output_text(&capped[1]);
+        assert!(
+            browser.len() < 300,
+            "browser output kept {} bytes",
+            browser.len()
+        );
+        assert!(browser.contains("tokens truncated"));
+        assert_eq!(output_text(&capped[3]), page);
+        assert_eq!(output_text(&capped[4]), page);
+    }
+
+    #[test]
+    fn shell_payloads_stay_valid_json() {
+        let payload = serde_json::json!({
+            "output": "x".repeat(5_000),
+            "metadata": {"exit_code": 0, "duration_seconds": 0.1},
+        })
+        .to_string();
+        let items = vec![call("shell", "s1"), output("s1", payload)];
+        let limits = ToolOutputLimits {
+            shell: Some(1_000),
+            ..ToolOutputLimits::default()
+        };
+        let capped = enforce_budgets(&items, &limits, 100_000, |_| None, |_| false);
+
+        let text = output_text(&capped[1]);
+        assert!(text.len() < 1_100, "shell output kept {} bytes", text.len());
+        let parsed: serde_json::Value = serde_json::from_str(&text).expect("valid JSON");
+        assert_eq!(parsed["metadata"]["exit_code"], 0);
+    }
+
+    #[test]
+    fn outputs_within_budget_are_borrowed() {
+        let items = vec![call("shell", "s1"), output("s1", "ok".to_owned())];
+        let capped = enforce_budgets(
+            &items,
+            &ToolOutputLimits::default(),
+            1_024,
+            |_| None,
+            |_| false,
+        );
+        assert!(matches!(capped, Cow::Borrowed(_)));
+    }
+}
@@ -7,6 +7,8 @@ mod inspect;
 #[cfg(feature = "browser-automation")]
 mod lifecycle;
 #[cfg(feature = "browser-automation")]
+mod mock;
+#[cfg(feature = "browser-automation")]
 mod page_ops;
 #[cfg(feature = "browser-automation")]
 mod screenshot;
@@ -51,6 +53,9 @@ pub(crate) async fn handle_browser_tool(
     ctx: &ToolCallCtx,
     arguments: String,
 ) -> ResponseInputItem {
+    #[cfg(feature = "browser-automation")]
+    sess.note_browser_tool_use();
+
     let parsed_value = match serde_json::from_str::<Value>(&arguments) {
         Ok(value) => value,
         Err(e) => {
@@ -92,16 +97,17 @@ pub(crate) async fn handle_browser_tool(
         "close" => lifecycle::handle_browser_close(sess, ctx).await,
         #[cfg(feature = "browser-automation")]
         "restart" => lifecycle::handle_browser_restart(sess, ctx, payload_string).await,
+        // Tab actions still accept their earlier target-based names.
         #[cfg(feature = "browser-automation")]
-        "targets" => lifecycle::handle_browser_targets(sess, ctx).await,
+        "tab_list" | "targets" => lifecycle::handle_browser_targets(sess, ctx).await,
         #[cfg(feature = "browser-automation")]
-        "new_tab" => lifecycle::handle_browser_new_tab(sess, ctx, payload_string).await,
+        "tab_open" | "new_tab" => lifecycle::handle_browser_new_tab(sess, ctx, payload_string).await,
         #[cfg(feature = "browser-automation")]
-        "switch_target" => lifecycle::handle_browser_switch_target(sess, ctx, payload_string).await,
+        "tab_switch" | "switch_target" => lifecycle::handle_browser_switch_target(sess, ctx, payload_string).await,
         #[cfg(feature = "browser-automation")]
         "activate_target" => lifecycle::handle_browser_activate_target(sess, ctx, payload_string).await,
         #[cfg(feature = "browser-automation")]
-        "close_target" => lifecycle::handle_browser_close_target(sess, ctx, payload_string).await,
+        "tab_close" | "close_target" => lifecycle::handle_browser_close_target(sess, ctx, payload_string).await,
         #[cfg(feature = "browser-automation")]
         "click" => input::handle_browser_click(sess, ctx, payload_string).await,
         #[cfg(feature = "browser-automation")]
@@ -129,8 +135,22 @@ pub(crate) async fn handle_browser_tool(
         #[cfg(feature = "browser-automation")]
         "console" => page_ops::handle_browser_console(sess, ctx, payload_string).await,
         #[cfg(feature = "browser-automation")]
+        "har" => page_ops::handle_browser_har(sess, ctx, payload_string).await,
+        #[cfg(feature = "browser-automation")]
+        "downloads" => page_ops::handle_browser_downloads(sess, ctx, payload_string).await,
+        #[cfg(feature = "browser-automation")]
+        "mock" => mock::handle_browser_mock(sess, ctx, payload_string).await,
+        #[cfg(feature = "browser-automation")]
+        "mock_list" => mock::handle_browser_mock_list(sess, ctx, payload_string).await,
+        #[cfg(feature = "browser-automation")]
+        "mock_remove" => mock::handle_browser_mock_remove(sess, ctx, payload_string).await,
+        #[cfg(feature = "browser-automation")]
+        "mock_clear" => mock::handle_browser_mock_clear(sess, ctx, payload_string).await,
+        #[cfg(feature = "browser-automation")]
         "inspect_selector" => inspect::handle_browser_inspect_selector(sess, ctx, payload_string).await,
         #[cfg(feature = "browser-automation")]
+        "dom" => inspect::handle_browser_dom(sess, ctx, payload_string).await,
+        #[cfg(feature = "browser-automation")]
         "screenshot" => screenshot::handle_browser_screenshot(sess, ctx, payload_string).await,
         #[cfg(feature = "browser-automation")]
         "cookies_get" => storage::handle_browser_cookies_get(sess, ctx, payload_string).await,
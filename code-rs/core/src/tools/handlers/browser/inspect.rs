@@ -359,3 +359,51 @@ pub(super) async fn handle_browser_inspect(
     .await
 }
 
+
+pub(super) async fn handle_browser_dom(
+    sess: &Session,
+    ctx: &ToolCallCtx,
+    arguments: String,
+) -> ResponseInputItem {
+    let params = serde_json::from_str::<serde_json::Value>(&arguments).ok();
+    let sess_clone = sess;
+    let arguments_clone = arguments.clone();
+    let call_id_clone = ctx.call_id.clone();
+
+    execute_custom_tool(
+        sess,
+        ctx,
+        "browser_dom".to_owned(),
+        params,
+        || async move {
+            let browser_manager = get_browser_manager_for_session(sess_clone).await;
+            let Some(browser_manager) = browser_manager else {
+                return tool_error(call_id_clone, "Browser is not initialized. Use browser_open to start the browser.");
+            };
+
+            let json = serde_json::from_str::<Value>(&arguments_clone).unwrap_or_default();
+            let mut options = code_browser::DomOutlineOptions::default();
+            if let Some(max_depth) = json.get("max_depth").and_then(Value::as_u64) {
+                options.max_depth = max_depth.clamp(1, 100) as usize;
+            }
+            if let Some(max_chars) = json.get("max_chars").and_then(Value::as_u64) {
+                options.max_chars = max_chars.clamp(1_000, 200_000) as usize;
+            }
+            if let Some(bounds) = json.get("bounds").and_then(Value::as_bool) {
+                options.include_bounds = bounds;
+            }
+
+            match browser_manager.dom_outline(&options).await {
+                Ok(outline) => {
+                    let header = match browser_manager.get_current_url().await {
+                        Some(url) => format!("Page outline of {url}:"),
+                        None => "Page outline:".to_owned(),
+                    };
+                    tool_output(call_id_clone, format!("{header}\n{outline}"))
+                }
+                Err(e) => tool_error(call_id_clone, format!("Failed to read the page structure: {e}")),
+            }
+        },
+    )
+    .await
+}
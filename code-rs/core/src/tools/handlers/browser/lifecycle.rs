@@ -278,7 +278,7 @@ pub(super) async fn handle_browser_targets(sess: &Session, ctx: &ToolCallCtx) ->
     execute_custom_tool(
         sess,
         ctx,
-        "browser_targets".to_owned(),
+        "browser_tab_list".to_owned(),
         None,
         || async move {
             let browser_manager = get_browser_manager_for_session(sess_clone).await;
@@ -296,7 +296,7 @@ pub(super) async fn handle_browser_targets(sess: &Session, ctx: &ToolCallCtx) ->
                         let payload = serde_json::json!({
                             "active_target_id": active_target_id,
                             "targets": targets,
-                            "hint": "Use browser action=switch_target with a target_id or index."
+                            "hint": "Use browser action=tab_switch with a target_id or index. Tabs keep their cursor position and console log while you work in another tab."
                         });
 
                         let pretty = serde_json::to_string_pretty(&payload)
@@ -327,7 +327,7 @@ pub(super) async fn handle_browser_new_tab(
     execute_custom_tool(
         sess,
         ctx,
-        "browser_new_tab".to_owned(),
+        "browser_tab_open".to_owned(),
         params,
         || async move {
             let browser_manager = match code_browser::global::get_browser_manager().await {
@@ -376,7 +376,7 @@ pub(super) async fn handle_browser_switch_target(
     execute_custom_tool(
         sess,
         ctx,
-        "browser_switch_target".to_owned(),
+        "browser_tab_switch".to_owned(),
         params.clone(),
         || async move {
             let browser_manager = get_browser_manager_for_session(sess_clone).await;
@@ -385,7 +385,7 @@ pub(super) async fn handle_browser_switch_target(
             };
 
             let Some(value) = params.as_ref() else {
-                return tool_error(call_id_clone.clone(), "Missing target_id or index for action=switch_target");
+                return tool_error(call_id_clone.clone(), "Missing target_id or index for action=tab_switch");
             };
 
             let target_id = match resolve_target_id_from_value(&browser_manager, value).await {
@@ -473,7 +473,7 @@ pub(super) async fn handle_browser_close_target(
     execute_custom_tool(
         sess,
         ctx,
-        "browser_close_target".to_owned(),
+        "browser_tab_close".to_owned(),
         params.clone(),
         || async move {
             let browser_manager = get_browser_manager_for_session(sess_clone).await;
@@ -482,7 +482,7 @@ pub(super) async fn handle_browser_close_target(
             };
 
             let Some(value) = params.as_ref() else {
-                return tool_error(call_id_clone.clone(), "Missing target_id or index for action=close_target");
+                return tool_error(call_id_clone.clone(), "Missing target_id or index for action=tab_close");
             };
 
             let target_id = match resolve_target_id_from_value(&browser_manager, value).await {
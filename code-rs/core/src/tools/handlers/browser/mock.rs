@@ -0,0 +1,246 @@
+use super::helpers::get_browser_manager_for_session;
+use crate::codex::Session;
+use crate::codex::ToolCallCtx;
+use crate::tools::events::execute_custom_tool;
+use crate::tools::handlers::{tool_error, tool_output};
+use code_browser::MockRule;
+use code_protocol::models::ResponseInputItem;
+use serde_json::Value;
+
+/// Largest fixture served by a mock.
+const MAX_FIXTURE_BYTES: u64 = 10 * 1024 * 1024;
+
+pub(super) async fn handle_browser_mock(
+    sess: &Session,
+    ctx: &ToolCallCtx,
+    arguments: String,
+) -> ResponseInputItem {
+    let params = serde_json::from_str(&arguments).ok();
+    let sess_clone = sess;
+    let arguments_clone = arguments.clone();
+    let call_id_clone = ctx.call_id.clone();
+
+    execute_custom_tool(
+        sess,
+        ctx,
+        "browser_mock".to_owned(),
+        params,
+        || async move {
+            let browser_manager = get_browser_manager_for_session(sess_clone).await;
+            let Some(browser_manager) = browser_manager else {
+                return tool_error(call_id_clone, "Browser is not initialized. Use browser_open to start the browser.");
+            };
+
+            let json = serde_json::from_str::<Value>(&arguments_clone).unwrap_or_default();
+            let Some(url_pattern) = json
+                .get("url_pattern")
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|pattern| !pattern.is_empty())
+            else {
+                return tool_error(call_id_clone, "action=mock requires url_pattern (e.g. '*/api/users*').");
+            };
+
+            let mut rule = match (
+                json.get("fixture").and_then(Value::as_str),
+                json.get("body").and_then(Value::as_str),
+            ) {
+                (Some(fixture), _) => {
+                    let path = sess_clone.get_cwd().join(fixture.trim());
+                    match tokio::fs::metadata(&path).await {
+                        Ok(meta) if meta.len() > MAX_FIXTURE_BYTES => {
+                            return tool_error(call_id_clone, format!(
+                                "Fixture {} is {} bytes; mocks serve at most {MAX_FIXTURE_BYTES} bytes.",
+                                path.display(),
+                                meta.len()
+                            ));
+                        }
+                        Ok(_) => {}
+                        Err(e) => {
+                            return tool_error(call_id_clone, format!("Cannot read fixture {}: {e}", path.display()));
+                        }
+                    }
+                    match MockRule::from_fixture(url_pattern.to_owned(), &path).await {
+                        Ok(rule) => rule,
+                        Err(e) => {
+                            return tool_error(call_id_clone, format!("Cannot read fixture {}: {e}", path.display()));
+                        }
+                    }
+                }
+                (None, Some(body)) => MockRule {
+                    url_pattern: url_pattern.to_owned(),
+                    method: None,
+                    status: 200,
+                    headers: Vec::new(),
+                    body: body.as_bytes().to_vec(),
+                    fixture: None,
+                },
+                (None, None) => {
+                    return tool_error(call_id_clone, "action=mock requires fixture (a file path) or body (inline response text).");
+                }
+            };
+
+            if let Some(status) = json.get("status").and_then(Value::as_u64) {
+                match u16::try_from(status) {
+                    Ok(status) if (100..=599).contains(&status) => rule.status = status,
+                    _ => return tool_error(call_id_clone, format!("Invalid HTTP status {status}.")),
+                }
+            }
+            if let Some(method) = json
+                .get("http_method")
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|method| !method.is_empty())
+            {
+                rule.method = Some(method.to_ascii_uppercase());
+            }
+            if let Some(headers) = json.get("headers").and_then(Value::as_object) {
+                for (name, value) in headers {
+                    let value = match value {
+                        Value::String(value) => value.clone(),
+                        other => other.to_string(),
+                    };
+                    rule.headers.retain(|(existing, _)| !existing.eq_ignore_ascii_case(name));
+                    rule.headers.push((name.clone(), value));
+                }
+            }
+
+            let summary = format!(
+                "Mocking {}{} with status {} ({} bytes{}).",
+                rule.method.as_deref().map(|method| format!("{method} ")).unwrap_or_default(),
+                rule.url_pattern,
+                rule.status,
+                rule.body.len(),
+                rule.fixture
+                    .as_ref()
+                    .map(|path| format!(" from {}", path.display()))
+                    .unwrap_or_default(),
+            );
+            match browser_manager.add_mock(rule).await {
+                Ok(()) => tool_output(call_id_clone, format!(
+                    "{summary} Matching requests on this tab are answered locally until action=mock_remove or action=mock_clear."
+                )),
+                Err(e) => tool_error(call_id_clone, format!("Failed to register mock: {e}")),
+            }
+        },
+    )
+    .await
+}
+
+pub(super) async fn handle_browser_mock_list(
+    sess: &Session,
+    ctx: &ToolCallCtx,
+    arguments: String,
+) -> ResponseInputItem {
+    let params = serde_json::from_str(&arguments).ok();
+    let sess_clone = sess;
+    let call_id_clone = ctx.call_id.clone();
+
+    execute_custom_tool(
+        sess,
+        ctx,
+        "browser_mock_list".to_owned(),
+        params,
+        || async move {
+            let browser_manager = get_browser_manager_for_session(sess_clone).await;
+            let Some(browser_manager) = browser_manager else {
+                return tool_error(call_id_clone, "Browser is not initialized. Use browser_open to start the browser.");
+            };
+
+            match browser_manager.list_mocks().await {
+                Ok(mocks) => tool_output(call_id_clone, format_mocks(&mocks)),
+                Err(e) => tool_error(call_id_clone, format!("Failed to list mocks: {e}")),
+            }
+        },
+    )
+    .await
+}
+
+pub(super) async fn handle_browser_mock_remove(
+    sess: &Session,
+    ctx: &ToolCallCtx,
+    arguments: String,
+) -> ResponseInputItem {
+    let params = serde_json::from_str(&arguments).ok();
+    let sess_clone = sess;
+    let arguments_clone = arguments.clone();
+    let call_id_clone = ctx.call_id.clone();
+
+    execute_custom_tool(
+        sess,
+        ctx,
+        "browser_mock_remove".to_owned(),
+        params,
+        || async move {
+            let browser_manager = get_browser_manager_for_session(sess_clone).await;
+            let Some(browser_manager) = browser_manager else {
+                return tool_error(call_id_clone, "Browser is not initialized. Use browser_open to start the browser.");
+            };
+
+            let json = serde_json::from_str::<Value>(&arguments_clone).unwrap_or_default();
+            let Some(url_pattern) = json.get("url_pattern").and_then(Value::as_str).map(str::trim) else {
+                return tool_error(call_id_clone, "action=mock_remove requires url_pattern.");
+            };
+            match browser_manager.remove_mock(url_pattern).await {
+                Ok(0) => tool_output(call_id_clone, format!("No mock is registered for {url_pattern}.")),
+                Ok(_) => tool_output(call_id_clone, format!("Stopped mocking {url_pattern}.")),
+                Err(e) => tool_error(call_id_clone, format!("Failed to remove mock: {e}")),
+            }
+        },
+    )
+    .await
+}
+
+pub(super) async fn handle_browser_mock_clear(
+    sess: &Session,
+    ctx: &ToolCallCtx,
+    arguments: String,
+) -> ResponseInputItem {
+    let params = serde_json::from_str(&arguments).ok();
+    let sess_clone = sess;
+    let call_id_clone = ctx.call_id.clone();
+
+    execute_custom_tool(
+        sess,
+        ctx,
+        "browser_mock_clear".to_owned(),
+        params,
+        || async move {
+            let browser_manager = get_browser_manager_for_session(sess_clone).await;
+            let Some(browser_manager) = browser_manager else {
+                return tool_error(call_id_clone, "Browser is not initialized. Use browser_open to start the browser.");
+            };
+
+            match browser_manager.clear_mocks().await {
+                Ok(removed) => tool_output(call_id_clone, format!("Removed {removed} mocks; requests reach the network again.")),
+                Err(e) => tool_error(call_id_clone, format!("Failed to clear mocks: {e}")),
+            }
+        },
+    )
+    .await
+}
+
+fn format_mocks(mocks: &[code_browser::MockInfo]) -> String {
+    use std::fmt::Write;
+
+    if mocks.is_empty() {
+        return "No mocks registered on this tab.".to_owned();
+    }
+    let mut output = format!("{} mocks on this tab:", mocks.len());
+    for mock in mocks {
+        output.push_str("\n- ");
+        if let Some(method) = &mock.method {
+            let _ = write!(output, "{method} ");
+        }
+        let _ = write!(
+            output,
+            "{} -> {} ({} bytes",
+            mock.url_pattern, mock.status, mock.body_bytes
+        );
+        if let Some(fixture) = &mock.fixture {
+            let _ = write!(output, " from {}", fixture.display());
+        }
+        let _ = write!(output, "), {} hits", mock.hits);
+    }
+    output
+}
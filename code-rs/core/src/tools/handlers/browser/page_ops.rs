@@ -255,3 +255,158 @@ pub(super) async fn handle_browser_cdp(
     .await
 }
 
+
+pub(super) async fn handle_browser_har(
+    sess: &Session,
+    ctx: &ToolCallCtx,
+    arguments: String,
+) -> ResponseInputItem {
+    let params = serde_json::from_str(&arguments).ok();
+    let sess_clone = sess;
+    let arguments_clone = arguments.clone();
+    let call_id_clone = ctx.call_id.clone();
+
+    execute_custom_tool(
+        sess,
+        ctx,
+        "browser_har".to_owned(),
+        params,
+        || async move {
+            let browser_manager = get_browser_manager_for_session(sess_clone).await;
+            let Some(browser_manager) = browser_manager else {
+                return tool_error(call_id_clone, "Browser is not initialized. Use browser_open to start the browser.");
+            };
+
+            let json = serde_json::from_str::<Value>(&arguments_clone).unwrap_or_default();
+            let clear = json.get("clear").and_then(Value::as_bool).unwrap_or(false);
+            // Without a path the HAR goes to the session's scratch directory so
+            // captured traffic stays out of the workspace.
+            let path = match json.get("path").and_then(Value::as_str) {
+                Some(path) if !path.trim().is_empty() => sess_clone.get_cwd().join(path.trim()),
+                _ => {
+                    let file_name = format!(
+                        "browser-{}.har",
+                        chrono::Local::now().format("%Y%m%d-%H%M%S")
+                    );
+                    match crate::scratch::ensure_scratch_dir(
+                        &crate::scratch::scratch_root(),
+                        sess_clone.session_uuid(),
+                    ) {
+                        Ok(dir) => dir.join(file_name),
+                        Err(e) => {
+                            return tool_error(call_id_clone, format!(
+                                "Failed to create the scratch directory for the HAR file: {e}"
+                            ));
+                        }
+                    }
+                }
+            };
+
+            let policy = sess_clone.get_sandbox_policy();
+            if !crate::tools::handlers::scratch::sandbox_allows_writes(
+                &policy,
+                sess_clone.get_cwd(),
+                path.parent().unwrap_or(&path),
+            ) {
+                return tool_error(call_id_clone, format!(
+                    "Cannot write the HAR file: {} is not writable in the current sandbox.",
+                    path.display()
+                ));
+            }
+
+            match browser_manager.export_har(&path, clear).await {
+                Ok(summary) => tool_output(call_id_clone, format_har_summary(&path, &summary)),
+                Err(e) => tool_error(call_id_clone, format!("Failed to export HAR: {e}")),
+            }
+        },
+    )
+    .await
+}
+
+fn format_har_summary(path: &std::path::Path, summary: &code_browser::HarSummary) -> String {
+    use std::fmt::Write;
+
+    let mut output = format!(
+        "Wrote {} requests to {} (response bodies omitted, credential headers redacted).",
+        summary.entries,
+        path.display()
+    );
+    if summary.dropped > 0 {
+        let _ = write!(
+            output,
+            " {} older requests were dropped by the capture limit.",
+            summary.dropped
+        );
+    }
+    if summary.failed == 0 {
+        output.push_str("\nNo failed requests.");
+    } else {
+        let _ = write!(output, "\n{} failed requests", summary.failed);
+        if summary.failed_requests.len() < summary.failed {
+            let _ = write!(output, " (latest {})", summary.failed_requests.len());
+        }
+        output.push(':');
+        for line in &summary.failed_requests {
+            let _ = write!(output, "\n  {line}");
+        }
+    }
+    output
+}
+
+pub(super) async fn handle_browser_downloads(
+    sess: &Session,
+    ctx: &ToolCallCtx,
+    arguments: String,
+) -> ResponseInputItem {
+    let params = serde_json::from_str(&arguments).ok();
+    let sess_clone = sess;
+    let call_id_clone = ctx.call_id.clone();
+
+    execute_custom_tool(
+        sess,
+        ctx,
+        "browser_downloads".to_owned(),
+        params,
+        || async move {
+            let browser_manager = get_browser_manager_for_session(sess_clone).await;
+            let Some(browser_manager) = browser_manager else {
+                return tool_error(call_id_clone, "Browser is not initialized. Use browser_open to start the browser.");
+            };
+
+            let dir = browser_manager.download_dir().await;
+            let downloads = browser_manager.list_downloads().await;
+            tool_output(call_id_clone, format_downloads(&dir, &downloads))
+        },
+    )
+    .await
+}
+
+fn format_downloads(dir: &std::path::Path, downloads: &[code_browser::DownloadInfo]) -> String {
+    use code_browser::DownloadState;
+    use std::fmt::Write;
+
+    if downloads.is_empty() {
+        return format!("No downloads yet. Files downloaded in the browser are saved to {}.", dir.display());
+    }
+    let mut output = format!("Downloads (saved to {}):", dir.display());
+    for download in downloads {
+        let status = match download.state {
+            DownloadState::Completed => match &download.path {
+                Some(path) => format!("{} ({} bytes)", path.display(), download.received_bytes),
+                None => format!("completed ({} bytes)", download.received_bytes),
+            },
+            DownloadState::Canceled => "canceled".to_owned(),
+            DownloadState::InProgress if download.total_bytes > 0 => format!(
+                "in progress ({}/{} bytes)",
+                download.received_bytes, download.total_bytes
+            ),
+            DownloadState::InProgress => format!("in progress ({} bytes)", download.received_bytes),
+        };
+        let _ = write!(
+            output,
+            "\n- {} from {} -> {status}",
+            download.suggested_filename, download.url
+        );
+    }
+    output
+}
@@ -0,0 +1,124 @@
+use std::path::Path;
+
+use crate::codex::Session;
+use crate::protocol::SandboxPolicy;
+use crate::tools::context::ToolInvocation;
+use crate::tools::events::execute_custom_tool;
+use crate::tools::handlers::{tool_error, tool_output};
+use crate::tools::registry::ToolHandler;
+use crate::turn_diff_tracker::TurnDiffTracker;
+use async_trait::async_trait;
+use code_protocol::models::ResponseInputItem;
+use serde_json::Value;
+use serde_json::json;
+
+/// `capabilities`: a JSON description of the session's sandbox, approval
+/// policy, tools and budgets. Everything but token usage and the time left
+/// is built once per submission and reused until the tool list changes.
+pub(crate) struct CapabilitiesToolHandler;
+
+fn describe_sandbox(policy: &SandboxPolicy, cwd: &Path) -> Value {
+    let writable_roots: Vec<String> = policy
+        .get_writable_roots_with_cwd(cwd)
+        .iter()
+        .map(|root| root.root.to_string_lossy().into_owned())
+        .collect();
+    json!({
+        "mode": policy.to_string(),
+        "full_disk_write": policy.has_full_disk_write_access(),
+        "writable_roots": writable_roots,
+        "network_access": policy.has_full_network_access(),
+    })
+}
+
+fn static_capabilities(sess: &Session) -> Value {
+    let cwd = sess.get_cwd();
+    json!({
+        "cwd": cwd.display().to_string(),
+        "sandbox": describe_sandbox(&sess.get_sandbox_policy(), cwd),
+        "approval_policy": sess.get_approval_policy().to_string(),
+        "tools": sess.turn_tool_names_snapshot(),
+        "mcp_tools_not_loaded": sess.omitted_mcp_tools_snapshot().len(),
+        "limits": sess.model_limits(),
+    })
+}
+
+#[async_trait]
+impl ToolHandler for CapabilitiesToolHandler {
+    async fn handle(
+        &self,
+        sess: &Session,
+        _turn_diff_tracker: &mut TurnDiffTracker,
+        inv: ToolInvocation,
+    ) -> ResponseInputItem {
+        let ctx = inv.ctx.clone();
+        let call_id = ctx.call_id.clone();
+        let sub_id = ctx.sub_id.clone();
+
+        execute_custom_tool(
+            sess,
+            &ctx,
+            crate::openai_tools::CAPABILITIES_TOOL_NAME.to_owned(),
+            None,
+            move || async move {
+                let mut description = match sess.cached_capabilities(&sub_id) {
+                    Some(cached) => cached,
+                    None => {
+                        let built = static_capabilities(sess);
+                        sess.store_capabilities(&sub_id, built.clone());
+                        built
+                    }
+                };
+
+                let tokens_in_context = sess
+                    .token_usage_info_snapshot()
+                    .map(|info| info.last_token_usage.tokens_in_context_window());
+                let time_budget = sess.time_budget_snapshot().map(|(total, remaining)| {
+                    json!({
+                        "total_seconds": total.as_secs(),
+                        "remaining_seconds": remaining.as_secs(),
+                    })
+                });
+                if let Some(fields) = description.as_object_mut() {
+                    fields.insert("tokens_in_context".to_owned(), json!(tokens_in_context));
+                    fields.insert("time_budget".to_owned(), json!(time_budget));
+                }
+
+                match serde_json::to_string_pretty(&description) {
+                    Ok(text) => tool_output(call_id.clone(), text),
+                    Err(err) => tool_error(call_id.clone(), format!("capabilities failed: {err}")),
+                }
+            },
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn describes_read_only_and_full_access_sandboxes() {
+        let cwd = Path::new("/work/project");
+        assert_eq!(
+            describe_sandbox(&SandboxPolicy::ReadOnly, cwd),
+            json!({
+                "mode": "read-only",
+                "full_disk_write": false,
+                "writable_roots": [],
+                "network_access": false,
+            })
+        );
+        assert_eq!(
+            describe_sandbox(&SandboxPolicy::DangerFullAccess, cwd),
+            json!({
+                "mode": "danger-full-access",
+                "full_disk_write": true,
+                "writable_roots": [],
+                "network_access": true,
+            })
+        );
+    }
+}
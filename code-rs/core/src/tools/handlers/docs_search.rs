@@ -0,0 +1,140 @@
+use crate::codex::Session;
+use crate::docs_bundles::DocsHit;
+use crate::docs_bundles::search_docs;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolPayload;
+use crate::tools::events::execute_custom_tool;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::unsupported_tool_call_output;
+use crate::turn_diff_tracker::TurnDiffTracker;
+use crate::tools::handlers::{tool_error, tool_output};
+use async_trait::async_trait;
+use code_protocol::models::ResponseInputItem;
+use serde::Deserialize;
+
+pub(crate) struct DocsSearchToolHandler;
+
+const DEFAULT_LIMIT: usize = 5;
+const MAX_LIMIT: usize = 20;
+
+fn default_limit() -> usize {
+    DEFAULT_LIMIT
+}
+
+#[derive(Deserialize)]
+struct DocsSearchArgs {
+    query: String,
+    #[serde(default)]
+    bundle: Option<String>,
+    #[serde(default = "default_limit")]
+    limit: usize,
+}
+
+#[async_trait]
+impl ToolHandler for DocsSearchToolHandler {
+    fn scheduling_hints(&self) -> crate::tools::registry::ToolSchedulingHints {
+        crate::tools::registry::ToolSchedulingHints::pure_parallel()
+    }
+
+    async fn handle(
+        &self,
+        sess: &Session,
+        _turn_diff_tracker: &mut TurnDiffTracker,
+        inv: ToolInvocation,
+    ) -> ResponseInputItem {
+        let ToolPayload::Function { arguments } = &inv.payload else {
+            return unsupported_tool_call_output(
+                &inv.ctx.call_id,
+                inv.payload.outputs_custom(),
+                format!("{} expects function-call arguments", inv.tool_name),
+            );
+        };
+
+        let params_for_event = serde_json::from_str::<serde_json::Value>(arguments).ok();
+        let arguments = arguments.clone();
+        let ctx = inv.ctx.clone();
+        let call_id = ctx.call_id.clone();
+        let bundles = sess.docs_bundles().to_vec();
+
+        execute_custom_tool(
+            sess,
+            &ctx,
+            crate::openai_tools::DOCS_SEARCH_TOOL_NAME.to_owned(),
+            params_for_event,
+            move || async move {
+                let args: DocsSearchArgs = match serde_json::from_str(&arguments) {
+                    Ok(args) => args,
+                    Err(err) => {
+                        return tool_error(
+                            call_id.clone(),
+                            format!("invalid docs_search arguments: {err}"),
+                        );
+                    }
+                };
+
+                let query = args.query.trim().to_owned();
+                if query.is_empty() {
+                    return tool_error(call_id.clone(), "query must not be empty");
+                }
+                if args.limit == 0 {
+                    return tool_error(call_id.clone(), "limit must be greater than zero");
+                }
+                let limit = args.limit.min(MAX_LIMIT);
+
+                let bundles = match args.bundle.as_deref().map(str::trim).filter(|name| !name.is_empty()) {
+                    Some(name) => {
+                        let selected: Vec<_> =
+                            bundles.into_iter().filter(|bundle| bundle.name == name).collect();
+                        if selected.is_empty() {
+                            return tool_error(call_id.clone(), format!("unknown docs bundle `{name}`"));
+                        }
+                        selected
+                    }
+                    None => bundles,
+                };
+
+                // Loading an index reads and parses it from disk on first use.
+                let search = tokio::task::spawn_blocking(move || search_docs(&bundles, &query, limit)).await;
+                let (hits, errors) = match search {
+                    Ok(result) => result,
+                    Err(err) => {
+                        return tool_error(call_id.clone(), format!("docs_search failed: {err}"));
+                    }
+                };
+
+                if hits.is_empty() {
+                    let mut message = "No matches found.".to_owned();
+                    for error in &errors {
+                        message.push('\n');
+                        message.push_str(error);
+                    }
+                    tool_error(call_id.clone(), message)
+                } else {
+                    tool_output(call_id.clone(), format_hits(&hits, &errors))
+                }
+            },
+        )
+        .await
+    }
+}
+
+fn format_hits(hits: &[DocsHit], errors: &[String]) -> String {
+    let mut blocks: Vec<String> = hits
+        .iter()
+        .enumerate()
+        .map(|(index, hit)| {
+            format!(
+                "[{}] {}: {}\n{}\n{}",
+                index + 1,
+                hit.bundle,
+                hit.title,
+                hit.path.display(),
+                hit.snippet.trim_end()
+            )
+        })
+        .collect();
+    if !errors.is_empty() {
+        blocks.push(format!("Skipped bundles:\n{}", errors.join("\n")));
+    }
+    blocks.join("\n\n")
+}
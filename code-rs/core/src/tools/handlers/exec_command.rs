@@ -66,7 +66,7 @@ impl ToolHandler for ExecCommandToolHandler {
         let tool_name = inv.tool_name.clone();
         let mgr = sess.exec_command_manager();
         let cwd = sess.get_cwd().to_path_buf();
-        let sandbox_policy = sess.get_sandbox_policy().clone();
+        let sandbox_policy = sess.get_sandbox_policy();
         let sandbox_policy_cwd = cwd.clone();
         let enforce_managed_network = sess.managed_network_proxy().is_some();
 
@@ -0,0 +1,157 @@
+use crate::codex::Session;
+use crate::project_memory::PutOutcome;
+use crate::protocol::SandboxPolicy;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolPayload;
+use crate::tools::events::execute_custom_tool;
+use crate::tools::handlers::{tool_error, tool_output};
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::unsupported_tool_call_output;
+use crate::turn_diff_tracker::TurnDiffTracker;
+use async_trait::async_trait;
+use code_protocol::models::ResponseInputItem;
+use serde::Deserialize;
+
+/// `memory_put` and `memory_search` over the project notes in
+/// `.code/memory/notes.json`.
+pub(crate) struct MemoryToolHandler;
+
+const DEFAULT_LIMIT: usize = 5;
+const MAX_LIMIT: usize = 20;
+
+fn default_limit() -> usize {
+    DEFAULT_LIMIT
+}
+
+#[derive(Deserialize)]
+struct MemoryPutArgs {
+    key: String,
+    text: String,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct MemorySearchArgs {
+    query: String,
+    #[serde(default = "default_limit")]
+    limit: usize,
+}
+
+#[async_trait]
+impl ToolHandler for MemoryToolHandler {
+    async fn handle(
+        &self,
+        sess: &Session,
+        _turn_diff_tracker: &mut TurnDiffTracker,
+        inv: ToolInvocation,
+    ) -> ResponseInputItem {
+        let ToolPayload::Function { arguments } = &inv.payload else {
+            return unsupported_tool_call_output(
+                &inv.ctx.call_id,
+                inv.payload.outputs_custom(),
+                format!("{} expects function-call arguments", inv.tool_name),
+            );
+        };
+
+        let params_for_event = serde_json::from_str::<serde_json::Value>(arguments).ok();
+        let arguments = arguments.clone();
+        let ctx = inv.ctx.clone();
+        let call_id = ctx.call_id.clone();
+        let cwd = sess.get_cwd().to_path_buf();
+        let is_put = inv.tool_name == crate::openai_tools::MEMORY_PUT_TOOL_NAME;
+        let read_only = matches!(sess.get_sandbox_policy(), SandboxPolicy::ReadOnly);
+
+        execute_custom_tool(
+            sess,
+            &ctx,
+            inv.tool_name.clone(),
+            params_for_event,
+            move || async move {
+                if is_put {
+                    let args: MemoryPutArgs = match serde_json::from_str(&arguments) {
+                        Ok(args) => args,
+                        Err(err) => {
+                            return tool_error(
+                                call_id.clone(),
+                                format!("invalid memory_put arguments: {err}"),
+                            );
+                        }
+                    };
+                    if read_only {
+                        return tool_error(
+                            call_id.clone(),
+                            "memory_put is unavailable under the read-only sandbox",
+                        );
+                    }
+                    let key = args.key.trim().to_owned();
+                    let tags: Vec<String> = args
+                        .tags
+                        .iter()
+                        .map(|tag| tag.trim().to_owned())
+                        .filter(|tag| !tag.is_empty())
+                        .collect();
+                    let put = tokio::task::spawn_blocking(move || {
+                        crate::project_memory::put_note(&cwd, &args.key, &args.text, tags, chrono::Utc::now())
+                    })
+                    .await;
+                    match put {
+                        Ok(Ok(PutOutcome::Created)) => tool_output(call_id.clone(), format!("Saved note `{key}`.")),
+                        Ok(Ok(PutOutcome::Updated)) => tool_output(call_id.clone(), format!("Updated note `{key}`.")),
+                        Ok(Ok(PutOutcome::Removed)) => tool_output(call_id.clone(), format!("Removed note `{key}`.")),
+                        Ok(Ok(PutOutcome::Missing)) => {
+                            tool_error(call_id.clone(), format!("No note named `{key}` to remove."))
+                        }
+                        Ok(Err(err)) => tool_error(call_id.clone(), format!("memory_put failed: {err}")),
+                        Err(err) => tool_error(call_id.clone(), format!("memory_put failed: {err}")),
+                    }
+                } else {
+                    let args: MemorySearchArgs = match serde_json::from_str(&arguments) {
+                        Ok(args) => args,
+                        Err(err) => {
+                            return tool_error(
+                                call_id.clone(),
+                                format!("invalid memory_search arguments: {err}"),
+                            );
+                        }
+                    };
+                    if args.query.trim().is_empty() {
+                        return tool_error(call_id.clone(), "query must not be empty");
+                    }
+                    if args.limit == 0 {
+                        return tool_error(call_id.clone(), "limit must be greater than zero");
+                    }
+                    let limit = args.limit.min(MAX_LIMIT);
+                    let notes = match crate::project_memory::load_notes(&cwd) {
+                        Ok(notes) => notes,
+                        Err(err) => {
+                            return tool_error(call_id.clone(), format!("memory_search failed: {err}"));
+                        }
+                    };
+                    let hits = crate::project_memory::search_notes(&notes, &args.query, limit);
+                    if hits.is_empty() {
+                        return tool_output(call_id.clone(), "No matching notes.");
+                    }
+                    let blocks: Vec<String> = hits
+                        .iter()
+                        .map(|note| {
+                            let tags = if note.tags.is_empty() {
+                                String::new()
+                            } else {
+                                format!(" [{}]", note.tags.join(", "))
+                            };
+                            format!(
+                                "{}{tags} (updated {})\n{}",
+                                note.key,
+                                note.updated_at.format("%Y-%m-%d"),
+                                note.text
+                            )
+                        })
+                        .collect();
+                    tool_output(call_id.clone(), blocks.join("\n\n"))
+                }
+            },
+        )
+        .await
+    }
+}
@@ -2,6 +2,8 @@ pub(crate) mod agent;
 pub(crate) mod apply_patch;
 pub(crate) mod bridge;
 pub(crate) mod browser;
+pub(crate) mod capabilities;
+pub(crate) mod docs_search;
 pub(crate) mod dynamic;
 pub(crate) mod exec_command;
 pub(crate) mod gh_run_wait;
@@ -12,13 +14,17 @@ pub(crate) mod list_dir;
 pub(crate) mod kill;
 pub(crate) mod mcp;
 pub(crate) mod mcp_resource;
+pub(crate) mod memory;
 pub(crate) mod plan;
 pub(crate) mod read_file;
 pub(crate) mod request_user_input;
 pub(crate) mod request_permissions;
+pub(crate) mod scratch;
 pub(crate) mod search_tool_bm25;
+pub(crate) mod tools_list_more;
 pub(crate) mod shell;
 pub(crate) mod wait;
+pub(crate) mod web_search;
 pub(crate) mod web_fetch;
 
 use code_protocol::models::{
@@ -0,0 +1,154 @@
+use std::path::Path;
+
+use crate::codex::Session;
+use crate::protocol::SandboxPolicy;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolPayload;
+use crate::tools::events::execute_custom_tool;
+use crate::tools::handlers::{tool_error, tool_output};
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::unsupported_tool_call_output;
+use crate::turn_diff_tracker::TurnDiffTracker;
+use async_trait::async_trait;
+use code_protocol::models::ResponseInputItem;
+use serde::Deserialize;
+
+/// `scratch`: a per-session directory outside the workspace for temporary
+/// files, see [`crate::scratch`].
+pub(crate) struct ScratchToolHandler;
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum ScratchAction {
+    Path,
+    List,
+    Clear,
+}
+
+#[derive(Deserialize)]
+struct ScratchArgs {
+    action: ScratchAction,
+    #[serde(default)]
+    name: Option<String>,
+}
+
+/// Whether commands run under `policy` may write to `dir`.
+pub(crate) fn sandbox_allows_writes(policy: &SandboxPolicy, cwd: &Path, dir: &Path) -> bool {
+    if policy.has_full_disk_write_access() {
+        return true;
+    }
+    let canonical = crate::util::canonicalize_or_original(dir);
+    policy
+        .get_writable_roots_with_cwd(cwd)
+        .iter()
+        .any(|root| root.is_path_writable(dir) || root.is_path_writable(&canonical))
+}
+
+fn format_listing(files: &[(String, u64)]) -> String {
+    files
+        .iter()
+        .map(|(path, bytes)| format!("{path} ({bytes} bytes)"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[async_trait]
+impl ToolHandler for ScratchToolHandler {
+    async fn handle(
+        &self,
+        sess: &Session,
+        _turn_diff_tracker: &mut TurnDiffTracker,
+        inv: ToolInvocation,
+    ) -> ResponseInputItem {
+        let ToolPayload::Function { arguments } = &inv.payload else {
+            return unsupported_tool_call_output(
+                &inv.ctx.call_id,
+                inv.payload.outputs_custom(),
+                format!("{} expects function-call arguments", inv.tool_name),
+            );
+        };
+
+        let params_for_event = serde_json::from_str::<serde_json::Value>(arguments).ok();
+        let arguments = arguments.clone();
+        let ctx = inv.ctx.clone();
+        let call_id = ctx.call_id.clone();
+        let session_id = sess.session_uuid();
+        let policy = sess.get_sandbox_policy();
+        let cwd = sess.get_cwd().to_path_buf();
+
+        execute_custom_tool(
+            sess,
+            &ctx,
+            inv.tool_name.clone(),
+            params_for_event,
+            move || async move {
+                let args: ScratchArgs = match serde_json::from_str(&arguments) {
+                    Ok(args) => args,
+                    Err(err) => {
+                        return tool_error(
+                            call_id.clone(),
+                            format!("invalid scratch arguments: {err}"),
+                        );
+                    }
+                };
+                let root = crate::scratch::scratch_root();
+                if !sandbox_allows_writes(&policy, &cwd, &root) {
+                    return tool_error(
+                        call_id.clone(),
+                        format!(
+                            "the scratch directory under {} is not writable in the current sandbox",
+                            root.display()
+                        ),
+                    );
+                }
+
+                let result = tokio::task::spawn_blocking(move || {
+                    let dir = crate::scratch::ensure_scratch_dir(&root, session_id)?;
+                    match (args.action, args.name.as_deref()) {
+                        (ScratchAction::Path, None) => Ok(dir.display().to_string()),
+                        (ScratchAction::Path, Some(name)) => {
+                            crate::scratch::scratch_path(&dir, name)
+                                .map(|path| path.display().to_string())
+                        }
+                        (ScratchAction::List, _) => {
+                            let files = crate::scratch::list_scratch(&dir)?;
+                            Ok(if files.is_empty() {
+                                format!("{} is empty.", dir.display())
+                            } else {
+                                format!("{}\n{}", dir.display(), format_listing(&files))
+                            })
+                        }
+                        (ScratchAction::Clear, _) => {
+                            let removed = crate::scratch::clear_scratch(&dir)?;
+                            Ok(format!("Removed {removed} entries from {}.", dir.display()))
+                        }
+                    }
+                })
+                .await;
+                match result {
+                    Ok(Ok(text)) => tool_output(call_id.clone(), text),
+                    Ok(Err(err)) => tool_error(call_id.clone(), format!("scratch failed: {err}")),
+                    Err(err) => tool_error(call_id.clone(), format!("scratch failed: {err}")),
+                }
+            },
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_only_sandbox_cannot_write_scratch() {
+        let cwd = Path::new("/work/project");
+        let dir = Path::new("/tmp/code-scratch");
+        assert!(!sandbox_allows_writes(&SandboxPolicy::ReadOnly, cwd, dir));
+        assert!(sandbox_allows_writes(
+            &SandboxPolicy::DangerFullAccess,
+            cwd,
+            dir
+        ));
+    }
+}
@@ -0,0 +1,153 @@
+use crate::codex::Session;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolPayload;
+use crate::tools::events::execute_custom_tool;
+use crate::tools::handlers::{tool_error, tool_output};
+use crate::tools::registry::ToolHandler;
+use crate::turn_diff_tracker::TurnDiffTracker;
+use async_trait::async_trait;
+use code_protocol::models::ResponseInputItem;
+use serde::Deserialize;
+use serde_json::json;
+
+/// Lists the MCP tools `[tools.mcp_budget]` left out of the request and
+/// loads the ones the model asks for into every later request.
+pub(crate) struct ToolsListMoreHandler;
+
+const DEFAULT_LIMIT: usize = 8;
+const MAX_DESCRIPTION_CHARS: usize = 160;
+
+fn default_limit() -> usize {
+    DEFAULT_LIMIT
+}
+
+#[derive(Debug, Deserialize)]
+struct ToolsListMoreArgs {
+    #[serde(default)]
+    names: Vec<String>,
+    #[serde(default)]
+    query: Option<String>,
+    #[serde(default = "default_limit")]
+    limit: usize,
+}
+
+fn short_description(description: Option<&str>) -> Option<String> {
+    let line = description?.lines().map(str::trim).find(|line| !line.is_empty())?;
+    if line.chars().count() <= MAX_DESCRIPTION_CHARS {
+        return Some(line.to_owned());
+    }
+    let truncated: String = line.chars().take(MAX_DESCRIPTION_CHARS - 1).collect();
+    Some(format!("{truncated}…"))
+}
+
+#[async_trait]
+impl ToolHandler for ToolsListMoreHandler {
+    async fn handle(
+        &self,
+        sess: &Session,
+        _turn_diff_tracker: &mut TurnDiffTracker,
+        inv: ToolInvocation,
+    ) -> ResponseInputItem {
+        let ToolPayload::Function { arguments } = &inv.payload else {
+            return tool_error(
+                inv.ctx.call_id,
+                "tools_list_more expects function-call arguments",
+            );
+        };
+
+        let params_for_event = serde_json::from_str::<serde_json::Value>(arguments).ok();
+        let arguments = arguments.clone();
+        let ctx = inv.ctx.clone();
+        let call_id = ctx.call_id.clone();
+
+        execute_custom_tool(
+            sess,
+            &ctx,
+            crate::openai_tools::TOOLS_LIST_MORE_TOOL_NAME.to_owned(),
+            params_for_event,
+            move || async move {
+                let args: ToolsListMoreArgs = if arguments.trim().is_empty() {
+                    ToolsListMoreArgs {
+                        names: Vec::new(),
+                        query: None,
+                        limit: DEFAULT_LIMIT,
+                    }
+                } else {
+                    match serde_json::from_str(&arguments) {
+                        Ok(args) => args,
+                        Err(err) => {
+                            return tool_error(
+                                call_id.clone(),
+                                format!("invalid tools_list_more arguments: {err}"),
+                            );
+                        }
+                    }
+                };
+                if args.limit == 0 {
+                    return tool_error(call_id.clone(), "limit must be greater than zero");
+                }
+
+                let omitted = sess.omitted_mcp_tools_snapshot();
+                let descriptions: Vec<(String, Option<String>)> = sess
+                    .mcp_connection_manager()
+                    .list_all_tools_with_server_names()
+                    .into_iter()
+                    .filter(|(name, _, _)| omitted.iter().any(|omitted| omitted == name))
+                    .map(|(name, _, tool)| (name, tool.description))
+                    .collect();
+
+                let query = args.query.as_deref().map(str::trim).unwrap_or_default();
+                let mut requested = args.names;
+                if !query.is_empty() {
+                    let query_words = crate::mcp::tool_budget::words(query);
+                    let mut ranked: Vec<(usize, &String)> = descriptions
+                        .iter()
+                        .map(|(name, description)| {
+                            let text = format!("{name} {}", description.as_deref().unwrap_or_default());
+                            let score = crate::mcp::tool_budget::words(&text)
+                                .intersection(&query_words)
+                                .count();
+                            (score, name)
+                        })
+                        .filter(|(score, _)| *score > 0)
+                        .collect();
+                    ranked.sort_by(|(score_a, name_a), (score_b, name_b)| {
+                        score_b.cmp(score_a).then_with(|| name_a.cmp(name_b))
+                    });
+                    requested.extend(ranked.into_iter().take(args.limit).map(|(_, name)| name.clone()));
+                }
+
+                let loaded = sess.load_mcp_tools(&requested);
+                let not_found: Vec<&String> = requested
+                    .iter()
+                    .filter(|name| !loaded.iter().any(|loaded| loaded.eq_ignore_ascii_case(name)))
+                    .collect();
+                let not_loaded: Vec<serde_json::Value> = descriptions
+                    .iter()
+                    .filter(|(name, _)| !loaded.contains(name))
+                    .map(|(name, description)| {
+                        json!({
+                            "name": name,
+                            "description": short_description(description.as_deref()),
+                        })
+                    })
+                    .collect();
+                let note = if loaded.is_empty() {
+                    "No tools were loaded. Pass `names` or `query` to load tools from `not_loaded`."
+                } else {
+                    "Loaded tools are available from your next step and stay loaded for the rest of the session."
+                };
+
+                let content = json!({
+                    "loaded": loaded,
+                    "not_found": not_found,
+                    "not_loaded": not_loaded,
+                    "note": note,
+                })
+                .to_string();
+                tool_output(call_id.clone(), content)
+            },
+        )
+        .await
+    }
+}
@@ -716,7 +716,9 @@ pub(crate) async fn handle_web_fetch(sess: &Session, ctx: &ToolCallCtx, argument
             let mut browser_proxy_authorization: Option<String> = None;
             let mut _network_attempt_guard: Option<crate::network_approval::NetworkAttemptGuard> = None;
 
-            let mut http_client_builder = reqwest::Client::builder().timeout(timeout);
+            let mut http_client_builder = crate::http_client::apply_extra_root_certificates(
+                reqwest::Client::builder().timeout(timeout),
+            );
             if let Some(managed_proxy) = sess.managed_network_proxy() {
                 let attempt_id = uuid::Uuid::new_v4().to_string();
                 let network_approval = sess.network_approval();
@@ -754,6 +756,8 @@ pub(crate) async fn handle_web_fetch(sess: &Session, ctx: &ToolCallCtx, argument
                         base64::engine::general_purpose::STANDARD.encode(format!("{username}:"));
                     browser_proxy_authorization = Some(format!("Basic {encoded}"));
                 }
+            } else {
+                http_client_builder = crate::http_client::apply_outbound_proxy(http_client_builder);
             }
 
             let client = match http_client_builder.build() {
@@ -0,0 +1,130 @@
+use crate::codex::Session;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolPayload;
+use crate::tools::events::execute_custom_tool;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::unsupported_tool_call_output;
+use crate::turn_diff_tracker::TurnDiffTracker;
+use crate::tools::handlers::{tool_error, tool_output};
+use crate::web_search::WebSearchResult;
+use crate::web_search::filter_allowed_domains;
+use crate::web_search::search;
+use async_trait::async_trait;
+use code_protocol::models::ResponseInputItem;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Runs `web_search` against the `[web_search]` backend when one replaces
+/// the provider's native tool.
+pub(crate) struct WebSearchToolHandler;
+
+#[derive(Deserialize)]
+struct WebSearchArgs {
+    query: String,
+    #[serde(default)]
+    count: Option<usize>,
+}
+
+/// Tool output. Results keep their citation metadata and are numbered so
+/// the model can refer to them.
+#[derive(Serialize)]
+struct WebSearchOutput<'a> {
+    provider: &'a str,
+    query: &'a str,
+    results: Vec<CitedResult>,
+}
+
+#[derive(Serialize)]
+struct CitedResult {
+    index: usize,
+    #[serde(flatten)]
+    result: WebSearchResult,
+}
+
+#[async_trait]
+impl ToolHandler for WebSearchToolHandler {
+    fn scheduling_hints(&self) -> crate::tools::registry::ToolSchedulingHints {
+        crate::tools::registry::ToolSchedulingHints::pure_parallel()
+    }
+
+    async fn handle(
+        &self,
+        sess: &Session,
+        _turn_diff_tracker: &mut TurnDiffTracker,
+        inv: ToolInvocation,
+    ) -> ResponseInputItem {
+        let ToolPayload::Function { arguments } = &inv.payload else {
+            return unsupported_tool_call_output(
+                &inv.ctx.call_id,
+                inv.payload.outputs_custom(),
+                format!("{} expects function-call arguments", inv.tool_name),
+            );
+        };
+        let Some(provider) = sess.web_search_provider().cloned() else {
+            return tool_error(
+                inv.ctx.call_id.clone(),
+                "web_search has no configured backend in this session",
+            );
+        };
+
+        sess.maybe_mark_memories_polluted("web_search_call");
+        let params_for_event = serde_json::from_str::<serde_json::Value>(arguments).ok();
+        let arguments = arguments.clone();
+        let ctx = inv.ctx.clone();
+        let call_id = ctx.call_id.clone();
+        let allowed_domains = sess.web_search_allowed_domains().to_vec();
+
+        execute_custom_tool(
+            sess,
+            &ctx,
+            crate::openai_tools::WEB_SEARCH_TOOL_NAME.to_owned(),
+            params_for_event,
+            move || async move {
+                let args: WebSearchArgs = match serde_json::from_str(&arguments) {
+                    Ok(args) => args,
+                    Err(err) => {
+                        return tool_error(
+                            call_id.clone(),
+                            format!("invalid web_search arguments: {err}"),
+                        );
+                    }
+                };
+                let query = args.query.trim();
+                if query.is_empty() {
+                    return tool_error(call_id.clone(), "query must not be empty");
+                }
+                let count = args
+                    .count
+                    .unwrap_or(provider.max_results)
+                    .clamp(1, provider.max_results);
+
+                let mut results = match search(&provider, query, count).await {
+                    Ok(results) => results,
+                    Err(err) => return tool_error(call_id.clone(), err),
+                };
+                filter_allowed_domains(&mut results, &allowed_domains);
+                if results.is_empty() {
+                    return tool_error(call_id.clone(), "No results found.");
+                }
+
+                let output = WebSearchOutput {
+                    provider: provider.name(),
+                    query,
+                    results: results
+                        .into_iter()
+                        .enumerate()
+                        .map(|(idx, result)| CitedResult { index: idx + 1, result })
+                        .collect(),
+                };
+                match serde_json::to_string(&output) {
+                    Ok(json) => tool_output(call_id.clone(), json),
+                    Err(err) => tool_error(
+                        call_id.clone(),
+                        format!("failed to encode web_search results: {err}"),
+                    ),
+                }
+            },
+        )
+        .await
+    }
+}
@@ -13,7 +13,10 @@ pub(crate) fn format_exec_output_str(exec_output: &ExecToolCallOutput) -> String
 
     // Always use the aggregated (stdout + stderr interleaved) stream so the
     // model sees the full build log regardless of which stream a tool used.
-    let mut formatted_output = aggregated_output.text.clone();
+    // Color codes are noise to the model, so they are stripped here; the UI
+    // renders the raw streams from the exec events instead.
+    let mut formatted_output =
+        crate::util::strip_ansi_escapes(&aggregated_output.text).into_owned();
     if let Some(truncated_before_bytes) = aggregated_output.truncated_before_bytes {
         let note = format!(
             "… clipped {} from the start of command output (showing last {}).\n\n",
@@ -49,3 +52,25 @@ pub(crate) fn format_exec_output_payload(exec_output: &ExecToolCallOutput, outpu
     })
     .to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exec::StreamOutput;
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    #[test]
+    fn model_output_has_ansi_escapes_removed() {
+        let colored = "\u{1b}[1m\u{1b}[32mCompiling\u{1b}[0m app\n\u{1b}]8;;https://x.test\u{7}link\u{1b}]8;;\u{1b}\\ done\u{1b}(B";
+        let output = ExecToolCallOutput {
+            exit_code: 0,
+            stdout: StreamOutput::new(String::new()),
+            stderr: StreamOutput::new(String::new()),
+            aggregated_output: StreamOutput::new(colored.to_owned()),
+            duration: Duration::from_millis(10),
+            timed_out: false,
+        };
+        assert_eq!(format_exec_output_str(&output), "Compiling app\nlink done");
+    }
+}
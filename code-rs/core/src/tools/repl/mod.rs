@@ -17,6 +17,9 @@ pub(crate) mod types;
 // Re-export public interface so callers don't need to reach into submodules.
 pub(crate) use types::{ReplArgs, ReplExecError, ReplExecResult, ReplRuntimeConfig};
 
+use crate::child_registry;
+use crate::child_registry::ChildKind;
+use crate::child_registry::ChildRegistration;
 use crate::codex::Session;
 use crate::codex::ToolCallCtx;
 use crate::openai_tools::OpenAiTool;
@@ -252,6 +255,7 @@ struct Kernel {
     /// held across awaits for the entire duration of `execute()`.
     tool_rx: Arc<Mutex<tokio::sync::mpsc::UnboundedReceiver<ToolRequest>>>,
     shutdown: CancellationToken,
+    _registration: Option<ChildRegistration>,
 }
 
 impl ReplManager {
@@ -782,6 +786,12 @@ impl ReplManager {
                 )
             })?;
 
+        let registration = child_registry::register(
+            ChildKind::ReplKernel,
+            child.id(),
+            &self.runtime.executable.display().to_string(),
+        );
+
         let stdin = child
             .stdin
             .take()
@@ -833,6 +843,7 @@ impl ReplManager {
             pending_execs,
             tool_rx,
             shutdown,
+            _registration: registration,
         })
     }
 
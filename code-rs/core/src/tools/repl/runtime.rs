@@ -124,7 +124,7 @@ pub(super) fn build_runtime_command(
 ) -> Result<Command, String> {
     use std::collections::HashMap;
 
-    let sandbox_policy = sess.get_sandbox_policy();
+    let sandbox_policy = &sess.get_sandbox_policy();
     let sandbox_policy_cwd = sess.get_cwd();
     let enforce_managed_network = sess.managed_network_proxy().is_some();
     let caps = runtime.kind.capabilities();
@@ -80,6 +80,8 @@ impl ToolRouter {
             Arc::new(handlers::request_permissions::RequestPermissionsHandler);
         let search_tool_bm25: Arc<dyn ToolHandler> =
             Arc::new(handlers::search_tool_bm25::SearchToolBm25Handler);
+        let tools_list_more: Arc<dyn ToolHandler> =
+            Arc::new(handlers::tools_list_more::ToolsListMoreHandler);
         let apply_patch: Arc<dyn ToolHandler> = Arc::new(handlers::apply_patch::ApplyPatchToolHandler);
         let exec_command: Arc<dyn ToolHandler> = Arc::new(handlers::exec_command::ExecCommandToolHandler);
         let mcp_resource: Arc<dyn ToolHandler> =
@@ -87,6 +89,12 @@ impl ToolRouter {
         let read_file: Arc<dyn ToolHandler> = Arc::new(handlers::read_file::ReadFileToolHandler);
         let list_dir: Arc<dyn ToolHandler> = Arc::new(handlers::list_dir::ListDirToolHandler);
         let grep_files: Arc<dyn ToolHandler> = Arc::new(handlers::grep_files::GrepFilesToolHandler);
+        let docs_search: Arc<dyn ToolHandler> = Arc::new(handlers::docs_search::DocsSearchToolHandler);
+        let memory: Arc<dyn ToolHandler> = Arc::new(handlers::memory::MemoryToolHandler);
+        let capabilities: Arc<dyn ToolHandler> =
+            Arc::new(handlers::capabilities::CapabilitiesToolHandler);
+        let scratch: Arc<dyn ToolHandler> = Arc::new(handlers::scratch::ScratchToolHandler);
+        let web_search: Arc<dyn ToolHandler> = Arc::new(handlers::web_search::WebSearchToolHandler);
         let repl_handler: Arc<dyn ToolHandler> = Arc::new(handlers::repl::ReplToolHandler);
         let repl_reset_handler: Arc<dyn ToolHandler> = Arc::new(handlers::repl::ReplResetToolHandler);
         let agent: Arc<dyn ToolHandler> = Arc::new(handlers::agent::AgentToolHandler);
@@ -108,6 +116,10 @@ impl ToolRouter {
         handlers.insert("request_user_input".into(), request_user_input);
         handlers.insert("request_permissions".into(), request_permissions);
         handlers.insert("search_tool_bm25".into(), search_tool_bm25);
+        handlers.insert(
+            crate::openai_tools::TOOLS_LIST_MORE_TOOL_NAME.into(),
+            tools_list_more,
+        );
         handlers.insert("apply_patch".into(), apply_patch);
         handlers.insert(crate::exec_command::EXEC_COMMAND_TOOL_NAME.into(), Arc::clone(&exec_command));
         handlers.insert(crate::exec_command::WRITE_STDIN_TOOL_NAME.into(), exec_command);
@@ -117,6 +129,12 @@ impl ToolRouter {
         handlers.insert(crate::openai_tools::READ_FILE_TOOL_NAME.into(), read_file);
         handlers.insert(crate::openai_tools::LIST_DIR_TOOL_NAME.into(), list_dir);
         handlers.insert(crate::openai_tools::GREP_FILES_TOOL_NAME.into(), grep_files);
+        handlers.insert(crate::openai_tools::DOCS_SEARCH_TOOL_NAME.into(), docs_search);
+        handlers.insert(crate::openai_tools::MEMORY_PUT_TOOL_NAME.into(), Arc::clone(&memory));
+        handlers.insert(crate::openai_tools::MEMORY_SEARCH_TOOL_NAME.into(), memory);
+        handlers.insert(crate::openai_tools::CAPABILITIES_TOOL_NAME.into(), capabilities);
+        handlers.insert(crate::openai_tools::SCRATCH_TOOL_NAME.into(), scratch);
+        handlers.insert(crate::openai_tools::WEB_SEARCH_TOOL_NAME.into(), web_search);
         handlers.insert(crate::openai_tools::REPL_TOOL_NAME.into(), Arc::clone(&repl_handler));
         handlers.insert(crate::openai_tools::REPL_RESET_TOOL_NAME.into(), Arc::clone(&repl_reset_handler));
         // Register per-runtime REPL tool entries so the router can dispatch
@@ -305,6 +323,7 @@ impl ToolRouter {
                 }
             }
 
+            sess.note_mcp_tool_used(tool_name.as_str());
             let call = ToolCall {
                 tool_name,
                 payload: ToolPayload::Mcp {
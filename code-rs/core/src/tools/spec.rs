@@ -20,6 +20,8 @@ pub struct ToolsConfig {
     pub web_search_request: bool,
     pub web_search_external: bool,
     pub search_tool: bool,
+    /// Per-request MCP tool schema budget; `None` sends every allowed tool.
+    pub mcp_budget: Option<crate::config_types::McpToolBudgetConfig>,
     pub repl: bool,
     /// Runtimes that passed the health probe and should be exposed as
     /// individual `repl_{label}` tools. Empty when `repl` is false.
@@ -27,6 +29,19 @@ pub struct ToolsConfig {
     pub include_view_image_tool: bool,
     pub web_search_allowed_domains: Option<Vec<String>>,
     pub agent_model_allowed_values: Vec<String>,
+    /// Backend that runs `web_search` as a function tool instead of the
+    /// provider's native tool.
+    pub web_search_provider: Option<crate::web_search::WebSearchProvider>,
+    /// Bundles searchable through `docs_search`; the tool is omitted when
+    /// empty.
+    pub docs_bundles: Vec<crate::docs_bundles::DocsBundle>,
+    /// Expose `memory_put` / `memory_search` over `.code/memory`.
+    pub project_memory: bool,
+    /// Expose `capabilities`, which describes the session's sandbox,
+    /// approvals, tools and budgets.
+    pub capabilities: bool,
+    /// Expose `scratch`, a per-session directory outside the workspace.
+    pub scratch: bool,
 }
 
 pub struct ToolsConfigParams<'a> {
@@ -89,11 +104,17 @@ impl ToolsConfig {
             web_search_request: include_web_search_request,
             web_search_external: true,
             search_tool: false,
+            mcp_budget: None,
             repl: false,
             repl_available_runtimes: Vec::new(),
             include_view_image_tool,
             web_search_allowed_domains: None,
             agent_model_allowed_values: Vec::new(),
+            web_search_provider: None,
+            docs_bundles: Vec::new(),
+            project_memory: false,
+            capabilities: false,
+            scratch: false,
         }
     }
 
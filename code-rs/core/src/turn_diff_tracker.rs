@@ -40,6 +40,11 @@ pub struct TurnDiffTracker {
     temp_name_to_current_path: HashMap<String, PathBuf>,
     /// Cache of known git worktree roots to avoid repeated filesystem walks.
     git_root_cache: Vec<PathBuf>,
+    /// Repository and submodule HEADs recorded by `track_submodules`.
+    submodule_baseline: Option<(PathBuf, Vec<(PathBuf, String)>)>,
+    /// Set by `summarize_large_files`: LFS pointers, binaries, and text over
+    /// this many bytes are described by a notice instead of diffed.
+    large_file_threshold: Option<u64>,
 }
 
 impl TurnDiffTracker {
@@ -124,6 +129,27 @@ impl TurnDiffTracker {
         }
     }
 
+    /// Records the commits checked out in the submodules of `cwd`'s repository
+    /// so the turn diff also reports submodules moved during the turn (for
+    /// example by shell commands), not only files edited via `apply_patch`.
+    pub fn track_submodules(&mut self, cwd: &Path) {
+        if self.submodule_baseline.is_some() {
+            return;
+        }
+        if let Ok(heads) = code_git_tooling::submodule_heads(cwd)
+            && !heads.is_empty()
+        {
+            self.submodule_baseline = Some((cwd.to_path_buf(), heads));
+        }
+    }
+
+    /// Replaces the content diff of Git LFS pointers, binary files, and text
+    /// files larger than `threshold_bytes` with a one-line notice (path,
+    /// sizes, pointer change), keeping them out of the diff sent onward.
+    pub fn summarize_large_files(&mut self, threshold_bytes: u64) {
+        self.large_file_threshold = Some(threshold_bytes);
+    }
+
     fn get_path_for_internal(&self, internal: &str) -> Option<PathBuf> {
         self.temp_name_to_current_path
             .get(internal)
@@ -159,6 +185,12 @@ impl TurnDiffTracker {
         loop {
             let git_marker = cur.join(".git");
             if git_marker.is_dir() || git_marker.is_file() {
+                // Files inside a submodule belong to the superproject's diff.
+                if git_marker.is_file() {
+                    while let Some(superproject) = superproject_root(&cur) {
+                        cur = superproject;
+                    }
+                }
                 if !self.git_root_cache.iter().any(|r| r == &cur) {
                     self.git_root_cache.push(cur.clone());
                 }
@@ -239,6 +271,18 @@ impl TurnDiffTracker {
             }
         }
 
+        if let Some((cwd, baseline)) = self.submodule_baseline.as_ref()
+            && let Ok(changes) = code_git_tooling::submodule_changes_since(cwd, baseline)
+        {
+            for change in changes {
+                // As with `git diff --submodule=log`, the summary precedes the
+                // hunk; patch tools treat it as commentary.
+                aggregated.push_str(&change.render());
+                aggregated.push('\n');
+                aggregated.push_str(&change.gitlink_diff());
+            }
+        }
+
         if aggregated.trim().is_empty() {
             Ok(None)
         } else {
@@ -310,6 +354,32 @@ impl TurnDiffTracker {
             let _ = writeln!(aggregated, "new mode {current_mode}");
         }
 
+        if let Some(threshold) = self.large_file_threshold {
+            let display = if right_bytes.is_some() { &right_display } else { &left_display };
+            if let Some(notice) = code_git_tooling::classify_file_change(
+                Path::new(display),
+                left_bytes,
+                right_bytes.as_deref(),
+                threshold,
+            ) {
+                let old_header = if left_present {
+                    format!("a/{left_display}")
+                } else {
+                    DEV_NULL.to_owned()
+                };
+                let new_header = if right_bytes.is_some() {
+                    format!("b/{right_display}")
+                } else {
+                    DEV_NULL.to_owned()
+                };
+                let _ = writeln!(aggregated, "index {left_oid}..{right_oid}");
+                let _ = writeln!(aggregated, "--- {old_header}");
+                let _ = writeln!(aggregated, "+++ {new_header}");
+                let _ = writeln!(aggregated, "Contents not diffed: {}", notice.render());
+                return aggregated;
+            }
+        }
+
         let left_text = left_bytes.and_then(|b| std::str::from_utf8(b).ok());
         let right_text = right_bytes
             .as_deref()
@@ -365,6 +435,21 @@ impl TurnDiffTracker {
     }
 }
 
+/// The working tree of the repository `dir` is a submodule of, if any.
+fn superproject_root(dir: &Path) -> Option<PathBuf> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["rev-parse", "--show-superproject-working-tree"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let root = String::from_utf8_lossy(&output.stdout).trim().to_owned();
+    (!root.is_empty()).then(|| PathBuf::from(root))
+}
+
 /// Compute the Git SHA-1 blob object ID for the given content (bytes).
 fn git_blob_sha1_hex_bytes(data: &[u8]) -> Output<sha1::Sha1> {
     // Git blob hash is sha1 of: "blob <len>\0<data>"
@@ -825,6 +910,40 @@ Binary files differ
         assert_eq!(diff, expected);
     }
 
+    #[test]
+    fn large_files_are_summarized_when_enabled() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("model.bin");
+        let pointer = |oid: char, size: u64| {
+            format!(
+                "version https://git-lfs.github.com/spec/v1\noid sha256:{}\nsize {size}\n",
+                oid.to_string().repeat(64)
+            )
+        };
+        fs::write(&file, pointer('a', 2048)).unwrap();
+
+        let mut acc = TurnDiffTracker::new();
+        acc.summarize_large_files(1024 * 1024);
+        let update_changes = HashMap::from([(
+            file.clone(),
+            FileChange::Update {
+                unified_diff: String::new(),
+                move_path: None,
+                original_content: String::new(),
+                new_content: String::new(),
+            },
+        )]);
+        acc.on_patch_begin(&update_changes);
+        fs::write(&file, pointer('b', 4096)).unwrap();
+
+        let diff = acc.get_unified_diff().unwrap().unwrap();
+        let diff = normalize_diff_for_test(&diff, dir.path());
+        assert!(diff.ends_with(
+            "Contents not diffed: <TMP>/model.bin: Git LFS object, 2.0 KiB -> 4.0 KiB (pointer sha256:aaaaaaa -> sha256:bbbbbbb)\n"
+        ));
+        assert!(!diff.contains("@@"));
+    }
+
     #[test]
     fn filenames_with_spaces_add_and_update() {
         let mut acc = TurnDiffTracker::new();
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::fs::OpenOptions;
 use std::io::{self, Write};
 use std::path::Path;
@@ -167,6 +168,50 @@ pub(crate) fn truncate_utf8_prefix_by_bytes(input: &str, max_bytes: usize) -> St
     truncate_on_char_boundary(input, max_bytes).to_owned()
 }
 
+/// Remove ANSI escape sequences (colors, cursor movement, OSC hyperlinks and
+/// titles) from `text`, leaving the printable content.
+pub fn strip_ansi_escapes(text: &str) -> Cow<'_, str> {
+    if !text.contains('\u{1b}') {
+        return Cow::Borrowed(text);
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch != '\u{1b}' {
+            out.push(ch);
+            continue;
+        }
+        match chars.next() {
+            // CSI: parameters and intermediates up to a final byte in @..~.
+            Some('[') => {
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            // OSC and other strings: terminated by BEL or ESC \.
+            Some(']' | 'P' | '_' | '^') => {
+                while let Some(c) = chars.next() {
+                    if c == '\u{7}' {
+                        break;
+                    }
+                    if c == '\u{1b}' && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            // Two-byte sequences such as ESC ( B carry one more byte.
+            Some('(' | ')' | '#') => {
+                chars.next();
+            }
+            _ => {}
+        }
+    }
+    Cow::Owned(out)
+}
+
 /// Check whether a string value is "truthy" (case-insensitive).
 ///
 /// Accepts: `"1"`, `"true"`, `"yes"`, `"on"`.
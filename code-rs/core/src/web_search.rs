@@ -0,0 +1,386 @@
+//! Pluggable backends for the `web_search` tool.
+//!
+//! By default `web_search` is the model provider's native tool. Setting
+//! `[web_search].provider` (globally or in a profile) to `bing`, `brave`,
+//! `searxng` or `custom` replaces it with a function tool that Code runs
+//! itself. Results from every backend are normalized to the same shape and
+//! numbered so the model can cite them, and each provider has its own
+//! requests-per-minute budget.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use std::time::Duration;
+use std::time::Instant;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::config_types::WebSearchProviderKind;
+use crate::config_types::WebSearchToml;
+
+pub const DEFAULT_MAX_RESULTS: usize = 5;
+pub const MAX_RESULTS_LIMIT: usize = 20;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(20);
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+/// Longer waits fail the call instead of stalling the turn.
+const MAX_RATE_LIMIT_WAIT: Duration = Duration::from_secs(15);
+
+const BING_ENDPOINT: &str = "https://api.bing.microsoft.com/v7.0/search";
+const BRAVE_ENDPOINT: &str = "https://api.search.brave.com/res/v1/web/search";
+
+/// A resolved, non-native `[web_search]` backend.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebSearchProvider {
+    pub kind: WebSearchProviderKind,
+    pub endpoint: String,
+    /// Environment variable read for the API key on each request.
+    pub api_key_env: Option<String>,
+    pub max_results: usize,
+    /// Zero disables rate limiting.
+    pub requests_per_minute: u32,
+    pub headers: BTreeMap<String, String>,
+}
+
+impl WebSearchProvider {
+    /// Resolves `[web_search]`. Returns `None` for the native `openai`
+    /// provider.
+    pub fn from_toml(toml: &WebSearchToml) -> std::io::Result<Option<Self>> {
+        let (default_endpoint, default_key_env, default_rpm) = match toml.provider {
+            WebSearchProviderKind::Openai => return Ok(None),
+            WebSearchProviderKind::Bing => (Some(BING_ENDPOINT), Some("BING_SEARCH_API_KEY"), 100),
+            WebSearchProviderKind::Brave => (Some(BRAVE_ENDPOINT), Some("BRAVE_SEARCH_API_KEY"), 60),
+            WebSearchProviderKind::Searxng => (None, None, 30),
+            WebSearchProviderKind::Custom => (None, None, 60),
+        };
+        let name = toml.provider.name();
+        let endpoint = match toml.endpoint.as_deref().map(str::trim).filter(|e| !e.is_empty()) {
+            Some(endpoint) => endpoint.to_owned(),
+            None => default_endpoint.map(str::to_owned).ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("web_search.endpoint is required when web_search.provider = \"{name}\""),
+                )
+            })?,
+        };
+        if let Err(err) = reqwest::Url::parse(&endpoint) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("web_search.endpoint `{endpoint}` is not a valid URL: {err}"),
+            ));
+        }
+
+        Ok(Some(Self {
+            kind: toml.provider,
+            endpoint,
+            api_key_env: toml
+                .api_key_env
+                .clone()
+                .or_else(|| default_key_env.map(str::to_owned)),
+            max_results: toml
+                .max_results
+                .unwrap_or(DEFAULT_MAX_RESULTS)
+                .clamp(1, MAX_RESULTS_LIMIT),
+            requests_per_minute: toml.requests_per_minute.unwrap_or(default_rpm),
+            headers: toml.headers.clone(),
+        }))
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.kind.name()
+    }
+
+    fn api_key(&self) -> Result<Option<String>, String> {
+        let Some(var) = self.api_key_env.as_deref() else {
+            return Ok(None);
+        };
+        match std::env::var(var) {
+            Ok(key) if !key.trim().is_empty() => Ok(Some(key.trim().to_owned())),
+            _ if matches!(self.kind, WebSearchProviderKind::Bing | WebSearchProviderKind::Brave) => Err(
+                format!("set {var} to use the {} web search provider", self.name()),
+            ),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// One search hit, normalized across providers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WebSearchResult {
+    pub title: String,
+    pub url: String,
+    pub snippet: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub published: Option<String>,
+}
+
+/// Runs `query` against `provider` and returns at most `count` results.
+pub async fn search(
+    provider: &WebSearchProvider,
+    query: &str,
+    count: usize,
+) -> Result<Vec<WebSearchResult>, String> {
+    let api_key = provider.api_key()?;
+    acquire_rate_limit_slot(provider).await?;
+
+    let client = crate::http_client::apply_outbound_network_settings(reqwest::Client::builder())
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|err| format!("failed to build HTTP client: {err}"))?;
+    let count_param = count.to_string();
+    let count_param = count_param.as_str();
+    let mut request = match provider.kind {
+        WebSearchProviderKind::Searxng => client
+            .get(format!("{}/search", provider.endpoint.trim_end_matches('/')))
+            .query(&[("q", query), ("format", "json")]),
+        WebSearchProviderKind::Bing => client
+            .get(&provider.endpoint)
+            .query(&[("q", query), ("count", count_param), ("textFormat", "Raw")]),
+        _ => client
+            .get(&provider.endpoint)
+            .query(&[("q", query), ("count", count_param)]),
+    };
+    request = request.header(reqwest::header::ACCEPT, "application/json");
+    if let Some(key) = api_key {
+        request = match provider.kind {
+            WebSearchProviderKind::Bing => request.header("Ocp-Apim-Subscription-Key", key),
+            WebSearchProviderKind::Brave => request.header("X-Subscription-Token", key),
+            _ => request.bearer_auth(key),
+        };
+    }
+    for (name, value) in &provider.headers {
+        request = request.header(name.as_str(), value.as_str());
+    }
+
+    let name = provider.name();
+    let response = request
+        .send()
+        .await
+        .map_err(|err| format!("{name} web search request failed: {err}"))?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        let body: String = body.chars().take(300).collect();
+        return Err(format!("{name} web search returned HTTP {}: {}", status.as_u16(), body.trim()));
+    }
+    let body: Value = response
+        .json()
+        .await
+        .map_err(|err| format!("{name} web search returned invalid JSON: {err}"))?;
+    // SearXNG has no result-count parameter.
+    let mut results = parse_results(provider.kind, &body);
+    results.truncate(count);
+    Ok(results)
+}
+
+/// Maps a provider's response body onto [`WebSearchResult`]s, skipping
+/// entries without a URL.
+fn parse_results(kind: WebSearchProviderKind, body: &Value) -> Vec<WebSearchResult> {
+    let (list, title, snippet, published): (&str, &str, &str, &[&str]) = match kind {
+        WebSearchProviderKind::Bing => ("/webPages/value", "name", "snippet", &["datePublished"]),
+        WebSearchProviderKind::Brave => ("/web/results", "title", "description", &["page_age", "age"]),
+        WebSearchProviderKind::Searxng => ("/results", "title", "content", &["publishedDate"]),
+        WebSearchProviderKind::Openai | WebSearchProviderKind::Custom => {
+            ("/results", "title", "snippet", &["published"])
+        }
+    };
+    let text = |entry: &Value, key: &str| {
+        entry
+            .get(key)
+            .and_then(Value::as_str)
+            .map(|value| strip_tags(value).trim().to_owned())
+            .filter(|value| !value.is_empty())
+    };
+
+    body.pointer(list)
+        .and_then(Value::as_array)
+        .map(|entries| {
+            entries
+                .iter()
+                .filter_map(|entry| {
+                    let url = text(entry, "url")?;
+                    Some(WebSearchResult {
+                        title: text(entry, title).unwrap_or_else(|| url.clone()),
+                        snippet: text(entry, snippet).unwrap_or_default(),
+                        published: published.iter().find_map(|key| text(entry, key)),
+                        url,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Drops inline markup such as Brave's `<strong>` highlighting.
+fn strip_tags(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut in_tag = false;
+    for ch in text.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => out.push(ch),
+            _ => {}
+        }
+    }
+    out
+}
+
+/// Keeps results whose host is one of `allowed_domains` or a subdomain of
+/// one, mirroring the native tool's `allowed_domains` filter.
+pub fn filter_allowed_domains(results: &mut Vec<WebSearchResult>, allowed_domains: &[String]) {
+    if allowed_domains.is_empty() {
+        return;
+    }
+    results.retain(|result| {
+        let Some(host) = reqwest::Url::parse(&result.url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_ascii_lowercase))
+        else {
+            return false;
+        };
+        allowed_domains.iter().any(|domain| {
+            let domain = domain.trim().trim_start_matches('.').to_ascii_lowercase();
+            host == domain || host.ends_with(&format!(".{domain}"))
+        })
+    });
+}
+
+/// Request timestamps in the current window, per provider and endpoint.
+static RATE_WINDOWS: LazyLock<Mutex<HashMap<String, VecDeque<Instant>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+async fn acquire_rate_limit_slot(provider: &WebSearchProvider) -> Result<(), String> {
+    if provider.requests_per_minute == 0 {
+        return Ok(());
+    }
+    let key = format!("{}|{}", provider.name(), provider.endpoint);
+    loop {
+        let wait = {
+            let mut windows = RATE_WINDOWS.lock().unwrap_or_else(PoisonError::into_inner);
+            let window = windows.entry(key.clone()).or_default();
+            match reserve_slot(window, provider.requests_per_minute, Instant::now()) {
+                None => return Ok(()),
+                Some(wait) => wait,
+            }
+        };
+        if wait > MAX_RATE_LIMIT_WAIT {
+            return Err(format!(
+                "{} web search rate limit ({} requests/minute) reached; retry in {}s",
+                provider.name(),
+                provider.requests_per_minute,
+                wait.as_secs() + 1
+            ));
+        }
+        tokio::time::sleep(wait).await;
+    }
+}
+
+/// Records a request at `now` if the window has room; otherwise returns how
+/// long until the oldest request leaves the window.
+fn reserve_slot(window: &mut VecDeque<Instant>, limit: u32, now: Instant) -> Option<Duration> {
+    while window
+        .front()
+        .is_some_and(|sent| now.saturating_duration_since(*sent) >= RATE_WINDOW)
+    {
+        window.pop_front();
+    }
+    if window.len() < limit as usize {
+        window.push_back(now);
+        return None;
+    }
+    window
+        .front()
+        .map(|oldest| RATE_WINDOW.saturating_sub(now.saturating_duration_since(*oldest)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn toml(provider: WebSearchProviderKind) -> WebSearchToml {
+        WebSearchToml {
+            provider,
+            ..WebSearchToml::default()
+        }
+    }
+
+    #[test]
+    fn resolves_provider_defaults_and_requires_endpoints() {
+        assert_eq!(WebSearchProvider::from_toml(&toml(WebSearchProviderKind::Openai)).ok(), Some(None));
+
+        let brave = WebSearchProvider::from_toml(&toml(WebSearchProviderKind::Brave))
+            .ok()
+            .flatten()
+            .expect("brave provider");
+        assert_eq!(brave.endpoint, BRAVE_ENDPOINT);
+        assert_eq!(brave.api_key_env.as_deref(), Some("BRAVE_SEARCH_API_KEY"));
+        assert_eq!(brave.requests_per_minute, 60);
+
+        let err = WebSearchProvider::from_toml(&toml(WebSearchProviderKind::Searxng))
+            .expect_err("searxng needs an endpoint");
+        assert!(err.to_string().contains("web_search.endpoint"), "{err}");
+    }
+
+    #[test]
+    fn normalizes_provider_responses() {
+        let brave = json!({"web": {"results": [
+            {"title": "Tokio", "url": "https://tokio.rs/", "description": "An <strong>async</strong> runtime", "page_age": "2024-05-01"},
+            {"title": "no url"}
+        ]}});
+        assert_eq!(
+            parse_results(WebSearchProviderKind::Brave, &brave),
+            vec![WebSearchResult {
+                title: "Tokio".to_owned(),
+                url: "https://tokio.rs/".to_owned(),
+                snippet: "An async runtime".to_owned(),
+                published: Some("2024-05-01".to_owned()),
+            }]
+        );
+
+        let bing = json!({"webPages": {"value": [{"name": "Rust", "url": "https://www.rust-lang.org/", "snippet": "A language"}]}});
+        let results = parse_results(WebSearchProviderKind::Bing, &bing);
+        assert_eq!(results[0].title, "Rust");
+        assert_eq!(results[0].published, None);
+
+        let searxng = json!({"results": [{"title": "MDN", "url": "https://developer.mozilla.org/", "content": "Docs", "engine": "duckduckgo"}]});
+        assert_eq!(parse_results(WebSearchProviderKind::Searxng, &searxng)[0].snippet, "Docs");
+    }
+
+    #[test]
+    fn allowed_domains_match_subdomains_only() {
+        let result = |url: &str| WebSearchResult {
+            title: String::new(),
+            url: url.to_owned(),
+            snippet: String::new(),
+            published: None,
+        };
+        let mut results = vec![
+            result("https://docs.rs/tokio"),
+            result("https://api.docs.rs/x"),
+            result("https://notdocs.rs/"),
+        ];
+        filter_allowed_domains(&mut results, &["docs.rs".to_owned()]);
+        let urls: Vec<_> = results.iter().map(|result| result.url.as_str()).collect();
+        assert_eq!(urls, vec!["https://docs.rs/tokio", "https://api.docs.rs/x"]);
+    }
+
+    #[test]
+    fn rate_window_waits_for_the_oldest_request() {
+        let start = Instant::now();
+        let mut window = VecDeque::new();
+        assert_eq!(reserve_slot(&mut window, 2, start), None);
+        assert_eq!(reserve_slot(&mut window, 2, start + Duration::from_secs(10)), None);
+        assert_eq!(
+            reserve_slot(&mut window, 2, start + Duration::from_secs(20)),
+            Some(Duration::from_secs(40))
+        );
+        assert_eq!(reserve_slot(&mut window, 2, start + RATE_WINDOW), None);
+        assert_eq!(window.len(), 2);
+    }
+}
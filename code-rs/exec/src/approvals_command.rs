@@ -0,0 +1,59 @@
+//! `code exec approvals`: manage the project's `.code/approvals.toml` from
+//! scripts and CI setup without starting a session.
+
+use std::path::Path;
+
+use anyhow::Context;
+use code_core::approvals_store;
+use code_core::protocol::ApprovedCommandMatchKind;
+
+use crate::cli::ApprovalsAction;
+use crate::cli::ApprovalsArgs;
+
+// Listings are the command's output, so they go to stdout.
+#[allow(clippy::print_stdout)]
+pub(crate) fn run_approvals(cwd: &Path, args: &ApprovalsArgs) -> anyhow::Result<()> {
+    let path = approvals_store::approvals_path(cwd);
+    match args.action.as_ref().unwrap_or(&ApprovalsAction::List) {
+        ApprovalsAction::List => {
+            let approvals = approvals_store::load_approvals(cwd)
+                .with_context(|| format!("failed to read {}", path.display()))?;
+            if approvals.is_empty() {
+                eprintln!("No command approvals saved in {}.", path.display());
+            }
+            for (idx, approval) in approvals.iter().enumerate() {
+                println!("{}. {}", idx + 1, approval.describe());
+            }
+        }
+        ApprovalsAction::Add { prefix, command } => {
+            let match_kind = if *prefix {
+                ApprovedCommandMatchKind::Prefix
+            } else {
+                ApprovedCommandMatchKind::Exact
+            };
+            let added = approvals_store::add_approval(cwd, command, match_kind)
+                .with_context(|| format!("failed to update {}", path.display()))?;
+            if added {
+                eprintln!("Saved the approval to {}.", path.display());
+            } else {
+                eprintln!("That approval is already saved in {}.", path.display());
+            }
+        }
+        ApprovalsAction::Revoke { index } => {
+            let approvals = approvals_store::load_approvals(cwd)
+                .with_context(|| format!("failed to read {}", path.display()))?;
+            let Some(approval) = usize::try_from(*index)
+                .ok()
+                .and_then(|index| approvals.get(index - 1))
+            else {
+                anyhow::bail!(
+                    "no saved approval #{index}; run `code exec approvals list` to see them"
+                );
+            };
+            approvals_store::revoke_approval(cwd, &approval.argv, approval.match_kind)
+                .with_context(|| format!("failed to update {}", path.display()))?;
+            eprintln!("Revoked {}.", approval.describe());
+        }
+    }
+    Ok(())
+}
@@ -8,6 +8,7 @@ use crate::auto_runtime::submit_and_wait;
 use crate::event_processor::CodexStatus;
 use crate::event_processor::EventProcessor;
 use crate::event_processor::handle_last_message;
+use crate::final_message_style::FinalMessageFormatter;
 use crate::review_output::make_assistant_message;
 use crate::review_output::make_user_message;
 use code_auto_drive_core::AutoCoordinatorCommand;
@@ -47,6 +48,7 @@ pub(crate) async fn run_auto_drive_session(
     conversation: Arc<CodexConversation>,
     mut event_processor: Box<dyn EventProcessor>,
     last_message_path: Option<PathBuf>,
+    final_message_formatter: FinalMessageFormatter,
     run_deadline: Option<Instant>,
 ) -> anyhow::Result<()> {
     let mut final_last_message: Option<String> = None;
@@ -65,6 +67,7 @@ pub(crate) async fn run_auto_drive_session(
                 final_output_json_schema: None,
             })
             .await?;
+        event_processor.turn_submitted();
         loop {
             let event = if let Some(deadline) = run_deadline {
                 let remaining = deadline.saturating_duration_since(Instant::now());
@@ -270,6 +273,10 @@ pub(crate) async fn run_auto_drive_session(
                     break;
                 }
             }
+            AutoCoordinatorEvent::Handoff { note } => {
+                eprintln!("[auto] handoff note:\n{note}");
+                final_last_message = Some(note);
+            }
             AutoCoordinatorEvent::StopAck => {
                 break;
             }
@@ -375,7 +382,10 @@ pub(crate) async fn run_auto_drive_session(
     }
 
     if let Some(path) = last_message_path.as_deref() {
-        handle_last_message(final_last_message.as_deref(), path);
+        let formatted = final_last_message
+            .as_deref()
+            .map(|message| final_message_formatter.format(message));
+        handle_last_message(formatted.as_deref(), path);
     }
 
     event_processor.print_final_output();
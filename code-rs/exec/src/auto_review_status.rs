@@ -15,6 +15,7 @@ struct AutoReviewSummary {
 
 #[derive(Debug, Clone)]
 pub(crate) struct AutoReviewCompletion {
+    agent_id: String,
     branch: Option<String>,
     worktree_path: Option<PathBuf>,
     summary: AutoReviewSummary,
@@ -71,6 +72,7 @@ impl AutoReviewTracker {
                 .unwrap_or_default();
 
             completions.push(AutoReviewCompletion {
+                agent_id: agent.id.clone(),
                 branch: agent.batch_id.clone(),
                 worktree_path: agent
                     .batch_id
@@ -87,6 +89,36 @@ impl AutoReviewTracker {
     pub(crate) fn is_running(&self) -> bool {
         !self.running.is_empty()
     }
+
+    /// Counts an agent as running before its first status update arrives.
+    pub(crate) fn track(&mut self, agent_id: &str) {
+        if !self.processed.contains(agent_id) {
+            self.running.insert(agent_id.to_owned());
+        }
+    }
+}
+
+impl AutoReviewCompletion {
+    pub(crate) fn agent_id(&self) -> &str {
+        &self.agent_id
+    }
+
+    pub(crate) fn worktree_path(&self) -> Option<&Path> {
+        self.worktree_path.as_deref()
+    }
+
+    /// The review's summary when it finished with findings.
+    pub(crate) fn findings_summary(&self) -> Option<&str> {
+        if self.error.is_some() || !self.summary.has_findings {
+            return None;
+        }
+        Some(
+            self.summary
+                .summary
+                .as_deref()
+                .unwrap_or("The review reported issues without a summary."),
+        )
+    }
 }
 
 pub(crate) fn emit_auto_review_completion(completion: &AutoReviewCompletion) {
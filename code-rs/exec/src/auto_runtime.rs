@@ -261,6 +261,7 @@ pub(crate) async fn submit_and_wait(
             final_output_json_schema: None,
         })
         .await?;
+    event_processor.turn_submitted();
 
     loop {
         let res = if let Some(deadline) = run_deadline {
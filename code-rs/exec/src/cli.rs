@@ -3,6 +3,7 @@ use clap::FromArgMatches;
 use clap::Parser;
 use clap::ValueEnum;
 use code_common::CliConfigOverrides;
+use code_core::ForkPoint;
 use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
@@ -42,6 +43,10 @@ pub struct Cli {
     #[arg(long = "sandbox", short = 's', value_enum)]
     pub sandbox_mode: Option<code_common::SandboxModeCliArg>,
 
+    /// Apply a named `[sandbox_presets.<name>]` entry from config.toml.
+    #[arg(long = "sandbox-preset", value_name = "NAME")]
+    pub sandbox_preset: Option<String>,
+
     /// Configuration profile from config.toml to specify default options.
     #[arg(long = "profile", short = 'p')]
     pub config_profile: Option<String>,
@@ -77,6 +82,32 @@ pub struct Cli {
     #[arg(long = "output-schema", value_name = "FILE")]
     pub output_schema: Option<PathBuf>,
 
+    /// Correction turns to request when the final response does not match
+    /// `--output-schema` before exiting with an error.
+    #[arg(
+        long = "output-schema-retries",
+        value_name = "N",
+        default_value_t = 2,
+        requires = "output_schema"
+    )]
+    pub output_schema_retries: u32,
+
+    /// Write the final response, parsed and validated against
+    /// `--output-schema`, to this file as JSON.
+    #[arg(long = "structured-output-file", value_name = "FILE", requires = "output_schema")]
+    pub structured_output_file: Option<PathBuf>,
+
+    /// Post-process the final message: `concise` strips preambles ("Sure!
+    /// Here's ...") and closing offers and tidies blank lines, following
+    /// `[final_message_style]` in config.toml.
+    #[arg(
+        long = "final-message-style",
+        value_enum,
+        default_value_t = FinalMessageStyle::Raw,
+        conflicts_with = "output_schema"
+    )]
+    pub final_message_style: FinalMessageStyle,
+
     #[clap(skip)]
     pub config_overrides: CliConfigOverrides,
 
@@ -89,6 +120,12 @@ pub struct Cli {
     #[arg(long = "color", value_enum, default_value_t = Color::Auto)]
     pub color: Color,
 
+    /// In human mode, ask commands to emit colors and print their output
+    /// with the original ANSI codes when stderr is a terminal. The model
+    /// still receives the output without escape sequences.
+    #[arg(long = "preserve-tool-colors", default_value_t = false, conflicts_with = "json")]
+    pub preserve_tool_colors: bool,
+
     /// Print events to stdout as JSONL.
     #[arg(
         long = "json",
@@ -98,6 +135,21 @@ pub struct Cli {
     )]
     pub json: bool,
 
+    /// In `--json` mode, write every streaming delta as its own line instead
+    /// of merging consecutive deltas.
+    #[arg(long = "json-raw-deltas", default_value_t = false)]
+    pub json_raw_deltas: bool,
+
+    /// In `--json` mode, the longest a merged delta line is held back before
+    /// it is written. Paragraph breaks and non-delta events flush it sooner.
+    #[arg(long = "json-delta-flush-ms", value_name = "MS", default_value_t = 250)]
+    pub json_delta_flush_ms: u64,
+
+    /// In `--json` mode, only write events of these kinds (comma-separated).
+    /// Writes every event when omitted.
+    #[arg(long = "json-include", value_name = "KINDS", value_enum, value_delimiter = ',')]
+    pub json_include: Vec<JsonEventKind>,
+
     /// Maximum wall-clock time budget (seconds) before aborting the run.
     ///
     /// When this budget is at least 50% consumed, the coordinator receives
@@ -109,8 +161,45 @@ pub struct Cli {
     )]
     pub max_seconds: Option<u64>,
 
+    /// Stop the run once the session has used this many tokens (input plus
+    /// output, as reported by the model provider).
+    #[arg(
+        long = "max-tokens",
+        value_name = "TOKENS",
+        value_parser = clap::value_parser!(u64).range(1..),
+        conflicts_with = "auto_drive"
+    )]
+    pub max_tokens: Option<u64>,
+
+    /// Stop the run once its estimated cost passes this many US dollars.
+    /// The estimate uses flat per-token rates, not the provider's invoice.
+    #[arg(
+        long = "max-cost-usd",
+        value_name = "USD",
+        value_parser = parse_cost_usd,
+        conflicts_with = "auto_drive"
+    )]
+    pub max_cost_usd: Option<f64>,
+
+    /// Keep the session open and re-run the prompt whenever a file under
+    /// PATH changes. May be repeated; `--max-seconds` then bounds each run.
+    #[arg(long = "watch", value_name = "PATH")]
+    pub watch: Vec<PathBuf>,
+
+    /// Listen on a Unix socket at PATH while the session runs, so an external
+    /// supervisor can inject developer notes between turns (see
+    /// `--inject-note`). Only the user running the session may connect.
+    #[arg(long = "control-socket", value_name = "PATH")]
+    pub control_socket: Option<PathBuf>,
+
+    /// Send TEXT as a developer note to the session listening on
+    /// `--control-socket`, then exit instead of starting a run.
+    #[arg(long = "inject-note", value_name = "TEXT", requires = "control_socket")]
+    pub inject_note: Option<String>,
+
     /// Maximum number of Auto Drive coordinator turns before stopping (0 = unlimited).
-    /// Only meaningful when --auto is enabled.
+    /// Reaching the cap triggers one wrap-up turn whose handoff note becomes
+    /// the last message. Only meaningful when --auto is enabled.
     #[arg(long = "turn-cap", value_name = "TURNS")]
     pub turn_cap: Option<u32>,
 
@@ -128,6 +217,46 @@ pub struct Cli {
     #[arg(long = "review-output-json", value_name = "FILE")]
     pub review_output_json: Option<PathBuf>,
 
+    /// When running /review, write the findings to this file as SARIF 2.1.0
+    /// (e.g., for GitHub code scanning). Locations are relative to the
+    /// repository root whenever the run is inside a git repository.
+    #[arg(long = "review-output-sarif", value_name = "FILE")]
+    pub review_output_sarif: Option<PathBuf>,
+
+    /// Write finding paths in the review output JSON relative to the
+    /// repository root, and record the repo root and commit SHA alongside
+    /// them, so the JSON stays usable on other machines and in CI.
+    #[arg(long = "review-paths-relative", default_value_t = false)]
+    pub review_paths_relative: bool,
+
+    /// Review the uncommitted changes in a parallel worktree while the prompt
+    /// runs. Once both finish, a final turn applies the review's accepted
+    /// fixes and re-runs validation.
+    #[arg(long = "review-async", default_value_t = false)]
+    pub review_async: bool,
+
+    /// After the run, ask the model for a hand-off summary (state of work,
+    /// open problems, next steps) and pin it to the session, so
+    /// `code resume --latest` opens the TUI with the summary on top.
+    #[arg(
+        long = "handoff",
+        default_value_t = false,
+        conflicts_with_all = ["auto_drive", "watch"]
+    )]
+    pub handoff: bool,
+
+    /// Report how long each startup subsystem (config, MCP servers, skills,
+    /// hooks, agents) took to initialize. Printed to stderr once the session
+    /// is configured.
+    #[arg(long = "profile-startup", default_value_t = false)]
+    pub profile_startup: bool,
+
+    /// Treat the prompt (argument or stdin) as a template: `{{env.VAR}}` is
+    /// replaced by the environment variable and `{{file:path}}` by the file's
+    /// contents, relative paths resolving against the current directory.
+    #[arg(long = "prompt-template", default_value_t = false)]
+    pub prompt_template: bool,
+
     /// Initial instructions for the agent. If not provided as an argument (or
     /// if `-` is used), instructions are read from stdin.
     #[arg(value_name = "PROMPT")]
@@ -141,6 +270,94 @@ pub enum Command {
 
     /// Run a code review against the current repository.
     Review(ReviewArgs),
+
+    /// Render a stored session to Markdown or HTML.
+    Export(ExportArgs),
+
+    /// Rebuild a stored session's history as of one recorded event and dump it as JSON.
+    Inspect(InspectArgs),
+
+    /// List, add, or revoke the command approvals saved in `.code/approvals.toml`.
+    Approvals(ApprovalsArgs),
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct ApprovalsArgs {
+    #[command(subcommand)]
+    pub action: Option<ApprovalsAction>,
+}
+
+#[derive(Debug, Clone, clap::Subcommand)]
+pub enum ApprovalsAction {
+    /// Print the saved approvals with the numbers `revoke` takes (the default).
+    List,
+
+    /// Approve a command for every session in this project.
+    Add {
+        /// Also approve commands that start with COMMAND.
+        #[arg(long, default_value_t = false)]
+        prefix: bool,
+
+        /// Command and arguments to approve.
+        #[arg(value_name = "COMMAND", required = true, trailing_var_arg = true)]
+        command: Vec<String>,
+    },
+
+    /// Remove the approval with this number from `list`.
+    Revoke {
+        #[arg(value_name = "N", value_parser = clap::value_parser!(u64).range(1..))]
+        index: u64,
+    },
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct InspectArgs {
+    /// Rollout file or session id to inspect.
+    #[arg(value_name = "ROLLOUT_OR_ID")]
+    pub session: String,
+
+    /// Stop after the event with this sequence number.
+    #[arg(long = "at-seq", value_name = "N")]
+    pub at_seq: u64,
+
+    /// Submission id the sequence number belongs to. Sequence numbers restart
+    /// with every submission; defaults to the last one in the rollout.
+    #[arg(long = "turn", value_name = "SUBMISSION_ID")]
+    pub turn: Option<String>,
+
+    /// File to write the snapshot to (defaults to stdout).
+    #[arg(long = "output", short = 'o', value_name = "FILE")]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct ExportArgs {
+    /// Rollout file or session id to export. Use --last for the most recent session.
+    #[arg(value_name = "ROLLOUT_OR_ID", conflicts_with = "last")]
+    pub session: Option<String>,
+
+    /// Export the most recent recorded session.
+    #[arg(long = "last", default_value_t = false)]
+    pub last: bool,
+
+    /// Consider sessions from every directory when combined with --last.
+    #[arg(long = "all", default_value_t = false)]
+    pub all: bool,
+
+    /// Output format. Defaults to HTML when --output ends in .html, Markdown otherwise.
+    #[arg(long = "format", value_enum)]
+    pub format: Option<ExportFormat>,
+
+    /// File to write the transcript to (defaults to stdout).
+    #[arg(long = "output", short = 'o', value_name = "FILE")]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum ExportFormat {
+    Markdown,
+    Html,
 }
 
 #[derive(Args, Debug)]
@@ -160,6 +377,15 @@ struct ResumeArgsRaw {
     #[arg(long = "all", default_value_t = false)]
     all: bool,
 
+    /// Continue in a copy of the session instead, leaving the original as it was.
+    #[arg(long = "fork", default_value_t = false)]
+    fork: bool,
+
+    /// Fork right after this recorded event: `SEQ` in the last submission, or
+    /// `SUBMISSION_ID:SEQ`. Defaults to the end of the session.
+    #[arg(long = "fork-at", value_name = "[SUBMISSION_ID:]SEQ", requires = "fork")]
+    fork_at: Option<ForkPoint>,
+
     /// Optional image(s) to attach to the prompt sent after resuming.
     #[arg(
         long = "image",
@@ -187,6 +413,12 @@ pub struct ResumeArgs {
     /// Show all sessions (disable cwd filtering) when combined with --last.
     pub all: bool,
 
+    /// Continue in a copy of the session instead, leaving the original as it was.
+    pub fork: bool,
+
+    /// Where to cut the copy when forking; the end of the session when `None`.
+    pub fork_at: Option<ForkPoint>,
+
     /// Optional image(s) to attach to the prompt sent after resuming.
     pub images: Vec<PathBuf>,
 
@@ -206,6 +438,8 @@ impl From<ResumeArgsRaw> for ResumeArgs {
             session_id,
             last: raw.last,
             all: raw.all,
+            fork: raw.fork,
+            fork_at: raw.fork_at,
             images: raw.images,
             prompt,
         }
@@ -266,6 +500,20 @@ pub struct ReviewArgs {
     /// Custom review instructions. If `-` is used, read from stdin.
     #[arg(value_name = "PROMPT", value_hint = clap::ValueHint::Other)]
     pub prompt: Option<String>,
+
+    /// Reviewer profile from `[review_profiles.<name>]` to review with.
+    #[arg(long = "review-profile", value_name = "NAME")]
+    pub review_profile: Option<String>,
+
+    /// Pin the review to the commits between REF's merge base with HEAD and
+    /// HEAD, instead of letting the reviewer infer the diff. REF may be a
+    /// branch or a commit; CI runs typically pass the PR's target branch.
+    #[arg(
+        long = "review-base",
+        value_name = "REF",
+        conflicts_with_all = ["uncommitted", "base", "commit"]
+    )]
+    pub review_base: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
@@ -277,6 +525,53 @@ pub enum Color {
     Auto,
 }
 
+/// How exec post-processes the final assistant message.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum FinalMessageStyle {
+    /// The message exactly as the model wrote it.
+    #[default]
+    Raw,
+    /// The answer body without boilerplate openers and sign-offs.
+    Concise,
+}
+
+/// Event categories selectable with `--json-include`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum)]
+#[value(rename_all = "snake_case")]
+pub enum JsonEventKind {
+    /// The config summary, prompt, and session configuration.
+    Session,
+    /// Task start/completion, aborted turns, and token usage.
+    Task,
+    /// Assistant messages and their deltas.
+    AgentMessage,
+    /// Reasoning summaries, raw reasoning, and their deltas.
+    Reasoning,
+    /// Shell commands, their output, approvals, and sandbox denials.
+    ExecCommand,
+    /// Patch application, patch approvals, and turn diffs.
+    Patch,
+    /// MCP, custom, web search, and image tool calls.
+    ToolCall,
+    /// Entering and leaving review mode, including the findings.
+    Review,
+    /// Plan updates.
+    Plan,
+    /// Error and warning events.
+    Error,
+    /// Everything else (background notices, hooks, user messages, ...).
+    Other,
+}
+
+fn parse_cost_usd(value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(usd) if usd.is_finite() && usd > 0.0 => Ok(usd),
+        Ok(_) => Err("must be a positive number of dollars".to_owned()),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -312,6 +607,23 @@ mod tests {
         assert_eq!(args.images.len(), 2);
     }
 
+    #[test]
+    fn resume_fork_at_requires_fork() {
+        let cli = Cli::parse_from(["code-exec", "resume", "--last", "--fork", "--fork-at", "3:12"]);
+        let Some(Command::Resume(args)) = cli.command else {
+            panic!("expected resume command");
+        };
+        assert!(args.fork);
+        assert_eq!(
+            args.fork_at,
+            Some(ForkPoint {
+                turn: Some("3".to_owned()),
+                event_seq: 12,
+            })
+        );
+        assert!(Cli::try_parse_from(["code-exec", "resume", "--last", "--fork-at", "12"]).is_err());
+    }
+
     #[test]
     fn review_parses_base_branch_scope() {
         let cli = Cli::parse_from(["code-exec", "review", "--base", "main"]);
@@ -322,4 +634,111 @@ mod tests {
         assert!(!args.uncommitted);
         assert_eq!(args.commit, None);
     }
+
+    #[test]
+    fn json_include_parses_comma_separated_kinds() {
+        let cli = Cli::parse_from([
+            "code-exec",
+            "--json",
+            "--json-include",
+            "agent_message,exec_command",
+            "--json-include=review",
+            "echo hi",
+        ]);
+        assert_eq!(
+            cli.json_include,
+            vec![
+                JsonEventKind::AgentMessage,
+                JsonEventKind::ExecCommand,
+                JsonEventKind::Review
+            ]
+        );
+        assert!(Cli::try_parse_from(["code-exec", "--json-include", "bogus", "hi"]).is_err());
+    }
+
+    #[test]
+    fn export_parses_last_and_output() {
+        let cli = Cli::parse_from(["code-exec", "export", "--last", "-o", "session.html"]);
+        let Some(Command::Export(args)) = cli.command else {
+            panic!("expected export command");
+        };
+        assert!(args.last);
+        assert_eq!(args.session, None);
+        assert_eq!(args.output, Some(PathBuf::from("session.html")));
+        assert_eq!(args.format, None);
+    }
+
+    #[test]
+    fn inspect_requires_at_seq() {
+        let cli = Cli::parse_from([
+            "code-exec",
+            "inspect",
+            "rollout.jsonl",
+            "--at-seq",
+            "42",
+            "--turn",
+            "7",
+        ]);
+        let Some(Command::Inspect(args)) = cli.command else {
+            panic!("expected inspect command");
+        };
+        assert_eq!(args.session, "rollout.jsonl");
+        assert_eq!(args.at_seq, 42);
+        assert_eq!(args.turn.as_deref(), Some("7"));
+        assert!(Cli::try_parse_from(["code-exec", "inspect", "rollout.jsonl"]).is_err());
+    }
+
+    #[test]
+    fn approvals_add_takes_the_rest_as_the_command() {
+        let cli = Cli::parse_from(["code-exec", "approvals", "add", "--prefix", "cargo", "test", "--all"]);
+        let Some(Command::Approvals(ApprovalsArgs {
+            action: Some(ApprovalsAction::Add { prefix, command }),
+        })) = cli.command
+        else {
+            panic!("expected approvals add");
+        };
+        assert!(prefix);
+        assert_eq!(command, vec!["cargo", "test", "--all"]);
+        assert!(Cli::try_parse_from(["code-exec", "approvals", "revoke", "0"]).is_err());
+        assert!(Cli::try_parse_from(["code-exec", "approvals", "add"]).is_err());
+    }
+
+    #[test]
+    fn budgets_parse_and_reject_non_positive_values() {
+        let cli = Cli::parse_from(["code-exec", "--max-tokens", "50000", "--max-cost-usd", "1.5", "hi"]);
+        assert_eq!(cli.max_tokens, Some(50_000));
+        assert_eq!(cli.max_cost_usd, Some(1.5));
+        assert!(Cli::try_parse_from(["code-exec", "--max-cost-usd", "0", "hi"]).is_err());
+        assert!(Cli::try_parse_from(["code-exec", "--max-tokens", "0", "hi"]).is_err());
+        assert!(Cli::try_parse_from(["code-exec", "--auto", "--max-tokens", "10", "hi"]).is_err());
+    }
+
+    #[test]
+    fn inject_note_requires_control_socket() {
+        let cli = Cli::parse_from([
+            "code-exec",
+            "--control-socket",
+            "/tmp/code.sock",
+            "--inject-note",
+            "Stop refactoring; fix the failing test.",
+        ]);
+        assert_eq!(cli.control_socket, Some(PathBuf::from("/tmp/code.sock")));
+        assert_eq!(cli.inject_note.as_deref(), Some("Stop refactoring; fix the failing test."));
+        assert!(Cli::try_parse_from(["code-exec", "--inject-note", "hi"]).is_err());
+    }
+
+    #[test]
+    fn prompt_template_flag_parses() {
+        let cli = Cli::parse_from(["code-exec", "--prompt-template", "-"]);
+        assert!(cli.prompt_template);
+        assert_eq!(cli.prompt.as_deref(), Some("-"));
+        assert!(!Cli::parse_from(["code-exec", "hi"]).prompt_template);
+    }
+
+    #[test]
+    fn handoff_conflicts_with_auto_and_watch() {
+        assert!(Cli::parse_from(["code-exec", "--handoff", "hi"]).handoff);
+        assert!(Cli::try_parse_from(["code-exec", "--handoff", "--auto", "hi"]).is_err());
+        assert!(Cli::try_parse_from(["code-exec", "--handoff", "--watch", "src", "hi"]).is_err());
+    }
 }
@@ -0,0 +1,278 @@
+//! `--control-socket`: a Unix socket through which an external supervisor
+//! can steer a running `code exec` session.
+//!
+//! Requests and replies are single lines of JSON. The only request today is
+//! `{"type":"inject_note","text":"..."}`, which queues `text` as a developer
+//! message for the next turn (`Op::AddPendingInputDeveloper`); an idle
+//! session starts a turn for it right away. Replies are `{"ok":true}` or
+//! `{"ok":false,"error":"..."}`.
+//!
+//! The socket file is created with mode 0600 and connections from other
+//! users are refused, so only the account running the session can inject.
+//! `code exec --control-socket PATH --inject-note TEXT` is the client side.
+
+use std::path::Path;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ControlRequest {
+    InjectNote { text: String },
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+struct ControlReply {
+    ok: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl ControlReply {
+    fn ok() -> Self {
+        Self {
+            ok: true,
+            error: None,
+        }
+    }
+
+    fn error(message: impl Into<String>) -> Self {
+        Self {
+            ok: false,
+            error: Some(message.into()),
+        }
+    }
+}
+
+/// Validates a note before it reaches the session.
+fn parse_request(line: &str) -> Result<ControlRequest, String> {
+    let request: ControlRequest =
+        serde_json::from_str(line).map_err(|err| format!("invalid request: {err}"))?;
+    match &request {
+        ControlRequest::InjectNote { text } if text.trim().is_empty() => {
+            Err("inject_note requires non-empty text".to_owned())
+        }
+        ControlRequest::InjectNote { .. } => Ok(request),
+    }
+}
+
+#[cfg(unix)]
+mod unix {
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::Path;
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    use anyhow::Context;
+    use code_core::CodexConversation;
+    use code_core::protocol::Op;
+    use tokio::io::AsyncBufReadExt;
+    use tokio::io::AsyncWriteExt;
+    use tokio::io::BufReader;
+    use tokio::net::UnixListener;
+    use tokio::net::UnixStream;
+    use tokio::task::JoinHandle;
+
+    use super::ControlReply;
+    use super::ControlRequest;
+    use super::parse_request;
+
+    /// Serves the control socket for as long as it is alive; dropping it stops
+    /// accepting connections and removes the socket file.
+    pub(crate) struct ControlSocket {
+        path: PathBuf,
+        task: JoinHandle<()>,
+    }
+
+    impl ControlSocket {
+        pub(crate) fn bind(path: &Path, conversation: Arc<CodexConversation>) -> anyhow::Result<Self> {
+            if path.exists() {
+                if std::os::unix::net::UnixStream::connect(path).is_ok() {
+                    anyhow::bail!("{} is in use by another session", path.display());
+                }
+                // Left behind by a session that did not shut down cleanly.
+                std::fs::remove_file(path)
+                    .with_context(|| format!("removing stale socket {}", path.display()))?;
+            }
+            let listener = UnixListener::bind(path)
+                .with_context(|| format!("binding {}", path.display()))?;
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))
+                .with_context(|| format!("restricting permissions on {}", path.display()))?;
+
+            let task = tokio::spawn(async move {
+                loop {
+                    let stream = match listener.accept().await {
+                        Ok((stream, _)) => stream,
+                        Err(err) => {
+                            tracing::warn!("control socket accept failed: {err}");
+                            continue;
+                        }
+                    };
+                    let conversation = conversation.clone();
+                    tokio::spawn(async move {
+                        if let Err(err) = serve_connection(stream, &conversation).await {
+                            tracing::debug!("control socket connection closed: {err}");
+                        }
+                    });
+                }
+            });
+            Ok(Self {
+                path: path.to_path_buf(),
+                task,
+            })
+        }
+    }
+
+    impl Drop for ControlSocket {
+        fn drop(&mut self) {
+            self.task.abort();
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+
+    fn same_user(stream: &UnixStream) -> bool {
+        // SAFETY: getuid has no preconditions and cannot fail.
+        let uid = unsafe { libc::getuid() };
+        stream.peer_cred().is_ok_and(|cred| cred.uid() == uid)
+    }
+
+    async fn serve_connection(
+        stream: UnixStream,
+        conversation: &CodexConversation,
+    ) -> std::io::Result<()> {
+        let authorized = same_user(&stream);
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+        while let Some(line) = lines.next_line().await? {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let reply = if !authorized {
+                ControlReply::error("connection refused: peer is a different user")
+            } else {
+                match parse_request(&line) {
+                    Ok(ControlRequest::InjectNote { text }) => {
+                        match conversation.submit(Op::AddPendingInputDeveloper { text }).await {
+                            Ok(_) => ControlReply::ok(),
+                            Err(err) => ControlReply::error(format!("session rejected note: {err}")),
+                        }
+                    }
+                    Err(message) => ControlReply::error(message),
+                }
+            };
+            let mut encoded = serde_json::to_string(&reply).unwrap_or_default();
+            encoded.push('\n');
+            writer.write_all(encoded.as_bytes()).await?;
+            if !authorized {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Client side of `--inject-note`: delivers one note and waits for the reply.
+    pub(crate) async fn send_note(path: &Path, text: &str) -> anyhow::Result<()> {
+        let stream = UnixStream::connect(path)
+            .await
+            .with_context(|| format!("connecting to {}", path.display()))?;
+        let (reader, mut writer) = stream.into_split();
+        let mut request = serde_json::to_string(&ControlRequest::InjectNote {
+            text: text.to_owned(),
+        })?;
+        request.push('\n');
+        writer.write_all(request.as_bytes()).await?;
+
+        let Some(line) = BufReader::new(reader).lines().next_line().await? else {
+            anyhow::bail!("{} closed the connection without replying", path.display());
+        };
+        let reply: ControlReply = serde_json::from_str(&line)
+            .with_context(|| format!("unexpected reply from {}", path.display()))?;
+        if reply.ok {
+            Ok(())
+        } else {
+            anyhow::bail!(reply.error.unwrap_or_else(|| "note was rejected".to_owned()))
+        }
+    }
+}
+
+#[cfg(unix)]
+pub(crate) use unix::ControlSocket;
+
+#[cfg(unix)]
+pub(crate) async fn send_note(path: &Path, text: &str) -> anyhow::Result<()> {
+    unix::send_note(path, text).await
+}
+
+#[cfg(not(unix))]
+pub(crate) struct ControlSocket;
+
+#[cfg(not(unix))]
+impl ControlSocket {
+    pub(crate) fn bind(
+        _path: &Path,
+        _conversation: std::sync::Arc<code_core::CodexConversation>,
+    ) -> anyhow::Result<Self> {
+        anyhow::bail!("--control-socket is only supported on Unix")
+    }
+}
+
+#[cfg(not(unix))]
+pub(crate) async fn send_note(_path: &Path, _text: &str) -> anyhow::Result<()> {
+    anyhow::bail!("--inject-note is only supported on Unix")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_inject_note_requests() {
+        assert_eq!(
+            parse_request(r#"{"type":"inject_note","text":"Wrap up and run the tests."}"#),
+            Ok(ControlRequest::InjectNote {
+                text: "Wrap up and run the tests.".to_owned(),
+            })
+        );
+        assert!(parse_request(r#"{"type":"inject_note","text":"  "}"#).is_err());
+        assert!(parse_request(r#"{"type":"shutdown"}"#).is_err());
+    }
+
+    #[test]
+    fn replies_omit_missing_errors() {
+        assert_eq!(serde_json::to_string(&ControlReply::ok()).unwrap(), r#"{"ok":true}"#);
+        assert_eq!(
+            serde_json::to_string(&ControlReply::error("nope")).unwrap(),
+            r#"{"ok":false,"error":"nope"}"#
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn send_note_reports_rejections() {
+        use tokio::io::AsyncBufReadExt;
+        use tokio::io::AsyncWriteExt;
+        use tokio::io::BufReader;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("control.sock");
+        let listener = tokio::net::UnixListener::bind(&path).unwrap();
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let (reader, mut writer) = stream.into_split();
+            let line = BufReader::new(reader).lines().next_line().await.unwrap().unwrap();
+            writer
+                .write_all(b"{\"ok\":false,\"error\":\"busy\"}\n")
+                .await
+                .unwrap();
+            line
+        });
+
+        let err = send_note(&path, "stop").await.unwrap_err();
+        assert_eq!(err.to_string(), "busy");
+        assert_eq!(
+            server.await.unwrap(),
+            r#"{"type":"inject_note","text":"stop"}"#
+        );
+    }
+}
@@ -37,6 +37,7 @@ use std::time::Instant;
 use crate::event_processor::CodexStatus;
 use crate::event_processor::EventProcessor;
 use crate::event_processor::handle_last_message;
+use crate::final_message_style::FinalMessageFormatter;
 use code_common::create_config_summary_entries;
 
 /// This should be configurable. When used in CI, users may not want to impose
@@ -88,6 +89,13 @@ pub(crate) struct EventProcessorWithHumanOutput {
     last_message_path: Option<PathBuf>,
     last_turn_diff: Option<String>,
     final_message: Option<String>,
+    /// Applied to the final message printed to stdout and written to
+    /// `--output-last-message`.
+    final_message_formatter: FinalMessageFormatter,
+
+    /// Print command output with its original ANSI codes instead of stripping
+    /// them (`--preserve-tool-colors`).
+    preserve_tool_colors: bool,
 
     /// If true, stop after the first `TaskComplete` event (default exec mode).
     /// Auto Drive sessions keep running across multiple turns, so they leave
@@ -126,6 +134,8 @@ impl EventProcessorWithHumanOutput {
                 last_message_path,
                 last_turn_diff: None,
                 final_message: None,
+                final_message_formatter: FinalMessageFormatter::default(),
+                preserve_tool_colors: false,
                 stop_on_task_complete,
             }
         } else {
@@ -149,11 +159,51 @@ impl EventProcessorWithHumanOutput {
                 last_message_path,
                 last_turn_diff: None,
                 final_message: None,
+                final_message_formatter: FinalMessageFormatter::default(),
+                preserve_tool_colors: false,
                 stop_on_task_complete,
             }
         }
     }
 
+    pub(crate) fn with_final_message_formatter(mut self, formatter: FinalMessageFormatter) -> Self {
+        self.final_message_formatter = formatter;
+        self
+    }
+
+    pub(crate) fn with_preserved_tool_colors(mut self, preserve: bool) -> Self {
+        self.preserve_tool_colors = preserve;
+        self
+    }
+
+    fn print_tool_output(&self, text: &str) {
+        let text = tool_output_for_display(text, self.preserve_tool_colors);
+        if self.preserve_tool_colors {
+            eprintln!("{text}");
+        } else {
+            eprintln!("{}", text.style(self.dimmed));
+        }
+    }
+}
+
+/// The first lines of a command's output as printed to stderr. Escape codes
+/// are stripped unless colors are preserved, in which case a trailing reset
+/// keeps an unterminated color from bleeding into the next line.
+fn tool_output_for_display(text: &str, preserve_colors: bool) -> String {
+    let text = if preserve_colors {
+        std::borrow::Cow::Borrowed(text)
+    } else {
+        code_core::util::strip_ansi_escapes(text)
+    };
+    let mut lines = text
+        .lines()
+        .take(MAX_OUTPUT_LINES_FOR_EXEC_TOOL_CALL)
+        .collect::<Vec<_>>()
+        .join("\n");
+    if preserve_colors && lines.contains('\u{1b}') {
+        lines.push_str("\u{1b}[0m");
+    }
+    lines
 }
 
 struct ExecCommandBegin {
@@ -217,7 +267,7 @@ impl EventProcessor for EventProcessorWithHumanOutput {
     fn process_event(&mut self, event: Event) -> CodexStatus {
         let Event { id, msg, .. } = event;
         match msg {
-            EventMsg::Error(ErrorEvent { message }) => {
+            EventMsg::Error(ErrorEvent { message, .. }) => {
                 let prefix = "ERROR:".style(self.red);
                 ts_println!(self, "{prefix} {message}");
             }
@@ -225,9 +275,51 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                 let prefix = "WARNING:".style(self.yellow);
                 ts_println!(self, "{prefix} {message}");
             }
+            EventMsg::PartialTurn(ev) => {
+                let prefix = "PARTIAL TURN:".style(self.yellow);
+                let calls = ev.tool_calls.len();
+                ts_println!(self, "{prefix} kept {calls} completed tool call(s) from the failed turn");
+                if let Some(text) = ev.partial_message.as_deref() {
+                    eprintln!("{}", text.style(self.dimmed));
+                }
+            }
             EventMsg::BackgroundEvent(BackgroundEventEvent { message }) => {
                 ts_println!(self, "{}", message.style(self.dimmed));
             }
+            EventMsg::McpAuthPrompt(ev) => {
+                let prefix = "MCP SIGN-IN:".style(self.yellow);
+                let url = ev
+                    .verification_uri_complete
+                    .as_deref()
+                    .unwrap_or(&ev.verification_uri);
+                ts_println!(
+                    self,
+                    "{prefix} `{}` needs authorization. Open {url} and enter the code {} (expires in {} min).",
+                    ev.server_name,
+                    ev.user_code,
+                    ev.expires_in_secs.div_ceil(60)
+                );
+            }
+            EventMsg::GitHeadChanged(ev) => {
+                let prefix = "WARNING:".style(self.yellow);
+                ts_println!(self, "{prefix} {}", ev.message);
+            }
+            EventMsg::TurnReverted(ev) => {
+                if ev.success {
+                    ts_println!(self, "{}", ev.message.style(self.dimmed));
+                    for file in &ev.files {
+                        eprintln!("  {}", file.path.style(self.dimmed));
+                    }
+                } else {
+                    let prefix = "ERROR:".style(self.red);
+                    ts_println!(self, "{prefix} {}", ev.message);
+                }
+            }
+            EventMsg::SandboxDenied(ev) => {
+                let prefix = "WARNING:".style(self.yellow);
+                let command = escape_command(&ev.command);
+                ts_println!(self, "{prefix} sandbox denied {command}");
+            }
             EventMsg::RequestUserInput(ev) => {
                 let question_count = ev.questions.len();
                 ts_println!(
@@ -262,9 +354,12 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             | EventMsg::BrowserSnapshot(_)
             | EventMsg::ListCustomPromptsResponse(_)
             | EventMsg::ListSkillsResponse(_)
+            | EventMsg::PromptPreview(_)
+            | EventMsg::RequestBudgetCheck(_)
             | EventMsg::McpListToolsResponse(_)
             | EventMsg::ViewImageToolCall(_)
             | EventMsg::AutoContextCheck(_)
+            | EventMsg::Heartbeat(_)
             | EventMsg::ExecCommandOutputDelta(_)
             | EventMsg::WebSearchBegin(_)
             | EventMsg::ConversationPath(_)
@@ -277,6 +372,9 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             | EventMsg::BrowserScreenshotUpdate(_)
             | EventMsg::AgentStatusUpdate(_)
             | EventMsg::CustomToolCallUpdate(_)
+            | EventMsg::UserInputQueued(_)
+            | EventMsg::QueuedUserInputDelivered(_)
+            | EventMsg::UserInputInterrupting(_)
             | EventMsg::TaskLifecycle(_) => {}
             EventMsg::TaskStarted => {
                 // Reset per-turn diff cache so we only print new diffs once.
@@ -287,7 +385,10 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             EventMsg::TaskComplete(TaskCompleteEvent { last_agent_message }) => {
                 self.final_message.clone_from(&last_agent_message);
                 if let Some(output_file) = self.last_message_path.as_deref() {
-                    handle_last_message(last_agent_message.as_deref(), output_file);
+                    let formatted = last_agent_message
+                        .as_deref()
+                        .map(|message| self.final_message_formatter.format(message));
+                    handle_last_message(formatted.as_deref(), output_file);
                 }
                 if self.stop_on_task_complete {
                     return CodexStatus::InitiateShutdown;
@@ -296,11 +397,13 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             }
             EventMsg::TokenCount(ev) => {
                 if let Some(usage_info) = ev.info {
-                    ts_println!(
-                        self,
-                        "tokens used: {}",
-                        format_with_separators_u64(usage_info.total_token_usage.blended_total())
-                    );
+                    let used = format_with_separators_u64(usage_info.total_token_usage.blended_total());
+                    if usage_info.retry_wasted_output_tokens > 0 {
+                        let wasted = format_with_separators_u64(usage_info.retry_wasted_output_tokens);
+                        ts_println!(self, "tokens used: {used} (~{wasted} regenerated after stream retries)");
+                    } else {
+                        ts_println!(self, "tokens used: {used}");
+                    }
                 }
             }
             EventMsg::AgentMessageDelta(AgentMessageDeltaEvent { delta }) => {
@@ -428,32 +531,25 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     (String::new(), format!("exec('{call_id}')"))
                 };
 
-                let truncated_stdout = stdout
-                    .lines()
-                    .take(MAX_OUTPUT_LINES_FOR_EXEC_TOOL_CALL)
-                    .collect::<Vec<_>>()
-                    .join("\n");
-                let truncated_stderr = stderr
-                    .lines()
-                    .take(MAX_OUTPUT_LINES_FOR_EXEC_TOOL_CALL)
-                    .collect::<Vec<_>>()
-                    .join("\n");
                 if exit_code == 0 {
                     let title = format!("{call} succeeded{duration}:");
                     ts_println!(self, "{}", title.style(self.green));
-                    if !truncated_stdout.is_empty() {
-                        eprintln!("{}", truncated_stdout.style(self.dimmed));
+                    if !stdout.is_empty() {
+                        self.print_tool_output(&stdout);
                     }
                 } else {
                     let title = format!("{call} exited {exit_code}{duration}:");
                     ts_println!(self, "{}", title.style(self.red));
-                    if !truncated_stdout.is_empty() {
-                        eprintln!("{}", truncated_stdout.style(self.dimmed));
+                    if !stdout.is_empty() {
+                        self.print_tool_output(&stdout);
                         eprintln!();
                     }
                     eprintln!("ERROR");
-                    if !truncated_stderr.is_empty() {
-                        eprintln!("{truncated_stderr}");
+                    if !stderr.is_empty() {
+                        eprintln!(
+                            "{}",
+                            tool_output_for_display(&stderr, self.preserve_tool_colors)
+                        );
                     }
                 }
             }
@@ -834,7 +930,7 @@ impl EventProcessor for EventProcessorWithHumanOutput {
 
     fn print_final_output(&mut self) {
         if let Some(message) = &self.final_message {
-            write_stdout_message_or_panic(message);
+            write_stdout_message_or_panic(&self.final_message_formatter.format(message));
         }
     }
 }
@@ -909,10 +1005,30 @@ mod tests {
             last_message_path: None,
             last_turn_diff: None,
             final_message: None,
+            final_message_formatter: FinalMessageFormatter::default(),
+            preserve_tool_colors: false,
             stop_on_task_complete: false,
         }
     }
 
+    #[test]
+    fn tool_output_strips_or_preserves_colors() {
+        let colored = "\u{1b}[31merror\u{1b}[0m: boom\n\u{1b}[33mwarning";
+        assert_eq!(
+            tool_output_for_display(colored, false),
+            "error: boom\nwarning"
+        );
+        assert_eq!(
+            tool_output_for_display(colored, true),
+            "\u{1b}[31merror\u{1b}[0m: boom\n\u{1b}[33mwarning\u{1b}[0m"
+        );
+        let long = (0..30).map(|n| n.to_string()).collect::<Vec<_>>().join("\n");
+        assert_eq!(
+            tool_output_for_display(&long, false).lines().count(),
+            MAX_OUTPUT_LINES_FOR_EXEC_TOOL_CALL
+        );
+    }
+
     #[cfg(unix)]
     fn capture_stdout<F: FnOnce()>(func: F) -> String {
         let mut fds = [0_i32; 2];
@@ -1,26 +1,208 @@
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::io::Write;
 use std::path::PathBuf;
+use std::time::Duration;
+use std::time::Instant;
 
+use code_core::citations::Source;
+use code_core::citations::SourceTracker;
 use code_core::config::Config;
+use code_core::event_batch::merge_stream_delta;
+use code_core::event_batch::stream_delta_text;
 use code_core::protocol::Event;
 use code_core::protocol::EventMsg;
 use code_core::protocol::TaskCompleteEvent;
 use serde_json::json;
 
+use crate::cli::JsonEventKind;
 use crate::event_processor::CodexStatus;
 use crate::event_processor::EventProcessor;
 use crate::event_processor::handle_last_message;
+use crate::final_message_style::FinalMessageFormatter;
+use crate::turn_timing::TurnTimer;
 use code_common::create_config_summary_entries;
 
+/// How streaming deltas (assistant text, reasoning) are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum JsonDeltaMode {
+    /// Merge consecutive deltas of one stream into a single line, flushed at
+    /// paragraph breaks, once `flush_after` has passed since the first merged
+    /// delta, or before any other event.
+    Coalesce { flush_after: Duration },
+    /// Write every delta as its own line.
+    Raw,
+}
+
+/// A delta line being accumulated, with the time its first delta arrived.
+struct PendingDelta {
+    event: Event,
+    started: Instant,
+}
+
 pub(crate) struct EventProcessorWithJsonOutput {
     last_message_path: Option<PathBuf>,
+    /// Applied to the message written to `--output-last-message`; the event
+    /// stream keeps the model's text as is.
+    final_message_formatter: FinalMessageFormatter,
     had_error: bool,
+    delta_mode: JsonDeltaMode,
+    pending_delta: Option<PendingDelta>,
+    /// Kinds selected with `--json-include`; empty writes every event.
+    include: HashSet<JsonEventKind>,
+    /// Tracked even when tool calls are filtered out, so agent messages
+    /// still list their sources.
+    sources: SourceTracker,
+    timer: TurnTimer,
 }
 
 impl EventProcessorWithJsonOutput {
-    pub fn new(last_message_path: Option<PathBuf>) -> Self {
-        Self { last_message_path, had_error: false }
+    pub fn new(
+        last_message_path: Option<PathBuf>,
+        delta_mode: JsonDeltaMode,
+        include: Vec<JsonEventKind>,
+    ) -> Self {
+        Self {
+            last_message_path,
+            final_message_formatter: FinalMessageFormatter::default(),
+            had_error: false,
+            delta_mode,
+            pending_delta: None,
+            include: include.into_iter().collect(),
+            sources: SourceTracker::default(),
+            timer: TurnTimer::default(),
+        }
+    }
+
+    pub(crate) fn with_final_message_formatter(mut self, formatter: FinalMessageFormatter) -> Self {
+        self.final_message_formatter = formatter;
+        self
+    }
+
+    fn includes(&self, kind: JsonEventKind) -> bool {
+        self.include.is_empty() || self.include.contains(&kind)
+    }
+
+    fn process_delta(&mut self, event: Event) {
+        let JsonDeltaMode::Coalesce { flush_after } = self.delta_mode else {
+            write_event_line(&event);
+            return;
+        };
+
+        let delta = stream_delta_text(&event.msg).unwrap_or_default();
+        let merged = match self.pending_delta.as_mut() {
+            Some(pending) => {
+                let ended_with_newline = stream_delta_text(&pending.event.msg)
+                    .is_some_and(|text| text.ends_with('\n'));
+                merge_stream_delta(&mut pending.event, &event)
+                    .then_some(ended_with_newline && delta.starts_with('\n'))
+            }
+            None => None,
+        };
+        let paragraph_break = match merged {
+            Some(split_break) => split_break || delta.contains("\n\n"),
+            None => {
+                self.flush_pending_delta();
+                let paragraph_break = delta.contains("\n\n");
+                self.pending_delta = Some(PendingDelta {
+                    event,
+                    started: Instant::now(),
+                });
+                paragraph_break
+            }
+        };
+
+        let expired = self
+            .pending_delta
+            .as_ref()
+            .is_some_and(|pending| pending.started.elapsed() >= flush_after);
+        if paragraph_break || expired {
+            self.flush_pending_delta();
+        }
+    }
+
+    fn flush_pending_delta(&mut self) {
+        if let Some(pending) = self.pending_delta.take() {
+            write_event_line(&pending.event);
+        }
+    }
+}
+
+fn event_kind(msg: &EventMsg) -> JsonEventKind {
+    match msg {
+        EventMsg::SessionConfigured(_)
+        | EventMsg::EnvironmentContextFull(_)
+        | EventMsg::EnvironmentContextDelta(_)
+        | EventMsg::ConversationPath(_) => JsonEventKind::Session,
+        EventMsg::TaskStarted
+        | EventMsg::TaskLifecycle(_)
+        | EventMsg::TaskComplete(_)
+        | EventMsg::TurnAborted(_)
+        | EventMsg::TokenCount(_)
+        | EventMsg::AutoContextCheck(_)
+        | EventMsg::Heartbeat(_)
+        | EventMsg::CompactionCheckpointWarning(_)
+        | EventMsg::AgentStatusUpdate(_) => JsonEventKind::Task,
+        EventMsg::AgentMessage(_) | EventMsg::AgentMessageDelta(_) => JsonEventKind::AgentMessage,
+        EventMsg::AgentReasoning(_)
+        | EventMsg::AgentReasoningDelta(_)
+        | EventMsg::AgentReasoningRawContent(_)
+        | EventMsg::AgentReasoningRawContentDelta(_)
+        | EventMsg::AgentReasoningSectionBreak(_) => JsonEventKind::Reasoning,
+        EventMsg::ExecCommandBegin(_)
+        | EventMsg::ExecCommandOutputDelta(_)
+        | EventMsg::ExecCommandEnd(_)
+        | EventMsg::ExecApprovalRequest(_)
+        | EventMsg::SandboxDenied(_) => JsonEventKind::ExecCommand,
+        EventMsg::PatchApplyBegin(_)
+        | EventMsg::PatchApplyEnd(_)
+        | EventMsg::ApplyPatchApprovalRequest(_)
+        | EventMsg::TurnDiff(_) => JsonEventKind::Patch,
+        EventMsg::McpToolCallBegin(_)
+        | EventMsg::McpToolCallEnd(_)
+        | EventMsg::CustomToolCallBegin(_)
+        | EventMsg::CustomToolCallUpdate(_)
+        | EventMsg::CustomToolCallEnd(_)
+        | EventMsg::DynamicToolCallRequest(_)
+        | EventMsg::ReplExecBegin(_)
+        | EventMsg::WebSearchBegin(_)
+        | EventMsg::WebSearchComplete(_)
+        | EventMsg::ImageGenerationBegin(_)
+        | EventMsg::ImageGenerationEnd(_)
+        | EventMsg::ViewImageToolCall(_) => JsonEventKind::ToolCall,
+        EventMsg::EnteredReviewMode(_) | EventMsg::ExitedReviewMode(_) => JsonEventKind::Review,
+        EventMsg::PlanUpdate(_) => JsonEventKind::Plan,
+        EventMsg::Error(_) | EventMsg::Warning(_) | EventMsg::PartialTurn(_) => {
+            JsonEventKind::Error
+        }
+        _ => JsonEventKind::Other,
+    }
+}
+
+/// Adds the sources an agent message links to as `msg.sources`, numbered by
+/// position.
+fn write_agent_message_line(event: &Event, sources: &[Source]) {
+    if sources.is_empty() {
+        write_event_line(event);
+    } else {
+        write_event_line_with(event, "sources", json!(sources));
+    }
+}
+
+/// Writes `event` with `value` added to its `msg` object under `key`.
+fn write_event_line_with(event: &Event, key: &str, value: serde_json::Value) {
+    let Ok(mut line) = serde_json::to_value(event) else {
+        return;
+    };
+    if let Some(msg) = line.get_mut("msg").and_then(serde_json::Value::as_object_mut) {
+        msg.insert(key.to_owned(), value);
+    }
+    write_stdout_line(format_args!("{line}"));
+}
+
+fn write_event_line(event: &Event) {
+    if let Ok(line) = serde_json::to_string(event) {
+        write_stdout_line(format_args!("{line}"));
     }
 }
 
@@ -33,6 +215,9 @@ fn write_stdout_line(args: std::fmt::Arguments<'_>) {
 
 impl EventProcessor for EventProcessorWithJsonOutput {
     fn print_config_summary(&mut self, config: &Config, prompt: &str) {
+        if !self.includes(JsonEventKind::Session) {
+            return;
+        }
         let entries = create_config_summary_entries(config)
             .into_iter()
             .map(|(key, value)| (key.to_owned(), value))
@@ -50,27 +235,49 @@ impl EventProcessor for EventProcessorWithJsonOutput {
     }
 
     fn process_event(&mut self, event: Event) -> CodexStatus {
+        let included = self.includes(event_kind(&event.msg));
+        if stream_delta_text(&event.msg).is_some() {
+            if included {
+                self.process_delta(event);
+            }
+            return CodexStatus::Running;
+        }
+        // Pending text always lands before whatever follows it.
+        self.flush_pending_delta();
+        self.sources.observe_event(&event.msg);
+        let timing = self.timer.observe(&event.msg, Instant::now());
+        if included && let Some(timing) = timing {
+            write_event_line_with(&event, "timing", json!(timing));
+        }
+
         match event.msg {
             EventMsg::Error(_) => { self.had_error = true; CodexStatus::Running }
-            EventMsg::AgentMessageDelta(_) | EventMsg::AgentReasoningDelta(_) => {
-                // Suppress streaming events in JSON mode.
-                CodexStatus::Running
-            }
             EventMsg::TaskComplete(TaskCompleteEvent { last_agent_message }) => {
                 if let Some(output_file) = self.last_message_path.as_deref() {
-                    handle_last_message(last_agent_message.as_deref(), output_file);
+                    let formatted = last_agent_message
+                        .as_deref()
+                        .map(|message| self.final_message_formatter.format(message));
+                    handle_last_message(formatted.as_deref(), output_file);
                 }
                 CodexStatus::InitiateShutdown
             }
             EventMsg::ShutdownComplete => CodexStatus::Shutdown,
+            EventMsg::AgentMessage(ref message) if included => {
+                write_agent_message_line(&event, &self.sources.cite(&message.message));
+                CodexStatus::Running
+            }
             _ => {
-                if let Ok(line) = serde_json::to_string(&event) {
-                    write_stdout_line(format_args!("{line}"));
+                if included && timing.is_none() {
+                    write_event_line(&event);
                 }
                 CodexStatus::Running
             }
         }
     }
 
+    fn turn_submitted(&mut self) {
+        self.timer.mark_submitted(Instant::now());
+    }
+
     // exit_code handled by CLI; suppress unused warnings by omitting method.
 }
@@ -0,0 +1,147 @@
+//! Exit codes for `code exec`.
+//!
+//! CI pipelines branch on these, so the numbers are stable and documented in
+//! `docs/exec.md`. Termination signals keep their own `128 + signal` codes
+//! (see [`crate::termination`]).
+
+use code_core::protocol::CodexErrorInfo;
+use code_core::protocol::EventMsg;
+
+/// How a run ended, as reported through the process exit code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RunOutcome {
+    Success = 0,
+    /// An error without a more specific class below.
+    Failed = 1,
+    /// The review finished with findings still open.
+    ReviewFindings = 2,
+    /// `--max-seconds` ran out.
+    DeadlineExceeded = 3,
+    /// The sandbox blocked a command.
+    SandboxDenied = 4,
+    /// The model provider rejected our credentials.
+    AuthFailed = 5,
+    /// `--max-tokens` or `--max-cost-usd` ran out.
+    BudgetExceeded = 6,
+    /// The final message still did not match `--output-schema` after the
+    /// allowed correction turns.
+    OutputSchemaInvalid = 7,
+}
+
+impl RunOutcome {
+    pub(crate) fn code(self) -> i32 {
+        self as i32
+    }
+}
+
+/// Failure classes seen while processing a run's events.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct RunFailures {
+    pub(crate) error: bool,
+    pub(crate) deadline_exceeded: bool,
+    pub(crate) budget_exceeded: bool,
+    pub(crate) sandbox_denied: bool,
+    pub(crate) auth_failed: bool,
+    pub(crate) schema_invalid: bool,
+}
+
+impl RunFailures {
+    pub(crate) fn observe(&mut self, msg: &EventMsg) {
+        match msg {
+            EventMsg::Error(err) => {
+                self.error = true;
+                match err.codex_error_info {
+                    Some(CodexErrorInfo::Unauthorized) => self.auth_failed = true,
+                    Some(CodexErrorInfo::SandboxError) => self.sandbox_denied = true,
+                    _ => {}
+                }
+            }
+            EventMsg::SandboxDenied(_) => self.sandbox_denied = true,
+            _ => {}
+        }
+    }
+
+    pub(crate) fn merge(&mut self, other: Self) {
+        self.error |= other.error;
+        self.deadline_exceeded |= other.deadline_exceeded;
+        self.budget_exceeded |= other.budget_exceeded;
+        self.sandbox_denied |= other.sandbox_denied;
+        self.auth_failed |= other.auth_failed;
+        self.schema_invalid |= other.schema_invalid;
+    }
+
+    /// Picks the most specific class when a run hit several: auth failures
+    /// first since nothing after them could succeed, then the deadline and
+    /// token or cost budgets, then sandbox denials and output that failed
+    /// `--output-schema`. Open review findings only count for an otherwise
+    /// clean run.
+    pub(crate) fn outcome(&self, review_findings: bool) -> RunOutcome {
+        if self.auth_failed {
+            RunOutcome::AuthFailed
+        } else if self.deadline_exceeded {
+            RunOutcome::DeadlineExceeded
+        } else if self.budget_exceeded {
+            RunOutcome::BudgetExceeded
+        } else if self.sandbox_denied {
+            RunOutcome::SandboxDenied
+        } else if self.schema_invalid {
+            RunOutcome::OutputSchemaInvalid
+        } else if self.error {
+            RunOutcome::Failed
+        } else if review_findings {
+            RunOutcome::ReviewFindings
+        } else {
+            RunOutcome::Success
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use code_core::protocol::ErrorEvent;
+    use code_core::protocol::SandboxDeniedEvent;
+
+    fn error(codex_error_info: Option<CodexErrorInfo>) -> EventMsg {
+        EventMsg::Error(ErrorEvent {
+            message: "boom".to_owned(),
+            codex_error_info,
+            code: None,
+            category: None,
+        })
+    }
+
+    #[test]
+    fn most_specific_failure_wins() {
+        assert_eq!(RunFailures::default().outcome(false), RunOutcome::Success);
+        assert_eq!(RunFailures::default().outcome(true), RunOutcome::ReviewFindings);
+
+        let mut failures = RunFailures::default();
+        failures.observe(&error(None));
+        assert_eq!(failures.outcome(true), RunOutcome::Failed);
+
+        failures.schema_invalid = true;
+        assert_eq!(failures.outcome(false).code(), 7);
+
+        failures.observe(&EventMsg::SandboxDenied(SandboxDeniedEvent {
+            call_id: "call-1".to_owned(),
+            command: vec!["touch".to_owned(), "/etc/x".to_owned()],
+        }));
+        assert_eq!(failures.outcome(false), RunOutcome::SandboxDenied);
+
+        failures.merge(RunFailures {
+            budget_exceeded: true,
+            ..RunFailures::default()
+        });
+        assert_eq!(failures.outcome(false).code(), 6);
+
+        failures.merge(RunFailures {
+            deadline_exceeded: true,
+            ..RunFailures::default()
+        });
+        assert_eq!(failures.outcome(false), RunOutcome::DeadlineExceeded);
+
+        failures.observe(&error(Some(CodexErrorInfo::Unauthorized)));
+        assert_eq!(failures.outcome(false).code(), 5);
+    }
+}
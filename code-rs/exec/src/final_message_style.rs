@@ -0,0 +1,229 @@
+//! `--final-message-style concise`: strips the boilerplate models wrap
+//! answers in ("Sure! Here's the summary:", "Let me know if ...") and tidies
+//! whitespace, so automation reading the final message gets just the body.
+
+use code_core::config_types::FinalMessageStyleConfig;
+
+use crate::cli::FinalMessageStyle;
+
+/// Interjections dropped when they make up the whole opening clause.
+const FILLER_OPENERS: &[&str] = &[
+    "sure",
+    "sure thing",
+    "certainly",
+    "of course",
+    "absolutely",
+    "great question",
+    "good question",
+    "okay",
+    "ok",
+    "alright",
+    "all right",
+    "got it",
+    "no problem",
+    "happy to help",
+    "i'd be happy to help",
+    "thanks for asking",
+];
+
+/// Lead-ins that announce the answer instead of giving it; dropped when they
+/// make up a short first line ending in `:`.
+const ANNOUNCEMENTS: &[&str] = &["here's", "here is", "here are", "below is", "below are"];
+
+/// Openings of a trailing offer to help further.
+const CLOSING_OFFERS: &[&str] = &[
+    "let me know",
+    "feel free to",
+    "hope this helps",
+    "i hope this helps",
+    "if you have any",
+    "if you need",
+    "if you'd like",
+    "would you like me to",
+    "happy to help further",
+];
+
+/// Longest first line still treated as an announcement.
+const MAX_ANNOUNCEMENT_CHARS: usize = 80;
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct FinalMessageFormatter {
+    style: FinalMessageStyle,
+    guide: FinalMessageStyleConfig,
+}
+
+impl FinalMessageFormatter {
+    pub(crate) fn new(style: FinalMessageStyle, guide: FinalMessageStyleConfig) -> Self {
+        Self { style, guide }
+    }
+
+    pub(crate) fn format(&self, message: &str) -> String {
+        match self.style {
+            FinalMessageStyle::Raw => message.to_owned(),
+            FinalMessageStyle::Concise => concise(message, &self.guide),
+        }
+    }
+}
+
+fn concise(message: &str, guide: &FinalMessageStyleConfig) -> String {
+    let mut body = message.trim().to_owned();
+    let mut stripped = false;
+    while let Some(rest) = strip_preamble(&body, guide) {
+        body = rest;
+        stripped = true;
+    }
+    if stripped {
+        body = capitalize_first(&body);
+    }
+    if guide.strip_closing_offers {
+        body = strip_closing_offer(&body);
+    }
+    let body = normalize_whitespace(&body, guide.max_blank_lines);
+    // Never reduce a message to nothing; the original is better than that.
+    if body.is_empty() {
+        message.trim().to_owned()
+    } else {
+        body
+    }
+}
+
+/// The message without its opening filler, or `None` when it has none.
+fn strip_preamble(body: &str, guide: &FinalMessageStyleConfig) -> Option<String> {
+    for phrase in &guide.strip_preambles {
+        let phrase = phrase.trim();
+        if let Some(head) = body.get(..phrase.len())
+            && !phrase.is_empty()
+            && head.eq_ignore_ascii_case(phrase)
+        {
+            return Some(trim_separator(&body[phrase.len()..]));
+        }
+    }
+
+    let clause_end = body.find([',', '!', '.', '\n']).unwrap_or(body.len());
+    let clause = body[..clause_end].trim().to_lowercase();
+    if FILLER_OPENERS.contains(&clause.as_str()) {
+        return Some(trim_separator(&body[clause_end..]));
+    }
+
+    let first_line = body.lines().next().unwrap_or_default().trim_end().to_lowercase();
+    if first_line.ends_with(':')
+        && first_line.chars().count() <= MAX_ANNOUNCEMENT_CHARS
+        && ANNOUNCEMENTS.iter().any(|lead| first_line.starts_with(lead))
+        && let Some((_, rest)) = body.split_once('\n')
+    {
+        return Some(rest.trim_start().to_owned());
+    }
+    None
+}
+
+fn trim_separator(rest: &str) -> String {
+    rest.trim_start_matches([',', '!', '.', ':', ' ', '\n'])
+        .to_owned()
+}
+
+fn capitalize_first(body: &str) -> String {
+    let mut chars = body.chars();
+    match chars.next() {
+        Some(first) if first.is_ascii_lowercase() => {
+            format!("{}{}", first.to_ascii_uppercase(), chars.as_str())
+        }
+        _ => body.to_owned(),
+    }
+}
+
+/// Drops a trailing offer to help, whether it is its own paragraph or the
+/// last sentence of one.
+fn strip_closing_offer(body: &str) -> String {
+    let is_offer = |text: &str| {
+        let text = text.trim().to_lowercase();
+        CLOSING_OFFERS.iter().any(|offer| text.starts_with(offer))
+    };
+    let trimmed = body.trim_end();
+    let last_line_start = trimmed.rfind('\n').map_or(0, |idx| idx + 1);
+    let last_line = &trimmed[last_line_start..];
+    if last_line_start > 0 && is_offer(last_line) {
+        return trimmed[..last_line_start].trim_end().to_owned();
+    }
+    if last_line.trim_start().starts_with("```") {
+        return trimmed.to_owned();
+    }
+    let sentence_start = last_line
+        .trim_end_matches(['.', '!', '?'])
+        .rfind(['.', '!', '?'])
+        .map(|idx| last_line_start + idx + 1);
+    match sentence_start {
+        Some(start) if is_offer(&trimmed[start..]) => trimmed[..start].trim_end().to_owned(),
+        _ => trimmed.to_owned(),
+    }
+}
+
+/// Trims trailing spaces and collapses runs of blank lines outside code
+/// fences.
+fn normalize_whitespace(body: &str, max_blank_lines: usize) -> String {
+    let mut lines: Vec<&str> = Vec::new();
+    let mut in_fence = false;
+    let mut blank_run = 0usize;
+    for line in body.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        }
+        if in_fence {
+            lines.push(line);
+            continue;
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            blank_run += 1;
+            if blank_run > max_blank_lines {
+                continue;
+            }
+        } else {
+            blank_run = 0;
+        }
+        lines.push(line);
+    }
+    lines.join("\n").trim().to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn concise_default(message: &str) -> String {
+        FinalMessageFormatter::new(FinalMessageStyle::Concise, FinalMessageStyleConfig::default())
+            .format(message)
+    }
+
+    #[test]
+    fn strips_preambles_and_closing_offers() {
+        assert_eq!(
+            concise_default(
+                "Sure! Here's the summary:\n\nthe bug was in `parse_args`.\n\n\n\nFixed it.  \n\nLet me know if you need anything else!"
+            ),
+            "The bug was in `parse_args`.\n\nFixed it."
+        );
+        assert_eq!(
+            concise_default("Great question. The cache is never invalidated. Hope this helps!"),
+            "The cache is never invalidated."
+        );
+        assert_eq!(concise_default("Sure."), "Sure.");
+    }
+
+    #[test]
+    fn leaves_answers_and_code_fences_alone() {
+        let message = "Surely the lock is held twice:\n\n```\nlet a = 1;\n\n\n\nlet b = 2;\n```";
+        assert_eq!(concise_default(message), message);
+
+        let guide = FinalMessageStyleConfig {
+            strip_preambles: vec!["As requested".to_owned()],
+            ..FinalMessageStyleConfig::default()
+        };
+        let formatter = FinalMessageFormatter::new(FinalMessageStyle::Concise, guide);
+        assert_eq!(formatter.format("As requested, the answer is 42."), "The answer is 42.");
+        assert_eq!(
+            FinalMessageFormatter::default().format("Sure! 42\n\n\n"),
+            "Sure! 42\n\n\n"
+        );
+    }
+}
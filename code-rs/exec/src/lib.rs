@@ -2,21 +2,32 @@
 // In --json mode, stdout must be valid JSONL. All other output goes to stderr.
 #![deny(clippy::print_stdout)]
 
+mod approvals_command;
 mod cli;
+mod control_socket;
 mod auto_runtime;
 mod auto_drive_session;
 mod auto_review_status;
 mod event_processor;
 mod event_processor_with_human_output;
 mod event_processor_with_json_output;
+mod exit_code;
+mod final_message_style;
+mod output_schema;
 mod prompt_input;
+mod review_async;
 mod review_command;
 mod review_output;
 mod review_scope;
+mod run_budget;
 mod run_setup;
 mod session_runtime;
+mod session_export;
+mod session_inspect;
 mod session_resume;
 mod slash;
+mod termination;
+mod turn_timing;
 
 pub use cli::Cli;
 pub use cli::Command;
@@ -32,11 +43,14 @@ use code_core::config_types::AutoDriveContinueMode;
 use code_core::model_family::{derive_default_model_family, find_family_for_model};
 use code_core::git_info::get_git_repo_root;
 use code_core::protocol::AskForApproval;
+use code_core::protocol::CodexErrorInfo;
 use code_protocol::protocol::SessionSource;
 use code_ollama::DEFAULT_OSS_MODEL;
 use code_protocol::config_types::SandboxMode;
 use event_processor_with_human_output::EventProcessorWithHumanOutput;
 use event_processor_with_json_output::EventProcessorWithJsonOutput;
+use event_processor_with_json_output::JsonDeltaMode;
+use std::io::IsTerminal;
 use std::path::PathBuf;
 use supports_color::Stream;
 use tokio::time::{Duration, Instant};
@@ -49,11 +63,22 @@ use crate::auto_runtime::merge_developer_message;
 use crate::cli::Command as ExecCommand;
 use crate::event_processor::EventProcessor;
 use crate::prompt_input::load_output_schema;
+use crate::exit_code::RunOutcome;
+use crate::final_message_style::FinalMessageFormatter;
+use crate::output_schema::OutputSchema;
+use crate::output_schema::structured_output_record;
+use crate::output_schema::write_structured_output;
+use crate::review_output::ReviewPathBase;
+use crate::review_output::review_findings_remain;
 use crate::review_output::write_review_json;
+use crate::review_output::write_review_sarif;
+use crate::run_budget::RunBudget;
+use crate::review_scope::pin_review_base;
 use crate::run_setup::PreparedRunInputs;
 use crate::run_setup::prepare_run_inputs;
 use crate::session_runtime::SessionRuntimeParams;
 use crate::session_runtime::run_session_runtime;
+use crate::session_resume::resolve_resume_entry;
 use crate::session_resume::resolve_resume_path;
 use crate::slash::{process_exec_slash_command, SlashContext, SlashDispatch};
 use code_auto_drive_core::AutoResolveState;
@@ -62,6 +87,14 @@ use code_core::timeboxed_exec_guidance::{
     AUTO_EXEC_TIMEBOXED_CLI_GUIDANCE,
 };
 
+/// Environment that makes common tools color their output even though it is
+/// captured through pipes, used by `--preserve-tool-colors`.
+const FORCE_COLOR_ENV: &[(&str, &str)] = &[
+    ("CLICOLOR_FORCE", "1"),
+    ("FORCE_COLOR", "1"),
+    ("CARGO_TERM_COLOR", "always"),
+];
+
 pub async fn run_main(cli: Cli, code_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()> {
     if let Err(err) = set_default_originator("code_exec") {
         tracing::warn!(?err, "Failed to set codex exec originator override {err:?}");
@@ -78,21 +111,76 @@ pub async fn run_main(cli: Cli, code_linux_sandbox_exe: Option<PathBuf>) -> anyh
         cwd,
         skip_git_repo_check,
         color,
+        preserve_tool_colors,
         last_message_file,
         json: json_mode,
+        json_raw_deltas,
+        json_delta_flush_ms,
+        json_include,
         sandbox_mode: sandbox_mode_cli_arg,
+        sandbox_preset,
         prompt,
         output_schema: output_schema_path,
+        output_schema_retries,
+        structured_output_file,
+        final_message_style,
         include_plan_tool,
         config_overrides,
         auto_drive,
         auto_review,
         max_seconds,
+        max_tokens,
+        max_cost_usd,
+        watch,
+        control_socket,
+        inject_note,
+        prompt_template,
         turn_cap,
         review_output_json,
+        review_output_sarif,
+        review_paths_relative,
+        review_async,
+        handoff,
+        profile_startup,
         ..
     } = cli;
 
+    if profile_startup {
+        code_core::startup_profile::enable();
+    }
+
+    // Approvals only edit the project's store; no config or session needed.
+    if let Some(ExecCommand::Approvals(args)) = &command {
+        let cwd = match cwd {
+            Some(cwd) => cwd,
+            None => std::env::current_dir()?,
+        };
+        return approvals_command::run_approvals(&cwd, args);
+    }
+
+    // Export and inspect only read a stored session; they need config for
+    // CODE_HOME and the session catalog, but no prompt, model, or conversation.
+    if let Some(ExecCommand::Export(_) | ExecCommand::Inspect(_)) = &command {
+        let cli_kv_overrides = config_overrides.parse_overrides().map_err(anyhow::Error::msg)?;
+        let overrides = ConfigOverrides {
+            config_profile,
+            cwd: cwd.map(|p| p.canonicalize().unwrap_or(p)),
+            ..Default::default()
+        };
+        let config = Config::load_with_cli_overrides(cli_kv_overrides, overrides)?;
+        if let Some(ExecCommand::Inspect(args)) = &command {
+            return session_inspect::run_inspect(&config, args).await;
+        }
+        if let Some(ExecCommand::Export(args)) = &command {
+            return session_export::run_export(&config, args).await;
+        }
+    }
+
+    // `--inject-note` talks to a session that is already running.
+    if let (Some(note), Some(path)) = (inject_note.as_deref(), control_socket.as_deref()) {
+        return control_socket::send_note(path, note).await;
+    }
+
     let run_deadline = max_seconds.map(|seconds| Instant::now() + Duration::from_secs(seconds));
     let run_deadline_std = run_deadline.map(tokio::time::Instant::into_std);
 
@@ -103,9 +191,21 @@ pub async fn run_main(cli: Cli, code_linux_sandbox_exe: Option<PathBuf>) -> anyh
         auto_drive_goal,
         images,
         timeboxed_auto_exec,
-    } = prepare_run_inputs(command.as_ref(), prompt, images, auto_drive, max_seconds);
+    } = prepare_run_inputs(
+        command.as_ref(),
+        prompt,
+        images,
+        auto_drive,
+        max_seconds,
+        prompt_template,
+    );
 
-    let _output_schema = load_output_schema(output_schema_path);
+    let output_schema = load_output_schema(output_schema_path).map(|schema| {
+        OutputSchema::compile(schema, output_schema_retries).unwrap_or_else(|err| {
+            eprintln!("--output-schema: {err}");
+            std::process::exit(1);
+        })
+    });
 
     let (stdout_with_ansi, stderr_with_ansi) = match color {
         cli::Color::Always => (true, true),
@@ -160,6 +260,7 @@ pub async fn run_main(cli: Cli, code_linux_sandbox_exe: Option<PathBuf>) -> anyh
         // the user for approval.
         approval_policy: Some(AskForApproval::Never),
         sandbox_mode,
+        sandbox_preset,
         cwd: cwd.map(|p| p.canonicalize().unwrap_or(p)),
         model_provider,
         code_linux_sandbox_exe,
@@ -186,11 +287,27 @@ pub async fn run_main(cli: Cli, code_linux_sandbox_exe: Option<PathBuf>) -> anyh
         }
     };
 
+    let config_started = std::time::Instant::now();
     let mut config = Config::load_with_cli_overrides(cli_kv_overrides, overrides)?;
-    config.max_run_seconds = max_seconds;
-    config.max_run_deadline = run_deadline_std;
+    code_core::startup_profile::record_since("config load", config_started);
+    if watch.is_empty() {
+        config.max_run_seconds = max_seconds;
+        config.max_run_deadline = run_deadline_std;
+    }
     config.demo_developer_message = cli.demo_developer_message.clone();
     config.timeboxed_exec_mode = timeboxed_auto_exec;
+    let preserve_tool_colors = preserve_tool_colors && std::io::stderr().is_terminal();
+    if preserve_tool_colors {
+        // Commands see pipes, not a terminal, so most only color when forced.
+        // Explicit `shell_environment_policy.set` values win.
+        for (name, value) in FORCE_COLOR_ENV {
+            config
+                .shell_environment_policy
+                .r#set
+                .entry((*name).to_owned())
+                .or_insert_with(|| (*value).to_owned());
+        }
+    }
     if timeboxed_auto_exec {
         config.demo_developer_message = merge_developer_message(
             config.demo_developer_message.take(),
@@ -231,8 +348,48 @@ pub async fn run_main(cli: Cli, code_linux_sandbox_exe: Option<PathBuf>) -> anyh
         }
     }
 
+    if let Some(name) = review_request.as_ref().and_then(|request| request.profile.as_deref())
+        && !config.review_profiles.contains_key(name)
+    {
+        eprintln!("Unknown review profile `{name}`; define it under [review_profiles.{name}] in config.toml.");
+        std::process::exit(1);
+    }
+
+    let review_base = match &command {
+        Some(ExecCommand::Review(args)) => args.review_base.as_deref(),
+        _ => None,
+    };
+    if let Some(base_ref) = review_base
+        && let Some(request) = review_request.take()
+    {
+        review_request = Some(pin_review_base(request, &config.cwd, base_ref, &config.large_files)?);
+    }
+
     let is_auto_review = auto_review;
 
+    if !watch.is_empty() && (review_request.is_some() || auto_drive_goal.is_some()) {
+        eprintln!("--watch re-runs a prompt; it cannot be combined with /review or --auto.");
+        std::process::exit(1);
+    }
+    if review_async && (review_request.is_some() || auto_drive_goal.is_some() || !watch.is_empty()) {
+        eprintln!("--review-async runs alongside a single prompt; it cannot be combined with /review, --auto or --watch.");
+        std::process::exit(1);
+    }
+    if handoff && (review_request.is_some() || auto_drive_goal.is_some()) {
+        eprintln!("--handoff summarizes a prompt run; it cannot be combined with /review or --auto.");
+        std::process::exit(1);
+    }
+    let mut watch_paths = Vec::with_capacity(watch.len());
+    for path in watch {
+        match path.canonicalize() {
+            Ok(resolved) => watch_paths.push(resolved),
+            Err(err) => {
+                eprintln!("--watch {}: {err}", path.display());
+                std::process::exit(1);
+            }
+        }
+    }
+
     if is_auto_review {
         if config.auto_review_use_chat_model {
             config.review_model = config.model.clone();
@@ -252,6 +409,15 @@ pub async fn run_main(cli: Cli, code_linux_sandbox_exe: Option<PathBuf>) -> anyh
                 config.auto_review_resolve_model_reasoning_effort;
         }
         config.review_resolve_use_chat_model = config.auto_review_resolve_use_chat_model;
+
+        // `[auto_review]` thresholds replace the Auto Drive selection so
+        // findings below them are reported but never handed to the fix loop.
+        if config.auto_review.has_threshold() {
+            config.auto_drive.auto_resolve_findings =
+                code_core::config_types::AutoResolveFindingSelection::Threshold;
+            config.auto_drive.auto_resolve_max_priority = config.auto_review.min_priority;
+            config.auto_drive.auto_resolve_min_confidence = config.auto_review.min_confidence;
+        }
     }
 
     let review_auto_resolve_requested = review_request.is_some()
@@ -321,15 +487,31 @@ pub async fn run_main(cli: Cli, code_linux_sandbox_exe: Option<PathBuf>) -> anyh
         config.model_auto_compact_token_limit = resolve_family.auto_compact_token_limit();
     }
     let stop_on_task_complete = auto_drive_goal.is_none() && auto_resolve_state.is_none();
+    let final_message_formatter =
+        FinalMessageFormatter::new(final_message_style, config.final_message_style.clone());
     let mut event_processor: Box<dyn EventProcessor> = if json_mode {
-        Box::new(EventProcessorWithJsonOutput::new(last_message_file.clone()))
+        let delta_mode = if json_raw_deltas {
+            JsonDeltaMode::Raw
+        } else {
+            JsonDeltaMode::Coalesce {
+                flush_after: Duration::from_millis(json_delta_flush_ms),
+            }
+        };
+        Box::new(EventProcessorWithJsonOutput::new(
+            last_message_file.clone(),
+            delta_mode,
+            json_include,
+        )
+        .with_final_message_formatter(final_message_formatter.clone()))
     } else {
         Box::new(EventProcessorWithHumanOutput::create_with_ansi(
             stdout_with_ansi,
             &config,
             last_message_file.clone(),
             stop_on_task_complete,
-        ))
+        )
+        .with_final_message_formatter(final_message_formatter.clone())
+        .with_preserved_tool_colors(preserve_tool_colors))
     };
 
     if oss {
@@ -360,26 +542,47 @@ pub async fn run_main(cli: Cli, code_linux_sandbox_exe: Option<PathBuf>) -> anyh
     let conversation_manager = ConversationManager::new(auth_manager.clone(), SessionSource::Exec);
 
     // Handle resume subcommand by resolving a rollout path and using explicit resume API.
-    let NewConversation {
-        conversation_id: _,
-        conversation,
-        session_configured,
-    } = if let Some(ExecCommand::Resume(args)) = command {
+    let started = if let Some(ExecCommand::Resume(args)) = &command
+        && args.fork
+    {
+        let Some(entry) = resolve_resume_entry(&config, args).await? else {
+            anyhow::bail!("no session to fork; pass a session id or --last");
+        };
+        conversation_manager
+            .fork_conversation_at(
+                code_protocol::ConversationId::from(entry.session_id),
+                args.fork_at.clone(),
+                config.clone(),
+            )
+            .await
+    } else if let Some(ExecCommand::Resume(args)) = command {
         let resume_path = resolve_resume_path(&config, &args).await?;
 
         if let Some(path) = resume_path {
             conversation_manager
                 .resume_conversation_from_rollout(config.clone(), path, auth_manager.clone())
-                .await?
+                .await
         } else {
             conversation_manager
                 .new_conversation(config.clone())
-                .await?
+                .await
         }
     } else {
         conversation_manager
             .new_conversation(config.clone())
-            .await?
+            .await
+    };
+    let NewConversation {
+        conversation_id,
+        conversation,
+        session_configured,
+    } = match started {
+        Ok(started) => started,
+        Err(err) if err.to_codex_error_info() == CodexErrorInfo::Unauthorized => {
+            eprintln!("Error: {err}");
+            std::process::exit(RunOutcome::AuthFailed.code());
+        }
+        Err(err) => return Err(err.into()),
     };
     if auto_drive_goal.is_some() {
         let summary_config = build_auto_drive_exec_config(&config);
@@ -388,18 +591,28 @@ pub async fn run_main(cli: Cli, code_linux_sandbox_exe: Option<PathBuf>) -> anyh
         event_processor.print_config_summary(&config, &summary_prompt);
     }
     info!("Codex initialized with event: {session_configured:?}");
+    code_git_tooling::set_ghost_session(Some(&conversation_id.to_string()));
+    let _control_socket = match control_socket.as_deref() {
+        Some(path) => Some(control_socket::ControlSocket::bind(path, conversation.clone())?),
+        None => None,
+    };
 
     if let Some(goal) = auto_drive_goal {
-        return run_auto_drive_session(
+        let cwd = config.cwd.clone();
+        let ghost_commits = config.ghost_commits.clone();
+        let result = run_auto_drive_session(
             goal,
             images,
             config,
             conversation,
             event_processor,
             last_message_file,
+            final_message_formatter,
             run_deadline,
         )
         .await;
+        code_core::prune_ghost_commits_on_exit(&cwd, &ghost_commits);
+        return result;
     }
 
     let runtime_outcome = run_session_runtime(SessionRuntimeParams {
@@ -409,21 +622,59 @@ pub async fn run_main(cli: Cli, code_linux_sandbox_exe: Option<PathBuf>) -> anyh
         review_request,
         prompt_to_send,
         images,
+        output_schema: output_schema.as_ref(),
         run_deadline,
         max_seconds,
+        budget: RunBudget {
+            max_tokens,
+            max_cost_usd,
+        },
         auto_resolve_state,
         max_auto_resolve_attempts,
         is_auto_review,
+        watch_paths,
+        review_async,
+        handoff,
     })
     .await?;
-    if let Some(path) = review_output_json
-        && !runtime_outcome.review_outputs.is_empty()
-    {
-        let _ = write_review_json(
-            path,
-            &runtime_outcome.review_outputs,
-            runtime_outcome.final_review_snapshot.as_ref(),
-        );
+    if let Some(code) = termination::exit_code() {
+        // Core has already flushed the rollout and stopped child processes.
+        std::process::exit(code);
+    }
+    code_core::prune_ghost_commits_on_exit(&config.cwd, &config.ghost_commits);
+    let wants_review_output = review_output_json.is_some() || review_output_sarif.is_some();
+    if wants_review_output && !runtime_outcome.review_outputs.is_empty() {
+        let snapshot = runtime_outcome.final_review_snapshot.as_ref();
+        // SARIF consumers resolve locations against the repository, so SARIF
+        // output is always relative when there is a repository to anchor to.
+        let path_base = if review_paths_relative || review_output_sarif.is_some() {
+            ReviewPathBase::detect(&config.cwd, snapshot).await
+        } else {
+            None
+        };
+        if review_paths_relative && path_base.is_none() {
+            eprintln!("--review-paths-relative: not in a git repository; keeping absolute paths");
+        }
+        if let Some(path) = review_output_json {
+            let json_base = path_base.as_ref().filter(|_| review_paths_relative);
+            let _ = write_review_json(
+                path,
+                &runtime_outcome.review_outputs,
+                snapshot,
+                json_base,
+                &runtime_outcome.acknowledged_findings,
+            );
+        }
+        if let Some(path) = review_output_sarif
+            && let Err(err) = write_review_sarif(
+                path.clone(),
+                &runtime_outcome.review_outputs,
+                path_base.as_ref(),
+                &runtime_outcome.acknowledged_findings,
+            )
+        {
+            eprintln!("Failed to write SARIF review output to {}: {err}", path.display());
+        }
     }
     if runtime_outcome.review_runs > 0 {
         eprintln!(
@@ -433,13 +684,71 @@ pub async fn run_main(cli: Cli, code_linux_sandbox_exe: Option<PathBuf>) -> anyh
             max_auto_resolve_attempts
         );
     }
+    if !runtime_outcome.acknowledged_findings.is_empty() {
+        eprintln!(
+            "Auto-resolve: {} finding(s) acknowledged but not fixed:",
+            runtime_outcome.acknowledged_findings.len()
+        );
+        for finding in &runtime_outcome.acknowledged_findings {
+            let location = &finding.code_location;
+            eprintln!(
+                "- {} ({}:{})",
+                finding.title.trim(),
+                location.absolute_file_path.display(),
+                location.line_range.start
+            );
+        }
+    }
     event_processor.print_final_output();
-    if runtime_outcome.error_seen {
-        std::process::exit(1);
+    if handoff {
+        report_handoff(&config, &conversation_id, runtime_outcome.handoff_summary.as_deref());
+    }
+    if let Some(document) = runtime_outcome.structured_output.as_ref() {
+        if json_mode {
+            println!("{}", structured_output_record(document));
+        }
+        if let Some(path) = structured_output_file.as_deref() {
+            write_structured_output(path, document);
+        }
+    }
+    if runtime_outcome.budget.exceeded().is_some() {
+        // Last line of the JSONL stream, or stderr next to the human output.
+        let summary = runtime_outcome.budget.summary_json();
+        if json_mode {
+            println!("{summary}");
+        } else {
+            eprintln!("{summary}");
+        }
+    }
+    let outcome = runtime_outcome.failures.outcome(review_findings_remain(
+        &runtime_outcome.review_outputs,
+        &runtime_outcome.acknowledged_findings,
+    ));
+    if outcome != RunOutcome::Success {
+        std::process::exit(outcome.code());
     }
 
     Ok(())
 }
 
+/// Pins the `--handoff` summary to the session for its next TUI resume.
+fn report_handoff(
+    config: &Config,
+    conversation_id: &code_protocol::ConversationId,
+    summary: Option<&str>,
+) {
+    let Some(summary) = summary.filter(|summary| !summary.trim().is_empty()) else {
+        eprintln!("No hand-off summary was written: the hand-off turn did not finish.");
+        return;
+    };
+    match code_core::session_handoff::save_handoff(&config.code_home, conversation_id, summary) {
+        Ok(path) => eprintln!(
+            "Hand-off summary saved to {}; run `code resume --latest` to continue with it pinned.",
+            path.display()
+        ),
+        Err(err) => eprintln!("Failed to save the hand-off summary: {err}"),
+    }
+}
+
 #[cfg(test)]
 mod tests;
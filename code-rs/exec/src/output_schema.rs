@@ -0,0 +1,160 @@
+//! `--output-schema` enforcement. The schema is sent with every turn as the
+//! model's response format, and the final assistant message is validated
+//! against it here. A message that does not match gets a correction turn, up
+//! to `--output-schema-retries` times, before the run fails. The parsed
+//! document is reported as a `structured_output` record in `--json` mode and
+//! written to `--structured-output-file`.
+
+use std::fmt::Write;
+use std::path::Path;
+
+use jsonschema::JSONSchema;
+use serde_json::Value;
+use serde_json::json;
+
+pub(crate) struct OutputSchema {
+    schema: Value,
+    validator: JSONSchema,
+    max_retries: u32,
+}
+
+impl OutputSchema {
+    pub(crate) fn compile(schema: Value, max_retries: u32) -> anyhow::Result<Self> {
+        let validator = JSONSchema::compile(&schema)
+            .map_err(|err| anyhow::anyhow!("invalid JSON Schema: {err}"))?;
+        Ok(Self {
+            schema,
+            validator,
+            max_retries,
+        })
+    }
+
+    pub(crate) fn schema(&self) -> &Value {
+        &self.schema
+    }
+
+    pub(crate) fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    /// Checks the final assistant message and returns the parsed document, or
+    /// one line per problem.
+    pub(crate) fn validate(&self, message: Option<&str>) -> Result<Value, Vec<String>> {
+        let Some(message) = message.map(strip_code_fence).filter(|text| !text.is_empty()) else {
+            return Err(vec!["the final message was empty".to_owned()]);
+        };
+        let instance: Value = serde_json::from_str(message)
+            .map_err(|err| vec![format!("the final message is not valid JSON: {err}")])?;
+        self.validator.validate(&instance).map_err(|errors| {
+            errors
+                .map(|error| {
+                    let path = error.instance_path.to_string();
+                    if path.is_empty() {
+                        error.to_string()
+                    } else {
+                        format!("{path}: {error}")
+                    }
+                })
+                .collect::<Vec<String>>()
+        })?;
+        Ok(instance)
+    }
+}
+
+/// Follow-up prompt asking the model to fix its previous answer.
+pub(crate) fn correction_prompt(errors: &[String]) -> String {
+    let mut prompt = String::from("Your final answer does not match the required JSON Schema:\n");
+    for error in errors {
+        let _ = writeln!(prompt, "- {error}");
+    }
+    prompt.push_str(
+        "Reply again with only the corrected JSON document. Do not repeat any work or add commentary.",
+    );
+    prompt
+}
+
+/// The `structured_output` JSONL record emitted after the run in `--json`
+/// mode, so consumers get the validated object without parsing the message.
+pub(crate) fn structured_output_record(document: &Value) -> Value {
+    json!({
+        "type": "structured_output",
+        "output": document,
+    })
+}
+
+/// Writes the validated final response for `--structured-output-file`.
+pub(crate) fn write_structured_output(path: &Path, document: &Value) {
+    let written = serde_json::to_string_pretty(document)
+        .map_err(std::io::Error::other)
+        .and_then(|text| std::fs::write(path, format!("{text}\n")));
+    if let Err(err) = written {
+        eprintln!("Failed to write structured output to {}: {err}", path.display());
+    }
+}
+
+/// Models sometimes wrap JSON in a Markdown fence despite the response format.
+fn strip_code_fence(message: &str) -> &str {
+    let trimmed = message.trim();
+    let Some(body) = trimmed.strip_prefix("```") else {
+        return trimmed;
+    };
+    let Some(body) = body.strip_suffix("```") else {
+        return trimmed;
+    };
+    // Drop the info string (e.g. `json`) on the opening line.
+    body.split_once('\n').map_or(body, |(_, rest)| rest).trim()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema() -> OutputSchema {
+        OutputSchema::compile(
+            json!({
+                "type": "object",
+                "properties": { "name": { "type": "string" } },
+                "required": ["name"],
+                "additionalProperties": false
+            }),
+            2,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn validates_final_messages() {
+        let schema = schema();
+        assert_eq!(schema.validate(Some(r#"{"name":"code"}"#)), Ok(json!({ "name": "code" })));
+        assert_eq!(
+            schema.validate(Some("```json\n{\"name\":\"code\"}\n```")),
+            Ok(json!({ "name": "code" }))
+        );
+
+        let errors = schema.validate(Some(r#"{"name":7}"#)).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("/name: "), "{errors:?}");
+
+        assert!(schema.validate(Some("Here you go!")).unwrap_err()[0].contains("not valid JSON"));
+        assert_eq!(
+            schema.validate(None),
+            Err(vec!["the final message was empty".to_owned()])
+        );
+    }
+
+    #[test]
+    fn correction_prompt_lists_errors() {
+        let prompt = correction_prompt(&["/name: 7 is not of type \"string\"".to_owned()]);
+        assert!(prompt.contains("- /name: 7 is not of type \"string\"\n"));
+        assert!(prompt.ends_with("add commentary."));
+    }
+
+    #[test]
+    fn structured_output_record_wraps_the_document() {
+        let document = schema().validate(Some("```json\n{\"name\":\"code\"}\n```")).unwrap();
+        assert_eq!(
+            structured_output_record(&document),
+            json!({ "type": "structured_output", "output": { "name": "code" } })
+        );
+    }
+}
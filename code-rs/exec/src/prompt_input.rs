@@ -1,6 +1,7 @@
 use serde_json::Value;
 use std::io::IsTerminal;
 use std::io::Read;
+use std::path::Path;
 use std::path::PathBuf;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -117,6 +118,43 @@ pub(crate) fn resolve_prompt(prompt_arg: Option<String>) -> String {
     }
 }
 
+/// Expands `{{env.VAR}}` and `{{file:path}}` placeholders for
+/// `--prompt-template`. Relative paths resolve against `base_dir`. Expanded
+/// text is not scanned again, and other `{{...}}` sequences are left as-is.
+pub(crate) fn expand_prompt_template(
+    template: &str,
+    base_dir: &Path,
+    lookup_env: &dyn Fn(&str) -> Option<String>,
+) -> Result<String, String> {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let inner = rest[start + 2..start + 2 + len].trim();
+        let end = start + 2 + len + 2;
+        out.push_str(&rest[..start]);
+        if let Some(name) = inner.strip_prefix("env.") {
+            let value = lookup_env(name)
+                .ok_or_else(|| format!("{{{{env.{name}}}}}: environment variable is not set"))?;
+            out.push_str(&value);
+        } else if let Some(path) = inner.strip_prefix("file:") {
+            let path = path.trim();
+            let bytes = std::fs::read(base_dir.join(path))
+                .map_err(|err| format!("{{{{file:{path}}}}}: {err}"))?;
+            let contents =
+                decode_prompt_bytes(&bytes).map_err(|err| format!("{{{{file:{path}}}}}: {err}"))?;
+            out.push_str(&contents);
+        } else {
+            out.push_str(&rest[start..end]);
+        }
+        rest = &rest[end..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
 pub(crate) fn load_output_schema(path: Option<PathBuf>) -> Option<Value> {
     let path = path?;
 
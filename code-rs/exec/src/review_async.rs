@@ -0,0 +1,135 @@
+//! `--review-async`: review the uncommitted changes in a parallel worktree
+//! while the prompt runs, then reconcile the findings in a final turn.
+//!
+//! The review is a background Auto Review agent, the same kind the TUI
+//! starts after a turn, pinned to a ghost snapshot of the working tree taken
+//! before the prompt is submitted. The session runtime keeps the process
+//! alive until the agent finishes; if it reports findings, one more turn asks
+//! the model to apply the fixes that still make sense on top of its own work
+//! and to re-run validation.
+
+use crate::auto_runtime::capture_auto_resolve_snapshot;
+use code_core::config::Config;
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+
+/// The background review started for this run.
+pub(crate) struct AsyncReview {
+    pub(crate) agent_id: String,
+    pub(crate) branch: String,
+    pub(crate) snapshot_id: String,
+}
+
+/// Snapshots the working tree and starts the review agent. Returns `None`
+/// (after telling the user why) when there is nothing to review.
+pub(crate) async fn start_async_review(config: &Config) -> Option<AsyncReview> {
+    if !has_uncommitted_changes(&config.cwd) {
+        eprintln!("[review-async] working tree has no uncommitted changes; skipping the parallel review.");
+        return None;
+    }
+    let Some(snapshot) =
+        capture_auto_resolve_snapshot(&config.cwd, None, "review-async snapshot")
+    else {
+        eprintln!("[review-async] could not snapshot the working tree; skipping the parallel review.");
+        return None;
+    };
+    let snapshot_id = snapshot.id().to_owned();
+    let short_id: String = snapshot_id.chars().take(8).collect();
+    let branch = format!("review-async-{short_id}");
+
+    let model = if config.auto_review_model.to_ascii_lowercase().starts_with("code-") {
+        config.auto_review_model.clone()
+    } else {
+        format!("code-{}", config.auto_review_model)
+    };
+    // Lets the review agent's own /review skip the lock this process holds.
+    let env = HashMap::from([("CODE_REVIEW_LOCK_LEASE".to_owned(), "1".to_owned())]);
+    let agent_config = code_core::config_types::AgentConfig {
+        name: model.clone(),
+        command: String::new(),
+        args: Vec::new(),
+        read_only: false,
+        enabled: true,
+        description: None,
+        env: Some(env),
+        args_read_only: None,
+        args_write: None,
+        instructions: None,
+    };
+    let prompt = format!(
+        "/review Analyze only changes made in commit {snapshot_id}. Identify critical bugs, regressions, security/performance/concurrency risks or incorrect assumptions. Provide actionable feedback and references to the changed code; ignore minor style or formatting nits."
+    );
+
+    let agent_id = code_core::AGENT_MANAGER
+        .write()
+        .await
+        .create_agent_with_options(code_core::AgentCreateRequest {
+            model,
+            name: Some("Async Review".to_owned()),
+            prompt,
+            context: None,
+            output_goal: None,
+            files: Vec::new(),
+            read_only: false,
+            batch_id: Some(branch.clone()),
+            config: Some(agent_config),
+            worktree_branch: Some(branch.clone()),
+            worktree_base: Some(snapshot_id.clone()),
+            source_kind: Some(code_core::protocol::AgentSourceKind::AutoReview),
+            reasoning_effort: config.auto_review_model_reasoning_effort.into(),
+        })
+        .await;
+    eprintln!("[review-async] reviewing uncommitted changes ({short_id}) in worktree branch {branch} while the prompt runs.");
+    Some(AsyncReview {
+        agent_id,
+        branch,
+        snapshot_id,
+    })
+}
+
+/// Final turn that folds the review into the main run.
+pub(crate) fn reconciliation_prompt(
+    review: &AsyncReview,
+    summary: &str,
+    worktree: Option<&Path>,
+) -> String {
+    let location = match worktree {
+        Some(path) => format!("worktree {} (branch {})", path.display(), review.branch),
+        None => format!("branch {}", review.branch),
+    };
+    format!(
+        "A review of the uncommitted changes that existed when this run started (snapshot {snapshot}) ran in parallel in {location} and reported:\n\n{summary}\n\nReconcile it with your work:\n1. Decide for each finding whether it still applies after your changes. Skip findings your changes already fixed or made obsolete, and say why.\n2. Apply the accepted fixes here. The review may have committed fixes on its branch; reuse them (`git diff {snapshot} {branch}`) where they apply cleanly, otherwise implement the fix directly. Do not merge or delete the review branch.\n3. Re-run the project's build and tests, fix any failures, and finish with a short list of accepted, skipped, and failed-to-apply findings.",
+        snapshot = review.snapshot_id,
+        branch = review.branch,
+    )
+}
+
+fn has_uncommitted_changes(cwd: &Path) -> bool {
+    Command::new("git")
+        .args(["status", "--porcelain", "--untracked-files=normal"])
+        .current_dir(cwd)
+        .output()
+        .is_ok_and(|output| output.status.success() && !output.stdout.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn reconciliation_prompt_names_snapshot_and_worktree() {
+        let review = AsyncReview {
+            agent_id: "agent-1".to_owned(),
+            branch: "review-async-abc12345".to_owned(),
+            snapshot_id: "abc12345ff".to_owned(),
+        };
+        let worktree = PathBuf::from("/tmp/branches/review-async-abc12345");
+        let prompt = reconciliation_prompt(&review, "Findings: Off-by-one in pager", Some(&worktree));
+        assert!(prompt.contains("(snapshot abc12345ff)"));
+        assert!(prompt.contains("worktree /tmp/branches/review-async-abc12345 (branch review-async-abc12345)"));
+        assert!(prompt.contains("Findings: Off-by-one in pager"));
+        assert!(prompt.contains("`git diff abc12345ff review-async-abc12345`"));
+    }
+}
@@ -50,9 +50,15 @@ pub(crate) fn build_review_request(args: ReviewArgs) -> anyhow::Result<ReviewReq
             prompt.clone(),
             Some(prompt),
         )
+    } else if let Some(base_ref) = args.review_base {
+        let prompt = format!(
+            "Review the changes on the current branch since it diverged from `{base_ref}`."
+        );
+        let hint = format!("changes since merge base with {base_ref}");
+        (ReviewTarget::BaseBranch { branch: base_ref }, prompt, Some(hint))
     } else {
         anyhow::bail!(
-            "Specify --uncommitted, --base, --commit, or provide custom review instructions"
+            "Specify --uncommitted, --base, --commit, --review-base, or provide custom review instructions"
         );
     };
 
@@ -60,6 +66,7 @@ pub(crate) fn build_review_request(args: ReviewArgs) -> anyhow::Result<ReviewReq
         target,
         user_facing_hint: hint,
         prompt,
+        profile: args.review_profile,
     })
 }
 
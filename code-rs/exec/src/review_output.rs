@@ -1,7 +1,12 @@
+use code_core::git_info::collect_git_info;
+use code_core::git_info::get_git_repo_root;
+use code_core::protocol::ReviewFinding;
 use code_core::protocol::ReviewOutputEvent;
 use code_core::protocol::ReviewSnapshotInfo;
 use code_protocol::models::ContentItem;
 use code_protocol::models::ResponseItem;
+use std::borrow::Cow;
+use std::path::Path;
 use std::path::PathBuf;
 
 pub(crate) fn build_fix_prompt(review: &ReviewOutputEvent) -> String {
@@ -76,6 +81,15 @@ pub(crate) fn review_summary_line(output: &ReviewOutputEvent) -> Option<String>
     }
 }
 
+/// Whether the run ends with review findings still open: the last review
+/// reported some, or auto-resolve acknowledged some without fixing them.
+pub(crate) fn review_findings_remain(
+    outputs: &[ReviewOutputEvent],
+    acknowledged: &[ReviewFinding],
+) -> bool {
+    !acknowledged.is_empty() || outputs.last().is_some_and(|output| !output.findings.is_empty())
+}
+
 pub(crate) fn make_user_message(text: String) -> ResponseItem {
     ResponseItem::Message {
         id: None,
@@ -96,10 +110,79 @@ pub(crate) fn make_assistant_message(text: String) -> ResponseItem {
     }
 }
 
+/// Repository that `--review-paths-relative` rewrites finding paths against,
+/// recorded in the review JSON so consumers on another machine can resolve
+/// them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ReviewPathBase {
+    pub(crate) repo_root: PathBuf,
+    pub(crate) commit_sha: Option<String>,
+    /// Auto Review runs in a snapshot worktree; findings there are relative
+    /// to the worktree rather than the main checkout.
+    pub(crate) worktree_path: Option<PathBuf>,
+}
+
+impl ReviewPathBase {
+    /// Prefers the review snapshot's repo and commit, falling back to the git
+    /// repository containing `cwd` and its current HEAD.
+    pub(crate) async fn detect(cwd: &Path, snapshot: Option<&ReviewSnapshotInfo>) -> Option<Self> {
+        let repo_root = snapshot
+            .and_then(|snapshot| snapshot.repo_root.clone())
+            .or_else(|| get_git_repo_root(cwd))?;
+        let commit_sha = match snapshot.and_then(|snapshot| snapshot.snapshot_commit.clone()) {
+            Some(sha) => Some(sha),
+            None => collect_git_info(&repo_root)
+                .await
+                .and_then(|info| info.commit_hash),
+        };
+        Some(Self {
+            repo_root,
+            commit_sha,
+            worktree_path: snapshot.and_then(|snapshot| snapshot.worktree_path.clone()),
+        })
+    }
+
+    /// Returns `path` relative to the worktree or repo root. Paths outside
+    /// both are returned unchanged.
+    fn relativize(&self, path: &Path) -> PathBuf {
+        let roots = self
+            .worktree_path
+            .iter()
+            .chain(std::iter::once(&self.repo_root));
+        for root in roots {
+            if let Ok(rel) = path.strip_prefix(root) {
+                return rel.to_path_buf();
+            }
+            // Tolerate symlinked prefixes such as /tmp -> /private/tmp.
+            if let (Ok(path), Ok(root)) = (path.canonicalize(), root.canonicalize())
+                && let Ok(rel) = path.strip_prefix(&root)
+            {
+                return rel.to_path_buf();
+            }
+        }
+        path.to_path_buf()
+    }
+
+    fn relativize_output(&self, output: &ReviewOutputEvent) -> ReviewOutputEvent {
+        let mut output = output.clone();
+        for finding in &mut output.findings {
+            self.relativize_finding(finding);
+        }
+        output
+    }
+
+    fn relativize_finding(&self, finding: &mut ReviewFinding) {
+        let location = &mut finding.code_location;
+        location.absolute_file_path = self.relativize(&location.absolute_file_path);
+    }
+}
+
 pub(crate) fn write_review_json(
     path: PathBuf,
     outputs: &[ReviewOutputEvent],
     snapshot: Option<&ReviewSnapshotInfo>,
+    path_base: Option<&ReviewPathBase>,
+    acknowledged: &[ReviewFinding],
 ) -> std::io::Result<()> {
     if outputs.is_empty() {
         return Ok(());
@@ -119,9 +202,49 @@ pub(crate) fn write_review_json(
         #[serde(skip_serializing_if = "Vec::is_empty")]
         runs: Vec<ReviewRun<'a>>,
         #[serde(flatten, skip_serializing_if = "Option::is_none")]
-        snapshot: Option<&'a ReviewSnapshotInfo>,
+        snapshot: Option<Cow<'a, ReviewSnapshotInfo>>,
+        /// Set when finding paths are relative to `repo_root`.
+        #[serde(skip_serializing_if = "std::ops::Not::not")]
+        paths_relative: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        commit_sha: Option<&'a str>,
+        /// Findings auto-resolve left out of its fix passes.
+        #[serde(skip_serializing_if = "<[_]>::is_empty")]
+        acknowledged_findings: &'a [ReviewFinding],
     }
 
+    let outputs: Cow<'_, [ReviewOutputEvent]> = match path_base {
+        Some(base) => Cow::Owned(outputs.iter().map(|output| base.relativize_output(output)).collect()),
+        None => Cow::Borrowed(outputs),
+    };
+    let acknowledged: Cow<'_, [ReviewFinding]> = match path_base {
+        Some(base) => Cow::Owned(
+            acknowledged
+                .iter()
+                .cloned()
+                .map(|mut finding| {
+                    base.relativize_finding(&mut finding);
+                    finding
+                })
+                .collect(),
+        ),
+        None => Cow::Borrowed(acknowledged),
+    };
+    let snapshot = match path_base {
+        Some(base) => {
+            let mut snapshot = snapshot.cloned().unwrap_or(ReviewSnapshotInfo {
+                snapshot_commit: None,
+                branch: None,
+                worktree_path: None,
+                repo_root: None,
+                review_profile: None,
+            });
+            snapshot.repo_root = Some(base.repo_root.clone());
+            Some(Cow::Owned(snapshot))
+        }
+        None => snapshot.map(Cow::Borrowed),
+    };
+
     let Some(latest) = outputs.last() else {
         return Ok(());
     };
@@ -138,8 +261,232 @@ pub(crate) fn write_review_json(
         latest,
         runs,
         snapshot,
+        paths_relative: path_base.is_some(),
+        commit_sha: path_base.and_then(|base| base.commit_sha.as_deref()),
+        acknowledged_findings: &acknowledged,
     };
     let json = serde_json::to_string_pretty(&payload)
         .map_err(|e| std::io::Error::other(e.to_string()))?;
     std::fs::write(path, json)
 }
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+/// `uriBaseId` for repository-relative finding paths, following the
+/// convention GitHub code scanning and most SARIF viewers expect.
+const SARIF_SRCROOT: &str = "%SRCROOT%";
+
+fn sarif_level(priority: i32) -> &'static str {
+    match priority {
+        i32::MIN..=1 => "error",
+        2 => "warning",
+        _ => "note",
+    }
+}
+
+fn sarif_rule_id(priority: i32) -> String {
+    format!("review/P{}", priority.clamp(0, 3))
+}
+
+/// Percent-encodes `path` into a URI path, leaving separators intact.
+fn sarif_uri_path(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let mut encoded = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => {
+                encoded.push(byte as char);
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+fn sarif_file_uri(path: &Path) -> String {
+    let uri = sarif_uri_path(path);
+    if uri.starts_with('/') {
+        format!("file://{uri}")
+    } else {
+        // Windows drive paths such as C:/repo.
+        format!("file:///{uri}")
+    }
+}
+
+fn sarif_artifact_location(path: &Path) -> serde_json::Value {
+    if path.is_absolute() {
+        serde_json::json!({ "uri": sarif_file_uri(path) })
+    } else {
+        serde_json::json!({ "uri": sarif_uri_path(path), "uriBaseId": SARIF_SRCROOT })
+    }
+}
+
+fn sarif_result(finding: &ReviewFinding, acknowledged: bool) -> serde_json::Value {
+    let title = finding.title.trim();
+    let body = finding.body.trim();
+    let text = if body.is_empty() {
+        title.to_owned()
+    } else {
+        format!("{title}\n\n{body}")
+    };
+    let range = &finding.code_location.line_range;
+    // SARIF lines are 1-based; a zero start means the reviewer gave no line.
+    let start_line = range.start.max(1);
+    let end_line = range.end.max(start_line);
+    serde_json::json!({
+        "ruleId": sarif_rule_id(finding.priority),
+        "level": sarif_level(finding.priority),
+        "message": { "text": text },
+        "locations": [{
+            "physicalLocation": {
+                "artifactLocation": sarif_artifact_location(&finding.code_location.absolute_file_path),
+                "region": { "startLine": start_line, "endLine": end_line },
+            }
+        }],
+        "properties": {
+            "priority": finding.priority,
+            "confidence": finding.confidence_score,
+            "acknowledged": acknowledged,
+        },
+    })
+}
+
+/// Builds a SARIF 2.1.0 log from the latest review pass, plus any findings
+/// auto-resolve acknowledged without fixing, which are still open.
+fn build_review_sarif(
+    outputs: &[ReviewOutputEvent],
+    path_base: Option<&ReviewPathBase>,
+    acknowledged: &[ReviewFinding],
+) -> serde_json::Value {
+    let relativize = |finding: &ReviewFinding| {
+        let mut finding = finding.clone();
+        if let Some(base) = path_base {
+            base.relativize_finding(&mut finding);
+        }
+        finding
+    };
+    let latest: Vec<ReviewFinding> = outputs
+        .last()
+        .map(|output| output.findings.iter().map(relativize).collect())
+        .unwrap_or_default();
+    let mut results: Vec<serde_json::Value> = latest
+        .iter()
+        .map(|finding| sarif_result(finding, false))
+        .collect();
+    for finding in acknowledged.iter().map(relativize) {
+        if !latest.contains(&finding) {
+            results.push(sarif_result(&finding, true));
+        }
+    }
+
+    let rules: Vec<serde_json::Value> = (0..=3)
+        .map(|priority| {
+            serde_json::json!({
+                "id": sarif_rule_id(priority),
+                "name": format!("ReviewFindingP{priority}"),
+                "shortDescription": { "text": format!("P{priority} code review finding") },
+                "defaultConfiguration": { "level": sarif_level(priority) },
+            })
+        })
+        .collect();
+
+    let mut run = serde_json::json!({
+        "tool": {
+            "driver": {
+                "name": "code-review",
+                "version": env!("CARGO_PKG_VERSION"),
+                "rules": rules,
+            }
+        },
+        "results": results,
+    });
+    if let Some(base) = path_base {
+        // Findings were relativized against the worktree when there is one.
+        let mut root = sarif_file_uri(base.worktree_path.as_ref().unwrap_or(&base.repo_root));
+        if !root.ends_with('/') {
+            root.push('/');
+        }
+        run["originalUriBaseIds"] = serde_json::json!({ SARIF_SRCROOT: { "uri": root } });
+        if let Some(sha) = &base.commit_sha {
+            run["properties"] = serde_json::json!({ "commitSha": sha });
+        }
+    }
+
+    serde_json::json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [run],
+    })
+}
+
+/// Writes the review findings as SARIF 2.1.0 for GitHub code scanning and
+/// other SARIF consumers.
+pub(crate) fn write_review_sarif(
+    path: PathBuf,
+    outputs: &[ReviewOutputEvent],
+    path_base: Option<&ReviewPathBase>,
+    acknowledged: &[ReviewFinding],
+) -> std::io::Result<()> {
+    let sarif = build_review_sarif(outputs, path_base, acknowledged);
+    let json = serde_json::to_string_pretty(&sarif)
+        .map_err(|e| std::io::Error::other(e.to_string()))?;
+    std::fs::write(path, json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use code_core::protocol::ReviewCodeLocation;
+    use code_core::protocol::ReviewLineRange;
+
+    fn finding(title: &str, path: &str, priority: i32) -> ReviewFinding {
+        ReviewFinding {
+            title: title.to_owned(),
+            body: "details".to_owned(),
+            confidence_score: 0.5,
+            priority,
+            code_location: ReviewCodeLocation {
+                absolute_file_path: PathBuf::from(path),
+                line_range: ReviewLineRange { start: 3, end: 7 },
+            },
+        }
+    }
+
+    #[test]
+    fn sarif_maps_findings_to_results_with_relative_locations() {
+        let output = ReviewOutputEvent {
+            findings: vec![finding("Null deref", "/repo/src/my lib.rs", 1)],
+            ..ReviewOutputEvent::default()
+        };
+        let base = ReviewPathBase {
+            repo_root: PathBuf::from("/repo"),
+            commit_sha: Some("abc123".to_owned()),
+            worktree_path: None,
+        };
+        let acknowledged = vec![finding("Style nit", "/repo/README.md", 3)];
+
+        let sarif = build_review_sarif(&[output], Some(&base), &acknowledged);
+        assert_eq!(sarif["version"], "2.1.0");
+        let run = &sarif["runs"][0];
+        assert_eq!(run["originalUriBaseIds"]["%SRCROOT%"]["uri"], "file:///repo/");
+        assert_eq!(run["properties"]["commitSha"], "abc123");
+
+        let results = run["results"].as_array().cloned().unwrap_or_default();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0]["ruleId"], "review/P1");
+        assert_eq!(results[0]["level"], "error");
+        assert_eq!(results[0]["message"]["text"], "Null deref\n\ndetails");
+        let location = &results[0]["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "src/my%20lib.rs");
+        assert_eq!(location["artifactLocation"]["uriBaseId"], "%SRCROOT%");
+        assert_eq!(location["region"]["startLine"], 3);
+        assert_eq!(location["region"]["endLine"], 7);
+        assert_eq!(results[1]["level"], "note");
+        assert_eq!(results[1]["properties"]["acknowledged"], true);
+
+        let absolute = build_review_sarif(&[], None, &acknowledged);
+        assert_eq!(
+            absolute["runs"][0]["results"][0]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "file:///repo/README.md"
+        );
+    }
+}
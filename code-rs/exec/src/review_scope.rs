@@ -1,10 +1,14 @@
 use std::fmt::Write;
 use std::path::Path;
 
+use anyhow::Context;
 use code_auto_drive_core::AUTO_RESOLVE_REVIEW_FOLLOWUP;
 use code_auto_drive_core::AutoResolveState;
+use code_core::config_types::LargeFilesConfig;
 use code_core::protocol::ReviewRequest;
 use code_git_tooling::GhostCommit;
+use code_git_tooling::LargeFileChange;
+use code_git_tooling::SubmoduleChange;
 
 use crate::review_output::format_review_findings;
 
@@ -30,11 +34,16 @@ pub(crate) fn snapshot_parent_diff_paths(cwd: &Path, parent: &str, head: &str) -
     Some(paths)
 }
 
+const SUBMODULE_SCOPE_HEADER: &str = "Submodule updates in this snapshot";
+const LARGE_FILE_SCOPE_HEADER: &str = "Large files in this snapshot";
+
 pub(crate) fn apply_commit_scope_to_review_request(
     mut request: ReviewRequest,
+    cwd: &Path,
     commit: &str,
     parent: &str,
     paths: Option<&[String]>,
+    large_files: &LargeFilesConfig,
 ) -> ReviewRequest {
     let short_commit = &commit[..commit.len().min(7)];
     let short_parent = &parent[..parent.len().min(7)];
@@ -50,6 +59,15 @@ pub(crate) fn apply_commit_scope_to_review_request(
             }
         }
 
+    match code_git_tooling::submodule_changes(cwd, parent, commit) {
+        Ok(changes) => append_submodule_scope(&mut prompt, &changes),
+        Err(err) => tracing::debug!("submodule scope unavailable: {err}"),
+    }
+    match code_git_tooling::large_file_changes(cwd, parent, commit, large_files.threshold_bytes()) {
+        Ok(changes) => append_large_file_scope(&mut prompt, &changes, large_files.include_in_diffs),
+        Err(err) => tracing::debug!("large file scope unavailable: {err}"),
+    }
+
     request.prompt = prompt;
     request.user_facing_hint = Some(format!("commit {short_commit} (parent {short_parent})"));
     request.target = code_protocol::protocol::ReviewTarget::Custom {
@@ -58,6 +76,98 @@ pub(crate) fn apply_commit_scope_to_review_request(
     request
 }
 
+/// Scopes a `--review-base` review to `merge-base(base_ref, HEAD)..HEAD`, so
+/// CI reviews always cover exactly the branch's commits regardless of what is
+/// in the working tree.
+pub(crate) fn pin_review_base(
+    mut request: ReviewRequest,
+    cwd: &Path,
+    base_ref: &str,
+    large_files: &LargeFilesConfig,
+) -> anyhow::Result<ReviewRequest> {
+    let merge_base = git_stdout(cwd, &["merge-base", base_ref, "HEAD"])
+        .with_context(|| format!("--review-base: no merge base between `{base_ref}` and HEAD"))?;
+    let head = git_stdout(cwd, &["rev-parse", "HEAD"])
+        .context("--review-base: could not resolve HEAD")?;
+    let short_base = &merge_base[..merge_base.len().min(7)];
+
+    let mut prompt = request.prompt.trim_end().to_owned();
+    let _ = write!(
+        prompt,
+        "\n\nReview scope: commits from merge base {merge_base} (with `{base_ref}`) to HEAD {head}. Ignore uncommitted changes."
+    );
+    if let Some(paths) = snapshot_parent_diff_paths(cwd, &merge_base, &head)
+        && !paths.is_empty()
+    {
+        prompt.push_str("\nFiles changed in this range:\n");
+        for path in paths {
+            let _ = writeln!(prompt, "- {path}");
+        }
+    }
+    match code_git_tooling::submodule_changes(cwd, &merge_base, &head) {
+        Ok(changes) => append_submodule_scope(&mut prompt, &changes),
+        Err(err) => tracing::debug!("submodule scope unavailable: {err}"),
+    }
+    match code_git_tooling::large_file_changes(cwd, &merge_base, &head, large_files.threshold_bytes()) {
+        Ok(changes) => append_large_file_scope(&mut prompt, &changes, large_files.include_in_diffs),
+        Err(err) => tracing::debug!("large file scope unavailable: {err}"),
+    }
+
+    request.prompt = prompt;
+    request.user_facing_hint = Some(format!("changes since {short_base} (merge base with {base_ref})"));
+    request.target = code_protocol::protocol::ReviewTarget::Custom {
+        instructions: request.prompt.clone(),
+    };
+    Ok(request)
+}
+
+fn git_stdout(cwd: &Path, args: &[&str]) -> anyhow::Result<String> {
+    let output = std::process::Command::new("git")
+        .current_dir(cwd)
+        .args(args)
+        .output()?;
+    if !output.status.success() {
+        anyhow::bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_owned())
+}
+
+/// Lists submodule bumps with their contained commits so the reviewer looks
+/// past the bare pointer change.
+pub(crate) fn append_submodule_scope(prompt: &mut String, changes: &[SubmoduleChange]) {
+    if changes.is_empty() {
+        return;
+    }
+    let _ = write!(
+        prompt,
+        "\n{SUBMODULE_SCOPE_HEADER} (review the contained changes, not just the pointer):\n"
+    );
+    for change in changes {
+        let _ = writeln!(prompt, "{}", change.render());
+    }
+}
+
+/// Lists LFS, binary, and oversized files by metadata. Unless
+/// `include_contents` is set, the reviewer is told not to diff them.
+pub(crate) fn append_large_file_scope(
+    prompt: &mut String,
+    changes: &[LargeFileChange],
+    include_contents: bool,
+) {
+    if changes.is_empty() {
+        return;
+    }
+    let guidance = if include_contents {
+        "Git LFS, binary, or large; diff selectively"
+    } else {
+        "contents excluded; review from this metadata and do not diff or print them"
+    };
+    let _ = write!(prompt, "\n{LARGE_FILE_SCOPE_HEADER} ({guidance}):\n");
+    for change in changes {
+        let _ = writeln!(prompt, "- {}", change.render());
+    }
+}
+
 pub(crate) fn capture_snapshot_against_base(
     cwd: &Path,
     base: &GhostCommit,
@@ -79,11 +189,21 @@ pub(crate) fn strip_scope_from_prompt(prompt: &str) -> String {
     if let Some(idx) = base.find(AUTO_RESOLVE_REVIEW_FOLLOWUP) {
         base = base[..idx].trim_end().to_owned();
     }
+    let mut in_scope_block = false;
     let filtered: Vec<&str> = base
         .lines()
         .filter(|line| {
             let trimmed = line.trim_start();
-            !(trimmed.starts_with("Review scope:") || trimmed.starts_with("commit "))
+            if trimmed.starts_with(SUBMODULE_SCOPE_HEADER)
+                || trimmed.starts_with(LARGE_FILE_SCOPE_HEADER)
+            {
+                in_scope_block = true;
+            } else if trimmed.is_empty() {
+                in_scope_block = false;
+            }
+            !(in_scope_block
+                || trimmed.starts_with("Review scope:")
+                || trimmed.starts_with("commit "))
         })
         .collect();
     filtered.join("\n")
@@ -141,10 +261,11 @@ pub(crate) fn head_is_ancestor_of_base(cwd: &Path, base_commit: &str) -> bool {
 
 pub(crate) async fn build_followup_review_request(
     state: &AutoResolveState,
-    _cwd: &Path,
+    cwd: &Path,
     snapshot: Option<&GhostCommit>,
     diff_paths: Option<&[String]>,
     parent_commit: Option<&str>,
+    large_files: &LargeFilesConfig,
 ) -> ReviewRequest {
     let mut prompt = strip_scope_from_prompt(&state.prompt);
 
@@ -158,10 +279,13 @@ pub(crate) async fn build_followup_review_request(
                 },
                 prompt: prompt.clone(),
                 user_facing_hint: user_facing_hint.clone(),
+                profile: None,
             },
+            cwd,
             snapshot.id(),
             parent,
             diff_paths,
+            large_files,
         );
         prompt = updated.prompt;
         user_facing_hint = updated.user_facing_hint;
@@ -199,5 +323,6 @@ pub(crate) async fn build_followup_review_request(
         target,
         user_facing_hint,
         prompt,
+        profile: None,
     }
 }
@@ -0,0 +1,183 @@
+//! `--max-tokens` and `--max-cost-usd`: stop a run once the session's
+//! cumulative token usage or estimated cost passes a budget.
+//!
+//! Usage comes from `TokenCount` events, which carry the session total, so a
+//! budget covers every turn of the run (and every run under `--watch`).
+
+use std::fmt;
+
+use code_core::protocol::EventMsg;
+use code_core::protocol::TokenUsage;
+use serde_json::json;
+
+const TOKENS_PER_MILLION: f64 = 1_000_000.0;
+// Same flat rates as the TUI's usage overlay; an estimate, not an invoice.
+const INPUT_COST_PER_MILLION_USD: f64 = 1.25;
+const CACHED_INPUT_COST_PER_MILLION_USD: f64 = 0.125;
+const OUTPUT_COST_PER_MILLION_USD: f64 = 10.0;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct RunBudget {
+    pub(crate) max_tokens: Option<u64>,
+    pub(crate) max_cost_usd: Option<f64>,
+}
+
+/// Which limit a run went over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BudgetLimit {
+    Tokens,
+    CostUsd,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct BudgetTracker {
+    budget: RunBudget,
+    usage: TokenUsage,
+    exceeded: Option<BudgetLimit>,
+}
+
+impl BudgetTracker {
+    pub(crate) fn new(budget: RunBudget) -> Self {
+        Self {
+            budget,
+            ..Self::default()
+        }
+    }
+
+    /// Updates the usage from a `TokenCount` event and reports the limit the
+    /// run just went over, once.
+    pub(crate) fn observe(&mut self, msg: &EventMsg) -> Option<BudgetLimit> {
+        let EventMsg::TokenCount(event) = msg else {
+            return None;
+        };
+        let info = event.info.as_ref()?;
+        self.usage = info.total_token_usage.clone();
+        if self.exceeded.is_some() {
+            return None;
+        }
+        self.exceeded = if self
+            .budget
+            .max_tokens
+            .is_some_and(|max| self.usage.total_tokens > max)
+        {
+            Some(BudgetLimit::Tokens)
+        } else if self
+            .budget
+            .max_cost_usd
+            .is_some_and(|max| self.cost_usd() > max)
+        {
+            Some(BudgetLimit::CostUsd)
+        } else {
+            None
+        };
+        self.exceeded
+    }
+
+    pub(crate) fn cost_usd(&self) -> f64 {
+        estimated_cost_usd(&self.usage)
+    }
+
+    pub(crate) fn exceeded(&self) -> Option<BudgetLimit> {
+        self.exceeded
+    }
+
+    /// One-line message for stderr, in the style of the `--max-seconds` one.
+    pub(crate) fn describe(&self, limit: BudgetLimit) -> String {
+        match limit {
+            BudgetLimit::Tokens => format!(
+                "Token budget exceeded (--max-tokens={}): used {} tokens",
+                self.budget.max_tokens.unwrap_or_default(),
+                self.usage.total_tokens
+            ),
+            BudgetLimit::CostUsd => format!(
+                "Cost budget exceeded (--max-cost-usd={}): estimated ${:.4}",
+                self.budget.max_cost_usd.unwrap_or_default(),
+                self.cost_usd()
+            ),
+        }
+    }
+
+    /// Final summary written when a budget stopped the run.
+    pub(crate) fn summary_json(&self) -> serde_json::Value {
+        json!({
+            "type": "budget_exceeded",
+            "limit": self.exceeded.map(|limit| limit.to_string()),
+            "max_tokens": self.budget.max_tokens,
+            "max_cost_usd": self.budget.max_cost_usd,
+            "usage": self.usage,
+            "estimated_cost_usd": self.cost_usd(),
+        })
+    }
+}
+
+impl fmt::Display for BudgetLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tokens => f.write_str("tokens"),
+            Self::CostUsd => f.write_str("cost_usd"),
+        }
+    }
+}
+
+fn estimated_cost_usd(usage: &TokenUsage) -> f64 {
+    let non_cached_input = usage.input_tokens.saturating_sub(usage.cached_input_tokens);
+    let input_cost = (non_cached_input as f64 / TOKENS_PER_MILLION) * INPUT_COST_PER_MILLION_USD;
+    let cached_cost =
+        (usage.cached_input_tokens as f64 / TOKENS_PER_MILLION) * CACHED_INPUT_COST_PER_MILLION_USD;
+    let output_cost = (usage.output_tokens as f64 / TOKENS_PER_MILLION) * OUTPUT_COST_PER_MILLION_USD;
+    input_cost + cached_cost + output_cost
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use code_core::protocol::TokenCountEvent;
+    use code_core::protocol::TokenUsageInfo;
+
+    fn token_count(input_tokens: u64, output_tokens: u64) -> EventMsg {
+        let usage = TokenUsage {
+            input_tokens,
+            cached_input_tokens: 0,
+            output_tokens,
+            reasoning_output_tokens: 0,
+            total_tokens: input_tokens + output_tokens,
+        };
+        EventMsg::TokenCount(TokenCountEvent {
+            info: Some(TokenUsageInfo {
+                total_token_usage: usage.clone(),
+                last_token_usage: usage,
+                ..TokenUsageInfo::default()
+            }),
+            rate_limits: None,
+        })
+    }
+
+    #[test]
+    fn token_budget_trips_once() {
+        let mut tracker = BudgetTracker::new(RunBudget {
+            max_tokens: Some(1_000),
+            max_cost_usd: None,
+        });
+        assert_eq!(tracker.observe(&token_count(600, 300)), None);
+        assert_eq!(tracker.observe(&token_count(900, 300)), Some(BudgetLimit::Tokens));
+        assert_eq!(tracker.observe(&token_count(1_500, 300)), None);
+        assert_eq!(tracker.exceeded(), Some(BudgetLimit::Tokens));
+
+        let summary = tracker.summary_json();
+        assert_eq!(summary["limit"], "tokens");
+        assert_eq!(summary["usage"]["total_tokens"], 1_800);
+    }
+
+    #[test]
+    fn cost_budget_uses_estimated_rates() {
+        let mut tracker = BudgetTracker::new(RunBudget {
+            max_tokens: None,
+            max_cost_usd: Some(0.5),
+        });
+        // 100k input tokens ($0.125) and 30k output tokens ($0.30).
+        assert_eq!(tracker.observe(&token_count(100_000, 30_000)), None);
+        // 20k more output tokens push the estimate to $0.625.
+        assert_eq!(tracker.observe(&token_count(100_000, 50_000)), Some(BudgetLimit::CostUsd));
+        assert!((tracker.cost_usd() - 0.625).abs() < 1e-9);
+    }
+}
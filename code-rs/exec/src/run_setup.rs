@@ -1,9 +1,12 @@
 use crate::auto_runtime::append_timeboxed_auto_drive_goal;
 use crate::cli::Command as ExecCommand;
+use crate::prompt_input::expand_prompt_template;
 use crate::prompt_input::resolve_prompt;
 use crate::review_command::build_review_request;
 use crate::review_command::review_summary;
 use code_core::protocol::ReviewRequest;
+use code_core::slash_commands::format_migrate_goal;
+use code_core::slash_commands::parse_migrate_args;
 use std::path::PathBuf;
 
 pub(crate) struct PreparedRunInputs {
@@ -21,6 +24,7 @@ pub(crate) fn prepare_run_inputs(
     images: Vec<PathBuf>,
     auto_drive: bool,
     max_seconds: Option<u64>,
+    prompt_template: bool,
 ) -> PreparedRunInputs {
     let review_request = match command {
         Some(ExecCommand::Review(args)) => Some(build_review_request(args.clone()).unwrap_or_else(|err| {
@@ -35,7 +39,12 @@ pub(crate) fn prepare_run_inputs(
         // Allow prompt before the subcommand by falling back to the parent-level prompt
         // when the Resume subcommand did not provide its own prompt.
         Some(ExecCommand::Resume(args)) => args.prompt.clone().or(prompt),
-        Some(ExecCommand::Review(_)) => None,
+        Some(
+            ExecCommand::Review(_)
+            | ExecCommand::Export(_)
+            | ExecCommand::Inspect(_)
+            | ExecCommand::Approvals(_),
+        ) => None,
         None => prompt,
     };
     let images = match command {
@@ -44,7 +53,13 @@ pub(crate) fn prepare_run_inputs(
             merged.extend(args.images.iter().cloned());
             merged
         }
-        Some(ExecCommand::Review(_)) | None => images,
+        Some(
+            ExecCommand::Review(_)
+            | ExecCommand::Export(_)
+            | ExecCommand::Inspect(_)
+            | ExecCommand::Approvals(_),
+        )
+        | None => images,
     };
 
     if review_request.is_some() && auto_drive {
@@ -57,12 +72,40 @@ pub(crate) fn prepare_run_inputs(
     } else {
         resolve_prompt(prompt_arg)
     };
+    let prompt = if prompt_template && review_request.is_none() {
+        let base_dir = std::env::current_dir().unwrap_or_default();
+        match expand_prompt_template(&prompt, &base_dir, &|name| std::env::var(name).ok()) {
+            Ok(expanded) => expanded,
+            Err(err) => {
+                eprintln!("--prompt-template: {err}");
+                std::process::exit(1);
+            }
+        }
+    } else {
+        prompt
+    };
 
     let mut auto_drive_goal: Option<String> = None;
     let trimmed_prompt = prompt.trim();
     if trimmed_prompt.starts_with("/auto") {
         auto_drive_goal = Some(trimmed_prompt.trim_start_matches("/auto").trim().to_owned());
     }
+    // `/migrate <dependency> <version>` runs as an Auto Drive goal.
+    let mut migrate_summary: Option<String> = None;
+    if let Some(args) = trimmed_prompt.strip_prefix("/migrate")
+        && (args.is_empty() || args.starts_with(char::is_whitespace))
+    {
+        match parse_migrate_args(args) {
+            Ok((dependency, version)) => {
+                auto_drive_goal = Some(format_migrate_goal(&dependency, &version));
+                migrate_summary = Some(format!("/migrate {dependency} {version}"));
+            }
+            Err(msg) => {
+                eprintln!("{msg}");
+                std::process::exit(1);
+            }
+        }
+    }
     if auto_drive {
         if trimmed_prompt.is_empty() {
             eprintln!("Auto Drive requires a goal. Provide one after --auto or prefix the prompt with /auto.");
@@ -93,6 +136,8 @@ pub(crate) fn prepare_run_inputs(
     let prompt_to_send = prompt.clone();
     let summary_prompt = if let Some(request) = review_request.as_ref() {
         review_summary(request)
+    } else if let Some(summary) = migrate_summary {
+        summary
     } else if let Some(goal) = auto_drive_goal.as_ref() {
         format!("/auto {goal}")
     } else {
@@ -0,0 +1,633 @@
+//! `code exec export`: renders a stored session (rollout file) as a Markdown
+//! or HTML transcript.
+//!
+//! The transcript is built from the events recorded alongside the model
+//! history, so it shows what the user saw: messages, commands with their
+//! output, applied patches, and review findings. Answers that link to pages
+//! returned by web search or fetch tools are followed by their sources.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use code_core::citations::Source;
+use code_core::citations::SourceTracker;
+use code_core::config::Config;
+use code_protocol::models::ContentItem;
+use code_protocol::models::ResponseItem;
+use code_protocol::protocol::EventMsg;
+use code_protocol::protocol::FileChange;
+use code_protocol::protocol::ReviewOutputEvent;
+use code_protocol::protocol::RolloutItem;
+use code_protocol::protocol::RolloutLine;
+
+use crate::cli::ExportArgs;
+use crate::cli::ExportFormat;
+use crate::session_resume::resolve_session_path;
+
+/// Command output beyond this many lines is elided from the transcript.
+const MAX_OUTPUT_LINES: usize = 200;
+
+#[derive(Debug, Default)]
+struct Transcript {
+    session_id: Option<String>,
+    started: Option<String>,
+    cwd: Option<PathBuf>,
+    branch: Option<String>,
+    commit: Option<String>,
+    entries: Vec<Entry>,
+}
+
+#[derive(Debug)]
+enum Entry {
+    User(String),
+    Assistant(String),
+    /// Pages the preceding answer links to, numbered by position.
+    Sources(Vec<Source>),
+    Command {
+        command: String,
+        exit_code: i32,
+        output: String,
+    },
+    Patch(String),
+    Review(ReviewOutputEvent),
+}
+
+pub(crate) async fn run_export(config: &Config, args: &ExportArgs) -> anyhow::Result<()> {
+    let path = resolve_export_path(config, args).await?;
+    let text = tokio::fs::read_to_string(&path)
+        .await
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let transcript = parse_rollout(&text);
+    if transcript.entries.is_empty() {
+        anyhow::bail!("{} has no conversation to export", path.display());
+    }
+
+    let format = args.format.unwrap_or_else(|| match &args.output {
+        Some(output) if has_html_extension(output) => ExportFormat::Html,
+        _ => ExportFormat::Markdown,
+    });
+    let rendered = match format {
+        ExportFormat::Markdown => render_markdown(&transcript),
+        ExportFormat::Html => render_html(&transcript),
+    };
+    match &args.output {
+        Some(output) => std::fs::write(output, rendered)
+            .with_context(|| format!("failed to write {}", output.display()))?,
+        None => print!("{rendered}"),
+    }
+    Ok(())
+}
+
+async fn resolve_export_path(config: &Config, args: &ExportArgs) -> anyhow::Result<PathBuf> {
+    if let Some(session) = args.session.as_deref() {
+        let path = PathBuf::from(session);
+        if path.is_file() {
+            return Ok(path);
+        }
+    }
+    let path = resolve_session_path(config, args.session.as_deref(), args.last, args.all).await?;
+    match (path, args.session.as_deref()) {
+        (Some(path), _) => Ok(path),
+        (None, Some(session)) => anyhow::bail!("no rollout file or session found for `{session}`"),
+        (None, None) if args.last => anyhow::bail!("no recorded sessions found"),
+        (None, None) => anyhow::bail!("pass a rollout path or session id, or use --last"),
+    }
+}
+
+fn has_html_extension(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("html") || ext.eq_ignore_ascii_case("htm"))
+}
+
+fn parse_rollout(text: &str) -> Transcript {
+    let mut transcript = Transcript::default();
+    let mut fallback_messages = Vec::new();
+    let mut sources = SourceTracker::default();
+    // Function calls by call id, until their output arrives.
+    let mut pending_calls: HashMap<String, (String, Option<serde_json::Value>)> = HashMap::new();
+    for line in text.lines().filter(|line| !line.trim().is_empty()) {
+        let Ok(line) = serde_json::from_str::<RolloutLine>(line) else {
+            continue;
+        };
+        match line.item {
+            RolloutItem::SessionMeta(meta) => {
+                if transcript.session_id.is_none() {
+                    transcript.session_id = Some(meta.meta.id.to_string());
+                    transcript.started = Some(meta.meta.timestamp);
+                    transcript.cwd = Some(meta.meta.cwd);
+                    if let Some(git) = meta.git {
+                        transcript.branch = git.branch;
+                        transcript.commit = git.commit_hash;
+                    }
+                }
+            }
+            RolloutItem::Event(event) => {
+                push_event(&mut transcript.entries, &mut sources, event.msg);
+            }
+            RolloutItem::EventMsg(msg) => push_event(&mut transcript.entries, &mut sources, msg),
+            RolloutItem::ResponseItem(item) => match item {
+                ResponseItem::FunctionCall {
+                    name,
+                    arguments,
+                    call_id,
+                    ..
+                } => {
+                    let arguments = serde_json::from_str(&arguments).ok();
+                    pending_calls.insert(call_id, (name, arguments));
+                }
+                ResponseItem::FunctionCallOutput { call_id, output } => {
+                    if let Some((name, arguments)) = pending_calls.remove(&call_id) {
+                        let text = output.text_content().unwrap_or_default();
+                        sources.record_tool_call(&name, arguments.as_ref(), text);
+                    }
+                }
+                ResponseItem::WebSearchCall { .. } => sources.record_native_search(),
+                item => {
+                    if let Some(entry) = message_entry(&item) {
+                        fallback_messages.push(entry);
+                    }
+                }
+            },
+            RolloutItem::Compacted(_) | RolloutItem::TurnContext(_) => {}
+        }
+    }
+
+    // Sessions recorded before events were persisted only have the model
+    // history; show its messages rather than nothing.
+    let has_messages = transcript
+        .entries
+        .iter()
+        .any(|entry| matches!(entry, Entry::User(_) | Entry::Assistant(_)));
+    if !has_messages {
+        transcript.entries.splice(0..0, fallback_messages);
+    }
+    transcript
+}
+
+fn push_event(entries: &mut Vec<Entry>, sources: &mut SourceTracker, msg: EventMsg) {
+    match msg {
+        EventMsg::UserMessage(event) => {
+            sources.clear();
+            entries.push(Entry::User(event.message));
+        }
+        EventMsg::AgentMessage(event) => {
+            let cited = sources.cite(&event.message);
+            entries.push(Entry::Assistant(event.message));
+            if !cited.is_empty() {
+                entries.push(Entry::Sources(cited));
+            }
+        }
+        EventMsg::ExecCommandEnd(event) => {
+            let command = shlex::try_join(event.command.iter().map(String::as_str))
+                .unwrap_or_else(|_| event.command.join(" "));
+            let output = if event.aggregated_output.is_empty() {
+                format!("{}{}", event.stdout, event.stderr)
+            } else {
+                event.aggregated_output
+            };
+            entries.push(Entry::Command {
+                command,
+                exit_code: event.exit_code,
+                output,
+            });
+        }
+        EventMsg::PatchApplyBegin(event) => {
+            let mut changes: Vec<_> = event.changes.into_iter().collect();
+            changes.sort_by(|(a, _), (b, _)| a.cmp(b));
+            let diff: String = changes
+                .iter()
+                .map(|(path, change)| file_change_diff(path, change))
+                .collect();
+            if !diff.is_empty() {
+                entries.push(Entry::Patch(diff));
+            }
+        }
+        EventMsg::ExitedReviewMode(event) => {
+            if let Some(output) = event.review_output {
+                entries.push(Entry::Review(output));
+            }
+        }
+        _ => {}
+    }
+}
+
+fn message_entry(item: &ResponseItem) -> Option<Entry> {
+    let ResponseItem::Message { role, content, .. } = item else {
+        return None;
+    };
+    let text = content
+        .iter()
+        .filter_map(|content| match content {
+            ContentItem::InputText { text } | ContentItem::OutputText { text } => Some(text.as_str()),
+            ContentItem::InputImage { .. } => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    // Injected context (instructions, environment) is wrapped in tags.
+    if text.trim().is_empty() || text.trim_start().starts_with('<') {
+        return None;
+    }
+    match role.as_str() {
+        "user" => Some(Entry::User(text)),
+        "assistant" => Some(Entry::Assistant(text)),
+        _ => None,
+    }
+}
+
+fn file_change_diff(path: &Path, change: &FileChange) -> String {
+    let display = path.display();
+    match change {
+        FileChange::Add { content } => {
+            let mut diff = format!("--- /dev/null\n+++ b/{display}\n");
+            for line in content.lines() {
+                let _ = writeln!(diff, "+{line}");
+            }
+            diff
+        }
+        FileChange::Delete { content } => {
+            let mut diff = format!("--- a/{display}\n+++ /dev/null\n");
+            for line in content.lines() {
+                let _ = writeln!(diff, "-{line}");
+            }
+            diff
+        }
+        FileChange::Update {
+            unified_diff,
+            move_path,
+        } => {
+            let mut diff = String::new();
+            if !unified_diff.starts_with("--- ") {
+                let target = move_path.as_deref().unwrap_or(path).display();
+                let _ = writeln!(diff, "--- a/{display}\n+++ b/{target}");
+            }
+            diff.push_str(unified_diff);
+            if !diff.ends_with('\n') {
+                diff.push('\n');
+            }
+            diff
+        }
+    }
+}
+
+/// Keeps the first [`MAX_OUTPUT_LINES`] lines and notes how many were cut.
+fn truncate_output(output: &str) -> String {
+    let total = output.lines().count();
+    if total <= MAX_OUTPUT_LINES {
+        return output.trim_end().to_owned();
+    }
+    let kept: Vec<&str> = output.lines().take(MAX_OUTPUT_LINES).collect();
+    format!("{}\n… {} more lines", kept.join("\n"), total - MAX_OUTPUT_LINES)
+}
+
+/// Shows finding paths relative to the session directory when possible.
+fn display_path(path: &Path, cwd: Option<&Path>) -> String {
+    cwd.and_then(|cwd| path.strip_prefix(cwd).ok())
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+fn short_commit(commit: &str) -> &str {
+    &commit[..commit.len().min(12)]
+}
+
+/// A code fence longer than any backtick run in `content`.
+fn fence_for(content: &str) -> String {
+    let mut longest = 0;
+    let mut run = 0;
+    for ch in content.chars() {
+        if ch == '`' {
+            run += 1;
+            longest = longest.max(run);
+        } else {
+            run = 0;
+        }
+    }
+    "`".repeat((longest + 1).max(3))
+}
+
+fn render_markdown(transcript: &Transcript) -> String {
+    let mut out = String::new();
+    let title = transcript.session_id.as_deref().unwrap_or("transcript");
+    let _ = writeln!(out, "# Session {title}\n");
+    if let Some(started) = &transcript.started {
+        let _ = writeln!(out, "- Started: {started}");
+    }
+    if let Some(cwd) = &transcript.cwd {
+        let _ = writeln!(out, "- Directory: `{}`", cwd.display());
+    }
+    match (&transcript.branch, &transcript.commit) {
+        (Some(branch), Some(commit)) => {
+            let _ = writeln!(out, "- Git: `{branch}` at `{}`", short_commit(commit));
+        }
+        (Some(branch), None) => {
+            let _ = writeln!(out, "- Git: `{branch}`");
+        }
+        (None, Some(commit)) => {
+            let _ = writeln!(out, "- Git: `{}`", short_commit(commit));
+        }
+        (None, None) => {}
+    }
+
+    for entry in &transcript.entries {
+        out.push('\n');
+        match entry {
+            Entry::User(text) => {
+                let _ = writeln!(out, "## User\n\n{}", text.trim());
+            }
+            Entry::Assistant(text) => {
+                let _ = writeln!(out, "## Assistant\n\n{}", text.trim());
+            }
+            Entry::Sources(sources) => {
+                let _ = writeln!(out, "**Sources**\n");
+                for (idx, source) in sources.iter().enumerate() {
+                    let number = idx + 1;
+                    match &source.title {
+                        Some(title) => {
+                            let _ = writeln!(out, "{number}. {}: <{}>", title.trim(), source.url);
+                        }
+                        None => {
+                            let _ = writeln!(out, "{number}. <{}>", source.url);
+                        }
+                    }
+                }
+            }
+            Entry::Command {
+                command,
+                exit_code,
+                output,
+            } => {
+                let body = format!("$ {command}\n{}", truncate_output(output));
+                let fence = fence_for(&body);
+                let _ = writeln!(out, "{fence}console\n{}\n{fence}", body.trim_end());
+                if *exit_code != 0 {
+                    let _ = writeln!(out, "\n_Exited with code {exit_code}._");
+                }
+            }
+            Entry::Patch(diff) => {
+                let fence = fence_for(diff);
+                let _ = writeln!(out, "{fence}diff\n{}\n{fence}", diff.trim_end());
+            }
+            Entry::Review(review) => {
+                let _ = writeln!(out, "## Review\n");
+                if !review.overall_correctness.trim().is_empty() {
+                    let _ = writeln!(out, "**Verdict:** {}\n", review.overall_correctness.trim());
+                }
+                if !review.overall_explanation.trim().is_empty() {
+                    let _ = writeln!(out, "{}\n", review.overall_explanation.trim());
+                }
+                if review.findings.is_empty() {
+                    let _ = writeln!(out, "No findings reported.");
+                }
+                for finding in &review.findings {
+                    let location = &finding.code_location;
+                    let _ = writeln!(
+                        out,
+                        "### [P{}] {}\n\n`{}:{}-{}`\n",
+                        finding.priority,
+                        finding.title.trim(),
+                        display_path(&location.absolute_file_path, transcript.cwd.as_deref()),
+                        location.line_range.start,
+                        location.line_range.end
+                    );
+                    if !finding.body.trim().is_empty() {
+                        let _ = writeln!(out, "{}\n", finding.body.trim());
+                    }
+                }
+            }
+        }
+    }
+    out
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+const HTML_STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:960px;margin:2rem auto;padding:0 1rem;line-height:1.5;color:#1f2328}\
+.meta{color:#59636e}.msg{white-space:pre-wrap;border-left:3px solid #d1d9e0;padding:.25rem .75rem;margin:.5rem 0}\
+.user{border-color:#0969da}.assistant{border-color:#8250df}\
+pre{background:#f6f8fa;padding:.75rem;overflow-x:auto;border-radius:6px}\
+.add{color:#1a7f37}.del{color:#cf222e}.hunk{color:#8250df}.failed{color:#cf222e}\
+.finding{border:1px solid #d1d9e0;border-radius:6px;padding:.5rem .75rem;margin:.5rem 0}";
+
+fn render_html(transcript: &Transcript) -> String {
+    let mut out = String::new();
+    let title = escape_html(transcript.session_id.as_deref().unwrap_or("transcript"));
+    let _ = writeln!(
+        out,
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Session {title}</title>\n<style>{HTML_STYLE}</style>\n</head>\n<body>\n<h1>Session {title}</h1>"
+    );
+    let mut meta = Vec::new();
+    if let Some(started) = &transcript.started {
+        meta.push(format!("Started {}", escape_html(started)));
+    }
+    if let Some(cwd) = &transcript.cwd {
+        meta.push(format!("<code>{}</code>", escape_html(&cwd.display().to_string())));
+    }
+    if let Some(branch) = &transcript.branch {
+        meta.push(format!("branch <code>{}</code>", escape_html(branch)));
+    }
+    if let Some(commit) = &transcript.commit {
+        meta.push(format!("commit <code>{}</code>", escape_html(short_commit(commit))));
+    }
+    if !meta.is_empty() {
+        let _ = writeln!(out, "<p class=\"meta\">{}</p>", meta.join(" · "));
+    }
+
+    for entry in &transcript.entries {
+        match entry {
+            Entry::User(text) => {
+                let _ = writeln!(
+                    out,
+                    "<h2>User</h2>\n<div class=\"msg user\">{}</div>",
+                    escape_html(text.trim())
+                );
+            }
+            Entry::Assistant(text) => {
+                let _ = writeln!(
+                    out,
+                    "<h2>Assistant</h2>\n<div class=\"msg assistant\">{}</div>",
+                    escape_html(text.trim())
+                );
+            }
+            Entry::Sources(sources) => {
+                let items: Vec<String> = sources
+                    .iter()
+                    .map(|source| {
+                        let url = escape_html(&source.url);
+                        let label = source.title.as_deref().map_or_else(|| url.clone(), escape_html);
+                        format!("<li><a href=\"{url}\">{label}</a></li>")
+                    })
+                    .collect();
+                let _ = writeln!(
+                    out,
+                    "<p class=\"meta\">Sources</p>\n<ol class=\"sources\">\n{}\n</ol>",
+                    items.join("\n")
+                );
+            }
+            Entry::Command {
+                command,
+                exit_code,
+                output,
+            } => {
+                let status = if *exit_code == 0 {
+                    String::new()
+                } else {
+                    format!(" <span class=\"failed\">(exit {exit_code})</span>")
+                };
+                let _ = writeln!(
+                    out,
+                    "<details>\n<summary><code>$ {}</code>{status}</summary>\n<pre>{}</pre>\n</details>",
+                    escape_html(command),
+                    escape_html(&truncate_output(output))
+                );
+            }
+            Entry::Patch(diff) => {
+                let lines: Vec<String> = diff.lines().map(diff_line_html).collect();
+                let _ = writeln!(out, "<pre class=\"diff\">{}</pre>", lines.join("\n"));
+            }
+            Entry::Review(review) => {
+                let _ = writeln!(out, "<h2>Review</h2>");
+                if !review.overall_correctness.trim().is_empty() {
+                    let _ = writeln!(
+                        out,
+                        "<p><strong>Verdict:</strong> {}</p>",
+                        escape_html(review.overall_correctness.trim())
+                    );
+                }
+                if !review.overall_explanation.trim().is_empty() {
+                    let _ = writeln!(
+                        out,
+                        "<p class=\"msg\">{}</p>",
+                        escape_html(review.overall_explanation.trim())
+                    );
+                }
+                if review.findings.is_empty() {
+                    let _ = writeln!(out, "<p>No findings reported.</p>");
+                }
+                for finding in &review.findings {
+                    let location = &finding.code_location;
+                    let path =
+                        display_path(&location.absolute_file_path, transcript.cwd.as_deref());
+                    let _ = writeln!(
+                        out,
+                        "<div class=\"finding\">\n<h3>[P{}] {}</h3>\n<p><code>{}:{}-{}</code></p>\n<div class=\"msg\">{}</div>\n</div>",
+                        finding.priority,
+                        escape_html(finding.title.trim()),
+                        escape_html(&path),
+                        location.line_range.start,
+                        location.line_range.end,
+                        escape_html(finding.body.trim())
+                    );
+                }
+            }
+        }
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn diff_line_html(line: &str) -> String {
+    let class = if line.starts_with("+++") || line.starts_with("---") {
+        None
+    } else if line.starts_with('+') {
+        Some("add")
+    } else if line.starts_with('-') {
+        Some("del")
+    } else if line.starts_with("@@") {
+        Some("hunk")
+    } else {
+        None
+    };
+    match class {
+        Some(class) => format!("<span class=\"{class}\">{}</span>", escape_html(line)),
+        None => escape_html(line),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROLLOUT: &str = r#"{"timestamp":"2025-01-01T00:00:00Z","type":"session_meta","payload":{"id":"5973b6c0-94b8-487b-a530-2aeb6098ae0e","timestamp":"2025-01-01T00:00:00Z","cwd":"/repo","originator":"code_exec","cli_version":"0.0.0","model_provider":null,"git":{"branch":"main"}}}
+{"timestamp":"2025-01-01T00:00:01Z","type":"event_msg","payload":{"type":"user_message","message":"Fix the <script> tag"}}
+{"timestamp":"2025-01-01T00:00:02Z","type":"event_msg","payload":{"type":"exec_command_end","call_id":"c1","turn_id":"t1","command":["bash","-lc","cat ```x```"],"cwd":"/repo","parsed_cmd":[],"stdout":"","stderr":"","aggregated_output":"boom\n","exit_code":2,"duration":{"secs":0,"nanos":0},"formatted_output":""}}
+{"timestamp":"2025-01-01T00:00:03Z","type":"event_msg","payload":{"type":"agent_message","message":"Done."}}
+not json
+"#;
+
+    #[test]
+    fn markdown_includes_messages_and_commands() {
+        let transcript = parse_rollout(ROLLOUT);
+        assert_eq!(transcript.entries.len(), 3);
+        assert_eq!(transcript.cwd.as_deref(), Some(Path::new("/repo")));
+
+        let markdown = render_markdown(&transcript);
+        assert!(markdown.starts_with("# Session 5973b6c0-94b8-487b-a530-2aeb6098ae0e\n"));
+        assert!(markdown.contains("- Git: `main`\n"));
+        assert!(markdown.contains("## User\n\nFix the <script> tag\n"));
+        // The command contains a triple backtick, so the fence grows.
+        assert!(markdown.contains("````console\n$ bash -lc 'cat ```x```'\nboom\n````\n"));
+        assert!(markdown.contains("_Exited with code 2._"));
+        assert!(markdown.contains("## Assistant\n\nDone.\n"));
+    }
+
+    #[test]
+    fn html_escapes_content() {
+        let html = render_html(&parse_rollout(ROLLOUT));
+        assert!(html.contains("Fix the &lt;script&gt; tag"));
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("<span class=\"failed\">(exit 2)</span>"));
+    }
+
+    #[test]
+    fn answers_list_the_search_results_they_link_to() {
+        let results = serde_json::json!({"results": [
+            {"index": 1, "title": "Tokio <select>", "url": "https://tokio.rs/select"},
+            {"index": 2, "title": "Unused", "url": "https://example.com/unused"},
+        ]});
+        let lines = [
+            serde_json::json!({"timestamp": "2025-01-01T00:00:00Z", "type": "event_msg", "payload": {"type": "user_message", "message": "How?"}}),
+            serde_json::json!({"timestamp": "2025-01-01T00:00:01Z", "type": "response_item", "payload": {"type": "function_call", "name": "web_search", "arguments": "{\"query\":\"select\"}", "call_id": "w1"}}),
+            serde_json::json!({"timestamp": "2025-01-01T00:00:02Z", "type": "response_item", "payload": {"type": "function_call_output", "call_id": "w1", "output": results.to_string()}}),
+            serde_json::json!({"timestamp": "2025-01-01T00:00:03Z", "type": "event_msg", "payload": {"type": "agent_message", "message": "Use select! [1](https://tokio.rs/select)."}}),
+        ];
+        let rollout: String = lines.iter().map(|line| format!("{line}\n")).collect();
+        let transcript = parse_rollout(&rollout);
+
+        let markdown = render_markdown(&transcript);
+        assert!(markdown.contains("**Sources**\n\n1. Tokio <select>: <https://tokio.rs/select>\n"));
+        assert!(!markdown.contains("example.com"));
+        let html = render_html(&transcript);
+        assert!(html.contains("<li><a href=\"https://tokio.rs/select\">Tokio &lt;select&gt;</a></li>"));
+    }
+
+    #[test]
+    fn update_diffs_get_file_headers() {
+        let diff = file_change_diff(
+            Path::new("src/lib.rs"),
+            &FileChange::Update {
+                unified_diff: "@@ -1 +1 @@\n-a\n+b\n".to_owned(),
+                move_path: None,
+            },
+        );
+        assert_eq!(diff, "--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1 +1 @@\n-a\n+b\n");
+        assert_eq!(diff_line_html("+<b>"), "<span class=\"add\">+&lt;b&gt;</span>");
+    }
+}
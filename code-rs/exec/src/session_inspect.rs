@@ -0,0 +1,529 @@
+//! `code exec inspect`: rebuilds the history a session showed as of one
+//! recorded event, so state and rendering bugs reported from the field can be
+//! bisected with nothing but the rollout file.
+//!
+//! Recorded events are replayed into a [`HistoryState`] in file order and the
+//! replay stops after the event matching `--at-seq` in the chosen submission
+//! (`event_seq` restarts with every submission). The replay covers the records
+//! that carry conversation state: messages, reasoning, commands with their
+//! streamed output, patches, background notices and errors. Each record's
+//! `order_debug` entry names the event that created it.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use anyhow::Context;
+use code_core::config::Config;
+use code_core::history::AssistantMessageState;
+use code_core::history::BackgroundEventRecord;
+use code_core::history::ExecAction;
+use code_core::history::ExecStatus;
+use code_core::history::ExecStreamChunk;
+use code_core::history::HistoryDomainEvent;
+use code_core::history::HistoryId;
+use code_core::history::HistoryMutation;
+use code_core::history::HistoryRecord;
+use code_core::history::HistorySnapshot;
+use code_core::history::HistoryState;
+use code_core::history::InlineSpan;
+use code_core::history::MessageLine;
+use code_core::history::MessageLineKind;
+use code_core::history::OrderKeySnapshot;
+use code_core::history::PatchEventType;
+use code_core::history::PatchFailureMetadata;
+use code_core::history::PatchRecord;
+use code_core::history::PlainMessageKind;
+use code_core::history::PlainMessageRole;
+use code_core::history::PlainMessageState;
+use code_core::history::ReasoningBlock;
+use code_core::history::ReasoningSection;
+use code_core::history::ReasoningState;
+use code_core::history::TextEmphasis;
+use code_core::history::TextTone;
+use code_core::parse_command::ParsedCommand;
+use code_core::protocol::EventMsg;
+use code_core::protocol::ExecOutputStream;
+use code_core::protocol::RecordedEvent;
+use code_core::protocol::recorded_event_from_protocol;
+use code_protocol::protocol::RolloutItem;
+use code_protocol::protocol::RolloutLine;
+
+use crate::cli::InspectArgs;
+use crate::session_resume::resolve_session_path;
+
+/// A recorded event and the time its rollout line was written.
+struct TimedEvent {
+    at: SystemTime,
+    event: RecordedEvent,
+}
+
+pub(crate) async fn run_inspect(config: &Config, args: &InspectArgs) -> anyhow::Result<()> {
+    let path = resolve_inspect_path(config, &args.session).await?;
+    let text = tokio::fs::read_to_string(&path)
+        .await
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let events = recorded_events(&text);
+    let Some(turn) = args
+        .turn
+        .clone()
+        .or_else(|| events.last().map(|timed| timed.event.id.clone()))
+    else {
+        anyhow::bail!("{} has no recorded events", path.display());
+    };
+
+    let replay = replay_until(&events, &turn, args.at_seq)?;
+    let snapshot = replay.snapshot();
+    eprintln!(
+        "[inspect] {} history records after turn {turn} seq {} ({} events replayed, {} without a history record)",
+        snapshot.records.len(),
+        args.at_seq,
+        replay.replayed,
+        replay.unmodeled,
+    );
+    let json = serde_json::to_string_pretty(&snapshot)?;
+    match &args.output {
+        Some(output) => std::fs::write(output, format!("{json}\n"))
+            .with_context(|| format!("failed to write {}", output.display()))?,
+        None => println!("{json}"),
+    }
+    Ok(())
+}
+
+async fn resolve_inspect_path(config: &Config, session: &str) -> anyhow::Result<PathBuf> {
+    let path = PathBuf::from(session);
+    if path.is_file() {
+        return Ok(path);
+    }
+    resolve_session_path(config, Some(session), false, false)
+        .await?
+        .with_context(|| format!("no rollout file or session found for `{session}`"))
+}
+
+fn recorded_events(text: &str) -> Vec<TimedEvent> {
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str::<RolloutLine>(line).ok())
+        .filter_map(|line| {
+            let RolloutItem::Event(event) = line.item else {
+                return None;
+            };
+            let at = chrono::DateTime::parse_from_rfc3339(&line.timestamp)
+                .map_or(SystemTime::UNIX_EPOCH, SystemTime::from);
+            recorded_event_from_protocol(event).map(|event| TimedEvent { at, event })
+        })
+        .collect()
+}
+
+fn replay_until(events: &[TimedEvent], turn: &str, at_seq: u64) -> anyhow::Result<Replay> {
+    let in_turn = |timed: &&TimedEvent| timed.event.id == turn;
+    let Some(stop) = events
+        .iter()
+        .position(|timed| timed.event.id == turn && timed.event.event_seq == at_seq)
+    else {
+        match events.iter().filter(in_turn).map(|timed| timed.event.event_seq).max() {
+            Some(last) => {
+                anyhow::bail!("turn {turn} has no event with seq {at_seq} (its last seq is {last})")
+            }
+            None => anyhow::bail!("no recorded events for turn {turn}"),
+        }
+    };
+
+    let mut replay = Replay::default();
+    for timed in &events[..=stop] {
+        replay.apply(timed);
+    }
+    Ok(replay)
+}
+
+#[derive(Default)]
+struct Replay {
+    state: HistoryState,
+    /// Order key and `sub_id#seq` of the event that created each record.
+    origins: HashMap<HistoryId, (OrderKeySnapshot, String)>,
+    /// Bytes already streamed per exec call, as (stdout, stderr).
+    exec_offsets: HashMap<String, (usize, usize)>,
+    patches: HashMap<String, HistoryId>,
+    last_request: u64,
+    replayed: usize,
+    unmodeled: usize,
+}
+
+impl Replay {
+    fn apply(&mut self, timed: &TimedEvent) {
+        let TimedEvent { at, event } = timed;
+        self.replayed += 1;
+        // Events without model ordering stay with the current request, after
+        // its outputs.
+        let key = match &event.order {
+            Some(order) => {
+                self.last_request = order.request_ordinal;
+                OrderKeySnapshot {
+                    req: order.request_ordinal,
+                    out: order.output_index.map_or(0, |index| index as i32),
+                    seq: order.sequence_number.unwrap_or(0),
+                }
+            }
+            None => OrderKeySnapshot {
+                req: self.last_request,
+                out: i32::MAX,
+                seq: event.event_seq,
+            },
+        };
+
+        let record = match &event.msg {
+            EventMsg::UserMessage(user) => Some(plain_message(
+                PlainMessageRole::User,
+                PlainMessageKind::User,
+                &user.message,
+            )),
+            EventMsg::AgentMessage(message) => {
+                Some(HistoryRecord::AssistantMessage(AssistantMessageState {
+                    id: HistoryId::ZERO,
+                    stream_id: None,
+                    markdown: message.message.clone(),
+                    citations: Vec::new(),
+                    metadata: None,
+                    token_usage: None,
+                    mid_turn: false,
+                    created_at: *at,
+                }))
+            }
+            EventMsg::AgentReasoning(reasoning) => Some(reasoning_record(&reasoning.text)),
+            EventMsg::Error(error) => Some(plain_message(
+                PlainMessageRole::Error,
+                PlainMessageKind::Error,
+                &error.message,
+            )),
+            EventMsg::BackgroundEvent(background) => {
+                Some(HistoryRecord::BackgroundEvent(BackgroundEventRecord {
+                    id: HistoryId::ZERO,
+                    title: String::new(),
+                    description: background.message.clone(),
+                }))
+            }
+            EventMsg::PatchApplyBegin(patch) => {
+                let record = HistoryRecord::Patch(PatchRecord {
+                    id: HistoryId::ZERO,
+                    patch_type: PatchEventType::ApplyBegin {
+                        auto_approved: patch.auto_approved,
+                    },
+                    changes: patch.changes.clone(),
+                    failure: None,
+                });
+                if let Some(id) = self.insert(record, key, event) {
+                    self.patches.insert(patch.call_id.clone(), id);
+                }
+                return;
+            }
+            EventMsg::PatchApplyEnd(end) => {
+                if let Some(id) = self.patches.remove(&end.call_id)
+                    && let Some(HistoryRecord::Patch(patch)) = self.state.record_mut(id)
+                {
+                    if end.success {
+                        patch.patch_type = PatchEventType::ApplySuccess;
+                    } else {
+                        patch.patch_type = PatchEventType::ApplyFailure;
+                        patch.failure = Some(PatchFailureMetadata {
+                            message: "apply_patch failed".to_owned(),
+                            stdout_excerpt: Some(end.stdout.clone()),
+                            stderr_excerpt: Some(end.stderr.clone()),
+                        });
+                    }
+                    return;
+                }
+                None
+            }
+            EventMsg::ExecCommandBegin(begin) => {
+                let mutation = self.state.apply_domain_event(HistoryDomainEvent::StartExec {
+                    index: self.state.records.len(),
+                    call_id: Some(begin.call_id.clone()),
+                    command: begin.command.clone(),
+                    parsed: begin.parsed_cmd.clone(),
+                    action: exec_action(&begin.parsed_cmd),
+                    started_at: *at,
+                    working_dir: Some(begin.cwd.clone()),
+                    env: Vec::new(),
+                    tags: Vec::new(),
+                });
+                self.exec_offsets.insert(begin.call_id.clone(), (0, 0));
+                self.record_origin(mutation, key, event);
+                return;
+            }
+            EventMsg::ExecCommandOutputDelta(delta) => {
+                let index = self
+                    .state
+                    .history_id_for_exec_call(&delta.call_id)
+                    .and_then(|id| self.state.index_of(id));
+                let (Some(index), Some(offsets)) = (index, self.exec_offsets.get_mut(&delta.call_id))
+                else {
+                    self.unmodeled += 1;
+                    return;
+                };
+                let content: Arc<str> = Arc::from(String::from_utf8_lossy(&delta.chunk).as_ref());
+                let (stdout_chunk, stderr_chunk) = match delta.stream {
+                    ExecOutputStream::Stdout => {
+                        let chunk = ExecStreamChunk { offset: offsets.0, content };
+                        offsets.0 += chunk.content.len();
+                        (Some(chunk), None)
+                    }
+                    ExecOutputStream::Stderr => {
+                        let chunk = ExecStreamChunk { offset: offsets.1, content };
+                        offsets.1 += chunk.content.len();
+                        (None, Some(chunk))
+                    }
+                };
+                self.state.apply_domain_event(HistoryDomainEvent::UpdateExecStream {
+                    index,
+                    stdout_chunk,
+                    stderr_chunk,
+                });
+                return;
+            }
+            EventMsg::ExecCommandEnd(end) => {
+                // Output that was not streamed arrives only with the end event.
+                let (streamed_stdout, streamed_stderr) =
+                    self.exec_offsets.remove(&end.call_id).unwrap_or_default();
+                self.state.apply_domain_event(HistoryDomainEvent::FinishExec {
+                    id: None,
+                    call_id: Some(end.call_id.clone()),
+                    status: if end.exit_code == 0 {
+                        ExecStatus::Success
+                    } else {
+                        ExecStatus::Error
+                    },
+                    exit_code: Some(end.exit_code),
+                    completed_at: Some(*at),
+                    stdout_tail: (streamed_stdout == 0).then(|| end.stdout.clone()),
+                    stderr_tail: (streamed_stderr == 0).then(|| end.stderr.clone()),
+                    wait_total: None,
+                    wait_active: false,
+                    wait_notes: Vec::new(),
+                });
+                return;
+            }
+            _ => None,
+        };
+        match record {
+            Some(record) => {
+                self.insert(record, key, event);
+            }
+            None => self.unmodeled += 1,
+        }
+    }
+
+    fn insert(
+        &mut self,
+        record: HistoryRecord,
+        key: OrderKeySnapshot,
+        event: &RecordedEvent,
+    ) -> Option<HistoryId> {
+        let index = self.state.records.len();
+        let mutation = self
+            .state
+            .apply_domain_event(HistoryDomainEvent::Insert { index, record: record.into() });
+        self.record_origin(mutation, key, event)
+    }
+
+    fn record_origin(
+        &mut self,
+        mutation: HistoryMutation,
+        key: OrderKeySnapshot,
+        event: &RecordedEvent,
+    ) -> Option<HistoryId> {
+        let HistoryMutation::Inserted { id, .. } = mutation else {
+            return None;
+        };
+        self.origins
+            .insert(id, (key, format!("{}#{}", event.id, event.event_seq)));
+        Some(id)
+    }
+
+    fn snapshot(&self) -> HistorySnapshot {
+        let (order, order_debug) = self
+            .state
+            .records
+            .iter()
+            .map(|record| match self.origins.get(&record.id()) {
+                Some((key, origin)) => (*key, Some(origin.clone())),
+                None => (
+                    OrderKeySnapshot {
+                        req: 0,
+                        out: 0,
+                        seq: 0,
+                    },
+                    None,
+                ),
+            })
+            .unzip();
+        self.state.snapshot().with_order(order, order_debug)
+    }
+}
+
+/// Mirrors the TUI: the first read, search or listing decides the action.
+fn exec_action(parsed: &[ParsedCommand]) -> ExecAction {
+    parsed
+        .iter()
+        .find_map(|command| match command {
+            ParsedCommand::Read { .. } => Some(ExecAction::Read),
+            ParsedCommand::Search { .. } => Some(ExecAction::Search),
+            ParsedCommand::ListFiles { .. } => Some(ExecAction::List),
+            _ => None,
+        })
+        .unwrap_or(ExecAction::Run)
+}
+
+fn span(text: &str) -> InlineSpan {
+    InlineSpan {
+        text: text.to_owned(),
+        tone: TextTone::Default,
+        emphasis: TextEmphasis::default(),
+        entity: None,
+    }
+}
+
+fn plain_message(role: PlainMessageRole, kind: PlainMessageKind, text: &str) -> HistoryRecord {
+    let lines = text
+        .lines()
+        .map(|line| MessageLine {
+            kind: if line.trim().is_empty() {
+                MessageLineKind::Blank
+            } else {
+                MessageLineKind::Paragraph
+            },
+            spans: vec![span(line)],
+        })
+        .collect();
+    HistoryRecord::PlainMessage(PlainMessageState {
+        id: HistoryId::ZERO,
+        role,
+        kind,
+        header: None,
+        lines,
+        metadata: None,
+    })
+}
+
+fn reasoning_record(text: &str) -> HistoryRecord {
+    let blocks: Vec<ReasoningBlock> = text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| ReasoningBlock::Paragraph(vec![span(line)]))
+        .collect();
+    let summary = blocks.first().and_then(|block| match block {
+        ReasoningBlock::Paragraph(spans) => Some(spans.clone()),
+        _ => None,
+    });
+    HistoryRecord::Reasoning(ReasoningState {
+        id: HistoryId::ZERO,
+        sections: vec![ReasoningSection {
+            heading: None,
+            summary,
+            blocks,
+        }],
+        effort: None,
+        in_progress: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use code_core::protocol::AgentMessageEvent;
+    use code_core::protocol::ExecCommandBeginEvent;
+    use code_core::protocol::ExecCommandEndEvent;
+    use code_core::protocol::ExecCommandOutputDeltaEvent;
+    use code_protocol::protocol::UserMessageEvent;
+    use std::time::Duration;
+
+    fn timed(id: &str, event_seq: u64, msg: EventMsg) -> TimedEvent {
+        TimedEvent {
+            at: SystemTime::UNIX_EPOCH,
+            event: RecordedEvent {
+                id: id.to_owned(),
+                event_seq,
+                order: None,
+                msg,
+            },
+        }
+    }
+
+    fn session() -> Vec<TimedEvent> {
+        vec![
+            timed(
+                "1",
+                0,
+                EventMsg::UserMessage(UserMessageEvent {
+                    message: "run the tests".to_owned(),
+                    images: None,
+                    local_images: Vec::new(),
+                    text_elements: Vec::new(),
+                }),
+            ),
+            timed(
+                "1",
+                1,
+                EventMsg::ExecCommandBegin(ExecCommandBeginEvent {
+                    call_id: "call-1".to_owned(),
+                    command: vec!["cargo".to_owned(), "test".to_owned()],
+                    cwd: PathBuf::from("/repo"),
+                    parsed_cmd: Vec::new(),
+                    parent_call_id: None,
+                }),
+            ),
+            timed(
+                "1",
+                2,
+                EventMsg::ExecCommandOutputDelta(ExecCommandOutputDeltaEvent {
+                    call_id: "call-1".to_owned(),
+                    stream: ExecOutputStream::Stdout,
+                    chunk: b"running 3 tests\n".to_vec().into(),
+                }),
+            ),
+            timed(
+                "1",
+                3,
+                EventMsg::ExecCommandEnd(ExecCommandEndEvent {
+                    call_id: "call-1".to_owned(),
+                    stdout: "running 3 tests\n".to_owned(),
+                    stderr: String::new(),
+                    exit_code: 0,
+                    duration: Duration::from_secs(1),
+                }),
+            ),
+            timed(
+                "2",
+                0,
+                EventMsg::AgentMessage(AgentMessageEvent {
+                    message: "All tests pass.".to_owned(),
+                }),
+            ),
+        ]
+    }
+
+    #[test]
+    fn replay_stops_at_requested_event() {
+        let events = session();
+        let snapshot = replay_until(&events, "1", 2).expect("replay").snapshot();
+        assert_eq!(snapshot.records.len(), 2);
+        let HistoryRecord::Exec(exec) = &snapshot.records[1] else {
+            panic!("expected exec record, got {:?}", snapshot.records[1]);
+        };
+        assert_eq!(exec.status, ExecStatus::Running);
+        assert_eq!(exec.stdout_chunks.len(), 1);
+        assert_eq!(snapshot.order_debug, vec![Some("1#0".to_owned()), Some("1#1".to_owned())]);
+
+        let snapshot = replay_until(&events, "2", 0).expect("replay").snapshot();
+        assert_eq!(snapshot.records.len(), 3);
+        let HistoryRecord::Exec(exec) = &snapshot.records[1] else {
+            panic!("expected exec record");
+        };
+        assert_eq!(exec.status, ExecStatus::Success);
+        // The streamed output is not duplicated by the end event.
+        assert_eq!(exec.stdout_chunks.len(), 1);
+
+        let err = replay_until(&events, "1", 9).err().expect("missing seq");
+        assert_eq!(err.to_string(), "turn 1 has no event with seq 9 (its last seq is 3)");
+    }
+}
@@ -1,6 +1,7 @@
 use anyhow::Context;
 use code_core::config::Config;
 use code_core::SessionCatalog;
+use code_core::SessionIndexEntry;
 use code_core::SessionQuery;
 use code_core::entry_to_rollout_path;
 use code_protocol::protocol::SessionSource;
@@ -10,21 +11,51 @@ pub(crate) async fn resolve_resume_path(
     config: &Config,
     args: &crate::cli::ResumeArgs,
 ) -> anyhow::Result<Option<PathBuf>> {
-    if !args.last && args.session_id.is_none() {
+    resolve_session_path(config, args.session_id.as_deref(), args.last, args.all).await
+}
+
+/// The catalog entry of the session `args` selects, for `resume --fork`.
+pub(crate) async fn resolve_resume_entry(
+    config: &Config,
+    args: &crate::cli::ResumeArgs,
+) -> anyhow::Result<Option<SessionIndexEntry>> {
+    resolve_session_entry(config, args.session_id.as_deref(), args.last, args.all).await
+}
+
+/// Finds the rollout file for `session_id`, or for the most recent session
+/// when `last` is set (limited to the current directory unless `all`).
+pub(crate) async fn resolve_session_path(
+    config: &Config,
+    session_id: Option<&str>,
+    last: bool,
+    all: bool,
+) -> anyhow::Result<Option<PathBuf>> {
+    Ok(resolve_session_entry(config, session_id, last, all)
+        .await?
+        .map(|entry| entry_to_rollout_path(&config.code_home, &entry)))
+}
+
+async fn resolve_session_entry(
+    config: &Config,
+    session_id: Option<&str>,
+    last: bool,
+    all: bool,
+) -> anyhow::Result<Option<SessionIndexEntry>> {
+    if !last && session_id.is_none() {
         return Ok(None);
     }
 
     let catalog = SessionCatalog::new(config.code_home.clone());
 
-    if let Some(id_str) = args.session_id.as_deref() {
+    if let Some(id_str) = session_id {
         let entry = catalog
             .find_by_id(id_str)
             .await
             .context("failed to look up session by id")?;
-        Ok(entry.map(|entry| entry_to_rollout_path(&config.code_home, &entry)))
-    } else if args.last {
+        Ok(entry)
+    } else if last {
         let query = SessionQuery {
-            cwd: (!args.all).then(|| config.cwd.clone()),
+            cwd: (!all).then(|| config.cwd.clone()),
             git_root: None,
             sources: vec![SessionSource::Cli, SessionSource::VSCode, SessionSource::Exec],
             min_user_messages: 1,
@@ -36,7 +67,7 @@ pub(crate) async fn resolve_resume_path(
             .get_latest(&query)
             .await
             .context("failed to get latest session from catalog")?;
-        Ok(entry.map(|entry| entry_to_rollout_path(&config.code_home, &entry)))
+        Ok(entry)
     } else {
         Ok(None)
     }
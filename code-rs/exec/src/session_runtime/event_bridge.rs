@@ -5,10 +5,14 @@ use code_core::protocol::Op;
 use std::sync::Arc;
 use tokio::sync::mpsc::UnboundedReceiver;
 use tokio::sync::mpsc::UnboundedSender;
+use tokio::time::Instant;
 use tracing::debug;
 use tracing::error;
 use tracing::info;
 
+use crate::termination;
+use crate::termination::TerminationSignals;
+
 pub(super) fn start_event_stream(conversation: Arc<CodexConversation>) -> UnboundedReceiver<Event> {
     let (tx, rx) = tokio::sync::mpsc::unbounded_channel::<Event>();
     spawn_event_bridge(conversation, tx);
@@ -17,128 +21,61 @@ pub(super) fn start_event_stream(conversation: Arc<CodexConversation>) -> Unboun
 
 fn spawn_event_bridge(conversation: Arc<CodexConversation>, tx: UnboundedSender<Event>) {
     tokio::spawn(async move {
-        #[cfg(unix)]
-        let mut sigterm_stream =
-            match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
-                Ok(stream) => Some(stream),
-                Err(err) => {
-                    tracing::warn!("failed to install SIGTERM handler: {err}");
-                    None
-                }
-            };
-        #[cfg(unix)]
-        let mut sigterm_requested = false;
+        let mut signals = TerminationSignals::install();
+        // Set once a termination signal has asked core to shut down; events
+        // keep flowing until `ShutdownComplete` or this deadline.
+        let mut shutdown_deadline: Option<Instant> = None;
 
         loop {
-            #[cfg(unix)]
-            {
-                if let Some(stream) = sigterm_stream.as_mut() {
-                    tokio::select! {
-                        _ = stream.recv() => {
-                            tracing::debug!("SIGTERM received; requesting shutdown");
-                            if let Err(e) = conversation.submit(Op::Shutdown).await {
-                                tracing::warn!("Failed to submit shutdown: {e}");
-                            }
-                            sigterm_requested = true;
-                            break;
-                        }
-                        _ = tokio::signal::ctrl_c() => {
-                            tracing::debug!("Keyboard interrupt");
-                            if let Err(e) = conversation.submit(Op::Interrupt).await {
-                                tracing::warn!("Failed to submit interrupt: {e}");
-                            }
-                            break;
-                        }
-                        res = conversation.next_event() => match res {
-                            Ok(event) => {
-                                debug!("Received event: {event:?}");
-
-                                let is_shutdown_complete = matches!(event.msg, EventMsg::ShutdownComplete);
-                                if let Err(err) = tx.send(event) {
-                                    error!("Error sending event: {err:?}");
-                                    break;
-                                }
-                                if is_shutdown_complete {
-                                    info!("Received shutdown event, exiting event loop.");
-                                    break;
-                                }
-                            },
-                            Err(err) => {
-                                error!("Error receiving event: {err:?}");
-                                break;
-                            }
-                        }
+            tokio::select! {
+                signal = signals.recv(), if shutdown_deadline.is_none() => {
+                    info!("termination signal {signal} received; shutting down gracefully");
+                    termination::record(signal);
+                    if let Err(e) = conversation.submit(Op::Shutdown).await {
+                        tracing::warn!("Failed to submit shutdown: {e}");
+                        break;
                     }
-                } else {
-                    tokio::select! {
-                        _ = tokio::signal::ctrl_c() => {
-                            tracing::debug!("Keyboard interrupt");
-                            if let Err(e) = conversation.submit(Op::Interrupt).await {
-                                tracing::warn!("Failed to submit interrupt: {e}");
-                            }
-                            break;
-                        }
-                        res = conversation.next_event() => match res {
-                            Ok(event) => {
-                                debug!("Received event: {event:?}");
-
-                                let is_shutdown_complete = matches!(event.msg, EventMsg::ShutdownComplete);
-                                if let Err(err) = tx.send(event) {
-                                    error!("Error sending event: {err:?}");
-                                    break;
-                                }
-                                if is_shutdown_complete {
-                                    info!("Received shutdown event, exiting event loop.");
-                                    break;
-                                }
-                            },
-                            Err(err) => {
-                                error!("Error receiving event: {err:?}");
-                                break;
-                            }
-                        }
+                    shutdown_deadline = Some(Instant::now() + termination::SHUTDOWN_GRACE);
+                }
+                _ = async {
+                    match shutdown_deadline {
+                        Some(deadline) => tokio::time::sleep_until(deadline).await,
+                        None => std::future::pending::<()>().await,
                     }
+                } => {
+                    tracing::warn!(
+                        "shutdown did not complete within {:?}; exiting",
+                        termination::SHUTDOWN_GRACE
+                    );
+                    break;
                 }
-            }
-            #[cfg(not(unix))]
-            {
-                tokio::select! {
-                    _ = tokio::signal::ctrl_c() => {
-                        tracing::debug!("Keyboard interrupt");
-                        if let Err(e) = conversation.submit(Op::Interrupt).await {
-                            tracing::warn!("Failed to submit interrupt: {e}");
-                        }
-                        break;
+                _ = tokio::signal::ctrl_c() => {
+                    tracing::debug!("Keyboard interrupt");
+                    if let Err(e) = conversation.submit(Op::Interrupt).await {
+                        tracing::warn!("Failed to submit interrupt: {e}");
                     }
-                    res = conversation.next_event() => match res {
-                        Ok(event) => {
-                            debug!("Received event: {event:?}");
+                    break;
+                }
+                res = conversation.next_event() => match res {
+                    Ok(event) => {
+                        debug!("Received event: {event:?}");
 
-                            let is_shutdown_complete = matches!(event.msg, EventMsg::ShutdownComplete);
-                            if let Err(err) = tx.send(event) {
-                                error!("Error sending event: {err:?}");
-                                break;
-                            }
-                            if is_shutdown_complete {
-                                info!("Received shutdown event, exiting event loop.");
-                                break;
-                            }
-                        },
-                        Err(err) => {
-                            error!("Error receiving event: {err:?}");
+                        let is_shutdown_complete = matches!(event.msg, EventMsg::ShutdownComplete);
+                        if let Err(err) = tx.send(event) {
+                            error!("Error sending event: {err:?}");
                             break;
                         }
+                        if is_shutdown_complete {
+                            info!("Received shutdown event, exiting event loop.");
+                            break;
+                        }
+                    },
+                    Err(err) => {
+                        error!("Error receiving event: {err:?}");
+                        break;
                     }
                 }
             }
         }
-        #[cfg(unix)]
-        drop(sigterm_stream);
-        #[cfg(unix)]
-        if sigterm_requested {
-            unsafe {
-                libc::raise(libc::SIGTERM);
-            }
-        }
     });
 }
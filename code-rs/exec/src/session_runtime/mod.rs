@@ -3,10 +3,12 @@ mod review_event_loop;
 mod review_submission;
 mod review_runtime;
 mod state;
+mod watch;
 
 use code_auto_drive_core::AutoResolveState;
 use code_core::CodexConversation;
 use code_core::config::Config;
+use code_core::protocol::ReviewFinding;
 use code_core::protocol::ReviewOutputEvent;
 use code_core::protocol::ReviewRequest;
 use code_core::protocol::ReviewSnapshotInfo;
@@ -21,18 +23,34 @@ pub(crate) struct SessionRuntimeParams<'a> {
     pub(crate) review_request: Option<ReviewRequest>,
     pub(crate) prompt_to_send: String,
     pub(crate) images: Vec<PathBuf>,
+    /// `--output-schema`, checked against each prompt run's final message.
+    pub(crate) output_schema: Option<&'a crate::output_schema::OutputSchema>,
     pub(crate) run_deadline: Option<Instant>,
     pub(crate) max_seconds: Option<u64>,
+    /// `--max-tokens` / `--max-cost-usd`, shared by every run of the session.
+    pub(crate) budget: crate::run_budget::RunBudget,
     pub(crate) auto_resolve_state: Option<AutoResolveState>,
     pub(crate) max_auto_resolve_attempts: u32,
     pub(crate) is_auto_review: bool,
+    /// Paths from `--watch`; when non-empty the prompt re-runs on every change.
+    pub(crate) watch_paths: Vec<PathBuf>,
+    /// `--review-async`: review uncommitted changes in parallel and reconcile at the end.
+    pub(crate) review_async: bool,
+    /// `--handoff`: ask for a hand-off summary once the run is done.
+    pub(crate) handoff: bool,
 }
 
 pub(crate) struct SessionRuntimeOutcome {
     pub(crate) review_outputs: Vec<ReviewOutputEvent>,
     pub(crate) final_review_snapshot: Option<ReviewSnapshotInfo>,
     pub(crate) review_runs: u32,
-    pub(crate) error_seen: bool,
+    pub(crate) acknowledged_findings: Vec<ReviewFinding>,
+    pub(crate) failures: crate::exit_code::RunFailures,
+    pub(crate) budget: crate::run_budget::BudgetTracker,
+    /// Last final message that matched `--output-schema`, parsed.
+    pub(crate) structured_output: Option<serde_json::Value>,
+    /// Final message of the `--handoff` turn.
+    pub(crate) handoff_summary: Option<String>,
 }
 
 pub(crate) use review_runtime::run_session_runtime;
@@ -2,18 +2,26 @@ mod exited_review;
 mod helpers;
 mod task_complete;
 
+use super::state::HandoffState;
 use super::state::ReviewRuntimeState;
 use crate::auto_review_status::AutoReviewTracker;
 use crate::auto_review_status::emit_auto_review_completion;
 use crate::auto_runtime::request_shutdown;
 use crate::event_processor::CodexStatus;
 use crate::event_processor::EventProcessor;
+use crate::exit_code::RunFailures;
+use crate::output_schema::OutputSchema;
+use crate::output_schema::correction_prompt;
+use crate::review_async::reconciliation_prompt;
+use crate::run_budget::BudgetTracker;
 use code_core::CodexConversation;
 use code_core::config::Config;
 use code_core::protocol::Event;
 use code_core::protocol::EventMsg;
+use code_core::protocol::InputItem;
 use code_core::protocol::Op;
 use code_core::protocol::ReviewRequest;
+use code_core::session_handoff::HANDOFF_PROMPT;
 use std::sync::Arc;
 use tokio::sync::mpsc::UnboundedReceiver;
 use tokio::time::Instant;
@@ -26,10 +34,20 @@ pub(super) struct ReviewEventLoopParams<'a> {
     pub(super) config: &'a Config,
     pub(super) event_processor: &'a mut dyn EventProcessor,
     pub(super) review_request: Option<&'a ReviewRequest>,
+    pub(super) output_schema: Option<&'a OutputSchema>,
     pub(super) run_deadline: Option<Instant>,
     pub(super) max_seconds: Option<u64>,
+    pub(super) budget: &'a mut BudgetTracker,
     pub(super) rx: &'a mut UnboundedReceiver<Event>,
     pub(super) state: &'a mut ReviewRuntimeState,
+    /// Watch mode: a finished or interrupted turn ends the run instead of the session.
+    pub(super) watch: bool,
+}
+
+pub(super) struct ReviewEventLoopOutcome {
+    pub(super) failures: RunFailures,
+    /// False when only the current watch-mode run ended and the session can take another turn.
+    pub(super) session_ended: bool,
 }
 
 pub(super) enum LoopControl {
@@ -78,6 +96,12 @@ impl ShutdownState {
         self.pending
     }
 
+    /// Drops a pending shutdown because another turn is starting.
+    fn cancel(&mut self) {
+        self.pending = false;
+        self.deadline = None;
+    }
+
     fn should_poll_deadline(&self) -> bool {
         self.pending && self.deadline.is_some() && self.grace_enabled
     }
@@ -89,23 +113,31 @@ impl ShutdownState {
 
 pub(super) async fn run_review_event_loop(
     params: ReviewEventLoopParams<'_>,
-) -> anyhow::Result<bool> {
+) -> anyhow::Result<ReviewEventLoopOutcome> {
     let ReviewEventLoopParams {
         conversation,
         config,
         event_processor,
         review_request,
+        output_schema,
         run_deadline,
         max_seconds,
+        budget,
         rx,
         state,
+        watch,
     } = params;
+    let mut run_deadline = run_deadline;
 
-    // Track whether a fatal error was reported by the server so we can
-    // exit with a non-zero status for automation-friendly signaling.
-    let mut error_seen = false;
+    // Track which failures the server reported so the exit code can tell
+    // automation what went wrong.
+    let mut failures = RunFailures::default();
     let mut shutdown_state = ShutdownState::new(config.tui.auto_review_enabled);
     let mut auto_review_tracker = AutoReviewTracker::new(&config.cwd);
+    if let Some(review) = state.async_review.as_ref() {
+        auto_review_tracker.track(&review.agent_id);
+    }
+    let mut schema_retries = 0;
 
     loop {
         tokio::select! {
@@ -120,8 +152,13 @@ pub(super) async fn run_review_event_loop(
                     "Time budget exceeded (--max-seconds={})",
                     max_seconds.unwrap_or_default()
                 );
-                error_seen = true;
+                failures.deadline_exceeded = true;
                 let _ = conversation.submit(Op::Interrupt).await;
+                if watch {
+                    // Keep the session for the next change; the run ends on `TurnAborted`.
+                    run_deadline = None;
+                    continue;
+                }
                 let _ = conversation.submit(Op::Shutdown).await;
                 break;
             }
@@ -133,10 +170,91 @@ pub(super) async fn run_review_event_loop(
                     let completions = auto_review_tracker.update(status);
                     for completion in completions {
                         emit_auto_review_completion(&completion);
+                        if let Some(review) = state
+                            .async_review
+                            .take_if(|review| review.agent_id == completion.agent_id())
+                        {
+                            state.pending_reconciliation = completion.findings_summary().map(|summary| {
+                                reconciliation_prompt(&review, summary, completion.worktree_path())
+                            });
+                        }
+                    }
+                }
+                if state.handoff == HandoffState::Running {
+                    // The hand-off turn is not part of the run's output; only
+                    // its final message is kept.
+                    let _ = budget.observe(&event.msg);
+                    match &event.msg {
+                        EventMsg::TaskComplete(done) => {
+                            state.handoff = HandoffState::Done(done.last_agent_message.clone());
+                            shutdown_state
+                                .request(conversation, &auto_review_tracker)
+                                .await?;
+                        }
+                        EventMsg::TurnAborted(_) => {
+                            state.handoff = HandoffState::Done(None);
+                            shutdown_state
+                                .request(conversation, &auto_review_tracker)
+                                .await?;
+                        }
+                        EventMsg::ShutdownComplete => {
+                            let _ = event_processor.process_event(event);
+                            break;
+                        }
+                        _ => {}
+                    }
+                    continue;
+                }
+                failures.observe(&event.msg);
+                if let Some(limit) = budget.observe(&event.msg) {
+                    eprintln!("{}", budget.describe(limit));
+                    failures.budget_exceeded = true;
+                    let _ = event_processor.process_event(event);
+                    // The budget is cumulative, so `--watch` stops here too.
+                    let _ = conversation.submit(Op::Interrupt).await;
+                    let _ = conversation.submit(Op::Shutdown).await;
+                    break;
+                }
+                if review_request.is_none()
+                    && let Some(schema) = output_schema
+                    && let EventMsg::TaskComplete(task_complete) = &event.msg
+                {
+                    match schema.validate(task_complete.last_agent_message.as_deref()) {
+                        Ok(document) => state.structured_output = Some(document),
+                        Err(errors) => {
+                            state.structured_output = None;
+                            if schema_retries < schema.max_retries() {
+                                schema_retries += 1;
+                                eprintln!(
+                                    "Final message does not match --output-schema; requesting a correction ({schema_retries}/{}).",
+                                    schema.max_retries()
+                                );
+                                let _ = event_processor.process_event(event);
+                                conversation
+                                    .submit(Op::UserInput {
+                                        items: vec![InputItem::Text {
+                                            text: correction_prompt(&errors),
+                                        }],
+                                        final_output_json_schema: Some(schema.schema().clone()),
+                                    })
+                                    .await?;
+                                event_processor.turn_submitted();
+                                continue;
+                            }
+                            eprintln!("Final message does not match --output-schema:");
+                            for error in &errors {
+                                eprintln!("- {error}");
+                            }
+                            failures.schema_invalid = true;
+                        }
                     }
                 }
-                if matches!(event.msg, EventMsg::Error(_)) {
-                    error_seen = true;
+                if watch && matches!(event.msg, EventMsg::TaskComplete(_) | EventMsg::TurnAborted(_)) {
+                    let _ = event_processor.process_event(event);
+                    return Ok(ReviewEventLoopOutcome {
+                        failures,
+                        session_ended: false,
+                    });
                 }
 
                 let loop_control = match &event.msg {
@@ -166,9 +284,17 @@ pub(super) async fn run_review_event_loop(
                 match shutdown {
                     CodexStatus::Running => {}
                     CodexStatus::InitiateShutdown => {
-                        shutdown_state
-                            .request(conversation, &auto_review_tracker)
-                            .await?;
+                        if let Some(prompt) = state.pending_reconciliation.take() {
+                            submit_reconciliation(conversation, &mut *event_processor, prompt)
+                                .await?;
+                        } else if state.handoff == HandoffState::Pending {
+                            state.handoff = HandoffState::Running;
+                            submit_handoff(conversation).await?;
+                        } else {
+                            shutdown_state
+                                .request(conversation, &auto_review_tracker)
+                                .await?;
+                        }
                     }
                     CodexStatus::Shutdown => {
                         break;
@@ -176,9 +302,16 @@ pub(super) async fn run_review_event_loop(
                 }
 
                 if shutdown_state.is_pending() {
-                    shutdown_state
-                        .request(conversation, &auto_review_tracker)
-                        .await?;
+                    // The prompt finished first and the review just reported.
+                    if let Some(prompt) = state.pending_reconciliation.take() {
+                        shutdown_state.cancel();
+                        submit_reconciliation(conversation, &mut *event_processor, prompt)
+                            .await?;
+                    } else {
+                        shutdown_state
+                            .request(conversation, &auto_review_tracker)
+                            .await?;
+                    }
                 }
             }
             _ = tokio::time::sleep_until(shutdown_state.deadline_or_now()),
@@ -191,5 +324,37 @@ pub(super) async fn run_review_event_loop(
         }
     }
 
-    Ok(error_seen)
+    Ok(ReviewEventLoopOutcome {
+        failures,
+        session_ended: true,
+    })
+}
+
+async fn submit_handoff(conversation: &Arc<CodexConversation>) -> anyhow::Result<()> {
+    eprintln!("[handoff] writing the hand-off summary.");
+    conversation
+        .submit(Op::UserInput {
+            items: vec![InputItem::Text {
+                text: HANDOFF_PROMPT.to_owned(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await?;
+    Ok(())
+}
+
+async fn submit_reconciliation(
+    conversation: &Arc<CodexConversation>,
+    event_processor: &mut dyn EventProcessor,
+    prompt: String,
+) -> anyhow::Result<()> {
+    eprintln!("[review-async] applying the parallel review's findings and re-running validation.");
+    conversation
+        .submit(Op::UserInput {
+            items: vec![InputItem::Text { text: prompt }],
+            final_output_json_schema: None,
+        })
+        .await?;
+    event_processor.turn_submitted();
+    Ok(())
 }
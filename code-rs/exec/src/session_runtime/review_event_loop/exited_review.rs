@@ -2,6 +2,8 @@ use super::super::state::ReviewRuntimeState;
 use super::LoopControl;
 use crate::review_output::review_summary_line;
 use code_auto_drive_core::AutoResolvePhase;
+use code_auto_drive_core::extend_acknowledged;
+use code_auto_drive_core::select_findings_for_fix;
 use code_core::config::Config;
 use code_core::git_info::current_branch_name;
 use code_core::protocol::ExitedReviewModeEvent;
@@ -99,9 +101,27 @@ pub(super) async fn handle_exited_review_mode_event(
                 state.auto_resolve_base_snapshot = None;
             }
             Some(output) => {
-                resolve_state.phase = AutoResolvePhase::PendingFix {
-                    review: output.clone(),
-                };
+                // `pick` has nobody to ask here, so it falls back to the threshold.
+                let (to_fix, acknowledged) = select_findings_for_fix(output, &config.auto_drive);
+                let skipped = acknowledged.len();
+                extend_acknowledged(&mut state.acknowledged_findings, acknowledged);
+                if to_fix.findings.is_empty() {
+                    eprintln!(
+                        "Auto-resolve: no findings meet the fix threshold ({skipped} acknowledged). Exiting."
+                    );
+                    state.auto_resolve_state = None;
+                    state.auto_resolve_base_snapshot = None;
+                } else {
+                    if skipped > 0 {
+                        eprintln!(
+                            "Auto-resolve: fixing {} of {} findings; {skipped} acknowledged but not fixed.",
+                            to_fix.findings.len(),
+                            output.findings.len()
+                        );
+                    }
+                    resolve_state.last_review = Some(to_fix.clone());
+                    resolve_state.phase = AutoResolvePhase::PendingFix { review: to_fix };
+                }
             }
             None => {
                 eprintln!("Auto-resolve: review ended without findings. Please inspect manually.");
@@ -91,6 +91,7 @@ pub(super) async fn submit_followup_review(
         Some(snap),
         Some(diff_paths),
         Some(base_id),
+        &config.large_files,
     )
     .await;
     state.last_review_epoch = Some(current_snapshot_epoch_for(&config.cwd));
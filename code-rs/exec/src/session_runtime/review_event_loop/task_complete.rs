@@ -233,6 +233,9 @@ pub(super) async fn handle_task_complete_event(
             AutoResolvePhase::WaitingForReview => {
                 // Task complete from a review; handled in ExitedReviewMode.
             }
+            AutoResolvePhase::AwaitingSelection { .. } => {
+                // Only interactive front ends pause for a finding selection.
+            }
         }
     }
 
@@ -2,9 +2,16 @@ use super::event_bridge::start_event_stream;
 use super::review_event_loop::ReviewEventLoopParams;
 use super::review_event_loop::run_review_event_loop;
 use super::review_submission::submit_initial_turn;
+use super::state::HandoffState;
 use super::state::ReviewRuntimeState;
+use super::watch::PathWatcher;
 use super::SessionRuntimeOutcome;
 use super::SessionRuntimeParams;
+use crate::event_processor::CodexStatus;
+use crate::exit_code::RunFailures;
+use crate::run_budget::BudgetTracker;
+use std::time::Duration;
+use tokio::time::Instant;
 
 pub(crate) async fn run_session_runtime(
     params: SessionRuntimeParams<'_>,
@@ -16,51 +23,116 @@ pub(crate) async fn run_session_runtime(
         review_request,
         prompt_to_send,
         images,
-        run_deadline,
+        output_schema,
+        mut run_deadline,
         max_seconds,
+        budget,
         auto_resolve_state,
         max_auto_resolve_attempts: _max_auto_resolve_attempts,
         is_auto_review,
+        watch_paths,
+        review_async,
+        handoff,
     } = params;
 
     let mut state = ReviewRuntimeState::new(auto_resolve_state);
+    if handoff {
+        state.handoff = HandoffState::Pending;
+    }
+    if review_async {
+        state.async_review = crate::review_async::start_async_review(config).await;
+    }
+    let mut watcher = if watch_paths.is_empty() {
+        None
+    } else {
+        Some(PathWatcher::new(&watch_paths)?)
+    };
     let mut rx = start_event_stream(conversation.clone());
+    let mut failures = RunFailures::default();
+    let mut budget = BudgetTracker::new(budget);
 
-    let submitted = submit_initial_turn(
-        &conversation,
-        config,
-        review_request.as_ref(),
-        prompt_to_send,
-        images,
-        is_auto_review,
-        &mut state,
-    )
-    .await?;
-    if !submitted {
-        return Ok(SessionRuntimeOutcome {
-            review_outputs: state.review_outputs,
-            final_review_snapshot: state.final_review_snapshot,
-            review_runs: state.review_runs,
-            error_seen: false,
-        });
-    }
+    loop {
+        let submitted = submit_initial_turn(
+            &conversation,
+            config,
+            review_request.as_ref(),
+            prompt_to_send.clone(),
+            images.clone(),
+            output_schema,
+            is_auto_review,
+            &mut state,
+        )
+        .await?;
+        if !submitted {
+            break;
+        }
+        event_processor.turn_submitted();
 
-    let error_seen = run_review_event_loop(ReviewEventLoopParams {
-        conversation: &conversation,
-        config,
-        event_processor,
-        review_request: review_request.as_ref(),
-        run_deadline,
-        max_seconds,
-        rx: &mut rx,
-        state: &mut state,
-    })
-    .await?;
+        let run = run_review_event_loop(ReviewEventLoopParams {
+            conversation: &conversation,
+            config,
+            event_processor: &mut *event_processor,
+            review_request: review_request.as_ref(),
+            output_schema,
+            run_deadline,
+            max_seconds,
+            budget: &mut budget,
+            rx: &mut rx,
+            state: &mut state,
+            watch: watcher.is_some(),
+        })
+        .await?;
+        failures.merge(run.failures);
+
+        let Some(path_watcher) = watcher.as_mut() else {
+            break;
+        };
+        if run.session_ended {
+            break;
+        }
+
+        path_watcher.discard_pending().await;
+        eprintln!("Watching for changes (Ctrl+C to stop)...");
+        let changed = loop {
+            tokio::select! {
+                batch = path_watcher.next_batch() => break batch,
+                maybe_event = rx.recv() => {
+                    let Some(event) = maybe_event else {
+                        break None;
+                    };
+                    if matches!(event_processor.process_event(event), CodexStatus::Shutdown) {
+                        break None;
+                    }
+                }
+            }
+        };
+        let Some(changed) = changed else {
+            break;
+        };
+        if let Some((first, rest)) = changed.split_first() {
+            let first = first.display();
+            match rest.len() {
+                0 => eprintln!("Change detected in {first}; re-running prompt."),
+                others => eprintln!(
+                    "Change detected in {first} and {others} other paths; re-running prompt."
+                ),
+            }
+        }
+        // `--max-seconds` bounds each run, not the whole watch session.
+        run_deadline = max_seconds.map(|seconds| Instant::now() + Duration::from_secs(seconds));
+    }
 
     Ok(SessionRuntimeOutcome {
         review_outputs: state.review_outputs,
         final_review_snapshot: state.final_review_snapshot,
         review_runs: state.review_runs,
-        error_seen,
+        acknowledged_findings: state.acknowledged_findings,
+        failures,
+        budget,
+        structured_output: state.structured_output,
+        handoff_summary: match state.handoff {
+            HandoffState::Done(summary) => summary,
+            _ => None,
+        },
     })
 }
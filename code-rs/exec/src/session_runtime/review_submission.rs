@@ -1,5 +1,6 @@
 use super::state::ReviewRuntimeState;
 use crate::auto_runtime::capture_auto_resolve_snapshot;
+use crate::output_schema::OutputSchema;
 use crate::review_scope::apply_commit_scope_to_review_request;
 use crate::review_scope::capture_snapshot_against_base;
 use code_core::CodexConversation;
@@ -15,12 +16,14 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use tracing::info;
 
+#[allow(clippy::too_many_arguments)]
 pub(super) async fn submit_initial_turn(
     conversation: &Arc<CodexConversation>,
     config: &Config,
     review_request: Option<&ReviewRequest>,
     prompt_to_send: String,
     images: Vec<PathBuf>,
+    output_schema: Option<&OutputSchema>,
     is_auto_review: bool,
     state: &mut ReviewRuntimeState,
 ) -> anyhow::Result<bool> {
@@ -68,9 +71,11 @@ pub(super) async fn submit_initial_turn(
             {
                 request = apply_commit_scope_to_review_request(
                     request,
+                    &config.cwd,
                     snap.id(),
                     base.id(),
                     Some(diff_paths.as_slice()),
+                    &config.large_files,
                 );
                 if let Some(resolve_state) = state.auto_resolve_state.as_mut() {
                     resolve_state.last_reviewed_commit = Some(snap.id().to_owned());
@@ -95,11 +100,10 @@ pub(super) async fn submit_initial_turn(
     });
     items.extend(images.into_iter().map(|path| InputItem::LocalImage { path }));
 
-    // Fallback for older core protocol: send only user input items.
     let event_id = conversation
         .submit(Op::UserInput {
             items,
-            final_output_json_schema: None,
+            final_output_json_schema: output_schema.map(|schema| schema.schema().clone()),
         })
         .await?;
     info!("Sent prompt with event ID: {event_id}");
@@ -1,8 +1,12 @@
 use code_auto_drive_core::AutoResolveState;
+use code_core::protocol::ReviewFinding;
 use code_core::protocol::ReviewOutputEvent;
 use code_core::protocol::ReviewSnapshotInfo;
 use code_core::review_coord::ReviewGuard;
 use code_git_tooling::GhostCommit;
+use serde_json::Value;
+
+use crate::review_async::AsyncReview;
 
 pub(super) struct ReviewRuntimeState {
     pub(super) auto_resolve_state: Option<AutoResolveState>,
@@ -14,6 +18,26 @@ pub(super) struct ReviewRuntimeState {
     pub(super) auto_resolve_followup_guard: Option<ReviewGuard>,
     pub(super) auto_resolve_base_snapshot: Option<GhostCommit>,
     pub(super) review_guard: Option<ReviewGuard>,
+    /// Findings auto-resolve chose not to fix, kept after its state is dropped.
+    pub(super) acknowledged_findings: Vec<ReviewFinding>,
+    /// `--review-async` review started for this run, if any.
+    pub(super) async_review: Option<AsyncReview>,
+    /// Reconciliation turn to run once both the prompt and the review finish.
+    pub(super) pending_reconciliation: Option<String>,
+    /// Final message parsed and validated against `--output-schema`.
+    pub(super) structured_output: Option<Value>,
+    pub(super) handoff: HandoffState,
+}
+
+/// Progress of the `--handoff` summary turn.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(super) enum HandoffState {
+    #[default]
+    Off,
+    /// Requested; runs once the prompt and any follow-up turns finish.
+    Pending,
+    Running,
+    Done(Option<String>),
 }
 
 impl ReviewRuntimeState {
@@ -28,6 +52,11 @@ impl ReviewRuntimeState {
             auto_resolve_followup_guard: None,
             auto_resolve_base_snapshot: None,
             review_guard: None,
+            acknowledged_findings: Vec::new(),
+            async_review: None,
+            pending_reconciliation: None,
+            structured_output: None,
+            handoff: HandoffState::Off,
         }
     }
 }
@@ -0,0 +1,129 @@
+//! File watching for `code exec --watch`.
+
+use notify::Event;
+use notify::EventKind;
+use notify::RecommendedWatcher;
+use notify::RecursiveMode;
+use notify::Watcher;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// Quiet period after the last change before a batch of changes is reported.
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone)]
+struct WatchTarget {
+    path: PathBuf,
+    is_dir: bool,
+}
+
+impl WatchTarget {
+    fn matches(&self, changed: &Path) -> bool {
+        if !self.is_dir {
+            return changed == self.path;
+        }
+        match changed.strip_prefix(&self.path) {
+            // Git rewrites its own files on every status/commit; never react to them.
+            Ok(relative) => !relative.components().any(|component| component.as_os_str() == ".git"),
+            Err(_) => false,
+        }
+    }
+}
+
+/// Reports changes under the paths passed to `--watch`.
+pub(super) struct PathWatcher {
+    _watcher: RecommendedWatcher,
+    rx: mpsc::UnboundedReceiver<PathBuf>,
+}
+
+impl PathWatcher {
+    /// Starts watching `paths`, which must already be absolute.
+    pub(super) fn new(paths: &[PathBuf]) -> anyhow::Result<Self> {
+        let targets: Vec<WatchTarget> = paths
+            .iter()
+            .map(|path| WatchTarget {
+                path: path.clone(),
+                is_dir: path.is_dir(),
+            })
+            .collect();
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        let filter = targets.clone();
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<Event>| {
+            let Ok(event) = res else {
+                return;
+            };
+            if matches!(event.kind, EventKind::Access(_)) {
+                return;
+            }
+            for path in event.paths {
+                if filter.iter().any(|target| target.matches(&path)) {
+                    let _ = tx.send(path);
+                }
+            }
+        })?;
+
+        for target in &targets {
+            if target.is_dir {
+                watcher.watch(&target.path, RecursiveMode::Recursive)?;
+            } else {
+                // Watch the parent so editors that save by replacing the file are still seen.
+                let parent = target.path.parent().unwrap_or(&target.path);
+                watcher.watch(parent, RecursiveMode::NonRecursive)?;
+            }
+        }
+
+        Ok(Self {
+            _watcher: watcher,
+            rx,
+        })
+    }
+
+    /// Drops every change seen so far, including the agent's own edits from
+    /// the run that just finished.
+    pub(super) async fn discard_pending(&mut self) {
+        // Give the OS time to deliver events for writes that just landed.
+        tokio::time::sleep(DEBOUNCE).await;
+        while self.rx.try_recv().is_ok() {}
+    }
+
+    /// Waits for a change, then collects further changes until the watched
+    /// paths have been quiet for [`DEBOUNCE`]. Returns `None` if the watcher
+    /// stopped.
+    pub(super) async fn next_batch(&mut self) -> Option<Vec<PathBuf>> {
+        let first = self.rx.recv().await?;
+        let mut changed = vec![first];
+        while let Ok(Some(path)) = tokio::time::timeout(DEBOUNCE, self.rx.recv()).await {
+            if !changed.contains(&path) {
+                changed.push(path);
+            }
+        }
+        Some(changed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn targets_match_their_file_or_tree_but_not_git_internals() {
+        let file = WatchTarget {
+            path: PathBuf::from("/repo/notes.md"),
+            is_dir: false,
+        };
+        assert!(file.matches(Path::new("/repo/notes.md")));
+        assert!(!file.matches(Path::new("/repo/other.md")));
+
+        let dir = WatchTarget {
+            path: PathBuf::from("/repo/src"),
+            is_dir: true,
+        };
+        assert!(dir.matches(Path::new("/repo/src/lib.rs")));
+        assert!(dir.matches(Path::new("/repo/src/nested/mod.rs")));
+        assert!(!dir.matches(Path::new("/repo/src/.git/index")));
+        assert!(!dir.matches(Path::new("/repo/tests/lib.rs")));
+    }
+}
@@ -1,6 +1,8 @@
 use code_core::config_types::{AgentConfig, SubagentCommandConfig};
 use code_core::protocol::ReviewRequest;
 
+use code_core::slash_commands::format_docsync_command;
+use code_core::slash_commands::format_repro_command;
 use code_core::slash_commands::format_subagent_command;
 
 #[derive(Clone, Copy)]
@@ -36,14 +38,20 @@ pub fn process_exec_slash_command(message: &str, ctx: SlashContext<'_>) -> Resul
         return Ok(SlashDispatch::NotSlash);
     }
 
-    // Allow existing exec auto-drive handling to continue unchanged.
-    if command == "auto" {
+    // Allow existing exec auto-drive handling to continue unchanged; `/migrate`
+    // is turned into an Auto Drive goal the same way.
+    if command == "auto" || command == "migrate" {
         return Ok(SlashDispatch::NotSlash);
     }
 
     match command.as_str() {
         "plan" | "solve" | "code" => handle_subagent(command.as_str(), args_raw, ctx),
         "review" => handle_review(args_raw),
+        "repro" => handle_repro(args_raw),
+        "docsync" => Ok(SlashDispatch::ExpandedPrompt {
+            prompt: format_docsync_command(args_raw),
+            summary: format!("/docsync {}", args_raw.replace('\n', " ")).trim_end().to_owned(),
+        }),
         other => {
             // Custom subagents
             if ctx
@@ -84,7 +92,23 @@ fn handle_subagent(
     })
 }
 
+fn handle_repro(args: &str) -> Result<SlashDispatch, String> {
+    if args.is_empty() {
+        return Err(
+            "Error: /repro requires a failing test or bug description. Usage: /repro <issue>"
+                .to_owned(),
+        );
+    }
+
+    Ok(SlashDispatch::ExpandedPrompt {
+        prompt: format_repro_command(args),
+        summary: format!("/repro {}", args.replace('\n', " ").trim()),
+    })
+}
+
 fn handle_review(args_raw: &str) -> Result<SlashDispatch, String> {
+    let (profile, args) = code_core::review_profiles::split_profile_flag(args_raw);
+    let args_raw = args.as_str();
     let (prompt, hint) = if args_raw.is_empty() {
         (
             "Review the current workspace changes and highlight bugs, regressions, risky patterns, and missing tests before merge.".to_owned(),
@@ -116,6 +140,7 @@ fn handle_review(args_raw: &str) -> Result<SlashDispatch, String> {
             },
             prompt,
             user_facing_hint: Some(hint),
+            profile,
         },
         summary,
     })
@@ -142,6 +167,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn repro_expands_to_prompt() {
+        let result = process_exec_slash_command("/repro flaky\ntimeout in sync", ctx(&[], &[])).unwrap();
+        match result {
+            SlashDispatch::ExpandedPrompt { prompt, summary } => {
+                assert!(prompt.contains("Please perform /repro"));
+                assert_eq!(summary, "/repro flaky timeout in sync");
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+        assert!(process_exec_slash_command("/repro", ctx(&[], &[])).is_err());
+    }
+
     #[test]
     fn custom_subagent_is_supported() {
         let subagent = SubagentCommandConfig { name: "audit".to_string(), ..Default::default() };
@@ -167,6 +205,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn review_profile_flag_is_split_from_instructions() {
+        let result = process_exec_slash_command("/review --profile security check auth", ctx(&[], &[])).unwrap();
+        match result {
+            SlashDispatch::Review { request, summary } => {
+                assert_eq!(request.profile.as_deref(), Some("security"));
+                assert_eq!(request.prompt, "check auth");
+                assert_eq!(summary, "/review check auth");
+            }
+            _ => panic!("expected review"),
+        }
+    }
+
     #[test]
     fn unsupported_command_returns_error() {
         let result = process_exec_slash_command("/theme", ctx(&[], &[]));
@@ -0,0 +1,80 @@
+//! SIGTERM/SIGHUP handling for `code exec`.
+//!
+//! A termination signal asks core for a graceful shutdown (abort the turn,
+//! stop child processes, flush the rollout) instead of tearing the process
+//! down mid-write. Once shutdown completes, or [`SHUTDOWN_GRACE`] runs out,
+//! the run exits with `128 + signal` so callers can tell it was terminated.
+
+use std::sync::atomic::AtomicI32;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+/// How long core gets to finish a signal-initiated shutdown.
+pub(crate) const SHUTDOWN_GRACE: Duration = Duration::from_secs(5);
+
+static TERMINATION_SIGNAL: AtomicI32 = AtomicI32::new(0);
+
+pub(crate) fn record(signal: i32) {
+    TERMINATION_SIGNAL.store(signal, Ordering::Release);
+}
+
+/// Exit code for a run ended by a termination signal, if one was received.
+pub(crate) fn exit_code() -> Option<i32> {
+    let signal = TERMINATION_SIGNAL.load(Ordering::Acquire);
+    (signal != 0).then_some(128 + signal)
+}
+
+#[cfg(unix)]
+pub(crate) struct TerminationSignals {
+    term: Option<tokio::signal::unix::Signal>,
+    hup: Option<tokio::signal::unix::Signal>,
+}
+
+#[cfg(unix)]
+impl TerminationSignals {
+    pub(crate) fn install() -> Self {
+        use tokio::signal::unix::SignalKind;
+        use tokio::signal::unix::signal;
+
+        let install = |kind: SignalKind, name: &str| match signal(kind) {
+            Ok(stream) => Some(stream),
+            Err(err) => {
+                tracing::warn!("failed to install {name} handler: {err}");
+                None
+            }
+        };
+        Self {
+            term: install(SignalKind::terminate(), "SIGTERM"),
+            hup: install(SignalKind::hangup(), "SIGHUP"),
+        }
+    }
+
+    /// Resolves with the signal number of the next SIGTERM or SIGHUP.
+    pub(crate) async fn recv(&mut self) -> i32 {
+        async fn next(stream: &mut Option<tokio::signal::unix::Signal>) -> Option<()> {
+            match stream {
+                Some(stream) => stream.recv().await,
+                None => std::future::pending().await,
+            }
+        }
+        tokio::select! {
+            Some(()) = next(&mut self.term) => libc::SIGTERM,
+            Some(()) = next(&mut self.hup) => libc::SIGHUP,
+            else => std::future::pending().await,
+        }
+    }
+}
+
+#[cfg(not(unix))]
+pub(crate) struct TerminationSignals;
+
+#[cfg(not(unix))]
+impl TerminationSignals {
+    pub(crate) fn install() -> Self {
+        Self
+    }
+
+    pub(crate) async fn recv(&mut self) -> i32 {
+        std::future::pending().await
+    }
+}
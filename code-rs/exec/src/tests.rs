@@ -3,6 +3,7 @@ use crate::auto_runtime::shutdown_state_after_request;
 use crate::auto_runtime::capture_auto_resolve_snapshot;
 use crate::prompt_input::PromptDecodeError;
 use crate::prompt_input::decode_prompt_bytes;
+use crate::prompt_input::expand_prompt_template;
 use crate::review_command::build_review_request;
 use crate::review_scope::head_is_ancestor_of_base;
 use crate::review_scope::should_skip_followup;
@@ -30,6 +31,8 @@ fn build_review_request_uncommitted() {
         commit: None,
         commit_title: None,
         prompt: None,
+        review_profile: None,
+        review_base: None,
     })
     .expect("build review request");
     assert!(matches!(
@@ -47,6 +50,8 @@ fn build_review_request_commit_title() {
         commit: Some("abc123".to_string()),
         commit_title: Some("Fix race condition".to_string()),
         prompt: None,
+        review_profile: Some("security".to_string()),
+        review_base: None,
     })
     .expect("build review request");
     assert!(matches!(
@@ -54,6 +59,7 @@ fn build_review_request_commit_title() {
         code_protocol::protocol::ReviewTarget::Commit { .. }
     ));
     assert!(request.prompt.contains("abc123"));
+    assert_eq!(request.profile.as_deref(), Some("security"));
     assert!(request.prompt.contains("Fix race condition"));
 }
 
@@ -98,6 +104,32 @@ fn shutdown_state_sends_immediately_without_grace() {
     assert!(deadline.is_none());
 }
 
+#[test]
+fn expand_prompt_template_inserts_env_and_files() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join("notes.txt"), "line one\n").unwrap();
+    let env = |name: &str| (name == "TICKET").then(|| "ABC-12".to_string());
+
+    let output = expand_prompt_template(
+        "Fix {{ env.TICKET }}: {{file:notes.txt}} {{other}} {{env.TICKET",
+        dir.path(),
+        &env,
+    );
+    assert_eq!(output, Ok("Fix ABC-12: line one\n {{other}} {{env.TICKET".to_string()));
+}
+
+#[test]
+fn expand_prompt_template_rejects_missing_sources() {
+    let dir = TempDir::new().unwrap();
+    let env = |_: &str| None;
+
+    assert_eq!(
+        expand_prompt_template("{{env.MISSING}}", dir.path(), &env),
+        Err("{{env.MISSING}}: environment variable is not set".to_string())
+    );
+    assert!(expand_prompt_template("{{file:absent.txt}}", dir.path(), &env).is_err());
+}
+
 #[test]
 fn decode_prompt_bytes_strips_utf8_bom() {
     let input = [0xEF, 0xBB, 0xBF, b'h', b'i', b'\n'];
@@ -182,15 +214,17 @@ fn write_review_json_includes_snapshot() {
         branch: Some("auto-review-branch".into()),
         worktree_path: Some(PathBuf::from("/tmp/wt")),
         repo_root: Some(PathBuf::from("/tmp/repo")),
+        review_profile: Some("security".into()),
     };
 
-    write_review_json(path.clone(), &[output], Some(&snapshot)).unwrap();
+    write_review_json(path.clone(), &[output], Some(&snapshot), None, &[]).unwrap();
 
     let content = std::fs::read_to_string(path).unwrap();
     let v: serde_json::Value = serde_json::from_str(&content).unwrap();
     assert_eq!(v["branch"], "auto-review-branch");
     assert_eq!(v["snapshot_commit"], "abc123");
     assert_eq!(v["worktree_path"], "/tmp/wt");
+    assert_eq!(v["review_profile"], "security");
     assert_eq!(v["findings"].as_array().unwrap().len(), 1);
     let runs = v["runs"].as_array().unwrap();
     assert_eq!(runs.len(), 1);
@@ -225,7 +259,7 @@ fn write_review_json_keeps_all_runs() {
         overall_confidence_score: 0.9,
     };
 
-    write_review_json(path.clone(), &[first, second], None).unwrap();
+    write_review_json(path.clone(), &[first, second], None, None, &[]).unwrap();
 
     let content = std::fs::read_to_string(path).unwrap();
     let v: serde_json::Value = serde_json::from_str(&content).unwrap();
@@ -236,6 +270,64 @@ fn write_review_json_keeps_all_runs() {
     assert_eq!(runs[0]["findings"].as_array().unwrap().len(), 1);
     assert_eq!(runs[1]["index"], 2);
     assert_eq!(runs[1]["findings"].as_array().unwrap().len(), 0);
+    assert!(v.get("acknowledged_findings").is_none());
+}
+
+#[test]
+fn write_review_json_relativizes_paths_against_repo_root() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("relative.json");
+
+    let finding = |file: &str| code_core::protocol::ReviewFinding {
+        title: "bug".into(),
+        body: "details".into(),
+        confidence_score: 0.6,
+        priority: 1,
+        code_location: code_core::protocol::ReviewCodeLocation {
+            absolute_file_path: PathBuf::from(file),
+            line_range: code_core::protocol::ReviewLineRange { start: 3, end: 4 },
+        },
+    };
+    let output = code_core::protocol::ReviewOutputEvent {
+        findings: vec![
+            finding("/work/repo/src/lib.rs"),
+            finding("/tmp/wt/src/main.rs"),
+            finding("/elsewhere/notes.md"),
+        ],
+        overall_correctness: "incorrect".into(),
+        overall_explanation: "needs fixes".into(),
+        overall_confidence_score: 0.7,
+    };
+    let base = crate::review_output::ReviewPathBase {
+        repo_root: PathBuf::from("/work/repo"),
+        commit_sha: Some("abc123".into()),
+        worktree_path: Some(PathBuf::from("/tmp/wt")),
+    };
+
+    let acknowledged = [finding("/work/repo/src/skipped.rs")];
+
+    write_review_json(path.clone(), &[output], None, Some(&base), &acknowledged).unwrap();
+
+    let content = std::fs::read_to_string(path).unwrap();
+    let v: serde_json::Value = serde_json::from_str(&content).unwrap();
+    assert_eq!(v["repo_root"], "/work/repo");
+    assert_eq!(v["commit_sha"], "abc123");
+    assert_eq!(v["paths_relative"], true);
+    let paths: Vec<&str> = v["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| f["code_location"]["absolute_file_path"].as_str().unwrap())
+        .collect();
+    assert_eq!(paths, vec!["src/lib.rs", "src/main.rs", "/elsewhere/notes.md"]);
+    assert_eq!(
+        v["runs"][0]["findings"][0]["code_location"]["absolute_file_path"],
+        "src/lib.rs"
+    );
+    assert_eq!(
+        v["acknowledged_findings"][0]["code_location"]["absolute_file_path"],
+        "src/skipped.rs"
+    );
 }
 
 #[test]
@@ -251,6 +343,43 @@ fn strip_scope_removes_previous_commit_scope() {
     assert!(cleaned.contains("Please review."));
 }
 
+#[test]
+fn strip_scope_removes_submodule_and_large_file_blocks() {
+    let mut prompt = "Please review.\nReview scope: commit abc123 (parent deadbeef)\n".to_owned();
+    crate::review_scope::append_submodule_scope(
+        &mut prompt,
+        &[code_git_tooling::SubmoduleChange {
+            path: PathBuf::from("vendor/lib"),
+            old_commit: Some("1111111111".to_owned()),
+            new_commit: Some("2222222222".to_owned()),
+            commits: Some(vec!["> Fix parser".to_owned()]),
+            stat: None,
+        }],
+    );
+    crate::review_scope::append_large_file_scope(
+        &mut prompt,
+        &[code_git_tooling::LargeFileChange {
+            path: PathBuf::from("assets/logo.png"),
+            kind: code_git_tooling::LargeFileKind::Binary,
+            old_size: Some(2048),
+            new_size: Some(4096),
+            old_pointer: None,
+            new_pointer: None,
+        }],
+        false,
+    );
+    prompt.push_str("\nKeep this line.");
+    assert!(prompt.contains("Submodule vendor/lib"));
+    assert!(prompt.contains("- assets/logo.png: binary file, 2.0 KiB -> 4.0 KiB"));
+
+    let cleaned = strip_scope_from_prompt(&prompt);
+    assert!(!cleaned.contains("vendor/lib"));
+    assert!(!cleaned.contains("Fix parser"));
+    assert!(!cleaned.contains("logo.png"));
+    assert!(cleaned.contains("Please review."));
+    assert!(cleaned.contains("Keep this line."));
+}
+
 #[test]
 fn should_skip_followup_detects_duplicate_snapshot() {
     let temp = TempDir::new().unwrap();
@@ -320,6 +449,70 @@ fn base_ancestor_check_matches_git_history() {
     assert!(!head_is_ancestor_of_base(temp.path(), "deadbeef"));
 }
 
+#[test]
+fn review_base_pins_scope_to_merge_base() {
+    let temp = TempDir::new().unwrap();
+    let run_git = |args: &[&str]| {
+        let output = std::process::Command::new("git")
+            .current_dir(temp.path())
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {args:?} failed");
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    };
+
+    run_git(&["init"]);
+    run_git(&["config", "user.email", "codex@example.com"]);
+    run_git(&["config", "user.name", "Codex Tester"]);
+    std::fs::write(temp.path().join("a.txt"), "a").unwrap();
+    run_git(&["add", "."]);
+    run_git(&["commit", "-m", "c1"]);
+    run_git(&["branch", "-M", "main"]);
+    let merge_base = run_git(&["rev-parse", "HEAD"]);
+    run_git(&["checkout", "-b", "feature"]);
+    std::fs::write(temp.path().join("b.txt"), "b").unwrap();
+    run_git(&["add", "."]);
+    run_git(&["commit", "-m", "c2"]);
+    // Uncommitted edits stay out of the pinned scope.
+    std::fs::write(temp.path().join("a.txt"), "dirty").unwrap();
+
+    let request = build_review_request(crate::cli::ReviewArgs {
+        uncommitted: false,
+        base: None,
+        commit: None,
+        commit_title: None,
+        prompt: None,
+        review_profile: None,
+        review_base: Some("main".to_string()),
+    })
+    .expect("build review request");
+    let request = crate::review_scope::pin_review_base(
+        request,
+        temp.path(),
+        "main",
+        &code_core::config_types::LargeFilesConfig::default(),
+    )
+    .expect("pin review base");
+
+    assert!(request.prompt.contains(&format!("merge base {merge_base} (with `main`)")));
+    assert!(request.prompt.contains("- b.txt"));
+    assert!(!request.prompt.contains("- a.txt"));
+    assert!(matches!(
+        request.target,
+        code_protocol::protocol::ReviewTarget::Custom { .. }
+    ));
+    assert!(
+        crate::review_scope::pin_review_base(
+            request,
+            temp.path(),
+            "no-such-branch",
+            &code_core::config_types::LargeFilesConfig::default(),
+        )
+        .is_err()
+    );
+}
+
 fn test_config(code_home: &Path) -> Config {
     let mut overrides = ConfigOverrides::default();
     let workspace = code_home.join("workspace");
@@ -471,6 +664,8 @@ async fn exec_resolve_last_prefers_latest_timestamp() {
         session_id: None,
         last: true,
         all: false,
+        fork: false,
+        fork_at: None,
         images: vec![],
         prompt: None,
     };
@@ -504,6 +699,8 @@ async fn exec_resolve_by_id_uses_catalog_bootstrap() {
         session_id: Some("cccccccc".to_string()),
         last: false,
         all: false,
+        fork: false,
+        fork_at: None,
         images: vec![],
         prompt: None,
     };
@@ -553,6 +750,8 @@ async fn exec_resolve_last_ignores_mtime_drift() {
         session_id: None,
         last: true,
         all: false,
+        fork: false,
+        fork_at: None,
         images: vec![],
         prompt: None,
     };
@@ -601,6 +800,8 @@ async fn exec_resolve_last_honors_all_flag_for_cwd_filtering() {
             session_id: None,
             last: true,
             all: false,
+            fork: false,
+            fork_at: None,
             images: vec![],
             prompt: None,
         },
@@ -622,6 +823,8 @@ async fn exec_resolve_last_honors_all_flag_for_cwd_filtering() {
             session_id: None,
             last: true,
             all: true,
+            fork: false,
+            fork_at: None,
             images: vec![],
             prompt: None,
         },
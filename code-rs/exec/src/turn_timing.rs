@@ -0,0 +1,198 @@
+//! Per-turn timing for `--json` output, written as `msg.timing` on the line
+//! that ends a turn (`task_complete` or `turn_aborted`).
+//!
+//! A turn's wall time splits into three parts:
+//! - `queue_ms`: from exec submitting the turn to the agent starting it.
+//!   `null` when exec did not submit the turn itself (e.g. a review
+//!   follow-up or a note injected over `--control-socket`).
+//! - `tool_execution_ms`: time at least one tool call was running. Parallel
+//!   and nested calls are counted once.
+//! - `model_latency_ms`: the rest of the turn, spent waiting on or streaming
+//!   from the model.
+
+use std::collections::HashSet;
+use std::time::Duration;
+use std::time::Instant;
+
+use code_core::protocol::EventMsg;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub(crate) struct TurnTiming {
+    pub(crate) queue_ms: Option<u64>,
+    pub(crate) model_latency_ms: u64,
+    pub(crate) tool_execution_ms: u64,
+    pub(crate) wall_ms: u64,
+}
+
+#[derive(Debug)]
+struct ActiveTurn {
+    started: Instant,
+    queue: Option<Duration>,
+    tools_in_flight: HashSet<String>,
+    /// When the tool currently running (if any) became the first in flight.
+    tools_since: Option<Instant>,
+    tool_time: Duration,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct TurnTimer {
+    submitted_at: Option<Instant>,
+    turn: Option<ActiveTurn>,
+}
+
+impl TurnTimer {
+    /// Records that exec just submitted a turn; the next `TaskStarted`
+    /// measures its queue time from here.
+    pub(crate) fn mark_submitted(&mut self, now: Instant) {
+        self.submitted_at = Some(now);
+    }
+
+    /// Feeds one event; returns the turn's timing when the event ends it.
+    pub(crate) fn observe(&mut self, msg: &EventMsg, now: Instant) -> Option<TurnTiming> {
+        match msg {
+            EventMsg::TaskStarted => {
+                if self.turn.is_none() {
+                    let queue = self
+                        .submitted_at
+                        .take()
+                        .map(|submitted| now.saturating_duration_since(submitted));
+                    self.turn = Some(ActiveTurn {
+                        started: now,
+                        queue,
+                        tools_in_flight: HashSet::new(),
+                        tools_since: None,
+                        tool_time: Duration::ZERO,
+                    });
+                }
+                None
+            }
+            EventMsg::TaskComplete(_) | EventMsg::TurnAborted(_) => {
+                self.turn.take().map(|turn| turn.finish(now))
+            }
+            _ => {
+                let turn = self.turn.as_mut()?;
+                if let Some(call_id) = tool_call_begin(msg) {
+                    if turn.tools_in_flight.is_empty() {
+                        turn.tools_since = Some(now);
+                    }
+                    turn.tools_in_flight.insert(call_id.to_owned());
+                } else if let Some(call_id) = tool_call_end(msg)
+                    && turn.tools_in_flight.remove(call_id)
+                    && turn.tools_in_flight.is_empty()
+                    && let Some(since) = turn.tools_since.take()
+                {
+                    turn.tool_time += now.saturating_duration_since(since);
+                }
+                None
+            }
+        }
+    }
+}
+
+impl ActiveTurn {
+    fn finish(self, now: Instant) -> TurnTiming {
+        let active = now.saturating_duration_since(self.started);
+        // A turn can end (e.g. when aborted) with tools still in flight.
+        let tool_time = self.tool_time
+            + self
+                .tools_since
+                .map(|since| now.saturating_duration_since(since))
+                .unwrap_or_default();
+        let queue = self.queue.unwrap_or_default();
+        TurnTiming {
+            queue_ms: self.queue.map(as_millis),
+            model_latency_ms: as_millis(active.saturating_sub(tool_time)),
+            tool_execution_ms: as_millis(tool_time),
+            wall_ms: as_millis(queue + active),
+        }
+    }
+}
+
+fn tool_call_begin(msg: &EventMsg) -> Option<&str> {
+    match msg {
+        EventMsg::ExecCommandBegin(event) => Some(&event.call_id),
+        EventMsg::McpToolCallBegin(event) => Some(&event.call_id),
+        EventMsg::CustomToolCallBegin(event) => Some(&event.call_id),
+        EventMsg::PatchApplyBegin(event) => Some(&event.call_id),
+        EventMsg::WebSearchBegin(event) => Some(&event.call_id),
+        _ => None,
+    }
+}
+
+fn tool_call_end(msg: &EventMsg) -> Option<&str> {
+    match msg {
+        EventMsg::ExecCommandEnd(event) => Some(&event.call_id),
+        EventMsg::McpToolCallEnd(event) => Some(&event.call_id),
+        EventMsg::CustomToolCallEnd(event) => Some(&event.call_id),
+        EventMsg::PatchApplyEnd(event) => Some(&event.call_id),
+        EventMsg::WebSearchComplete(event) => Some(&event.call_id),
+        _ => None,
+    }
+}
+
+fn as_millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use code_core::protocol::TaskCompleteEvent;
+    use code_core::protocol::WebSearchBeginEvent;
+    use code_core::protocol::WebSearchCompleteEvent;
+
+    fn begin(call_id: &str) -> EventMsg {
+        EventMsg::WebSearchBegin(WebSearchBeginEvent {
+            call_id: call_id.to_owned(),
+            query: None,
+        })
+    }
+
+    fn end(call_id: &str) -> EventMsg {
+        EventMsg::WebSearchComplete(WebSearchCompleteEvent {
+            call_id: call_id.to_owned(),
+            query: None,
+        })
+    }
+
+    #[test]
+    fn splits_turn_into_queue_model_and_tool_time() {
+        let t0 = Instant::now();
+        let ms = |millis| t0 + Duration::from_millis(millis);
+        let complete = EventMsg::TaskComplete(TaskCompleteEvent {
+            last_agent_message: None,
+        });
+
+        let mut timer = TurnTimer::default();
+        timer.mark_submitted(t0);
+        assert_eq!(timer.observe(&EventMsg::TaskStarted, ms(40)), None);
+        // Two overlapping searches, 100ms to 250ms, count once.
+        timer.observe(&begin("a"), ms(100));
+        timer.observe(&begin("b"), ms(150));
+        timer.observe(&end("a"), ms(200));
+        timer.observe(&end("b"), ms(250));
+
+        assert_eq!(
+            timer.observe(&complete, ms(400)),
+            Some(TurnTiming {
+                queue_ms: Some(40),
+                model_latency_ms: 210,
+                tool_execution_ms: 150,
+                wall_ms: 400,
+            })
+        );
+
+        // A turn exec did not submit has no queue time.
+        timer.observe(&EventMsg::TaskStarted, ms(500));
+        assert_eq!(
+            timer.observe(&complete, ms(600)),
+            Some(TurnTiming {
+                queue_ms: None,
+                model_latency_ms: 100,
+                tool_execution_ms: 0,
+                wall_ms: 100,
+            })
+        );
+    }
+}
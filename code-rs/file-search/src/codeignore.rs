@@ -0,0 +1,106 @@
+//! `.codeignore` files: gitignore-syntax rules for paths that should stay out
+//! of the model's context (generated folders, fixtures, vendored code) even
+//! when they are tracked in git.
+//!
+//! Like `.gitignore`, a `.codeignore` may sit in any directory and its
+//! patterns are relative to that directory; rules in deeper files win.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use ignore::Match;
+use ignore::gitignore::Gitignore;
+use ignore::gitignore::GitignoreBuilder;
+
+pub const CODEIGNORE_FILENAME: &str = ".codeignore";
+
+/// Checks paths under `root` against the `.codeignore` files between `root`
+/// and each path. Files are read the first time their directory is needed.
+#[derive(Debug)]
+pub struct CodeIgnore {
+    root: PathBuf,
+    matchers: RefCell<HashMap<PathBuf, Option<Gitignore>>>,
+}
+
+impl CodeIgnore {
+    pub fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            matchers: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Whether `root` itself has a `.codeignore`.
+    pub fn has_root_file(root: &Path) -> bool {
+        root.join(CODEIGNORE_FILENAME).is_file()
+    }
+
+    /// Whether `path` (absolute, or relative to the root) is ignored, either
+    /// directly or because one of its parent directories is.
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        let relative = path.strip_prefix(&self.root).unwrap_or(path);
+        if relative.is_absolute() {
+            return false;
+        }
+        let full = self.root.join(relative);
+        // `ancestors` yields the deepest directory first, so nested files take
+        // precedence over the ones above them.
+        for dir in relative.ancestors().skip(1) {
+            let dir = self.root.join(dir);
+            let mut matchers = self.matchers.borrow_mut();
+            let matcher = matchers
+                .entry(dir.clone())
+                .or_insert_with(|| load_matcher(&dir));
+            let Some(matcher) = matcher else {
+                continue;
+            };
+            match matcher.matched_path_or_any_parents(&full, is_dir) {
+                Match::Ignore(_) => return true,
+                Match::Whitelist(_) => return false,
+                Match::None => {}
+            }
+        }
+        false
+    }
+}
+
+fn load_matcher(dir: &Path) -> Option<Gitignore> {
+    let file = dir.join(CODEIGNORE_FILENAME);
+    if !file.is_file() {
+        return None;
+    }
+    let mut builder = GitignoreBuilder::new(dir);
+    // Invalid lines are skipped, as git does; the valid ones still apply.
+    let _ = builder.add(&file);
+    builder.build().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn nested_files_override_parent_rules() {
+        let root = tempfile::tempdir().expect("tempdir");
+        let root = root.path();
+        fs::create_dir_all(root.join("web/generated")).expect("dirs");
+        fs::write(root.join(CODEIGNORE_FILENAME), "fixtures/\n*.snap\n").expect("root");
+        fs::write(
+            root.join("web").join(CODEIGNORE_FILENAME),
+            "generated/\n!keep.snap\n",
+        )
+        .expect("nested");
+
+        let ignore = CodeIgnore::new(root);
+        assert!(ignore.is_ignored(Path::new("fixtures"), true));
+        assert!(ignore.is_ignored(Path::new("fixtures/big.json"), false));
+        assert!(ignore.is_ignored(Path::new("src/a.snap"), false));
+        assert!(ignore.is_ignored(&root.join("web/generated/api.ts"), false));
+        assert!(!ignore.is_ignored(Path::new("web/keep.snap"), false));
+        assert!(!ignore.is_ignored(Path::new("src/main.rs"), false));
+        assert!(!ignore.is_ignored(Path::new("web/generated.rs"), false));
+    }
+}
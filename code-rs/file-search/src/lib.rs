@@ -19,8 +19,11 @@ use std::sync::atomic::Ordering;
 use tokio::process::Command;
 
 mod cli;
+mod codeignore;
 
 pub use cli::Cli;
+pub use codeignore::CODEIGNORE_FILENAME;
+pub use codeignore::CodeIgnore;
 
 /// A single match result returned from the search.
 ///
@@ -153,7 +156,9 @@ pub fn run(
         // Allow hidden entries.
         .hidden(false)
         // Don't require git to be present to apply to apply git-related ignore rules.
-        .require_git(false);
+        .require_git(false)
+        // Keep `.codeignore`d paths out of @-mention results as well.
+        .add_custom_ignore_filename(CODEIGNORE_FILENAME);
 
     if !exclude.is_empty() {
         let mut override_builder = OverrideBuilder::new(search_directory);
@@ -6,6 +6,8 @@ use tempfile::Builder;
 
 use crate::GhostCommit;
 use crate::GitToolingError;
+use crate::ghost_refs::ghost_session;
+use crate::ghost_refs::record_ghost_ref;
 use crate::operations::apply_repo_prefix_to_force_include;
 use crate::operations::ensure_git_repository;
 use crate::operations::normalize_relative_path;
@@ -25,6 +27,7 @@ pub struct CreateGhostCommitOptions<'a> {
     pub force_include: Vec<PathBuf>,
     pub parent: Option<&'a str>,
     pub post_commit_hook: Option<&'a dyn Fn()>,
+    pub session: Option<&'a str>,
 }
 
 impl<'a> CreateGhostCommitOptions<'a> {
@@ -36,6 +39,7 @@ impl<'a> CreateGhostCommitOptions<'a> {
             force_include: Vec::new(),
             parent: None,
             post_commit_hook: None,
+            session: None,
         }
     }
 
@@ -53,6 +57,14 @@ impl<'a> CreateGhostCommitOptions<'a> {
         self
     }
 
+    /// Records the commit under this session's ghost refs instead of the
+    /// process-wide session set with `set_ghost_session`.
+    #[must_use]
+    pub fn session(mut self, session: &'a str) -> Self {
+        self.session = Some(session);
+        self
+    }
+
     /// Registers a hook to run after the ghost commit is created.
     #[must_use]
     pub fn post_commit_hook(mut self, hook: &'a dyn Fn()) -> Self {
@@ -153,6 +165,10 @@ pub fn create_ghost_commit(
         Some(commit_env.as_slice()),
     )?;
 
+    if let Some(session) = options.session.map(ToOwned::to_owned).or_else(ghost_session) {
+        record_ghost_ref(repo_root.as_path(), &session, &commit_id)?;
+    }
+
     if let Some(hook) = options.post_commit_hook {
         hook();
     }
@@ -190,6 +206,63 @@ pub fn restore_to_commit(repo_path: &Path, commit_id: &str) -> Result<(), GitToo
     Ok(())
 }
 
+/// Lines added and removed in one file between two commits. Both counts are
+/// `None` for binary files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffStatEntry {
+    pub path: String,
+    pub added: Option<u64>,
+    pub removed: Option<u64>,
+    /// The file exists in `to` but not in `from`.
+    pub new_file: bool,
+}
+
+/// Files changed between commits `from` and `to` of the repository
+/// containing `repo_path`, with paths relative to the repository root.
+pub fn diff_numstat(
+    repo_path: &Path,
+    from: &str,
+    to: &str,
+) -> Result<Vec<DiffStatEntry>, GitToolingError> {
+    ensure_git_repository(repo_path)?;
+    let repo_root = resolve_repository_root(repo_path)?;
+    let numstat = run_git_for_stdout(
+        repo_root.as_path(),
+        ["diff", "--numstat", "-z", "--no-renames", from, to],
+        None,
+    )?;
+    let added = run_git_for_stdout(
+        repo_root.as_path(),
+        ["diff", "--name-only", "-z", "--no-renames", "--diff-filter=A", from, to],
+        None,
+    )?;
+    let added: Vec<&str> = added.split('\0').filter(|path| !path.is_empty()).collect();
+    let mut entries = parse_numstat(&numstat);
+    for entry in &mut entries {
+        entry.new_file = added.contains(&entry.path.as_str());
+    }
+    Ok(entries)
+}
+
+/// Parses `git diff --numstat -z --no-renames` output.
+fn parse_numstat(numstat: &str) -> Vec<DiffStatEntry> {
+    numstat
+        .split('\0')
+        .filter_map(|entry| {
+            let mut fields = entry.trim_start().splitn(3, '\t');
+            let added = fields.next()?;
+            let removed = fields.next()?;
+            let path = fields.next().filter(|path| !path.is_empty())?;
+            Some(DiffStatEntry {
+                path: path.to_owned(),
+                added: added.parse().ok(),
+                removed: removed.parse().ok(),
+                new_file: false,
+            })
+        })
+        .collect()
+}
+
 /// Returns the default author and committer identity for ghost commits.
 fn default_commit_identity() -> Vec<(OsString, OsString)> {
     vec![
@@ -335,6 +408,39 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    /// Lists the files changed between two snapshots, marking new ones.
+    fn diff_numstat_between_snapshots() -> Result<(), GitToolingError> {
+        let temp = tempfile::tempdir()?;
+        let repo = temp.path();
+        init_test_repo(repo);
+        std::fs::write(repo.join("tracked.txt"), "one\ntwo\n")?;
+        let before = create_ghost_commit(&CreateGhostCommitOptions::new(repo))?;
+
+        std::fs::write(repo.join("tracked.txt"), "one\n2\nthree\n")?;
+        std::fs::write(repo.join("added.txt"), "new\n")?;
+        let after = create_ghost_commit(&CreateGhostCommitOptions::new(repo))?;
+
+        assert_eq!(
+            diff_numstat(repo, before.id(), after.id())?,
+            vec![
+                DiffStatEntry {
+                    path: "added.txt".to_owned(),
+                    added: Some(1),
+                    removed: Some(0),
+                    new_file: true,
+                },
+                DiffStatEntry {
+                    path: "tracked.txt".to_owned(),
+                    added: Some(2),
+                    removed: Some(1),
+                    new_file: false,
+                },
+            ]
+        );
+        Ok(())
+    }
+
     #[test]
     /// Ensures ghost commits succeed in repositories without an existing HEAD.
     fn create_snapshot_without_existing_head() -> Result<(), GitToolingError> {
@@ -428,6 +534,39 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    /// Session snapshots get a ghost ref that retention can prune.
+    fn session_ghost_commits_are_recorded_and_pruned() -> Result<(), GitToolingError> {
+        let temp = tempfile::tempdir()?;
+        let repo = temp.path();
+        init_test_repo(repo);
+        std::fs::write(repo.join("tracked.txt"), "one\n")?;
+        let first = create_ghost_commit(&CreateGhostCommitOptions::new(repo).session("s1"))?;
+        std::fs::write(repo.join("tracked.txt"), "two\n")?;
+        let second = create_ghost_commit(&CreateGhostCommitOptions::new(repo).session("s1"))?;
+
+        let refs = crate::list_ghost_refs(repo)?;
+        let mut recorded: Vec<&str> = refs.iter().map(|ghost| ghost.commit_id.as_str()).collect();
+        recorded.sort_unstable();
+        let mut expected = vec![first.id(), second.id()];
+        expected.sort_unstable();
+        assert_eq!(recorded, expected);
+        assert!(refs.iter().all(|ghost| ghost.session == "s1"));
+
+        let retention = crate::GhostRetention {
+            keep_per_session: Some(1),
+            max_age: None,
+        };
+        let dry_run = crate::prune_ghost_refs(repo, &retention, true)?;
+        assert_eq!((dry_run.kept, dry_run.pruned.len()), (1, 1));
+        assert_eq!(crate::list_ghost_refs(repo)?.len(), 2);
+
+        let report = crate::prune_ghost_refs(repo, &retention, false)?;
+        assert_eq!((report.kept, report.pruned.len()), (1, 1));
+        assert_eq!(crate::list_ghost_refs(repo)?.len(), 1);
+        Ok(())
+    }
+
     #[test]
     /// Rejects force-included paths that escape the repository.
     fn create_ghost_commit_rejects_force_include_parent_path() {
@@ -0,0 +1,272 @@
+//! Refs that keep ghost commits reachable, and the retention policy that
+//! prunes them.
+//!
+//! Ghost commits are dangling by design. Once a ghost session is set (see
+//! [`set_ghost_session`]), each new ghost commit is recorded as
+//! `refs/code/ghost/<session>/<commit>` so undo and review snapshots survive
+//! `git gc` for as long as the session needs them. [`prune_ghost_refs`]
+//! deletes refs beyond the per-session limit or past the age limit; the
+//! objects themselves are then reclaimed by git's normal garbage collection.
+
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::path::Path;
+use std::sync::RwLock;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use crate::GitToolingError;
+use crate::operations::ensure_git_repository;
+use crate::operations::resolve_repository_root;
+use crate::operations::run_git_for_status;
+use crate::operations::run_git_for_stdout;
+
+/// Namespace holding one ref per recorded ghost commit.
+pub const GHOST_REF_PREFIX: &str = "refs/code/ghost/";
+
+static GHOST_SESSION: RwLock<Option<String>> = RwLock::new(None);
+
+/// Sets the session new ghost commits are recorded under. `None` stops
+/// recording refs.
+pub fn set_ghost_session(session: Option<&str>) {
+    let session = session.map(sanitize_session);
+    match GHOST_SESSION.write() {
+        Ok(mut guard) => *guard = session,
+        Err(poisoned) => *poisoned.into_inner() = session,
+    }
+}
+
+pub(crate) fn ghost_session() -> Option<String> {
+    match GHOST_SESSION.read() {
+        Ok(guard) => guard.clone(),
+        Err(poisoned) => poisoned.into_inner().clone(),
+    }
+}
+
+/// Keeps ref names valid whatever the session id looks like.
+fn sanitize_session(session: &str) -> String {
+    let cleaned: String = session
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || ch == '-' || ch == '_' {
+                ch
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    if cleaned.is_empty() {
+        "default".to_owned()
+    } else {
+        cleaned
+    }
+}
+
+pub(crate) fn record_ghost_ref(
+    repo_root: &Path,
+    session: &str,
+    commit_id: &str,
+) -> Result<(), GitToolingError> {
+    let refname = format!("{GHOST_REF_PREFIX}{}/{commit_id}", sanitize_session(session));
+    run_git_for_status(
+        repo_root,
+        [OsString::from("update-ref"), OsString::from(refname), OsString::from(commit_id)],
+        None,
+    )
+}
+
+/// How many ghost commits to keep, and for how long.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct GhostRetention {
+    /// Most recent snapshots kept per session; `None` keeps all.
+    pub keep_per_session: Option<usize>,
+    /// Snapshots older than this are pruned; `None` never expires them.
+    pub max_age: Option<Duration>,
+}
+
+/// A recorded ghost commit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GhostRef {
+    pub refname: String,
+    pub session: String,
+    pub commit_id: String,
+    /// Committer timestamp of the ghost commit (seconds since the epoch).
+    pub created_unix: i64,
+}
+
+/// Result of a prune pass.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GhostPruneReport {
+    pub kept: usize,
+    pub pruned: Vec<GhostRef>,
+}
+
+/// Lists the ghost refs recorded in the repository containing `repo_path`.
+pub fn list_ghost_refs(repo_path: &Path) -> Result<Vec<GhostRef>, GitToolingError> {
+    ensure_git_repository(repo_path)?;
+    let repo_root = resolve_repository_root(repo_path)?;
+    let output = run_git_for_stdout(
+        repo_root.as_path(),
+        [
+            "for-each-ref",
+            "--format=%(refname)%09%(objectname)%09%(committerdate:unix)",
+            GHOST_REF_PREFIX,
+        ],
+        None,
+    )?;
+    Ok(output.lines().filter_map(parse_ghost_ref_line).collect())
+}
+
+fn parse_ghost_ref_line(line: &str) -> Option<GhostRef> {
+    let mut fields = line.split('\t');
+    let refname = fields.next()?;
+    let commit_id = fields.next()?;
+    let created_unix = fields.next()?.trim().parse().ok()?;
+    let (session, _) = refname.strip_prefix(GHOST_REF_PREFIX)?.rsplit_once('/')?;
+    Some(GhostRef {
+        refname: refname.to_owned(),
+        session: session.to_owned(),
+        commit_id: commit_id.to_owned(),
+        created_unix,
+    })
+}
+
+/// Picks the refs `retention` no longer covers: anything past the newest
+/// `keep_per_session` of its session, or older than `max_age` at `now_unix`.
+pub fn expired_ghost_refs<'a>(
+    refs: &'a [GhostRef],
+    retention: &GhostRetention,
+    now_unix: i64,
+) -> Vec<&'a GhostRef> {
+    let mut by_session: HashMap<&str, Vec<&GhostRef>> = HashMap::new();
+    for ghost in refs {
+        by_session.entry(ghost.session.as_str()).or_default().push(ghost);
+    }
+    let max_age_secs = retention
+        .max_age
+        .map(|age| i64::try_from(age.as_secs()).unwrap_or(i64::MAX));
+
+    let mut expired = Vec::new();
+    for mut session_refs in by_session.into_values() {
+        session_refs.sort_by_key(|ghost| std::cmp::Reverse(ghost.created_unix));
+        for (rank, ghost) in session_refs.into_iter().enumerate() {
+            let over_count = retention.keep_per_session.is_some_and(|keep| rank >= keep);
+            let too_old = max_age_secs
+                .is_some_and(|max| now_unix.saturating_sub(ghost.created_unix) > max);
+            if over_count || too_old {
+                expired.push(ghost);
+            }
+        }
+    }
+    expired.sort_by(|a, b| a.refname.cmp(&b.refname));
+    expired
+}
+
+/// Applies `retention` to the repository's ghost refs. With `dry_run`, only
+/// reports what would be pruned.
+pub fn prune_ghost_refs(
+    repo_path: &Path,
+    retention: &GhostRetention,
+    dry_run: bool,
+) -> Result<GhostPruneReport, GitToolingError> {
+    let refs = list_ghost_refs(repo_path)?;
+    let now_unix = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| i64::try_from(elapsed.as_secs()).unwrap_or(i64::MAX));
+    let expired: Vec<GhostRef> = expired_ghost_refs(&refs, retention, now_unix)
+        .into_iter()
+        .cloned()
+        .collect();
+
+    if !dry_run {
+        let repo_root = resolve_repository_root(repo_path)?;
+        for ghost in &expired {
+            // Passing the old value makes the delete a no-op if the ref moved.
+            run_git_for_status(
+                repo_root.as_path(),
+                ["update-ref", "-d", ghost.refname.as_str(), ghost.commit_id.as_str()],
+                None,
+            )?;
+        }
+    }
+
+    Ok(GhostPruneReport {
+        kept: refs.len() - expired.len(),
+        pruned: expired,
+    })
+}
+
+/// Prunes worktree registrations whose directories no longer exist and
+/// returns git's description of each one.
+pub fn prune_stale_worktrees(repo_path: &Path, dry_run: bool) -> Result<Vec<String>, GitToolingError> {
+    ensure_git_repository(repo_path)?;
+    let repo_root = resolve_repository_root(repo_path)?;
+    let mut args = vec!["worktree", "prune", "--verbose"];
+    if dry_run {
+        args.push("--dry-run");
+    }
+    let output = run_git_for_stdout(repo_root.as_path(), args, None)?;
+    Ok(output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(ToOwned::to_owned)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn ghost(session: &str, commit: &str, created_unix: i64) -> GhostRef {
+        GhostRef {
+            refname: format!("{GHOST_REF_PREFIX}{session}/{commit}"),
+            session: session.to_owned(),
+            commit_id: commit.to_owned(),
+            created_unix,
+        }
+    }
+
+    fn commits<'a>(refs: &[&'a GhostRef]) -> Vec<&'a str> {
+        refs.iter().map(|ghost| ghost.commit_id.as_str()).collect()
+    }
+
+    #[test]
+    fn parses_for_each_ref_lines() {
+        let line = format!("{GHOST_REF_PREFIX}abc-123/deadbeef\tdeadbeef\t1700000000");
+        assert_eq!(parse_ghost_ref_line(&line), Some(ghost("abc-123", "deadbeef", 1_700_000_000)));
+        assert_eq!(parse_ghost_ref_line("refs/heads/main\tdeadbeef\t1"), None);
+    }
+
+    #[test]
+    fn keeps_newest_per_session_and_expires_old_refs() {
+        let refs = vec![
+            ghost("a", "a1", 100),
+            ghost("a", "a2", 200),
+            ghost("a", "a3", 300),
+            ghost("b", "b1", 50),
+        ];
+        let keep_two = GhostRetention {
+            keep_per_session: Some(2),
+            max_age: None,
+        };
+        assert_eq!(commits(&expired_ghost_refs(&refs, &keep_two, 1_000)), vec!["a1"]);
+
+        let by_age = GhostRetention {
+            keep_per_session: None,
+            max_age: Some(Duration::from_secs(850)),
+        };
+        assert_eq!(commits(&expired_ghost_refs(&refs, &by_age, 1_000)), vec!["a1", "b1"]);
+
+        assert!(expired_ghost_refs(&refs, &GhostRetention::default(), 1_000).is_empty());
+    }
+
+    #[test]
+    fn sanitizes_session_names_for_refs() {
+        assert_eq!(sanitize_session("0190-abcd"), "0190-abcd");
+        assert_eq!(sanitize_session("a/b c"), "a-b-c");
+        assert_eq!(sanitize_session(""), "default");
+    }
+}
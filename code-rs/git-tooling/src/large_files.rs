@@ -0,0 +1,464 @@
+//! Git LFS pointers and large binary files in a change set.
+//!
+//! Diffing the contents of such files helps nobody: LFS files show up as
+//! pointer text, binaries as noise, and very large files blow up the diff a
+//! model has to read. These helpers describe the change instead (path, sizes,
+//! pointer change) so turn diffs and review prompts can skip the contents.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::GitToolingError;
+use crate::operations::ensure_git_repository;
+use crate::operations::resolve_repository_root;
+use crate::operations::run_git_for_stdout;
+
+const LFS_POINTER_VERSION: &str = "version https://git-lfs.github.com/spec/v1";
+/// Pointer files are ~130 bytes; anything much larger is real content.
+const MAX_LFS_POINTER_BYTES: u64 = 1024;
+/// How far into a blob git looks for a NUL byte when deciding it is binary.
+const BINARY_SNIFF_BYTES: usize = 8000;
+const NULL_OID: &str = "0000000000000000000000000000000000000000";
+const SKIPPED_MODES: [&str; 2] = ["160000", "120000"];
+/// Paths per `ls-tree`/`check-attr` invocation, to stay under argv limits.
+const PATHS_PER_CALL: usize = 200;
+
+/// A parsed Git LFS pointer file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LfsPointer {
+    /// Object id, e.g. `sha256:4d7a…`.
+    pub oid: String,
+    /// Size of the real object in bytes.
+    pub size: u64,
+}
+
+/// Parses `bytes` as a Git LFS pointer file.
+pub fn parse_lfs_pointer(bytes: &[u8]) -> Option<LfsPointer> {
+    if bytes.len() as u64 > MAX_LFS_POINTER_BYTES {
+        return None;
+    }
+    let text = std::str::from_utf8(bytes).ok()?;
+    let mut lines = text.lines();
+    if lines.next()?.trim_end() != LFS_POINTER_VERSION {
+        return None;
+    }
+    let mut oid = None;
+    let mut size = None;
+    for line in lines {
+        if let Some(value) = line.strip_prefix("oid ") {
+            oid = Some(value.trim().to_owned());
+        } else if let Some(value) = line.strip_prefix("size ") {
+            size = value.trim().parse().ok();
+        }
+    }
+    Some(LfsPointer {
+        oid: oid?,
+        size: size?,
+    })
+}
+
+/// Why a file's contents are left out of a diff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LargeFileKind {
+    /// Stored in Git LFS; the repository only holds a pointer.
+    Lfs,
+    /// Binary contents.
+    Binary,
+    /// Text larger than the configured threshold.
+    Oversized,
+}
+
+/// A changed file described by metadata instead of a content diff.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LargeFileChange {
+    /// Path relative to the repository root.
+    pub path: PathBuf,
+    pub kind: LargeFileKind,
+    /// Size before the change; `None` when the file was added.
+    pub old_size: Option<u64>,
+    /// Size after the change; `None` when the file was deleted.
+    pub new_size: Option<u64>,
+    /// LFS object id before the change, for [`LargeFileKind::Lfs`].
+    pub old_pointer: Option<String>,
+    /// LFS object id after the change, for [`LargeFileKind::Lfs`].
+    pub new_pointer: Option<String>,
+}
+
+impl LargeFileChange {
+    /// One-line notice, e.g.
+    /// `assets/model.bin: Git LFS object, 1.2 MiB -> 1.5 MiB (pointer sha256:4d7a214 -> sha256:9f2c1e0)`.
+    pub fn render(&self) -> String {
+        let path = self.path.display().to_string().replace('\\', "/");
+        let kind = match self.kind {
+            LargeFileKind::Lfs => "Git LFS object",
+            LargeFileKind::Binary => "binary file",
+            LargeFileKind::Oversized => "large file",
+        };
+        let sizes = match (self.old_size, self.new_size) {
+            (None, Some(new)) => format!("added ({})", format_size(new)),
+            (Some(old), None) => format!("deleted (was {})", format_size(old)),
+            (Some(old), Some(new)) => format!("{} -> {}", format_size(old), format_size(new)),
+            (None, None) => "size unknown".to_owned(),
+        };
+        let mut out = format!("{path}: {kind}, {sizes}");
+        if self.kind == LargeFileKind::Lfs {
+            let old = short_pointer(self.old_pointer.as_deref());
+            let new = short_pointer(self.new_pointer.as_deref());
+            let _ = write!(out, " (pointer {old} -> {new})");
+        }
+        out
+    }
+}
+
+fn short_pointer(oid: Option<&str>) -> String {
+    let Some(oid) = oid else {
+        return "none".to_owned();
+    };
+    match oid.split_once(':') {
+        Some((algo, hash)) => format!("{algo}:{}", hash.get(..7).unwrap_or(hash)),
+        None => oid.get(..7).unwrap_or(oid).to_owned(),
+    }
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = UNITS[0];
+    for next in &UNITS[1..] {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = next;
+    }
+    format!("{value:.1} {unit}")
+}
+
+fn looks_binary(bytes: &[u8]) -> bool {
+    bytes.iter().take(BINARY_SNIFF_BYTES).any(|byte| *byte == 0)
+}
+
+/// Classifies a change from the file contents on each side (`None` for a
+/// missing side). Returns `None` for ordinary text changes that can be
+/// diffed, where neither side exceeds `threshold_bytes`.
+pub fn classify_file_change(
+    path: &Path,
+    old: Option<&[u8]>,
+    new: Option<&[u8]>,
+    threshold_bytes: u64,
+) -> Option<LargeFileChange> {
+    let old_pointer = old.and_then(parse_lfs_pointer);
+    let new_pointer = new.and_then(parse_lfs_pointer);
+    let size = |bytes: &[u8]| bytes.len() as u64;
+    if old_pointer.is_some() || new_pointer.is_some() {
+        return Some(LargeFileChange {
+            path: path.to_path_buf(),
+            kind: LargeFileKind::Lfs,
+            old_size: old_pointer.as_ref().map(|p| p.size).or_else(|| old.map(size)),
+            new_size: new_pointer.as_ref().map(|p| p.size).or_else(|| new.map(size)),
+            old_pointer: old_pointer.map(|p| p.oid),
+            new_pointer: new_pointer.map(|p| p.oid),
+        });
+    }
+    let kind = if old.is_some_and(looks_binary) || new.is_some_and(looks_binary) {
+        LargeFileKind::Binary
+    } else if old.into_iter().chain(new).any(|bytes| size(bytes) > threshold_bytes) {
+        LargeFileKind::Oversized
+    } else {
+        return None;
+    };
+    Some(LargeFileChange {
+        path: path.to_path_buf(),
+        kind,
+        old_size: old.map(size),
+        new_size: new.map(size),
+        old_pointer: None,
+        new_pointer: None,
+    })
+}
+
+/// Lists the LFS-tracked, binary, and over-`threshold_bytes` files changed
+/// between commits `from` and `to` of the repository containing
+/// `repo_path`, without reading their contents.
+pub fn large_file_changes(
+    repo_path: &Path,
+    from: &str,
+    to: &str,
+    threshold_bytes: u64,
+) -> Result<Vec<LargeFileChange>, GitToolingError> {
+    ensure_git_repository(repo_path)?;
+    let repo_root = resolve_repository_root(repo_path)?;
+    let raw = run_git_for_stdout(
+        repo_root.as_path(),
+        ["diff", "--raw", "-z", "--no-abbrev", "--no-renames", from, to],
+        None,
+    )?;
+    let entries = parse_raw_blobs(&raw);
+    if entries.is_empty() {
+        return Ok(Vec::new());
+    }
+    let numstat = run_git_for_stdout(
+        repo_root.as_path(),
+        ["diff", "--numstat", "-z", "--no-renames", from, to],
+        None,
+    )?;
+    let binary = parse_numstat_binary(&numstat);
+
+    let paths: Vec<&str> = entries.iter().map(|entry| entry.path.as_str()).collect();
+    let old_sizes = blob_sizes(&repo_root, from, &paths)?;
+    let new_sizes = blob_sizes(&repo_root, to, &paths)?;
+    let lfs_paths = lfs_tracked(&repo_root, &paths)?;
+
+    let mut out = Vec::new();
+    for entry in &entries {
+        let old_size = entry.old_oid.as_ref().and_then(|_| old_sizes.get(&entry.path).copied());
+        let new_size = entry.new_oid.as_ref().and_then(|_| new_sizes.get(&entry.path).copied());
+        let mut change = LargeFileChange {
+            path: PathBuf::from(&entry.path),
+            kind: LargeFileKind::Oversized,
+            old_size,
+            new_size,
+            old_pointer: None,
+            new_pointer: None,
+        };
+        if lfs_paths.contains(entry.path.as_str()) {
+            let old = read_pointer(&repo_root, entry.old_oid.as_deref(), old_size);
+            let new = read_pointer(&repo_root, entry.new_oid.as_deref(), new_size);
+            if old.is_some() || new.is_some() {
+                change.kind = LargeFileKind::Lfs;
+                change.old_size = old.as_ref().map(|p| p.size).or(old_size);
+                change.new_size = new.as_ref().map(|p| p.size).or(new_size);
+                change.old_pointer = old.map(|p| p.oid);
+                change.new_pointer = new.map(|p| p.oid);
+                out.push(change);
+                continue;
+            }
+        }
+        if binary.contains(entry.path.as_str()) {
+            change.kind = LargeFileKind::Binary;
+            out.push(change);
+        } else if old_size.max(new_size).is_some_and(|size| size > threshold_bytes) {
+            out.push(change);
+        }
+    }
+    Ok(out)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct RawBlobEntry {
+    path: String,
+    old_oid: Option<String>,
+    new_oid: Option<String>,
+}
+
+/// Parses `git diff --raw -z` output, skipping gitlinks and symlinks.
+fn parse_raw_blobs(raw: &str) -> Vec<RawBlobEntry> {
+    let mut out = Vec::new();
+    let mut fields = raw.split('\0');
+    while let Some(meta) = fields.next() {
+        let Some(path) = fields.next() else {
+            break;
+        };
+        let parts: Vec<&str> = meta.trim_start_matches(':').split(' ').collect();
+        let [old_mode, new_mode, old_oid, new_oid, _status] = parts.as_slice() else {
+            continue;
+        };
+        if SKIPPED_MODES.contains(old_mode) || SKIPPED_MODES.contains(new_mode) {
+            continue;
+        }
+        let side = |oid: &str| (oid != NULL_OID).then(|| oid.to_owned());
+        out.push(RawBlobEntry {
+            path: path.to_owned(),
+            old_oid: side(old_oid),
+            new_oid: side(new_oid),
+        });
+    }
+    out
+}
+
+/// Paths git's `--numstat` reports as binary (`-\t-\tpath`).
+fn parse_numstat_binary(numstat: &str) -> HashSet<&str> {
+    numstat
+        .split('\0')
+        .filter_map(|entry| entry.trim_start().strip_prefix("-\t-\t"))
+        .collect()
+}
+
+/// Blob sizes for `paths` in `commit`, via `ls-tree -l`.
+fn blob_sizes(
+    repo_root: &Path,
+    commit: &str,
+    paths: &[&str],
+) -> Result<HashMap<String, u64>, GitToolingError> {
+    let mut sizes = HashMap::new();
+    for chunk in paths.chunks(PATHS_PER_CALL) {
+        let mut args = vec!["ls-tree", "-r", "-l", "-z", "--full-tree", commit, "--"];
+        args.extend_from_slice(chunk);
+        let listing = run_git_for_stdout(repo_root, args, None)?;
+        for entry in listing.split('\0') {
+            let Some((meta, path)) = entry.split_once('\t') else {
+                continue;
+            };
+            if let Some(size) = meta.split_whitespace().nth(3).and_then(|size| size.parse().ok()) {
+                sizes.insert(path.to_owned(), size);
+            }
+        }
+    }
+    Ok(sizes)
+}
+
+/// Paths whose `filter` attribute is `lfs`.
+fn lfs_tracked<'a>(repo_root: &Path, paths: &[&'a str]) -> Result<HashSet<&'a str>, GitToolingError> {
+    let mut tracked = HashSet::new();
+    if !repo_root.join(".gitattributes").is_file() {
+        return Ok(tracked);
+    }
+    for chunk in paths.chunks(PATHS_PER_CALL) {
+        let mut args = vec!["check-attr", "-z", "filter", "--"];
+        args.extend_from_slice(chunk);
+        let output = run_git_for_stdout(repo_root, args, None)?;
+        let fields: Vec<&str> = output.split('\0').collect();
+        for triple in fields.chunks(3) {
+            if let [path, _attr, "lfs"] = triple
+                && let Some(original) = chunk.iter().find(|candidate| *candidate == path)
+            {
+                tracked.insert(*original);
+            }
+        }
+    }
+    Ok(tracked)
+}
+
+fn read_pointer(repo_root: &Path, oid: Option<&str>, size: Option<u64>) -> Option<LfsPointer> {
+    let oid = oid?;
+    if size.is_some_and(|size| size > MAX_LFS_POINTER_BYTES) {
+        return None;
+    }
+    let contents = run_git_for_stdout(repo_root, ["cat-file", "blob", oid], None).ok()?;
+    parse_lfs_pointer(contents.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const POINTER_A: &str = "version https://git-lfs.github.com/spec/v1\noid sha256:4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393\nsize 1258291\n";
+    const POINTER_B: &str = "version https://git-lfs.github.com/spec/v1\noid sha256:9f2c1e0b6cd4c8e1e33f2f1b2c5b7d8e9a0b1c2d3e4f5a6b7c8d9e0f1a2b3c4d\nsize 1572864\n";
+
+    #[test]
+    fn parses_lfs_pointers() {
+        assert_eq!(
+            parse_lfs_pointer(POINTER_A.as_bytes()),
+            Some(LfsPointer {
+                oid: "sha256:4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393".to_owned(),
+                size: 1_258_291,
+            })
+        );
+        assert_eq!(parse_lfs_pointer(b"version 1\nsize 3\n"), None);
+        assert_eq!(parse_lfs_pointer(b"fn main() {}\n"), None);
+    }
+
+    #[test]
+    fn classifies_lfs_binary_and_oversized_changes() {
+        let path = Path::new("assets/model.bin");
+        let lfs = classify_file_change(path, Some(POINTER_A.as_bytes()), Some(POINTER_B.as_bytes()), 1024)
+            .expect("lfs change");
+        assert_eq!(lfs.kind, LargeFileKind::Lfs);
+        assert_eq!(
+            lfs.render(),
+            "assets/model.bin: Git LFS object, 1.2 MiB -> 1.5 MiB (pointer sha256:4d7a214 -> sha256:9f2c1e0)"
+        );
+
+        let binary = classify_file_change(path, None, Some(b"\x89PNG\0\0"), 1024).expect("binary");
+        assert_eq!(binary.kind, LargeFileKind::Binary);
+        assert_eq!(binary.render(), "assets/model.bin: binary file, added (6 B)");
+
+        let big = "x".repeat(2048);
+        let oversized =
+            classify_file_change(path, Some(big.as_bytes()), None, 1024).expect("oversized");
+        assert_eq!(oversized.render(), "assets/model.bin: large file, deleted (was 2.0 KiB)");
+
+        assert_eq!(classify_file_change(path, Some(b"a\n"), Some(b"b\n"), 1024), None);
+    }
+
+    #[test]
+    fn parses_raw_blobs_and_numstat() {
+        let old = "a".repeat(40);
+        let new = "b".repeat(40);
+        let raw = format!(
+            ":100644 100644 {old} {new} M\0img.png\0:000000 100644 {NULL_OID} {new} A\0src/lib.rs\0:160000 160000 {old} {new} M\0vendor/lib\0"
+        );
+        assert_eq!(
+            parse_raw_blobs(&raw),
+            vec![
+                RawBlobEntry {
+                    path: "img.png".to_owned(),
+                    old_oid: Some(old),
+                    new_oid: Some(new.clone()),
+                },
+                RawBlobEntry {
+                    path: "src/lib.rs".to_owned(),
+                    old_oid: None,
+                    new_oid: Some(new),
+                },
+            ]
+        );
+        let binary = parse_numstat_binary(concat!("-\t-\timg.png\0", "3\t1\tsrc/lib.rs\0"));
+        assert_eq!(binary, HashSet::from(["img.png"]));
+    }
+
+    fn git(dir: &Path, args: &[&str]) -> String {
+        let output = std::process::Command::new("git")
+            .current_dir(dir)
+            .args(["-c", "user.name=Tester", "-c", "user.email=test@example.com"])
+            .args(args)
+            .output()
+            .expect("git command");
+        assert!(output.status.success(), "git command failed: {args:?}");
+        String::from_utf8_lossy(&output.stdout).trim().to_owned()
+    }
+
+    #[test]
+    fn lists_large_file_changes_between_commits() -> Result<(), GitToolingError> {
+        let temp = tempfile::tempdir()?;
+        let repo = temp.path();
+        git(repo, &["init", "-q"]);
+        std::fs::write(repo.join(".gitattributes"), "*.bin filter=lfs diff=lfs merge=lfs -text\n")?;
+        std::fs::write(repo.join("model.bin"), POINTER_A)?;
+        std::fs::write(repo.join("notes.txt"), "hello\n")?;
+        git(repo, &["add", "-A"]);
+        git(repo, &["commit", "-qm", "init"]);
+        let before = git(repo, &["rev-parse", "HEAD"]);
+
+        std::fs::write(repo.join("model.bin"), POINTER_B)?;
+        std::fs::write(repo.join("logo.png"), b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR")?;
+        std::fs::write(repo.join("data.csv"), "1,2,3\n".repeat(400))?;
+        std::fs::write(repo.join("notes.txt"), "hello again\n")?;
+        git(repo, &["add", "-A"]);
+        git(repo, &["commit", "-qm", "update"]);
+        let after = git(repo, &["rev-parse", "HEAD"]);
+
+        let changes = large_file_changes(repo, &before, &after, 1024)?;
+        let summary: Vec<(&str, LargeFileKind)> = changes
+            .iter()
+            .map(|change| (change.path.to_str().unwrap_or_default(), change.kind))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("data.csv", LargeFileKind::Oversized),
+                ("logo.png", LargeFileKind::Binary),
+                ("model.bin", LargeFileKind::Lfs),
+            ]
+        );
+        assert_eq!(changes[2].new_size, Some(1_572_864));
+        Ok(())
+    }
+}
@@ -6,15 +6,51 @@ use ts_rs::TS;
 
 mod errors;
 mod ghost_commits;
+mod ghost_refs;
+mod large_files;
 mod operations;
 mod platform;
+mod stash_guard;
+mod submodules;
+mod worktree_pool;
 
 pub use errors::GitToolingError;
 pub use ghost_commits::CreateGhostCommitOptions;
+pub use ghost_commits::DiffStatEntry;
 pub use ghost_commits::create_ghost_commit;
+pub use ghost_commits::diff_numstat;
 pub use ghost_commits::restore_ghost_commit;
 pub use ghost_commits::restore_to_commit;
+pub use ghost_refs::GHOST_REF_PREFIX;
+pub use ghost_refs::GhostPruneReport;
+pub use ghost_refs::GhostRef;
+pub use ghost_refs::GhostRetention;
+pub use ghost_refs::expired_ghost_refs;
+pub use ghost_refs::list_ghost_refs;
+pub use ghost_refs::prune_ghost_refs;
+pub use ghost_refs::prune_stale_worktrees;
+pub use ghost_refs::set_ghost_session;
+pub use large_files::LargeFileChange;
+pub use large_files::LargeFileKind;
+pub use large_files::LfsPointer;
+pub use large_files::classify_file_change;
+pub use large_files::large_file_changes;
+pub use large_files::parse_lfs_pointer;
 pub use platform::create_symlink;
+pub use stash_guard::GUARD_STASH_PREFIX;
+pub use stash_guard::GuardRestore;
+pub use stash_guard::GuardStash;
+pub use stash_guard::restore_guard_stash;
+pub use stash_guard::stash_uncommitted;
+pub use submodules::SubmoduleChange;
+pub use submodules::submodule_changes;
+pub use submodules::submodule_changes_since;
+pub use submodules::submodule_heads;
+pub use worktree_pool::PooledWorktree;
+pub use worktree_pool::WorktreeCheckoutOptions;
+pub use worktree_pool::WorktreePool;
+pub use worktree_pool::changed_dirs;
+pub use worktree_pool::checkout_worktree;
 
 /// Details of a ghost commit created from a repository state.
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
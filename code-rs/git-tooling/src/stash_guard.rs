@@ -0,0 +1,238 @@
+//! Labeled stashes that protect uncommitted work around git commands that
+//! rewrite the working tree.
+//!
+//! Before such a command runs, [`stash_uncommitted`] moves tracked and
+//! untracked changes into a stash labeled [`GUARD_STASH_PREFIX`]; afterwards
+//! [`restore_guard_stash`] pops it back. When the pop conflicts, or the command
+//! left a rebase or merge in progress, the stash is kept so nothing is lost.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::GitToolingError;
+use crate::operations::ensure_git_repository;
+use crate::operations::resolve_repository_root;
+use crate::operations::run_git_for_status;
+use crate::operations::run_git_for_stdout;
+
+/// Prefix of the message of every guard stash.
+pub const GUARD_STASH_PREFIX: &str = "code-guard:";
+
+/// Git state files that mean a multi-step operation is waiting on the user.
+const IN_PROGRESS_MARKERS: [&str; 5] = [
+    "rebase-merge",
+    "rebase-apply",
+    "MERGE_HEAD",
+    "CHERRY_PICK_HEAD",
+    "REVERT_HEAD",
+];
+
+/// Uncommitted changes set aside by [`stash_uncommitted`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuardStash {
+    repo_root: PathBuf,
+    oid: String,
+    message: String,
+    files: usize,
+}
+
+impl GuardStash {
+    /// Commit id of the stash entry.
+    pub fn oid(&self) -> &str {
+        &self.oid
+    }
+
+    /// Full stash message, starting with [`GUARD_STASH_PREFIX`].
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Number of changed paths that were stashed.
+    pub fn files(&self) -> usize {
+        self.files
+    }
+}
+
+/// Outcome of [`restore_guard_stash`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuardRestore {
+    /// The changes are back in the working tree and the stash was dropped.
+    Restored { files: usize },
+    /// Popping conflicted; conflict markers are in the working tree and the
+    /// stash was kept.
+    Conflicted { oid: String, message: String },
+    /// A rebase, merge, cherry-pick, or revert is in progress, so the stash
+    /// was left alone until it finishes.
+    Deferred { oid: String, message: String },
+    /// The stash no longer exists (the command dropped or popped it).
+    Missing { oid: String, message: String },
+}
+
+impl GuardRestore {
+    /// Human-readable report, including how to recover kept stashes.
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Restored { files } => format!(
+                "Uncommitted changes ({files} path(s)) were stashed before this command and have been restored."
+            ),
+            Self::Conflicted { oid, message } => format!(
+                "Uncommitted changes were stashed before this command, but restoring them conflicted. Resolve the conflict markers; the stash \"{message}\" ({oid}) was kept and can be re-applied with `git stash apply {oid}`."
+            ),
+            Self::Deferred { oid, message } => format!(
+                "Uncommitted changes were stashed before this command and kept as \"{message}\" ({oid}) because a rebase or merge is in progress. Run `git stash apply {oid}` once it is finished."
+            ),
+            Self::Missing { oid, message } => format!(
+                "Uncommitted changes were stashed before this command as \"{message}\", but the stash is gone. Recover it with `git stash apply {oid}` while the object still exists."
+            ),
+        }
+    }
+}
+
+/// Stashes tracked and untracked changes in the repository containing
+/// `repo_path` under a labeled message. Returns `None` for a clean tree.
+pub fn stash_uncommitted(repo_path: &Path, label: &str) -> Result<Option<GuardStash>, GitToolingError> {
+    ensure_git_repository(repo_path)?;
+    let repo_root = resolve_repository_root(repo_path)?;
+    let status = run_git_for_stdout(
+        repo_root.as_path(),
+        ["status", "--porcelain", "--untracked-files=normal"],
+        None,
+    )?;
+    let files = status.lines().filter(|line| !line.trim().is_empty()).count();
+    if files == 0 {
+        return Ok(None);
+    }
+    let message = format!("{GUARD_STASH_PREFIX} {label}");
+    run_git_for_status(
+        repo_root.as_path(),
+        ["stash", "push", "--include-untracked", "--message", message.as_str()],
+        None,
+    )?;
+    let oid = run_git_for_stdout(repo_root.as_path(), ["rev-parse", "--verify", "refs/stash"], None)?;
+    Ok(Some(GuardStash {
+        repo_root,
+        oid,
+        message,
+        files,
+    }))
+}
+
+/// Pops `stash` back into the working tree, keeping it whenever that would
+/// not restore the changes cleanly.
+pub fn restore_guard_stash(stash: &GuardStash) -> Result<GuardRestore, GitToolingError> {
+    let Some(index) = stash_index(&stash.repo_root, &stash.oid)? else {
+        return Ok(GuardRestore::Missing {
+            oid: stash.oid.clone(),
+            message: stash.message.clone(),
+        });
+    };
+    if operation_in_progress(&stash.repo_root)? {
+        return Ok(GuardRestore::Deferred {
+            oid: stash.oid.clone(),
+            message: stash.message.clone(),
+        });
+    }
+    let reference = format!("stash@{{{index}}}");
+    match run_git_for_status(stash.repo_root.as_path(), ["stash", "pop", reference.as_str()], None) {
+        Ok(()) => Ok(GuardRestore::Restored { files: stash.files }),
+        Err(err) => {
+            // A failed pop keeps the entry; anything else is unexpected.
+            if stash_index(&stash.repo_root, &stash.oid)?.is_some() {
+                Ok(GuardRestore::Conflicted {
+                    oid: stash.oid.clone(),
+                    message: stash.message.clone(),
+                })
+            } else {
+                Err(err)
+            }
+        }
+    }
+}
+
+fn stash_index(repo_root: &Path, oid: &str) -> Result<Option<usize>, GitToolingError> {
+    let list = run_git_for_stdout(repo_root, ["stash", "list", "--format=%H"], None)?;
+    Ok(list.lines().position(|line| line.trim() == oid))
+}
+
+fn operation_in_progress(repo_root: &Path) -> Result<bool, GitToolingError> {
+    for marker in IN_PROGRESS_MARKERS {
+        let path = run_git_for_stdout(repo_root, ["rev-parse", "--git-path", marker], None)?;
+        let path = PathBuf::from(path);
+        let path = if path.is_absolute() { path } else { repo_root.join(path) };
+        if path.exists() {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn git(dir: &Path, args: &[&str]) -> String {
+        let output = std::process::Command::new("git")
+            .current_dir(dir)
+            .args(["-c", "user.name=Tester", "-c", "user.email=test@example.com"])
+            .args(args)
+            .output()
+            .expect("git command");
+        assert!(output.status.success(), "git command failed: {args:?}");
+        String::from_utf8_lossy(&output.stdout).trim().to_owned()
+    }
+
+    fn init_repo(repo: &Path) -> Result<(), GitToolingError> {
+        git(repo, &["init", "-q", "-b", "main"]);
+        std::fs::write(repo.join("a.txt"), "one\n")?;
+        git(repo, &["add", "-A"]);
+        git(repo, &["commit", "-qm", "init"]);
+        Ok(())
+    }
+
+    #[test]
+    fn clean_tree_is_not_stashed() -> Result<(), GitToolingError> {
+        let temp = tempfile::tempdir()?;
+        init_repo(temp.path())?;
+        assert_eq!(stash_uncommitted(temp.path(), "noop")?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn restores_changes_after_hard_reset() -> Result<(), GitToolingError> {
+        let temp = tempfile::tempdir()?;
+        let repo = temp.path();
+        init_repo(repo)?;
+        std::fs::write(repo.join("a.txt"), "user edit\n")?;
+        std::fs::write(repo.join("new.txt"), "untracked\n")?;
+
+        let stash = stash_uncommitted(repo, "before `git reset --hard`")?.expect("dirty tree");
+        assert_eq!(stash.files(), 2);
+        assert!(stash.message().starts_with(GUARD_STASH_PREFIX));
+        git(repo, &["reset", "--hard", "-q"]);
+
+        assert_eq!(restore_guard_stash(&stash)?, GuardRestore::Restored { files: 2 });
+        assert_eq!(std::fs::read_to_string(repo.join("a.txt"))?, "user edit\n");
+        assert_eq!(std::fs::read_to_string(repo.join("new.txt"))?, "untracked\n");
+        assert_eq!(git(repo, &["stash", "list"]), "");
+        Ok(())
+    }
+
+    #[test]
+    fn keeps_stash_when_restore_conflicts() -> Result<(), GitToolingError> {
+        let temp = tempfile::tempdir()?;
+        let repo = temp.path();
+        init_repo(repo)?;
+        std::fs::write(repo.join("a.txt"), "user edit\n")?;
+
+        let stash = stash_uncommitted(repo, "before `git checkout`")?.expect("dirty tree");
+        std::fs::write(repo.join("a.txt"), "agent edit\n")?;
+        git(repo, &["commit", "-qam", "agent"]);
+
+        let restore = restore_guard_stash(&stash)?;
+        assert!(matches!(restore, GuardRestore::Conflicted { .. }));
+        assert!(restore.describe().contains(stash.oid()));
+        assert_eq!(git(repo, &["stash", "list", "--format=%H"]), stash.oid());
+        Ok(())
+    }
+}
@@ -0,0 +1,370 @@
+//! Submodule pointer changes ("bumps") and what they contain.
+//!
+//! A plain diff shows a submodule as a single gitlink line, so reviews and
+//! turn diffs would miss what actually changed inside it. These helpers list
+//! bumps between two commits, or against previously recorded submodule
+//! HEADs, and summarize the commits and files in between when the submodule
+//! is checked out locally with both commits available.
+
+use std::ffi::OsString;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::GitToolingError;
+use crate::operations::ensure_git_repository;
+use crate::operations::resolve_repository_root;
+use crate::operations::run_git_for_status;
+use crate::operations::run_git_for_stdout;
+
+const GITLINK_MODE: &str = "160000";
+const MAX_SUMMARY_COMMITS: usize = 20;
+
+/// A submodule whose recorded commit changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubmoduleChange {
+    /// Submodule path relative to the superproject root.
+    pub path: PathBuf,
+    /// Previously recorded commit; `None` when the submodule was added.
+    pub old_commit: Option<String>,
+    /// Newly recorded commit; `None` when the submodule was removed.
+    pub new_commit: Option<String>,
+    /// Commits between the two as `> subject` (added) or `< subject`
+    /// (rewound) lines. `None` when the submodule is not checked out or lacks
+    /// one of the commits.
+    pub commits: Option<Vec<String>>,
+    /// `git diff --stat` of the files changed inside the submodule, when
+    /// available.
+    pub stat: Option<String>,
+}
+
+impl SubmoduleChange {
+    /// Describes the bump like `git diff --submodule=log`, followed by the
+    /// file summary when known.
+    pub fn render(&self) -> String {
+        let path = self.path.display();
+        let old = short_commit(self.old_commit.as_deref());
+        let new = short_commit(self.new_commit.as_deref());
+        let mut out = match (&self.old_commit, &self.new_commit) {
+            (None, _) => format!("Submodule {path} {old}...{new} (new submodule)"),
+            (_, None) => format!("Submodule {path} {old}...{new} (submodule deleted)"),
+            _ => format!("Submodule {path} {old}..{new}"),
+        };
+        match &self.commits {
+            Some(commits) => {
+                out.push(':');
+                for commit in commits {
+                    let _ = write!(out, "\n  {commit}");
+                }
+            }
+            None if self.old_commit.is_some() && self.new_commit.is_some() => {
+                out.push_str(" (contents not available locally)");
+            }
+            None => {}
+        }
+        if let Some(stat) = &self.stat {
+            for line in stat.lines() {
+                let _ = write!(out, "\n  {}", line.trim());
+            }
+        }
+        out
+    }
+
+    /// The gitlink hunk git itself emits for this change, for inclusion in a
+    /// unified diff.
+    pub fn gitlink_diff(&self) -> String {
+        let path = self.path.display().to_string().replace('\\', "/");
+        let mut out = format!("diff --git a/{path} b/{path}\n");
+        match (&self.old_commit, &self.new_commit) {
+            (None, Some(new)) => {
+                let _ = write!(
+                    out,
+                    "new file mode {GITLINK_MODE}\nindex 0000000..{}\n--- /dev/null\n+++ b/{path}\n@@ -0,0 +1 @@\n+Subproject commit {new}\n",
+                    short_commit(Some(new))
+                );
+            }
+            (Some(old), None) => {
+                let _ = write!(
+                    out,
+                    "deleted file mode {GITLINK_MODE}\nindex {}..0000000\n--- a/{path}\n+++ /dev/null\n@@ -1 +0,0 @@\n-Subproject commit {old}\n",
+                    short_commit(Some(old))
+                );
+            }
+            (Some(old), Some(new)) => {
+                let _ = write!(
+                    out,
+                    "index {}..{} {GITLINK_MODE}\n--- a/{path}\n+++ b/{path}\n@@ -1 +1 @@\n-Subproject commit {old}\n+Subproject commit {new}\n",
+                    short_commit(Some(old)),
+                    short_commit(Some(new))
+                );
+            }
+            (None, None) => {}
+        }
+        out
+    }
+}
+
+fn short_commit(commit: Option<&str>) -> &str {
+    commit.map_or("0000000", |commit| commit.get(..7).unwrap_or(commit))
+}
+
+/// Lists the submodule bumps between commits `from` and `to` of the
+/// repository containing `repo_path`.
+pub fn submodule_changes(
+    repo_path: &Path,
+    from: &str,
+    to: &str,
+) -> Result<Vec<SubmoduleChange>, GitToolingError> {
+    ensure_git_repository(repo_path)?;
+    let repo_root = resolve_repository_root(repo_path)?;
+    let raw = run_git_for_stdout(
+        repo_root.as_path(),
+        ["diff", "--raw", "-z", "--no-abbrev", "--no-renames", from, to],
+        None,
+    )?;
+    Ok(parse_raw_gitlinks(&raw)
+        .into_iter()
+        .map(|(path, old, new)| summarize_bump(&repo_root, path, old, new))
+        .collect())
+}
+
+/// Records the commit each initialized submodule has checked out, relative
+/// to the superproject root. Cheap no-op for repositories without
+/// `.gitmodules`.
+pub fn submodule_heads(repo_path: &Path) -> Result<Vec<(PathBuf, String)>, GitToolingError> {
+    ensure_git_repository(repo_path)?;
+    let repo_root = resolve_repository_root(repo_path)?;
+    if !repo_root.join(".gitmodules").is_file() {
+        return Ok(Vec::new());
+    }
+    let status = run_git_for_stdout(repo_root.as_path(), ["submodule", "status"], None)?;
+    Ok(status.lines().filter_map(parse_submodule_status_line).collect())
+}
+
+/// Lists submodules whose checked-out commit moved since `baseline` was
+/// taken with [`submodule_heads`].
+pub fn submodule_changes_since(
+    repo_path: &Path,
+    baseline: &[(PathBuf, String)],
+) -> Result<Vec<SubmoduleChange>, GitToolingError> {
+    if baseline.is_empty() {
+        return Ok(Vec::new());
+    }
+    let repo_root = resolve_repository_root(repo_path)?;
+    let current = submodule_heads(repo_path)?;
+    Ok(baseline
+        .iter()
+        .filter_map(|(path, old)| {
+            let new = current
+                .iter()
+                .find(|(current_path, _)| current_path == path)
+                .map(|(_, head)| head.clone());
+            (new.as_deref() != Some(old.as_str()))
+                .then(|| summarize_bump(&repo_root, path.clone(), Some(old.clone()), new))
+        })
+        .collect())
+}
+
+/// Parses `git diff --raw -z` output, keeping entries where either side is a
+/// gitlink.
+fn parse_raw_gitlinks(raw: &str) -> Vec<(PathBuf, Option<String>, Option<String>)> {
+    let mut out = Vec::new();
+    let mut fields = raw.split('\0');
+    while let Some(meta) = fields.next() {
+        let Some(path) = fields.next() else {
+            break;
+        };
+        let parts: Vec<&str> = meta.trim_start_matches(':').split(' ').collect();
+        let [old_mode, new_mode, old_sha, new_sha, _status] = parts.as_slice() else {
+            continue;
+        };
+        if *old_mode != GITLINK_MODE && *new_mode != GITLINK_MODE {
+            continue;
+        }
+        let old = (*old_mode == GITLINK_MODE).then(|| (*old_sha).to_owned());
+        let new = (*new_mode == GITLINK_MODE).then(|| (*new_sha).to_owned());
+        out.push((PathBuf::from(path), old, new));
+    }
+    out
+}
+
+/// Parses a `git submodule status` line; uninitialized submodules (`-`) are
+/// skipped. The leading status column may already be trimmed away.
+fn parse_submodule_status_line(line: &str) -> Option<(PathBuf, String)> {
+    if line.starts_with('-') {
+        return None;
+    }
+    let line = line.trim_start_matches([' ', '+', 'U']);
+    let (sha, rest) = line.split_once(' ')?;
+    let path = match rest.rsplit_once(" (") {
+        Some((path, describe)) if describe.ends_with(')') => path,
+        _ => rest,
+    };
+    Some((PathBuf::from(path), sha.to_owned()))
+}
+
+fn summarize_bump(
+    repo_root: &Path,
+    path: PathBuf,
+    old_commit: Option<String>,
+    new_commit: Option<String>,
+) -> SubmoduleChange {
+    let mut change = SubmoduleChange {
+        path,
+        old_commit,
+        new_commit,
+        commits: None,
+        stat: None,
+    };
+    let (Some(old), Some(new)) = (change.old_commit.as_deref(), change.new_commit.as_deref()) else {
+        return change;
+    };
+    let dir = repo_root.join(&change.path);
+    if !dir.join(".git").exists() || !has_commit(&dir, old) || !has_commit(&dir, new) {
+        return change;
+    }
+    let range = format!("{old}...{new}");
+    let max_count = format!("--max-count={MAX_SUMMARY_COMMITS}");
+    change.commits = run_git_for_stdout(
+        &dir,
+        ["log", "--left-right", "--format=%m %s", max_count.as_str(), range.as_str()],
+        None,
+    )
+    .ok()
+    .map(|log| log.lines().map(ToOwned::to_owned).collect());
+    change.stat = run_git_for_stdout(&dir, ["diff", "--stat", old, new], None)
+        .ok()
+        .filter(|stat| !stat.is_empty());
+    change
+}
+
+fn has_commit(dir: &Path, commit: &str) -> bool {
+    run_git_for_status(
+        dir,
+        [
+            OsString::from("cat-file"),
+            OsString::from("-e"),
+            OsString::from(format!("{commit}^{{commit}}")),
+        ],
+        None,
+    )
+    .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const OLD: &str = "ad9fe1be4d767e5545d5cbad64eca80ed202fa84";
+    const NEW: &str = "6df860f59f179572c106c81b491b1c1ae2fe56c8";
+
+    #[test]
+    fn parses_gitlink_entries_from_raw_diff() {
+        let raw = format!(
+            ":100644 100644 {OLD} {NEW} M\0src/lib.rs\0:160000 160000 {OLD} {NEW} M\0vendor/lib\0:000000 160000 {} {NEW} A\0vendor/new\0",
+            "0".repeat(40)
+        );
+        assert_eq!(
+            parse_raw_gitlinks(&raw),
+            vec![
+                (PathBuf::from("vendor/lib"), Some(OLD.to_owned()), Some(NEW.to_owned())),
+                (PathBuf::from("vendor/new"), None, Some(NEW.to_owned())),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_submodule_status_lines() {
+        assert_eq!(
+            parse_submodule_status_line(&format!("+{NEW} vendor/my lib (heads/main)")),
+            Some((PathBuf::from("vendor/my lib"), NEW.to_owned()))
+        );
+        assert_eq!(
+            parse_submodule_status_line(&format!(" {OLD} vendor/lib")),
+            Some((PathBuf::from("vendor/lib"), OLD.to_owned()))
+        );
+        assert_eq!(
+            parse_submodule_status_line(&format!("{OLD} vendor/lib (v1.0)")),
+            Some((PathBuf::from("vendor/lib"), OLD.to_owned()))
+        );
+        assert_eq!(parse_submodule_status_line(&format!("-{OLD} vendor/uninit")), None);
+    }
+
+    fn git(dir: &Path, args: &[&str]) -> String {
+        let output = std::process::Command::new("git")
+            .current_dir(dir)
+            .args(["-c", "user.name=Tester", "-c", "user.email=test@example.com"])
+            .args(["-c", "protocol.file.allow=always"])
+            .args(args)
+            .output()
+            .expect("git command");
+        assert!(output.status.success(), "git command failed: {args:?}");
+        String::from_utf8_lossy(&output.stdout).trim().to_owned()
+    }
+
+    #[test]
+    fn summarizes_bumps_of_checked_out_submodules() -> Result<(), GitToolingError> {
+        let temp = tempfile::tempdir()?;
+        let lib = temp.path().join("lib");
+        let sup = temp.path().join("super");
+        std::fs::create_dir_all(&lib)?;
+        std::fs::create_dir_all(&sup)?;
+        git(&lib, &["init", "-q"]);
+        std::fs::write(lib.join("a.txt"), "a\n")?;
+        git(&lib, &["add", "-A"]);
+        git(&lib, &["commit", "-qm", "lib: add a"]);
+        git(&sup, &["init", "-q"]);
+        git(&sup, &["submodule", "add", "-q", lib.to_str().expect("utf-8 path"), "vendor/lib"]);
+        git(&sup, &["commit", "-qm", "add lib"]);
+        let before = git(&sup, &["rev-parse", "HEAD"]);
+        let heads = submodule_heads(&sup)?;
+        assert_eq!(heads.len(), 1);
+
+        let checkout = sup.join("vendor/lib");
+        std::fs::write(checkout.join("b.txt"), "b\n")?;
+        git(&checkout, &["add", "-A"]);
+        git(&checkout, &["commit", "-qm", "lib: add b"]);
+        assert_eq!(submodule_changes_since(&sup, &heads)?.len(), 1);
+
+        git(&sup, &["commit", "-qam", "bump lib"]);
+        let after = git(&sup, &["rev-parse", "HEAD"]);
+        let changes = submodule_changes(&sup, &before, &after)?;
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].path, PathBuf::from("vendor/lib"));
+        assert_eq!(changes[0].commits, Some(vec!["> lib: add b".to_owned()]));
+        assert!(changes[0].stat.as_deref().is_some_and(|stat| stat.contains("b.txt")));
+        Ok(())
+    }
+
+    #[test]
+    fn renders_bump_summary_and_gitlink_hunk() {
+        let change = SubmoduleChange {
+            path: PathBuf::from("vendor/lib"),
+            old_commit: Some(OLD.to_owned()),
+            new_commit: Some(NEW.to_owned()),
+            commits: Some(vec!["> add b".to_owned()]),
+            stat: Some(" b.txt | 1 +\n 1 file changed, 1 insertion(+)".to_owned()),
+        };
+        assert_eq!(
+            change.render(),
+            "Submodule vendor/lib ad9fe1b..6df860f:\n  > add b\n  b.txt | 1 +\n  1 file changed, 1 insertion(+)"
+        );
+        assert_eq!(
+            change.gitlink_diff(),
+            format!(
+                "diff --git a/vendor/lib b/vendor/lib\nindex ad9fe1b..6df860f 160000\n--- a/vendor/lib\n+++ b/vendor/lib\n@@ -1 +1 @@\n-Subproject commit {OLD}\n+Subproject commit {NEW}\n"
+            )
+        );
+
+        let unavailable = SubmoduleChange {
+            commits: None,
+            stat: None,
+            ..change
+        };
+        assert_eq!(
+            unavailable.render(),
+            "Submodule vendor/lib ad9fe1b..6df860f (contents not available locally)"
+        );
+    }
+}
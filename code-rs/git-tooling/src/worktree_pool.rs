@@ -0,0 +1,338 @@
+//! A capped pool of detached worktrees reused across background reviews.
+//!
+//! `git worktree add` checks out the whole tree, which takes seconds on large
+//! repositories. Pool slots stay on disk between reviews, so handing one out
+//! again is a hard reset plus clean onto the requested commit. A slot that
+//! fails its health check (missing, registered to another repository, or
+//! unable to reset) is rebuilt from scratch.
+//!
+//! On very large repositories a worktree can be limited to a few directories
+//! with a cone-mode sparse checkout; see [`WorktreeCheckoutOptions`].
+
+use std::ffi::OsString;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::GitToolingError;
+use crate::operations::ensure_git_repository;
+use crate::operations::resolve_repository_root;
+use crate::operations::run_git_for_status;
+use crate::operations::run_git_for_stdout;
+
+/// Slots named `<prefix>1` through `<prefix><capacity>` under one directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorktreePool {
+    dir: PathBuf,
+    prefix: String,
+    capacity: usize,
+}
+
+/// How a worktree is checked out.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorktreeCheckoutOptions {
+    /// Keep gitignored files (e.g. build outputs) when cleaning a reused
+    /// worktree.
+    pub keep_gitignored: bool,
+    /// Directories, relative to the repository root, to limit the checkout
+    /// to; files at the root are always included. `None` checks out
+    /// everything.
+    pub sparse_dirs: Option<Vec<PathBuf>>,
+}
+
+/// A slot checked out at the requested commit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PooledWorktree {
+    pub name: String,
+    pub path: PathBuf,
+    /// Whether an existing slot was reset instead of created.
+    pub reused: bool,
+    /// Time spent resetting or creating the slot.
+    pub setup_time: Duration,
+}
+
+impl WorktreePool {
+    /// A pool of at most `capacity` (at least one) worktrees in `dir`.
+    pub fn new(dir: impl Into<PathBuf>, prefix: impl Into<String>, capacity: usize) -> Self {
+        Self {
+            dir: dir.into(),
+            prefix: prefix.into(),
+            capacity: capacity.max(1),
+        }
+    }
+
+    pub fn slot_names(&self) -> Vec<String> {
+        (1..=self.capacity)
+            .map(|index| format!("{}{index}", self.prefix))
+            .collect()
+    }
+
+    /// Whether `name` is one of this pool's slots.
+    pub fn is_slot(&self, name: &str) -> bool {
+        name.strip_prefix(self.prefix.as_str())
+            .and_then(|index| index.parse::<usize>().ok())
+            .is_some_and(|index| (1..=self.capacity).contains(&index))
+    }
+
+    /// Checks out `base_ref` in the first slot `claim` accepts, trying slots
+    /// that already exist on disk before creating new ones. `claim` returns a
+    /// guard that keeps the slot reserved (e.g. a lock), or `None` when the
+    /// slot is in use. Returns `Ok(None)` when every slot is busy.
+    pub fn checkout<G>(
+        &self,
+        repo_path: &Path,
+        base_ref: &str,
+        options: &WorktreeCheckoutOptions,
+        mut claim: impl FnMut(&Path) -> Option<G>,
+    ) -> Result<Option<(PooledWorktree, G)>, GitToolingError> {
+        ensure_git_repository(repo_path)?;
+        std::fs::create_dir_all(&self.dir)?;
+
+        let (mut order, cold): (Vec<_>, Vec<_>) = self
+            .slot_names()
+            .into_iter()
+            .partition(|name| self.dir.join(name).exists());
+        order.extend(cold);
+
+        for name in order {
+            let path = self.dir.join(&name);
+            let Some(guard) = claim(&path) else {
+                continue;
+            };
+            let started = Instant::now();
+            let reused = checkout_worktree(repo_path, &path, base_ref, options)?;
+            let worktree = PooledWorktree {
+                name,
+                path,
+                reused,
+                setup_time: started.elapsed(),
+            };
+            return Ok(Some((worktree, guard)));
+        }
+        Ok(None)
+    }
+}
+
+/// Checks out `base_ref` in the detached worktree at `path`, resetting it in
+/// place when it is a healthy worktree of the repository at `repo_path` and
+/// recreating it otherwise. Returns whether the existing worktree was reused.
+pub fn checkout_worktree(
+    repo_path: &Path,
+    path: &Path,
+    base_ref: &str,
+    options: &WorktreeCheckoutOptions,
+) -> Result<bool, GitToolingError> {
+    ensure_git_repository(repo_path)?;
+    let repo_root = resolve_repository_root(repo_path)?;
+    let common_dir = git_common_dir(&repo_root)?;
+    if is_healthy_slot(path, &common_dir) && reset_slot(path, base_ref, options).is_ok() {
+        return Ok(true);
+    }
+    rebuild_slot(&repo_root, path, base_ref, options)?;
+    Ok(false)
+}
+
+/// Directories holding the files `commit` changed relative to its first
+/// parent, suitable for [`WorktreeCheckoutOptions::sparse_dirs`]. Root-level
+/// files need no entry.
+pub fn changed_dirs(repo_path: &Path, commit: &str) -> Result<Vec<PathBuf>, GitToolingError> {
+    let output = run_git_for_stdout(
+        repo_path,
+        ["diff-tree", "-r", "--root", "--no-commit-id", "--name-only", commit],
+        None,
+    )?;
+    let mut dirs: Vec<PathBuf> = output
+        .lines()
+        .filter_map(|line| Path::new(line.trim()).parent())
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .map(Path::to_path_buf)
+        .collect();
+    dirs.sort();
+    dirs.dedup();
+    Ok(dirs)
+}
+
+fn git_common_dir(dir: &Path) -> Result<PathBuf, GitToolingError> {
+    let raw = run_git_for_stdout(dir, ["rev-parse", "--git-common-dir"], None)?;
+    let path = dir.join(raw);
+    Ok(path.canonicalize().unwrap_or(path))
+}
+
+/// A slot is healthy when it is a worktree of the same repository.
+fn is_healthy_slot(path: &Path, repo_common_dir: &Path) -> bool {
+    path.join(".git").is_file()
+        && git_common_dir(path).is_ok_and(|common| common == repo_common_dir)
+}
+
+fn reset_slot(path: &Path, base_ref: &str, options: &WorktreeCheckoutOptions) -> Result<(), GitToolingError> {
+    apply_sparse_dirs(path, options.sparse_dirs.as_deref())?;
+    run_git_for_status(path, ["reset", "--hard", "--quiet", base_ref], None)?;
+    let clean_flags = if options.keep_gitignored { "-fdq" } else { "-fdxq" };
+    run_git_for_status(path, ["clean", clean_flags], None)
+}
+
+/// Narrows the worktree to `dirs`, or restores a full checkout for `None`.
+/// Sparse settings are per worktree and leave the main checkout alone.
+fn apply_sparse_dirs(path: &Path, dirs: Option<&[PathBuf]>) -> Result<(), GitToolingError> {
+    let Some(dirs) = dirs else {
+        let sparse = run_git_for_stdout(path, ["config", "--bool", "core.sparseCheckout"], None)
+            .is_ok_and(|value| value == "true");
+        if sparse {
+            run_git_for_status(path, ["sparse-checkout", "disable"], None)?;
+        }
+        return Ok(());
+    };
+    let mut args = vec![
+        OsString::from("sparse-checkout"),
+        OsString::from("set"),
+        OsString::from("--cone"),
+    ];
+    args.extend(dirs.iter().map(|dir| dir.as_os_str().to_owned()));
+    run_git_for_status(path, args, None)
+}
+
+fn rebuild_slot(
+    repo_root: &Path,
+    path: &Path,
+    base_ref: &str,
+    options: &WorktreeCheckoutOptions,
+) -> Result<(), GitToolingError> {
+    if path.exists() {
+        let remove = [
+            OsString::from("worktree"),
+            OsString::from("remove"),
+            OsString::from("--force"),
+            path.as_os_str().to_owned(),
+        ];
+        // Fails for slots git no longer knows about; the directory goes below.
+        let _ = run_git_for_status(repo_root, remove, None);
+        if path.exists() {
+            std::fs::remove_dir_all(path)?;
+        }
+    }
+    run_git_for_status(repo_root, ["worktree", "prune"], None)?;
+    let sparse = options.sparse_dirs.is_some();
+    let mut add = vec![
+        OsString::from("worktree"),
+        OsString::from("add"),
+        OsString::from("--detach"),
+    ];
+    if sparse {
+        // Populate only the sparse cone below instead of the whole tree.
+        add.push(OsString::from("--no-checkout"));
+    }
+    add.push(path.as_os_str().to_owned());
+    add.push(OsString::from(base_ref));
+    run_git_for_status(repo_root, add, None)?;
+    if sparse {
+        apply_sparse_dirs(path, options.sparse_dirs.as_deref())?;
+        run_git_for_status(path, ["reset", "--hard", "--quiet", base_ref], None)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::process::Command;
+
+    fn run_git_in(repo_path: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .current_dir(repo_path)
+            .args(args)
+            .status()
+            .expect("git command");
+        assert!(status.success(), "git command failed: {args:?}");
+    }
+
+    fn commit_file(repo: &Path, contents: &str) -> String {
+        std::fs::write(repo.join("file.txt"), contents).expect("write file");
+        run_git_in(repo, &["add", "-A"]);
+        run_git_in(
+            repo,
+            &["-c", "user.name=Tester", "-c", "user.email=test@example.com", "commit", "-qm", contents],
+        );
+        run_git_for_stdout(repo, ["rev-parse", "HEAD"], None).expect("rev-parse")
+    }
+
+    #[test]
+    fn slot_names_are_capped() {
+        let pool = WorktreePool::new("/tmp/pool", "auto-review-", 2);
+        assert_eq!(pool.slot_names(), vec!["auto-review-1", "auto-review-2"]);
+        assert!(pool.is_slot("auto-review-2"));
+        assert!(!pool.is_slot("auto-review-3"));
+        assert!(!pool.is_slot("auto-review-deadbeef"));
+    }
+
+    #[test]
+    fn reuses_warm_slots_and_rebuilds_broken_ones() -> Result<(), GitToolingError> {
+        let temp = tempfile::tempdir()?;
+        let repo = temp.path().join("repo");
+        std::fs::create_dir_all(&repo)?;
+        run_git_in(&repo, &["init", "-q"]);
+        let first = commit_file(&repo, "one\n");
+        let second = commit_file(&repo, "two\n");
+        let pool = WorktreePool::new(temp.path().join("pool"), "slot-", 2);
+        let keep = WorktreeCheckoutOptions {
+            keep_gitignored: true,
+            ..WorktreeCheckoutOptions::default()
+        };
+
+        let (cold, ()) = pool.checkout(&repo, &first, &keep, |_| Some(()))?.expect("slot");
+        assert!(!cold.reused);
+        assert_eq!(cold.name, "slot-1");
+        std::fs::write(cold.path.join("scratch.txt"), "leftover")?;
+
+        let (warm, ()) = pool.checkout(&repo, &second, &keep, |_| Some(()))?.expect("slot");
+        assert!(warm.reused);
+        assert_eq!(warm.path, cold.path);
+        assert_eq!(std::fs::read_to_string(warm.path.join("file.txt"))?, "two\n");
+        assert!(!warm.path.join("scratch.txt").exists());
+
+        std::fs::remove_file(warm.path.join(".git"))?;
+        let (rebuilt, ()) = pool.checkout(&repo, &first, &keep, |_| Some(()))?.expect("slot");
+        assert_eq!(rebuilt.name, "slot-1");
+        assert!(!rebuilt.reused);
+        assert_eq!(std::fs::read_to_string(rebuilt.path.join("file.txt"))?, "one\n");
+
+        let (other, ()) = pool
+            .checkout(&repo, &first, &keep, |path| (!path.ends_with("slot-1")).then_some(()))?
+            .expect("slot");
+        assert_eq!(other.name, "slot-2");
+
+        assert!(pool.checkout(&repo, &first, &keep, |_| None::<()>)?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn sparse_checkout_limits_to_changed_dirs() -> Result<(), GitToolingError> {
+        let temp = tempfile::tempdir()?;
+        let repo = temp.path().join("repo");
+        for dir in ["big/assets", "src"] {
+            std::fs::create_dir_all(repo.join(dir))?;
+        }
+        run_git_in(&repo, &["init", "-q"]);
+        std::fs::write(repo.join("big/assets/blob.bin"), "large")?;
+        std::fs::write(repo.join("src/lib.rs"), "fn a() {}\n")?;
+        commit_file(&repo, "base\n");
+        std::fs::write(repo.join("src/lib.rs"), "fn b() {}\n")?;
+        let head = commit_file(&repo, "change\n");
+        assert_eq!(changed_dirs(&repo, &head)?, vec![PathBuf::from("src")]);
+
+        let worktree = temp.path().join("review");
+        let sparse = WorktreeCheckoutOptions {
+            keep_gitignored: true,
+            sparse_dirs: Some(changed_dirs(&repo, &head)?),
+        };
+        assert!(!checkout_worktree(&repo, &worktree, &head, &sparse)?);
+        assert!(worktree.join("src/lib.rs").exists());
+        assert!(worktree.join("file.txt").exists());
+        assert!(!worktree.join("big").exists());
+
+        assert!(checkout_worktree(&repo, &worktree, &head, &WorktreeCheckoutOptions::default())?);
+        assert!(worktree.join("big/assets/blob.bin").exists());
+        Ok(())
+    }
+}
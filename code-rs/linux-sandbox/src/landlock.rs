@@ -1,4 +1,5 @@
 use std::collections::BTreeMap;
+use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 use std::path::PathBuf;
@@ -6,13 +7,16 @@ use std::path::PathBuf;
 use code_core::error::CodexErr;
 use code_core::error::Result;
 use code_core::error::SandboxErr;
+use code_core::managed_network_proxy_api::proxy_loopback_ports_from_env;
 use code_core::protocol::SandboxPolicy;
 
 use landlock::ABI;
 use landlock::Access;
 use landlock::AccessFs;
+use landlock::AccessNet;
 use landlock::CompatLevel;
 use landlock::Compatible;
+use landlock::NetPort;
 use landlock::Ruleset;
 use landlock::RulesetAttr;
 use landlock::RulesetCreatedAttr;
@@ -33,7 +37,20 @@ pub(crate) fn apply_sandbox_policy_to_current_thread(
     cwd: &Path,
 ) -> Result<()> {
     if !sandbox_policy.has_full_network_access() {
-        install_network_seccomp_filter_on_current_thread()?;
+        // With a network allowlist, commands may open TCP connections to the
+        // managed proxy's ports and nothing else. Kernels without Landlock
+        // network rules keep the network closed.
+        let proxy_ports = if sandbox_policy.network_allowlist().is_empty() {
+            Vec::new()
+        } else {
+            let env: HashMap<String, String> = std::env::vars().collect();
+            proxy_loopback_ports_from_env(&env)
+        };
+        if !proxy_ports.is_empty() && install_proxy_only_landlock_rules_on_current_thread(&proxy_ports)? {
+            install_network_seccomp_filter_on_current_thread(NetworkFilter::TcpOnly)?;
+        } else {
+            install_network_seccomp_filter_on_current_thread(NetworkFilter::DenyAll)?;
+        }
     }
 
     if !sandbox_policy.has_full_disk_write_access() {
@@ -118,9 +135,35 @@ fn gpu_device_paths(dev_root: &Path) -> Vec<PathBuf> {
     paths
 }
 
+/// Limits TCP connections to `ports`, where the managed proxy listens on
+/// loopback. Returns `false` when the kernel cannot enforce Landlock network
+/// rules.
+fn install_proxy_only_landlock_rules_on_current_thread(ports: &[u16]) -> Result<bool> {
+    let mut ruleset = Ruleset::default()
+        .set_compatibility(CompatLevel::BestEffort)
+        .handle_access(AccessNet::ConnectTcp)?
+        .create()?;
+    for port in ports {
+        ruleset = ruleset.add_rule(NetPort::new(*port, AccessNet::ConnectTcp))?;
+    }
+    let status = ruleset.restrict_self()?;
+    Ok(status.ruleset == landlock::RulesetStatus::FullyEnforced)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum NetworkFilter {
+    /// Only AF_UNIX sockets.
+    DenyAll,
+    /// AF_UNIX sockets plus TCP client sockets; Landlock decides where TCP
+    /// may connect.
+    TcpOnly,
+}
+
 /// Installs a seccomp filter that blocks outbound network access except for
-/// AF_UNIX domain sockets.
-fn install_network_seccomp_filter_on_current_thread() -> std::result::Result<(), SandboxErr> {
+/// AF_UNIX domain sockets (and TCP client sockets with `NetworkFilter::TcpOnly`).
+fn install_network_seccomp_filter_on_current_thread(
+    mode: NetworkFilter,
+) -> std::result::Result<(), SandboxErr> {
     // Build rule map.
     let mut rules: BTreeMap<i64, Vec<SeccompRule>> = BTreeMap::new();
 
@@ -129,25 +172,27 @@ fn install_network_seccomp_filter_on_current_thread() -> std::result::Result<(),
         rules.insert(nr, vec![]); // empty rule vec = unconditional match
     };
 
-    deny_syscall(libc::SYS_connect);
     deny_syscall(libc::SYS_accept);
     deny_syscall(libc::SYS_accept4);
     deny_syscall(libc::SYS_bind);
     deny_syscall(libc::SYS_listen);
-    deny_syscall(libc::SYS_getpeername);
-    deny_syscall(libc::SYS_getsockname);
-    deny_syscall(libc::SYS_shutdown);
-    deny_syscall(libc::SYS_sendto);
-    deny_syscall(libc::SYS_sendmsg);
-    deny_syscall(libc::SYS_sendmmsg);
-    // NOTE: allowing recvfrom allows some tools like: `cargo clippy` to run
-    // with their socketpair + child processes for sub-proc management
-    // deny_syscall(libc::SYS_recvfrom);
-    deny_syscall(libc::SYS_recvmsg);
-    deny_syscall(libc::SYS_recvmmsg);
-    deny_syscall(libc::SYS_getsockopt);
-    deny_syscall(libc::SYS_setsockopt);
     deny_syscall(libc::SYS_ptrace);
+    if mode == NetworkFilter::DenyAll {
+        deny_syscall(libc::SYS_connect);
+        deny_syscall(libc::SYS_getpeername);
+        deny_syscall(libc::SYS_getsockname);
+        deny_syscall(libc::SYS_shutdown);
+        deny_syscall(libc::SYS_sendto);
+        deny_syscall(libc::SYS_sendmsg);
+        deny_syscall(libc::SYS_sendmmsg);
+        // NOTE: allowing recvfrom allows some tools like: `cargo clippy` to run
+        // with their socketpair + child processes for sub-proc management
+        // deny_syscall(libc::SYS_recvfrom);
+        deny_syscall(libc::SYS_recvmsg);
+        deny_syscall(libc::SYS_recvmmsg);
+        deny_syscall(libc::SYS_getsockopt);
+        deny_syscall(libc::SYS_setsockopt);
+    }
 
     // For `socket` we allow AF_UNIX (arg0 == AF_UNIX) and deny everything else.
     let unix_only_rule = SeccompRule::new(vec![SeccompCondition::new(
@@ -157,7 +202,11 @@ fn install_network_seccomp_filter_on_current_thread() -> std::result::Result<(),
         libc::AF_UNIX as u64,
     )?])?;
 
-    rules.insert(libc::SYS_socket, vec![unix_only_rule.clone()]);
+    let socket_rules = match mode {
+        NetworkFilter::DenyAll => vec![unix_only_rule.clone()],
+        NetworkFilter::TcpOnly => tcp_client_socket_rules()?,
+    };
+    rules.insert(libc::SYS_socket, socket_rules);
     rules.insert(libc::SYS_socketpair, vec![unix_only_rule]); // always deny (Unix can use socketpair but fine, keep open?)
 
     let filter = SeccompFilter::new(
@@ -180,6 +229,42 @@ fn install_network_seccomp_filter_on_current_thread() -> std::result::Result<(),
     Ok(())
 }
 
+/// `socket` rules for `NetworkFilter::TcpOnly`: deny domains other than
+/// AF_UNIX, AF_INET and AF_INET6, and any non-stream inet socket, so UDP and
+/// raw sockets cannot bypass the Landlock TCP rules.
+fn tcp_client_socket_rules() -> std::result::Result<Vec<SeccompRule>, SandboxErr> {
+    let mut rules = vec![SeccompRule::new(
+        [libc::AF_UNIX, libc::AF_INET, libc::AF_INET6]
+            .into_iter()
+            .map(|domain| {
+                SeccompCondition::new(0, SeccompCmpArgLen::Dword, SeccompCmpOp::Ne, domain as u64)
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?,
+    )?];
+    // The socket type shares its argument with SOCK_NONBLOCK/SOCK_CLOEXEC.
+    const SOCK_TYPE_MASK: u64 = 0xf;
+    for domain in [libc::AF_INET, libc::AF_INET6] {
+        for sock_type in [
+            libc::SOCK_DGRAM,
+            libc::SOCK_RAW,
+            libc::SOCK_RDM,
+            libc::SOCK_SEQPACKET,
+            libc::SOCK_PACKET,
+        ] {
+            rules.push(SeccompRule::new(vec![
+                SeccompCondition::new(0, SeccompCmpArgLen::Dword, SeccompCmpOp::Eq, domain as u64)?,
+                SeccompCondition::new(
+                    1,
+                    SeccompCmpArgLen::Dword,
+                    SeccompCmpOp::MaskedEq(SOCK_TYPE_MASK),
+                    sock_type as u64,
+                )?,
+            ])?);
+        }
+    }
+    Ok(rules)
+}
+
 #[cfg(test)]
 mod tests {
     use super::gpu_device_paths;
@@ -0,0 +1,152 @@
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Result;
+use anyhow::anyhow;
+use tokio::time::Instant;
+
+/// Opens after `failure_threshold` consecutive failed calls. While open, calls
+/// fail immediately; once `cooldown` has passed a single trial call is let
+/// through (half-open), and its outcome closes or re-opens the circuit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitBreakerConfig {
+    pub failure_threshold: u32,
+    pub cooldown: Duration,
+}
+
+/// Why an open circuit is rejecting calls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitStatus {
+    pub consecutive_failures: u32,
+    /// Time left before the next trial call; zero once the circuit is
+    /// half-open.
+    pub retry_in: Duration,
+}
+
+impl CircuitStatus {
+    pub fn describe(&self) -> String {
+        let failures = self.consecutive_failures;
+        if self.retry_in.is_zero() {
+            format!(
+                "circuit half-open after {failures} consecutive failed calls; the next call is a trial"
+            )
+        } else {
+            format!(
+                "circuit open after {failures} consecutive failed calls; retrying in {}s",
+                self.retry_in.as_secs().max(1)
+            )
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct BreakerState {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+    trial_in_flight: bool,
+}
+
+#[derive(Debug)]
+pub(crate) struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    state: Mutex<BreakerState>,
+}
+
+impl CircuitBreaker {
+    pub(crate) fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config: CircuitBreakerConfig {
+                failure_threshold: config.failure_threshold.max(1),
+                cooldown: config.cooldown,
+            },
+            state: Mutex::new(BreakerState::default()),
+        }
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, BreakerState> {
+        match self.state.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// Admits a call, or fails fast while the circuit is open. Returns `true`
+    /// when the admitted call is the half-open trial.
+    pub(crate) fn admit(&self) -> Result<bool> {
+        let mut state = self.lock_state();
+        let Some(open_until) = state.open_until else {
+            return Ok(false);
+        };
+        let status = CircuitStatus {
+            consecutive_failures: state.consecutive_failures,
+            retry_in: open_until.saturating_duration_since(Instant::now()),
+        };
+        if !status.retry_in.is_zero() || state.trial_in_flight {
+            return Err(anyhow!("MCP server unavailable: {}", status.describe()));
+        }
+        state.trial_in_flight = true;
+        Ok(true)
+    }
+
+    pub(crate) fn record(&self, success: bool) {
+        let mut state = self.lock_state();
+        if success {
+            *state = BreakerState::default();
+            return;
+        }
+        state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+        // A failed trial re-opens the circuit straight away.
+        if state.trial_in_flight || state.consecutive_failures >= self.config.failure_threshold {
+            state.open_until = Some(Instant::now() + self.config.cooldown);
+        }
+        state.trial_in_flight = false;
+    }
+
+    /// Gives the trial slot back when the trial call ended without an outcome
+    /// (for example, it was cancelled).
+    pub(crate) fn release_trial(&self) {
+        self.lock_state().trial_in_flight = false;
+    }
+
+    pub(crate) fn status(&self) -> Option<CircuitStatus> {
+        let state = self.lock_state();
+        state.open_until.map(|open_until| CircuitStatus {
+            consecutive_failures: state.consecutive_failures,
+            retry_in: open_until.saturating_duration_since(Instant::now()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn opens_after_threshold_and_half_opens_after_cooldown() {
+        let breaker = CircuitBreaker::new(CircuitBreakerConfig {
+            failure_threshold: 2,
+            cooldown: Duration::from_secs(30),
+        });
+
+        assert!(!breaker.admit().expect("closed"));
+        breaker.record(false);
+        assert_eq!(breaker.status(), None);
+        breaker.record(false);
+        let status = breaker.status().expect("open");
+        assert_eq!(status.consecutive_failures, 2);
+        assert!(breaker.admit().is_err());
+
+        tokio::time::advance(Duration::from_secs(30)).await;
+        assert!(breaker.admit().expect("trial admitted"));
+        assert!(breaker.admit().is_err(), "only one trial at a time");
+
+        // A failed trial re-opens the circuit for another cooldown.
+        breaker.record(false);
+        assert!(breaker.admit().is_err());
+        tokio::time::advance(Duration::from_secs(30)).await;
+        assert!(breaker.admit().expect("second trial"));
+        breaker.record(true);
+        assert_eq!(breaker.status(), None);
+        assert!(!breaker.admit().expect("closed again"));
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::VecDeque;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
@@ -7,6 +8,20 @@ use anyhow::anyhow;
 use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 use tokio::time::{Instant, sleep_until, timeout};
 
+mod circuit_breaker;
+
+use circuit_breaker::CircuitBreaker;
+pub use circuit_breaker::{CircuitBreakerConfig, CircuitStatus};
+
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Limits that only apply to a whole server, not to individual tools.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct McpServerLimits {
+    pub max_calls_per_minute: Option<u32>,
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+}
+
 #[derive(Debug)]
 pub struct McpCallLimiter {
     semaphore: Arc<Semaphore>,
@@ -15,6 +30,10 @@ pub struct McpCallLimiter {
     queue_timeout: Option<Duration>,
     max_queue_depth: Option<u32>,
     queued: AtomicUsize,
+    max_calls_per_minute: Option<u32>,
+    /// Start times reserved within the last rate window, oldest first.
+    recent_starts: Mutex<VecDeque<Instant>>,
+    breaker: Option<CircuitBreaker>,
 }
 
 impl McpCallLimiter {
@@ -23,6 +42,22 @@ impl McpCallLimiter {
         min_interval: Option<Duration>,
         queue_timeout: Option<Duration>,
         max_queue_depth: Option<u32>,
+    ) -> Arc<Self> {
+        Self::new_for_server(
+            max_concurrent,
+            min_interval,
+            queue_timeout,
+            max_queue_depth,
+            McpServerLimits::default(),
+        )
+    }
+
+    pub fn new_for_server(
+        max_concurrent: u32,
+        min_interval: Option<Duration>,
+        queue_timeout: Option<Duration>,
+        max_queue_depth: Option<u32>,
+        limits: McpServerLimits,
     ) -> Arc<Self> {
         Arc::new(Self {
             semaphore: Arc::new(Semaphore::new(max_concurrent.max(1) as usize)),
@@ -31,9 +66,42 @@ impl McpCallLimiter {
             queue_timeout,
             max_queue_depth,
             queued: AtomicUsize::new(0),
+            max_calls_per_minute: limits.max_calls_per_minute.map(|max| max.max(1)),
+            recent_starts: Mutex::new(VecDeque::new()),
+            breaker: limits.circuit_breaker.map(CircuitBreaker::new),
         })
     }
 
+    /// `Some` while the circuit breaker is open or half-open.
+    pub fn circuit_status(&self) -> Option<CircuitStatus> {
+        self.breaker.as_ref().and_then(CircuitBreaker::status)
+    }
+
+    /// Reserves the earliest start at or after `earliest` that keeps the
+    /// calls in any rate window within `max_calls_per_minute`.
+    fn reserve_rate_slot(&self, earliest: Instant) -> Instant {
+        let Some(max_calls) = self.max_calls_per_minute else {
+            return earliest;
+        };
+        let mut starts = match self.recent_starts.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        // Never reserve before an earlier reservation, so the queue stays
+        // sorted and its front is always the oldest start.
+        let mut scheduled = starts.back().map_or(earliest, |last| earliest.max(*last));
+        while starts.front().is_some_and(|front| *front + RATE_WINDOW <= scheduled) {
+            starts.pop_front();
+        }
+        if starts.len() >= max_calls as usize
+            && let Some(oldest) = starts.pop_front()
+        {
+            scheduled = scheduled.max(oldest + RATE_WINDOW);
+        }
+        starts.push_back(scheduled);
+        scheduled
+    }
+
     fn min_interval(&self) -> Duration { self.min_interval }
 
     fn queue_timeout(&self) -> Option<Duration> { self.queue_timeout }
@@ -46,6 +114,22 @@ impl McpCallLimiter {
     }
 
     async fn acquire(self: &Arc<Self>) -> Result<McpLimiterGuard> {
+        // Fail fast while the circuit is open instead of queueing.
+        let trial = match &self.breaker {
+            Some(breaker) => breaker.admit()?,
+            None => false,
+        };
+        let acquired = self.acquire_permit(trial).await;
+        if acquired.is_err()
+            && trial
+            && let Some(breaker) = &self.breaker
+        {
+            breaker.release_trial();
+        }
+        acquired
+    }
+
+    async fn acquire_permit(self: &Arc<Self>, trial: bool) -> Result<McpLimiterGuard> {
         let queued_guard = QueuedGuard::enter(Arc::clone(self))?;
 
         let entered_at = Instant::now();
@@ -66,6 +150,8 @@ impl McpCallLimiter {
             _queued_guard: queued_guard,
             _permit: permit,
             entered_at,
+            trial,
+            recorded: false,
         })
     }
 }
@@ -75,12 +161,34 @@ pub struct McpLimiterGuard {
     _queued_guard: QueuedGuard,
     _permit: OwnedSemaphorePermit,
     entered_at: Instant,
+    /// Whether this call is the circuit breaker's half-open trial.
+    trial: bool,
+    recorded: bool,
 }
 
 impl McpLimiterGuard {
     fn limiter(&self) -> &Arc<McpCallLimiter> { &self.limiter }
 
     fn entered_at(&self) -> Instant { self.entered_at }
+
+    /// Feeds the outcome of the call into the circuit breaker, if any.
+    pub fn record_outcome(&mut self, success: bool) {
+        if let Some(breaker) = &self.limiter.breaker {
+            breaker.record(success);
+        }
+        self.recorded = true;
+    }
+}
+
+impl Drop for McpLimiterGuard {
+    fn drop(&mut self) {
+        if self.trial
+            && !self.recorded
+            && let Some(breaker) = &self.limiter.breaker
+        {
+            breaker.release_trial();
+        }
+    }
 }
 
 pub async fn acquire_and_schedule(
@@ -98,13 +206,17 @@ pub async fn acquire_and_schedule(
         // Lock order matters to avoid deadlocks: always lock server first.
         let mut server_next = server_guard.limiter().lock_next_start();
         let mut tool_next = tool_guard.limiter().lock_next_start();
-        let scheduled = (*server_next).max((*tool_next).max(now));
+        let scheduled = server_guard
+            .limiter()
+            .reserve_rate_slot((*server_next).max((*tool_next).max(now)));
         *server_next = scheduled + server_guard.limiter().min_interval();
         *tool_next = scheduled + tool_guard.limiter().min_interval();
         scheduled
     } else {
         let mut server_next = server_guard.limiter().lock_next_start();
-        let scheduled = (*server_next).max(now);
+        let scheduled = server_guard
+            .limiter()
+            .reserve_rate_slot((*server_next).max(now));
         *server_next = scheduled + server_guard.limiter().min_interval();
         scheduled
     };
@@ -199,5 +311,54 @@ mod tests {
         let result = second.await.expect("join");
         assert!(result.is_err());
     }
+
+    #[tokio::test(start_paused = true)]
+    async fn max_calls_per_minute_delays_call_past_the_window() {
+        let limits = McpServerLimits {
+            max_calls_per_minute: Some(2),
+            circuit_breaker: None,
+        };
+        let limiter = McpCallLimiter::new_for_server(10, None, None, None, limits);
+
+        let _first = acquire_and_schedule(&limiter, None).await.expect("first");
+        let _second = acquire_and_schedule(&limiter, None).await.expect("second");
+
+        let limiter3 = Arc::clone(&limiter);
+        let third = tokio::spawn(async move { acquire_and_schedule(&limiter3, None).await });
+        tokio::task::yield_now().await;
+        assert!(!third.is_finished());
+
+        tokio::time::advance(Duration::from_secs(59)).await;
+        tokio::task::yield_now().await;
+        assert!(!third.is_finished());
+
+        tokio::time::advance(Duration::from_secs(1)).await;
+        let _third = third.await.expect("join").expect("third");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn open_circuit_fails_fast_until_trial_succeeds() {
+        let limits = McpServerLimits {
+            max_calls_per_minute: None,
+            circuit_breaker: Some(CircuitBreakerConfig {
+                failure_threshold: 1,
+                cooldown: Duration::from_secs(10),
+            }),
+        };
+        let limiter = McpCallLimiter::new_for_server(10, None, None, None, limits);
+
+        let (mut failed, _tool) = acquire_and_schedule(&limiter, None).await.expect("first");
+        failed.record_outcome(false);
+        drop(failed);
+        assert!(limiter.circuit_status().is_some());
+        assert!(acquire_and_schedule(&limiter, None).await.is_err());
+
+        tokio::time::advance(Duration::from_secs(10)).await;
+        // A trial dropped without an outcome gives its slot back.
+        drop(acquire_and_schedule(&limiter, None).await.expect("trial"));
+        let (mut trial, _tool) = acquire_and_schedule(&limiter, None).await.expect("retry trial");
+        trial.record_outcome(true);
+        assert_eq!(limiter.circuit_status(), None);
+    }
 }
 
@@ -21,7 +21,8 @@ use uuid::Uuid;
 
 use crate::outgoing_message::OutgoingMessageSender;
 use crate::outgoing_message::OutgoingNotification;
-use crate::session_store::{SessionEntry, SessionMap};
+use crate::resources::record_event;
+use crate::session_store::{EventLog, SessionEntry, SessionMap};
 
 pub async fn new_session(
     request_id: RequestId,
@@ -62,7 +63,9 @@ pub async fn new_session(
 
 pub async fn prompt(
     acp_session_id: acp::SessionId,
+    session_uuid: Uuid,
     codex: Arc<CodexConversation>,
+    events: Arc<EventLog>,
     prompt: Vec<acp::ContentBlock>,
     outgoing: Arc<OutgoingMessageSender>,
 ) -> Result<acp::StopReason> {
@@ -83,6 +86,7 @@ pub async fn prompt(
 
     loop {
         let event = codex.next_event().await?;
+        record_event(&outgoing, session_uuid, &events, &event).await;
 
         let acp_update = match event.msg {
             EventMsg::Error(error_event) => {
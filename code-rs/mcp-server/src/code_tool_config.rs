@@ -280,6 +280,7 @@ impl CodexToolCallParam {
             dynamic_tools,
             compact_prompt_override: None,
             compact_prompt_override_file: None,
+            sandbox_preset: None,
         };
 
         let cli_overrides = cli_overrides
@@ -40,7 +40,8 @@ use crate::outgoing_message::OutgoingMessageSenderExt;
 use crate::outgoing_message::OutgoingNotificationMeta;
 use crate::patch_approval::PatchApprovalRequestContext;
 use crate::patch_approval::handle_patch_approval_request;
-use crate::session_store::{SessionEntry, SessionMap};
+use crate::resources::record_event;
+use crate::session_store::{EventLog, SessionEntry, SessionMap};
 
 pub(crate) const INVALID_PARAMS_ERROR_CODE: i64 = -32602;
 
@@ -80,6 +81,7 @@ pub async fn run_code_tool_session(
     };
     let session_uuid: Uuid = conversation_id.into();
     let entry = SessionEntry::new(conversation.clone(), config_for_session);
+    let events = entry.events.clone();
     session_map.lock().await.insert(session_uuid, entry);
 
     let session_configured_event = Event {
@@ -89,6 +91,7 @@ pub async fn run_code_tool_session(
         msg: EventMsg::SessionConfigured(session_configured.clone()),
         order: None,
     };
+    record_event(&outgoing, session_uuid, &events, &session_configured_event).await;
     outgoing
         .send_event_as_notification(
             &session_configured_event,
@@ -129,12 +132,15 @@ pub async fn run_code_tool_session(
         outgoing,
         id,
         running_requests_id_to_code_uuid,
+        session_uuid,
+        events,
     )
     .await;
 }
 
 pub async fn run_code_tool_session_reply(
     conversation: Arc<CodexConversation>,
+    events: Arc<EventLog>,
     outgoing: Arc<OutgoingMessageSender>,
     request_id: RequestId,
     prompt: String,
@@ -166,6 +172,8 @@ pub async fn run_code_tool_session_reply(
         outgoing,
         request_id,
         running_requests_id_to_code_uuid,
+        session_id,
+        events,
     )
     .await;
 }
@@ -175,6 +183,8 @@ async fn run_code_tool_session_inner(
     outgoing: Arc<OutgoingMessageSender>,
     request_id: RequestId,
     running_requests_id_to_code_uuid: Arc<Mutex<HashMap<RequestId, Uuid>>>,
+    session_id: Uuid,
+    events: Arc<EventLog>,
 ) {
     let request_id_str = match &request_id {
         RequestId::String(s) => s.clone(),
@@ -186,6 +196,7 @@ async fn run_code_tool_session_inner(
     loop {
         match codex.next_event().await {
             Ok(event) => {
+                record_event(&outgoing, session_id, &events, &event).await;
                 outgoing
                     .send_event_as_notification(
                         &event,
@@ -342,15 +353,23 @@ async fn run_code_tool_session_inner(
                     | EventMsg::TaskStarted
                     | EventMsg::TokenCount(_)
                     | EventMsg::AutoContextCheck(_)
+                    | EventMsg::Heartbeat(_)
                     | EventMsg::AgentReasoning(_)
                     | EventMsg::AgentReasoningSectionBreak(_)
                     | EventMsg::McpToolCallBegin(_)
                     | EventMsg::McpToolCallEnd(_)
                     | EventMsg::McpListToolsResponse(_)
+                    | EventMsg::McpAuthPrompt(_)
                     | EventMsg::ExecCommandBegin(_)
                     | EventMsg::ExecCommandOutputDelta(_)
                     | EventMsg::ExecCommandEnd(_)
+                    | EventMsg::SandboxDenied(_)
                     | EventMsg::BackgroundEvent(_)
+                    | EventMsg::GitHeadChanged(_)
+                    | EventMsg::TurnReverted(_)
+                    | EventMsg::UserInputQueued(_)
+                    | EventMsg::QueuedUserInputDelivered(_)
+                    | EventMsg::UserInputInterrupting(_)
                     | EventMsg::PatchApplyBegin(_)
                     | EventMsg::PatchApplyEnd(_)
                     | EventMsg::TurnDiff(_)
@@ -365,6 +384,9 @@ async fn run_code_tool_session_inner(
                     | EventMsg::EnvironmentContextDelta(_)
                     | EventMsg::ListCustomPromptsResponse(_)
                     | EventMsg::ListSkillsResponse(_)
+                    | EventMsg::PromptPreview(_)
+                    | EventMsg::RequestBudgetCheck(_)
+                    | EventMsg::PartialTurn(_)
                     | EventMsg::AgentStatusUpdate(_)
                     | EventMsg::HookStarted(_)
                     | EventMsg::HookCompleted(_)
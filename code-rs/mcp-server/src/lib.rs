@@ -28,6 +28,7 @@ mod exec_approval;
 pub(crate) mod message_processor;
 mod outgoing_message;
 mod patch_approval;
+mod resources;
 mod session_store;
 
 use crate::message_processor::MessageProcessor;
@@ -14,6 +14,7 @@ use crate::code_tool_config::CodexToolCallParam;
 use crate::code_tool_config::CodexToolCallReplyParam;
 use crate::error_code::INVALID_REQUEST_ERROR_CODE;
 use crate::error_code::INTERNAL_ERROR_CODE;
+use crate::error_code::RESOURCE_NOT_FOUND_ERROR_CODE;
 use crate::outgoing_message::{OutgoingMessageSender, OutgoingNotification};
 use crate::session_store::SessionMap;
 use agent_client_protocol as acp;
@@ -268,19 +269,19 @@ impl MessageProcessor {
                 self.handle_ping(request_id, params).await;
             }
             McpClientRequest::ListResourcesRequest(params) => {
-                self.handle_list_resources(params);
+                self.handle_list_resources(request_id, params).await;
             }
             McpClientRequest::ListResourceTemplatesRequest(params) => {
-                self.handle_list_resource_templates(params);
+                self.handle_list_resource_templates(request_id, params).await;
             }
             McpClientRequest::ReadResourceRequest(params) => {
-                self.handle_read_resource(params);
+                self.handle_read_resource(request_id, params).await;
             }
             McpClientRequest::SubscribeRequest(params) => {
-                self.handle_subscribe(params);
+                self.handle_subscribe(request_id, params).await;
             }
             McpClientRequest::UnsubscribeRequest(params) => {
-                self.handle_unsubscribe(params);
+                self.handle_unsubscribe(request_id, params).await;
             }
             McpClientRequest::ListPromptsRequest(params) => {
                 self.handle_list_prompts(params);
@@ -429,6 +430,10 @@ impl MessageProcessor {
             "capabilities": {
                 "tools": {
                     "listChanged": true
+                },
+                "resources": {
+                    "subscribe": true,
+                    "listChanged": false
                 }
             },
             "agentCapabilities": agent_capabilities,
@@ -456,40 +461,83 @@ impl MessageProcessor {
             .await;
     }
 
-    fn handle_list_resources(
+    async fn handle_list_resources(
         &self,
+        id: RequestId,
         params: <mcp_types::ListResourcesRequest as mcp_types::ModelContextProtocolRequest>::Params,
     ) {
         tracing::info!("resources/list -> params: {:?}", params);
+        let result = crate::resources::list_resources(&self.session_map).await;
+        self.outgoing.send_response(id, result).await;
     }
 
-    fn handle_list_resource_templates(
+    async fn handle_list_resource_templates(
         &self,
+        id: RequestId,
         params:
             <mcp_types::ListResourceTemplatesRequest as mcp_types::ModelContextProtocolRequest>::Params,
     ) {
         tracing::info!("resources/templates/list -> params: {:?}", params);
+        self.outgoing
+            .send_response(id, crate::resources::resource_templates())
+            .await;
     }
 
-    fn handle_read_resource(
+    async fn handle_read_resource(
         &self,
+        id: RequestId,
         params: <mcp_types::ReadResourceRequest as mcp_types::ModelContextProtocolRequest>::Params,
     ) {
         tracing::info!("resources/read -> params: {:?}", params);
+        match crate::resources::read_resource(&self.session_map, &params.uri).await {
+            Some(result) => self.outgoing.send_response(id, result).await,
+            None => self.send_resource_not_found(id, &params.uri).await,
+        }
     }
 
-    fn handle_subscribe(
+    async fn handle_subscribe(
         &self,
+        id: RequestId,
         params: <mcp_types::SubscribeRequest as mcp_types::ModelContextProtocolRequest>::Params,
     ) {
         tracing::info!("resources/subscribe -> params: {:?}", params);
+        self.set_resource_subscription(id, &params.uri, true).await;
     }
 
-    fn handle_unsubscribe(
+    async fn handle_unsubscribe(
         &self,
+        id: RequestId,
         params: <mcp_types::UnsubscribeRequest as mcp_types::ModelContextProtocolRequest>::Params,
     ) {
         tracing::info!("resources/unsubscribe -> params: {:?}", params);
+        self.set_resource_subscription(id, &params.uri, false).await;
+    }
+
+    async fn set_resource_subscription(&self, id: RequestId, uri: &str, subscribed: bool) {
+        let events = match crate::resources::parse_conversation_uri(uri) {
+            Some(session_id) => self
+                .session_map
+                .lock()
+                .await
+                .get(&session_id)
+                .map(|entry| entry.events.clone()),
+            None => None,
+        };
+        let Some(events) = events else {
+            self.send_resource_not_found(id, uri).await;
+            return;
+        };
+        events.set_subscribed(subscribed);
+        self.outgoing.send_response(id, json!({})).await;
+    }
+
+    async fn send_resource_not_found(&self, id: RequestId, uri: &str) {
+        let error = JSONRPCErrorError {
+            code: RESOURCE_NOT_FOUND_ERROR_CODE,
+            message: format!("resource not found: {uri}"),
+            data: Some(json!({ "uri": uri })),
+        };
+        self.outgoing.send_error(id, error).await;
     }
 
     fn handle_list_prompts(
@@ -705,13 +753,12 @@ impl MessageProcessor {
         let session_map = self.session_map.clone();
 
         tokio::spawn(async move {
-            let codex = {
+            let entry = {
                 let map = session_map.lock().await;
-                map.get(&session_id)
-                    .map(|entry| entry.conversation.clone())
+                map.get(&session_id).cloned()
             };
 
-            let Some(codex) = codex else {
+            let Some(entry) = entry else {
                 tracing::warn!("Session not found for session_id: {session_id}");
                 let result = CallToolResult {
                     content: vec![ContentBlock::TextContent(TextContent {
@@ -727,7 +774,8 @@ impl MessageProcessor {
             };
 
             crate::code_tool_runner::run_code_tool_session_reply(
-                codex,
+                entry.conversation,
+                entry.events,
                 outgoing,
                 request_id,
                 prompt,
@@ -1008,6 +1056,7 @@ impl MessageProcessor {
         };
 
         let session = session_entry.conversation;
+        let events = session_entry.events;
 
         let outgoing = self.outgoing.clone();
         let requests_code_map = self.running_requests_id_to_code_uuid.clone();
@@ -1021,7 +1070,9 @@ impl MessageProcessor {
 
             let result = crate::acp_tool_runner::prompt(
                 acp_session_id.clone(),
+                session_uuid,
                 session,
+                events,
                 prompt_blocks,
                 outgoing.clone(),
             )
@@ -1180,13 +1231,12 @@ impl MessageProcessor {
             }
         };
 
-        let session = {
+        let entry = {
             let map = self.session_map.lock().await;
-            map.get(&session_id)
-                .map(|entry| entry.conversation.clone())
+            map.get(&session_id).cloned()
         };
 
-        let Some(session) = session else {
+        let Some(entry) = entry else {
             tracing::warn!("Unknown session id: {}", session_id);
             let result = CallToolResult {
                 content: vec![ContentBlock::TextContent(TextContent {
@@ -1210,7 +1260,15 @@ impl MessageProcessor {
                 .await
                 .insert(request_id.clone(), session_id);
 
-            let result = crate::acp_tool_runner::prompt(acp_session_id, session, prompt, outgoing.clone()).await;
+            let result = crate::acp_tool_runner::prompt(
+                acp_session_id,
+                session_id,
+                entry.conversation,
+                entry.events,
+                prompt,
+                outgoing.clone(),
+            )
+            .await;
 
             let result = match result {
                 Ok(stop_reason) => {
@@ -1637,6 +1695,7 @@ fn configure_session_op_from_config(config: &Config) -> Op {
         dynamic_tools: config.dynamic_tools.clone(),
         shell: config.shell.clone(),
         shell_style_profiles: config.shell_style_profiles.clone(),
+        shell_env_set: None,
         network: config.network.clone(),
         tools_repl: config.tools_repl,
         repl_default_runtime: config.repl_default_runtime,
@@ -0,0 +1,133 @@
+//! MCP resources for the conversations this server is running. Each session
+//! is exposed as `codex://conversations/<id>`; reading it returns the model,
+//! working directory and most recent events, and clients that subscribe get
+//! `notifications/resources/updated` every time a new event is recorded.
+
+use code_core::protocol::Event;
+use serde_json::Value;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::outgoing_message::OutgoingMessageSender;
+use crate::outgoing_message::OutgoingNotification;
+use crate::session_store::EventLog;
+use crate::session_store::SessionMap;
+
+const CONVERSATION_URI_PREFIX: &str = "codex://conversations/";
+const JSON_MIME_TYPE: &str = "application/json";
+
+pub(crate) fn conversation_uri(session_id: &Uuid) -> String {
+    format!("{CONVERSATION_URI_PREFIX}{session_id}")
+}
+
+pub(crate) fn parse_conversation_uri(uri: &str) -> Option<Uuid> {
+    let id = uri.strip_prefix(CONVERSATION_URI_PREFIX)?;
+    Uuid::parse_str(id.trim_end_matches('/')).ok()
+}
+
+/// `resources/list` result: one resource per active session.
+pub(crate) async fn list_resources(session_map: &SessionMap) -> Value {
+    let mut session_ids: Vec<Uuid> = session_map.lock().await.keys().copied().collect();
+    session_ids.sort();
+    let resources: Vec<Value> = session_ids
+        .iter()
+        .map(|session_id| {
+            json!({
+                "uri": conversation_uri(session_id),
+                "name": format!("conversation {session_id}"),
+                "description": "Model, working directory and recent events of a conversation.",
+                "mimeType": JSON_MIME_TYPE,
+            })
+        })
+        .collect();
+    json!({ "resources": resources })
+}
+
+/// `resources/templates/list` result.
+pub(crate) fn resource_templates() -> Value {
+    json!({
+        "resourceTemplates": [{
+            "uriTemplate": format!("{CONVERSATION_URI_PREFIX}{{conversation_id}}"),
+            "name": "conversation",
+            "description": "Model, working directory and recent events of a conversation.",
+            "mimeType": JSON_MIME_TYPE,
+        }]
+    })
+}
+
+/// `resources/read` result for `uri`, or `None` when it names no active
+/// session.
+pub(crate) async fn read_resource(session_map: &SessionMap, uri: &str) -> Option<Value> {
+    let session_id = parse_conversation_uri(uri)?;
+    let entry = session_map.lock().await.get(&session_id).cloned()?;
+    let (model, cwd) = {
+        let config = entry.config.lock().await;
+        (config.model.clone(), config.cwd.clone())
+    };
+    let snapshot = json!({
+        "conversationId": session_id,
+        "model": model,
+        "cwd": cwd,
+        "totalEvents": entry.events.total(),
+        "events": entry.events.recent(),
+    });
+    Some(json!({
+        "contents": [{
+            "uri": conversation_uri(&session_id),
+            "mimeType": JSON_MIME_TYPE,
+            "text": serde_json::to_string_pretty(&snapshot).unwrap_or_default(),
+        }]
+    }))
+}
+
+/// Records a streamed event for `resources/read` and notifies subscribers of
+/// the session's resource.
+pub(crate) async fn record_event(
+    outgoing: &OutgoingMessageSender,
+    session_id: Uuid,
+    events: &EventLog,
+    event: &Event,
+) {
+    let Ok(value) = serde_json::to_value(event) else {
+        return;
+    };
+    if events.record(value) {
+        outgoing
+            .send_notification(OutgoingNotification {
+                method: "notifications/resources/updated".to_owned(),
+                params: Some(json!({ "uri": conversation_uri(&session_id) })),
+            })
+            .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn conversation_uris_round_trip() {
+        let session_id = Uuid::now_v7();
+        let uri = conversation_uri(&session_id);
+        assert_eq!(parse_conversation_uri(&uri), Some(session_id));
+        assert_eq!(parse_conversation_uri(&format!("{uri}/")), Some(session_id));
+        assert_eq!(parse_conversation_uri("codex://conversations/nope"), None);
+        assert_eq!(parse_conversation_uri("file:///tmp/a"), None);
+    }
+
+    #[test]
+    fn event_log_keeps_recent_events_and_reports_subscription() {
+        let log = EventLog::default();
+        assert!(!log.record(json!({ "n": 0 })));
+        log.set_subscribed(true);
+        for n in 1..=250 {
+            assert!(log.record(json!({ "n": n })));
+        }
+        let recent = log.recent();
+        assert_eq!(log.total(), 251);
+        assert_eq!(recent.len(), 200);
+        assert_eq!(recent.first(), Some(&json!({ "n": 51 })));
+        assert_eq!(recent.last(), Some(&json!({ "n": 250 })));
+    }
+}
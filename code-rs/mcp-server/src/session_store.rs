@@ -1,16 +1,24 @@
 use std::collections::HashMap;
+use std::collections::VecDeque;
 use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 
 use code_core::config::Config;
 use code_core::CodexConversation;
 use tokio::sync::Mutex;
 use uuid::Uuid;
 
+/// Events kept per session for `resources/read`.
+const MAX_RECENT_EVENTS: usize = 200;
+
 /// In-memory session entry tracking an active Codex conversation and its config.
 #[derive(Clone)]
 pub struct SessionEntry {
     pub conversation: Arc<CodexConversation>,
     pub config: Arc<Mutex<Config>>,
+    pub events: Arc<EventLog>,
 }
 
 impl SessionEntry {
@@ -18,7 +26,51 @@ impl SessionEntry {
         Self {
             conversation,
             config: Arc::new(Mutex::new(config)),
+            events: Arc::new(EventLog::default()),
+        }
+    }
+}
+
+/// The most recent events of a session, serialized as they were streamed,
+/// plus whether a client subscribed to the session's resource.
+#[derive(Default)]
+pub struct EventLog {
+    events: std::sync::Mutex<VecDeque<serde_json::Value>>,
+    total: AtomicU64,
+    subscribed: AtomicBool,
+}
+
+impl EventLog {
+    /// Records an event and returns whether subscribers should be told about
+    /// it.
+    pub fn record(&self, event: serde_json::Value) -> bool {
+        let mut events = match self.events.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        if events.len() == MAX_RECENT_EVENTS {
+            events.pop_front();
         }
+        events.push_back(event);
+        self.total.fetch_add(1, Ordering::Relaxed);
+        self.subscribed.load(Ordering::Relaxed)
+    }
+
+    /// Recent events, oldest first.
+    pub fn recent(&self) -> Vec<serde_json::Value> {
+        match self.events.lock() {
+            Ok(guard) => guard.iter().cloned().collect(),
+            Err(poisoned) => poisoned.into_inner().iter().cloned().collect(),
+        }
+    }
+
+    /// Events recorded over the session's lifetime, including dropped ones.
+    pub fn total(&self) -> u64 {
+        self.total.load(Ordering::Relaxed)
+    }
+
+    pub fn set_subscribed(&self, subscribed: bool) {
+        self.subscribed.store(subscribed, Ordering::Relaxed);
     }
 }
 
@@ -63,6 +63,7 @@ pub(crate) async fn handle_create_conversation(
         dynamic_tools: None,
         compact_prompt_override: None,
         compact_prompt_override_file: None,
+        sandbox_preset: None,
     };
 
     let cfg: CodexConfig = match CodexConfig::load_with_cli_overrides(cli_overrides, overrides) {
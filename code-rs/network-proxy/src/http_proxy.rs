@@ -18,6 +18,8 @@ use crate::responses::blocked_message_with_policy;
 use crate::responses::blocked_text_response_with_policy;
 use crate::responses::json_response;
 use crate::runtime::unix_socket_permissions_supported;
+use crate::state::AllowedRequest;
+use crate::state::AllowedRequestArgs;
 use crate::state::BlockedRequest;
 use crate::state::BlockedRequestArgs;
 use crate::state::NetworkProxyState;
@@ -269,6 +271,17 @@ async fn http_connect_accept(
         ));
     }
 
+    app_state
+        .record_allowed(AllowedRequest::new(AllowedRequestArgs {
+            host: host.clone(),
+            port: authority.port,
+            protocol: "http-connect".to_owned(),
+            client,
+            method: Some("CONNECT".to_owned()),
+            attempt_id: network_attempt_id,
+        }))
+        .await;
+
     req.extensions_mut().insert(ProxyTarget(authority));
     req.extensions_mut().insert(mode);
 
@@ -571,6 +584,16 @@ async fn http_plain_proxy(
         ));
     }
 
+    app_state
+        .record_allowed(AllowedRequest::new(AllowedRequestArgs {
+            host: host.clone(),
+            port,
+            protocol: "http".to_owned(),
+            client: client.clone(),
+            method: Some(req.method().as_str().to_owned()),
+            attempt_id: network_attempt_id,
+        }))
+        .await;
     let client = client.as_deref().unwrap_or_default();
     let method = req.method();
     info!("request allowed (client={client}, host={host}, method={method})");
@@ -79,6 +79,33 @@ fn ipv4_in_cidr(ip: Ipv4Addr, base: [u8; 4], prefix: u8) -> bool {
     (ip & mask) == (base & mask)
 }
 
+fn ipv6_in_cidr(ip: Ipv6Addr, base: Ipv6Addr, prefix: u8) -> bool {
+    let mask = if prefix == 0 {
+        0
+    } else {
+        u128::MAX << (128 - prefix)
+    };
+    (u128::from(ip) & mask) == (u128::from(base) & mask)
+}
+
+/// Returns true if `ip` falls inside one of the CIDR entries (`10.0.0.0/8`,
+/// `fd00::/8`) in `patterns`. Other entries are ignored.
+pub(crate) fn ip_in_cidr_patterns(patterns: &[String], ip: IpAddr) -> bool {
+    patterns.iter().any(|pattern| {
+        let Some((base, prefix)) = pattern.trim().split_once('/') else {
+            return false;
+        };
+        let (Ok(base), Ok(prefix)) = (base.parse::<IpAddr>(), prefix.parse::<u8>()) else {
+            return false;
+        };
+        match (ip, base) {
+            (IpAddr::V4(ip), IpAddr::V4(base)) => prefix <= 32 && ipv4_in_cidr(ip, base.octets(), prefix),
+            (IpAddr::V6(ip), IpAddr::V6(base)) => prefix <= 128 && ipv6_in_cidr(ip, base, prefix),
+            _ => false,
+        }
+    })
+}
+
 fn is_non_public_ipv6(ip: Ipv6Addr) -> bool {
     if let Some(v4) = ip.to_ipv4() {
         return is_non_public_ipv4(v4) || ip.is_loopback();
@@ -375,6 +402,19 @@ mod tests {
         assert!(!is_loopback_host(&Host::parse("1.2.3.4").unwrap()));
     }
 
+    #[test]
+    fn ip_in_cidr_patterns_matches_v4_and_v6_ranges() {
+        let patterns = vec![
+            "example.com".to_string(),
+            "10.20.0.0/16".to_string(),
+            "fd00::/8".to_string(),
+        ];
+        assert!(ip_in_cidr_patterns(&patterns, "10.20.3.4".parse().unwrap()));
+        assert!(!ip_in_cidr_patterns(&patterns, "10.21.0.1".parse().unwrap()));
+        assert!(ip_in_cidr_patterns(&patterns, "fd12::1".parse().unwrap()));
+        assert!(!ip_in_cidr_patterns(&patterns, "2001:db8::1".parse().unwrap()));
+    }
+
     #[test]
     fn is_non_public_ip_rejects_private_and_loopback_ranges() {
         assert!(is_non_public_ip("127.0.0.1".parse().unwrap()));
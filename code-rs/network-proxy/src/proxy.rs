@@ -3,6 +3,7 @@ use crate::config;
 use crate::http_proxy;
 use crate::metadata::proxy_username_for_attempt_id;
 use crate::network_policy::NetworkPolicyDecider;
+use crate::runtime::AllowedRequestObserver;
 use crate::runtime::BlockedRequestObserver;
 use crate::runtime::unix_socket_permissions_supported;
 use crate::socks5;
@@ -73,6 +74,7 @@ pub struct NetworkProxyBuilder {
     managed_by_codex: bool,
     policy_decider: Option<Arc<dyn NetworkPolicyDecider>>,
     blocked_request_observer: Option<Arc<dyn BlockedRequestObserver>>,
+    allowed_request_observer: Option<Arc<dyn AllowedRequestObserver>>,
 }
 
 impl Default for NetworkProxyBuilder {
@@ -85,6 +87,7 @@ impl Default for NetworkProxyBuilder {
             managed_by_codex: true,
             policy_decider: None,
             blocked_request_observer: None,
+            allowed_request_observer: None,
         }
     }
 }
@@ -144,6 +147,22 @@ impl NetworkProxyBuilder {
         self
     }
 
+    pub fn allowed_request_observer<O>(mut self, observer: O) -> Self
+    where
+        O: AllowedRequestObserver,
+    {
+        self.allowed_request_observer = Some(Arc::new(observer));
+        self
+    }
+
+    pub fn allowed_request_observer_arc(
+        mut self,
+        observer: Arc<dyn AllowedRequestObserver>,
+    ) -> Self {
+        self.allowed_request_observer = Some(observer);
+        self
+    }
+
     pub async fn build(self) -> Result<NetworkProxy> {
         let state = self.state.ok_or_else(|| {
             anyhow::anyhow!(
@@ -153,6 +172,9 @@ impl NetworkProxyBuilder {
         state
             .set_blocked_request_observer(self.blocked_request_observer.clone())
             .await;
+        state
+            .set_allowed_request_observer(self.allowed_request_observer.clone())
+            .await;
         let current_cfg = state.current_cfg().await?;
         let (requested_http_addr, requested_socks_addr, requested_admin_addr, reserved_listeners) =
             if self.managed_by_codex {
@@ -1,6 +1,7 @@
 use crate::config::NetworkMode;
 use crate::config::NetworkProxyConfig;
 use crate::policy::Host;
+use crate::policy::ip_in_cidr_patterns;
 use crate::policy::is_loopback_host;
 use crate::policy::is_non_public_ip;
 use crate::policy::normalize_host;
@@ -128,6 +129,51 @@ impl BlockedRequest {
     }
 }
 
+/// A connection the proxy let through to its destination.
+#[derive(Clone, Debug, Serialize)]
+pub struct AllowedRequest {
+    pub host: String,
+    pub port: u16,
+    pub protocol: String,
+    pub client: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attempt_id: Option<String>,
+    pub timestamp: i64,
+}
+
+pub struct AllowedRequestArgs {
+    pub host: String,
+    pub port: u16,
+    pub protocol: String,
+    pub client: Option<String>,
+    pub method: Option<String>,
+    pub attempt_id: Option<String>,
+}
+
+impl AllowedRequest {
+    pub fn new(args: AllowedRequestArgs) -> Self {
+        let AllowedRequestArgs {
+            host,
+            port,
+            protocol,
+            client,
+            method,
+            attempt_id,
+        } = args;
+        Self {
+            host,
+            port,
+            protocol,
+            client,
+            method,
+            attempt_id,
+            timestamp: unix_timestamp(),
+        }
+    }
+}
+
 fn blocked_request_violation_log_line(entry: &BlockedRequest) -> String {
     match serde_json::to_string(entry) {
         Ok(json) => format!("{NETWORK_POLICY_VIOLATION_PREFIX} {json}"),
@@ -186,10 +232,35 @@ where
     }
 }
 
+/// Notified for every connection the proxy allows, e.g. to keep an egress log.
+#[async_trait]
+pub trait AllowedRequestObserver: Send + Sync + 'static {
+    async fn on_allowed_request(&self, request: AllowedRequest);
+}
+
+#[async_trait]
+impl<O: AllowedRequestObserver + ?Sized> AllowedRequestObserver for Arc<O> {
+    async fn on_allowed_request(&self, request: AllowedRequest) {
+        (**self).on_allowed_request(request).await;
+    }
+}
+
+#[async_trait]
+impl<F, Fut> AllowedRequestObserver for F
+where
+    F: Fn(AllowedRequest) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send,
+{
+    async fn on_allowed_request(&self, request: AllowedRequest) {
+        (self)(request).await;
+    }
+}
+
 pub struct NetworkProxyState {
     state: Arc<RwLock<ConfigState>>,
     reloader: Arc<dyn ConfigReloader>,
     blocked_request_observer: Arc<RwLock<Option<Arc<dyn BlockedRequestObserver>>>>,
+    allowed_request_observer: Arc<RwLock<Option<Arc<dyn AllowedRequestObserver>>>>,
 }
 
 impl std::fmt::Debug for NetworkProxyState {
@@ -206,6 +277,7 @@ impl Clone for NetworkProxyState {
             state: self.state.clone(),
             reloader: self.reloader.clone(),
             blocked_request_observer: self.blocked_request_observer.clone(),
+            allowed_request_observer: self.allowed_request_observer.clone(),
         }
     }
 }
@@ -224,6 +296,7 @@ impl NetworkProxyState {
             state: Arc::new(RwLock::new(state)),
             reloader,
             blocked_request_observer: Arc::new(RwLock::new(blocked_request_observer)),
+            allowed_request_observer: Arc::new(RwLock::new(None)),
         }
     }
 
@@ -235,6 +308,14 @@ impl NetworkProxyState {
         *observer = blocked_request_observer;
     }
 
+    pub async fn set_allowed_request_observer(
+        &self,
+        allowed_request_observer: Option<Arc<dyn AllowedRequestObserver>>,
+    ) {
+        let mut observer = self.allowed_request_observer.write().await;
+        *observer = allowed_request_observer;
+    }
+
     pub async fn current_cfg(&self) -> Result<NetworkProxyConfig> {
         // Callers treat `NetworkProxyState` as a live view of policy. We reload-on-demand so edits to
         // `config.toml` (including Codex-managed writes) take effect without a restart.
@@ -312,7 +393,13 @@ impl NetworkProxyState {
             return Ok(HostBlockDecision::Blocked(HostBlockReason::Denied));
         }
 
-        let is_allowlisted = allow_set.is_match(host_str);
+        let host_ip = host_str
+            .split_once('%')
+            .map_or(host_str, |(ip, _)| ip)
+            .parse::<IpAddr>()
+            .ok();
+        let cidr_allowlisted = host_ip.is_some_and(|ip| ip_in_cidr_patterns(&allowed_domains, ip));
+        let is_allowlisted = allow_set.is_match(host_str) || cidr_allowlisted;
         if !allow_local_binding {
             // If the intent is "prevent access to local/internal networks", we must not rely solely
             // on string checks like `localhost` / `127.0.0.1`. Attackers can use DNS rebinding or
@@ -336,7 +423,7 @@ impl NetworkProxyState {
             };
 
             if local_literal {
-                if !is_explicit_local_allowlisted(&allowed_domains, &host) {
+                if !cidr_allowlisted && !is_explicit_local_allowlisted(&allowed_domains, &host) {
                     return Ok(HostBlockDecision::Blocked(HostBlockReason::NotAllowedLocal));
                 }
             } else if host_resolves_to_non_public_ip(host_str, port).await {
@@ -391,6 +478,19 @@ impl NetworkProxyState {
         Ok(())
     }
 
+    /// Reports a connection the proxy is about to forward. Allowed requests
+    /// are not buffered; they only reach the allowed-request observer.
+    pub async fn record_allowed(&self, entry: AllowedRequest) {
+        debug!(
+            "recorded allowed request (host={}, port={}, protocol={}, attempt_id={:?})",
+            entry.host, entry.port, entry.protocol, entry.attempt_id
+        );
+        let allowed_request_observer = self.allowed_request_observer.read().await.clone();
+        if let Some(observer) = allowed_request_observer {
+            observer.on_allowed_request(entry).await;
+        }
+    }
+
     /// Returns a snapshot of buffered blocked-request entries without consuming
     /// them.
     pub async fn blocked_snapshot(&self) -> Result<Vec<BlockedRequest>> {
@@ -684,6 +784,24 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn host_blocked_allows_ip_literals_in_cidr_entries() {
+        let state = network_proxy_state_for_policy(NetworkProxySettings {
+            allowed_domains: vec!["10.20.0.0/16".to_string()],
+            allow_local_binding: false,
+            ..NetworkProxySettings::default()
+        });
+
+        assert_eq!(
+            state.host_blocked("10.20.1.2", 443).await.unwrap(),
+            HostBlockDecision::Allowed
+        );
+        assert_eq!(
+            state.host_blocked("10.30.1.2", 443).await.unwrap(),
+            HostBlockDecision::Blocked(HostBlockReason::NotAllowedLocal)
+        );
+    }
+
     #[tokio::test]
     async fn blocked_snapshot_does_not_consume_entries() {
         let state = network_proxy_state_for_policy(NetworkProxySettings::default());
@@ -12,6 +12,8 @@ use crate::reasons::REASON_METHOD_NOT_ALLOWED;
 use crate::reasons::REASON_PROXY_DISABLED;
 use crate::responses::PolicyDecisionDetails;
 use crate::responses::blocked_message_with_policy;
+use crate::state::AllowedRequest;
+use crate::state::AllowedRequestArgs;
 use crate::state::BlockedRequest;
 use crate::state::BlockedRequestArgs;
 use crate::state::NetworkProxyState;
@@ -265,6 +267,16 @@ async fn handle_socks5_tcp(
             return Err(policy_denied_error(&reason, &details).into());
         }
         Ok(NetworkDecision::Allow) => {
+            app_state
+                .record_allowed(AllowedRequest::new(AllowedRequestArgs {
+                    host: host.clone(),
+                    port,
+                    protocol: "socks5".to_owned(),
+                    client: client.clone(),
+                    method: None,
+                    attempt_id: None,
+                }))
+                .await;
             let client = client.as_deref().unwrap_or_default();
             info!("SOCKS allowed (client={client}, host={host}, port={port})");
         }
@@ -6,6 +6,8 @@ use crate::runtime::ConfigState;
 use serde::Deserialize;
 use std::collections::HashSet;
 
+pub use crate::runtime::AllowedRequest;
+pub use crate::runtime::AllowedRequestArgs;
 pub use crate::runtime::BlockedRequest;
 pub use crate::runtime::BlockedRequestArgs;
 pub use crate::runtime::NetworkProxyState;
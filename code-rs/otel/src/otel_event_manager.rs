@@ -50,6 +50,8 @@ pub struct TurnLatencyPayload {
     pub attempt: u64,
     pub gap_ms: Option<u64>,
     pub duration_ms: Option<u64>,
+    /// Time from sending the request to the first streamed output.
+    pub first_token_ms: Option<u64>,
     pub pending_input_count: u64,
     pub pending_user_input_count: u64,
     pub pending_background_execs: u64,
@@ -519,6 +521,7 @@ impl OtelEventManager {
             attempt = payload.attempt,
             gap_ms = payload.gap_ms,
             duration_ms = payload.duration_ms,
+            first_token_ms = payload.first_token_ms,
             pending_input_count = payload.pending_input_count,
             pending_user_input_count = payload.pending_user_input_count,
             pending_background_execs = payload.pending_background_execs,
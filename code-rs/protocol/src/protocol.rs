@@ -2171,6 +2171,11 @@ pub struct ReviewRequest {
     /// flows.
     #[serde(default)]
     pub prompt: String,
+    /// Reviewer profile (`[review_profiles.<name>]`) to apply; falls back to
+    /// the configured `review_profile` when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub profile: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
@@ -2183,6 +2188,9 @@ pub struct ReviewSnapshotInfo {
     pub worktree_path: Option<PathBuf>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub repo_root: Option<PathBuf>,
+    /// Reviewer profile the review ran with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub review_profile: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
@@ -2482,6 +2490,9 @@ pub struct McpListToolsResponseEvent {
 pub enum McpServerFailurePhase {
     Start,
     ListTools,
+    // The server is running, but its circuit breaker is open after repeated
+    // failed tool calls.
+    CircuitOpen,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq, Eq)]
@@ -19,8 +19,8 @@ use crate::utils::apply_default_headers;
 use crate::utils::build_default_headers;
 
 const DISCOVERY_TIMEOUT: Duration = Duration::from_secs(5);
-const OAUTH_DISCOVERY_HEADER: &str = "MCP-Protocol-Version";
-const OAUTH_DISCOVERY_VERSION: &str = "2024-11-05";
+pub(crate) const OAUTH_DISCOVERY_HEADER: &str = "MCP-Protocol-Version";
+pub(crate) const OAUTH_DISCOVERY_VERSION: &str = "2024-11-05";
 
 pub struct StreamableHttpAuthStatusArgs<'a> {
     pub code_home: &'a Path,
@@ -139,7 +139,7 @@ struct OAuthDiscoveryMetadata {
 /// This is a requirement for MCP servers to support OAuth.
 /// <https://datatracker.ietf.org/doc/html/rfc8414#section-3.1>
 /// <https://github.com/modelcontextprotocol/rust-sdk/blob/main/crates/rmcp/src/transport/auth.rs#L182>
-fn discovery_paths(base_path: &str) -> Vec<String> {
+pub(crate) fn discovery_paths(base_path: &str) -> Vec<String> {
     let trimmed = base_path.trim_start_matches('/').trim_end_matches('/');
     let canonical = "/.well-known/oauth-authorization-server".to_owned();
 
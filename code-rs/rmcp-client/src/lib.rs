@@ -1,7 +1,9 @@
 mod auth_status;
 mod logging_client_handler;
 mod oauth;
+mod oauth_refresh;
 mod open_url;
+mod perform_oauth_device_login;
 mod perform_oauth_login;
 mod program_resolver;
 mod rmcp_client;
@@ -16,6 +18,12 @@ pub use oauth::StoredOAuthTokens;
 pub use oauth::WrappedOAuthTokenResponse;
 pub use oauth::delete_oauth_tokens;
 pub use oauth::save_oauth_tokens;
+pub use oauth_refresh::OAuthAccessTokenArgs;
+pub use oauth_refresh::load_oauth_access_token;
+pub use perform_oauth_device_login::DeviceLoginArgs;
+pub use perform_oauth_device_login::DeviceLoginHandle;
+pub use perform_oauth_device_login::DeviceLoginPrompt;
+pub use perform_oauth_device_login::start_device_login;
 pub use perform_oauth_login::OauthLoginHandle;
 pub use perform_oauth_login::OauthLoginArgs;
 pub use perform_oauth_login::perform_oauth_login;
@@ -0,0 +1,148 @@
+//! Access tokens for streamable HTTP servers from stored OAuth credentials,
+//! refreshed with the stored refresh token when they are about to expire.
+
+use std::path::Path;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::anyhow;
+use anyhow::bail;
+use oauth2::TokenResponse;
+use reqwest::ClientBuilder;
+use reqwest::header::ACCEPT;
+use rmcp::transport::auth::OAuthTokenResponse;
+use tracing::warn;
+
+use crate::OAuthCredentialsStoreMode;
+use crate::StoredOAuthTokens;
+use crate::WrappedOAuthTokenResponse;
+use crate::oauth::compute_expires_at_millis;
+use crate::oauth::load_oauth_tokens;
+use crate::perform_oauth_device_login::discover_authorization_server;
+use crate::save_oauth_tokens;
+
+const HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+/// Refresh this long before the recorded expiry so the token does not lapse
+/// while the server starts.
+const REFRESH_MARGIN: Duration = Duration::from_secs(60);
+
+pub struct OAuthAccessTokenArgs<'a> {
+    pub code_home: &'a Path,
+    pub server_name: &'a str,
+    pub url: &'a str,
+    pub store_mode: OAuthCredentialsStoreMode,
+    /// Carries the caller's proxy and TLS settings.
+    pub http_client_builder: ClientBuilder,
+}
+
+/// Access token stored for the server, refreshed first when it expires within
+/// a minute.
+///
+/// Returns `Ok(None)` when nothing is stored or an expired token cannot be
+/// refreshed, so the caller falls back to signing in again.
+pub async fn load_oauth_access_token(args: OAuthAccessTokenArgs<'_>) -> Result<Option<String>> {
+    let OAuthAccessTokenArgs {
+        code_home,
+        server_name,
+        url,
+        store_mode,
+        http_client_builder,
+    } = args;
+    let Some(tokens) = load_oauth_tokens(code_home, server_name, url, store_mode)? else {
+        return Ok(None);
+    };
+    if !needs_refresh(tokens.expires_at, now_millis()) {
+        return Ok(Some(
+            tokens.token_response.0.access_token().secret().clone(),
+        ));
+    }
+
+    match refresh_tokens(&tokens, http_client_builder).await {
+        Ok(refreshed) => {
+            save_oauth_tokens(code_home, server_name, &refreshed, store_mode)?;
+            Ok(Some(
+                refreshed.token_response.0.access_token().secret().clone(),
+            ))
+        }
+        Err(err) => {
+            warn!("failed to refresh the OAuth token for MCP server `{server_name}`: {err:#}");
+            Ok(None)
+        }
+    }
+}
+
+fn needs_refresh(expires_at: Option<u64>, now_ms: u64) -> bool {
+    let margin = REFRESH_MARGIN.as_millis() as u64;
+    expires_at.is_some_and(|expires_at| expires_at <= now_ms.saturating_add(margin))
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO)
+        .as_millis() as u64
+}
+
+async fn refresh_tokens(
+    tokens: &StoredOAuthTokens,
+    http_client_builder: ClientBuilder,
+) -> Result<StoredOAuthTokens> {
+    let refresh_token = tokens
+        .token_response
+        .0
+        .refresh_token()
+        .ok_or_else(|| anyhow!("no refresh token is stored"))?;
+    let client = http_client_builder.timeout(HTTP_TIMEOUT).build()?;
+    let token_endpoint = discover_authorization_server(&client, &tokens.url)
+        .await?
+        .and_then(|metadata| metadata.token_endpoint)
+        .ok_or_else(|| anyhow!("the server no longer advertises a token endpoint"))?;
+
+    let response = client
+        .post(&token_endpoint)
+        .header(ACCEPT, "application/json")
+        .form(&[
+            ("grant_type", "refresh_token"),
+            ("refresh_token", refresh_token.secret().as_str()),
+            ("client_id", tokens.client_id.as_str()),
+        ])
+        .send()
+        .await
+        .context("failed to reach the token endpoint")?;
+    if !response.status().is_success() {
+        bail!("token refresh failed with {}", response.status());
+    }
+    let mut credentials: OAuthTokenResponse = response
+        .json()
+        .await
+        .context("failed to parse the token response")?;
+    // Servers that do not rotate refresh tokens omit them from the response.
+    if credentials.refresh_token().is_none() {
+        credentials.set_refresh_token(Some(refresh_token.clone()));
+    }
+
+    Ok(StoredOAuthTokens {
+        server_name: tokens.server_name.clone(),
+        url: tokens.url.clone(),
+        client_id: tokens.client_id.clone(),
+        expires_at: compute_expires_at_millis(&credentials),
+        token_response: WrappedOAuthTokenResponse(credentials),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refreshes_only_near_a_known_expiry() {
+        let now = 1_000_000;
+        assert!(!needs_refresh(None, now));
+        assert!(!needs_refresh(Some(now + 120_000), now));
+        assert!(needs_refresh(Some(now + 30_000), now));
+        assert!(needs_refresh(Some(now - 1), now));
+    }
+}
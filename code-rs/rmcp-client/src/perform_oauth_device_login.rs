@@ -0,0 +1,410 @@
+//! OAuth 2.0 device authorization grant (RFC 8628) for streamable HTTP MCP
+//! servers.
+//!
+//! Unlike the browser flow in `perform_oauth_login`, the device flow needs no
+//! local callback server: the user opens the verification URL on any device
+//! and enters the code while we poll the token endpoint. This is what lets a
+//! headless `code exec` run, or a TUI on a remote machine, sign in to a server.
+//! <https://datatracker.ietf.org/doc/html/rfc8628>
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::Instant;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use reqwest::Client;
+use reqwest::ClientBuilder;
+use reqwest::StatusCode;
+use reqwest::Url;
+use reqwest::header::ACCEPT;
+use reqwest::header::AUTHORIZATION;
+use rmcp::transport::auth::OAuthTokenResponse;
+use serde::Deserialize;
+use serde_json::json;
+use tracing::debug;
+
+use crate::OAuthCredentialsStoreMode;
+use crate::StoredOAuthTokens;
+use crate::WrappedOAuthTokenResponse;
+use crate::auth_status::OAUTH_DISCOVERY_HEADER;
+use crate::auth_status::OAUTH_DISCOVERY_VERSION;
+use crate::auth_status::discovery_paths;
+use crate::oauth::compute_expires_at_millis;
+use crate::save_oauth_tokens;
+use crate::utils::apply_default_headers;
+use crate::utils::build_default_headers;
+
+const HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+const DEVICE_CODE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:device_code";
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// RFC 8628 section 3.5: back off by five seconds on every `slow_down`.
+const SLOW_DOWN_STEP: Duration = Duration::from_secs(5);
+const CLIENT_NAME: &str = "Code";
+
+pub struct DeviceLoginArgs<'a> {
+    pub code_home: &'a Path,
+    pub server_name: &'a str,
+    pub server_url: &'a str,
+    pub store_mode: OAuthCredentialsStoreMode,
+    pub http_headers: Option<HashMap<String, String>>,
+    pub env_http_headers: Option<HashMap<String, String>>,
+    pub scopes: &'a [String],
+    /// Carries the caller's proxy and TLS settings.
+    pub http_client_builder: ClientBuilder,
+}
+
+/// What the user has to do to authorize the device.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceLoginPrompt {
+    pub verification_uri: String,
+    /// Verification URL with the code already filled in, when the server
+    /// provides one.
+    pub verification_uri_complete: Option<String>,
+    pub user_code: String,
+    pub expires_in: Duration,
+}
+
+/// A device login waiting for the user; [`DeviceLoginHandle::wait`] polls the
+/// token endpoint until the user approves, denies, or the code expires.
+pub struct DeviceLoginHandle {
+    prompt: DeviceLoginPrompt,
+    client: Client,
+    token_endpoint: String,
+    client_id: String,
+    device_code: String,
+    interval: Duration,
+    deadline: Instant,
+    code_home: PathBuf,
+    server_name: String,
+    server_url: String,
+    store_mode: OAuthCredentialsStoreMode,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct AuthorizationServerMetadata {
+    #[serde(default)]
+    pub(crate) token_endpoint: Option<String>,
+    #[serde(default)]
+    device_authorization_endpoint: Option<String>,
+    #[serde(default)]
+    registration_endpoint: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ClientRegistrationResponse {
+    client_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeviceAuthorizationResponse {
+    device_code: String,
+    user_code: String,
+    verification_uri: String,
+    #[serde(default)]
+    verification_uri_complete: Option<String>,
+    expires_in: u64,
+    #[serde(default)]
+    interval: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenErrorResponse {
+    error: String,
+    #[serde(default)]
+    error_description: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum PollOutcome {
+    Pending,
+    SlowDown,
+    Failed(String),
+}
+
+/// Starts a device login when the server at `server_url` rejects an
+/// unauthenticated request with 401 and advertises a device authorization
+/// endpoint.
+///
+/// Returns `Ok(None)` when the server does not ask for OAuth, does not support
+/// the device flow, or an `Authorization` header is already configured.
+pub async fn start_device_login(args: DeviceLoginArgs<'_>) -> Result<Option<DeviceLoginHandle>> {
+    let DeviceLoginArgs {
+        code_home,
+        server_name,
+        server_url,
+        store_mode,
+        http_headers,
+        env_http_headers,
+        scopes,
+        http_client_builder,
+    } = args;
+
+    let default_headers = build_default_headers(http_headers, env_http_headers)?;
+    if default_headers.contains_key(AUTHORIZATION) {
+        return Ok(None);
+    }
+    let client = apply_default_headers(http_client_builder.timeout(HTTP_TIMEOUT), &default_headers)
+        .build()?;
+
+    if !requires_authorization(&client, server_url).await? {
+        return Ok(None);
+    }
+    let Some(metadata) = discover_authorization_server(&client, server_url).await? else {
+        debug!("MCP server `{server_name}` returned 401 without OAuth metadata");
+        return Ok(None);
+    };
+    let (Some(device_endpoint), Some(token_endpoint)) = (
+        metadata.device_authorization_endpoint,
+        metadata.token_endpoint,
+    ) else {
+        debug!("MCP server `{server_name}` does not support the OAuth device flow");
+        return Ok(None);
+    };
+    let Some(registration_endpoint) = metadata.registration_endpoint else {
+        bail!(
+            "MCP server `{server_name}` supports the OAuth device flow but not dynamic client registration"
+        );
+    };
+
+    let client_id = register_client(&client, &registration_endpoint).await?;
+    let mut form = vec![("client_id", client_id.clone())];
+    if !scopes.is_empty() {
+        form.push(("scope", scopes.join(" ")));
+    }
+    let response = client
+        .post(&device_endpoint)
+        .header(ACCEPT, "application/json")
+        .form(&form)
+        .send()
+        .await
+        .context("failed to request a device code")?;
+    if !response.status().is_success() {
+        bail!(
+            "device authorization request failed with {}",
+            response.status()
+        );
+    }
+    let device: DeviceAuthorizationResponse = response
+        .json()
+        .await
+        .context("failed to parse the device authorization response")?;
+
+    let expires_in = Duration::from_secs(device.expires_in);
+    Ok(Some(DeviceLoginHandle {
+        prompt: DeviceLoginPrompt {
+            verification_uri: device.verification_uri,
+            verification_uri_complete: device.verification_uri_complete,
+            user_code: device.user_code,
+            expires_in,
+        },
+        client,
+        token_endpoint,
+        client_id,
+        device_code: device.device_code,
+        interval: device
+            .interval
+            .map_or(DEFAULT_POLL_INTERVAL, Duration::from_secs),
+        deadline: Instant::now() + expires_in,
+        code_home: code_home.to_path_buf(),
+        server_name: server_name.to_owned(),
+        server_url: server_url.to_owned(),
+        store_mode,
+    }))
+}
+
+impl DeviceLoginHandle {
+    pub fn prompt(&self) -> &DeviceLoginPrompt {
+        &self.prompt
+    }
+
+    /// Polls until the user finishes authorizing, then stores the tokens.
+    pub async fn wait(mut self) -> Result<()> {
+        loop {
+            tokio::time::sleep(self.interval).await;
+            if Instant::now() >= self.deadline {
+                bail!("the device code expired before authorization completed");
+            }
+
+            let response = self
+                .client
+                .post(&self.token_endpoint)
+                .header(ACCEPT, "application/json")
+                .form(&[
+                    ("grant_type", DEVICE_CODE_GRANT_TYPE),
+                    ("device_code", self.device_code.as_str()),
+                    ("client_id", self.client_id.as_str()),
+                ])
+                .send()
+                .await;
+            let response = match response {
+                Ok(response) => response,
+                Err(err) => {
+                    // Transient network errors should not end the login.
+                    debug!("device token poll failed: {err}");
+                    continue;
+                }
+            };
+
+            if response.status().is_success() {
+                let credentials: OAuthTokenResponse = response
+                    .json()
+                    .await
+                    .context("failed to parse the token response")?;
+                let stored = StoredOAuthTokens {
+                    server_name: self.server_name.clone(),
+                    url: self.server_url.clone(),
+                    client_id: self.client_id.clone(),
+                    expires_at: compute_expires_at_millis(&credentials),
+                    token_response: WrappedOAuthTokenResponse(credentials),
+                };
+                save_oauth_tokens(&self.code_home, &self.server_name, &stored, self.store_mode)?;
+                return Ok(());
+            }
+
+            let status = response.status();
+            let error: TokenErrorResponse = response
+                .json()
+                .await
+                .with_context(|| format!("token endpoint returned {status}"))?;
+            match poll_outcome(error) {
+                PollOutcome::Pending => {}
+                PollOutcome::SlowDown => self.interval += SLOW_DOWN_STEP,
+                PollOutcome::Failed(message) => bail!(message),
+            }
+        }
+    }
+}
+
+fn poll_outcome(error: TokenErrorResponse) -> PollOutcome {
+    match error.error.as_str() {
+        "authorization_pending" => PollOutcome::Pending,
+        "slow_down" => PollOutcome::SlowDown,
+        "access_denied" => PollOutcome::Failed("authorization was denied".to_owned()),
+        "expired_token" => {
+            PollOutcome::Failed("the device code expired before authorization completed".to_owned())
+        }
+        other => PollOutcome::Failed(match error.error_description {
+            Some(description) => format!("authorization failed: {other}: {description}"),
+            None => format!("authorization failed: {other}"),
+        }),
+    }
+}
+
+/// Sends an unauthenticated JSON-RPC ping and reports whether the server
+/// answered 401.
+async fn requires_authorization(client: &Client, server_url: &str) -> Result<bool> {
+    let response = client
+        .post(server_url)
+        .header(ACCEPT, "application/json, text/event-stream")
+        .json(&json!({ "jsonrpc": "2.0", "id": 0, "method": "ping" }))
+        .send()
+        .await
+        .with_context(|| format!("failed to reach {server_url}"))?;
+    Ok(response.status() == StatusCode::UNAUTHORIZED)
+}
+
+/// RFC 8414 discovery of the authorization server that protects `server_url`.
+pub(crate) async fn discover_authorization_server(
+    client: &Client,
+    server_url: &str,
+) -> Result<Option<AuthorizationServerMetadata>> {
+    let base_url = Url::parse(server_url)?;
+    for candidate_path in discovery_paths(base_url.path()) {
+        let mut discovery_url = base_url.clone();
+        discovery_url.set_path(&candidate_path);
+        let response = match client
+            .get(discovery_url)
+            .header(OAUTH_DISCOVERY_HEADER, OAUTH_DISCOVERY_VERSION)
+            .send()
+            .await
+        {
+            Ok(response) if response.status() == StatusCode::OK => response,
+            Ok(_) => continue,
+            Err(err) => {
+                debug!("OAuth discovery request failed for {server_url}: {err}");
+                continue;
+            }
+        };
+        match response.json::<AuthorizationServerMetadata>().await {
+            Ok(metadata) => return Ok(Some(metadata)),
+            Err(err) => debug!("invalid OAuth metadata for {server_url}: {err}"),
+        }
+    }
+    Ok(None)
+}
+
+/// RFC 7591 dynamic registration of a public client for the device flow.
+async fn register_client(client: &Client, registration_endpoint: &str) -> Result<String> {
+    let response = client
+        .post(registration_endpoint)
+        .json(&json!({
+            "client_name": CLIENT_NAME,
+            "grant_types": [DEVICE_CODE_GRANT_TYPE, "refresh_token"],
+            "token_endpoint_auth_method": "none",
+        }))
+        .send()
+        .await
+        .context("failed to register an OAuth client")?;
+    if !response.status().is_success() {
+        bail!(
+            "OAuth client registration failed with {}",
+            response.status()
+        );
+    }
+    let registration: ClientRegistrationResponse = response
+        .json()
+        .await
+        .context("failed to parse the client registration response")?;
+    Ok(registration.client_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn error(code: &str, description: Option<&str>) -> TokenErrorResponse {
+        TokenErrorResponse {
+            error: code.to_owned(),
+            error_description: description.map(str::to_owned),
+        }
+    }
+
+    #[test]
+    fn poll_outcome_follows_rfc_8628_error_codes() {
+        assert_eq!(
+            poll_outcome(error("authorization_pending", None)),
+            PollOutcome::Pending
+        );
+        assert_eq!(
+            poll_outcome(error("slow_down", None)),
+            PollOutcome::SlowDown
+        );
+        assert_eq!(
+            poll_outcome(error("access_denied", None)),
+            PollOutcome::Failed("authorization was denied".to_owned())
+        );
+        assert_eq!(
+            poll_outcome(error("invalid_client", Some("unknown client"))),
+            PollOutcome::Failed("authorization failed: invalid_client: unknown client".to_owned())
+        );
+    }
+
+    #[test]
+    fn device_authorization_response_interval_is_optional() {
+        let response: DeviceAuthorizationResponse = serde_json::from_value(json!({
+            "device_code": "device",
+            "user_code": "ABCD-EFGH",
+            "verification_uri": "https://auth.example.com/device",
+            "expires_in": 600,
+        }))
+        .expect("parse");
+        assert_eq!(response.interval, None);
+        assert_eq!(response.verification_uri_complete, None);
+        assert_eq!(response.user_code, "ABCD-EFGH");
+    }
+}
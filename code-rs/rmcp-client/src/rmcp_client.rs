@@ -75,6 +75,7 @@ enum ClientState {
 /// <https://github.com/modelcontextprotocol/rust-sdk>
 pub struct RmcpClient {
     state: Mutex<ClientState>,
+    process_id: Option<u32>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -179,6 +180,7 @@ impl RmcpClient {
 
         let (transport, stderr) = spawned
             .ok_or_else(|| last_err.unwrap_or_else(|| io::Error::other("failed to spawn rmcp server")))?;
+        let process_id = transport.id();
 
         if let Some(stderr) = stderr {
             tokio::spawn(async move {
@@ -202,15 +204,19 @@ impl RmcpClient {
             state: Mutex::new(ClientState::Connecting {
                 transport: Some(PendingTransport::ChildProcess(transport)),
             }),
+            process_id,
         })
     }
 
+    /// `http_client_builder` carries the caller's proxy and TLS settings; the
+    /// default headers are layered on top of it.
     pub fn new_streamable_http_client(
         url: String,
         bearer_token: Option<String>,
         bearer_token_env_var: Option<String>,
         http_headers: Option<HashMap<String, String>>,
         env_http_headers: Option<HashMap<String, String>>,
+        http_client_builder: reqwest::ClientBuilder,
     ) -> Result<Self> {
         let default_headers = build_default_headers(http_headers, env_http_headers)?;
         let bearer_token =
@@ -221,16 +227,22 @@ impl RmcpClient {
             config = config.auth_header(token);
         }
 
-        let http_client = apply_default_headers(reqwest::Client::builder(), &default_headers).build()?;
+        let http_client = apply_default_headers(http_client_builder, &default_headers).build()?;
         let transport = StreamableHttpClientTransport::with_client(http_client, config);
 
         Ok(Self {
             state: Mutex::new(ClientState::Connecting {
                 transport: Some(PendingTransport::StreamableHttp(transport)),
             }),
+            process_id: None,
         })
     }
 
+    /// PID of the server process for stdio clients; `None` for HTTP servers.
+    pub fn process_id(&self) -> Option<u32> {
+        self.process_id
+    }
+
     /// Perform the initialization handshake with the MCP server.
     /// <https://modelcontextprotocol.io/specification/2025-06-18/basic/lifecycle#initialization>
     pub async fn initialize(
@@ -0,0 +1,243 @@
+//! Plain-English summaries of shell commands for approval prompts.
+//!
+//! The explanation is built from [`parse_command`]; the effects are a
+//! best-effort classification of what the command touches, so a reviewer who
+//! does not read shell fluently can still tell a file listing from a
+//! `git push`. Nothing here is a security boundary.
+
+use std::path::Path;
+
+use shlex::split as shlex_split;
+
+use crate::command_safety::is_dangerous_command::command_might_be_dangerous;
+use crate::command_safety::is_dangerous_command::find_git_subcommand;
+use crate::parse_command::ParsedCommand;
+use crate::parse_command::parse_command;
+use crate::strip_bash_lc_and_escape;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CommandEffect {
+    ReadsFiles,
+    WritesFiles,
+    Network,
+    Destructive,
+}
+
+impl CommandEffect {
+    pub fn label(self) -> &'static str {
+        match self {
+            CommandEffect::ReadsFiles => "reads files",
+            CommandEffect::WritesFiles => "writes files",
+            CommandEffect::Network => "network",
+            CommandEffect::Destructive => "destructive",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandSummary {
+    /// One sentence describing the command, e.g. "Search src for \"todo\",
+    /// then run `cargo test`".
+    pub explanation: String,
+    /// Sorted and deduplicated.
+    pub effects: Vec<CommandEffect>,
+}
+
+const NETWORK_PROGRAMS: &[&str] = &[
+    "curl", "wget", "ssh", "scp", "sftp", "rsync", "nc", "ncat", "telnet", "ftp", "ping", "dig",
+    "nslookup", "http", "https",
+];
+const WRITE_PROGRAMS: &[&str] = &[
+    "mv", "cp", "touch", "mkdir", "tee", "ln", "chmod", "chown", "install", "patch", "dd",
+    "truncate", "unzip", "tar",
+];
+const DESTRUCTIVE_PROGRAMS: &[&str] = &["rm", "rmdir", "shred", "mkfs", "wipefs"];
+const PACKAGE_MANAGERS: &[&str] = &[
+    "npm", "pnpm", "yarn", "bun", "pip", "pip3", "uv", "cargo", "go", "gem", "brew", "apt",
+    "apt-get", "dnf", "yum",
+];
+const PACKAGE_FETCH_SUBCOMMANDS: &[&str] =
+    &["install", "add", "update", "upgrade", "fetch", "get", "ci", "sync", "publish"];
+const GIT_NETWORK_SUBCOMMANDS: &[&str] = &["clone", "fetch", "pull", "push", "ls-remote", "submodule"];
+const GIT_WRITE_SUBCOMMANDS: &[&str] = &[
+    "add", "commit", "checkout", "switch", "merge", "rebase", "reset", "restore", "rm", "mv",
+    "stash", "apply", "am", "cherry-pick", "revert", "tag", "branch", "init", "clean",
+];
+
+/// Explains `command` and classifies its effects.
+pub fn summarize_command(command: &[String]) -> CommandSummary {
+    let parsed = parse_command(command);
+    let mut effects = Vec::new();
+    let mut steps = Vec::with_capacity(parsed.len());
+    for step in &parsed {
+        match step {
+            ParsedCommand::Read { name, .. } => {
+                effects.push(CommandEffect::ReadsFiles);
+                steps.push(format!("read {name}"));
+            }
+            ParsedCommand::ListFiles { path, .. } => {
+                effects.push(CommandEffect::ReadsFiles);
+                steps.push(match path {
+                    Some(path) => format!("list files in {path}"),
+                    None => "list files".to_owned(),
+                });
+            }
+            ParsedCommand::Search { query, path, .. } => {
+                effects.push(CommandEffect::ReadsFiles);
+                steps.push(match (query, path) {
+                    (Some(query), Some(path)) => format!("search {path} for \"{query}\""),
+                    (Some(query), None) => format!("search for \"{query}\""),
+                    (None, Some(path)) => format!("search {path}"),
+                    (None, None) => "search files".to_owned(),
+                });
+            }
+            ParsedCommand::ReadCommand { cmd } => {
+                effects.push(CommandEffect::ReadsFiles);
+                steps.push(format!("inspect with `{cmd}`"));
+            }
+            ParsedCommand::Unknown { cmd } => {
+                let tokens = shlex_split(cmd)
+                    .unwrap_or_else(|| cmd.split_whitespace().map(str::to_owned).collect());
+                effects.extend(program_effects(&tokens));
+                steps.push(format!("run `{cmd}`"));
+            }
+        }
+    }
+
+    if writes_via_redirect(&strip_bash_lc_and_escape(command)) {
+        effects.push(CommandEffect::WritesFiles);
+    }
+    if command_might_be_dangerous(command) {
+        effects.push(CommandEffect::Destructive);
+    }
+    effects.sort_unstable();
+    effects.dedup();
+
+    let explanation = if steps.is_empty() {
+        format!("run `{}`", strip_bash_lc_and_escape(command))
+    } else {
+        steps.join(", then ")
+    };
+    CommandSummary {
+        explanation: capitalize(&explanation),
+        effects,
+    }
+}
+
+fn program_effects(tokens: &[String]) -> Vec<CommandEffect> {
+    // Skip leading `VAR=value` assignments.
+    let start = tokens
+        .iter()
+        .position(|token| !token.contains('=') || token.starts_with('-'))
+        .unwrap_or(tokens.len());
+    let argv = &tokens[start..];
+    let Some(program) = argv.first() else {
+        return Vec::new();
+    };
+    let program = Path::new(program)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(program);
+    let args = &argv[1..];
+    let subcommand = args.iter().find(|arg| !arg.starts_with('-')).map(String::as_str);
+
+    let mut effects = Vec::new();
+    if NETWORK_PROGRAMS.contains(&program) {
+        effects.push(CommandEffect::Network);
+    }
+    if WRITE_PROGRAMS.contains(&program) {
+        effects.push(CommandEffect::WritesFiles);
+    }
+    if DESTRUCTIVE_PROGRAMS.contains(&program) {
+        effects.push(CommandEffect::Destructive);
+    }
+    if matches!(program, "sed" | "perl")
+        && args.iter().any(|arg| arg.starts_with("-i") || arg.starts_with("-pi"))
+    {
+        effects.push(CommandEffect::WritesFiles);
+    }
+    if find_git_subcommand(argv, GIT_NETWORK_SUBCOMMANDS).is_some() {
+        effects.push(CommandEffect::Network);
+    }
+    if find_git_subcommand(argv, GIT_WRITE_SUBCOMMANDS).is_some() {
+        effects.push(CommandEffect::WritesFiles);
+    }
+    if PACKAGE_MANAGERS.contains(&program)
+        && subcommand.is_some_and(|subcommand| PACKAGE_FETCH_SUBCOMMANDS.contains(&subcommand))
+    {
+        effects.push(CommandEffect::Network);
+        effects.push(CommandEffect::WritesFiles);
+    }
+    effects
+}
+
+/// True when the script redirects output into a file (`>` or `>>`), ignoring
+/// `/dev/null` and descriptor duplication such as `2>&1`.
+fn writes_via_redirect(script: &str) -> bool {
+    let tokens = shlex_split(script)
+        .unwrap_or_else(|| script.split_whitespace().map(str::to_owned).collect());
+    let mut iter = tokens.iter().peekable();
+    while let Some(token) = iter.next() {
+        let op = token.trim_start_matches(|c: char| c.is_ascii_digit() || c == '&');
+        if !op.starts_with('>') {
+            continue;
+        }
+        let target = op.trim_start_matches('>');
+        let target = if target.is_empty() {
+            iter.peek().map_or("", |next| next.as_str())
+        } else {
+            target
+        };
+        if !target.is_empty() && !target.starts_with('&') && target != "/dev/null" {
+            return true;
+        }
+    }
+    false
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn bash(script: &str) -> Vec<String> {
+        vec!["bash".to_owned(), "-lc".to_owned(), script.to_owned()]
+    }
+
+    #[test]
+    fn explains_read_only_commands() {
+        let summary = summarize_command(&bash("rg -n todo src && cat README.md"));
+        assert_eq!(
+            summary,
+            CommandSummary {
+                explanation: "Search src for \"todo\", then read README.md".to_owned(),
+                effects: vec![CommandEffect::ReadsFiles],
+            }
+        );
+    }
+
+    #[test]
+    fn classifies_writes_network_and_destructive_commands() {
+        let effects = |script: &str| summarize_command(&bash(script)).effects;
+        assert_eq!(effects("echo done > notes.txt"), vec![CommandEffect::WritesFiles]);
+        assert_eq!(effects("cargo test 2>&1 > /dev/null"), Vec::<CommandEffect>::new());
+        assert_eq!(effects("git -C repo push origin main"), vec![CommandEffect::Network]);
+        assert_eq!(
+            effects("npm install left-pad"),
+            vec![CommandEffect::WritesFiles, CommandEffect::Network]
+        );
+        assert!(effects("rm -rf build").contains(&CommandEffect::Destructive));
+        assert_eq!(
+            summarize_command(&bash("curl -fsSL https://example.com")).explanation,
+            "Run `curl -fsSL https://example.com`"
+        );
+    }
+}
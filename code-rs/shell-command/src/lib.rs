@@ -7,6 +7,7 @@ use shlex::try_join;
 pub mod bash;
 pub mod command_safety;
 pub mod command_canonicalization;
+pub mod command_summary;
 pub mod parse_command;
 mod invocation;
 
@@ -1,4 +1,5 @@
 mod events;
+mod health;
 mod init;
 mod input;
 mod render;
@@ -10,11 +10,13 @@ impl App<'_> {
     /// Pull the next event with priority for interactive input.
     /// Never returns None due to idleness; only returns None if both channels disconnect.
     pub(super) fn next_event_priority(&mut self) -> Option<AppEvent> {
-        next_event_priority_impl(
+        let event = next_event_priority_impl(
             &self.app_event_rx_high,
             &self.app_event_rx_bulk,
             &mut self.consecutive_high_events,
-        )
+        )?;
+        self.app_event_tx.note_received(&event);
+        Some(event)
     }
 }
 
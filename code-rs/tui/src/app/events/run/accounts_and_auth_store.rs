@@ -218,6 +218,11 @@
                         widget.auto_handle_action(message);
                     }
                 }
+                AppEvent::AutoCoordinatorHandoff { note } => {
+                    if let AppState::Chat { widget } = &mut self.app_state {
+                        widget.auto_handle_handoff(note);
+                    }
+                }
                 AppEvent::AutoCoordinatorTokenMetrics {
                     total_usage,
                     last_turn_usage,
@@ -294,6 +299,13 @@
                                 widget.handle_undo_command();
                             }
                         }
+                        SlashCommand::UndoTurn => {
+                            let turn_id = Some(command_args.trim())
+                                .filter(|id| !id.is_empty())
+                                .map(str::to_owned);
+                            self.app_event_tx
+                                .send(AppEvent::codex_op(Op::RevertTurn { turn_id }));
+                        }
                         SlashCommand::Review => {
                             if let AppState::Chat { widget } = &mut self.app_state {
                                 if command_args.is_empty() {
@@ -328,6 +340,28 @@
                                 widget.show_resume_picker();
                             }
                         }
+                        SlashCommand::Fork => {
+                            if let AppState::Chat { widget } = &mut self.app_state {
+                                let trimmed = command_args.trim();
+                                let at = if trimmed.is_empty() {
+                                    Ok(None)
+                                } else {
+                                    trimmed.parse::<code_core::ForkPoint>().map(Some)
+                                };
+                                match (at, widget.session_id()) {
+                                    (Err(err), _) => widget.debug_notice(format!(
+                                        "Usage: /fork [[SUBMISSION_ID:]SEQ] ({err})"
+                                    )),
+                                    (Ok(at), Some(session_id)) => {
+                                        self.app_event_tx
+                                            .send(AppEvent::ForkSessionAt { session_id, at });
+                                    }
+                                    (Ok(_), None) => {
+                                        widget.debug_notice("Session not ready yet.".to_owned());
+                                    }
+                                }
+                            }
+                        }
                         SlashCommand::Rename => {
                             if let AppState::Chat { widget } = &mut self.app_state {
                                 let trimmed = command_args.trim();
@@ -486,6 +520,11 @@
                                 widget.handle_auto_command(goal);
                             }
                         }
+                        SlashCommand::Migrate => {
+                            if let AppState::Chat { widget } = &mut self.app_state {
+                                widget.handle_migrate_command(&command_args);
+                            }
+                        }
                         SlashCommand::Status => {
                             if let AppState::Chat { widget } = &mut self.app_state {
                                 widget.add_status_output();
@@ -522,11 +561,31 @@
                                 widget.handle_memories_command(command_args);
                             }
                         }
+                        SlashCommand::Prompt => {
+                            if let AppState::Chat { widget } = &mut self.app_state {
+                                widget.handle_prompt_command(command_args);
+                            }
+                        }
                         SlashCommand::Shell => {
                             if let AppState::Chat { widget } = &mut self.app_state {
                                 widget.handle_shell_command(command_args);
                             }
                         }
+                        SlashCommand::Cd => {
+                            if let AppState::Chat { widget } = &mut self.app_state {
+                                widget.handle_cd_command(&command_args);
+                            }
+                        }
+                        SlashCommand::Env => {
+                            if let AppState::Chat { widget } = &mut self.app_state {
+                                widget.handle_env_command(&command_args);
+                            }
+                        }
+                        SlashCommand::Approvals => {
+                            if let AppState::Chat { widget } = &mut self.app_state {
+                                widget.handle_approvals_command(&command_args);
+                            }
+                        }
                         SlashCommand::Notifications => {
                             if let AppState::Chat { widget } = &mut self.app_state {
                                 widget.handle_notifications_command(command_args);
@@ -627,7 +686,11 @@
                         // Prompt-expanding commands should have been handled in submit_user_message
                         // but add a fallback just in case. Use a helper that shows the original
                         // slash command in history while sending the expanded prompt to the model.
-                        SlashCommand::Plan | SlashCommand::Solve | SlashCommand::Code => {
+                        SlashCommand::Plan
+                        | SlashCommand::Solve
+                        | SlashCommand::Code
+                        | SlashCommand::Repro
+                        | SlashCommand::Docsync => {
                             // These should have been expanded already, but handle them anyway
                             if let AppState::Chat { widget } = &mut self.app_state {
                                 let expanded = command.expand_prompt(command_args.trim());
@@ -792,6 +855,35 @@
                         }
                     });
                 }
+                AppEvent::ForkSessionAt { session_id, at } => {
+                    if let AppState::Chat { widget } = &mut self.app_state {
+                        widget.flash_footer_notice("Forking session…");
+                    }
+
+                    let cfg = self.config.clone();
+                    let tx = self.app_event_tx.clone();
+                    tokio::spawn(async move {
+                        let catalog = SessionCatalog::new(cfg.code_home.clone());
+                        let forked = match catalog.find_by_id(&session_id.to_string()).await {
+                            Ok(Some(entry)) => {
+                                let source = catalog.entry_rollout_path(&entry);
+                                code_core::fork_rollout_at(&cfg, &source, at.as_ref()).await
+                            }
+                            Ok(None) => Err(anyhow::anyhow!("session {session_id} is not in the catalog")),
+                            Err(err) => Err(err),
+                        };
+                        match forked {
+                            Ok(new_path) => {
+                                tx.send(AppEvent::ResumeFrom(new_path));
+                            }
+                            Err(err) => {
+                                tx.send(AppEvent::SessionRenameCompleted {
+                                    message: format!("Failed to fork session: {err:#}"),
+                                });
+                            }
+                        }
+                    });
+                }
                 AppEvent::PrepareAgents => {
                     if let AppState::Chat { widget } = &mut self.app_state {
                         widget.prepare_agents();
@@ -258,6 +258,15 @@
                 AppEvent::CodexEvent(event) => {
                     self.dispatch_code_event(*event);
                 }
+                AppEvent::TerminationSignal => {
+                    let shutting_down = match &mut self.app_state {
+                        AppState::Chat { widget } => widget.shutdown_for_signal(),
+                        AppState::Onboarding { .. } => false,
+                    };
+                    if !shutting_down {
+                        self.app_event_tx.send(AppEvent::ExitRequest);
+                    }
+                }
                 AppEvent::ExitRequest => {
                     // Stop background threads and break the UI loop.
                     self.commit_anim_running.store(false, Ordering::Release);
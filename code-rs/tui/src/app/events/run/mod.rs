@@ -10,7 +10,6 @@ use crossterm::execute;
 use crossterm::SynchronizedUpdate;
 
 use code_cloud_tasks_client::{CloudTaskError, TaskId};
-use code_core::config::add_project_allowed_command;
 use code_core::config_types::AuthCredentialsStoreMode;
 use code_core::config_types::Notifications;
 #[cfg(debug_assertions)]
@@ -24,7 +23,6 @@ use crate::app_event::{AppEvent, SessionPickerAction};
 use crate::bottom_pane::SettingsSection;
 use crate::chatwidget::ChatWidget;
 use crate::cloud_tasks_service;
-use crate::exec_command::strip_bash_lc_and_escape;
 use crate::external_editor;
 use crate::get_git_diff::get_git_diff;
 use crate::history_cell;
@@ -80,6 +78,7 @@ impl App<'_> {
 
         'main: loop {
             let Some(event) = self.next_event_priority() else { break 'main };
+            self.maybe_sample_session_health();
             include!("history_insert.rs")
         }
         if self.alt_screen_active {
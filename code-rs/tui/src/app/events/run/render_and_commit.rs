@@ -97,6 +97,8 @@
                     };
 
                     self.redraw_inflight.store(false, Ordering::Release);
+                    self.last_frame_drawn_at = Some(Instant::now());
+                    self.low_bandwidth_redraw_deferred.store(false, Ordering::Release);
                     let needs_follow_up = self.post_frame_redraw.swap(false, Ordering::AcqRel);
                     if needs_follow_up {
                         self.schedule_redraw();
@@ -106,6 +108,9 @@
                         Ok(()) => {
                             self.stdout_backpressure_skips = 0;
                             if self.timing_enabled { self.timing.on_redraw_end(t0); }
+                            if let AppState::Chat { widget } = &mut self.app_state {
+                                widget.perf_record_frame_draw(t0.elapsed());
+                            }
                         }
                         Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
                             // A draw can fail after partially writing to the terminal. In that case,
@@ -143,6 +148,12 @@
                             .tui
                             .stream
                             .commit_tick_ms
+                            .or_else(|| {
+                                self.config
+                                    .tui
+                                    .low_bandwidth
+                                    .then_some(crate::timing::LOW_BANDWIDTH_COMMIT_TICK_MS)
+                            })
                             .or_else(|| self.config.tui.stream.responsive.then_some(30))
                             .unwrap_or(50);
                         if thread_spawner::spawn_lightweight("commit-anim", move || {
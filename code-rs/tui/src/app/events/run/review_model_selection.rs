@@ -188,6 +188,21 @@
                         widget.handle_git_init_finished(ok, message);
                     }
                 }
+                AppEvent::ShowProjectTrustPrompt { project, ignored } => {
+                    if let AppState::Chat { widget } = &mut self.app_state {
+                        widget.show_project_trust_prompt(project, ignored);
+                    }
+                }
+                AppEvent::TrustProject { project } => {
+                    if let AppState::Chat { widget } = &mut self.app_state {
+                        widget.trust_project(project);
+                    }
+                }
+                AppEvent::ShowCrashRecoveryPrompt { marker, path, snippet } => {
+                    if let AppState::Chat { widget } = &mut self.app_state {
+                        widget.show_crash_recovery_prompt(marker, path, snippet);
+                    }
+                }
                 AppEvent::SubmitTextWithPreface { visible, preface } => {
                     if let AppState::Chat { widget } = &mut self.app_state {
                         widget.submit_text_message_with_preface(visible, preface);
@@ -290,6 +305,7 @@
                             branch,
                             has_findings,
                             findings,
+                            below_threshold: 0,
                             summary,
                             error,
                             agent_id,
@@ -302,6 +318,26 @@
                         widget.show_review_custom_prompt();
                     }
                 }
+                AppEvent::OpenReviewFinding { index } => {
+                    if let AppState::Chat { widget } = &mut self.app_state {
+                        widget.open_review_finding(index);
+                    }
+                }
+                AppEvent::FixReviewFindings { indices } => {
+                    if let AppState::Chat { widget } = &mut self.app_state {
+                        widget.fix_review_findings(&indices);
+                    }
+                }
+                AppEvent::ToggleAutoResolveFinding { index } => {
+                    if let AppState::Chat { widget } = &mut self.app_state {
+                        widget.toggle_auto_resolve_finding(index);
+                    }
+                }
+                AppEvent::ConfirmAutoResolveFindings { fix } => {
+                    if let AppState::Chat { widget } = &mut self.app_state {
+                        widget.confirm_auto_resolve_findings(fix);
+                    }
+                }
                 event => {
                     include!("cloud_tasks.rs")
                 }
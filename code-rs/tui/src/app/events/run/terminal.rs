@@ -7,27 +7,11 @@
                 AppEvent::RegisterApprovedCommand { command, match_kind, persist, semantic_prefix } => {
                     if let AppState::Chat { widget } = &mut self.app_state {
                         widget.register_approved_command(
-                            command.clone(),
+                            command,
                             match_kind,
-                            semantic_prefix.clone(),
+                            semantic_prefix,
+                            persist,
                         );
-                        if persist {
-                            if let Err(err) = add_project_allowed_command(
-                                &self.config.code_home,
-                                &self.config.cwd,
-                                &command,
-                                match_kind,
-                            ) {
-                                widget.history_push_plain_state(history_cell::new_error_event(format!(
-                                    "Failed to persist always-allow command: {err:#}",
-                                )));
-                            } else {
-                                let display = strip_bash_lc_and_escape(&command);
-                                widget.push_background_tail(format!(
-                                    "Always allowing `{display}` for this project.",
-                                ));
-                            }
-                        }
                     }
                 }
                 AppEvent::MarkTaskIdle => {
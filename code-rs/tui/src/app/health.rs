@@ -0,0 +1,35 @@
+use std::time::Instant;
+
+use super::state::{App, AppState};
+
+impl App<'_> {
+    /// Samples session metrics when the sampling interval has elapsed. Called
+    /// from the event loop, so an idle session (no events) is not sampled.
+    pub(super) fn maybe_sample_session_health(&mut self) {
+        let now = Instant::now();
+        if !self.session_health.is_due(now) {
+            return;
+        }
+        let mut sample = self.session_health.sample_process(now);
+        (sample.queue_high, sample.queue_bulk) = self.app_event_tx.queue_depth();
+        if let AppState::Chat { widget } = &self.app_state {
+            sample.history_records = widget.history_record_count();
+        }
+        tracing::debug!(
+            target: "code_tui::session_health",
+            rss_bytes = ?sample.rss_bytes,
+            cpu_percent = ?sample.cpu_percent,
+            history_records = sample.history_records,
+            queue_high = sample.queue_high,
+            queue_bulk = sample.queue_bulk,
+            child_processes = ?sample.child_processes,
+            "session health sample"
+        );
+
+        if let Some(crossing) = self.session_health.check_soft_limit(&sample)
+            && let AppState::Chat { widget } = &mut self.app_state
+        {
+            widget.handle_memory_soft_limit(crossing);
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use std::collections::{HashMap, HashSet};
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
 use std::sync::mpsc::channel;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -44,6 +44,7 @@ impl App<'_> {
             fork_source_path,
             startup_footer_notice,
             latest_upgrade_version,
+            crashed_session,
         } = args;
         let auth_manager = AuthManager::shared_with_mode_and_originator(
             config.code_home.clone(),
@@ -124,7 +125,7 @@ impl App<'_> {
         #[cfg(unix)]
         let mut sigterm_guard = None;
         #[cfg(unix)]
-        let sigterm_flag = Arc::new(AtomicBool::new(false));
+        let termination_signal = Arc::new(AtomicI32::new(0));
         {
             let app_event_tx = app_event_tx.clone();
             let input_running_thread = input_running.clone();
@@ -230,16 +231,25 @@ impl App<'_> {
         #[cfg(unix)]
         {
             let term_trigger = Arc::new(AtomicBool::new(false));
+            let hup_trigger = Arc::new(AtomicBool::new(false));
             let tx = app_event_tx.clone();
             let running_for_thread = input_running.clone();
-            let trigger_for_thread = Arc::clone(&term_trigger);
-            let flag_for_thread = sigterm_flag.clone();
+            let term_for_thread = Arc::clone(&term_trigger);
+            let hup_for_thread = Arc::clone(&hup_trigger);
+            let signal_for_thread = termination_signal.clone();
             let listener = move || {
                 while running_for_thread.load(Ordering::Relaxed) {
-                    if trigger_for_thread.swap(false, Ordering::SeqCst) {
+                    let signal = if term_for_thread.swap(false, Ordering::SeqCst) {
+                        Some(SIGTERM)
+                    } else if hup_for_thread.swap(false, Ordering::SeqCst) {
+                        Some(SIGHUP)
+                    } else {
+                        None
+                    };
+                    if let Some(signal) = signal {
                         running_for_thread.store(false, Ordering::Release);
-                        flag_for_thread.store(true, Ordering::Release);
-                        tx.send(AppEvent::ExitRequest);
+                        signal_for_thread.store(signal, Ordering::Release);
+                        tx.send(AppEvent::TerminationSignal);
                         break;
                     }
                     std::thread::sleep(Duration::from_millis(50));
@@ -258,7 +268,7 @@ impl App<'_> {
                 }
                 // SIGHUP (terminal disconnect / Termux OOM) triggers the same
                 // clean-exit path so raw mode and alt screen are restored.
-                if let Err(err) = flag::register(SIGHUP, Arc::clone(&term_trigger)) {
+                if let Err(err) = flag::register(SIGHUP, Arc::clone(&hup_trigger)) {
                     tracing::warn!("failed to register SIGHUP handler: {err}");
                 }
             } else {
@@ -319,6 +329,9 @@ impl App<'_> {
             if let Some(path) = fork_source_path {
                 app_event_tx.send(AppEvent::ForkFrom(path));
             }
+            if let Some(marker) = crashed_session {
+                chat_widget.maybe_offer_crash_recovery(marker);
+            }
             AppState::Chat {
                 widget: Box::new(chat_widget),
             }
@@ -326,6 +339,8 @@ impl App<'_> {
 
         let file_search = FileSearchManager::new(config.cwd.clone(), app_event_tx.clone());
         let start_in_alt = config.tui.alternate_screen;
+        let session_health =
+            crate::session_health::SessionHealthMonitor::new(config.tui.memory_soft_limit_mb);
         Self {
             _server: conversation_manager,
             app_event_tx,
@@ -342,6 +357,9 @@ impl App<'_> {
             redraw_inflight,
             post_frame_redraw,
             stdout_backpressure_skips: 0,
+            last_frame_drawn_at: None,
+            low_bandwidth_redraw_deferred: AtomicBool::new(false),
+            session_health,
             frame_timer,
             input_running,
             input_suspended,
@@ -367,7 +385,7 @@ impl App<'_> {
             #[cfg(unix)]
             sigterm_guard,
             #[cfg(unix)]
-            sigterm_flag,
+            termination_signal,
         }
     }
 }
@@ -11,6 +11,7 @@ use ratatui::layout::Rect;
 
 use crate::app_event::AppEvent;
 use crate::thread_spawner;
+use crate::timing::{LOW_BANDWIDTH_ANIMATION_INTERVAL, LOW_BANDWIDTH_REDRAW_INTERVAL};
 use crate::tui;
 
 use super::state::{App, AppState, REDRAW_DEBOUNCE};
@@ -21,6 +22,27 @@ impl App<'_> {
     /// animation scheduled a future frame), we still trigger an immediate redraw
     /// to keep keypress echo latency low.
     pub(super) fn schedule_redraw(&self) {
+        // Low-bandwidth mode trades keypress echo latency for fewer frames:
+        // requests that arrive too soon after the last frame collapse into one
+        // deferred redraw on the frame timer.
+        if self.config.tui.low_bandwidth
+            && let Some(last) = self.last_frame_drawn_at
+        {
+            let elapsed = last.elapsed();
+            if elapsed < LOW_BANDWIDTH_REDRAW_INTERVAL {
+                if !self
+                    .low_bandwidth_redraw_deferred
+                    .swap(true, Ordering::AcqRel)
+                {
+                    self.frame_timer.schedule(
+                        LOW_BANDWIDTH_REDRAW_INTERVAL - elapsed,
+                        self.app_event_tx.clone(),
+                    );
+                }
+                return;
+            }
+        }
+
         // Only queue a new frame when one is not already in flight; otherwise record
         // that we owe a follow-up immediately after the active frame completes.
         let should_send = self
@@ -52,7 +74,15 @@ impl App<'_> {
     }
 
     /// Schedule a redraw after the specified duration.
+    ///
+    /// In low-bandwidth mode the delay is floored so per-frame animations
+    /// (spinners, shimmer, header wave) tick at most once per second.
     pub(super) fn schedule_redraw_in(&self, duration: Duration) {
+        let duration = if self.config.tui.low_bandwidth {
+            duration.max(LOW_BANDWIDTH_ANIMATION_INTERVAL)
+        } else {
+            duration
+        };
         self.frame_timer
             .schedule(duration, self.app_event_tx.clone());
     }
@@ -5,9 +5,11 @@ use super::{App, AppState};
 impl App<'_> {
     pub(crate) const DEFAULT_TERMINAL_TITLE: &'static str = "Code";
 
+    /// The SIGTERM/SIGHUP that ended the session, if any.
     #[cfg(unix)]
-    pub(crate) fn sigterm_triggered(&self) -> bool {
-        self.sigterm_flag.load(Ordering::Relaxed)
+    pub(crate) fn termination_signal(&self) -> Option<i32> {
+        let signal = self.termination_signal.load(Ordering::Acquire);
+        (signal != 0).then_some(signal)
     }
 
     #[cfg(unix)]
@@ -149,6 +149,13 @@ pub(crate) struct App<'a> {
     pub(super) post_frame_redraw: Arc<AtomicBool>,
     /// Count of consecutive redraws skipped because stdout/PTY was not writable.
     pub(super) stdout_backpressure_skips: u32,
+    /// When the last frame finished drawing; low-bandwidth mode batches redraw
+    /// requests that arrive within `LOW_BANDWIDTH_REDRAW_INTERVAL` of it.
+    pub(super) last_frame_drawn_at: Option<Instant>,
+    /// True while a batched low-bandwidth redraw is armed on the frame timer.
+    pub(super) low_bandwidth_redraw_deferred: AtomicBool,
+    /// Periodic RSS/CPU/queue sampling and the memory soft-limit guardrail.
+    pub(super) session_health: crate::session_health::SessionHealthMonitor,
     /// Shared scheduler for future animation frames. Ensures the shortest
     /// requested interval wins while preserving later deadlines.
     pub(super) frame_timer: Arc<FrameTimer>,
@@ -176,8 +183,9 @@ pub(crate) struct App<'a> {
 
     #[cfg(unix)]
     pub(super) sigterm_guard: Option<SigId>,
+    /// Signal number of the SIGTERM/SIGHUP that ended the session, or 0.
     #[cfg(unix)]
-    pub(super) sigterm_flag: Arc<AtomicBool>,
+    pub(super) termination_signal: Arc<std::sync::atomic::AtomicI32>,
 
     /// Perform a hard clear on the first frame to ensure the entire buffer
     /// starts with our theme background. This avoids terminals that may show
@@ -250,6 +258,8 @@ pub(crate) struct AppInitArgs {
     pub(crate) fork_source_path: Option<PathBuf>,
     pub(crate) startup_footer_notice: Option<String>,
     pub(crate) latest_upgrade_version: Option<String>,
+    /// Session for this cwd that ended without a clean shutdown.
+    pub(crate) crashed_session: Option<crate::resume::crash_recovery::SessionMarker>,
 }
 
 pub(super) struct BufferDiffProfiler {
@@ -19,6 +19,7 @@ use code_core::config_types::McpServerSchedulingToml;
 use code_core::config_types::McpToolSchedulingOverrideToml;
 #[cfg(feature = "managed-network-proxy")]
 use code_core::config::NetworkProxySettingsToml;
+use code_core::config_loader::IgnoredProjectLayer;
 use code_core::plugins::{
     ConfiguredMarketplace,
     MarketplaceListError,
@@ -271,6 +272,10 @@ pub(crate) enum AppEvent {
     /// Request to exit the application gracefully.
     ExitRequest,
 
+    /// SIGTERM/SIGHUP arrived: shut the session down (abort the turn, stop
+    /// child processes, flush the rollout) before exiting.
+    TerminationSignal,
+
     /// Forward an `Op` to the Agent. Using an `AppEvent` for this avoids
     /// bubbling channels through layers of widgets.
     CodexOp(Box<code_core::protocol::Op>),
@@ -303,6 +308,10 @@ pub(crate) enum AppEvent {
     AutoCoordinatorAction {
         message: String,
     },
+    /// Handoff note written by the wrap-up turn after the turn cap was reached.
+    AutoCoordinatorHandoff {
+        note: String,
+    },
     AutoCoordinatorTokenMetrics {
         total_usage: TokenUsage,
         last_turn_usage: TokenUsage,
@@ -557,6 +566,19 @@ pub(crate) enum AppEvent {
     /// Git init completed (success or failure).
     GitInitFinished { ok: bool, message: String },
 
+    /// The project is untrusted and has `.code/` config or hooks that were skipped.
+    ShowProjectTrustPrompt { project: PathBuf, ignored: Vec<IgnoredProjectLayer> },
+    /// Persist a trust grant for the project to the user config.
+    TrustProject { project: PathBuf },
+
+    /// The previous session in this cwd ended without a clean shutdown; offer
+    /// to resume it from its rollout at `path`.
+    ShowCrashRecoveryPrompt {
+        marker: crate::resume::crash_recovery::SessionMarker,
+        path: PathBuf,
+        snippet: Option<String>,
+    },
+
     /// Submit a message with hidden preface instructions
     SubmitTextWithPreface { visible: String, preface: String },
 
@@ -614,6 +636,18 @@ pub(crate) enum AppEvent {
     /// Show the multi-line prompt input to collect custom review instructions.
     OpenReviewCustomPrompt,
 
+    /// Open a finding from the last review next to the code it refers to.
+    OpenReviewFinding { index: usize },
+
+    /// Fix findings from the last review, one turn per finding.
+    FixReviewFindings { indices: Vec<usize> },
+
+    /// Auto-resolve finding picker: flip whether a finding goes to the fix phase.
+    ToggleAutoResolveFinding { index: usize },
+
+    /// Auto-resolve finding picker: fix the selected findings, or none.
+    ConfirmAutoResolveFindings { fix: bool },
+
     /// Cloud tasks: fetch the latest list based on the active environment filter.
     FetchCloudTasks { environment: Option<String> },
     /// Cloud tasks: response containing the refreshed task list.
@@ -776,6 +810,13 @@ pub(crate) enum AppEvent {
     /// Fork a rollout into a new session file and resume it.
     ForkFrom(std::path::PathBuf),
 
+    /// Fork the stored session `session_id`, up to `at` when set, and resume
+    /// the copy.
+    ForkSessionAt {
+        session_id: uuid::Uuid,
+        at: Option<code_core::ForkPoint>,
+    },
+
     /// Begin jump-back to the Nth last user message (1 = latest).
     /// Trims visible history up to that point and pre-fills the composer.
     JumpBack { nth: usize, prefill: String, history_snapshot: Option<HistorySnapshot> },
@@ -797,7 +838,8 @@ pub(crate) enum AppEvent {
     /// (clear spinner/status, finalize running exec/tool cells) while the core
     /// continues its own abort/cleanup in parallel.
     CancelRunningTask,
-    /// Register a command pattern as approved, optionally persisting to config.
+    /// Register a command pattern as approved, optionally persisting it to the
+    /// project's `.code/approvals.toml`.
     RegisterApprovedCommand {
         command: Vec<String>,
         match_kind: ApprovedCommandMatchKind,
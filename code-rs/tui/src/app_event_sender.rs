@@ -1,3 +1,5 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::mpsc::Sender;
 
 use crate::app_event::{AppEvent, BackgroundPlacement};
@@ -11,17 +13,62 @@ pub(crate) struct AppEventSender {
     high_tx: Sender<AppEvent>,
     // Bulk/streaming events (history inserts, commit ticks, file search, etc.).
     bulk_tx: Sender<AppEvent>,
+    // Events sent but not yet taken by the app loop; shared by all clones.
+    depth: Arc<QueueDepthCounters>,
+}
+
+#[derive(Debug, Default)]
+struct QueueDepthCounters {
+    high: AtomicUsize,
+    bulk: AtomicUsize,
+}
+
+impl QueueDepthCounters {
+    fn counter(&self, is_high: bool) -> &AtomicUsize {
+        if is_high { &self.high } else { &self.bulk }
+    }
+}
+
+fn is_high_priority(event: &AppEvent) -> bool {
+    matches!(
+        event,
+        AppEvent::KeyEvent(_)
+            | AppEvent::MouseEvent(_)
+            | AppEvent::Paste(_)
+            | AppEvent::RequestRedraw
+            | AppEvent::Redraw
+            | AppEvent::ExitRequest
+            | AppEvent::TerminationSignal
+            | AppEvent::SetTerminalTitle { .. }
+            | AppEvent::EmitTuiNotification { .. }
+            | AppEvent::AutoCoordinatorCountdown { .. }
+    )
 }
 
 impl AppEventSender {
     /// Create a sender that splits events by priority across two channels.
     pub(crate) fn new_dual(high_tx: Sender<AppEvent>, bulk_tx: Sender<AppEvent>) -> Self {
-        Self { high_tx, bulk_tx }
+        Self { high_tx, bulk_tx, depth: Arc::default() }
     }
     /// Backward‑compatible constructor for tests/fixtures that expect a single
     /// channel. Routes both high‑priority and bulk events to the same sender.
     pub(crate) fn new(app_event_tx: Sender<AppEvent>) -> Self {
-        Self { high_tx: app_event_tx.clone(), bulk_tx: app_event_tx }
+        Self { high_tx: app_event_tx.clone(), bulk_tx: app_event_tx, depth: Arc::default() }
+    }
+
+    /// Approximate number of queued `(high, bulk)` events. Only meaningful when
+    /// the receiving loop calls [`Self::note_received`] for each event.
+    pub(crate) fn queue_depth(&self) -> (usize, usize) {
+        (
+            self.depth.high.load(Ordering::Relaxed),
+            self.depth.bulk.load(Ordering::Relaxed),
+        )
+    }
+
+    /// Record that the app loop dequeued `event`.
+    pub(crate) fn note_received(&self, event: &AppEvent) {
+        let counter = self.depth.counter(is_high_priority(event));
+        let _ = counter.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1));
     }
 
     /// Send an event to the app event channel. If it fails, we swallow the
@@ -39,22 +86,14 @@ impl AppEventSender {
         if !matches!(event, AppEvent::CodexOp(_)) {
             session_log::log_inbound_app_event(&event);
         }
-        let is_high = matches!(
-            event,
-            AppEvent::KeyEvent(_)
-                | AppEvent::MouseEvent(_)
-                | AppEvent::Paste(_)
-                | AppEvent::RequestRedraw
-                | AppEvent::Redraw
-                | AppEvent::ExitRequest
-                | AppEvent::SetTerminalTitle { .. }
-                | AppEvent::EmitTuiNotification { .. }
-                | AppEvent::AutoCoordinatorCountdown { .. }
-        );
+        let is_high = is_high_priority(&event);
 
         let tx = if is_high { &self.high_tx } else { &self.bulk_tx };
         match tx.send(event) {
-            Ok(()) => true,
+            Ok(()) => {
+                self.depth.counter(is_high).fetch_add(1, Ordering::Relaxed);
+                true
+            }
             Err(std::sync::mpsc::SendError(event)) => {
                 tracing::error!(?event, "failed to send event: sending on a closed channel");
                 false
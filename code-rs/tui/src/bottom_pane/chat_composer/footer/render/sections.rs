@@ -81,6 +81,23 @@ pub(super) fn build_sections(
     let ctrl_c_present = !ctrl_c_spans.is_empty();
 
     let mut left_misc_after_ctrlc: Vec<Span<'static>> = Vec::new();
+    // Queue chip: inputs waiting for the running turn's next model request.
+    if view.queued_input_count > 0 {
+        left_misc_after_ctrlc.push(
+            Span::from(format!(" {} queued ", view.queued_input_count))
+                .style(Style::default().fg(crate::colors::info()).add_modifier(Modifier::REVERSED)),
+        );
+    }
+    // While busy, Enter queues the draft; Alt+Enter interrupts instead.
+    if view.is_task_running && !view.auto_drive_active && !view.is_empty() {
+        if !left_misc_after_ctrlc.is_empty() {
+            left_misc_after_ctrlc.push(Span::from("  "));
+        }
+        left_misc_after_ctrlc.push(Span::from("Enter").style(key_hint_style));
+        left_misc_after_ctrlc.push(Span::from(" queue  ").style(label_style));
+        left_misc_after_ctrlc.push(Span::from("Alt+Enter").style(key_hint_style));
+        left_misc_after_ctrlc.push(Span::from(" interrupt").style(label_style));
+    }
     if let Some(hint) = &view.standard_terminal_hint {
         if view.auto_drive_active {
             let (left_hint, right_hint) = match hint.split_once('\t') {
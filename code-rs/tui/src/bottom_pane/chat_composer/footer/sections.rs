@@ -129,7 +129,7 @@ impl ChatComposer {
             ],
         };
 
-        let status_spans = match status.status {
+        let mut status_spans = match status.status {
             AutoReviewIndicatorStatus::Running => {
                 let phase_label = match status.phase {
                     AutoReviewPhase::Resolving => "Resolving",
@@ -175,6 +175,17 @@ impl ChatComposer {
                 ]
             }
         };
+        if status.below_threshold > 0
+            && matches!(
+                status.status,
+                AutoReviewIndicatorStatus::Clean | AutoReviewIndicatorStatus::Fixed
+            )
+        {
+            status_spans.push(Span::styled(
+                format!(" · {} below threshold", status.below_threshold),
+                label_style,
+            ));
+        }
 
         (status_spans, agent_hint_spans)
     }
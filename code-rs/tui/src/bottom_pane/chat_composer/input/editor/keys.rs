@@ -170,58 +170,72 @@ pub(super) fn handle_key_event_without_popup(
                 _ => unreachable!("outer match restricts code to Up/Down"),
             }
         }
+        // -------------------------------------------------------------
+        // Alt+Enter while a turn is running — interrupt it and send the
+        // message now instead of queueing it for the next model request.
+        // -------------------------------------------------------------
+        KeyEvent {
+            code: KeyCode::Enter,
+            modifiers: KeyModifiers::ALT,
+            kind: KeyEventKind::Press,
+            ..
+        } if view.is_task_running => match submit_composer_text(view) {
+            (InputResult::Submitted(text), redraw) => (InputResult::SubmittedInterrupt(text), redraw),
+            other => other,
+        },
         KeyEvent {
             code: KeyCode::Enter,
             modifiers: KeyModifiers::NONE,
             kind: KeyEventKind::Press | KeyEventKind::Repeat,
             ..
-        } => {
-            if view.handle_backslash_continuation() {
-                return (InputResult::None, true);
-            }
-            let original_text = view.textarea.text().to_owned();
-            let first_line = original_text.lines().next().unwrap_or("");
-            if let Some((name, rest)) = parse_slash_name(first_line)
-                && rest.is_empty()
-                && let Some(cmd) = ChatComposer::resolve_builtin_slash_command(name)
-            {
-                if cmd.is_prompt_expanding() {
-                    view.app_event_tx.send(crate::app_event::AppEvent::PrepareAgents);
-                }
-                view.history.record_local_submission(&original_text);
-                view.app_event_tx
-                    .send(crate::app_event::AppEvent::DispatchCommand(cmd, original_text));
-                view.textarea.set_text("");
-                view.active_popup = ActivePopup::None;
-                return (InputResult::Command(cmd), true);
-            }
+        } => submit_composer_text(view),
+        input => view.handle_input_basic(input),
+    }
+}
 
-            let mut text = original_text.clone();
-            view.textarea.set_text("");
+fn submit_composer_text(view: &mut ChatComposer) -> (InputResult, bool) {
+    if view.handle_backslash_continuation() {
+        return (InputResult::None, true);
+    }
+    let original_text = view.textarea.text().to_owned();
+    let first_line = original_text.lines().next().unwrap_or("");
+    if let Some((name, rest)) = parse_slash_name(first_line)
+        && rest.is_empty()
+        && let Some(cmd) = ChatComposer::resolve_builtin_slash_command(name)
+    {
+        if cmd.is_prompt_expanding() {
+            view.app_event_tx.send(crate::app_event::AppEvent::PrepareAgents);
+        }
+        view.history.record_local_submission(&original_text);
+        view.app_event_tx
+            .send(crate::app_event::AppEvent::DispatchCommand(cmd, original_text));
+        view.textarea.set_text("");
+        view.active_popup = ActivePopup::None;
+        return (InputResult::Command(cmd), true);
+    }
 
-            // Replace all pending pastes in the text
-            for (placeholder, actual) in &view.pending_pastes {
-                if text.contains(placeholder) {
-                    text = text.replace(placeholder, actual);
-                }
-            }
-            view.pending_pastes.clear();
+    let mut text = original_text.clone();
+    view.textarea.set_text("");
 
-            if text.is_empty() {
-                (InputResult::None, true)
-            } else {
-                if let Some((name, _rest)) = parse_slash_name(first_line)
-                    && let Some(cmd) = ChatComposer::resolve_builtin_slash_command(name)
-                    && cmd.is_prompt_expanding()
-                {
-                    view.app_event_tx.send(crate::app_event::AppEvent::PrepareAgents);
-                }
+    // Replace all pending pastes in the text
+    for (placeholder, actual) in &view.pending_pastes {
+        if text.contains(placeholder) {
+            text = text.replace(placeholder, actual);
+        }
+    }
+    view.pending_pastes.clear();
 
-                view.history.record_local_submission(&original_text);
-                (InputResult::Submitted(text), true)
-            }
+    if text.is_empty() {
+        (InputResult::None, true)
+    } else {
+        if let Some((name, _rest)) = parse_slash_name(first_line)
+            && let Some(cmd) = ChatComposer::resolve_builtin_slash_command(name)
+            && cmd.is_prompt_expanding()
+        {
+            view.app_event_tx.send(crate::app_event::AppEvent::PrepareAgents);
         }
-        input => view.handle_input_basic(input),
+
+        view.history.record_local_submission(&original_text);
+        (InputResult::Submitted(text), true)
     }
 }
-
@@ -89,6 +89,9 @@ struct TokenCursorContext<'a> {
 #[derive(Debug, PartialEq)]
 pub(crate) enum InputResult {
     Submitted(String),
+    /// Alt+Enter while a turn is running: interrupt the turn and send now
+    /// rather than queueing for its next model request.
+    SubmittedInterrupt(String),
     Command(SlashCommand),
     ScrollUp,
     ScrollDown,
@@ -128,6 +131,7 @@ pub(crate) struct AutoReviewFooterStatus {
     pub(crate) status: AutoReviewIndicatorStatus,
     pub(crate) findings: Option<usize>,
     pub(crate) phase: AutoReviewPhase,
+    pub(crate) below_threshold: usize,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -177,6 +181,8 @@ pub(crate) struct ChatComposer {
     access_mode_label: Option<String>,
     access_mode_label_expiry: Option<std::time::Instant>,
     access_mode_hint_expiry: Option<std::time::Instant>,
+    // Inputs queued in core for the running turn, shown as a footer chip
+    queued_input_count: usize,
     // Footer hint visibility flags
     show_reasoning_hint: bool,
     show_diffs_hint: bool,
@@ -257,6 +263,7 @@ impl ChatComposer {
             access_mode_label: None,
             access_mode_label_expiry: None,
             access_mode_hint_expiry: None,
+            queued_input_count: 0,
             show_reasoning_hint: false,
             show_diffs_hint: false,
             reasoning_shown: false,
@@ -395,6 +402,10 @@ impl ChatComposer {
         self.access_mode_hint_expiry = Some(std::time::Instant::now() + dur);
     }
 
+    pub(crate) fn set_queued_input_count(&mut self, count: usize) {
+        self.queued_input_count = count;
+    }
+
     pub fn set_reasoning_state(&mut self, shown: bool) {
         self.reasoning_shown = shown;
     }
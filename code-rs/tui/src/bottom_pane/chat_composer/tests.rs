@@ -15,6 +15,7 @@ fn auto_review_status_stays_left_with_auto_drive_footer() {
         status: AutoReviewIndicatorStatus::Running,
         findings: None,
         phase: AutoReviewPhase::Reviewing,
+        below_threshold: 0,
     }));
 
     let area = Rect {
@@ -38,6 +39,35 @@ fn auto_review_status_stays_left_with_auto_drive_footer() {
     assert!(auto_idx < esc_idx, "Auto Review status should be left-most");
 }
 
+#[test]
+fn auto_review_footer_shows_below_threshold_count() {
+    let (tx, _rx) = std::sync::mpsc::channel::<AppEvent>();
+    let app_tx = AppEventSender::new(tx);
+    let mut composer = ChatComposer::new(true, app_tx, true);
+
+    composer.set_auto_review_status(Some(AutoReviewFooterStatus {
+        status: AutoReviewIndicatorStatus::Clean,
+        findings: None,
+        phase: AutoReviewPhase::Reviewing,
+        below_threshold: 2,
+    }));
+
+    let area = Rect {
+        x: 0,
+        y: 0,
+        width: 96,
+        height: 1,
+    };
+    let mut buf = Buffer::empty(area);
+    composer.render_footer(area, &mut buf);
+
+    let line: String = (0..area.width)
+        .map(|x| buf[(area.x + x, area.y)].symbol().to_string())
+        .collect();
+
+    assert!(line.contains("Correct · 2 below threshold"), "footer was: {line}");
+}
+
 #[test]
 fn footer_shows_1m_context_suffix_when_extended_context_is_active() {
     let (tx, _rx) = std::sync::mpsc::channel::<AppEvent>();
@@ -246,7 +276,7 @@ fn builtin_slash_popup_confirm_canonicalizes_partial_command_text() {
 
     let (result, handled) = composer.confirm_slash_popup_selection();
 
-    assert_eq!(result, InputResult::Command(SlashCommand::Prompts));
+    assert_eq!(result, InputResult::Command(SlashCommand::Prompt));
     assert!(handled);
 
     let events = rx.try_iter().collect::<Vec<_>>();
@@ -260,8 +290,8 @@ fn builtin_slash_popup_confirm_canonicalizes_partial_command_text() {
         panic!("expected DispatchCommand event, got: {events:?}");
     };
 
-    assert_eq!(cmd, SlashCommand::Prompts);
-    assert_eq!(payload, "/prompts");
+    assert_eq!(cmd, SlashCommand::Prompt);
+    assert_eq!(payload, "/prompt");
 }
 
 #[test]
@@ -301,3 +331,52 @@ fn insert_selected_path_quotes_and_escapes_internal_quotes() {
         "\"/tmp/my \\\"quoted\\\" file.txt\" "
     );
 }
+
+#[test]
+fn alt_enter_interrupts_only_while_a_task_is_running() {
+    let (tx, _rx) = std::sync::mpsc::channel::<AppEvent>();
+    let app_tx = AppEventSender::new(tx);
+    let mut composer = ChatComposer::new(true, app_tx, true);
+    let alt_enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::ALT);
+
+    composer.textarea.set_text("first line");
+    composer.textarea.set_cursor("first line".len());
+    let (result, _) = composer.handle_key_event(alt_enter);
+    assert_eq!(result, InputResult::None);
+    assert_eq!(composer.textarea.text(), "first line\n");
+
+    composer.is_task_running = true;
+    composer.textarea.set_text("stop and do this");
+    let (result, _) = composer.handle_key_event(alt_enter);
+    assert_eq!(
+        result,
+        InputResult::SubmittedInterrupt("stop and do this".to_string())
+    );
+    assert!(composer.is_empty());
+}
+
+#[test]
+fn footer_shows_queue_chip_and_send_choice_while_busy() {
+    let (tx, _rx) = std::sync::mpsc::channel::<AppEvent>();
+    let app_tx = AppEventSender::new(tx);
+    let mut composer = ChatComposer::new(true, app_tx, true);
+    composer.is_task_running = true;
+    composer.set_queued_input_count(2);
+    composer.textarea.set_text("draft");
+
+    let area = Rect {
+        x: 0,
+        y: 0,
+        width: 96,
+        height: 1,
+    };
+    let mut buf = Buffer::empty(area);
+    composer.render_footer(area, &mut buf);
+
+    let line: String = (0..area.width)
+        .map(|x| buf[(area.x + x, area.y)].symbol().to_string())
+        .collect();
+
+    assert!(line.contains("2 queued"), "footer was: {line}");
+    assert!(line.contains("Alt+Enter interrupt"), "footer was: {line}");
+}
@@ -3,6 +3,8 @@ pub(crate) mod app_link_view;
 pub(crate) mod auto_coordinator;
 pub(crate) mod cloud_tasks;
 pub(crate) mod custom_prompt;
+pub(crate) mod prompt_stack;
 pub(crate) mod request_user_input;
+pub(crate) mod review_findings;
 pub(crate) mod resume_selection;
 pub(crate) mod undo_timeline;
@@ -0,0 +1,248 @@
+//! `/prompt`: the system/developer message stack the next turn will send,
+//! with a token estimate per segment. Optional segments can be switched off
+//! for the next turn only.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Modifier;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Paragraph, Widget, Wrap};
+
+use code_core::protocol::{Op, PromptSegment};
+use code_protocol::num_format::format_with_separators_u64;
+
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+use crate::bottom_pane::popup_consts::MAX_POPUP_ROWS;
+use crate::bottom_pane::settings_ui::hints::{self, KeyHint};
+use crate::bottom_pane::{BottomPane, BottomPaneView, CancellationEvent, ConditionalUpdate};
+use crate::components::popup_frame::render_popup_frame;
+
+const PREVIEW_ROWS: u16 = 8;
+
+pub(crate) struct PromptStackView {
+    segments: Vec<PromptSegment>,
+    selected: usize,
+    complete: bool,
+    app_event_tx: AppEventSender,
+}
+
+impl PromptStackView {
+    pub(crate) fn new(segments: Vec<PromptSegment>, app_event_tx: AppEventSender) -> Self {
+        Self {
+            segments,
+            selected: 0,
+            complete: false,
+            app_event_tx,
+        }
+    }
+
+    fn enabled_tokens(&self) -> u64 {
+        self.segments
+            .iter()
+            .filter(|segment| segment.enabled)
+            .map(|segment| segment.tokens)
+            .sum()
+    }
+
+    fn toggle_selected(&mut self) {
+        if let Some(segment) = self.segments.get_mut(self.selected)
+            && segment.optional
+        {
+            segment.enabled = !segment.enabled;
+        }
+    }
+
+    fn apply(&mut self) {
+        let segments = self
+            .segments
+            .iter()
+            .filter(|segment| !segment.enabled)
+            .map(|segment| segment.id.clone())
+            .collect();
+        self.app_event_tx
+            .send(AppEvent::codex_op(Op::SetDisabledPromptSegments { segments }));
+        self.complete = true;
+    }
+
+    fn list_rows(&self) -> u16 {
+        self.segments.len().clamp(1, MAX_POPUP_ROWS) as u16
+    }
+
+    fn segment_line(&self, idx: usize, segment: &PromptSegment) -> Line<'static> {
+        let marker = match (segment.optional, segment.enabled) {
+            (false, _) => "[=]",
+            (true, true) => "[x]",
+            (true, false) => "[ ]",
+        };
+        let tokens = format!("{} tok", format_with_separators_u64(segment.tokens));
+        let mut style = if segment.enabled {
+            crate::colors::style_text()
+        } else {
+            crate::colors::style_text_dim().add_modifier(Modifier::CROSSED_OUT)
+        };
+        if idx == self.selected {
+            style = style.patch(crate::colors::style_on_selection().add_modifier(Modifier::BOLD));
+        }
+        Line::from(vec![
+            Span::styled(format!("{marker} {:<28}", segment.label), style),
+            Span::styled(format!("{:<10}", segment.role), crate::colors::style_text_dim()),
+            Span::styled(format!("{tokens:>10}"), style),
+        ])
+    }
+}
+
+impl BottomPaneView<'_> for PromptStackView {
+    fn handle_key_event(&mut self, _pane: &mut BottomPane<'_>, key_event: KeyEvent) {
+        match key_event {
+            KeyEvent { code: KeyCode::Up, .. }
+            | KeyEvent { code: KeyCode::Char('k'), modifiers: KeyModifiers::NONE, .. } => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            KeyEvent { code: KeyCode::Down, .. }
+            | KeyEvent { code: KeyCode::Char('j'), modifiers: KeyModifiers::NONE, .. } => {
+                self.selected = (self.selected + 1).min(self.segments.len().saturating_sub(1));
+            }
+            KeyEvent { code: KeyCode::Char(' '), .. } => self.toggle_selected(),
+            KeyEvent { code: KeyCode::Enter, .. } => self.apply(),
+            KeyEvent { code: KeyCode::Esc, .. } => self.complete = true,
+            _ => {}
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    fn on_ctrl_c(&mut self, _pane: &mut BottomPane<'_>) -> CancellationEvent {
+        self.complete = true;
+        CancellationEvent::Handled
+    }
+
+    fn update_status_text(&mut self, _text: &str) -> ConditionalUpdate {
+        ConditionalUpdate::NoRedraw
+    }
+
+    fn desired_height(&self, _width: u16) -> u16 {
+        // Borders (+2), segment rows, spacer (+1), preview, spacer (+1), footer (+1)
+        2 + self.list_rows() + 1 + PREVIEW_ROWS + 1 + 1
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let total = self.segments.iter().map(|segment| segment.tokens).sum::<u64>();
+        let title = format!(
+            "Prompt stack · {} of {} tokens",
+            format_with_separators_u64(self.enabled_tokens()),
+            format_with_separators_u64(total)
+        );
+        let Some(inner) = render_popup_frame(area, buf, &title) else {
+            return;
+        };
+        let content = Rect {
+            x: inner.x.saturating_add(1),
+            width: inner.width.saturating_sub(2),
+            ..inner
+        };
+
+        let rows = usize::from(self.list_rows());
+        let top = self.selected.saturating_sub(rows.saturating_sub(1));
+        let lines: Vec<Line<'static>> = self
+            .segments
+            .iter()
+            .enumerate()
+            .skip(top)
+            .take(rows)
+            .map(|(idx, segment)| self.segment_line(idx, segment))
+            .collect();
+        let list_area = Rect {
+            height: self.list_rows().min(content.height),
+            ..content
+        };
+        Paragraph::new(lines).render(list_area, buf);
+
+        let preview_area = Rect {
+            y: list_area.y.saturating_add(list_area.height + 1),
+            height: PREVIEW_ROWS.min(
+                content
+                    .height
+                    .saturating_sub(list_area.height + 3),
+            ),
+            ..content
+        };
+        if let Some(segment) = self.segments.get(self.selected) {
+            Paragraph::new(segment.text.as_str())
+                .style(crate::colors::style_text_dim())
+                .wrap(Wrap { trim: false })
+                .render(preview_area, buf);
+        }
+
+        let footer = Rect {
+            y: inner.y.saturating_add(inner.height.saturating_sub(1)),
+            height: 1,
+            ..content
+        };
+        let footer_line = hints::shortcut_line(&[
+            hints::hint_nav(" navigate"),
+            KeyHint::new(hints::key_space(), " toggle for next turn"),
+            hints::hint_enter(" apply"),
+            hints::hint_esc(" cancel"),
+        ]);
+        Paragraph::new(footer_line)
+            .style(crate::colors::style_text_on_bg())
+            .render(footer, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    fn segment(id: &str, optional: bool) -> PromptSegment {
+        PromptSegment {
+            id: id.to_owned(),
+            label: id.to_owned(),
+            role: "developer".to_owned(),
+            text: "text".to_owned(),
+            tokens: 10,
+            optional,
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn enter_sends_disabled_optional_segments() {
+        let (tx, rx) = mpsc::channel::<AppEvent>();
+        let mut view = PromptStackView::new(
+            vec![
+                segment("instructions", false),
+                segment("developer_prompt", true),
+                segment("user_instructions", true),
+            ],
+            AppEventSender::new(tx),
+        );
+
+        // The base instructions cannot be switched off.
+        view.toggle_selected();
+        view.selected = 2;
+        view.toggle_selected();
+        assert_eq!(view.enabled_tokens(), 20);
+        view.apply();
+
+        assert!(view.complete);
+        let ops: Vec<Op> = rx
+            .try_iter()
+            .filter_map(|event| match event {
+                AppEvent::CodexOp(op) => Some(*op),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            ops,
+            vec![Op::SetDisabledPromptSegments {
+                segments: vec!["user_instructions".to_owned()],
+            }]
+        );
+    }
+}
@@ -0,0 +1,280 @@
+//! Findings of the last review with checkboxes, for fixing them one at a
+//! time instead of through auto-resolve. The highlighted finding can be
+//! expanded to preview the code it points at.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Modifier;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Paragraph, Widget};
+
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+use crate::bottom_pane::popup_consts::MAX_POPUP_ROWS;
+use crate::bottom_pane::settings_ui::hints::{self, KeyHint};
+use crate::bottom_pane::{BottomPane, BottomPaneView, CancellationEvent, ConditionalUpdate};
+use crate::components::popup_frame::render_popup_frame;
+
+const PREVIEW_ROWS: u16 = 9;
+
+pub(crate) struct ReviewFindingRow {
+    pub title: String,
+    /// `path:line` relative to the working directory when possible.
+    pub location: String,
+    pub priority: i32,
+    /// The referenced lines with a little context.
+    pub preview: Vec<Line<'static>>,
+}
+
+pub(crate) struct ReviewFindingsView {
+    rows: Vec<ReviewFindingRow>,
+    checked: Vec<bool>,
+    selected: usize,
+    expanded: bool,
+    complete: bool,
+    app_event_tx: AppEventSender,
+}
+
+impl ReviewFindingsView {
+    pub(crate) fn new(rows: Vec<ReviewFindingRow>, app_event_tx: AppEventSender) -> Self {
+        let checked = vec![false; rows.len()];
+        Self {
+            rows,
+            checked,
+            selected: 0,
+            expanded: false,
+            complete: false,
+            app_event_tx,
+        }
+    }
+
+    fn toggle_selected(&mut self) {
+        if let Some(flag) = self.checked.get_mut(self.selected) {
+            *flag = !*flag;
+        }
+    }
+
+    /// Indices to fix: the checked findings, or the highlighted one when none
+    /// are checked.
+    fn indices_to_fix(&self) -> Vec<usize> {
+        let checked: Vec<usize> = self
+            .checked
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, flag)| flag.then_some(idx))
+            .collect();
+        if checked.is_empty() && self.selected < self.rows.len() {
+            vec![self.selected]
+        } else {
+            checked
+        }
+    }
+
+    fn fix(&mut self) {
+        let indices = self.indices_to_fix();
+        if indices.is_empty() {
+            return;
+        }
+        self.app_event_tx.send(AppEvent::FixReviewFindings { indices });
+        self.complete = true;
+    }
+
+    fn open_viewer(&mut self) {
+        if self.selected < self.rows.len() {
+            self.app_event_tx
+                .send(AppEvent::OpenReviewFinding { index: self.selected });
+            self.complete = true;
+        }
+    }
+
+    fn list_rows(&self) -> u16 {
+        self.rows.len().clamp(1, MAX_POPUP_ROWS) as u16
+    }
+
+    fn preview_rows(&self) -> u16 {
+        if self.expanded { PREVIEW_ROWS } else { 0 }
+    }
+
+    fn row_line(&self, idx: usize, row: &ReviewFindingRow) -> Line<'static> {
+        let marker = if self.checked.get(idx).copied().unwrap_or(false) {
+            "[x]"
+        } else {
+            "[ ]"
+        };
+        let mut style = crate::colors::style_text();
+        let mut dim = crate::colors::style_text_dim();
+        let mut priority_style = if row.priority <= 1 {
+            crate::colors::style_warning()
+        } else {
+            crate::colors::style_text_dim()
+        };
+        if idx == self.selected {
+            let highlight = crate::colors::style_on_selection().add_modifier(Modifier::BOLD);
+            style = style.patch(highlight);
+            dim = dim.patch(highlight);
+            priority_style = priority_style.patch(highlight);
+        }
+        Line::from(vec![
+            Span::styled(format!("{marker} "), style),
+            Span::styled(format!("P{:<2}", row.priority), priority_style),
+            Span::styled(format!("{} ", row.location), dim),
+            Span::styled(row.title.clone(), style),
+        ])
+    }
+}
+
+impl BottomPaneView<'_> for ReviewFindingsView {
+    fn handle_key_event(&mut self, _pane: &mut BottomPane<'_>, key_event: KeyEvent) {
+        match key_event {
+            KeyEvent { code: KeyCode::Up, .. }
+            | KeyEvent { code: KeyCode::Char('k'), modifiers: KeyModifiers::NONE, .. } => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            KeyEvent { code: KeyCode::Down, .. }
+            | KeyEvent { code: KeyCode::Char('j'), modifiers: KeyModifiers::NONE, .. } => {
+                self.selected = (self.selected + 1).min(self.rows.len().saturating_sub(1));
+            }
+            KeyEvent { code: KeyCode::Right | KeyCode::Tab, .. } => self.expanded = !self.expanded,
+            KeyEvent { code: KeyCode::Left, .. } => self.expanded = false,
+            KeyEvent { code: KeyCode::Char(' '), .. } => self.toggle_selected(),
+            KeyEvent { code: KeyCode::Char('o'), modifiers: KeyModifiers::NONE, .. } => {
+                self.open_viewer();
+            }
+            KeyEvent { code: KeyCode::Enter, .. } => self.fix(),
+            KeyEvent { code: KeyCode::Esc, .. } => self.complete = true,
+            _ => {}
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    fn on_ctrl_c(&mut self, _pane: &mut BottomPane<'_>) -> CancellationEvent {
+        self.complete = true;
+        CancellationEvent::Handled
+    }
+
+    fn update_status_text(&mut self, _text: &str) -> ConditionalUpdate {
+        ConditionalUpdate::NoRedraw
+    }
+
+    fn desired_height(&self, _width: u16) -> u16 {
+        // Borders (+2), finding rows, optional spacer + preview, spacer (+1), footer (+1)
+        let preview = match self.preview_rows() {
+            0 => 0,
+            rows => 1 + rows,
+        };
+        2 + self.list_rows() + preview + 1 + 1
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let chosen = self.checked.iter().filter(|flag| **flag).count();
+        let title = if chosen == 0 {
+            format!("Review findings · {}", self.rows.len())
+        } else {
+            format!("Review findings · {chosen} of {} selected", self.rows.len())
+        };
+        let Some(inner) = render_popup_frame(area, buf, &title) else {
+            return;
+        };
+        let content = Rect {
+            x: inner.x.saturating_add(1),
+            width: inner.width.saturating_sub(2),
+            ..inner
+        };
+
+        let rows = usize::from(self.list_rows());
+        let top = self.selected.saturating_sub(rows.saturating_sub(1));
+        let lines: Vec<Line<'static>> = self
+            .rows
+            .iter()
+            .enumerate()
+            .skip(top)
+            .take(rows)
+            .map(|(idx, row)| self.row_line(idx, row))
+            .collect();
+        let list_area = Rect {
+            height: self.list_rows().min(content.height),
+            ..content
+        };
+        Paragraph::new(lines).render(list_area, buf);
+
+        if self.expanded
+            && let Some(row) = self.rows.get(self.selected)
+        {
+            let preview_area = Rect {
+                y: list_area.y.saturating_add(list_area.height + 1),
+                height: PREVIEW_ROWS.min(content.height.saturating_sub(list_area.height + 3)),
+                ..content
+            };
+            Paragraph::new(row.preview.clone()).render(preview_area, buf);
+        }
+
+        let footer = Rect {
+            y: inner.y.saturating_add(inner.height.saturating_sub(1)),
+            height: 1,
+            ..content
+        };
+        let expand_hint = if self.expanded { " hide code" } else { " show code" };
+        let footer_line = hints::shortcut_line(&[
+            hints::hint_nav(" navigate"),
+            KeyHint::new(hints::key_space(), " select"),
+            KeyHint::new(crate::icons::arrow_right(), expand_hint),
+            KeyHint::new("o", " open"),
+            hints::hint_enter(" fix"),
+            hints::hint_esc(" dismiss"),
+        ]);
+        Paragraph::new(footer_line)
+            .style(crate::colors::style_text_on_bg())
+            .render(footer, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    fn row(title: &str) -> ReviewFindingRow {
+        ReviewFindingRow {
+            title: title.to_owned(),
+            location: "src/lib.rs:1".to_owned(),
+            priority: 1,
+            preview: Vec::new(),
+        }
+    }
+
+    fn fixed_indices(rx: &mpsc::Receiver<AppEvent>) -> Vec<Vec<usize>> {
+        rx.try_iter()
+            .filter_map(|event| match event {
+                AppEvent::FixReviewFindings { indices } => Some(indices),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn enter_fixes_checked_findings_or_the_highlighted_one() {
+        let (tx, rx) = mpsc::channel::<AppEvent>();
+        let mut view = ReviewFindingsView::new(
+            vec![row("first"), row("second"), row("third")],
+            AppEventSender::new(tx.clone()),
+        );
+        view.selected = 1;
+        view.fix();
+        assert!(view.complete);
+        assert_eq!(fixed_indices(&rx), vec![vec![1]]);
+
+        let mut view = ReviewFindingsView::new(
+            vec![row("first"), row("second"), row("third")],
+            AppEventSender::new(tx),
+        );
+        view.toggle_selected();
+        view.selected = 2;
+        view.toggle_selected();
+        view.fix();
+        assert_eq!(fixed_indices(&rx), vec![vec![0, 2]]);
+    }
+}
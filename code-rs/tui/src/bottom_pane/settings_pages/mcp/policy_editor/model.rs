@@ -243,6 +243,10 @@ impl ServerSchedulingEditor {
             min_interval_sec,
             queue_timeout_sec,
             max_queue_depth,
+            // Not editable here; keep whatever the config file set.
+            max_calls_per_minute: self.scheduling.max_calls_per_minute,
+            circuit_breaker_failures: self.scheduling.circuit_breaker_failures,
+            circuit_breaker_cooldown_sec: self.scheduling.circuit_breaker_cooldown_sec,
         })
     }
 
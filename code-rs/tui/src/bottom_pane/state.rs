@@ -316,6 +316,12 @@ impl<'a> BottomPane<'a> {
         }
     }
 
+    /// Number of inputs waiting for the running turn's next model request.
+    pub(crate) fn set_queued_input_count(&mut self, count: usize) {
+        self.composer.set_queued_input_count(count);
+        self.request_redraw();
+    }
+
     pub(crate) fn set_access_mode_label(&mut self, label: Option<String>) {
         self.composer.set_access_mode_label(label);
         // Hide the "(Shift+Tab change)" suffix after a short time for persistent modes.
@@ -10,8 +10,10 @@ use super::panes::app_link_view::AppLinkView;
 use super::panes::auto_coordinator::{AutoCoordinatorView, AutoCoordinatorViewModel};
 use super::panes::cloud_tasks::CloudTasksView;
 use super::panes::custom_prompt::CustomPromptView;
+use super::panes::prompt_stack::PromptStackView;
 use super::panes::request_user_input::RequestUserInputView;
 use super::panes::resume_selection::{ResumeRow, ResumeSelectionView};
+use super::panes::review_findings::ReviewFindingsView;
 use super::panes::undo_timeline::UndoTimelineView;
 use super::settings_pages;
 use super::{ActiveViewKind, BottomPane, BottomPaneParams, BottomPaneView, ChatComposer};
@@ -366,6 +368,21 @@ impl<'a> BottomPane<'a> {
         self.set_other_view(view, true);
     }
 
+    /// Show the findings of the last review for picking ones to fix.
+    pub(crate) fn show_review_findings(
+        &mut self,
+        rows: Vec<crate::bottom_pane::panes::review_findings::ReviewFindingRow>,
+    ) {
+        let view = ReviewFindingsView::new(rows, self.app_event_tx.clone());
+        self.set_other_view(view, true);
+    }
+
+    /// Show the `/prompt` stack preview for the next turn.
+    pub(crate) fn show_prompt_stack(&mut self, segments: Vec<code_core::protocol::PromptSegment>) {
+        let view = PromptStackView::new(segments, self.app_event_tx.clone());
+        self.set_other_view(view, true);
+    }
+
     /// Show MCP servers status/toggle UI
     pub fn show_mcp_settings(
         &mut self,
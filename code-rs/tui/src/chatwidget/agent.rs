@@ -78,8 +78,10 @@ pub(crate) fn spawn_new_conversation_runtime(
             }
         });
 
-        while let Ok(event) = conversation.next_event().await {
-            app_event_tx_clone.send(AppEvent::codex_event(event));
+        while let Ok(events) = conversation.next_event_batch().await {
+            for event in events {
+                app_event_tx_clone.send(AppEvent::codex_event(event));
+            }
         }
     });
 
@@ -113,8 +115,10 @@ pub(crate) fn spawn_existing_conversation_runtime(
             }
         });
 
-        while let Ok(event) = conversation.next_event().await {
-            app_event_tx_clone.send(AppEvent::codex_event(event));
+        while let Ok(events) = conversation.next_event_batch().await {
+            for event in events {
+                app_event_tx_clone.send(AppEvent::codex_event(event));
+            }
         }
     });
 }
@@ -0,0 +1,76 @@
+//! `/approvals`: list, add, or revoke the command approvals saved in the
+//! project's `.code/approvals.toml`. Changes go through the session so the
+//! running conversation picks them up immediately.
+
+use super::*;
+use code_core::approvals_store;
+use code_core::slash_commands::ApprovalsCommand;
+
+impl ChatWidget<'_> {
+    pub(crate) fn handle_approvals_command(&mut self, args: &str) {
+        let command = match code_core::slash_commands::parse_approvals_command(args) {
+            Ok(command) => command,
+            Err(message) => {
+                self.history_push_plain_state(history_cell::new_error_event(message));
+                return;
+            }
+        };
+        match command {
+            ApprovalsCommand::List => {
+                let Some(approvals) = self.load_stored_approvals() else {
+                    return;
+                };
+                let path = approvals_store::approvals_path(&self.config.cwd);
+                let lines = if approvals.is_empty() {
+                    vec![format!("No command approvals saved in {}.", path.display())]
+                } else {
+                    std::iter::once(format!("Command approvals saved in {}:", path.display()))
+                        .chain(approvals.iter().enumerate().map(|(idx, approval)| {
+                            format!("  {}. {}", idx + 1, approval.describe())
+                        }))
+                        .chain(std::iter::once(
+                            "Revoke one with /approvals revoke <N>.".to_owned(),
+                        ))
+                        .collect()
+                };
+                self.history_push_plain_paragraphs(
+                    crate::history::state::PlainMessageKind::Notice,
+                    lines,
+                );
+            }
+            ApprovalsCommand::Add {
+                command,
+                match_kind,
+            } => {
+                self.register_approved_command(command, match_kind, None, true);
+            }
+            ApprovalsCommand::Revoke { index } => {
+                let Some(approvals) = self.load_stored_approvals() else {
+                    return;
+                };
+                let Some(approval) = approvals.into_iter().nth(index - 1) else {
+                    self.history_push_plain_state(history_cell::new_error_event(format!(
+                        "Error: no saved approval #{index}. Run /approvals to list them."
+                    )));
+                    return;
+                };
+                self.submit_op(Op::RevokeApprovedCommand {
+                    command: approval.argv,
+                    match_kind: approval.match_kind,
+                });
+            }
+        }
+    }
+
+    fn load_stored_approvals(&mut self) -> Option<Vec<approvals_store::StoredApproval>> {
+        match approvals_store::load_approvals(&self.config.cwd) {
+            Ok(approvals) => Some(approvals),
+            Err(err) => {
+                self.history_push_plain_state(history_cell::new_error_event(format!(
+                    "Failed to read saved approvals: {err}"
+                )));
+                None
+            }
+        }
+    }
+}
@@ -46,6 +46,9 @@ impl ChatWidget<'_> {
     }
 
     pub(super) fn auto_reduced_motion_preference() -> bool {
+        if crate::tui_env::low_bandwidth() {
+            return true;
+        }
         match std::env::var("CODE_TUI_REDUCED_MOTION") {
             Ok(value) => {
                 let normalized = value.trim().to_ascii_lowercase();
@@ -229,6 +232,9 @@ impl ChatWidget<'_> {
                             show_notice,
                         });
                     }
+                    AutoCoordinatorEvent::Handoff { note } => {
+                        app_event_tx.send(AppEvent::AutoCoordinatorHandoff { note });
+                    }
                     AutoCoordinatorEvent::StopAck => {
                         app_event_tx.send(AppEvent::AutoCoordinatorStopAck);
                     }
@@ -276,6 +282,20 @@ impl ChatWidget<'_> {
         }
     }
 
+    /// `/migrate <dependency> <version>`: runs a phased dependency upgrade
+    /// through Auto Drive.
+    pub(crate) fn handle_migrate_command(&mut self, args: &str) {
+        match code_core::slash_commands::parse_migrate_args(args) {
+            Ok((dependency, version)) => {
+                let goal = code_core::slash_commands::format_migrate_goal(&dependency, &version);
+                self.handle_auto_command(Some(goal));
+            }
+            Err(message) => {
+                self.history_push_plain_state(history_cell::new_error_event(message));
+            }
+        }
+    }
+
     pub(crate) fn handle_auto_command(&mut self, goal: Option<String>) {
         let provided = goal.unwrap_or_default();
         let trimmed = provided.trim();
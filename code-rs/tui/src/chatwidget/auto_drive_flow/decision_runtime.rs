@@ -594,4 +594,13 @@ Have we met every part of this goal and is there no further work to do?"
         self.auto_card_add_action(message, AutoDriveActionKind::Info);
     }
 
+    pub(crate) fn auto_handle_handoff(&mut self, note: String) {
+        if let Some(item) = Self::auto_drive_make_assistant_message(note.clone()) {
+            self.auto_history.append_raw(std::slice::from_ref(&item));
+        }
+        let lines = vec!["AUTO DRIVE HANDOFF".to_owned(), note];
+        self.history_push_plain_paragraphs(PlainMessageKind::Notice, lines);
+        self.request_redraw();
+    }
+
 }
@@ -449,6 +449,7 @@ impl ChatWidget<'_> {
             self.auto_resolve_state = None;
         }
         let hint_opt = (!hint.trim().is_empty()).then(|| hint.clone());
+        self.review_profile_override = None;
         self.begin_review(target, prompt, hint_opt, Some(preparation));
     }
 
@@ -62,30 +62,38 @@ pub(super) async fn run_background_review_inner(
 
         let snapshot_id = snapshot.id().to_owned();
         bump_snapshot_epoch_for(&config.cwd);
+        let checkout_options =
+            auto_review_checkout_options(&config.review.worktree, &git_root, &snapshot_id);
 
         // Attempt to hold the shared review lock; if busy or a previous review
         // with findings is still surfaced, fall back to a per-request
         // auto-review worktree to avoid clobbering pending fixes.
         let (worktree_path, branch, worktree_guard) = if prefer_fallback {
             let (path, name, guard) =
-                allocate_fallback_auto_review_worktree(&git_root, &snapshot_id).await?;
+                allocate_fallback_auto_review_worktree(&git_root, &snapshot_id, checkout_options)
+                    .await?;
             (path, name, guard)
         } else {
             match try_acquire_lock("review", &config.cwd) {
                 Ok(Some(g)) => {
-                    let path = code_core::git_worktree::prepare_reusable_worktree(
-                        &git_root,
-                        AUTO_REVIEW_SHARED_WORKTREE,
-                        snapshot_id.as_str(),
-                        true,
-                    )
+                    let path = code_core::git_worktree::branches_dir(&git_root)
+                        .join(AUTO_REVIEW_SHARED_WORKTREE);
+                    task::spawn_blocking({
+                        let repo = git_root.clone();
+                        let path = path.clone();
+                        let base = snapshot_id.clone();
+                        move || code_git_tooling::checkout_worktree(&repo, &path, &base, &checkout_options)
+                    })
                     .await
+                    .map_err(|e| format!("failed to spawn worktree task: {e}"))?
                     .map_err(|e| format!("failed to prepare worktree: {e}"))?;
+                    bump_snapshot_epoch_for(&path);
                     (path, AUTO_REVIEW_SHARED_WORKTREE.to_owned(), g)
                 }
                 Ok(None) => {
                     let (path, name, guard) =
-                        allocate_fallback_auto_review_worktree(&git_root, &snapshot_id).await?;
+                        allocate_fallback_auto_review_worktree(&git_root, &snapshot_id, checkout_options)
+                            .await?;
                     (path, name, guard)
                 }
                 Err(err) => {
@@ -127,6 +135,41 @@ pub(super) async fn run_background_review_inner(
             "/review Analyze only changes made in commit {snapshot_id}. Identify critical bugs, regressions, security/performance/concurrency risks or incorrect assumptions. Provide actionable feedback and references to the changed code; ignore minor style or formatting nits."
         );
 
+        // A bare gitlink bump hides the real change; spell out what moved.
+        if let Some(parent) = snapshot.parent() {
+            let submodules = code_git_tooling::submodule_changes(&git_root, parent, &snapshot_id)
+                .unwrap_or_default();
+            if !submodules.is_empty() {
+                review_prompt.push_str(
+                    "\n\nSubmodule updates in this commit (review the contained changes, not just the pointer):",
+                );
+                for change in &submodules {
+                    review_prompt.push('\n');
+                    review_prompt.push_str(&change.render());
+                }
+            }
+
+            let large_files = &config.large_files;
+            let notices = code_git_tooling::large_file_changes(
+                &git_root,
+                parent,
+                &snapshot_id,
+                large_files.threshold_bytes(),
+            )
+            .unwrap_or_default();
+            if !notices.is_empty() {
+                review_prompt.push_str(if large_files.include_in_diffs {
+                    "\n\nLarge files in this commit (Git LFS, binary, or large; diff selectively):"
+                } else {
+                    "\n\nLarge files in this commit (contents excluded; review from this metadata and do not diff or print them):"
+                });
+                for notice in &notices {
+                    review_prompt.push_str("\n- ");
+                    review_prompt.push_str(&notice.render());
+                }
+            }
+        }
+
         if let Some(context) = turn_context {
             review_prompt.push_str("\n\n");
             review_prompt.push_str(&context);
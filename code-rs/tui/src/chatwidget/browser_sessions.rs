@@ -582,6 +582,17 @@ fn summarize_action_label(tool_name: &str) -> String {
         "browser_move" => "Move".to_owned(),
         "browser_history" => "History".to_owned(),
         "browser_console" => "Console".to_owned(),
+        "browser_har" => "HAR".to_owned(),
+        "browser_downloads" => "Downloads".to_owned(),
+        "browser_dom" => "Outline".to_owned(),
+        "browser_mock" => "Mock".to_owned(),
+        "browser_mock_list" => "Mocks".to_owned(),
+        "browser_mock_remove" => "Unmock".to_owned(),
+        "browser_mock_clear" => "Clear mocks".to_owned(),
+        "browser_tab_list" => "Tabs".to_owned(),
+        "browser_tab_open" => "New tab".to_owned(),
+        "browser_tab_switch" => "Switch tab".to_owned(),
+        "browser_tab_close" => "Close tab".to_owned(),
         "browser_javascript" => "Script".to_owned(),
         "browser_cdp" => "CDP".to_owned(),
         "browser_status" => "Status".to_owned(),
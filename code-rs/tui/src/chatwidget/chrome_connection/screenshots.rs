@@ -6,6 +6,7 @@ use crate::app_event_sender::AppEventSender;
 
 use super::args::parse_port_from_ws;
 use super::super::BackgroundOrderTicket;
+use super::super::ChatWidget;
 
 pub(super) fn set_latest_screenshot(
     latest_screenshot: &Arc<Mutex<Option<(PathBuf, String)>>>,
@@ -170,6 +171,9 @@ pub(super) async fn finish_cdp_connection(
         let _ = super::super::write_cached_connection(p, ws).await;
     });
 
+    ChatWidget::install_browser_download_notice(&browser_manager, app_event_tx.clone(), ticket.clone())
+        .await;
+
     install_cdp_navigation_callback(
         browser_manager.clone(),
         latest_screenshot.clone(),
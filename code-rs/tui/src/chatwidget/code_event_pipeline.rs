@@ -81,6 +81,8 @@ impl ChatWidget<'_> {
         // Strict ordering: all LLM/tool events must carry OrderMeta; internal events use synthetic keys.
         // Track provider order to anchor internal inserts at the bottom of the active request.
         self.note_order(event.order.as_ref());
+        // Tool results feed the source list shown under the final answer.
+        self.turn_sources.observe_event(&event.msg);
 
         let Event { id, msg, .. } = event.clone();
         match msg {
@@ -99,8 +101,11 @@ impl ChatWidget<'_> {
             EventMsg::SessionConfigured(event) => {
                 // Record session id for potential future fork/backtrack features
                 self.session_id = Some(event.session_id);
+                crate::resume::crash_recovery::set_session_id(event.session_id);
+                code_git_tooling::set_ghost_session(Some(&event.session_id.to_string()));
                 self.bottom_pane
                     .set_history_metadata(event.history_log_id, event.history_entry_count);
+                self.bottom_pane.set_queued_input_count(0);
                 // Record session information at the top of the conversation.
                 // Only surface a notice when the model differs from what we requested.
                 if self.config.model != event.model {
@@ -109,6 +114,9 @@ impl ChatWidget<'_> {
                     let _ = self
                         .history_insert_plain_state_with_key(session_state, key, "prelude");
                 }
+                if self.config.experimental_resume.is_some() {
+                    self.pin_session_handoff(event.session_id);
+                }
 
                 if let Some(user_message) = self.initial_user_message.take() {
                     // If the user provided an initial message, add it to the
@@ -134,6 +142,8 @@ impl ChatWidget<'_> {
                     self.submit_op(Op::ListMcpTools);
                 }
 
+                self.maybe_prompt_project_trust();
+
                 if self.resume_placeholder_visible && event.history_entry_count == 0 {
                     self.replace_resume_placeholder_with_notice(RESUME_NO_HISTORY_NOTICE);
                 }
@@ -338,13 +348,20 @@ impl ChatWidget<'_> {
                 self.bottom_pane.set_auto_context_phase(event.phase);
                 self.request_redraw();
             }
-            EventMsg::Error(ErrorEvent { message }) => {
+            EventMsg::Heartbeat(event) => {
+                self.handle_heartbeat_event(event);
+            }
+            EventMsg::Error(ErrorEvent { message, .. }) => {
                 self.on_error(message);
             }
             EventMsg::Warning(WarningEvent { message }) => {
                 self.history_push_plain_state(history_cell::new_warning_event(message));
                 self.request_redraw();
             }
+            EventMsg::PartialTurn(event) => {
+                self.history_push_plain_state(history_cell::new_partial_turn(&event));
+                self.request_redraw();
+            }
             EventMsg::HookStarted(event) => {
                 let run = event.run;
                 let event_label = match run.event_name {
@@ -477,6 +494,10 @@ impl ChatWidget<'_> {
             EventMsg::ExecCommandEnd(ev) => {
                 self.handle_exec_command_end_event(ev, event.order.clone(), event.event_seq);
             }
+            // The exec cell already shows the denied command's output.
+            EventMsg::SandboxDenied(_) => {}
+            // The TUI never enables request budget negotiation.
+            EventMsg::RequestBudgetCheck(_) => {}
             EventMsg::McpToolCallBegin(ev) => {
                 self.handle_mcp_tool_call_begin_event(ev, event.order.as_ref(), event.event_seq);
             }
@@ -562,6 +583,9 @@ impl ChatWidget<'_> {
                     });
                 }
             }
+            EventMsg::PromptPreview(ev) => {
+                self.bottom_pane.show_prompt_stack(ev.segments);
+            }
             EventMsg::ListSkillsResponse(ev) => {
                 let len = ev.skills.len();
                 debug!("received {len} skills");
@@ -579,6 +603,45 @@ impl ChatWidget<'_> {
             EventMsg::BackgroundEvent(BackgroundEventEvent { message }) => {
                 self.handle_background_event_event(id, message, event.order.as_ref());
             }
+            EventMsg::GitHeadChanged(ev) => {
+                *self.git_branch_cache.borrow_mut() = GitBranchCache::default();
+                self.reset_auto_review_baselines();
+                self.history_push_plain_state(history_cell::new_warning_event(ev.message));
+                self.request_redraw();
+            }
+            EventMsg::TurnReverted(ev) => {
+                if ev.success {
+                    let mut message = ev.message;
+                    for file in &ev.files {
+                        let change = match (file.deleted, file.added, file.removed) {
+                            (true, _, _) => "deleted".to_owned(),
+                            (false, Some(added), Some(removed)) => format!("+{added} -{removed} undone"),
+                            (false, _, _) => "binary".to_owned(),
+                        };
+                        message.push_str(&format!("\n  {} ({change})", file.path));
+                    }
+                    self.push_background_tail(message);
+                } else {
+                    self.history_push_plain_state(history_cell::new_error_event(ev.message));
+                }
+                self.request_redraw();
+            }
+            EventMsg::McpAuthPrompt(ev) => {
+                self.handle_mcp_auth_prompt_event(ev);
+            }
+            EventMsg::UserInputQueued(ev) => {
+                self.bottom_pane.set_queued_input_count(ev.pending);
+            }
+            EventMsg::QueuedUserInputDelivered(ev) => {
+                self.bottom_pane.set_queued_input_count(ev.pending);
+            }
+            EventMsg::UserInputInterrupting(ev) => {
+                self.bottom_pane.set_queued_input_count(ev.pending);
+                self.push_background_tail(
+                    "Interrupting the current turn to send your message.".to_owned(),
+                );
+                self.request_redraw();
+            }
             EventMsg::AgentStatusUpdate(event) => {
                 self.handle_agent_status_update_event(event);
             }
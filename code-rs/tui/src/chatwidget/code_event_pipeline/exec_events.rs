@@ -32,7 +32,9 @@ impl ChatWidget<'_> {
             self.ensure_spinner_for_activity("exec-output");
         }
         if let Some(running) = self.exec.running_commands.get_mut(&call_id) {
-            let chunk = String::from_utf8_lossy(&ev.chunk).into_owned();
+            // Decode straight into a shared buffer; history records and cells
+            // hold this allocation rather than copying it per delta.
+            let chunk: std::sync::Arc<str> = std::sync::Arc::from(String::from_utf8_lossy(&ev.chunk));
             let chunk_len = chunk.len();
             let (stdout_chunk, stderr_chunk) = match ev.stream {
                 ExecOutputStream::Stdout => {
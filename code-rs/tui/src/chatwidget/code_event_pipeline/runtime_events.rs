@@ -2,6 +2,9 @@ use super::*;
 use code_core::protocol::AgentStatusUpdateEvent;
 use code_core::protocol::BrowserScreenshotUpdateEvent;
 use code_core::protocol::ExitedReviewModeEvent;
+use code_core::protocol::HeartbeatEvent;
+use code_core::protocol::HeartbeatPhase;
+use code_core::protocol::McpAuthPromptEvent;
 use code_core::protocol::OrderMeta;
 use code_protocol::protocol::ReviewRequest;
 
@@ -270,7 +273,57 @@ impl ChatWidget<'_> {
         }
     }
 
+    /// Keeps the status line moving while a turn is quiet so a long tool call
+    /// or slow model response does not look hung.
+    pub(super) fn handle_heartbeat_event(&mut self, event: HeartbeatEvent) {
+        if !self.bottom_pane.is_task_running() {
+            return;
+        }
+        let elapsed = heartbeat_elapsed(event.elapsed_ms);
+        let status = match (event.phase, event.detail) {
+            (HeartbeatPhase::AwaitingUser, _) => "waiting for your approval".to_owned(),
+            (HeartbeatPhase::ToolCall, Some(detail)) => format!("running {detail} · {elapsed}"),
+            (HeartbeatPhase::ToolCall, None) => format!("running tool · {elapsed}"),
+            (HeartbeatPhase::Model, _) => format!("waiting for model · {elapsed}"),
+        };
+        self.bottom_pane.update_status_text(&status);
+    }
+
+    pub(super) fn handle_mcp_auth_prompt_event(&mut self, event: McpAuthPromptEvent) {
+        let McpAuthPromptEvent {
+            server_name,
+            verification_uri,
+            verification_uri_complete,
+            user_code,
+            expires_in_secs,
+        } = event;
+        let mut lines = vec![
+            format!("Open {verification_uri} and enter the code {user_code}."),
+        ];
+        if let Some(url) = verification_uri_complete {
+            lines.push(format!("Or open {url} to skip typing the code."));
+        }
+        lines.push(format!(
+            "The code expires in {} min; the server starts as soon as you approve.",
+            expires_in_secs.div_ceil(60)
+        ));
+        let kind = PlainMessageKind::Notice;
+        let mut state = history_cell::plain_message_state_from_paragraphs(
+            kind,
+            history_cell::plain_role_for_kind(kind),
+            lines,
+        );
+        state.header = Some(MessageHeader {
+            label: format!("Sign in to MCP server `{server_name}`"),
+            badge: None,
+        });
+        self.history_push_plain_state(state);
+        self.request_redraw();
+    }
+
     pub(super) fn handle_turn_aborted_event(&mut self) {
+        // An interrupted fix should not roll straight into the next one.
+        self.pending_review_finding_fixes.clear();
         self.pending_request_user_input = None;
         self.pending_mcp_elicitation = None;
         // TurnAborted is emitted when the agent task is aborted and does not
@@ -349,7 +402,9 @@ impl ChatWidget<'_> {
                 _ => "<< Code review finished >>".to_owned(),
             };
             self.push_background_tail(finish_banner);
+            self.remember_review_findings(&output.findings);
         } else {
+            self.review_findings.clear();
             let banner = match hint.as_deref() {
                 Some(h) if !h.trim().is_empty() => {
                     let trimmed = h.trim();
@@ -373,3 +428,12 @@ impl ChatWidget<'_> {
         }
     }
 }
+
+fn heartbeat_elapsed(elapsed_ms: u64) -> String {
+    let secs = elapsed_ms / 1000;
+    if secs < 60 {
+        format!("{secs}s")
+    } else {
+        format!("{}m {:02}s", secs / 60, secs % 60)
+    }
+}
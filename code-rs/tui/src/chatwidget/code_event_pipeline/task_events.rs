@@ -144,6 +144,7 @@ impl ChatWidget<'_> {
         // Final re-check for idle state.
         self.maybe_hide_spinner();
         self.maybe_trigger_auto_review();
+        self.dispatch_next_review_finding_fix();
         self.emit_turn_complete_notification(last_agent_message);
         self.suppress_next_agent_hint = false;
         self.mark_needs_redraw();
@@ -0,0 +1,100 @@
+use super::*;
+
+use std::fmt::Write as _;
+
+use crate::resume::crash_recovery::SessionMarker;
+
+impl ChatWidget<'_> {
+    /// Looks up the rollout of a session that ended without a clean shutdown
+    /// and asks the user whether to resume it, if it has anything to resume.
+    pub(crate) fn maybe_offer_crash_recovery(&mut self, marker: SessionMarker) {
+        let Some(session_id) = marker.session_id else {
+            return;
+        };
+        let code_home = self.config.code_home.clone();
+        let tx = self.app_event_tx.clone();
+        tokio::spawn(async move {
+            let catalog = code_core::SessionCatalog::new(code_home);
+            let entry = match catalog.find_by_id(&session_id.to_string()).await {
+                Ok(Some(entry)) => entry,
+                Ok(None) => return,
+                Err(err) => {
+                    tracing::warn!("failed to look up crashed session {session_id}: {err}");
+                    return;
+                }
+            };
+            if entry.user_message_count == 0 && marker.queued_messages.is_empty() {
+                return;
+            }
+            let path = catalog.entry_rollout_path(&entry);
+            tx.send(AppEvent::ShowCrashRecoveryPrompt {
+                marker,
+                path,
+                snippet: entry.last_user_snippet,
+            });
+        });
+    }
+
+    pub(crate) fn show_crash_recovery_prompt(
+        &mut self,
+        marker: SessionMarker,
+        path: PathBuf,
+        snippet: Option<String>,
+    ) {
+        let mut subtitle =
+            "The last session in this directory did not shut down cleanly.".to_owned();
+        if let Some(snippet) = snippet.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+            let _ = write!(subtitle, "\nLast message: {snippet}");
+        }
+        if !marker.queued_messages.is_empty() {
+            let _ = write!(
+                subtitle,
+                "\n{} queued message(s) will be put back in the composer.",
+                marker.queued_messages.len()
+            );
+        }
+        if !marker.pending_approvals.is_empty() {
+            subtitle.push_str(
+                "\nThese approvals were pending and are not re-run; the agent asks again if it retries:",
+            );
+            for approval in &marker.pending_approvals {
+                let _ = write!(subtitle, "\n  {}", approval.summary);
+            }
+        }
+
+        let queued = marker.queued_messages.join("\n\n");
+        let items = vec![
+            SelectionItem {
+                name: "Resume that session".to_owned(),
+                description: Some("Reload its conversation from the last saved state.".to_owned()),
+                is_current: true,
+                actions: vec![Box::new(move |tx: &AppEventSender| {
+                    tx.send(AppEvent::ResumeFrom(path.clone()));
+                    if !queued.is_empty() {
+                        tx.send(AppEvent::PrefillComposer(queued.clone()));
+                    }
+                })],
+            },
+            SelectionItem {
+                name: "Start fresh".to_owned(),
+                description: Some(format!(
+                    "It stays available in the /resume picker and `{} resume`.",
+                    crate::resume_command_name()
+                )),
+                is_current: false,
+                actions: Vec::new(),
+            },
+        ];
+
+        let view = ListSelectionView::new(
+            " Recover interrupted session ".to_owned(),
+            Some(subtitle),
+            Some("Enter select - Esc cancel".to_owned()),
+            items,
+            self.app_event_tx.clone(),
+            6,
+        );
+        self.bottom_pane.show_list_selection(view);
+        self.request_redraw();
+    }
+}
@@ -29,14 +29,18 @@ pub(super) fn handle_diff_key(chat: &mut ChatWidget<'_>, key_event: KeyEvent) ->
             scroll_selected_diff_tab(chat, true);
             true
         }
-        KeyCode::Char('u') => {
+        KeyCode::Char('u') if !is_review_findings(chat) => {
             prompt_undo_for_selected_diff_block(chat);
             true
         }
-        KeyCode::Char('e') => {
+        KeyCode::Char('e') if !is_review_findings(chat) => {
             prompt_explain_selected_diff_block(chat);
             true
         }
+        KeyCode::Char('f') if is_review_findings(chat) => {
+            prompt_fix_for_selected_finding(chat);
+            true
+        }
         KeyCode::Esc => {
             chat.diffs.overlay = None;
             chat.diffs.confirm = None;
@@ -51,6 +55,10 @@ fn handle_diff_confirm_key(chat: &mut ChatWidget<'_>, code: KeyCode) -> bool {
     // If a confirmation banner is active, only Enter applies to it.
     if let Some(confirm) = chat.diffs.confirm.take() {
         if matches!(code, KeyCode::Enter) {
+            if is_review_findings(chat) {
+                // The fix runs in the main conversation; get out of its way.
+                chat.diffs.overlay = None;
+            }
             chat.submit_user_message(confirm.text_to_submit.into());
             chat.request_redraw();
             return true;
@@ -61,6 +69,10 @@ fn handle_diff_confirm_key(chat: &mut ChatWidget<'_>, code: KeyCode) -> bool {
     false
 }
 
+fn is_review_findings(chat: &ChatWidget<'_>) -> bool {
+    chat.diffs.overlay.as_ref().is_some_and(super::diff_ui::DiffOverlay::is_review_findings)
+}
+
 fn move_selected_diff_tab(chat: &mut ChatWidget<'_>, right: bool) {
     let Some(overlay) = chat.diffs.overlay.as_mut() else {
         return;
@@ -109,6 +121,20 @@ fn prompt_undo_for_selected_diff_block(chat: &mut ChatWidget<'_>) {
     chat.request_redraw();
 }
 
+fn prompt_fix_for_selected_finding(chat: &mut ChatWidget<'_>) {
+    let Some(overlay) = chat.diffs.overlay.as_ref() else {
+        return;
+    };
+    let super::diff_ui::DiffOverlayKind::ReviewFindings { fix_prompts } = &overlay.kind else {
+        return;
+    };
+    let Some(prompt) = fix_prompts.get(overlay.selected).cloned() else {
+        return;
+    };
+    chat.diffs.confirm = Some(super::diff_ui::DiffConfirm { text_to_submit: prompt });
+    chat.request_redraw();
+}
+
 fn prompt_explain_selected_diff_block(chat: &mut ChatWidget<'_>) {
     let Some(diff_text) = selected_diff_block_text(chat) else {
         return;
@@ -8,12 +8,41 @@ pub(crate) struct DiffOverlay {
     pub(crate) tabs: Vec<(String, Vec<DiffBlock>)>,
     pub(crate) selected: usize,
     pub(crate) scroll_offsets: Vec<u16>,
+    pub(crate) kind: DiffOverlayKind,
+}
+
+/// What the overlay is showing. Review findings reuse the viewer with one tab
+/// per finding and swap the explain/undo actions for "fix this finding".
+pub(crate) enum DiffOverlayKind {
+    SessionDiff,
+    ReviewFindings {
+        /// Targeted fix prompt per tab.
+        fix_prompts: Vec<String>,
+    },
 }
 
 impl DiffOverlay {
     pub(crate) fn new(tabs: Vec<(String, Vec<DiffBlock>)>) -> Self {
         let n = tabs.len();
-        Self { tabs, selected: 0, scroll_offsets: vec![0; n] }
+        Self { tabs, selected: 0, scroll_offsets: vec![0; n], kind: DiffOverlayKind::SessionDiff }
+    }
+
+    pub(crate) fn review_findings(
+        tabs: Vec<(String, Vec<DiffBlock>)>,
+        fix_prompts: Vec<String>,
+        selected: usize,
+    ) -> Self {
+        let n = tabs.len();
+        Self {
+            tabs,
+            selected: selected.min(n.saturating_sub(1)),
+            scroll_offsets: vec![0; n],
+            kind: DiffOverlayKind::ReviewFindings { fix_prompts },
+        }
+    }
+
+    pub(crate) fn is_review_findings(&self) -> bool {
+        matches!(self.kind, DiffOverlayKind::ReviewFindings { .. })
     }
 }
 
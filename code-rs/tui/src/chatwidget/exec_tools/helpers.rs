@@ -57,7 +57,7 @@ pub(super) fn stream_chunks_tail_to_text(chunks: &[ExecStreamChunk], max_bytes:
             break;
         }
         let remaining = max_bytes - total_bytes;
-        let content = chunk.content.as_str();
+        let content: &str = &chunk.content;
         if content.len() <= remaining {
             pieces.push(content.to_owned());
             total_bytes = total_bytes.saturating_add(content.len());
@@ -294,6 +294,7 @@ pub(super) fn exec_record_from_begin(ev: &ExecCommandBeginEvent) -> ExecRecord {
         working_dir: Some(ev.cwd.clone()),
         env: Vec::new(),
         tags: Vec::new(),
+        output_folded: None,
     }
 }
 
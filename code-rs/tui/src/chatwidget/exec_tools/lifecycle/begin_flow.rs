@@ -283,6 +283,7 @@ pub(in super::super::super) fn handle_repl_begin_now(
         working_dir: Some(ev.cwd.clone()),
         env: Vec::new(),
         tags: Vec::new(),
+        output_folded: None,
     };
 
     let cell = history_cell::ReplCell::new_active(exec_record.clone(), ev.code, ev.runtime_kind, ev.runtime_version);
@@ -0,0 +1,233 @@
+//! History bookmarks. Alt+M bookmarks the bottommost visible history cell and
+//! Alt+J opens a jump list of the bookmarked cells. Bookmarks live in
+//! `HistoryState`, so they are part of the persisted snapshot and survive
+//! resume.
+
+use super::ChatWidget;
+use super::layout_scroll;
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Modifier;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Widget};
+
+// Returns true if the key was handled by bookmarks or the jump list.
+pub(super) fn handle_bookmark_key(chat: &mut ChatWidget<'_>, key_event: KeyEvent) -> bool {
+    if !matches!(key_event.kind, KeyEventKind::Press | KeyEventKind::Repeat) {
+        return chat.bookmark_list.open;
+    }
+    let alt_chord = |c: char| {
+        key_event.modifiers == KeyModifiers::ALT
+            && matches!(key_event.code, KeyCode::Char(code) if code.eq_ignore_ascii_case(&c))
+    };
+
+    if !chat.bookmark_list.open {
+        if alt_chord('m') {
+            toggle_visible_bookmark(chat);
+            return true;
+        }
+        if alt_chord('j') {
+            open(chat);
+            return true;
+        }
+        return false;
+    }
+
+    let len = chat.history_state.bookmarks().len();
+    if len == 0 {
+        close(chat);
+        return true;
+    }
+    match key_event.code {
+        KeyCode::Esc => close(chat),
+        _ if alt_chord('j') => close(chat),
+        KeyCode::Up | KeyCode::Char('k') => {
+            chat.bookmark_list.selected = (chat.bookmark_list.selected + len - 1) % len;
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            chat.bookmark_list.selected = (chat.bookmark_list.selected + 1) % len;
+        }
+        KeyCode::Enter => {
+            jump_to_selected(chat);
+            close(chat);
+        }
+        KeyCode::Delete | KeyCode::Char('d') => remove_selected(chat),
+        _ => {}
+    }
+    chat.request_redraw();
+    true
+}
+
+fn open(chat: &mut ChatWidget<'_>) {
+    let len = chat.history_state.bookmarks().len();
+    if len == 0 {
+        chat.bottom_pane
+            .update_status_text("no bookmarks yet (Alt+M marks the cell at the bottom of the view)");
+        chat.request_redraw();
+        return;
+    }
+    chat.bookmark_list.open = true;
+    chat.bookmark_list.selected = len - 1;
+    chat.request_redraw();
+}
+
+fn close(chat: &mut ChatWidget<'_>) {
+    chat.bookmark_list = Default::default();
+    chat.request_redraw();
+}
+
+fn toggle_visible_bookmark(chat: &mut ChatWidget<'_>) {
+    let (start, end) = chat
+        .visible_history_cell_range_for_shortcuts()
+        .unwrap_or((0, chat.history_cells.len()));
+    let Some(id) = (start..end)
+        .rev()
+        .find_map(|idx| chat.history_cell_ids.get(idx).copied().flatten())
+    else {
+        chat.bottom_pane
+            .update_status_text("nothing to bookmark yet");
+        chat.request_redraw();
+        return;
+    };
+    let status = if chat.history_state.toggle_bookmark(id) {
+        "bookmark added (Alt+J to jump)"
+    } else {
+        "bookmark removed"
+    };
+    chat.mark_history_dirty();
+    chat.bottom_pane.update_status_text(status);
+    chat.request_redraw();
+}
+
+fn jump_to_selected(chat: &mut ChatWidget<'_>) {
+    let Some(id) = chat
+        .history_state
+        .bookmarks()
+        .get(chat.bookmark_list.selected)
+        .copied()
+    else {
+        return;
+    };
+    if let Some(idx) = chat.history_cell_ids.iter().position(|cell_id| *cell_id == Some(id)) {
+        layout_scroll::jump_to_history_index(chat, idx);
+    }
+}
+
+fn remove_selected(chat: &mut ChatWidget<'_>) {
+    let bookmarks = chat.history_state.bookmarks();
+    let Some(id) = bookmarks.get(chat.bookmark_list.selected).copied() else {
+        return;
+    };
+    chat.history_state.toggle_bookmark(id);
+    chat.mark_history_dirty();
+    if bookmarks.len() == 1 {
+        close(chat);
+    } else {
+        chat.bookmark_list.selected = chat.bookmark_list.selected.min(bookmarks.len() - 2);
+    }
+}
+
+/// First non-blank line of a cell, used as its label in the jump list.
+fn first_line_text(lines: &[Line<'static>]) -> String {
+    lines
+        .iter()
+        .map(|line| {
+            line.spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect::<String>()
+        })
+        .find(|text| !text.trim().is_empty())
+        .map(|text| text.trim().to_owned())
+        .unwrap_or_default()
+}
+
+impl ChatWidget<'_> {
+    /// Draws the jump list docked to the bottom of the history area.
+    pub(super) fn render_bookmark_list(&self, history_area: Rect, buf: &mut Buffer) {
+        if !self.bookmark_list.open {
+            return;
+        }
+        let bookmarks = self.history_state.bookmarks();
+        let height = (bookmarks.len() as u16 + 2).min(history_area.height);
+        if height < 3 {
+            return;
+        }
+        let area = Rect {
+            y: history_area.bottom() - height,
+            height,
+            ..history_area
+        };
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(Line::from(vec![
+                Span::styled(" Bookmarks ", crate::colors::style_text()),
+                Span::styled(
+                    "— Enter jump · d remove · Esc close ",
+                    crate::colors::style_text_dim(),
+                ),
+            ]))
+            .style(crate::colors::style_on_background())
+            .border_style(crate::colors::style_border_on_bg());
+        let inner = block.inner(area).inner(crate::ui_consts::HORIZONTAL_PAD);
+        Clear.render(area, buf);
+        block.render(area, buf);
+        if inner.width == 0 || inner.height == 0 {
+            return;
+        }
+
+        let width = inner.width as usize;
+        let rows = inner.height as usize;
+        // Keep the selected row visible when the list outgrows the box.
+        let first = self.bookmark_list.selected.saturating_sub(rows - 1);
+        let lines: Vec<Line<'static>> = bookmarks
+            .iter()
+            .enumerate()
+            .skip(first)
+            .take(rows)
+            .map(|(row, id)| {
+                let label = self
+                    .history_cell_ids
+                    .iter()
+                    .position(|cell_id| *cell_id == Some(*id))
+                    .and_then(|idx| {
+                        let cell = self.history_cells.get(idx)?;
+                        Some(first_line_text(&self.cell_lines_for_index(idx, cell.as_ref())))
+                    })
+                    .unwrap_or_default();
+                let text = crate::text_formatting::truncate_to_display_width_with_suffix(
+                    &format!("{:>2}. {label}", row + 1),
+                    width,
+                    "…",
+                );
+                if row == self.bookmark_list.selected {
+                    Line::from(Span::styled(
+                        text,
+                        crate::colors::style_primary().add_modifier(Modifier::BOLD),
+                    ))
+                } else {
+                    Line::from(Span::styled(text, crate::colors::style_text()))
+                }
+            })
+            .collect();
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labels_use_first_non_blank_line() {
+        let lines = vec![
+            Line::from(""),
+            Line::from(vec![Span::raw("  cargo "), Span::raw("test  ")]),
+            Line::from("second"),
+        ];
+        assert_eq!(first_line_text(&lines), "cargo test");
+        assert_eq!(first_line_text(&[]), "");
+    }
+}
@@ -154,6 +154,32 @@ impl ChatWidget<'_> {
         self.request_redraw();
     }
 
+    /// Shows the summary left by `code exec --handoff` for this session at the
+    /// top of the conversation. The summary is consumed, so it is pinned on
+    /// the first resume only.
+    pub(in crate::chatwidget) fn pin_session_handoff(&mut self, session_id: uuid::Uuid) {
+        let summary = match code_core::session_handoff::take_handoff(&self.config.code_home, session_id) {
+            Ok(Some(summary)) => summary,
+            Ok(None) => return,
+            Err(err) => {
+                tracing::warn!("failed to read the hand-off summary: {err}");
+                return;
+            }
+        };
+        let kind = PlainMessageKind::Notice;
+        let mut state = history_cell::plain_message_state_from_paragraphs(
+            kind,
+            history_cell::plain_role_for_kind(kind),
+            summary.lines().map(str::to_owned),
+        );
+        state.header = Some(MessageHeader {
+            label: "Hand-off from the headless run".to_owned(),
+            badge: None,
+        });
+        let key = self.next_req_key_top();
+        let _ = self.history_insert_plain_state_with_key(state, key, "prelude");
+    }
+
     pub(crate) fn handle_session_picker_load_failed(&mut self, message: String) {
         self.resume_picker_loading = false;
         self.bottom_pane.flash_footer_notice(message);
@@ -7,6 +7,10 @@ impl ChatWidget<'_> {
     pub(in super::super::super) fn handle_exec_approval_now(&mut self, _id: String, ev: ExecApprovalRequestEvent) {
         // Use approval_id when present, otherwise fall back to call_id.
         let approval_id = ev.effective_approval_id();
+        crate::resume::crash_recovery::approval_requested(
+            approval_id.clone(),
+            crate::exec_command::strip_bash_lc_and_escape(&ev.command),
+        );
         let ticket = self.make_background_before_next_output_ticket();
         if let Some(ctx) = ev.network_approval_context {
             self.bottom_pane.push_approval_request(
@@ -50,6 +54,13 @@ impl ChatWidget<'_> {
             reason,
             grant_root,
         } = ev;
+        let mut patched_paths: Vec<String> =
+            changes.keys().map(|path| path.display().to_string()).collect();
+        patched_paths.sort();
+        crate::resume::crash_recovery::approval_requested(
+            call_id.clone(),
+            format!("apply patch to {}", patched_paths.join(", ")),
+        );
 
         // Clone for session storage before moving into history
         let changes_clone = changes.clone();
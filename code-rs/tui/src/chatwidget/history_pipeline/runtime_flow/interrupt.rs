@@ -58,6 +58,7 @@ impl ChatWidget<'_> {
         // Defensive: clear transient flags so UI can quiesce
         self.agents_ready_to_start = false;
         self.active_task_ids.clear();
+        crate::resume::crash_recovery::clear_pending_approvals();
         // Restore any queued messages back into the composer so the user can
         // immediately press Enter to resume the conversation where they left off.
         if !self.queued_user_messages.is_empty() {
@@ -0,0 +1,146 @@
+/// Maximum number of distinct render settings (mostly widths) we remember per
+/// history cell. Resizing back and forth between a handful of widths should
+/// never re-measure a cell whose content has not changed.
+const HEIGHT_CACHE_SETTINGS_PER_ID: usize = 4;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct HeightKey {
+    width: u16,
+    theme_epoch: u64,
+    reasoning_visible: bool,
+}
+
+impl HeightKey {
+    fn new(settings: RenderSettings) -> Self {
+        Self {
+            width: settings.width,
+            theme_epoch: settings.theme_epoch,
+            reasoning_visible: settings.reasoning_visible,
+        }
+    }
+}
+
+/// Memoized wrapped heights keyed by `(HistoryId, width)`.
+///
+/// Unlike the layout cache, entries survive terminal resizes so returning to a
+/// previous width is a lookup rather than a re-wrap. Each history id keeps a
+/// small most-recently-used list of settings, and invalidating an id when its
+/// content changes is a single map removal.
+#[derive(Default)]
+pub(crate) struct HeightCache {
+    entries: HashMap<HistoryId, Vec<(HeightKey, u16)>>,
+}
+
+impl HeightCache {
+    pub(crate) fn get(&self, history_id: HistoryId, settings: RenderSettings) -> Option<u16> {
+        let key = HeightKey::new(settings);
+        self.entries
+            .get(&history_id)?
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, height)| *height)
+    }
+
+    pub(crate) fn insert(&mut self, history_id: HistoryId, settings: RenderSettings, height: u16) {
+        if history_id == HistoryId::ZERO {
+            return;
+        }
+        let key = HeightKey::new(settings);
+        let slots = self.entries.entry(history_id).or_default();
+        if let Some(pos) = slots.iter().position(|(k, _)| *k == key) {
+            slots.remove(pos);
+        } else if slots.len() >= HEIGHT_CACHE_SETTINGS_PER_ID {
+            slots.pop();
+        }
+        slots.insert(0, (key, height));
+    }
+
+    /// Drops every cached height for `history_id`; call when its content changes.
+    pub(crate) fn invalidate(&mut self, history_id: HistoryId) {
+        self.entries.remove(&history_id);
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        self.entries.values().map(Vec::len).sum()
+    }
+
+    #[cfg(test)]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod height_cache_tests {
+    use super::*;
+
+    fn settings(width: u16) -> RenderSettings {
+        RenderSettings::new(width, 0, false)
+    }
+
+    #[test]
+    fn heights_survive_width_changes() {
+        let state = HistoryRenderState::new();
+        let id = HistoryId(7);
+        state.handle_width_change(80);
+        state.height_cache.borrow_mut().insert(id, settings(80), 3);
+        state.handle_width_change(40);
+        state.height_cache.borrow_mut().insert(id, settings(40), 6);
+        state.handle_width_change(80);
+
+        assert_eq!(state.cached_height(id, settings(80)), Some(3));
+        assert_eq!(state.cached_height(id, settings(40)), Some(6));
+    }
+
+    #[test]
+    fn invalidating_one_id_keeps_the_others() {
+        let state = HistoryRenderState::new();
+        state.height_cache.borrow_mut().insert(HistoryId(1), settings(80), 2);
+        state.height_cache.borrow_mut().insert(HistoryId(2), settings(80), 5);
+
+        state.invalidate_history_id(HistoryId(1));
+
+        assert_eq!(state.cached_height(HistoryId(1), settings(80)), None);
+        assert_eq!(state.cached_height(HistoryId(2), settings(80)), Some(5));
+    }
+
+    #[test]
+    fn least_recently_used_width_is_evicted() {
+        let mut cache = HeightCache::default();
+        let id = HistoryId(3);
+        for width in 10..10 + HEIGHT_CACHE_SETTINGS_PER_ID as u16 {
+            cache.insert(id, settings(width), width);
+        }
+        // Touch the oldest width so the second-oldest becomes the eviction target.
+        cache.insert(id, settings(10), 10);
+        cache.insert(id, settings(99), 1);
+
+        assert_eq!(cache.len(), HEIGHT_CACHE_SETTINGS_PER_ID);
+        assert_eq!(cache.get(id, settings(10)), Some(10));
+        assert_eq!(cache.get(id, settings(11)), None);
+        assert_eq!(cache.get(id, settings(99)), Some(1));
+    }
+
+    #[test]
+    fn theme_and_reasoning_changes_miss() {
+        let mut cache = HeightCache::default();
+        let id = HistoryId(4);
+        cache.insert(id, RenderSettings::new(80, 1, false), 4);
+
+        assert_eq!(cache.get(id, RenderSettings::new(80, 2, false)), None);
+        assert_eq!(cache.get(id, RenderSettings::new(80, 1, true)), None);
+        assert_eq!(cache.get(id, RenderSettings::new(80, 1, false)), Some(4));
+    }
+
+    #[test]
+    fn zero_id_is_never_cached() {
+        let mut cache = HeightCache::default();
+        cache.insert(HistoryId::ZERO, settings(80), 4);
+        assert!(cache.is_empty());
+    }
+}
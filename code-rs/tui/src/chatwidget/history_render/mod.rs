@@ -31,6 +31,7 @@ use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
 
 include!("cache_stats.rs");
+include!("height_cache.rs");
 include!("render_state.rs");
 include!("layout_cache.rs");
 include!("requests.rs");
@@ -1,7 +1,7 @@
 /// Memoized layout data for history rendering.
 pub(crate) struct HistoryRenderState {
     pub(crate) layout_cache: RefCell<HashMap<CacheKey, Rc<CachedLayout>>>,
-    pub(crate) height_cache: RefCell<HashMap<CacheKey, u16>>,
+    pub(crate) height_cache: RefCell<HeightCache>,
     fallback_cache: RefCell<HashMap<HistoryId, Rc<[Line<'static>]>>>,
     pub(crate) height_cache_last_width: Cell<u16>,
     pub(crate) prefix_sums: RefCell<Vec<u16>>,
@@ -22,7 +22,7 @@ impl HistoryRenderState {
     pub(crate) fn new() -> Self {
         Self {
             layout_cache: RefCell::new(HashMap::new()),
-            height_cache: RefCell::new(HashMap::new()),
+            height_cache: RefCell::new(HeightCache::default()),
             fallback_cache: RefCell::new(HashMap::new()),
             height_cache_last_width: Cell::new(0),
             prefix_sums: RefCell::new(Vec::new()),
@@ -54,12 +54,11 @@ impl HistoryRenderState {
 
     pub(crate) fn handle_width_change(&self, width: u16) {
         if self.height_cache_last_width.get() != width {
+            // Layouts are large, so only the current width is kept. Heights are
+            // tiny and stay cached across widths; see `HeightCache`.
             self.layout_cache
                 .borrow_mut()
                 .retain(|key, _| key.width == width);
-            self.height_cache
-                .borrow_mut()
-                .retain(|key, _| key.width == width);
             self.fallback_cache.borrow_mut().clear();
             self.prefix_sums.borrow_mut().clear();
             self.last_total_height.set(0);
@@ -80,9 +79,7 @@ impl HistoryRenderState {
         self.layout_cache
             .borrow_mut()
             .retain(|key, _| key.history_id != id);
-        self.height_cache
-            .borrow_mut()
-            .retain(|key, _| key.history_id != id);
+        self.height_cache.borrow_mut().invalidate(id);
         self.fallback_cache.borrow_mut().remove(&id);
         self.prefix_sums.borrow_mut().clear();
         self.last_total_height.set(0);
@@ -172,8 +169,7 @@ impl HistoryRenderState {
         if history_id == HistoryId::ZERO {
             return None;
         }
-        let key = CacheKey::new(history_id, settings);
-        self.height_cache.borrow().get(&key).copied()
+        self.height_cache.borrow().get(history_id, settings)
     }
 
     pub(crate) fn update_spacing_ranges(&self, ranges: Vec<(u16, u16)>) {
@@ -323,134 +319,197 @@ impl HistoryRenderState {
         settings: RenderSettings,
     ) -> Vec<VisibleCell<'a>> {
         requests
+            .iter()
+            .map(|req| self.visible_cell(history_state, req, settings))
+            .collect()
+    }
+
+    /// Resolves heights for every request without building layouts for cells
+    /// whose height is already memoized. Used for prefix-sum rebuilds, where
+    /// only heights matter; the returned flag is `false` when some entries
+    /// carry no layout and the caller must resolve the visible window again
+    /// before painting.
+    pub(crate) fn measure_cells<'a>(
+        &self,
+        history_state: &HistoryState,
+        requests: &[RenderRequest<'a>],
+        settings: RenderSettings,
+    ) -> (Vec<VisibleCell<'a>>, bool) {
+        let mut paintable = true;
+        let cells = requests
             .iter()
             .map(|req| {
-                let assistant_plan = if settings.width == 0 {
-                    None
-                } else if let Some(assistant_cell) = req.assistant {
-                    if assistant_cell.is_collapsed() {
-                        None
-                    } else {
-                        Some(assistant_cell.ensure_layout(settings.width))
-                    }
-                } else if let RenderRequestKind::Assistant { id } = req.kind {
-                    history_state
-                        .record(id)
-                        .and_then(|record| match record {
-                            HistoryRecord::AssistantMessage(state) => Some(Rc::new(
-                                compute_assistant_layout(state, req.config, settings.width),
-                            )),
-                            _ => None,
-                        })
+                let cached = if Self::height_cacheable(req, settings) {
+                    self.height_cache.borrow().get(req.history_id, settings)
                 } else {
                     None
                 };
+                match cached {
+                    Some(height) => {
+                        paintable = false;
+                        VisibleCell {
+                            cell: req.cell,
+                            assistant_plan: None,
+                            layout: None,
+                            height,
+                            height_source: HeightSource::Cached,
+                            height_measure_ns: None,
+                        }
+                    }
+                    None => self.visible_cell(history_state, req, settings),
+                }
+            })
+            .collect();
+        (cells, paintable)
+    }
 
-                let has_custom_render = req
-                    .cell
-                    .is_some_and(crate::history_cell::HistoryCell::has_custom_render);
+    /// Whether a request's layout height may be memoized and reused without
+    /// rebuilding its layout. Assistant messages resolve heights through their
+    /// own per-width plan cache and streaming cells change every frame.
+    fn height_cacheable(req: &RenderRequest<'_>, settings: RenderSettings) -> bool {
+        req.use_cache
+            && req.history_id != HistoryId::ZERO
+            && settings.width > 0
+            && req.assistant.is_none()
+            && !matches!(
+                req.kind,
+                RenderRequestKind::Streaming { .. } | RenderRequestKind::Assistant { .. }
+            )
+    }
 
-                let prohibit_cache = matches!(req.kind, RenderRequestKind::Streaming { .. });
-                let use_cache = req.use_cache && !prohibit_cache;
+    fn visible_cell<'a>(
+        &self,
+        history_state: &HistoryState,
+        req: &RenderRequest<'a>,
+        settings: RenderSettings,
+    ) -> VisibleCell<'a> {
+        let assistant_plan = if settings.width == 0 {
+            None
+        } else if let Some(assistant_cell) = req.assistant {
+            if assistant_cell.is_collapsed() {
+                None
+            } else {
+                Some(assistant_cell.ensure_layout(settings.width))
+            }
+        } else if let RenderRequestKind::Assistant { id } = req.kind {
+            history_state
+                .record(id)
+                .and_then(|record| match record {
+                    HistoryRecord::AssistantMessage(state) => Some(Rc::new(
+                        compute_assistant_layout(state, req.config, settings.width),
+                    )),
+                    _ => None,
+                })
+        } else {
+            None
+        };
 
-                let layout = if has_custom_render
-                    || settings.width == 0
-                    || assistant_plan.is_some()
-                {
-                    None
-                } else if use_cache && req.history_id != HistoryId::ZERO {
-                    Some(self.render_cached(req.history_id, settings, || {
-                        req.build_lines(history_state, None)
-                    }))
-                } else {
-                    Some(self.render_adhoc(settings.width, || {
-                        req.build_lines(history_state, None)
-                    }))
-                };
+        let has_custom_render = req
+            .cell
+            .is_some_and(crate::history_cell::HistoryCell::has_custom_render);
 
-                let use_height_cache = use_cache && req.history_id != HistoryId::ZERO;
-                let cached_height = if use_height_cache {
-                    let key = CacheKey::new(req.history_id, settings);
-                    self.height_cache
-                        .borrow()
-                        .get(&key)
-                        .copied()
-                        .map(|h| (h, HeightSource::Cached, None))
-                } else {
-                    None
-                };
+        let prohibit_cache = matches!(req.kind, RenderRequestKind::Streaming { .. });
+        let use_cache = req.use_cache && !prohibit_cache;
 
-                let (height, height_source, height_measure_ns) = if settings.width == 0 {
-                    (0, HeightSource::ZeroWidth, None)
-                } else if let Some(plan) = assistant_plan.as_ref() {
-                    (plan.total_rows(), HeightSource::AssistantPlan, None)
-                } else if let Some(layout_ref) = layout.as_ref() {
-                    (
-                        clamp_u16(layout_ref
-                            .line_count()),
-                        HeightSource::Layout,
-                        None,
-                    )
-                } else if let Some((h, src, measure)) = cached_height {
-                    (h, src, measure)
-                } else if let Some(cell) = req.cell {
-                    if cell.has_custom_render() {
-                        let start = Instant::now();
-                        let computed = cell.desired_height(settings.width);
-                        let elapsed = start.elapsed().as_nanos();
-                        if use_height_cache {
-                            let key = CacheKey::new(req.history_id, settings);
-                            self.height_cache.borrow_mut().insert(key, computed);
-                        }
-                        (
-                            computed,
-                            HeightSource::DesiredHeight,
-                            Some(elapsed),
-                        )
-                    } else if let Some(lines) = req.fallback_lines.as_ref() {
-                        let wrapped = word_wrap_lines(lines, settings.width);
-                        let height = clamp_u16(wrapped.len());
-                        if use_height_cache {
-                            let key = CacheKey::new(req.history_id, settings);
-                            self.height_cache.borrow_mut().insert(key, height);
-                        }
-                        (height, HeightSource::FallbackLines, None)
-                    } else {
-                        let start = Instant::now();
-                        let computed = cell.desired_height(settings.width);
-                        let elapsed = start.elapsed().as_nanos();
-                        if use_height_cache {
-                            let key = CacheKey::new(req.history_id, settings);
-                            self.height_cache.borrow_mut().insert(key, computed);
-                        }
-                        (
-                            computed,
-                            HeightSource::DesiredHeight,
-                            Some(elapsed),
-                        )
-                    }
-                } else if let Some(lines) = req.fallback_lines.as_ref() {
-                    let wrapped = word_wrap_lines(lines, settings.width);
-                    let height = clamp_u16(wrapped.len());
-                    if use_height_cache {
-                        let key = CacheKey::new(req.history_id, settings);
-                        self.height_cache.borrow_mut().insert(key, height);
-                    }
-                    (height, HeightSource::FallbackLines, None)
-                } else {
-                    (0, HeightSource::Unknown, None)
-                };
+        let layout = if has_custom_render
+            || settings.width == 0
+            || assistant_plan.is_some()
+        {
+            None
+        } else if use_cache && req.history_id != HistoryId::ZERO {
+            Some(self.render_cached(req.history_id, settings, || {
+                req.build_lines(history_state, None)
+            }))
+        } else {
+            Some(self.render_adhoc(settings.width, || {
+                req.build_lines(history_state, None)
+            }))
+        };
 
-                VisibleCell {
-                    cell: req.cell,
-                    assistant_plan,
-                    layout,
-                    height,
-                    height_source,
-                    height_measure_ns,
+        let use_height_cache = use_cache && req.history_id != HistoryId::ZERO;
+        let cached_height = if use_height_cache {
+            self.height_cache
+                .borrow()
+                .get(req.history_id, settings)
+                .map(|h| (h, HeightSource::Cached, None))
+        } else {
+            None
+        };
+
+        let (height, height_source, height_measure_ns) = if settings.width == 0 {
+            (0, HeightSource::ZeroWidth, None)
+        } else if let Some(plan) = assistant_plan.as_ref() {
+            (plan.total_rows(), HeightSource::AssistantPlan, None)
+        } else if let Some(layout_ref) = layout.as_ref() {
+            let height = clamp_u16(layout_ref.line_count());
+            if Self::height_cacheable(req, settings) {
+                self.height_cache
+                    .borrow_mut()
+                    .insert(req.history_id, settings, height);
+            }
+            (height, HeightSource::Layout, None)
+        } else if let Some((h, src, measure)) = cached_height {
+            (h, src, measure)
+        } else if let Some(cell) = req.cell {
+            if cell.has_custom_render() {
+                let start = Instant::now();
+                let computed = cell.desired_height(settings.width);
+                let elapsed = start.elapsed().as_nanos();
+                if use_height_cache {
+                    self.height_cache
+                        .borrow_mut()
+                        .insert(req.history_id, settings, computed);
                 }
-            })
-            .collect()
+                (
+                    computed,
+                    HeightSource::DesiredHeight,
+                    Some(elapsed),
+                )
+            } else if let Some(lines) = req.fallback_lines.as_ref() {
+                let wrapped = word_wrap_lines(lines, settings.width);
+                let height = clamp_u16(wrapped.len());
+                if use_height_cache {
+                    self.height_cache
+                        .borrow_mut()
+                        .insert(req.history_id, settings, height);
+                }
+                (height, HeightSource::FallbackLines, None)
+            } else {
+                let start = Instant::now();
+                let computed = cell.desired_height(settings.width);
+                let elapsed = start.elapsed().as_nanos();
+                if use_height_cache {
+                    self.height_cache
+                        .borrow_mut()
+                        .insert(req.history_id, settings, computed);
+                }
+                (
+                    computed,
+                    HeightSource::DesiredHeight,
+                    Some(elapsed),
+                )
+            }
+        } else if let Some(lines) = req.fallback_lines.as_ref() {
+            let wrapped = word_wrap_lines(lines, settings.width);
+            let height = clamp_u16(wrapped.len());
+            if use_height_cache {
+                self.height_cache
+                    .borrow_mut()
+                    .insert(req.history_id, settings, height);
+            }
+            (height, HeightSource::FallbackLines, None)
+        } else {
+            (0, HeightSource::Unknown, None)
+        };
+
+        VisibleCell {
+            cell: req.cell,
+            assistant_plan,
+            layout,
+            height,
+            height_source,
+            height_measure_ns,
+        }
     }
 
     fn render_cached<F>(&self, history_id: HistoryId, settings: RenderSettings, build_lines: F) -> LayoutRef
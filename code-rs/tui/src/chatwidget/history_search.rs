@@ -0,0 +1,222 @@
+//! Full-text search across the conversation history (Ctrl+F with an empty
+//! composer). Matches are whole history cells; the viewport jumps to the
+//! current one and every visible occurrence of the query is highlighted.
+
+use super::ChatWidget;
+use super::layout_scroll;
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Clear, Paragraph, Widget};
+
+// Returns true if the key was handled by history search.
+pub(super) fn handle_history_search_key(chat: &mut ChatWidget<'_>, key_event: KeyEvent) -> bool {
+    if !matches!(key_event.kind, KeyEventKind::Press | KeyEventKind::Repeat) {
+        return chat.history_search.active;
+    }
+    let ctrl = key_event.modifiers.contains(KeyModifiers::CONTROL);
+
+    if !chat.history_search.active {
+        // Ctrl+F only opens search when it would not move the composer cursor.
+        if ctrl && key_event.code == KeyCode::Char('f') && chat.bottom_pane.composer_is_empty() {
+            chat.history_search.active = true;
+            chat.request_redraw();
+            return true;
+        }
+        return false;
+    }
+
+    match key_event.code {
+        KeyCode::Esc => close(chat),
+        KeyCode::Char('f') if ctrl => close(chat),
+        KeyCode::Enter | KeyCode::Down => step(chat, true),
+        KeyCode::Up => step(chat, false),
+        KeyCode::Char('n') if ctrl => step(chat, true),
+        KeyCode::Char('p') if ctrl => step(chat, false),
+        KeyCode::Backspace => {
+            chat.history_search.query.pop();
+            refresh_matches(chat);
+        }
+        KeyCode::Char(c) if !ctrl && !key_event.modifiers.contains(KeyModifiers::ALT) => {
+            chat.history_search.query.push(c);
+            refresh_matches(chat);
+        }
+        _ => {}
+    }
+    true
+}
+
+fn close(chat: &mut ChatWidget<'_>) {
+    chat.history_search = Default::default();
+    chat.request_redraw();
+}
+
+/// Recomputes matches for the edited query and jumps to the newest one.
+fn refresh_matches(chat: &mut ChatWidget<'_>) {
+    let query = chat.history_search.query.clone();
+    let texts: Vec<String> = chat
+        .history_cells
+        .iter()
+        .enumerate()
+        .map(|(idx, cell)| lines_text(&chat.cell_lines_for_index(idx, cell.as_ref())))
+        .collect();
+    chat.history_search.matches = find_matches(texts.iter().map(String::as_str), &query);
+    chat.history_search.current = chat.history_search.matches.len().checked_sub(1);
+    jump_to_current(chat);
+}
+
+/// Moves to the next (older-to-newer) or previous match, wrapping around.
+fn step(chat: &mut ChatWidget<'_>, forward: bool) {
+    let len = chat.history_search.matches.len();
+    let Some(current) = chat.history_search.current else {
+        return;
+    };
+    chat.history_search.current = Some(if forward {
+        (current + 1) % len
+    } else {
+        (current + len - 1) % len
+    });
+    jump_to_current(chat);
+}
+
+fn jump_to_current(chat: &mut ChatWidget<'_>) {
+    if let Some(idx) = chat
+        .history_search
+        .current
+        .and_then(|current| chat.history_search.matches.get(current).copied())
+    {
+        layout_scroll::jump_to_history_index(chat, idx);
+    }
+    chat.request_redraw();
+}
+
+fn lines_text(lines: &[Line<'static>]) -> String {
+    let mut text = String::new();
+    for line in lines {
+        for span in &line.spans {
+            text.push_str(&span.content);
+        }
+        text.push('\n');
+    }
+    text
+}
+
+/// Indices of the history cells whose text contains `query`, ignoring case.
+fn find_matches<'a>(texts: impl Iterator<Item = &'a str>, query: &str) -> Vec<usize> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let query = query.to_lowercase();
+    texts
+        .enumerate()
+        .filter(|(_, text)| text.to_lowercase().contains(&query))
+        .map(|(idx, _)| idx)
+        .collect()
+}
+
+/// Restyles every occurrence of `query` inside `area`, one buffer row at a time.
+fn highlight_matches(buf: &mut Buffer, area: Rect, query: &str, style: Style) {
+    let query: Vec<char> = query.to_lowercase().chars().collect();
+    if query.is_empty() {
+        return;
+    }
+    for y in area.top()..area.bottom() {
+        // One entry per column; wide characters leave an empty continuation cell.
+        let row: Vec<Option<char>> = (area.left()..area.right())
+            .map(|x| {
+                let mut chars = buf[(x, y)].symbol().chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => c.to_lowercase().next(),
+                    _ => None,
+                }
+            })
+            .collect();
+        let mut start = 0;
+        while start + query.len() <= row.len() {
+            let hit = query
+                .iter()
+                .zip(&row[start..])
+                .all(|(want, have)| *have == Some(*want));
+            if hit {
+                for offset in 0..query.len() {
+                    let x = area.left() + (start + offset) as u16;
+                    buf[(x, y)].set_style(style);
+                }
+                start += query.len();
+            } else {
+                start += 1;
+            }
+        }
+    }
+}
+
+impl ChatWidget<'_> {
+    /// Highlights matches in the rendered history and draws the search bar on
+    /// the last row of the history area.
+    pub(super) fn render_history_search(&self, history_area: Rect, content_area: Rect, buf: &mut Buffer) {
+        let search = &self.history_search;
+        if !search.active || history_area.height == 0 {
+            return;
+        }
+
+        let highlight = Style::default()
+            .fg(crate::colors::background())
+            .bg(crate::colors::warning());
+        highlight_matches(buf, content_area, &search.query, highlight);
+
+        let bar = Rect {
+            y: history_area.bottom() - 1,
+            height: 1,
+            ..history_area
+        };
+        let status = match search.current {
+            Some(current) => format!("{}/{}", current + 1, search.matches.len()),
+            None if search.query.is_empty() => String::new(),
+            None => "no matches".to_owned(),
+        };
+        let line = Line::from(vec![
+            Span::styled(" Search: ", crate::colors::style_primary().add_modifier(Modifier::BOLD)),
+            Span::styled(search.query.clone(), crate::colors::style_text()),
+            Span::styled("▏", crate::colors::style_text_dim()),
+            Span::styled(format!("  {status}"), crate::colors::style_text_dim()),
+            Span::styled(
+                "   Enter/↓ next · ↑ previous · Esc close",
+                crate::colors::style_text_dim(),
+            ),
+        ]);
+        Clear.render(bar, buf);
+        Paragraph::new(line)
+            .style(crate::colors::style_on_background())
+            .render(bar, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_cells_ignoring_case() {
+        let cells = ["Run the Tests", "cargo build", "tests passed\n"];
+        assert_eq!(find_matches(cells.into_iter(), "TESTS"), vec![0, 2]);
+        assert_eq!(find_matches(cells.into_iter(), "missing"), Vec::<usize>::new());
+        assert_eq!(find_matches(cells.into_iter(), ""), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn highlights_every_occurrence_in_a_row() {
+        let area = Rect::new(0, 0, 12, 1);
+        let mut buf = Buffer::empty(area);
+        buf.set_string(0, 0, "abc ABC abx", Style::default());
+        let style = Style::default().add_modifier(Modifier::REVERSED);
+
+        highlight_matches(&mut buf, area, "abc", style);
+
+        let highlighted: Vec<u16> = (0..area.width)
+            .filter(|&x| buf[(x, 0)].modifier.contains(Modifier::REVERSED))
+            .collect();
+        assert_eq!(highlighted, vec![0, 1, 2, 4, 5, 6]);
+    }
+}
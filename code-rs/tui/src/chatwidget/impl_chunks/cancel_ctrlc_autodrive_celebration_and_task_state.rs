@@ -27,6 +27,18 @@ impl ChatWidget<'_> {
         }
     }
 
+    /// Asks core to shut down after SIGTERM/SIGHUP; `ShutdownComplete` then
+    /// requests the exit. Falls back to exiting after a grace period if core
+    /// does not answer. Returns `false` when there is no session to shut down.
+    pub(crate) fn shutdown_for_signal(&mut self) -> bool {
+        if self.session_id.is_none() {
+            return false;
+        }
+        self.submit_op(Op::Shutdown);
+        self.spawn_app_event_after(SIGNAL_SHUTDOWN_GRACE, AppEvent::ExitRequest);
+        true
+    }
+
     pub(crate) fn composer_is_empty(&self) -> bool {
         self.bottom_pane.composer_is_empty()
     }
@@ -276,6 +288,9 @@ impl ChatWidget<'_> {
 
     /// Forward an `Op` directly to codex.
     pub(crate) fn submit_op(&self, op: Op) {
+        if let Op::ExecApproval { id, .. } | Op::PatchApproval { id, .. } = &op {
+            crate::resume::crash_recovery::approval_resolved(id);
+        }
         if let Err(e) = self.code_op_tx.send(op) {
             tracing::error!("failed to submit op: {e}");
         }
@@ -312,6 +327,7 @@ impl ChatWidget<'_> {
         command: Vec<String>,
         match_kind: ApprovedCommandMatchKind,
         semantic_prefix: Option<Vec<String>>,
+        persist: bool,
     ) {
         if command.is_empty() {
             return;
@@ -320,6 +336,7 @@ impl ChatWidget<'_> {
             command,
             match_kind,
             semantic_prefix,
+            persist,
         };
         self.submit_op(op);
     }
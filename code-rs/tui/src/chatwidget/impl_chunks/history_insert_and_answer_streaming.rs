@@ -336,6 +336,7 @@ impl ChatWidget<'_> {
             Some(MessageMetadata {
                 citations,
                 token_usage,
+                sources: Vec::new(),
             })
         }
     }
@@ -453,6 +454,10 @@ impl ChatWidget<'_> {
             .stream
             .preview_source_for_kind(StreamKind::Answer)
             .unwrap_or_default();
+        // Low-bandwidth mode keeps recording deltas but only repaints the
+        // streaming cell once a paragraph completes.
+        let repaint = !self.config.tui.low_bandwidth
+            || crate::markdown_stream::ends_with_paragraph_break(&preview);
         let delta = if delta.is_empty() {
             None
         } else {
@@ -489,7 +494,8 @@ impl ChatWidget<'_> {
                 self.mark_history_dirty();
             }
             HistoryMutation::Replaced { id, record, .. } => {
-                if matches!(record, HistoryRecord::AssistantStream(_))
+                if repaint
+                    && matches!(record, HistoryRecord::AssistantStream(_))
                     && self.cell_index_for_history_id(id).is_some()
                 {
                     self.update_cell_from_record(id, record);
@@ -610,10 +616,25 @@ impl ChatWidget<'_> {
                 metadata = Some(MessageMetadata {
                     citations: citations.clone(),
                     token_usage: None,
+                    sources: Vec::new(),
                 });
             }
         }
 
+        let sources = self.turn_sources.cite(source);
+        if !sources.is_empty() {
+            match metadata.as_mut() {
+                Some(meta) => meta.sources = sources,
+                None => {
+                    metadata = Some(MessageMetadata {
+                        citations: citations.clone(),
+                        token_usage: None,
+                        sources,
+                    });
+                }
+            }
+        }
+
         let should_attach_token_usage = self.last_token_usage.total_tokens > 0;
         if should_attach_token_usage {
             if let Some(meta) = metadata.as_mut() {
@@ -624,6 +645,7 @@ impl ChatWidget<'_> {
                 metadata = Some(MessageMetadata {
                     citations,
                     token_usage: Some(self.last_token_usage.clone()),
+                    sources: Vec::new(),
                 });
             }
         }
@@ -9,9 +9,19 @@ impl ChatWidget<'_> {
             }
             "off" => {
                 self.perf_state.enabled = false;
+                self.perf_state.overlay = false;
                 self.perf_state.pending_scroll_rows.set(0);
                 self.add_perf_output("performance tracing: off".to_owned());
             }
+            "overlay" => {
+                self.perf_state.overlay = !self.perf_state.overlay;
+                if self.perf_state.overlay {
+                    // The overlay reads live counters, so tracing must be on.
+                    self.perf_state.enabled = true;
+                }
+                let state = if self.perf_state.overlay { "on" } else { "off" };
+                self.add_perf_output(format!("performance overlay: {state}"));
+            }
             "reset" => {
                 self.perf_state.stats.borrow_mut().reset();
                 self.perf_state.pending_scroll_rows.set(0);
@@ -22,12 +32,40 @@ impl ChatWidget<'_> {
                 self.add_perf_output(summary);
             }
             _ => {
-                self.add_perf_output("usage: /perf on | off | show | reset".to_owned());
+                self.add_perf_output("usage: /perf on | off | show | reset | overlay".to_owned());
             }
         }
         self.request_redraw();
     }
 
+    pub(crate) fn history_record_count(&self) -> usize {
+        self.history_cells.len()
+    }
+
+    /// Sheds rebuildable render state and warns once RSS crosses
+    /// `tui.memory_soft_limit_mb`.
+    pub(crate) fn handle_memory_soft_limit(
+        &mut self,
+        crossing: crate::session_health::SoftLimitCrossing,
+    ) {
+        let crate::session_health::SoftLimitCrossing::Exceeded { rss_bytes, limit_bytes } =
+            crossing;
+        tracing::warn!(rss_bytes, limit_bytes, "session memory above soft limit");
+
+        // Cached layouts and heights are rebuilt on demand; re-syncing the
+        // virtualization window freezes everything outside the viewport.
+        self.history_render.invalidate_all();
+        self.sync_history_virtualization();
+
+        let rss = code_core::util::format_bytes(usize::try_from(rss_bytes).unwrap_or(usize::MAX));
+        let limit =
+            code_core::util::format_bytes(usize::try_from(limit_bytes).unwrap_or(usize::MAX));
+        self.history_push_plain_state(history_cell::new_warning_event(format!(
+            "Memory use is {rss}, above the {limit} soft limit. Run /compact or start a new session with /new to keep this session responsive."
+        )));
+        self.request_redraw();
+    }
+
     pub(crate) fn handle_demo_command(&mut self, command_args: String) {
         let trimmed_args = command_args.trim();
         if !trimmed_args.is_empty() {
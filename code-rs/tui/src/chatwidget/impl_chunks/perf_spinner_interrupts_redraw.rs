@@ -5,6 +5,16 @@ impl ChatWidget<'_> {
     pub(crate) fn perf_summary(&self) -> String {
         self.perf_state.stats.borrow().summary()
     }
+    /// Feed the app-measured draw time of the last frame into the profiler.
+    pub(crate) fn perf_record_frame_draw(&self, elapsed: std::time::Duration) {
+        if !self.perf_state.enabled {
+            return;
+        }
+        self.perf_state
+            .stats
+            .borrow_mut()
+            .record_frame_draw(elapsed.as_nanos());
+    }
     // Build an ordered key from model-provided OrderMeta. Callers must
     // guarantee presence by passing a concrete reference (compile-time guard).
 
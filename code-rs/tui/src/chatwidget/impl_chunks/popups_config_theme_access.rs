@@ -183,6 +183,10 @@ impl ChatWidget<'_> {
             lines.push(kv(&jump_parent_hotkey, "Jump to parent tool call (composer empty)"));
             let jump_child_hotkey = history_label(hotkeys.jump_to_latest_child_call, "}");
             lines.push(kv(&jump_child_hotkey, "Jump to latest spawned tool call (composer empty)"));
+            lines.push(kv(&ctrl("F"), "Search conversation history (composer empty)"));
+            lines.push(kv(&alt("M"), "Bookmark the bottommost visible history cell"));
+            lines.push(kv(&alt("J"), "Jump list of bookmarked cells"));
+            lines.push(kv("F6", "Dock agents status panel beside the conversation"));
             lines.push(kv(&ctrl("G"), "Open external editor"));
             lines.push(kv(&ctrl("R"), "Toggle reasoning"));
             lines.push(kv(&ctrl("T"), "Toggle screen"));
@@ -658,6 +662,7 @@ impl ChatWidget<'_> {
             dynamic_tools: Vec::new(),
             shell: self.config.shell.clone(),
             shell_style_profiles: self.config.shell_style_profiles.clone(),
+            shell_env_set: Some(self.config.shell_environment_policy.r#set.clone()),
             network: self.config.network.clone(),
             tools_repl: self.config.tools_repl,
             repl_default_runtime: self.config.repl_default_runtime,
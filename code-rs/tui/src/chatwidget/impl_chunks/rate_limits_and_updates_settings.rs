@@ -582,6 +582,17 @@ impl ChatWidget<'_> {
         self.show_settings_overlay(Some(SettingsSection::Limits));
     }
 
+    pub(crate) fn handle_prompt_command(&mut self, args: String) {
+        if !args.trim().is_empty() {
+            self.history_push_plain_state(history_cell::new_error_event(
+                "Usage: /prompt".to_owned(),
+            ));
+            return;
+        }
+        self.flash_footer_notice("Assembling prompt stack…");
+        self.submit_op(Op::PreviewPrompt);
+    }
+
     pub(crate) fn handle_memories_command(&mut self, args: String) {
         let trimmed = args.trim();
         match trimmed {
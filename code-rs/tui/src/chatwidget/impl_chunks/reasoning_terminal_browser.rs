@@ -109,6 +109,27 @@ impl ChatWidget<'_> {
         self.config.tui.show_reasoning
     }
 
+    /// Announce finished browser downloads in the history so the user can see
+    /// where the file landed.
+    #[cfg(feature = "browser-automation")]
+    async fn install_browser_download_notice(
+        browser_manager: &code_browser::BrowserManager,
+        app_event_tx: AppEventSender,
+        ticket: BackgroundOrderTicket,
+    ) {
+        browser_manager
+            .set_download_callback(move |download| {
+                let message = match (download.state, &download.path) {
+                    (code_browser::DownloadState::Completed, Some(path)) => {
+                        format!("Browser: downloaded {} to {}", download.suggested_filename, path.display())
+                    }
+                    _ => format!("Browser: download of {} was canceled", download.suggested_filename),
+                };
+                app_event_tx.send_background_event_with_ticket(&ticket, message);
+            })
+            .await;
+    }
+
     #[cfg(feature = "browser-automation")]
     fn schedule_browser_autofix(
         app_event_tx: AppEventSender,
@@ -390,6 +411,13 @@ impl ChatWidget<'_> {
                             .await;
                     }
 
+                    ChatWidget::install_browser_download_notice(
+                        &browser_manager,
+                        app_event_tx.clone(),
+                        ticket.clone(),
+                    )
+                    .await;
+
                     // Set the browser manager as the global manager so both TUI and Session use the same instance
                     code_browser::global::set_global_browser_manager(browser_manager.clone())
                         .await;
@@ -384,6 +384,11 @@ impl ChatWidget<'_> {
                     self.push_background_tail(Self::format_mcp_status_report(&rows));
                 }
             }
+            "reload" => {
+                // Core re-reads the config, diffs it against the running
+                // servers and answers with a summary and a fresh tool list.
+                self.submit_op(Op::ReloadMcpServers);
+            }
             "on" | "off" => {
                 let name = parts.next().unwrap_or("");
                 if name.is_empty() {
@@ -578,7 +583,7 @@ impl ChatWidget<'_> {
             }
             _ => {
                 let msg = format!(
-                    "Unknown MCP command: '{sub}'\nUsage:\n  /mcp status\n  /mcp on <name>\n  /mcp off <name>\n  /mcp add <name> <command> [args…] [ENV=VAL…]"
+                    "Unknown MCP command: '{sub}'\nUsage:\n  /mcp status\n  /mcp reload\n  /mcp on <name>\n  /mcp off <name>\n  /mcp add <name> <command> [args…] [ENV=VAL…]"
                 );
                 self.history_push_plain_state(history_cell::new_error_event(msg));
             }
@@ -373,6 +373,12 @@ impl ChatWidget<'_> {
     }
 
     pub(in super::super) fn refresh_queued_user_messages(&mut self, schedule_watchdog: bool) {
+        crate::resume::crash_recovery::set_queued_messages(
+            self.queued_user_messages
+                .iter()
+                .map(|message| message.display_text.clone())
+                .collect(),
+        );
         let mut scheduled_watchdog = false;
         if self.queued_user_messages.is_empty() {
             self.queue_block_started_at = None;
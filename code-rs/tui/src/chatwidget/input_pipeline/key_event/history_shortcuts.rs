@@ -1,5 +1,5 @@
 impl ChatWidget<'_> {
-    fn visible_history_cell_range_for_shortcuts(&self) -> Option<(usize, usize)> {
+    pub(in crate::chatwidget) fn visible_history_cell_range_for_shortcuts(&self) -> Option<(usize, usize)> {
         let viewport_height = self.layout.last_history_viewport_height.get();
         if viewport_height == 0 {
             return None;
@@ -57,7 +57,7 @@ impl ChatWidget<'_> {
         (start < end).then_some((start, end))
     }
 
-    fn toggle_bottommost_exec_fold(&self) {
+    fn toggle_bottommost_exec_fold(&mut self) {
         use crate::history_cell::{
             ExecCell,
             ReplCell,
@@ -80,6 +80,7 @@ impl ChatWidget<'_> {
                     tracing::debug!("toggle_bottommost_exec_fold: exec idx={idx} call_id={:?}", cell.call_id());
                 }
                 exec_cell.toggle_output_collapsed();
+                self.persist_exec_output_fold(idx);
                 self.invalidate_height_cache();
                 self.request_redraw();
                 return;
@@ -135,6 +136,27 @@ impl ChatWidget<'_> {
         }
     }
 
+    /// Records the user's fold choice on the exec record so it survives
+    /// re-hydration of the cell and session resume.
+    fn persist_exec_output_fold(&mut self, idx: usize) {
+        use crate::history_cell::ExecCell;
+
+        let Some(exec_cell) = self
+            .history_cells
+            .get_mut(idx)
+            .and_then(|cell| cell.as_any_mut().downcast_mut::<ExecCell>())
+        else {
+            return;
+        };
+        let folded = exec_cell.is_output_collapsed();
+        exec_cell.record.output_folded = Some(folded);
+        let id = exec_cell.record.id;
+        if let Some(HistoryRecord::Exec(record)) = self.history_state.record_mut(id) {
+            record.output_folded = Some(folded);
+            self.mark_history_dirty();
+        }
+    }
+
     /// Toggle fold/collapse for a specific history cell by index (used by mouse clicks).
     pub(in crate::chatwidget) fn toggle_fold_at_index(&self, idx: usize) {
         use crate::history_cell::{
@@ -33,6 +33,12 @@ impl ChatWidget<'_> {
         if self.help.overlay.is_some() {
             return;
         }
+        if history_search::handle_history_search_key(self, key_event) {
+            return;
+        }
+        if history_bookmarks::handle_bookmark_key(self, key_event) {
+            return;
+        }
         if diff_handlers::handle_diff_key(self, key_event) {
             return;
         }
@@ -311,6 +317,10 @@ impl ChatWidget<'_> {
                 self.handle_clickable_action(action);
                 return;
             }
+            if key_event.code == crossterm::event::KeyCode::F(6) && key_event.modifiers.is_empty() {
+                self.toggle_agents_split_panel();
+                return;
+            }
         }
 
         if let KeyEvent {
@@ -470,8 +480,10 @@ impl ChatWidget<'_> {
         }
         self.auto_sync_goal_escape_state_from_composer();
 
+        // Alt+Enter submits like Enter but interrupts a running turn.
+        let interrupt_running_turn = matches!(input_result, InputResult::SubmittedInterrupt(_));
         match input_result {
-            InputResult::Submitted(text) => {
+            InputResult::Submitted(text) | InputResult::SubmittedInterrupt(text) => {
                 if let Some(pending) = self.pending_request_user_input.take() {
                     self.submit_request_user_input_answer(pending, text);
                     return;
@@ -505,6 +517,7 @@ impl ChatWidget<'_> {
                     return;
                 }
                 let user_message = self.parse_message_with_images(text);
+                self.interrupt_next_user_message = interrupt_running_turn;
                 self.submit_user_message(user_message);
             }
             InputResult::Command(_cmd) => {
@@ -7,6 +7,14 @@ impl ChatWidget<'_> {
         }
     }
 
+    /// Docks or undocks the agents status panel beside the conversation
+    /// (`tui.split_agents_panel`) for the rest of the session.
+    pub(in super::super) fn toggle_agents_split_panel(&mut self) {
+        self.config.tui.split_agents_panel = !self.config.tui.split_agents_panel;
+        self.invalidate_height_cache();
+        self.request_redraw();
+    }
+
     pub(crate) fn handle_paste(&mut self, text: String) {
         if settings_handlers::handle_settings_paste(self, text.clone()) {
             return;
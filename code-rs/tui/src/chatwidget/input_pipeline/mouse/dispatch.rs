@@ -251,8 +251,10 @@ impl ChatWidget<'_> {
 
     /// Process `InputResult` from mouse events (similar to key event handling).
     pub(in super::super) fn process_mouse_input_result(&mut self, input_result: InputResult) {
+        // Alt+Enter submits like Enter but interrupts a running turn.
+        let interrupt_running_turn = matches!(input_result, InputResult::SubmittedInterrupt(_));
         match input_result {
-            InputResult::Submitted(text) => {
+            InputResult::Submitted(text) | InputResult::SubmittedInterrupt(text) => {
                 if let Some(pending) = self.pending_request_user_input.take() {
                     self.submit_request_user_input_answer(pending, text);
                     return;
@@ -264,6 +266,7 @@ impl ChatWidget<'_> {
                     return;
                 }
                 let user_message = self.parse_message_with_images(text);
+                self.interrupt_next_user_message = interrupt_running_turn;
                 self.submit_user_message(user_message);
             }
             InputResult::Command(_cmd) => {
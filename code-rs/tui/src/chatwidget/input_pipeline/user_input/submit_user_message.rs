@@ -10,6 +10,7 @@ impl ChatWidget<'_> {
         for cell in &self.history_cells {
             cell.trigger_fade();
         }
+        let interrupt_running_turn = std::mem::take(&mut self.interrupt_next_user_message);
         let mut message = user_message;
         // If our configured cwd no longer exists (e.g., a worktree folder was
         // deleted outside the app), try to automatically recover to the repo
@@ -482,6 +483,17 @@ impl ChatWidget<'_> {
             || !self.queued_user_messages.is_empty())
             && !wait_only_active;
 
+        if turn_active && interrupt_running_turn {
+            tracing::info!("[queue] Interrupting the running turn with user input");
+            self.queued_user_messages.push_back(message.clone());
+            self.refresh_queued_user_messages(false);
+            let _ = self.capture_ghost_snapshot(
+                (!message.display_text.trim().is_empty()).then(|| message.display_text.clone()),
+            );
+            self.dispatch_queued_batch(vec![message]);
+            return;
+        }
+
         if turn_active {
             tracing::info!(
                 "[queue] Enqueuing user input while turn is active (queue_size={}, task_running={}, stream_active={}, active_tasks={})",
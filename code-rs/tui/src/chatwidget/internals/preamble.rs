@@ -4,6 +4,8 @@ pub(crate) const DOUBLE_ESC_HINT: &str = "undo timeline";
 pub(crate) const AUTO_ESC_EXIT_HINT: &str = "Press Esc to exit Auto Drive";
 pub(crate) const AUTO_ESC_EXIT_HINT_DOUBLE: &str = "Press Esc again to exit Auto Drive";
 pub(crate) const AUTO_COMPLETION_CELEBRATION_DURATION: Duration = Duration::from_secs(5);
+/// How long core gets to finish a SIGTERM/SIGHUP shutdown before the app exits anyway.
+pub(crate) const SIGNAL_SHUTDOWN_GRACE: Duration = Duration::from_secs(5);
 pub(crate) const HISTORY_ANIMATION_FRAME_INTERVAL: Duration = crate::timing::ANIMATION_FRAME_INTERVAL;
 pub(crate) const AUTO_BOOTSTRAP_GOAL_PLACEHOLDER: &str = "Deriving goal from recent conversation";
 pub(crate) const AUTO_DRIVE_SESSION_SUMMARY_NOTICE: &str = "Summarizing session";
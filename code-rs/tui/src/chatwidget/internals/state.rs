@@ -297,6 +297,25 @@ impl HelpFocus {
     }
 }
 
+/// Ctrl+F search across the conversation history.
+#[derive(Default)]
+pub(crate) struct HistorySearchState {
+    pub(crate) active: bool,
+    pub(crate) query: String,
+    /// History cell indices containing the query, oldest first.
+    pub(crate) matches: Vec<usize>,
+    /// Position in `matches` the viewport is showing.
+    pub(crate) current: Option<usize>,
+}
+
+/// Alt+J jump list over bookmarked history cells.
+#[derive(Default)]
+pub(crate) struct BookmarkListState {
+    pub(crate) open: bool,
+    /// Row highlighted in the jump list, in history order.
+    pub(crate) selected: usize,
+}
+
 #[derive(Default)]
 pub(crate) struct HelpState {
     pub(crate) overlay: Option<HelpOverlay>,
@@ -486,6 +505,8 @@ impl HelpOverlay {
 #[derive(Default)]
 pub(crate) struct PerfState {
     pub(crate) enabled: bool,
+    /// Show the live frame profiler overlay (`/perf overlay`).
+    pub(crate) overlay: bool,
     pub(crate) stats: RefCell<PerfStats>,
     pub(crate) pending_scroll_rows: Cell<u64>,
 }
@@ -74,6 +74,7 @@ use serde_json::Value as JsonValue;
 mod diff_handlers;
 mod agent_summary;
 mod agent_editor_flow;
+mod approvals_flow;
 mod esc;
 mod modals;
 mod agent;
@@ -86,8 +87,10 @@ mod diff_ui;
 mod exec_tools;
 mod gh_actions;
 mod history_links;
+mod history_bookmarks;
 mod history_pipeline;
 mod history_render;
+mod history_search;
 mod history_virtualization_impl;
 mod help_handlers;
 mod secrets_help;
@@ -107,11 +110,14 @@ mod notifications;
 mod ordering;
 mod system_ordering;
 mod background_review;
+mod crash_recovery;
 mod overlay_rendering;
 mod perf;
+mod project_trust;
 mod rate_limit_refresh;
 mod repo_workflow;
 mod review_flow;
+mod session_env_flow;
 mod session_flow;
 mod shell_config_flow;
 mod session_tuning_flow;
@@ -441,6 +447,8 @@ use code_git_tooling::{
     CreateGhostCommitOptions,
     GhostCommit,
     GitToolingError,
+    WorktreeCheckoutOptions,
+    WorktreePool,
 };
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyEventKind;
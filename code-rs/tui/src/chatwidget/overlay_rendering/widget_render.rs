@@ -1,8 +1,10 @@
 use super::*;
 
+mod agents_status_panel;
 mod history_scroller;
 mod overlay_stack;
 mod pending_command;
+mod perf_overlay;
 mod terminal_overlay;
 
 impl ChatWidget<'_> {
@@ -43,12 +45,13 @@ impl ChatWidget<'_> {
             return;
         }
 
+        let (conversation_area, agents_panel_area) = self.split_agents_panel_area(history_area);
         let padding = 1u16;
         let content_area = Rect {
-            x: history_area.x + padding,
-            y: history_area.y,
-            width: history_area.width.saturating_sub(padding * 2),
-            height: history_area.height,
+            x: conversation_area.x + padding,
+            y: conversation_area.y,
+            width: conversation_area.width.saturating_sub(padding * 2),
+            height: conversation_area.height,
         };
 
         // The history scroller renders cells inside `content_area` with a 4-column
@@ -90,13 +93,18 @@ impl ChatWidget<'_> {
         }
 
         self.render_history_scroller(
-            history_area,
+            conversation_area,
             content_area,
             base_style,
             streaming_cell,
             queued_preview_cells,
             buf,
         );
+        self.render_history_search(conversation_area, content_area, buf);
+        self.render_bookmark_list(conversation_area, buf);
+        if let Some(panel_area) = agents_panel_area {
+            self.render_agents_status_panel(panel_area, buf);
+        }
 
         self.render_terminal_overlay_and_bottom_pane(area, history_area, bottom_pane_area, buf);
         self.render_overlay_stack(area, history_area, bottom_pane_area, buf);
@@ -104,7 +112,12 @@ impl ChatWidget<'_> {
         if let Some(t0) = _perf_widget_start {
             let dt = t0.elapsed().as_nanos();
             let mut p = self.perf_state.stats.borrow_mut();
-            p.ns_widget_render_total = p.ns_widget_render_total.saturating_add(dt);
+            p.record_widget_render(dt);
+        }
+
+        // Drawn last so it sits above every other overlay.
+        if self.perf_state.overlay {
+            self.render_perf_overlay(history_area, buf);
         }
     }
 }
@@ -0,0 +1,88 @@
+use super::*;
+
+/// Narrowest history area that still gets the docked panel; below this the
+/// conversation keeps the full width.
+const MIN_SPLIT_WIDTH: u16 = 100;
+
+impl ChatWidget<'_> {
+    /// Splits the history area when `tui.split_agents_panel` is on, returning
+    /// the conversation area and the docked agents panel (if any).
+    pub(super) fn split_agents_panel_area(&self, history_area: Rect) -> (Rect, Option<Rect>) {
+        if !self.config.tui.split_agents_panel
+            || self.agents_terminal.active
+            || history_area.width < MIN_SPLIT_WIDTH
+        {
+            return (history_area, None);
+        }
+        let panel_width = (history_area.width / 3).clamp(28, 48);
+        let conversation = Rect {
+            width: history_area.width - panel_width,
+            ..history_area
+        };
+        let panel = Rect {
+            x: conversation.right(),
+            width: panel_width,
+            ..history_area
+        };
+        (conversation, Some(panel))
+    }
+
+    /// Live agent status docked beside the conversation. Ctrl+A still opens
+    /// the full agents terminal for logs and controls.
+    pub(super) fn render_agents_status_panel(&self, area: Rect, buf: &mut Buffer) {
+        let s_text = crate::colors::style_text();
+        let s_text_dim = crate::colors::style_text_dim();
+
+        let block = Block::default()
+            .borders(Borders::LEFT)
+            .title(Line::from(vec![
+                Span::styled(" Agents ", s_text),
+                Span::styled("— Ctrl+A details · F6 hide", s_text_dim),
+            ]))
+            .style(crate::colors::style_on_background())
+            .border_style(crate::colors::style_border_on_bg());
+        let inner = block.inner(area).inner(crate::ui_consts::HORIZONTAL_PAD);
+        block.render(area, buf);
+        if inner.width == 0 || inner.height == 0 {
+            return;
+        }
+
+        let width = inner.width as usize;
+        let truncate = |text: &str| {
+            crate::text_formatting::truncate_to_display_width_with_suffix(text, width, "…")
+        };
+        let mut lines: Vec<RtLine<'static>> = Vec::new();
+        for agent_id in &self.agents_terminal.order {
+            let Some(entry) = self.agents_terminal.entries.get(agent_id) else {
+                continue;
+            };
+            let name = entry.model.as_deref().unwrap_or(&entry.name);
+            let label = format!(
+                "{} {}",
+                agent_status_icon(entry.status),
+                crate::text_formatting::format_model_label(name)
+            );
+            lines.push(RtLine::from(Span::styled(
+                truncate(&label),
+                Style::default().fg(agent_status_color(entry.status)),
+            )));
+            let detail = entry
+                .error
+                .as_deref()
+                .or(entry.last_progress.as_deref())
+                .unwrap_or_else(|| agent_status_label(entry.status));
+            let detail = detail.lines().next().unwrap_or_default();
+            lines.push(RtLine::from(Span::styled(
+                truncate(&format!("  {detail}")),
+                s_text_dim,
+            )));
+        }
+        if lines.is_empty() {
+            lines.push(RtLine::from(Span::styled("No agents yet", s_text_dim)));
+        }
+
+        // Keep the newest agents visible when the list outgrows the panel.
+        let overflow = lines.len().saturating_sub(inner.height as usize);
+        Paragraph::new(RtText::from(lines.split_off(overflow))).render(inner, buf);
+    }
+}
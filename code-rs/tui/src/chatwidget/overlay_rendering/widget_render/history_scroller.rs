@@ -28,7 +28,7 @@ impl ChatWidget<'_> {
         let reasoning_visible = self.is_reasoning_shown();
         let cache_width = content_area.width.saturating_sub(GUTTER_WIDTH);
 
-        // Opportunistically clear height cache if width changed
+        // Drop layouts for other widths; memoized heights are kept
         self.history_render.handle_width_change(cache_width);
 
         // Perf: count a frame
@@ -137,7 +137,9 @@ impl ChatWidget<'_> {
             }
 
             let prefix_start = perf_enabled.then(std::time::Instant::now);
-            let cells = self.history_render.visible_cells(
+            // Heights memoized from earlier frames (including other widths)
+            // skip layout entirely; the visible window is resolved below.
+            let (cells, paintable) = self.history_render.measure_cells(
                 &self.history_state,
                 render_requests,
                 render_settings,
@@ -258,7 +260,7 @@ impl ChatWidget<'_> {
                 self.history_cells.len(),
             );
             self.history_render.update_spacing_ranges(spacing_ranges);
-            rendered_cells_full = Some(cells);
+            rendered_cells_full = paintable.then_some(cells);
             self.history_prefix_append_only.set(true);
             }
         }
@@ -81,9 +81,13 @@ impl ChatWidget<'_> {
                 let t_dim = s_text_dim;
                 let t_fg = s_text;
                 let has_tabs = overlay.tabs.len() > 1;
+                let is_findings = overlay.is_review_findings();
                 let mut title_spans: Vec<ratatui::text::Span<'static>> = vec![
                     ratatui::text::Span::styled(" ", t_dim),
-                    ratatui::text::Span::styled("Diff viewer", t_fg),
+                    ratatui::text::Span::styled(
+                        if is_findings { "Review findings" } else { "Diff viewer" },
+                        t_fg,
+                    ),
                 ];
                 if has_tabs {
                     title_spans.extend_from_slice(&[
@@ -92,13 +96,23 @@ impl ChatWidget<'_> {
                         ratatui::text::Span::styled(" change tabs ", t_dim),
                     ]);
                 }
+                if is_findings {
+                    title_spans.extend_from_slice(&[
+                        ratatui::text::Span::styled(crate::ui_consts::SEP_EM_CONT, t_dim),
+                        ratatui::text::Span::styled("f", t_fg),
+                        ratatui::text::Span::styled(" fix this finding ", t_dim),
+                    ]);
+                } else {
+                    title_spans.extend_from_slice(&[
+                        ratatui::text::Span::styled(crate::ui_consts::SEP_EM_CONT, t_dim),
+                        ratatui::text::Span::styled("e", t_fg),
+                        ratatui::text::Span::styled(" explain ", t_dim),
+                        ratatui::text::Span::styled(crate::ui_consts::SEP_EM_CONT, t_dim),
+                        ratatui::text::Span::styled("u", t_fg),
+                        ratatui::text::Span::styled(" undo ", t_dim),
+                    ]);
+                }
                 title_spans.extend_from_slice(&[
-                    ratatui::text::Span::styled(crate::ui_consts::SEP_EM_CONT, t_dim),
-                    ratatui::text::Span::styled("e", t_fg),
-                    ratatui::text::Span::styled(" explain ", t_dim),
-                    ratatui::text::Span::styled(crate::ui_consts::SEP_EM_CONT, t_dim),
-                    ratatui::text::Span::styled("u", t_fg),
-                    ratatui::text::Span::styled(" undo ", t_dim),
                     ratatui::text::Span::styled(crate::ui_consts::SEP_EM_CONT, t_dim),
                     ratatui::text::Span::styled(crate::icons::escape(), t_fg),
                     ratatui::text::Span::styled(" close ", t_dim),
@@ -306,8 +320,13 @@ impl ChatWidget<'_> {
                             height: h,
                         };
                         Clear.render(dialog, buf);
+                        let (dlg_title, dlg_question) = if is_findings {
+                            ("Confirm Fix", "Ask the model to fix just this finding?")
+                        } else {
+                            ("Confirm Undo", "Are you sure you want to undo this diff?")
+                        };
                         let dlg_block = crate::components::popup_frame::themed_block()
-                            .title("Confirm Undo");
+                            .title(dlg_title);
                         let dlg_inner = dlg_block.inner(dialog);
                         dlg_block.render(dialog, buf);
                         // Fill dialog inner area with theme background for consistent look
@@ -318,7 +337,7 @@ impl ChatWidget<'_> {
                             }
                         }
                         let lines = vec![
-                            ratatui::text::Line::from("Are you sure you want to undo this diff?"),
+                            ratatui::text::Line::from(dlg_question),
                             ratatui::text::Line::from(
                                 "Press Enter to confirm • Esc to cancel".to_owned().dim(),
                             ),
@@ -0,0 +1,47 @@
+use super::*;
+
+impl ChatWidget<'_> {
+    /// Live frame profiler HUD pinned to the top-right of the history area.
+    pub(super) fn render_perf_overlay(&self, history_area: Rect, buf: &mut Buffer) {
+        let (high, bulk) = self.app_event_tx.queue_depth();
+        let lines = self
+            .perf_state
+            .stats
+            .borrow()
+            .overlay_lines(perf::QueueDepth { high, bulk });
+
+        let content_width = lines
+            .iter()
+            .map(|line| UnicodeWidthStr::width(line.as_str()))
+            .max()
+            .unwrap_or(0) as u16;
+        // Borders (2) + horizontal padding (2).
+        let width = content_width.saturating_add(4).min(history_area.width);
+        let height = (lines.len() as u16).saturating_add(2).min(history_area.height);
+        if width < 8 || height < 3 {
+            return;
+        }
+        let area = Rect {
+            x: history_area.x + history_area.width - width,
+            y: history_area.y,
+            width,
+            height,
+        };
+
+        Clear.render(area, buf);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(Span::styled(" perf ", crate::colors::style_text_dim()))
+            .style(crate::colors::style_on_background())
+            .border_style(crate::colors::style_border_on_bg());
+        let inner = block.inner(area).inner(crate::ui_consts::HORIZONTAL_PAD);
+        block.render(area, buf);
+
+        let text_style = crate::colors::style_text();
+        let body: Vec<RtLine<'static>> = lines
+            .into_iter()
+            .map(|line| RtLine::from(Span::styled(line, text_style)))
+            .collect();
+        Paragraph::new(RtText::from(body)).render(inner, buf);
+    }
+}
@@ -3,8 +3,12 @@
 //! Kept as a separate module to keep `chatwidget.rs` lean. Pure data + helpers
 //! with no UI dependencies so it is easy to unit‑test in isolation.
 
+use std::collections::VecDeque;
 use std::fmt::Write as _;
 
+/// Number of recent frames kept for the live profiler overlay percentiles.
+const FRAME_WINDOW: usize = 120;
+
 #[derive(Default, Clone, Debug)]
 pub struct PerfStats {
     pub frames: u64,
@@ -44,6 +48,16 @@ pub struct PerfStats {
     // Undo/resume instrumentation
     pub undo_restore_events: u64,
     pub ns_undo_restore: u128,
+    // Rolling per-frame samples for the live overlay (`/perf overlay`)
+    pub recent_widget_render_ns: VecDeque<u128>,
+    pub recent_frame_draw_ns: VecDeque<u128>,
+}
+
+/// Pending app events at the time of the last frame, split by channel.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QueueDepth {
+    pub high: usize,
+    pub bulk: usize,
 }
 
 #[derive(Default, Clone, Debug)]
@@ -202,8 +216,112 @@ impl PerfStats {
         self.ns_scroll_render = self.ns_scroll_render.saturating_add(ns);
     }
 
+    /// Widget render time for one frame (layout + paint, excluding terminal IO).
+    pub fn record_widget_render(&mut self, ns: u128) {
+        self.ns_widget_render_total = self.ns_widget_render_total.saturating_add(ns);
+        push_sample(&mut self.recent_widget_render_ns, ns);
+    }
+
+    /// Full draw time for one frame as measured by the app loop (includes
+    /// buffer diffing and terminal writes).
+    pub fn record_frame_draw(&mut self, ns: u128) {
+        push_sample(&mut self.recent_frame_draw_ns, ns);
+    }
+
+    /// Compact multi-line report rendered by the live profiler overlay.
+    pub fn overlay_lines(&self, queue: QueueDepth) -> Vec<String> {
+        let mut lines = Vec::with_capacity(8);
+        let (draw_last, draw_p50, draw_p95) = window_stats_ms(&self.recent_frame_draw_ns);
+        let (widget_last, widget_p50, widget_p95) = window_stats_ms(&self.recent_widget_render_ns);
+        lines.push(format!(
+            "draw   {draw_last:>6.2}ms  p50 {draw_p50:>6.2}  p95 {draw_p95:>6.2}"
+        ));
+        lines.push(format!(
+            "widget {widget_last:>6.2}ms  p50 {widget_p50:>6.2}  p95 {widget_p95:>6.2}"
+        ));
+        lines.push(format!(
+            "queue  high={} bulk={}  frames={}",
+            queue.high, queue.bulk, self.frames
+        ));
+        lines.push(format!(
+            "height cache hits={} misses={}",
+            self.height_hits_total, self.height_misses_total
+        ));
+
+        let mut kinds: Vec<(&str, &ItemStat)> = self
+            .per_kind_total
+            .iter()
+            .map(|(k, s)| (k.as_str(), s))
+            .collect();
+        kinds.sort_by_key(|(_, s)| std::cmp::Reverse(s.ns));
+        if !kinds.is_empty() {
+            lines.push("measure by kind:".to_owned());
+        }
+        for (kind, stat) in kinds.into_iter().take(4) {
+            lines.push(format!(
+                "  {kind} {:.2}ms/{}",
+                (stat.ns as f64) / 1_000_000.0,
+                stat.calls,
+            ));
+        }
+        lines
+    }
+
     pub fn record_undo_restore(&mut self, ns: u128) {
         self.undo_restore_events = self.undo_restore_events.saturating_add(1);
         self.ns_undo_restore = self.ns_undo_restore.saturating_add(ns);
     }
 }
+
+fn push_sample(window: &mut VecDeque<u128>, ns: u128) {
+    if window.len() == FRAME_WINDOW {
+        window.pop_front();
+    }
+    window.push_back(ns);
+}
+
+/// Returns (last, p50, p95) in milliseconds for a sample window.
+fn window_stats_ms(window: &VecDeque<u128>) -> (f64, f64, f64) {
+    let Some(last) = window.back().copied() else {
+        return (0.0, 0.0, 0.0);
+    };
+    let mut sorted: Vec<u128> = window.iter().copied().collect();
+    sorted.sort_unstable();
+    let pct = |p: f64| {
+        let idx = ((sorted.len() as f64 - 1.0) * p).round() as usize;
+        (sorted[idx] as f64) / 1_000_000.0
+    };
+    ((last as f64) / 1_000_000.0, pct(0.50), pct(0.95))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_window_is_bounded_and_reports_percentiles() {
+        let mut stats = PerfStats::default();
+        for ms in 1..=(FRAME_WINDOW as u128 + 10) {
+            stats.record_frame_draw(ms * 1_000_000);
+        }
+        assert_eq!(stats.recent_frame_draw_ns.len(), FRAME_WINDOW);
+
+        let (last, p50, p95) = window_stats_ms(&stats.recent_frame_draw_ns);
+        assert_eq!(last, (FRAME_WINDOW + 10) as f64);
+        assert!(p50 < p95);
+        assert!(p95 <= last);
+    }
+
+    #[test]
+    fn overlay_lines_include_queue_depth_and_kinds() {
+        let mut stats = PerfStats::default();
+        stats.record_widget_render(2_000_000);
+        stats.per_kind_total.insert(
+            "exec".to_owned(),
+            ItemStat { calls: 3, ns: 4_500_000 },
+        );
+        let lines = stats.overlay_lines(QueueDepth { high: 1, bulk: 7 });
+        assert!(lines.iter().any(|l| l.contains("high=1 bulk=7")));
+        assert!(lines.iter().any(|l| l.contains("exec 4.50ms/3")));
+    }
+}
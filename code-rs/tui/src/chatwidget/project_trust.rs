@@ -0,0 +1,125 @@
+use super::*;
+
+use std::fmt::Write as _;
+
+use code_core::config_loader::IgnoredProjectLayer;
+
+impl ChatWidget<'_> {
+    /// Checks once per widget whether the cwd has `.code/` config or hooks
+    /// that are being skipped because the project is untrusted, and asks the
+    /// user to trust it if so.
+    pub(super) fn maybe_prompt_project_trust(&mut self) {
+        if self.project_trust_prompted {
+            return;
+        }
+        self.project_trust_prompted = true;
+
+        let code_home = self.config.code_home.clone();
+        let cwd = self.config.cwd.clone();
+        let tx = self.app_event_tx.clone();
+        tokio::spawn(async move {
+            let stack = match code_core::config_loader::load_config_layers_state_with_cwd(
+                &code_home,
+                Some(cwd.as_path()),
+                &[],
+                code_core::config_loader::LoaderOverrides::default(),
+            )
+            .await
+            {
+                Ok(stack) => stack,
+                Err(err) => {
+                    tracing::warn!("failed to load config layers for trust check: {err}");
+                    return;
+                }
+            };
+            let ignored = stack.ignored_project_layers();
+            if ignored.is_empty() {
+                return;
+            }
+            let project = tokio::task::spawn_blocking(move || {
+                code_core::config::project_trust_root(&cwd)
+            })
+            .await;
+            if let Ok(project) = project {
+                tx.send(AppEvent::ShowProjectTrustPrompt { project, ignored });
+            }
+        });
+    }
+
+    pub(crate) fn show_project_trust_prompt(
+        &mut self,
+        project: PathBuf,
+        ignored: Vec<IgnoredProjectLayer>,
+    ) {
+        let mut subtitle = format!(
+            "{} is not trusted, so these project files are being ignored:",
+            project.display()
+        );
+        for layer in &ignored {
+            if let Some(config_file) = &layer.config_file {
+                let _ = write!(subtitle, "\n  {} (config layer)", config_file.display());
+            }
+            if let Some(hooks_file) = &layer.hooks_file {
+                let _ = write!(subtitle, "\n  {} (lifecycle hooks)", hooks_file.display());
+            }
+        }
+
+        let trust_project = project.clone();
+        let items = vec![
+            SelectionItem {
+                name: "Trust this project".to_owned(),
+                description: Some(
+                    "Saved to your user config.toml; applies to new sessions.".to_owned(),
+                ),
+                is_current: true,
+                actions: vec![Box::new(move |tx: &AppEventSender| {
+                    tx.send(AppEvent::TrustProject {
+                        project: trust_project.clone(),
+                    });
+                })],
+            },
+            SelectionItem {
+                name: "Not now".to_owned(),
+                description: Some(format!(
+                    "Keep ignoring them. Run `code trust add {}` later to trust it.",
+                    project.display()
+                )),
+                is_current: false,
+                actions: Vec::new(),
+            },
+        ];
+
+        let view = ListSelectionView::new(
+            " Untrusted project ".to_owned(),
+            Some(subtitle),
+            Some("Enter select - Esc cancel".to_owned()),
+            items,
+            self.app_event_tx.clone(),
+            6,
+        );
+        self.bottom_pane.show_list_selection(view);
+        self.request_redraw();
+    }
+
+    pub(crate) fn trust_project(&mut self, project: PathBuf) {
+        match code_core::config::set_project_trusted(&self.config.code_home, &project) {
+            Ok(()) => {
+                self.history_push_plain_paragraphs(
+                    PlainMessageKind::Notice,
+                    vec![
+                        format!("Trusted {}.", project.display()),
+                        "Project config layers and hooks load in new sessions; start one with /new."
+                            .to_owned(),
+                    ],
+                );
+            }
+            Err(err) => {
+                self.history_push_plain_state(history_cell::new_error_event(format!(
+                    "Failed to trust {}: {err}",
+                    project.display()
+                )));
+            }
+        }
+        self.request_redraw();
+    }
+}
@@ -1,6 +1,12 @@
 use std::fmt::Write as _;
 
 use super::super::*;
+use super::findings::finding_location;
+use code_auto_drive_core::default_finding_selection;
+use code_auto_drive_core::extend_acknowledged;
+use code_auto_drive_core::is_acknowledged;
+use code_auto_drive_core::split_findings;
+use code_core::config_types::AutoResolveFindingSelection;
 use code_protocol::protocol::ReviewTarget;
 
 /// Append a "Commit under review: <sha> (short SHA <short>). <instruction>" block.
@@ -22,7 +28,22 @@ impl ChatWidget<'_> {
     }
 
     pub(in crate::chatwidget) fn auto_resolve_clear(&mut self) {
-        self.auto_resolve_state = None;
+        let acknowledged = self
+            .auto_resolve_state
+            .take()
+            .map(|state| state.acknowledged_findings)
+            .unwrap_or_default();
+        if !acknowledged.is_empty() {
+            let mut report = format!(
+                "Auto-resolve: {} finding(s) acknowledged but not fixed:",
+                acknowledged.len()
+            );
+            for finding in &acknowledged {
+                let location = finding_location(finding, &self.config.cwd);
+                let _ = write!(report, "\n- {} ({location})", finding.title.trim());
+            }
+            self.auto_resolve_notice(report);
+        }
         self.maybe_resume_auto_after_review();
     }
 
@@ -42,7 +63,7 @@ impl ChatWidget<'_> {
 
     pub(in crate::chatwidget) fn auto_resolve_should_block_auto_resume(&self) -> bool {
         match self.auto_resolve_state.as_ref().map(|state| &state.phase) {
-            Some(AutoResolvePhase::PendingFix { .. } | AutoResolvePhase::AwaitingFix { ..
+            Some(AutoResolvePhase::AwaitingSelection { .. } | AutoResolvePhase::PendingFix { .. } | AutoResolvePhase::AwaitingFix { ..
 } | AutoResolvePhase::AwaitingJudge { .. }) => true,
             Some(AutoResolvePhase::WaitingForReview) => self.is_review_flow_active(),
             None => false,
@@ -95,6 +116,7 @@ impl ChatWidget<'_> {
 
         let notice: Option<String>;
         let mut should_clear = false;
+        let mut show_picker = false;
         {
             let Some(state) = self.auto_resolve_state.as_mut() else {
                 return;
@@ -118,10 +140,39 @@ impl ChatWidget<'_> {
                     });
                     should_clear = true;
                 } else {
-                    state.phase = AutoResolvePhase::PendingFix {
-                        review: output.clone(),
-                    };
-                    notice = Some("Auto-resolve: review found issues. Preparing follow-up fix request.".to_owned());
+                    let settings = &self.config.auto_drive;
+                    let mut selected = default_finding_selection(output, settings);
+                    if settings.auto_resolve_findings == AutoResolveFindingSelection::Pick {
+                        // Findings the user already passed on stay unselected.
+                        for (flag, finding) in selected.iter_mut().zip(&output.findings) {
+                            *flag &= !is_acknowledged(&state.acknowledged_findings, finding);
+                        }
+                        state.phase = AutoResolvePhase::AwaitingSelection {
+                            review: output.clone(),
+                            selected,
+                        };
+                        show_picker = true;
+                        notice = Some("Auto-resolve: review found issues. Choose which findings to fix.".to_owned());
+                    } else {
+                        let total = output.findings.len();
+                        let (to_fix, acknowledged) = split_findings(output, &selected);
+                        extend_acknowledged(&mut state.acknowledged_findings, acknowledged);
+                        let fixing = to_fix.findings.len();
+                        if fixing == 0 {
+                            notice = Some("Auto-resolve: no findings meet the fix threshold. Exiting.".to_owned());
+                            should_clear = true;
+                        } else {
+                            notice = Some(if fixing == total {
+                                "Auto-resolve: review found issues. Preparing follow-up fix request.".to_owned()
+                            } else {
+                                format!(
+                                    "Auto-resolve: review found issues. Preparing a fix request for {fixing} of {total} findings; the rest stay acknowledged."
+                                )
+                            });
+                            state.last_review = Some(to_fix.clone());
+                            state.phase = AutoResolvePhase::PendingFix { review: to_fix };
+                        }
+                    }
                 }
             } else {
                 notice = Some(
@@ -131,11 +182,119 @@ impl ChatWidget<'_> {
             }
         }
 
+        if let Some(message) = notice {
+            self.auto_resolve_notice(message);
+        }
         if should_clear {
             self.auto_resolve_clear();
         }
-        if let Some(message) = notice {
-            self.auto_resolve_notice(message);
+        if show_picker {
+            self.show_auto_resolve_finding_picker();
+        }
+    }
+
+    /// Lists the findings of a review awaiting selection with checkboxes, plus
+    /// actions to fix the selected ones or skip fixing altogether.
+    pub(crate) fn show_auto_resolve_finding_picker(&mut self) {
+        self.show_auto_resolve_finding_picker_at(0);
+    }
+
+    fn show_auto_resolve_finding_picker_at(&mut self, cursor: usize) {
+        let Some(AutoResolvePhase::AwaitingSelection { review, selected }) =
+            self.auto_resolve_state.as_ref().map(|state| &state.phase)
+        else {
+            return;
+        };
+        let chosen = selected.iter().filter(|flag| **flag).count();
+        let total = review.findings.len();
+
+        let mut items: Vec<SelectionItem> = Vec::with_capacity(total + 2);
+        items.push(SelectionItem {
+            name: format!("Fix selected findings ({chosen} of {total})"),
+            description: Some("Findings left unchecked are reported as acknowledged".to_owned()),
+            is_current: false,
+            actions: vec![Box::new(|tx: &crate::app_event_sender::AppEventSender| {
+                tx.send(crate::app_event::AppEvent::ConfirmAutoResolveFindings { fix: true });
+            })],
+        });
+        for (index, finding) in review.findings.iter().enumerate() {
+            let mark = if selected.get(index).copied().unwrap_or(false) { "[x]" } else { "[ ]" };
+            items.push(SelectionItem {
+                name: format!("{mark} {}", finding.title.trim()),
+                description: Some(format!(
+                    "{} · P{} · confidence {:.1}",
+                    finding_location(finding, &self.config.cwd),
+                    finding.priority,
+                    finding.confidence_score
+                )),
+                is_current: false,
+                actions: vec![Box::new(move |tx: &crate::app_event_sender::AppEventSender| {
+                    tx.send(crate::app_event::AppEvent::ToggleAutoResolveFinding { index });
+                })],
+            });
+        }
+        items.push(SelectionItem {
+            name: "Skip fixes (acknowledge all)".to_owned(),
+            description: None,
+            is_current: false,
+            actions: vec![Box::new(|tx: &crate::app_event_sender::AppEventSender| {
+                tx.send(crate::app_event::AppEvent::ConfirmAutoResolveFindings { fix: false });
+            })],
+        });
+
+        let view = ListSelectionView::new(
+            " Auto-resolve: choose findings to fix ".to_owned(),
+            Some("Enter toggles a finding · pick \"Fix selected\" when done".to_owned()),
+            Some("Esc to decide later with /review --findings".to_owned()),
+            items,
+            self.app_event_tx.clone(),
+            10,
+        )
+        .with_selected_index(cursor);
+        self.bottom_pane.show_list_selection(view);
+    }
+
+    pub(crate) fn toggle_auto_resolve_finding(&mut self, index: usize) {
+        let Some(AutoResolvePhase::AwaitingSelection { selected, .. }) =
+            self.auto_resolve_state.as_mut().map(|state| &mut state.phase)
+        else {
+            return;
+        };
+        let Some(flag) = selected.get_mut(index) else {
+            return;
+        };
+        *flag = !*flag;
+        // Keep the cursor on the toggled row (row 0 is the confirm action).
+        self.show_auto_resolve_finding_picker_at(index + 1);
+    }
+
+    /// Finishes a finding selection: the chosen findings go to the fix phase
+    /// (or none when `fix` is false) and the rest are acknowledged.
+    pub(crate) fn confirm_auto_resolve_findings(&mut self, fix: bool) {
+        let task_running = self.is_task_running();
+        let Some(state) = self.auto_resolve_state.as_mut() else {
+            return;
+        };
+        let AutoResolvePhase::AwaitingSelection { review, selected } = &state.phase else {
+            return;
+        };
+        let selected: &[bool] = if fix { selected } else { &[] };
+        let (to_fix, acknowledged) = split_findings(review, selected);
+        extend_acknowledged(&mut state.acknowledged_findings, acknowledged);
+        if to_fix.findings.is_empty() {
+            self.auto_resolve_notice("Auto-resolve: no findings selected for fixing. Exiting.");
+            self.auto_resolve_clear();
+            return;
+        }
+        state.last_review = Some(to_fix.clone());
+        if task_running {
+            // The review turn is still winding down; dispatch on completion.
+            state.phase = AutoResolvePhase::PendingFix { review: to_fix };
+        } else {
+            state.phase = AutoResolvePhase::AwaitingFix {
+                review: to_fix.clone(),
+            };
+            self.dispatch_auto_fix(&to_fix);
         }
     }
 
@@ -166,7 +325,7 @@ impl ChatWidget<'_> {
                 let message = last_agent_message.unwrap_or_default();
                 self.auto_resolve_process_judge(review, message);
             }
-            AutoResolvePhase::WaitingForReview => {}
+            AutoResolvePhase::AwaitingSelection { .. } | AutoResolvePhase::WaitingForReview => {}
         }
     }
 
@@ -205,7 +364,7 @@ impl ChatWidget<'_> {
     pub(in crate::chatwidget) fn dispatch_auto_judge(&mut self, review: &ReviewOutputEvent, fix_message: Option<String>) {
         let summary = Self::auto_resolve_format_findings(review);
         let mut preface = String::from(
-            "You are evaluating whether the latest fixes resolved the findings from `/review`. Respond with a strict JSON object containing `status` and optional `rationale`. Valid `status` values: `review_again`, `no_issue`, `continue_fix`. Do not include any additional text before or after the JSON."
+            "You are evaluating whether the latest fixes resolved the findings from `/review`. Respond with a strict JSON object containing `status`, `rationale`, and `findings`. Valid `status` values: `review_again`, `no_issue`, `continue_fix`. `rationale` explains the decision. `findings` has one entry per original finding with its `title`, a `verdict` of `fixed`, `not_fixed`, or `regressed`, and an optional `note`. Do not include any additional text before or after the JSON."
         );
         if !summary.is_empty() {
             let _ = write!(preface, "\n\nOriginal findings:\n{summary}");
@@ -213,7 +372,7 @@ impl ChatWidget<'_> {
         if let Some(fix) = fix_message.as_ref().map(|s| s.trim()).filter(|s| !s.is_empty()) {
             let _ = write!(preface, "\n\nLatest agent response:\n{fix}");
         }
-        preface.push_str("\n\nReturn JSON: {\"status\": \"...\", \"rationale\": \"optional explanation\"}.");
+        preface.push_str("\n\nReturn JSON: {\"status\": \"...\", \"rationale\": \"why\", \"findings\": [{\"title\": \"...\", \"verdict\": \"fixed\", \"note\": \"optional\"}]}.");
         if let Some(commit) = self.auto_resolve_commit_sha() {
             append_commit_block(
                 &mut preface,
@@ -323,6 +482,7 @@ impl ChatWidget<'_> {
             return;
         };
 
+        self.auto_resolve_record_judge(&decision);
         let status = decision.status.to_ascii_lowercase();
         let rationale = decision.rationale.unwrap_or_default();
 
@@ -434,6 +594,52 @@ impl ChatWidget<'_> {
         }
     }
 
+    /// Keeps the judge's full reasoning in history so users can audit why
+    /// automation continued or declared success.
+    fn auto_resolve_record_judge(&mut self, decision: &AutoResolveDecision) {
+        // The first line of a notice becomes its (hidden) header.
+        let mut lines: Vec<RtLine<'static>> = vec![
+            RtLine::from("auto review"),
+            RtLine::from(RtSpan::styled(
+                format!("Auto-resolve judge: {}", decision.status.trim()),
+                Style::default().add_modifier(Modifier::BOLD),
+            )),
+        ];
+        if let Some(rationale) = decision
+            .rationale
+            .as_deref()
+            .map(str::trim)
+            .filter(|text| !text.is_empty())
+        {
+            lines.push(RtLine::from(""));
+            lines.extend(rationale.lines().map(|line| RtLine::from(line.to_owned())));
+        }
+        if !decision.findings.is_empty() {
+            lines.push(RtLine::from(""));
+        }
+        for finding in &decision.findings {
+            let (label, style) = match finding.verdict {
+                AutoResolveVerdict::Fixed => ("fixed", crate::colors::style_success()),
+                AutoResolveVerdict::NotFixed => ("not fixed", crate::colors::style_warning()),
+                AutoResolveVerdict::Regressed => ("regressed", crate::colors::style_error()),
+                AutoResolveVerdict::Unknown => ("unknown", crate::colors::style_text_dim()),
+            };
+            let mut spans = vec![
+                RtSpan::styled(format!("{label:<9} "), style),
+                RtSpan::raw(finding.title.trim().to_owned()),
+            ];
+            if let Some(note) = finding.note.as_deref().map(str::trim).filter(|note| !note.is_empty()) {
+                spans.push(RtSpan::styled(format!(" — {note}"), crate::colors::style_text_dim()));
+            }
+            lines.push(RtLine::from(spans));
+        }
+        let state = history_cell::plain_message_state_from_lines(
+            lines,
+            history_cell::HistoryCellType::Notice,
+        );
+        self.history_push_plain_state(state);
+    }
+
     pub(in crate::chatwidget) fn auto_resolve_parse_decision(raw: &str) -> Option<AutoResolveDecision> {
         if let Ok(decision) = serde_json::from_str::<AutoResolveDecision>(raw) {
             return Some(decision);
@@ -1,4 +1,7 @@
 use std::fmt::Write as _;
+use std::path::Path;
+
+use code_file_search::CodeIgnore;
 
 use super::super::*;
 
@@ -62,6 +65,20 @@ impl ChatWidget<'_> {
         }
     }
 
+    /// Forgets every auto-review baseline after HEAD moved outside the
+    /// session; diffing against them would pull in the other branch's history.
+    pub(in crate::chatwidget) fn reset_auto_review_baselines(&mut self) {
+        self.auto_review_baseline = None;
+        self.auto_review_reviewed_marker = None;
+        self.pending_auto_review_range = None;
+        if let Some(path) = self.auto_review_baseline_path()
+            && let Err(err) = std::fs::remove_file(&path)
+            && err.kind() != std::io::ErrorKind::NotFound
+        {
+            tracing::warn!("failed to clear auto review baseline: {err}");
+        }
+    }
+
     pub(in crate::chatwidget) fn maybe_trigger_auto_review(&mut self) {
         if !self.config.tui.auto_review_enabled {
             return;
@@ -98,16 +115,57 @@ impl ChatWidget<'_> {
         };
 
         if let Some(base) = base_snapshot {
+            if self.auto_review_filters_paths() && !self.auto_review_has_changes_since(&base) {
+                tracing::debug!(
+                    "auto review skipped: turn changed no paths covered by [auto_review] or left out by .codeignore"
+                );
+                self.turn_had_code_edits = false;
+                return;
+            }
             self.launch_background_review(Some(base));
         }
     }
 
+    /// Whether some changed paths may not count towards an auto review,
+    /// because of `[auto_review]` globs or a `.codeignore` at the repo root.
+    fn auto_review_filters_paths(&self) -> bool {
+        self.config.auto_review.has_path_filter()
+            || self
+                .auto_review_git_root()
+                .is_some_and(|root| CodeIgnore::has_root_file(&root))
+    }
+
+    /// Whether the working tree changed since `reviewed`, counting only the
+    /// paths `[auto_review] include`/`exclude` cover and no `.codeignore`
+    /// leaves out.
     pub(in crate::chatwidget) fn auto_review_has_changes_since(&self, reviewed: &GhostCommit) -> bool {
+        let path_filter = if self.config.auto_review.has_path_filter() {
+            match self.config.auto_review.path_filter() {
+                Ok(filter) => Some(filter),
+                Err(err) => {
+                    tracing::warn!("ignoring invalid auto review path globs: {err}");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        let codeignore = self.auto_review_git_root().map(|root| CodeIgnore::new(&root));
+        let covered = |path: &str| {
+            path_filter.as_ref().is_none_or(|filter| filter.matches(path))
+                && codeignore
+                    .as_ref()
+                    .is_none_or(|codeignore| !codeignore.is_ignored(Path::new(path), false))
+        };
+
         let reviewed_id = reviewed.id();
         let tracked_changes = match self.run_git_command(
             ["diff", "--name-only", reviewed_id],
             |stdout| {
-                Ok(stdout.lines().any(|line| !line.trim().is_empty()))
+                Ok(stdout
+                    .lines()
+                    .map(str::trim)
+                    .any(|line| !line.is_empty() && covered(line)))
             },
         ) {
             Ok(changed) => changed,
@@ -143,13 +201,13 @@ impl ChatWidget<'_> {
         
 
         match self.run_git_command(
-            ["ls-files", "--others", "--exclude-standard"],
+            ["ls-files", "--others", "--exclude-standard", "--full-name"],
             |stdout| {
                 Ok(stdout
                     .lines()
                     .map(str::trim)
                     .filter(|line| !line.is_empty())
-                    .any(|path| !snapshot_paths.contains(path)))
+                    .any(|path| !snapshot_paths.contains(path) && covered(path)))
             },
         ) {
             Ok(changed) => changed,
@@ -250,6 +308,7 @@ impl ChatWidget<'_> {
             AutoReviewIndicatorStatus::Running,
             None,
             AutoReviewPhase::Reviewing,
+            0,
         );
 
         #[cfg(test)]
@@ -316,6 +375,7 @@ impl ChatWidget<'_> {
                     AutoReviewIndicatorStatus::Running,
                     findings,
                     phase,
+                    0,
                 );
                 continue;
             }
@@ -327,6 +387,7 @@ impl ChatWidget<'_> {
                     status: state.status,
                     findings: state.findings,
                     phase,
+                    below_threshold: state.below_threshold,
                 }));
             }
 
@@ -365,7 +426,16 @@ impl ChatWidget<'_> {
                 (worktree_path, branch, None)
             };
 
-            let (has_findings, findings, summary) = Self::parse_agent_review_result(agent.result.as_deref());
+            let (mut has_findings, mut findings, summary) =
+                Self::parse_agent_review_result(agent.result.as_deref());
+            let below_threshold = Self::findings_below_threshold(
+                agent.result.as_deref(),
+                &self.config.auto_review,
+            );
+            if below_threshold > 0 {
+                findings = findings.saturating_sub(below_threshold);
+                has_findings = findings > 0;
+            }
 
             self.processed_auto_review_agents.insert(agent.id.clone());
             self.on_background_review_finished(BackgroundReviewFinishedEvent {
@@ -373,6 +443,7 @@ impl ChatWidget<'_> {
                 branch,
                 has_findings,
                 findings,
+                below_threshold,
                 summary,
                 error: agent.error.clone(),
                 agent_id: Some(agent.id.clone()),
@@ -471,6 +542,46 @@ impl ChatWidget<'_> {
         )
     }
 
+    /// Number of findings in the counted pass (the same one
+    /// `parse_agent_review_result` reports) that fall outside the
+    /// `[auto_review]` thresholds. Only structured JSON results are inspected.
+    pub(in crate::chatwidget) fn findings_below_threshold(
+        raw: Option<&str>,
+        thresholds: &code_core::config_types::AutoReviewConfig,
+    ) -> usize {
+        #[derive(serde::Deserialize)]
+        struct MultiRunReview {
+            #[serde(flatten)]
+            latest: ReviewOutputEvent,
+            #[serde(default)]
+            runs: Vec<ReviewOutputEvent>,
+        }
+
+        if !thresholds.has_threshold() {
+            return 0;
+        }
+        let Some(text) = raw.map(str::trim).filter(|text| !text.is_empty()) else {
+            return 0;
+        };
+        // A bare `ReviewOutputEvent` also parses here, with no runs.
+        let Ok(wrapper) = serde_json::from_str::<MultiRunReview>(text) else {
+            return 0;
+        };
+        let runs = if wrapper.runs.is_empty() {
+            vec![wrapper.latest]
+        } else {
+            wrapper.runs
+        };
+        runs.iter()
+            .rfind(|run| !run.findings.is_empty())
+            .map_or(0, |run| {
+                run.findings
+                    .iter()
+                    .filter(|finding| !thresholds.accepts(finding.priority, finding.confidence_score))
+                    .count()
+            })
+    }
+
     fn extract_review_from_mixed_text(text: &str) -> Option<(bool, usize, Option<String>)> {
         #[derive(serde::Deserialize)]
         struct MultiRunReview {
@@ -632,11 +743,13 @@ impl ChatWidget<'_> {
         status: AutoReviewIndicatorStatus,
         findings: Option<usize>,
         phase: AutoReviewPhase,
+        below_threshold: usize,
     ) {
         let state = AutoReviewStatus {
             status,
             findings,
             phase,
+            below_threshold,
         };
         self.auto_review_status = Some(state);
         self.bottom_pane
@@ -644,6 +757,7 @@ impl ChatWidget<'_> {
                 status,
                 findings,
                 phase,
+                below_threshold,
             }));
         self.request_redraw();
     }
@@ -756,6 +870,7 @@ impl ChatWidget<'_> {
                 AutoReviewIndicatorStatus::Running,
                 None,
                 AutoReviewPhase::Reviewing,
+                0,
             );
         }
         // Ensure the main status spinner is cleared once the foreground turn ends;
@@ -775,6 +890,7 @@ impl ChatWidget<'_> {
             branch,
             has_findings,
             findings,
+            below_threshold,
             summary,
             error,
             agent_id,
@@ -837,6 +953,12 @@ impl ChatWidget<'_> {
                 note.push('\n');
                 note.push_str(&summary_note);
             }
+            if below_threshold > 0 {
+                let _ = write!(
+                    note,
+                    "\n{below_threshold} more finding(s) fell below the [auto_review] priority/confidence thresholds; they are listed in the review but were not counted or auto-resolved."
+                );
+            }
             developer_note = Some(note);
             AutoReviewIndicatorStatus::Fixed
         } else {
@@ -848,7 +970,13 @@ impl ChatWidget<'_> {
         let phase = self
             .auto_review_status
             .map_or(AutoReviewPhase::Reviewing, |s| s.phase);
-        self.set_auto_review_indicator(indicator_status, findings_for_indicator, phase);
+        let below_threshold_for_indicator = if errored { 0 } else { below_threshold };
+        self.set_auto_review_indicator(
+            indicator_status,
+            findings_for_indicator,
+            phase,
+            below_threshold_for_indicator,
+        );
         if matches!(indicator_status, AutoReviewIndicatorStatus::Fixed) {
             self.insert_auto_review_notice(
                 &branch,
@@ -325,6 +325,19 @@ impl ChatWidget<'_> {
     }
 
     pub(crate) fn handle_review_command(&mut self, args: String) {
+        if args.trim() == "--findings" {
+            let awaiting_selection = matches!(
+                self.auto_resolve_state.as_ref().map(|state| &state.phase),
+                Some(AutoResolvePhase::AwaitingSelection { .. })
+            );
+            if awaiting_selection {
+                self.show_auto_resolve_finding_picker();
+            } else {
+                self.show_review_findings_picker();
+            }
+            return;
+        }
+
         if self.is_task_running() {
             self.history_push_plain_state(crate::history_cell::new_error_event(
                 "`/review` — complete or cancel the current task before starting a new review.".to_owned(),
@@ -333,7 +346,19 @@ impl ChatWidget<'_> {
             return;
         }
 
-        let trimmed = args.trim();
+        let (profile, rest) = code_core::review_profiles::split_profile_flag(&args);
+        if let Some(name) = profile.as_deref()
+            && !self.config.review_profiles.contains_key(name)
+        {
+            self.history_push_plain_state(crate::history_cell::new_error_event(format!(
+                "`/review` — unknown review profile `{name}`. Define it under [review_profiles.{name}] in config.toml."
+            )));
+            self.request_redraw();
+            return;
+        }
+        self.review_profile_override = profile;
+
+        let trimmed = rest.as_str();
         let auto_resolve = self.config.tui.review_auto_resolve;
         if trimmed.is_empty() {
             if Self::is_branch_worktree_path(&self.config.cwd)
@@ -473,6 +498,7 @@ impl ChatWidget<'_> {
             target,
             prompt,
             user_facing_hint: hint,
+            profile: self.review_profile_override.clone(),
         };
         match try_acquire_lock("review", &self.config.cwd) {
             Ok(Some(guard)) => {
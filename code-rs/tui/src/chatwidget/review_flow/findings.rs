@@ -0,0 +1,269 @@
+//! Navigating from review results to the code they point at.
+//!
+//! When a review finishes with findings, a picker lists them under the
+//! summary cell with their location and priority. Findings can be checked and
+//! fixed one turn each, expanded to preview the referenced code, or opened in
+//! the diff viewer in findings mode: one tab per finding showing its body next
+//! to the referenced file region, read fresh from disk, with `f` offering a
+//! targeted "fix just this" prompt.
+
+use super::super::*;
+use crate::bottom_pane::panes::review_findings::ReviewFindingRow;
+use code_core::protocol::ReviewFinding;
+
+/// Lines of surrounding code shown above and below a finding's range.
+const FINDING_CONTEXT_LINES: usize = 3;
+
+impl ChatWidget<'_> {
+    /// Keeps the findings of the review that just finished and, for manual
+    /// reviews, offers them in a picker.
+    pub(in crate::chatwidget) fn remember_review_findings(&mut self, findings: &[ReviewFinding]) {
+        self.review_findings = findings.to_vec();
+        if findings.is_empty() || self.auto_resolve_enabled() || self.auto_state.is_active() {
+            return;
+        }
+        self.show_review_findings_picker();
+    }
+
+    pub(crate) fn show_review_findings_picker(&mut self) {
+        if self.review_findings.is_empty() {
+            self.bottom_pane
+                .flash_footer_notice("No review findings to show");
+            self.request_redraw();
+            return;
+        }
+
+        let cwd = &self.config.cwd;
+        let rows = self
+            .review_findings
+            .iter()
+            .map(|finding| ReviewFindingRow {
+                title: finding.title.trim().to_owned(),
+                location: finding_location(finding, cwd),
+                priority: finding.priority,
+                preview: finding_preview(finding, cwd),
+            })
+            .collect();
+        self.bottom_pane.show_review_findings(rows);
+    }
+
+    /// Queues a "fix just this" turn per picked finding and starts the first
+    /// one when the conversation is idle.
+    pub(crate) fn fix_review_findings(&mut self, indices: &[usize]) {
+        let prompts: Vec<String> = indices
+            .iter()
+            .filter_map(|index| self.review_findings.get(*index))
+            .map(finding_fix_prompt)
+            .collect();
+        if prompts.is_empty() {
+            return;
+        }
+        if prompts.len() > 1 {
+            self.bottom_pane.flash_footer_notice(format!(
+                "Fixing {} findings, one turn each",
+                prompts.len()
+            ));
+        }
+        self.pending_review_finding_fixes.extend(prompts);
+        self.dispatch_next_review_finding_fix();
+    }
+
+    /// Sends the next queued finding fix once nothing else is running or
+    /// waiting to be sent.
+    pub(in crate::chatwidget) fn dispatch_next_review_finding_fix(&mut self) {
+        if self.is_task_running()
+            || !self.active_task_ids.is_empty()
+            || !self.queued_user_messages.is_empty()
+        {
+            return;
+        }
+        if let Some(prompt) = self.pending_review_finding_fixes.pop_front() {
+            self.submit_user_message(prompt.into());
+        }
+    }
+
+    pub(crate) fn open_review_finding(&mut self, index: usize) {
+        if index >= self.review_findings.len() {
+            return;
+        }
+        let cwd = self.config.cwd.clone();
+        let tabs = self
+            .review_findings
+            .iter()
+            .enumerate()
+            .map(|(idx, finding)| finding_tab(idx, finding, &cwd))
+            .collect();
+        let fix_prompts = self.review_findings.iter().map(finding_fix_prompt).collect();
+        self.diffs.overlay = Some(DiffOverlay::review_findings(tabs, fix_prompts, index));
+        self.diffs.confirm = None;
+        self.request_redraw();
+    }
+}
+
+fn resolve_finding_path(finding: &ReviewFinding, cwd: &Path) -> PathBuf {
+    let path = &finding.code_location.absolute_file_path;
+    if path.is_absolute() {
+        path.clone()
+    } else {
+        cwd.join(path)
+    }
+}
+
+pub(super) fn finding_location(finding: &ReviewFinding, cwd: &Path) -> String {
+    let path = resolve_finding_path(finding, cwd);
+    let shown = path.strip_prefix(cwd).unwrap_or(&path).display().to_string();
+    let range = &finding.code_location.line_range;
+    if range.end > range.start {
+        format!("{shown}:{}-{}", range.start, range.end)
+    } else {
+        format!("{shown}:{}", range.start)
+    }
+}
+
+fn finding_tab(index: usize, finding: &ReviewFinding, cwd: &Path) -> (String, Vec<DiffBlock>) {
+    let location = finding_location(finding, cwd);
+    let mut header = vec![RtLine::from(RtSpan::styled(
+        finding.title.trim().to_owned(),
+        crate::colors::style_text_bold(),
+    ))];
+    header.push(RtLine::from(RtSpan::styled(
+        format!(
+            "{location} · priority P{} · confidence {:.1}",
+            finding.priority, finding.confidence_score
+        ),
+        crate::colors::style_text_dim(),
+    )));
+    header.push(RtLine::default());
+    header.extend(
+        finding
+            .body
+            .trim()
+            .lines()
+            .map(|line| RtLine::from(line.to_owned())),
+    );
+    header.push(RtLine::default());
+
+    let path = resolve_finding_path(finding, cwd);
+    let region = finding_preview(finding, cwd);
+
+    let label = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .map_or_else(|| location.clone(), ToString::to_string);
+    let line = finding.code_location.line_range.start;
+    (
+        format!("{}. {label}:{line}", index + 1),
+        vec![DiffBlock { lines: header }, DiffBlock { lines: region }],
+    )
+}
+
+/// The referenced region as shown when a picker row is expanded.
+fn finding_preview(finding: &ReviewFinding, cwd: &Path) -> Vec<RtLine<'static>> {
+    let path = resolve_finding_path(finding, cwd);
+    match std::fs::read_to_string(&path) {
+        Ok(contents) => render_finding_region(&contents, &finding.code_location.line_range),
+        Err(err) => vec![RtLine::from(RtSpan::styled(
+            format!("Could not read {}: {err}", path.display()),
+            crate::colors::style_error(),
+        ))],
+    }
+}
+
+/// Renders the referenced lines with a little context, marking the lines the
+/// finding covers.
+fn render_finding_region(
+    contents: &str,
+    range: &code_core::protocol::ReviewLineRange,
+) -> Vec<RtLine<'static>> {
+    let lines: Vec<&str> = contents.lines().collect();
+    if lines.is_empty() {
+        return vec![RtLine::from(RtSpan::styled(
+            "(file is empty)".to_owned(),
+            crate::colors::style_text_dim(),
+        ))];
+    }
+    let start = (range.start.max(1) as usize).min(lines.len());
+    let end = (range.end as usize).clamp(start, lines.len());
+    let first = start.saturating_sub(FINDING_CONTEXT_LINES).max(1);
+    let last = (end + FINDING_CONTEXT_LINES).min(lines.len());
+    let number_width = last.to_string().len();
+
+    (first..=last)
+        .map(|number| {
+            let in_range = (start..=end).contains(&number);
+            let marker = if in_range { "▌" } else { " " };
+            let text_style = if in_range {
+                crate::colors::style_text()
+            } else {
+                crate::colors::style_text_dim()
+            };
+            RtLine::from(vec![
+                RtSpan::styled(marker.to_owned(), crate::colors::style_warning()),
+                RtSpan::styled(
+                    format!("{number:>number_width$} "),
+                    crate::colors::style_text_dim(),
+                ),
+                RtSpan::styled(lines[number - 1].to_owned(), text_style),
+            ])
+        })
+        .collect()
+}
+
+fn finding_fix_prompt(finding: &ReviewFinding) -> String {
+    let path = finding.code_location.absolute_file_path.display();
+    let range = &finding.code_location.line_range;
+    let title = finding.title.trim();
+    let body = finding.body.trim();
+    format!(
+        "Please fix just this review finding. Keep the change minimal and leave unrelated code and other findings alone.\n\n{title}\nLocation: {path}:{}-{}\n\n{body}",
+        range.start, range.end
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use code_core::protocol::ReviewCodeLocation;
+    use code_core::protocol::ReviewLineRange;
+
+    fn line_text(line: &RtLine<'_>) -> String {
+        line.spans.iter().map(|span| span.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn region_marks_finding_lines_with_context() {
+        let contents = (1..=10).map(|n| format!("line {n}")).collect::<Vec<_>>().join("\n");
+        let rendered = render_finding_region(&contents, &ReviewLineRange { start: 5, end: 6 });
+        let text: Vec<String> = rendered.iter().map(line_text).collect();
+        assert_eq!(text.first().map(String::as_str), Some("  2 line 2"));
+        assert_eq!(text.last().map(String::as_str), Some("  9 line 9"));
+        assert!(text.contains(&"▌ 5 line 5".to_owned()));
+        assert!(text.contains(&"▌ 6 line 6".to_owned()));
+        assert!(text.contains(&"  7 line 7".to_owned()));
+    }
+
+    #[test]
+    fn region_clamps_out_of_range_lines() {
+        let rendered = render_finding_region("only\n", &ReviewLineRange { start: 40, end: 41 });
+        let text: Vec<String> = rendered.iter().map(line_text).collect();
+        assert_eq!(text, vec!["▌1 only".to_owned()]);
+    }
+
+    #[test]
+    fn fix_prompt_targets_a_single_finding() {
+        let finding = ReviewFinding {
+            title: "[P1] Off-by-one in pagination".to_owned(),
+            body: "The last page is skipped.".to_owned(),
+            confidence_score: 0.8,
+            priority: 1,
+            code_location: ReviewCodeLocation {
+                absolute_file_path: PathBuf::from("/repo/src/page.rs"),
+                line_range: ReviewLineRange { start: 12, end: 14 },
+            },
+        };
+        let prompt = finding_fix_prompt(&finding);
+        assert!(prompt.starts_with("Please fix just this review finding."));
+        assert!(prompt.contains("Location: /repo/src/page.rs:12-14"));
+        assert!(prompt.ends_with("The last page is skipped."));
+    }
+}
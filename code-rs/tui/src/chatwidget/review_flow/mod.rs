@@ -3,3 +3,4 @@ mod auto_resolve;
 mod auto_review;
 mod pickers;
 mod entrypoints;
+mod findings;
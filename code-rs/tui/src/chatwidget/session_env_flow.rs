@@ -0,0 +1,114 @@
+//! `/cd` and `/env`: move the session to another directory or adjust the
+//! command environment without restarting. Both reconfigure the running
+//! session, which keeps the conversation and refreshes the environment
+//! context sent with the next turn.
+
+use super::*;
+use code_core::slash_commands::EnvCommand;
+
+impl ChatWidget<'_> {
+    pub(crate) fn handle_cd_command(&mut self, args: &str) {
+        if args.trim().is_empty() {
+            self.history_push_plain_paragraphs(
+                crate::history::state::PlainMessageKind::Notice,
+                vec![format!("Working directory: {}", self.config.cwd.display())],
+            );
+            return;
+        }
+        if self.reject_reconfigure_while_running("/cd") {
+            return;
+        }
+        match code_core::slash_commands::resolve_cd_target(&self.config.cwd, args) {
+            Ok(target) if target == self.config.cwd => {
+                self.debug_notice(format!("Already in {}", target.display()));
+            }
+            // Same path as /branch and the header picker: updates the app and
+            // session cwd and tells the model where to run commands from.
+            Ok(target) => self.app_event_tx.send(AppEvent::SwitchCwd(target, None)),
+            Err(message) => {
+                self.history_push_plain_state(history_cell::new_error_event(message));
+            }
+        }
+    }
+
+    pub(crate) fn handle_env_command(&mut self, args: &str) {
+        let command = match code_core::slash_commands::parse_env_command(args) {
+            Ok(command) => command,
+            Err(message) => {
+                self.history_push_plain_state(history_cell::new_error_event(message));
+                return;
+            }
+        };
+        let (notice, agent_note) = match command {
+            EnvCommand::List => {
+                let mut vars: Vec<_> = self.config.shell_environment_policy.r#set.iter().collect();
+                vars.sort();
+                let lines = if vars.is_empty() {
+                    vec!["No environment variables are set for commands.".to_owned()]
+                } else {
+                    std::iter::once("Environment variables set for commands:".to_owned())
+                        .chain(vars.into_iter().map(|(name, value)| format!("  {name}={value}")))
+                        .collect()
+                };
+                self.history_push_plain_paragraphs(
+                    crate::history::state::PlainMessageKind::Notice,
+                    lines,
+                );
+                return;
+            }
+            EnvCommand::Set { name, value } => {
+                if self.reject_reconfigure_while_running("/env") {
+                    return;
+                }
+                let notice = format!("Set {name}={value} for subsequent commands");
+                let note = format!(
+                    "System: The user set the environment variable {name}={value} for all subsequent commands in this session."
+                );
+                self.config
+                    .shell_environment_policy
+                    .r#set
+                    .insert(name, value);
+                (notice, note)
+            }
+            EnvCommand::Unset { name } => {
+                if self.reject_reconfigure_while_running("/env") {
+                    return;
+                }
+                if self
+                    .config
+                    .shell_environment_policy
+                    .r#set
+                    .remove(&name)
+                    .is_none()
+                {
+                    self.debug_notice(format!("{name} is not set by this session"));
+                    return;
+                }
+                (
+                    format!("Unset {name} for subsequent commands"),
+                    format!(
+                        "System: The user removed the environment variable {name} from subsequent commands in this session."
+                    ),
+                )
+            }
+        };
+        self.submit_op(self.current_configure_session_op());
+        self.queue_agent_note(agent_note);
+        self.history_push_plain_paragraphs(
+            crate::history::state::PlainMessageKind::Notice,
+            vec![notice],
+        );
+        self.request_redraw();
+    }
+
+    /// Reconfiguring aborts the running turn, so these commands wait for it.
+    fn reject_reconfigure_while_running(&mut self, command: &str) -> bool {
+        if !self.is_task_running() {
+            return false;
+        }
+        self.history_push_plain_state(history_cell::new_error_event(format!(
+            "{command} is unavailable while a task is running; wait for it to finish or press Esc to interrupt."
+        )));
+        true
+    }
+}
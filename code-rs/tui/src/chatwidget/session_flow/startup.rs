@@ -116,6 +116,7 @@ impl ChatWidget<'_> {
             ),
             total_token_usage: TokenUsage::default(),
             last_token_usage: TokenUsage::default(),
+            turn_sources: code_core::citations::SourceTracker::default(),
             rate_limit_snapshot: None,
             rate_limit_warnings: RateLimitWarningState::default(),
             rate_limit_fetch_inflight: false,
@@ -181,6 +182,9 @@ impl ChatWidget<'_> {
             suppress_next_agent_hint: false,
             active_review_hint: None,
             active_review_prompt: None,
+            review_profile_override: None,
+            review_findings: Vec::new(),
+            pending_review_finding_fixes: VecDeque::new(),
             auto_resolve_state: None,
             auto_resolve_attempts_baseline: config.auto_drive.auto_resolve_review_attempts.get(),
             turn_had_code_edits: false,
@@ -223,6 +227,8 @@ impl ChatWidget<'_> {
                 body_visible_rows: std::cell::Cell::new(0),
             },
             help: HelpState::default(),
+            history_search: HistorySearchState::default(),
+            bookmark_list: BookmarkListState::default(),
             settings: SettingsState::default(),
             limits: LimitsState::default(),
             terminal: TerminalState::default(),
@@ -234,6 +240,7 @@ impl ChatWidget<'_> {
             pending_git_init_resume: None,
             git_init_inflight: false,
             git_init_declined: false,
+            project_trust_prompted: false,
             pending_upgrade_notice: None,
             history_render: HistoryRenderState::new(),
             last_render_settings: Cell::new(RenderSettings::new(0, 0, false)),
@@ -277,6 +284,7 @@ impl ChatWidget<'_> {
             queued_user_messages: std::collections::VecDeque::new(),
             pending_dispatched_user_messages: std::collections::VecDeque::new(),
             pending_user_prompts_for_next_turn: 0,
+            interrupt_next_user_message: false,
             queue_block_started_at: None,
             ghost_snapshots: Vec::new(),
             ghost_snapshots_disabled: false,
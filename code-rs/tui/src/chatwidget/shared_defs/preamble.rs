@@ -422,6 +422,28 @@ struct AutoResolveDecision {
     status: String,
     #[serde(default)]
     rationale: Option<String>,
+    /// Per-finding outcome of the fix attempt, as judged by the model.
+    #[serde(default)]
+    findings: Vec<AutoResolveFindingVerdict>,
+}
+
+#[derive(Deserialize)]
+struct AutoResolveFindingVerdict {
+    #[serde(default)]
+    title: String,
+    verdict: AutoResolveVerdict,
+    #[serde(default)]
+    note: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum AutoResolveVerdict {
+    Fixed,
+    NotFixed,
+    Regressed,
+    #[serde(other)]
+    Unknown,
 }
 
 const AGENTS_OVERVIEW_STATIC_ROWS: usize = 2; // spacer + "Add new agent" row
@@ -465,6 +487,7 @@ struct AutoReviewStatus {
     status: AutoReviewIndicatorStatus,
     findings: Option<usize>,
     phase: AutoReviewPhase,
+    below_threshold: usize,
 }
 
 fn detect_auto_review_phase(progress: Option<&str>) -> AutoReviewPhase {
@@ -543,8 +566,45 @@ async fn remove_worktree_path(git_root: &Path, path: &Path) -> Result<(), String
     Ok(())
 }
 
+fn auto_review_worktree_pool(git_root: &Path) -> WorktreePool {
+    WorktreePool::new(
+        code_core::git_worktree::branches_dir(git_root),
+        AUTO_REVIEW_FALLBACK_PREFIX,
+        AUTO_REVIEW_FALLBACK_MAX,
+    )
+}
+
+/// Checkout options for auto-review worktrees. With `[review.worktree]
+/// sparse`, only the directories the snapshot touched plus `context_paths`
+/// are checked out.
+fn auto_review_checkout_options(
+    settings: &code_core::config_types::ReviewWorktreeConfig,
+    git_root: &Path,
+    snapshot_id: &str,
+) -> WorktreeCheckoutOptions {
+    let sparse_dirs = settings.sparse.then(|| {
+        match code_git_tooling::changed_dirs(git_root, snapshot_id) {
+            Ok(mut dirs) => {
+                dirs.extend(settings.context_paths.iter().map(PathBuf::from));
+                dirs.sort();
+                dirs.dedup();
+                Some(dirs)
+            }
+            Err(err) => {
+                tracing::warn!("falling back to a full review checkout: {err}");
+                None
+            }
+        }
+    });
+    WorktreeCheckoutOptions {
+        keep_gitignored: true,
+        sparse_dirs: sparse_dirs.flatten(),
+    }
+}
+
 async fn cleanup_fallback_worktrees(git_root: &Path) -> Result<(), String> {
-    let branches_dir = auto_review_branches_dir(git_root)?;
+    let branches_dir = code_core::git_worktree::branches_dir(git_root);
+    let pool = auto_review_worktree_pool(git_root);
     let mut entries: Vec<(PathBuf, SystemTime)> = Vec::new();
     if let Ok(read_dir) = fs::read_dir(&branches_dir) {
         for entry in read_dir.flatten() {
@@ -556,6 +616,14 @@ async fn cleanup_fallback_worktrees(git_root: &Path) -> Result<(), String> {
             if !name.starts_with(AUTO_REVIEW_FALLBACK_PREFIX) || name == AUTO_REVIEW_SHARED_WORKTREE {
                 continue;
             }
+            // Per-snapshot worktrees from before the pool are never reused.
+            if !pool.is_slot(&name) {
+                if let Ok(Some(g)) = try_acquire_lock("review-fallback", &path) {
+                    drop(g);
+                    let _ = remove_worktree_path(git_root, &path).await;
+                }
+                continue;
+            }
             let meta = entry.metadata().ok();
             let mtime = meta
                 .and_then(|m| m.modified().ok())
@@ -564,7 +632,7 @@ async fn cleanup_fallback_worktrees(git_root: &Path) -> Result<(), String> {
         }
     }
 
-    // Age-based prune
+    // Age-based prune; the pool's capacity already bounds the count.
     let now = SystemTime::now();
     for (path, mtime) in &entries {
         if let Ok(elapsed) = now.duration_since(*mtime)
@@ -575,59 +643,41 @@ async fn cleanup_fallback_worktrees(git_root: &Path) -> Result<(), String> {
                 }
     }
 
-    // Count-based prune
-    let mut remaining: Vec<(PathBuf, SystemTime)> = entries
-        .into_iter()
-        .filter(|(p, _)| p.exists())
-        .collect();
-    remaining.sort_by_key(|(_, t)| *t);
-    while remaining.len() > AUTO_REVIEW_FALLBACK_MAX {
-        if let Some((path, _)) = remaining.first().cloned() {
-            if let Ok(Some(g)) = try_acquire_lock("review-fallback", &path) {
-                drop(g);
-                let _ = remove_worktree_path(git_root, &path).await;
-                remaining.remove(0);
-            } else {
-                // Busy; skip pruning this one
-                break;
-            }
-        }
-    }
-
     Ok(())
 }
 
 async fn allocate_fallback_auto_review_worktree(
     git_root: &Path,
     snapshot_id: &str,
+    options: WorktreeCheckoutOptions,
 ) -> Result<(PathBuf, String, ReviewGuard), String> {
     cleanup_fallback_worktrees(git_root).await?;
-    let branches_dir = auto_review_branches_dir(git_root)?;
-    let short = &snapshot_id[..snapshot_id.len().min(8)];
-
-    for attempt in 0..AUTO_REVIEW_FALLBACK_MAX {
-        let suffix = if attempt == 0 { String::new() } else { format!("-{}", attempt + 1) };
-        let name = format!("{AUTO_REVIEW_FALLBACK_PREFIX}{short}{suffix}");
-        let path = branches_dir.join(&name);
-
-        match try_acquire_lock("review-fallback", &path) {
-            Ok(Some(guard)) => {
-                let worktree_path = code_core::git_worktree::prepare_reusable_worktree(
-                    git_root,
-                    &name,
-                    snapshot_id,
-                    true,
-                )
-                .await
-                .map_err(|e| format!("failed to prepare fallback worktree: {e}"))?;
-                return Ok((worktree_path, name, guard));
-            }
-            Ok(None) => {} // in use, try next suffix
-            Err(err) => return Err(format!("could not acquire fallback review lock: {err}")),
-        }
-    }
-
-    Err("Auto review fallback pool is busy; try again soon.".to_owned())
+    let pool = auto_review_worktree_pool(git_root);
+    let repo = git_root.to_path_buf();
+    let base = snapshot_id.to_owned();
+    let checkout = tokio::task::spawn_blocking(move || {
+        pool.checkout(&repo, &base, &options, |path| {
+            try_acquire_lock("review-fallback", path).unwrap_or_else(|err| {
+                tracing::warn!("could not acquire fallback review lock for {}: {err}", path.display());
+                None
+            })
+        })
+    })
+    .await
+    .map_err(|e| format!("failed to spawn worktree task: {e}"))?
+    .map_err(|e| format!("failed to prepare fallback worktree: {e}"))?;
+
+    let Some((worktree, guard)) = checkout else {
+        return Err("Auto review fallback pool is busy; try again soon.".to_owned());
+    };
+    bump_snapshot_epoch_for(&worktree.path);
+    tracing::info!(
+        "auto review worktree {} {} in {} ms",
+        worktree.name,
+        if worktree.reused { "reset" } else { "created" },
+        worktree.setup_time.as_millis()
+    );
+    Ok((worktree.path, worktree.name, guard))
 }
 
 #[derive(Clone, Debug)]
@@ -784,6 +834,8 @@ pub(crate) struct ChatWidget<'a> {
     initial_user_message: Option<UserMessage>,
     total_token_usage: TokenUsage,
     last_token_usage: TokenUsage,
+    /// Web sources returned by this turn's tools, matched against the answer.
+    turn_sources: code_core::citations::SourceTracker,
     rate_limit_snapshot: Option<RateLimitSnapshotEvent>,
     rate_limit_warnings: RateLimitWarningState,
     rate_limit_fetch_inflight: bool,
@@ -861,6 +913,14 @@ pub(crate) struct ChatWidget<'a> {
     suppress_next_agent_hint: bool,
     active_review_hint: Option<String>,
     active_review_prompt: Option<String>,
+    /// Reviewer profile picked with `/review --profile`; `None` uses the
+    /// configured default.
+    review_profile_override: Option<String>,
+    /// Findings of the last completed review, for the findings picker.
+    review_findings: Vec<code_core::protocol::ReviewFinding>,
+    /// Fix prompts for findings picked in the findings picker, each sent as
+    /// its own turn once the previous one finishes.
+    pending_review_finding_fixes: VecDeque<String>,
     auto_resolve_state: Option<AutoResolveState>,
     auto_resolve_attempts_baseline: u32,
     turn_had_code_edits: bool,
@@ -902,6 +962,8 @@ pub(crate) struct ChatWidget<'a> {
 
     // Help overlay state
     help: HelpState,
+    history_search: HistorySearchState,
+    bookmark_list: BookmarkListState,
 
     // Settings overlay state
     settings: SettingsState,
@@ -928,6 +990,7 @@ pub(crate) struct ChatWidget<'a> {
     pending_git_init_resume: Option<GitInitResume>,
     git_init_inflight: bool,
     git_init_declined: bool,
+    project_trust_prompted: bool,
 
     pending_upgrade_notice: Option<(u64, String)>,
 
@@ -970,6 +1033,9 @@ pub(crate) struct ChatWidget<'a> {
     // a new turn; used to anchor the next turn window so assistant output
     // appears after them.
     pending_user_prompts_for_next_turn: usize,
+    // Set by Alt+Enter: the next message submitted during a running turn
+    // interrupts it (`Op::UserInput`) instead of being queued.
+    interrupt_next_user_message: bool,
     ghost_snapshots: Vec<GhostSnapshot>,
     ghost_snapshots_disabled: bool,
     ghost_snapshots_disabled_reason: Option<GhostSnapshotsDisabledReason>,
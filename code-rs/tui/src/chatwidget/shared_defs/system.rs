@@ -43,6 +43,9 @@ pub(crate) struct BackgroundReviewFinishedEvent {
     pub(crate) branch: String,
     pub(crate) has_findings: bool,
     pub(crate) findings: usize,
+    /// Findings outside the `[auto_review]` thresholds, not included in
+    /// `findings`.
+    pub(crate) below_threshold: usize,
     pub(crate) summary: Option<String>,
     pub(crate) error: Option<String>,
     pub(crate) agent_id: Option<String>,
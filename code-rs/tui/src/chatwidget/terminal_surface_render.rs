@@ -137,7 +137,8 @@ impl ChatWidget<'_> {
         use std::fs;
         use std::path::Path;
 
-        let head_path = self.config.cwd.join(".git/HEAD");
+        let head_path = code_core::git_info::resolve_git_dir(&self.config.cwd)
+            .map_or_else(|| self.config.cwd.join(".git/HEAD"), |git_dir| git_dir.join("HEAD"));
         let mut cache = self.git_branch_cache.borrow_mut();
         let now = Instant::now();
 
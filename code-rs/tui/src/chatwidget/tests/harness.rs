@@ -141,6 +141,7 @@ fn reset_history(chat: &mut ChatWidget<'_>) {
         last_fix_message: None,
         last_reviewed_commit: None,
         snapshot_epoch: None,
+        acknowledged_findings: Vec::new(),
     }
     }
     
@@ -232,6 +232,7 @@
             },
         ],
         order_debug: Vec::new(),
+        bookmarks: Vec::new(),
     };
     
     chat.restore_history_snapshot(&snapshot);
@@ -177,6 +177,27 @@
     assert!(summary_text.contains("Final pass reported no issues"));
     }
     
+    #[test]
+    fn findings_below_threshold_counts_filtered_findings() {
+    let json = r#"{
+        "findings": [
+            {"title": "crash", "body": "fix", "confidence_score": 0.9, "priority": 0, "code_location": {"absolute_file_path": "foo", "line_range": {"start":1,"end":1}}},
+            {"title": "nit", "body": "style", "confidence_score": 0.9, "priority": 3, "code_location": {"absolute_file_path": "foo", "line_range": {"start":2,"end":2}}},
+            {"title": "maybe", "body": "unsure", "confidence_score": 0.2, "priority": 1, "code_location": {"absolute_file_path": "foo", "line_range": {"start":3,"end":3}}}
+        ],
+        "overall_correctness": "incorrect",
+        "overall_explanation": "needs work",
+        "overall_confidence_score": 0.6
+    }"#;
+    let mut thresholds = code_core::config_types::AutoReviewConfig::default();
+    assert_eq!(ChatWidget::findings_below_threshold(Some(json), &thresholds), 0);
+    
+    thresholds.min_priority = Some(1);
+    thresholds.min_confidence = Some(0.5);
+    assert_eq!(ChatWidget::findings_below_threshold(Some(json), &thresholds), 2);
+    assert_eq!(ChatWidget::findings_below_threshold(Some("no issues"), &thresholds), 0);
+    }
+    
     #[test]
     fn parse_agent_review_result_skip_lock() {
     let text = "Another review is already running; skipping this /review.";
@@ -285,6 +306,65 @@
     assert!(chat.background_review.is_none());
     }
     
+    #[test]
+    fn auto_review_only_runs_for_paths_covered_by_include_exclude() {
+    let _stub_lock = AUTO_STUB_LOCK.lock().unwrap();
+    let _rt = enter_test_runtime_guard();
+    let calls = Arc::new(AtomicUsize::new(0));
+    let calls_clone = calls.clone();
+    let _guard = AutoReviewStubGuard::install(move || {
+        calls_clone.fetch_add(1, Ordering::SeqCst);
+    });
+    
+    let repo = tempdir().expect("temp repo");
+    let repo_path = repo.path();
+    let git = |args: &[&str]| {
+        let status = Command::new("git")
+            .current_dir(repo_path)
+            .args(args)
+            .status()
+            .expect("git command");
+        assert!(status.success(), "git command failed: {args:?}");
+    };
+    
+    git(&["init"]);
+    git(&["config", "user.email", "auto@review.test"]);
+    git(&["config", "user.name", "Auto Review"]);
+    std::fs::write(repo_path.join("Cargo.lock"), "v1").expect("write lock");
+    git(&["add", "."]);
+    git(&["commit", "-m", "init"]);
+    
+    let snapshot = create_ghost_commit(
+        &CreateGhostCommitOptions::new(repo_path).message("auto review snapshot"),
+    )
+    .expect("ghost snapshot");
+    
+    std::fs::write(repo_path.join("Cargo.lock"), "v2").expect("update lock");
+    std::fs::create_dir_all(repo_path.join("src/generated")).expect("create dirs");
+    std::fs::write(repo_path.join("src/generated/schema.rs"), "// generated")
+        .expect("write generated file");
+    std::fs::write(repo_path.join("notes.md"), "notes").expect("write notes");
+    
+    let mut harness = ChatWidgetHarness::new();
+    let chat = harness.chat();
+    chat.config.cwd = repo_path.to_path_buf();
+    chat.config.tui.auto_review_enabled = true;
+    chat.config.auto_review.include = vec!["src/**".to_string()];
+    chat.config.auto_review.exclude = vec!["**/generated/**".to_string(), "*.lock".to_string()];
+    chat.turn_had_code_edits = true;
+    chat.auto_review_baseline = Some(snapshot.clone());
+    chat.auto_review_reviewed_marker = Some(snapshot);
+    
+    chat.maybe_trigger_auto_review();
+    assert_eq!(calls.load(Ordering::SeqCst), 0, "uncovered changes should not start a review");
+    assert!(chat.background_review.is_none());
+    
+    std::fs::write(repo_path.join("src/lib.rs"), "pub fn covered() {}").expect("write src");
+    chat.turn_had_code_edits = true;
+    chat.maybe_trigger_auto_review();
+    assert_eq!(calls.load(Ordering::SeqCst), 1, "covered change should start a review");
+    }
+    
     #[test]
     fn task_started_defers_auto_review_baseline_capture() {
     let _stub_lock = AUTO_STUB_LOCK.lock().unwrap();
@@ -338,6 +418,7 @@
         branch: "auto-review-branch".to_string(),
         has_findings: true,
         findings: 2,
+        below_threshold: 0,
         summary: Some("Short summary".to_string()),
         error: None,
         agent_id: Some("agent-123".to_string()),
@@ -404,6 +485,7 @@
         branch: "auto-review-branch".to_string(),
         has_findings: true,
         findings: 1,
+        below_threshold: 0,
         summary: Some(review_json.to_string()),
         error: None,
         agent_id: Some("agent-123".to_string()),
@@ -638,6 +720,7 @@
         branch: "auto-review-running".to_string(),
         has_findings: true,
         findings: 2,
+        below_threshold: 0,
         summary: Some("found issues".to_string()),
         error: None,
         agent_id: Some("agent-running".to_string()),
@@ -705,6 +788,7 @@
         branch: "auto-review-running".to_string(),
         has_findings: false,
         findings: 0,
+        below_threshold: 0,
         summary: None,
         error: None,
         agent_id: Some("agent-running".to_string()),
@@ -773,6 +857,7 @@
         branch: "auto-review-running".to_string(),
         has_findings: false,
         findings: 0,
+        below_threshold: 0,
         summary: None,
         error: None,
         agent_id: Some("agent-running".to_string()),
@@ -1396,6 +1481,9 @@
         event_seq: 0,
         msg: EventMsg::Error(ErrorEvent {
             message: "internal error; agent loop died unexpectedly".to_string(),
+            codex_error_info: None,
+            code: None,
+            category: None,
         }),
         order: None,
     });
@@ -1810,6 +1898,64 @@
     assert!(chat.diffs.confirm.is_none(), "diff confirm should clear after Esc");
     }
     
+    #[test]
+    fn review_findings_open_in_viewer_and_offer_targeted_fix() {
+    let mut harness = ChatWidgetHarness::new();
+    let chat = harness.chat();
+    let dir = tempdir().expect("tempdir");
+    let source = dir.path().join("lib.rs");
+    std::fs::write(&source, "fn a() {}\nfn b() {\n    todo!()\n}\n").expect("write source");
+    
+    let finding = |title: &str, start: u32| code_core::protocol::ReviewFinding {
+        title: title.to_string(),
+        body: format!("{title} body"),
+        confidence_score: 0.9,
+        priority: 1,
+        code_location: code_core::protocol::ReviewCodeLocation {
+            absolute_file_path: source.clone(),
+            line_range: code_core::protocol::ReviewLineRange { start, end: start },
+        },
+    };
+    chat.handle_code_event(Event {
+        id: "review".to_string(),
+        event_seq: 0,
+        msg: EventMsg::ExitedReviewMode(code_core::protocol::ExitedReviewModeEvent {
+            review_output: Some(ReviewOutputEvent {
+                findings: vec![finding("First", 1), finding("Panics in b", 3)],
+                overall_correctness: "incorrect".to_string(),
+                overall_explanation: String::new(),
+                overall_confidence_score: 0.8,
+            }),
+            snapshot: None,
+        }),
+        order: None,
+    });
+    assert_eq!(chat.review_findings.len(), 2);
+    assert!(chat.bottom_pane.has_active_view(), "findings picker should be offered");
+    
+    chat.open_review_finding(1);
+    let overlay = chat.diffs.overlay.as_ref().expect("findings overlay");
+    assert!(overlay.is_review_findings());
+    assert_eq!(overlay.selected, 1);
+    let text: String = overlay.tabs[1]
+        .1
+        .iter()
+        .flat_map(|block| block.lines.iter())
+        .flat_map(|line| line.spans.iter().map(|span| span.content.to_string()))
+        .collect();
+    assert!(text.contains("Panics in b body"));
+    assert!(text.contains("todo!()"));
+    
+    // Undo/explain belong to the session diff viewer only.
+    chat.handle_key_event(KeyEvent::new(KeyCode::Char('u'), KeyModifiers::NONE));
+    assert!(chat.diffs.confirm.is_none());
+    
+    chat.handle_key_event(KeyEvent::new(KeyCode::Char('f'), KeyModifiers::NONE));
+    let confirm = chat.diffs.confirm.as_ref().expect("fix confirmation");
+    assert!(confirm.text_to_submit.contains("Panics in b"));
+    assert!(!confirm.text_to_submit.contains("First"));
+    }
+    
     #[test]
     fn esc_router_handles_agents_terminal_overlay() {
     let mut harness = ChatWidgetHarness::new();
@@ -2300,6 +2446,140 @@
     );
     }
     
+    fn two_priority_review() -> ReviewOutputEvent {
+    let finding = |title: &str, priority: i32| ReviewFinding {
+        title: title.to_string(),
+        body: "details".to_string(),
+        confidence_score: 0.8,
+        priority,
+        code_location: ReviewCodeLocation {
+            absolute_file_path: PathBuf::from("src/lib.rs"),
+            line_range: ReviewLineRange { start: 1, end: 1 },
+        },
+    };
+    ReviewOutputEvent {
+        findings: vec![finding("urgent", 1), finding("nit", 3)],
+        overall_correctness: "incorrect".to_string(),
+        overall_explanation: "needs follow up".to_string(),
+        overall_confidence_score: 0.8,
+    }
+    }
+    
+    #[test]
+    fn auto_resolve_pick_mode_fixes_only_chosen_findings() {
+    let _runtime_guard = enter_test_runtime_guard();
+    let mut harness = ChatWidgetHarness::new();
+    let chat = harness.chat();
+    
+    chat.config.tui.review_auto_resolve = true;
+    chat.config.auto_drive.auto_resolve_findings =
+        code_core::config_types::AutoResolveFindingSelection::Pick;
+    chat.config.auto_drive.auto_resolve_max_priority = Some(1);
+    
+    chat.start_review_with_scope(
+        ReviewTarget::UncommittedChanges,
+        "Review workspace".to_string(),
+        Some("workspace".to_string()),
+        Some("Preparing code review request...".to_string()),
+        true,
+    );
+    chat.auto_resolve_handle_review_enter();
+    chat.auto_resolve_handle_review_exit(Some(two_priority_review()));
+    
+    match chat.auto_resolve_state.as_ref().map(|state| &state.phase) {
+        Some(AutoResolvePhase::AwaitingSelection { selected, .. }) => {
+            assert_eq!(selected, &vec![true, false], "threshold pre-selects the P1 finding");
+        }
+        _ => panic!("pick mode should wait for a selection"),
+    }
+    assert!(
+        chat.auto_resolve_should_block_auto_resume(),
+        "auto drive must not resume while findings are being chosen"
+    );
+    
+    chat.toggle_auto_resolve_finding(0);
+    chat.toggle_auto_resolve_finding(1);
+    chat.confirm_auto_resolve_findings(true);
+    
+    let state = chat.auto_resolve_state.as_ref().expect("fix phase should start");
+    let review = match &state.phase {
+        AutoResolvePhase::PendingFix { review } | AutoResolvePhase::AwaitingFix { review } => review,
+        _ => panic!("confirming should hand the selection to the fix phase"),
+    };
+    let titles: Vec<&str> = review.findings.iter().map(|f| f.title.as_str()).collect();
+    assert_eq!(titles, vec!["nit"]);
+    let acknowledged: Vec<&str> = state
+        .acknowledged_findings
+        .iter()
+        .map(|f| f.title.as_str())
+        .collect();
+    assert_eq!(acknowledged, vec!["urgent"]);
+    }
+    
+    #[test]
+    fn auto_resolve_threshold_mode_reports_unfixed_findings() {
+    let _runtime_guard = enter_test_runtime_guard();
+    let mut harness = ChatWidgetHarness::new();
+    let chat = harness.chat();
+    
+    chat.config.tui.review_auto_resolve = true;
+    chat.config.auto_drive.auto_resolve_findings =
+        code_core::config_types::AutoResolveFindingSelection::Threshold;
+    chat.config.auto_drive.auto_resolve_max_priority = Some(0);
+    
+    chat.start_review_with_scope(
+        ReviewTarget::UncommittedChanges,
+        "Review workspace".to_string(),
+        Some("workspace".to_string()),
+        Some("Preparing code review request...".to_string()),
+        true,
+    );
+    chat.auto_resolve_handle_review_enter();
+    chat.auto_resolve_handle_review_exit(Some(two_priority_review()));
+    
+    assert!(
+        chat.auto_resolve_state.is_none(),
+        "nothing meets the threshold, so there is nothing to fix"
+    );
+    let report_present = chat.history_cells.iter().any(|cell| {
+        cell.display_lines_trimmed().iter().any(|line| {
+            line.spans
+                .iter()
+                .any(|span| span.content.contains("2 finding(s) acknowledged but not fixed"))
+        })
+    });
+    assert!(report_present, "unfixed findings should be listed in the final report");
+    }
+    
+    #[test]
+    fn auto_resolve_judge_rationale_and_verdicts_land_in_history() {
+    let _runtime_guard = enter_test_runtime_guard();
+    let mut harness = ChatWidgetHarness::new();
+    let chat = harness.chat();
+    
+    chat.auto_resolve_process_judge(
+        two_priority_review(),
+        r#"{"status":"no_issue","rationale":"Both call sites now validate input.","findings":[{"title":"urgent","verdict":"fixed"},{"title":"nit","verdict":"regressed","note":"new warning in lib.rs"}]}"#.to_string(),
+    );
+    
+    let judge_cell = chat
+        .history_cells
+        .iter()
+        .map(|cell| {
+            cell.display_lines_trimmed()
+                .iter()
+                .map(|line| line.spans.iter().map(|span| span.content.as_ref()).collect::<String>())
+                .collect::<Vec<_>>()
+        })
+        .find(|lines| lines.iter().any(|line| line.contains("Auto-resolve judge: no_issue")))
+        .expect("judge decision should get its own history cell");
+    assert!(judge_cell.iter().any(|line| line.contains("Both call sites now validate input.")));
+    assert!(judge_cell.iter().any(|line| line.contains("fixed") && line.contains("urgent")));
+    assert!(judge_cell
+        .iter()
+        .any(|line| line.contains("regressed") && line.contains("nit — new warning in lib.rs")));
+    }
+    
     #[test]
     fn auto_handle_decision_launches_cli_agents_and_review() {
     let mut harness = ChatWidgetHarness::new();
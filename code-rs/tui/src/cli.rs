@@ -39,6 +39,10 @@ pub struct Cli {
     #[arg(long = "sandbox", short = 's')]
     pub sandbox_mode: Option<code_common::SandboxModeCliArg>,
 
+    /// Apply a named `[sandbox_presets.<name>]` entry from config.toml.
+    #[arg(long = "sandbox-preset", value_name = "NAME")]
+    pub sandbox_preset: Option<String>,
+
     /// Configure when the model requires human approval before executing a command.
     #[arg(long = "ask-for-approval", short = 'a')]
     pub approval_policy: Option<ApprovalModeCliArg>,
@@ -96,6 +100,18 @@ pub struct Cli {
     #[arg(long = "timing", default_value_t = false)]
     pub timing: bool,
 
+    /// Render for slow or high-latency terminals (e.g. over SSH): batch
+    /// redraws, disable animations, and repaint streaming answers only at
+    /// paragraph boundaries. Same as `-c tui.low_bandwidth=true`.
+    #[arg(long = "low-bandwidth", default_value_t = false)]
+    pub low_bandwidth: bool,
+
+    /// Report how long each startup subsystem (config, MCP servers, skills,
+    /// hooks, agents) took to initialize. The report is shown in the
+    /// transcript once the session is configured.
+    #[arg(long = "profile-startup", default_value_t = false)]
+    pub profile_startup: bool,
+
     #[clap(skip)]
     pub config_overrides: CliConfigOverrides,
 
@@ -104,6 +120,11 @@ pub struct Cli {
     #[clap(skip)]
     pub demo_developer_message: Option<String>,
 
+    /// Context pack rendered from a `code new --template` template, added to
+    /// the session's user instructions.
+    #[clap(skip)]
+    pub session_context: Option<String>,
+
     /// Start in resume picker mode when true (used by `code resume`).
     #[clap(skip)]
     pub resume_picker: bool,
@@ -163,6 +163,15 @@ impl ListSelectionView {
         s
     }
 
+    /// Starts with the cursor on `idx` without marking that row as current.
+    pub(crate) fn with_selected_index(mut self, idx: usize) -> Self {
+        let len = self.items.len();
+        self.state.selected_idx = Some(idx);
+        self.state.clamp_selection(len);
+        self.state.ensure_visible(len, self.max_rows.min(len));
+        self
+    }
+
     pub(crate) fn view_id(&self) -> Option<&'static str> {
         self.view_id
     }
@@ -492,7 +492,35 @@ pub(crate) fn assistant_markdown_lines_with_context(
     for line in out.iter_mut().skip(1) {
         line.style = line.style.patch(Style::default().fg(fg));
     }
-    out.into_iter().skip(1).collect()
+    let mut lines: Vec<Line<'static>> = out.into_iter().skip(1).collect();
+    if let Some(meta) = state.metadata.as_ref()
+        && !meta.sources.is_empty()
+    {
+        lines.extend(source_list_lines(&meta.sources));
+    }
+    lines
+}
+
+/// Numbered list of the pages the answer links to, shown under its text.
+fn source_list_lines(sources: &[code_core::citations::Source]) -> Vec<Line<'static>> {
+    let dim = Style::default().fg(crate::colors::text_dim());
+    let mut lines = vec![
+        Line::default(),
+        Line::from(Span::styled("Sources", dim.add_modifier(Modifier::BOLD))),
+    ];
+    for (idx, source) in sources.iter().enumerate() {
+        let number = idx + 1;
+        let mut spans = vec![Span::styled(format!("[{number}] "), dim)];
+        if let Some(title) = &source.title {
+            spans.push(Span::raw(format!("{title} ")));
+        }
+        spans.push(Span::styled(
+            source.url.clone(),
+            dim.add_modifier(Modifier::UNDERLINED),
+        ));
+        lines.push(Line::from(spans));
+    }
+    lines
 }
 
 pub(crate) fn compute_assistant_layout(
@@ -1148,4 +1176,39 @@ mod tests {
             assert!(unicode_width::UnicodeWidthStr::width(text.as_str()) <= 4);
         }
     }
+
+    #[test]
+    fn cited_sources_render_as_numbered_list() {
+        let source = |url: &str, title: Option<&str>| code_core::citations::Source {
+            url: url.to_owned(),
+            title: title.map(str::to_owned),
+        };
+        let state = AssistantMessageState {
+            id: crate::history::state::HistoryId::ZERO,
+            stream_id: None,
+            markdown: "See [1](https://a.example) and https://b.example.".to_owned(),
+            citations: Vec::new(),
+            metadata: Some(crate::history::state::MessageMetadata {
+                citations: Vec::new(),
+                token_usage: None,
+                sources: vec![
+                    source("https://a.example", Some("A")),
+                    source("https://b.example", None),
+                ],
+            }),
+            token_usage: None,
+            mid_turn: false,
+            created_at: std::time::SystemTime::UNIX_EPOCH,
+        };
+        let lines = assistant_markdown_lines_with_context(
+            &state,
+            UriBasedFileOpener::None,
+            Path::new("/"),
+        );
+        let rendered: Vec<String> = lines.iter().map(line_text).collect();
+        assert_eq!(
+            rendered[rendered.len() - 3..],
+            ["Sources", "[1] A https://a.example", "[2] https://b.example"]
+        );
+    }
 }
@@ -349,7 +349,9 @@ impl ExecCell {
         };
         let start_time = matches!(record.status, ExecStatus::Running).then(Instant::now);
 
-        let collapsed_output = should_auto_collapse_output(output.as_ref());
+        let collapsed_output = record
+            .output_folded
+            .unwrap_or_else(|| should_auto_collapse_output(output.as_ref()));
 
         Self {
             record,
@@ -380,6 +382,10 @@ impl ExecCell {
         self.layout_cache.invalidate();
     }
 
+    pub(crate) fn is_output_collapsed(&self) -> bool {
+        self.collapsed_output.get()
+    }
+
     pub(crate) fn toggle_cell_collapsed(&self) {
         self.cell_collapsed.set(!self.cell_collapsed.get());
     }
@@ -833,7 +839,7 @@ fn chunk_from_text(text: &str) -> Vec<ExecStreamChunk> {
     } else {
         vec![ExecStreamChunk {
             offset: 0,
-            content: text.to_owned(),
+            content: text.into(),
         }]
     }
 }
@@ -871,6 +877,7 @@ fn build_exec_record(
                 working_dir: None,
                 env: Vec::new(),
                 tags: Vec::new(),
+                output_folded: None,
             }
         }
         None => ExecRecord {
@@ -891,6 +898,7 @@ fn build_exec_record(
             working_dir: None,
             env: Vec::new(),
             tags: Vec::new(),
+            output_folded: None,
         },
     }
 }
@@ -1001,6 +1009,25 @@ mod tests {
         assert!(cell.is_collapsed());
         assert_eq!(cell.collapsed_display_lines(&CollapsedContext { reply_number: 1 }), cell.display_lines_trimmed());
     }
+
+    #[test]
+    fn recorded_output_fold_overrides_auto_collapse() {
+        let mut record = build_exec_record(
+            vec!["seq".to_owned(), "45".to_owned()],
+            Vec::new(),
+            Some(output_with_lines(45)),
+        );
+        assert!(ExecCell::from_record(record.clone()).is_output_collapsed());
+
+        record.output_folded = Some(false);
+        let mut cell = ExecCell::from_record(record.clone());
+        assert!(!cell.is_output_collapsed());
+
+        // A record update (e.g. wait notes) keeps the user's choice.
+        record.wait_total = Some(Duration::from_secs(1));
+        cell.sync_from_record(&record);
+        assert!(!cell.is_output_collapsed());
+    }
 }
 
 // ==================== MergedExecCell ====================
@@ -14,6 +14,8 @@ use code_core::history::state::MAX_EXEC_STREAM_RETAINED_BYTES;
 use ratatui::style::{Modifier, Style, Stylize};
 use ratatui::text::{Line, Span};
 use std::cell::Cell;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use std::time::Instant;
 
 use super::core::CommandOutput;
@@ -24,8 +26,22 @@ const PREVIEW_HEAD_LINES: usize = 2;
 const PREVIEW_TAIL_LINES: usize = 5;
 const EXEC_PREVIEW_MAX_CHARS: usize = 16_000;
 pub(crate) const STREAMING_EXIT_CODE: i32 = i32::MIN;
+/// Default for `tui.exec_output_fold_lines`.
 pub(crate) const OUTPUT_FOLD_THRESHOLD: usize = 40;
 
+static OUTPUT_FOLD_LINES: AtomicUsize = AtomicUsize::new(OUTPUT_FOLD_THRESHOLD);
+
+/// Applies `tui.exec_output_fold_lines`. Called once at startup.
+pub(crate) fn set_output_fold_threshold(lines: usize) {
+    OUTPUT_FOLD_LINES.store(lines.max(1), Ordering::Relaxed);
+}
+
+/// Lines of command output kept visible while it is folded; longer output is
+/// folded automatically when the command finishes.
+pub(crate) fn output_fold_threshold() -> usize {
+    OUTPUT_FOLD_LINES.load(Ordering::Relaxed)
+}
+
 pub(crate) fn describe_exit_code(code: i32) -> &'static str {
     match code {
         1 => "general error",
@@ -615,9 +631,10 @@ fn chunks_to_string(chunks: &[ExecStreamChunk]) -> String {
     if chunks.is_empty() {
         return String::new();
     }
-    let mut sorted = chunks.to_vec();
+    let mut sorted: Vec<&ExecStreamChunk> = chunks.iter().collect();
     sorted.sort_by_key(|chunk| chunk.offset);
-    let mut combined = String::new();
+    let total: usize = sorted.iter().map(|chunk| chunk.content.len()).sum();
+    let mut combined = String::with_capacity(total);
     for chunk in sorted {
         combined.push_str(&chunk.content);
     }
@@ -662,14 +679,15 @@ pub(crate) fn should_auto_collapse_output(output: Option<&CommandOutput>) -> boo
         .lines()
         .count()
         .saturating_add(out.stderr.lines().count())
-        > OUTPUT_FOLD_THRESHOLD
+        > output_fold_threshold()
 }
 
 /// Shared output/streaming state sync used by `ExecCell` and `ReplCell`.
 ///
 /// Callers are responsible for capturing `was_running` before overwriting their
 /// stored record so we only auto-collapse on the running -> completed
-/// transition.
+/// transition. A fold state the user chose (`ExecRecord::output_folded`)
+/// always wins over auto-collapse.
 pub(crate) fn sync_exec_output_state<L: Default>(
     record: &ExecRecord,
     was_running: bool,
@@ -690,7 +708,9 @@ pub(crate) fn sync_exec_output_state<L: Default>(
         *start_time = None;
     }
 
-    if was_running
+    if let Some(folded) = record.output_folded {
+        collapsed_output.set(folded);
+    } else if was_running
         && !matches!(record.status, ExecStatus::Running)
         && should_auto_collapse_output(output.as_ref())
     {
@@ -731,7 +751,7 @@ pub(crate) struct FoldSectionLimits {
 impl FoldConfig {
     /// Standard output fold (`ExecCell`, `ReplCell`, `WebFetchToolCell`).
     pub(crate) fn output() -> Self {
-        Self { threshold: OUTPUT_FOLD_THRESHOLD }
+        Self { threshold: output_fold_threshold() }
     }
 
     /// Custom threshold (e.g. `WebFetchToolCell` body preview).
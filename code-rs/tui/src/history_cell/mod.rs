@@ -104,6 +104,7 @@ pub(crate) use formatting::{
     normalize_overwrite_sequences,
     output_lines,
     pretty_provider_name,
+    set_output_fold_threshold,
     trim_empty_lines,
 };
 pub(crate) use frozen::FrozenHistoryCell;
@@ -113,6 +114,7 @@ pub(crate) use patch::{new_patch_apply_failure, new_patch_event, PatchSummaryCel
 pub(crate) use plain::{
     new_error_event,
     new_model_output,
+    new_partial_turn,
     new_prompts_output,
     new_queued_user_prompt,
     new_reasoning_output,
@@ -24,7 +24,7 @@ use code_ansi_escape::ansi_escape_line;
 use code_common::create_config_summary_entries;
 use code_core::config::Config;
 use code_core::config_types::ReasoningEffort;
-use code_core::protocol::{SessionConfiguredEvent, TokenUsage};
+use code_core::protocol::{PartialTurnEvent, SessionConfiguredEvent, TokenUsage};
 use code_protocol::num_format::format_with_separators_u64;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
@@ -1008,6 +1008,33 @@ pub(crate) fn new_warning_event(message: String) -> PlainMessageState {
     plain_message_state_from_lines(lines, HistoryCellType::Notice)
 }
 
+/// Progress a failed turn kept: its completed tool calls and the assistant
+/// text streamed before the failure.
+pub(crate) fn new_partial_turn(event: &PartialTurnEvent) -> PlainMessageState {
+    let warn_style = crate::colors::style_warning();
+    let mut lines: Vec<Line<'static>> = vec![Line::from("partial turn")];
+    lines.push(Line::from(vec![Span::styled(
+        format!("Turn failed: {}", event.error),
+        warn_style,
+    )]));
+    if !event.tool_calls.is_empty() {
+        lines.push(Line::from("Kept completed tool calls:"));
+        lines.extend(
+            event
+                .tool_calls
+                .iter()
+                .map(|call| Line::from(format!("  • {}", call.name)).dim()),
+        );
+    }
+    if let Some(text) = event.partial_message.as_deref() {
+        lines.push(Line::from("Partial response:"));
+        lines.extend(text.lines().map(|line| Line::from(line.to_owned()).dim()));
+    }
+    lines.push(Line::from("Send a message to continue from here.".dim()));
+
+    plain_message_state_from_lines(lines, HistoryCellType::Notice)
+}
+
 pub(crate) fn new_prompts_output() -> PlainMessageState {
     let lines: Vec<Line<'static>> = vec![
         Line::from("/prompts").fg(crate::colors::keyword()),
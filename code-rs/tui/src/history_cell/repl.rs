@@ -18,8 +18,8 @@ use super::CommandOutput;
 use super::HistoryCell;
 use super::HistoryCellType;
 use super::formatting::{
-    OUTPUT_FOLD_THRESHOLD,
     describe_exit_code,
+    output_fold_threshold,
     output_lines,
     trim_empty_lines,
 };
@@ -52,7 +52,7 @@ pub(crate) struct ReplCell {
     /// When true the code block shows only the first non-empty line + "…".
     pub(crate) code_collapsed: Cell<bool>,
     cell_collapsed: Cell<bool>,
-    /// When true the output block is capped at `OUTPUT_FOLD_THRESHOLD` lines.
+    /// When true the output block is capped at `output_fold_threshold()` lines.
     pub(crate) collapsed_output: Cell<bool>,
     child_call_ids: HashSet<String>,
     last_child_call_id: Option<String>,
@@ -115,7 +115,7 @@ impl ReplCell {
     pub(crate) fn has_foldable_output(&self) -> bool {
         self.output
             .as_ref()
-            .is_some_and(|_| output_lines(self.output.as_ref(), false, false).len() > OUTPUT_FOLD_THRESHOLD)
+            .is_some_and(|_| output_lines(self.output.as_ref(), false, false).len() > output_fold_threshold())
     }
 
     /// Update output data from an `ExecRecord` produced by the history domain.
@@ -307,7 +307,7 @@ impl ReplCell {
                         .lines()
                         .count()
                         .saturating_add(o.stderr.lines().count())
-                        > OUTPUT_FOLD_THRESHOLD
+                        > output_fold_threshold()
                 });
         if has_hidden_output {
             spans.push(Span::styled(" • output ([)", dim_style));
@@ -522,6 +522,7 @@ mod tests {
             working_dir: None,
             env: Vec::new(),
             tags: Vec::new(),
+            output_folded: None,
         }
     }
 
@@ -547,7 +548,7 @@ mod tests {
         cell.record.completed_at = Some(SystemTime::UNIX_EPOCH);
         cell.output = Some(output_with_lines(count));
         cell.start_time = None;
-        cell.collapsed_output.set(count > OUTPUT_FOLD_THRESHOLD);
+        cell.collapsed_output.set(count > output_fold_threshold());
         cell
     }
 
@@ -582,7 +583,7 @@ mod tests {
 
     #[test]
     fn repl_cell_collapse_reports_trait_state() {
-        let cell = repl_with_output(OUTPUT_FOLD_THRESHOLD + 1);
+        let cell = repl_with_output(output_fold_threshold() + 1);
 
         assert!(cell.is_fold_toggleable());
         assert!(!cell.is_collapsed());
@@ -56,6 +56,17 @@ fn browser_running_title(tool_name: &str) -> &'static str {
         "browser_history" => "Navigating...",
         "browser_inspect" => "Inspecting...",
         "browser_console" => "Reading console...",
+        "browser_har" => "Exporting network log...",
+        "browser_downloads" => "Listing downloads...",
+        "browser_dom" => "Reading page structure...",
+        "browser_mock" => "Mocking requests...",
+        "browser_mock_list" => "Listing mocks...",
+        "browser_mock_remove" => "Removing mock...",
+        "browser_mock_clear" => "Clearing mocks...",
+        "browser_tab_list" => "Listing tabs...",
+        "browser_tab_open" => "Opening tab...",
+        "browser_tab_switch" => "Switching tab...",
+        "browser_tab_close" => "Closing tab...",
         "browser_move" => "Moving...",
         _ => "Working...",
     }
@@ -930,6 +941,17 @@ fn browser_tool_title(tool_name: &str) -> &'static str {
         "browser_history" => "Browser History",
         "browser_inspect" => "Browser Inspect",
         "browser_console" => "Browser Console",
+        "browser_har" => "Browser HAR",
+        "browser_downloads" => "Browser Downloads",
+        "browser_dom" => "Browser Page Outline",
+        "browser_mock" => "Browser Mock",
+        "browser_mock_list" => "Browser Mocks",
+        "browser_mock_remove" => "Browser Mock Removed",
+        "browser_mock_clear" => "Browser Mocks Cleared",
+        "browser_tab_list" => "Browser Tabs",
+        "browser_tab_open" => "Browser New Tab",
+        "browser_tab_switch" => "Browser Switch Tab",
+        "browser_tab_close" => "Browser Close Tab",
         "browser_cdp" => "Browser CDP",
         "browser_move" => "Browser Move",
         _ => "Browser Tool",
@@ -106,6 +106,7 @@ pub(crate) mod onboarding;
 pub mod public_widgets;
 mod render;
 // mod scroll_view; // Orphaned after trait-based HistoryCell migration
+mod session_health;
 mod session_log;
 mod shimmer;
 mod slash_command;
@@ -424,6 +425,18 @@ pub mod test_helpers {
     }
 }
 
+/// Appends a template's context pack to the user instructions so it reaches
+/// the model once, with the rest of the session's instructions.
+fn apply_session_context(config: &mut Config, session_context: Option<&str>) {
+    let Some(pack) = session_context else {
+        return;
+    };
+    config.user_instructions = Some(match config.user_instructions.take() {
+        Some(existing) => format!("{existing}\n\n{pack}"),
+        None => pack.to_owned(),
+    });
+}
+
 fn theme_configured_in_config_file(code_home: &std::path::Path) -> bool {
     let config_path = code_home.join("config.toml");
     let Ok(contents) = std::fs::read_to_string(&config_path) else {
@@ -494,6 +507,9 @@ pub async fn run_main(
     code_utils_rustls_provider::ensure_rustls_crypto_provider();
 
     cli.finalize_defaults();
+    if cli.profile_startup {
+        code_core::startup_profile::enable();
+    }
 
     let (sandbox_mode, approval_policy) = if cli.full_auto {
         (
@@ -550,6 +566,7 @@ pub async fn run_main(
         dynamic_tools: None,
         compact_prompt_override: cli.compact_prompt_override.clone(),
         compact_prompt_override_file: cli.compact_prompt_file.clone(),
+        sandbox_preset: cli.sandbox_preset.clone(),
     };
 
     // Parse `-c` overrides from the CLI.
@@ -607,6 +624,7 @@ pub async fn run_main(
         }
     };
 
+    let config_started = std::time::Instant::now();
     let mut config = {
         // Load configuration and support CLI overrides.
         match Config::load_with_cli_overrides(cli_kv_overrides.clone(), overrides.clone()) {
@@ -614,8 +632,10 @@ pub async fn run_main(
             Err(err) => exit_with_stderr(format_args!("Error loading configuration: {err}")),
         }
     };
+    code_core::startup_profile::record_since("config load", config_started);
 
     config.demo_developer_message = cli.demo_developer_message.clone();
+    apply_session_context(&mut config, cli.session_context.as_deref());
 
     let cli_personality_override = cli_kv_overrides.iter().any(|(path, _)| {
         matches!(path.as_str(), "model_personality" | "model-personality")
@@ -635,6 +655,7 @@ pub async fn run_main(
                     Ok(updated) => {
                         config = updated;
                         config.demo_developer_message = cli.demo_developer_message.clone();
+                        apply_session_context(&mut config, cli.session_context.as_deref());
                     }
                     Err(err) => {
                         tracing::error!("Error reloading configuration: {err}");
@@ -852,7 +873,9 @@ fn run_ratatui_app(
     install_unified_panic_hook();
     maybe_apply_terminal_theme_detection(&mut config, theme_configured_explicitly);
 
+    let terminal_started = std::time::Instant::now();
     let (mut terminal, terminal_info) = tui::init(&config)?;
+    code_core::startup_profile::record_since("terminal init", terminal_started);
     if config.tui.alternate_screen {
         terminal.clear()?;
     } else {
@@ -869,6 +892,7 @@ fn run_ratatui_app(
 
     // Initialize high-fidelity session event logging if enabled.
     session_log::maybe_init(&config);
+    let crashed_session = resume::crash_recovery::start(&config.code_home, &config.cwd);
 
     let Cli {
         prompt,
@@ -876,6 +900,7 @@ fn run_ratatui_app(
         debug,
         order,
         timing,
+        low_bandwidth,
         resume_picker,
         fork_picker,
         fork_source_path,
@@ -883,9 +908,23 @@ fn run_ratatui_app(
         resume_session_id: _,
         ..
     } = cli;
+    // Only offer to recover when this launch did not already pick a session
+    // or a prompt to start with.
+    let starts_elsewhere = prompt.is_some()
+        || resume_picker
+        || fork_picker
+        || fork_source_path.is_some()
+        || config.experimental_resume.is_some();
+    let crashed_session = crashed_session.filter(|_| !starts_elsewhere);
+
+    if low_bandwidth || tui_env::low_bandwidth_env_requested() {
+        config.tui.low_bandwidth = true;
+    }
+    tui_env::set_low_bandwidth(config.tui.low_bandwidth);
 
     // Activate icon mode from config.toml [tui].
     icons::init(config.tui.effective_icon_mode(), config.tui.icons.clone());
+    history_cell::set_output_fold_threshold(config.tui.exec_output_fold_lines);
     // Activate fused shortcut-hint rendering from config.toml [tui.settings_menu].
     bottom_pane::settings_ui::hints::set_fuse_hint_key_labels(
         config.tui.settings_menu.fuse_hint_key_labels,
@@ -907,9 +946,11 @@ fn run_ratatui_app(
         fork_source_path,
         startup_footer_notice,
         latest_upgrade_version,
+        crashed_session,
     });
 
     let app_result = app.run(&mut terminal);
+    resume::crash_recovery::finish();
     let session_id = app.session_id();
     let usage = app.token_usage();
 
@@ -920,6 +961,7 @@ fn run_ratatui_app(
 
     // After restoring the terminal, clean up any worktrees created by this process.
     cleanup_session_worktrees_and_print();
+    code_core::prune_ghost_commits_on_exit(&config.cwd, &config.ghost_commits);
     // Mark the end of the recorded session.
     session_log::log_session_end();
     if let Some(summary) = timing_summary {
@@ -927,15 +969,15 @@ fn run_ratatui_app(
     }
 
     #[cfg(unix)]
-    let sigterm_triggered = app.sigterm_triggered();
+    let termination_signal = app.termination_signal();
     #[cfg(unix)]
     app.clear_sigterm_guard();
     drop(app);
+    // Exit with 128 + signal so wrappers can tell the session was terminated;
+    // core already flushed the rollout and stopped child processes.
     #[cfg(unix)]
-    if sigterm_triggered {
-        unsafe {
-            libc::raise(libc::SIGTERM);
-        }
+    if let Some(signal) = termination_signal {
+        std::process::exit(128 + signal);
     }
 
     // ignore error when collecting usage – report underlying error instead
@@ -1240,6 +1282,7 @@ fn determine_repo_trust_state(
                 config.sandbox_policy = SandboxPolicy::WorkspaceWrite {
                     writable_roots: workspace_write.writable_roots.clone(),
                     network_access,
+                    network_allowlist: workspace_write.network_allowlist.clone(),
                     exclude_tmpdir_env_var: workspace_write.exclude_tmpdir_env_var,
                     exclude_slash_tmp: workspace_write.exclude_slash_tmp,
                     allow_git_writes: workspace_write.allow_git_writes,
@@ -1285,6 +1328,7 @@ mod tests {
                 approval_policy: None,
                 sandbox_mode: None,
                 memories: None,
+                privacy: None,
                 always_allow_commands: None,
                 hooks: vec![],
                 commands: vec![],
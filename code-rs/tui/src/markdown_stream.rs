@@ -364,6 +364,11 @@ impl MarkdownStreamCollector {
         self.buffer.ends_with('\n')
     }
 
+    /// True when the buffer ends with a blank line, i.e. a paragraph boundary.
+    pub fn ends_with_paragraph_break(&self) -> bool {
+        ends_with_paragraph_break(&self.buffer)
+    }
+
     /// Render a preview of the current buffer into lines without mutating
     /// internal counters. Unlike `finalize_and_drain`, this does not append a
     /// synthetic trailing newline, so the preview reflects what a soft-commit
@@ -523,6 +528,15 @@ pub(crate) struct StepResult {
 
 /// Streams already-rendered rows into history while computing the newest K
 /// rows to show in a live overlay.
+/// True when `source` ends with a whitespace-only line followed by a newline.
+pub(crate) fn ends_with_paragraph_break(source: &str) -> bool {
+    let Some(body) = source.strip_suffix('\n') else {
+        return false;
+    };
+    body.rfind('\n')
+        .is_some_and(|idx| body[idx + 1..].trim().is_empty())
+}
+
 pub(crate) struct AnimatedLineStreamer {
     queue: VecDeque<Line<'static>>,
 }
@@ -0,0 +1,236 @@
+//! Detection of TUI sessions that ended without a clean shutdown.
+//!
+//! Every TUI process keeps a marker under `<code_home>/running-sessions/`
+//! with its cwd, session id, queued messages and pending approvals, and
+//! removes it when the app exits normally. A marker whose process is gone on
+//! the next launch therefore belongs to a session that crashed or was killed.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use serde::Deserialize;
+use serde::Serialize;
+use uuid::Uuid;
+
+const MARKER_DIR: &str = "running-sessions";
+
+/// State of a running TUI session, as last written to its marker.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SessionMarker {
+    pub pid: u32,
+    pub cwd: PathBuf,
+    pub session_id: Option<Uuid>,
+    pub updated_at: String,
+    /// Messages typed while the agent was busy and not yet sent.
+    #[serde(default)]
+    pub queued_messages: Vec<String>,
+    /// Commands and patches that were waiting for approval.
+    #[serde(default)]
+    pub pending_approvals: Vec<PendingApproval>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PendingApproval {
+    pub id: String,
+    pub summary: String,
+}
+
+struct ActiveMarker {
+    path: PathBuf,
+    marker: SessionMarker,
+}
+
+static ACTIVE: Mutex<Option<ActiveMarker>> = Mutex::new(None);
+
+/// Starts tracking this process's session for `cwd` and returns the most
+/// recent session for `cwd` that ended without a clean shutdown, if any.
+pub(crate) fn start(code_home: &Path, cwd: &Path) -> Option<SessionMarker> {
+    let dir = code_home.join(MARKER_DIR);
+    let abandoned = take_abandoned(&dir, cwd, code_core::review_coord::pid_alive);
+    if let Err(err) = std::fs::create_dir_all(&dir) {
+        tracing::warn!("failed to create {}: {err}", dir.display());
+        return abandoned;
+    }
+    let pid = std::process::id();
+    let active = ActiveMarker {
+        path: dir.join(format!("pid-{pid}.json")),
+        marker: SessionMarker {
+            pid,
+            cwd: cwd.to_path_buf(),
+            updated_at: now(),
+            ..SessionMarker::default()
+        },
+    };
+    write_marker(&active);
+    *lock_active() = Some(active);
+    abandoned
+}
+
+/// Removes this process's marker; called once the app shut down cleanly.
+pub(crate) fn finish() {
+    if let Some(active) = lock_active().take() {
+        let _ = std::fs::remove_file(&active.path);
+    }
+}
+
+/// Records the session this process is showing. Switching to another
+/// session drops what was queued or pending in the previous one.
+pub(crate) fn set_session_id(session_id: Uuid) {
+    update(|marker| {
+        if marker.session_id != Some(session_id) {
+            marker.session_id = Some(session_id);
+            marker.queued_messages.clear();
+            marker.pending_approvals.clear();
+        }
+    });
+}
+
+pub(crate) fn set_queued_messages(messages: Vec<String>) {
+    update(|marker| marker.queued_messages = messages);
+}
+
+pub(crate) fn approval_requested(id: String, summary: String) {
+    update(|marker| {
+        marker
+            .pending_approvals
+            .push(PendingApproval { id, summary })
+    });
+}
+
+pub(crate) fn approval_resolved(id: &str) {
+    update(|marker| {
+        marker
+            .pending_approvals
+            .retain(|approval| approval.id != id)
+    });
+}
+
+pub(crate) fn clear_pending_approvals() {
+    update(|marker| marker.pending_approvals.clear());
+}
+
+fn lock_active() -> std::sync::MutexGuard<'static, Option<ActiveMarker>> {
+    match ACTIVE.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+/// Applies `change` to the active marker and rewrites it if anything changed.
+fn update(change: impl FnOnce(&mut SessionMarker)) {
+    let mut guard = lock_active();
+    let Some(active) = guard.as_mut() else {
+        return;
+    };
+    let before = active.marker.clone();
+    change(&mut active.marker);
+    if active.marker != before {
+        active.marker.updated_at = now();
+        write_marker(active);
+    }
+}
+
+fn write_marker(active: &ActiveMarker) {
+    let body = match serde_json::to_vec_pretty(&active.marker) {
+        Ok(body) => body,
+        Err(err) => {
+            tracing::warn!("failed to serialize session marker: {err}");
+            return;
+        }
+    };
+    // Write then rename so a crash mid-write never leaves a torn marker.
+    let tmp = active.path.with_extension("json.tmp");
+    if let Err(err) = std::fs::write(&tmp, body).and_then(|()| std::fs::rename(&tmp, &active.path))
+    {
+        tracing::warn!("failed to write {}: {err}", active.path.display());
+    }
+}
+
+fn now() -> String {
+    chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+}
+
+/// Removes the markers in `dir` left by processes that are no longer running
+/// and returns the most recently updated one for `cwd` that has a session.
+fn take_abandoned(
+    dir: &Path,
+    cwd: &Path,
+    is_running: impl Fn(u32) -> bool,
+) -> Option<SessionMarker> {
+    let entries = std::fs::read_dir(dir).ok()?;
+    let mut latest: Option<SessionMarker> = None;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+        let marker = std::fs::read(&path)
+            .ok()
+            .and_then(|body| serde_json::from_slice::<SessionMarker>(&body).ok());
+        if let Some(marker) = &marker
+            && is_running(marker.pid)
+        {
+            continue;
+        }
+        let _ = std::fs::remove_file(&path);
+        let Some(marker) = marker else {
+            continue;
+        };
+        if marker.cwd != cwd || marker.session_id.is_none() {
+            continue;
+        }
+        if latest
+            .as_ref()
+            .is_none_or(|current| current.updated_at < marker.updated_at)
+        {
+            latest = Some(marker);
+        }
+    }
+    latest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn write(dir: &Path, marker: &SessionMarker) {
+        let path = dir.join(format!("pid-{}.json", marker.pid));
+        std::fs::write(path, serde_json::to_vec(marker).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn finds_latest_abandoned_session_for_cwd() {
+        let dir = tempfile::tempdir().unwrap();
+        let cwd = Path::new("/work/project");
+        let marker = |pid: u32, cwd: &Path, updated_at: &str| SessionMarker {
+            pid,
+            cwd: cwd.to_path_buf(),
+            session_id: Some(Uuid::new_v4()),
+            updated_at: updated_at.to_owned(),
+            ..SessionMarker::default()
+        };
+        let older = marker(11, cwd, "2026-01-01T10:00:00.000Z");
+        let newer = SessionMarker {
+            queued_messages: vec!["run the tests".to_owned()],
+            ..marker(12, cwd, "2026-01-01T11:00:00.000Z")
+        };
+        let running = marker(13, cwd, "2026-01-01T12:00:00.000Z");
+        let elsewhere = marker(14, Path::new("/work/other"), "2026-01-01T13:00:00.000Z");
+        for m in [&older, &newer, &running, &elsewhere] {
+            write(dir.path(), m);
+        }
+        std::fs::write(dir.path().join("pid-15.json"), "not json").unwrap();
+
+        let found = take_abandoned(dir.path(), cwd, |pid| pid == 13);
+        assert_eq!(found, Some(newer));
+
+        let mut left: Vec<String> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        left.sort();
+        assert_eq!(left, vec!["pid-13.json".to_owned()]);
+    }
+}
@@ -1,3 +1,4 @@
+pub mod crash_recovery;
 pub mod discovery;
 
 #[cfg(test)]
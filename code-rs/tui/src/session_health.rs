@@ -0,0 +1,215 @@
+//! Periodic self-metrics for long-running sessions.
+//!
+//! Marathon sessions accumulate history cells, render caches and child
+//! processes. The app loop samples process RSS, CPU use, history size, event
+//! queue depth and child process count every [`SAMPLE_INTERVAL`], logs the
+//! sample at debug level, and reports when RSS crosses the configured
+//! `tui.memory_soft_limit_mb` so the chat widget can shed caches and warn the
+//! user before the OOM killer steps in.
+
+use std::time::Duration;
+use std::time::Instant;
+
+/// How often the app loop samples process metrics.
+pub(crate) const SAMPLE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Once over the soft limit, RSS must drop below this fraction of it before
+/// another crossing is reported, so hovering at the limit warns only once.
+const REARM_FRACTION: f64 = 0.9;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct HealthSample {
+    pub rss_bytes: Option<u64>,
+    /// CPU use since the previous sample, as a percentage of one core.
+    pub cpu_percent: Option<f64>,
+    pub history_records: usize,
+    pub queue_high: usize,
+    pub queue_bulk: usize,
+    pub child_processes: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SoftLimitCrossing {
+    Exceeded { rss_bytes: u64, limit_bytes: u64 },
+}
+
+#[derive(Debug)]
+pub(crate) struct SessionHealthMonitor {
+    soft_limit_bytes: Option<u64>,
+    last_sample_at: Option<Instant>,
+    last_cpu: Option<(Instant, Duration)>,
+    over_limit: bool,
+}
+
+impl SessionHealthMonitor {
+    /// `soft_limit_mb == 0` disables the limit; metrics are still sampled.
+    pub(crate) fn new(soft_limit_mb: u64) -> Self {
+        Self {
+            soft_limit_bytes: (soft_limit_mb > 0).then(|| soft_limit_mb.saturating_mul(1024 * 1024)),
+            last_sample_at: None,
+            last_cpu: None,
+            over_limit: false,
+        }
+    }
+
+    pub(crate) fn is_due(&self, now: Instant) -> bool {
+        self.last_sample_at
+            .is_none_or(|last| now.duration_since(last) >= SAMPLE_INTERVAL)
+    }
+
+    /// Reads process-wide metrics, filling in CPU use relative to the last
+    /// call. Widget-owned counts are left for the caller to fill in.
+    pub(crate) fn sample_process(&mut self, now: Instant) -> HealthSample {
+        self.last_sample_at = Some(now);
+        let cpu_time = process_cpu_time();
+        let cpu_percent = match (self.last_cpu, cpu_time) {
+            (Some((prev_at, prev_cpu)), Some(cpu)) => {
+                let wall = now.duration_since(prev_at).as_secs_f64();
+                (wall > 0.0)
+                    .then(|| cpu.saturating_sub(prev_cpu).as_secs_f64() / wall * 100.0)
+            }
+            _ => None,
+        };
+        if let Some(cpu) = cpu_time {
+            self.last_cpu = Some((now, cpu));
+        }
+        HealthSample {
+            rss_bytes: process_rss_bytes(),
+            cpu_percent,
+            child_processes: child_process_count(),
+            ..HealthSample::default()
+        }
+    }
+
+    /// Returns a crossing the first time RSS exceeds the soft limit, and
+    /// again only after it has dropped back below the re-arm threshold.
+    pub(crate) fn check_soft_limit(&mut self, sample: &HealthSample) -> Option<SoftLimitCrossing> {
+        let (limit_bytes, rss_bytes) = (self.soft_limit_bytes?, sample.rss_bytes?);
+        if self.over_limit {
+            if (rss_bytes as f64) < limit_bytes as f64 * REARM_FRACTION {
+                self.over_limit = false;
+            }
+            return None;
+        }
+        if rss_bytes <= limit_bytes {
+            return None;
+        }
+        self.over_limit = true;
+        Some(SoftLimitCrossing::Exceeded { rss_bytes, limit_bytes })
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn process_rss_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let kib = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))?
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    Some(kib * 1024)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn process_rss_bytes() -> Option<u64> {
+    None
+}
+
+#[cfg(unix)]
+fn process_cpu_time() -> Option<Duration> {
+    let mut usage = std::mem::MaybeUninit::<libc::rusage>::uninit();
+    // SAFETY: `getrusage` fully initializes `usage` when it returns 0.
+    let usage = unsafe {
+        if libc::getrusage(libc::RUSAGE_SELF, usage.as_mut_ptr()) != 0 {
+            return None;
+        }
+        usage.assume_init()
+    };
+    let to_duration = |tv: libc::timeval| {
+        Duration::from_secs(tv.tv_sec.max(0) as u64)
+            + Duration::from_micros(tv.tv_usec.max(0) as u64)
+    };
+    Some(to_duration(usage.ru_utime) + to_duration(usage.ru_stime))
+}
+
+#[cfg(not(unix))]
+fn process_cpu_time() -> Option<Duration> {
+    None
+}
+
+/// Counts direct children of this process across all of its threads.
+#[cfg(target_os = "linux")]
+fn child_process_count() -> Option<usize> {
+    let tasks = std::fs::read_dir("/proc/self/task").ok()?;
+    let count = tasks
+        .filter_map(Result::ok)
+        .filter_map(|task| std::fs::read_to_string(task.path().join("children")).ok())
+        .map(|children| children.split_whitespace().count())
+        .sum();
+    Some(count)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn child_process_count() -> Option<usize> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_with_rss(mib: u64) -> HealthSample {
+        HealthSample {
+            rss_bytes: Some(mib * 1024 * 1024),
+            ..HealthSample::default()
+        }
+    }
+
+    #[test]
+    fn soft_limit_reports_once_until_rss_recovers() {
+        let mut monitor = SessionHealthMonitor::new(100);
+
+        assert_eq!(monitor.check_soft_limit(&sample_with_rss(80)), None);
+        assert_eq!(
+            monitor.check_soft_limit(&sample_with_rss(120)),
+            Some(SoftLimitCrossing::Exceeded {
+                rss_bytes: 120 * 1024 * 1024,
+                limit_bytes: 100 * 1024 * 1024,
+            })
+        );
+        assert_eq!(monitor.check_soft_limit(&sample_with_rss(130)), None);
+        // Dipping just under the limit does not re-arm the warning.
+        assert_eq!(monitor.check_soft_limit(&sample_with_rss(95)), None);
+        assert_eq!(monitor.check_soft_limit(&sample_with_rss(110)), None);
+
+        assert_eq!(monitor.check_soft_limit(&sample_with_rss(50)), None);
+        assert!(monitor.check_soft_limit(&sample_with_rss(101)).is_some());
+    }
+
+    #[test]
+    fn zero_limit_disables_the_guardrail() {
+        let mut monitor = SessionHealthMonitor::new(0);
+        assert_eq!(monitor.check_soft_limit(&sample_with_rss(1 << 20)), None);
+    }
+
+    #[test]
+    fn sampling_is_due_once_per_interval() {
+        let mut monitor = SessionHealthMonitor::new(0);
+        let start = Instant::now();
+        assert!(monitor.is_due(start));
+        let _ = monitor.sample_process(start);
+        assert!(!monitor.is_due(start + SAMPLE_INTERVAL / 2));
+        assert!(monitor.is_due(start + SAMPLE_INTERVAL));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn linux_reports_rss() {
+        let sample = SessionHealthMonitor::new(0).sample_process(Instant::now());
+        assert!(sample.rss_bytes.is_some_and(|rss| rss > 0));
+        assert!(sample.child_processes.is_some());
+    }
+}
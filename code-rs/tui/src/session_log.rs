@@ -243,6 +243,15 @@ pub(crate) fn log_inbound_app_event(event: &AppEvent) {
             });
             LOGGER.write_json_line(value);
         }
+        AppEvent::AutoCoordinatorHandoff { note } => {
+            let value = json!({
+                "ts": now_ts(),
+                "dir": "to_tui",
+                "kind": "auto_coordinator_handoff",
+                "note": note,
+            });
+            LOGGER.write_json_line(value);
+        }
         AppEvent::AutoCoordinatorTokenMetrics {
             total_usage,
             last_turn_usage,
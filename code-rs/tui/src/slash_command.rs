@@ -71,6 +71,7 @@ pub(crate) enum SlashCommand {
     Init,
     Compact,
     Undo,
+    UndoTurn,
     Review,
     Cloud,
     Diff,
@@ -86,6 +87,9 @@ pub(crate) enum SlashCommand {
     Settings,
     Memories,
     Shell,
+    Cd,
+    Env,
+    Approvals,
     Mode,
     Model,
     Fast,
@@ -96,6 +100,7 @@ pub(crate) enum SlashCommand {
     Reasoning,
     Verbosity,
     Prompts,
+    Prompt,
     Skills,
     #[strum(serialize = "app", serialize = "apps")]
     Apps,
@@ -111,6 +116,7 @@ pub(crate) enum SlashCommand {
     Validation,
     Mcp,
     Resume,
+    Fork,
     Rename,
     Login,
     #[strum(serialize = "account", serialize = "accounts")]
@@ -122,6 +128,9 @@ pub(crate) enum SlashCommand {
     Plan,
     Solve,
     Code,
+    Repro,
+    Docsync,
+    Migrate,
     Logout,
     Quit,
     #[cfg(debug_assertions)]
@@ -135,16 +144,21 @@ impl SlashCommand {
             SlashCommand::Chrome => "connect to your running Chrome via DevTools (CDP)",
             SlashCommand::Browser => "launch built-in headless browser for screenshots & automation",
             SlashCommand::Resume => "resume a past session for this folder",
+            SlashCommand::Fork => "continue in a copy of this session (/fork [[SUBMISSION_ID:]SEQ])",
             SlashCommand::Rename => "rename the current session",
             SlashCommand::Plan => "create a comprehensive plan (multiple agents)",
             SlashCommand::Solve => "solve a challenging problem (multiple agents)",
             SlashCommand::Code => "perform a coding task (multiple agents)",
+            SlashCommand::Repro => "write and commit a failing reproduction script under repro/",
+            SlashCommand::Docsync => "update docs for the current changes as a separate patch",
+            SlashCommand::Migrate => "upgrade a dependency in phases with Auto Drive (/migrate <dep> <version>)",
             SlashCommand::Reasoning => "change reasoning effort (minimal/low/medium/high)",
             SlashCommand::Verbosity => "change text verbosity (high/medium/low)",
             SlashCommand::New => "start a new chat during a conversation",
             SlashCommand::Init => "create an AGENTS.md file with instructions for Code",
             SlashCommand::Compact => "summarize conversation to prevent hitting the context limit",
             SlashCommand::Undo => "restore the workspace to the last Code snapshot",
+            SlashCommand::UndoTurn => "revert the file changes of the last turn (/undo-turn [TURN_ID])",
             SlashCommand::Review => "review your changes for potential issues",
             SlashCommand::Cloud => "browse, apply, and create cloud tasks",
             SlashCommand::Quit => "exit Code",
@@ -162,7 +176,13 @@ impl SlashCommand {
             SlashCommand::Settings => "manage all settings in one place",
             SlashCommand::Memories => "inspect, rebuild, clear, or configure Memories artifacts",
             SlashCommand::Shell => "configure the shell used for command execution",
+            SlashCommand::Cd => "switch the session to another directory (/cd <path>)",
+            SlashCommand::Env => "set environment variables for commands (/env set NAME=value)",
+            SlashCommand::Approvals => {
+                "list, add, or revoke command approvals saved for this project"
+            }
             SlashCommand::Prompts => "manage custom prompts",
+            SlashCommand::Prompt => "preview the next turn's prompt stack and switch segments off",
             SlashCommand::Skills => "manage skills",
             SlashCommand::Apps => "browse and use apps (connectors)",
             SlashCommand::Plugins => "browse and manage plugins",
@@ -184,7 +204,7 @@ impl SlashCommand {
             SlashCommand::Push => "commit, push, and monitor workflows",
             SlashCommand::Validation => "control validation harness (status/on/off)",
             SlashCommand::Mcp => "manage MCP servers",
-            SlashCommand::Perf => "performance tracing (on/off/show/reset)",
+            SlashCommand::Perf => "performance tracing (on/off/show/reset/overlay)",
             SlashCommand::Demo => "populate history with demo cells (dev/perf only)",
             SlashCommand::Login => "manage Code sign-ins (add/select/disconnect)",
             SlashCommand::Accounts => "manage account switching settings",
@@ -206,7 +226,11 @@ impl SlashCommand {
     pub(crate) fn is_prompt_expanding(self) -> bool {
         matches!(
             self,
-            SlashCommand::Plan | SlashCommand::Solve | SlashCommand::Code
+            SlashCommand::Plan
+                | SlashCommand::Solve
+                | SlashCommand::Code
+                | SlashCommand::Repro
+                | SlashCommand::Docsync
         )
     }
 
@@ -226,7 +250,7 @@ impl SlashCommand {
     pub(crate) fn requires_arguments(self) -> bool {
         matches!(
             self,
-            SlashCommand::Plan | SlashCommand::Solve | SlashCommand::Code
+            SlashCommand::Plan | SlashCommand::Solve | SlashCommand::Code | SlashCommand::Repro
         )
     }
 
@@ -263,6 +287,8 @@ impl SlashCommand {
             SlashCommand::Code => Some(code_core::slash_commands::format_code_command(
                 args, None, None,
             )),
+            SlashCommand::Repro => Some(code_core::slash_commands::format_repro_command(args)),
+            SlashCommand::Docsync => Some(code_core::slash_commands::format_docsync_command(args)),
             _ => None,
         }
     }
@@ -398,6 +424,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn repro_command_expands_and_requires_an_issue() {
+        match process_slash_command_message("/repro login fails with an empty password") {
+            ProcessedCommand::ExpandedPrompt(prompt) => {
+                assert!(prompt.contains("login fails with an empty password"));
+            }
+            other => panic!("expected ExpandedPrompt, got {other:?}"),
+        }
+        assert!(matches!(
+            process_slash_command_message("/repro"),
+            ProcessedCommand::Error(_)
+        ));
+    }
+
+    #[test]
+    fn docsync_command_expands_without_arguments() {
+        match process_slash_command_message("/docsync") {
+            ProcessedCommand::ExpandedPrompt(prompt) => {
+                assert!(prompt.contains("Please perform /docsync"));
+            }
+            other => panic!("expected ExpandedPrompt, got {other:?}"),
+        }
+    }
+
     #[test]
     fn auto_command_with_newline_arguments_is_regular_command() {
         let msg = "/auto\ninspect the failing build";
@@ -64,6 +64,12 @@ impl StreamController {
         }
     }
 
+    /// Low-bandwidth mode gates commits on paragraph boundaries and drains
+    /// queued lines in one batch instead of animating them line by line.
+    fn low_bandwidth(&self) -> bool {
+        self.config.tui.low_bandwidth
+    }
+
     pub(crate) fn reset_headers_for_new_turn(&mut self) {
         self.header.reset_for_new_turn();
     }
@@ -274,7 +280,9 @@ pub(crate) fn set_last_sequence_number(&mut self, kind: StreamKind, seq: Option<
             state.collector.push_delta(delta);
             state.tail_chars_since_commit = state.tail_chars_since_commit.saturating_add(delta.len());
         }
-        if delta.contains('\n') {
+        let at_commit_boundary = delta.contains('\n')
+            && (!self.low_bandwidth() || self.state(kind).collector.ends_with_paragraph_break());
+        if at_commit_boundary {
             let mut newly_completed = self.state_mut(kind).collector.commit_complete_lines(&cfg);
             // Reduce leading blanks to at most one across commits
             if !newly_completed.is_empty() {
@@ -317,7 +325,7 @@ pub(crate) fn set_last_sequence_number(&mut self, kind: StreamKind, seq: Option<
         }
 
         // Char-threshold soft commit (when no newline has arrived for a while)
-        if !delta.contains('\n') {
+        if !delta.contains('\n') && !self.low_bandwidth() {
             let threshold = self.config.tui.stream.soft_commit_chars
                 .or_else(|| self.config.tui.stream.responsive.then_some(160));
             if let Some(limit) = threshold {
@@ -606,7 +614,7 @@ pub(crate) fn set_last_sequence_number(&mut self, kind: StreamKind, seq: Option<
         // Timeout-based soft commit: if no newline arrived and nothing is queued, force a soft commit.
         let timeout_ms = self.config.tui.stream.soft_commit_timeout_ms
             .or_else(|| self.config.tui.stream.responsive.then_some(400));
-        if let Some(ms) = timeout_ms {
+        if let Some(ms) = timeout_ms.filter(|_| !self.low_bandwidth()) {
             let queue_empty = self.state(kind).is_idle();
             let overdue = self
                 .state(kind)
@@ -640,9 +648,10 @@ pub(crate) fn set_last_sequence_number(&mut self, kind: StreamKind, seq: Option<
                 }
             }
         }
+        let drain_batch = self.low_bandwidth();
         let step = {
             let state = self.state_mut(kind);
-            state.step()
+            if drain_batch { state.drain_all() } else { state.step() }
         };
         if !step.history.is_empty() {
             let mut lines: Lines = Vec::new();
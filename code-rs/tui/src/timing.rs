@@ -8,6 +8,17 @@ pub(crate) const ANIMATION_FRAME_INTERVAL: Duration = Duration::from_millis(120)
 /// redraws to avoid overwhelming slow terminals.
 pub(crate) const REDRAW_DEBOUNCE: Duration = Duration::from_millis(33);
 
+/// Minimum spacing between frames in low-bandwidth mode. Redraw requests that
+/// arrive sooner are batched into a single deferred frame.
+pub(crate) const LOW_BANDWIDTH_REDRAW_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Floor for animation frame scheduling in low-bandwidth mode so spinners and
+/// other per-frame effects tick at most once per second.
+pub(crate) const LOW_BANDWIDTH_ANIMATION_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Streaming commit tick used in low-bandwidth mode when `commit_tick_ms` is unset.
+pub(crate) const LOW_BANDWIDTH_COMMIT_TICK_MS: u64 = 250;
+
 /// Timeout for individual terminal escape-sequence queries (cell size, bg color,
 /// etc.) sent during startup.
 pub(crate) const TERMINAL_QUERY_TIMEOUT: Duration = Duration::from_millis(100);
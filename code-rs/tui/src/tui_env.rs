@@ -6,6 +6,7 @@ static FORCE_MINIMAL_HEADER_OVERRIDE_SET: AtomicBool = AtomicBool::new(false);
 static FORCE_MINIMAL_HEADER_OVERRIDE_VALUE: AtomicBool = AtomicBool::new(false);
 #[cfg(not(any(test, feature = "test-helpers")))]
 static FORCE_MINIMAL_HEADER_ENV: OnceLock<bool> = OnceLock::new();
+static LOW_BANDWIDTH: AtomicBool = AtomicBool::new(false);
 
 pub(crate) fn force_minimal_header() -> bool {
     if FORCE_MINIMAL_HEADER_OVERRIDE_SET.load(Ordering::Relaxed) {
@@ -25,6 +26,29 @@ pub(crate) fn force_minimal_header() -> bool {
     }
 }
 
+/// Whether low-bandwidth rendering is active for this process.
+///
+/// Set once at startup from `tui.low_bandwidth`; read by render paths that do
+/// not have access to `Config` (animations, reduced-motion checks).
+pub(crate) fn low_bandwidth() -> bool {
+    LOW_BANDWIDTH.load(Ordering::Relaxed)
+}
+
+pub(crate) fn set_low_bandwidth(enabled: bool) {
+    LOW_BANDWIDTH.store(enabled, Ordering::Relaxed);
+}
+
+/// `CODE_TUI_LOW_BANDWIDTH=1` forces low-bandwidth rendering regardless of config.
+pub(crate) fn low_bandwidth_env_requested() -> bool {
+    match std::env::var("CODE_TUI_LOW_BANDWIDTH") {
+        Ok(value) => {
+            let normalized = value.trim().to_ascii_lowercase();
+            !matches!(normalized.as_str(), "" | "0" | "false" | "off" | "no")
+        }
+        Err(_) => false,
+    }
+}
+
 #[cfg(test)]
 pub(crate) struct ForceMinimalHeaderOverrideGuard {
     prev_set: bool,
@@ -13,6 +13,7 @@ use code_core::command_canonicalization::{
     CanonicalApprovalCommandKind,
 };
 use code_core::protocol::Op;
+use code_shell_command::command_summary::{summarize_command, CommandEffect};
 use code_core::protocol::ReviewDecision;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
@@ -137,8 +138,9 @@ impl UserApprovalWidget<'_> {
                         "Code wants to run ".bold(),
                         cmd_span,
                     ]),
-                    Line::from(""),
                 ];
+                contents.extend(command_summary_lines(command));
+                contents.push(Line::from(""));
                 if let Some(reason) = reason {
                     contents.push(Line::from(reason.clone().italic()));
                     contents.push(Line::from(""));
@@ -700,6 +702,37 @@ impl WidgetRef for &UserApprovalWidget<'_> {
     }
 }
 
+/// Plain-English explanation and effect labels shown under the command, so
+/// the decision does not depend on reading the shell syntax.
+fn command_summary_lines(command: &[String]) -> Vec<Line<'static>> {
+    let summary = summarize_command(command);
+    let s_text_dim = crate::colors::style_text_dim();
+    let mut lines = vec![Line::from(vec![
+        Span::styled("  What it does: ", s_text_dim),
+        Span::raw(summary.explanation),
+    ])];
+    if !summary.effects.is_empty() {
+        let mut spans = vec![Span::styled("  Effects: ", s_text_dim)];
+        for (idx, effect) in summary.effects.iter().enumerate() {
+            if idx > 0 {
+                spans.push(Span::styled(" · ", s_text_dim));
+            }
+            let style = match effect {
+                CommandEffect::ReadsFiles => Style::default().fg(crate::colors::success()),
+                CommandEffect::WritesFiles | CommandEffect::Network => {
+                    Style::default().fg(crate::colors::warning())
+                }
+                CommandEffect::Destructive => Style::default()
+                    .fg(crate::colors::error())
+                    .add_modifier(Modifier::BOLD),
+            };
+            spans.push(Span::styled(effect.label(), style));
+        }
+        lines.push(Line::from(spans));
+    }
+    lines
+}
+
 fn build_exec_select_options(command: &[String]) -> Vec<SelectOption> {
     let mut options = Vec::with_capacity(4);
 
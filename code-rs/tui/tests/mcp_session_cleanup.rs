@@ -139,6 +139,7 @@ async fn mcp_stdio_server_exits_before_next_session() {
             HashSet::new(),
             tx_event.clone(),
             AskForApproval::OnRequest,
+            config.spawned_env.clone(),
         )
             .await
             .expect("start first MCP manager");
@@ -163,6 +164,7 @@ async fn mcp_stdio_server_exits_before_next_session() {
         HashSet::new(),
         tx_event,
         AskForApproval::OnRequest,
+        config.spawned_env.clone(),
     )
         .await
         .expect("start second MCP manager");
@@ -14,10 +14,19 @@ use code_core::history::state::{
     TextEmphasis,
     TextTone,
 };
-use code_core::protocol::{Event, EventMsg, ExecCommandBeginEvent, ExecCommandEndEvent, OrderMeta};
+use code_core::protocol::{
+    Event,
+    EventMsg,
+    ExecCommandBeginEvent,
+    ExecCommandEndEvent,
+    ExecCommandOutputDeltaEvent,
+    ExecOutputStream,
+    OrderMeta,
+};
 use code_tui::test_helpers::{render_chat_widget_to_vt100, ChatWidgetHarness};
+use serde_bytes::ByteBuf;
 use std::path::PathBuf;
-use std::time::{Duration, SystemTime};
+use std::time::{Duration, Instant, SystemTime};
 
 fn make_code_block(lines: usize) -> String {
     let mut out = String::new();
@@ -104,7 +113,7 @@ fn seed_merged_exec_cells(harness: &mut ChatWidgetHarness, count: usize) {
                 status: ExecStatus::Success,
                 stdout_chunks: vec![ExecStreamChunk {
                     offset: 0,
-                    content: format!("line {idx}:{seg}: {pad}\n", pad = "x".repeat(180)),
+                    content: format!("line {idx}:{seg}: {pad}\n", pad = "x".repeat(180)).into(),
                 }],
                 stderr_chunks: Vec::new(),
                 exit_code: Some(0),
@@ -116,6 +125,7 @@ fn seed_merged_exec_cells(harness: &mut ChatWidgetHarness, count: usize) {
                 working_dir: None,
                 env: Vec::new(),
                 tags: Vec::new(),
+                output_folded: None,
             });
         }
 
@@ -538,3 +548,51 @@ fn print_widget_render_cost_for_web_fetch_scrollback_scenario() {
         "render_perf_numbers_web_fetch: frames={rendered_frames} avg_widget_render_ms={avg_widget_ms:.3} avg_visible_render_ms={avg_visible_ms:.3}"
     );
 }
+
+#[test]
+fn print_history_cost_for_streaming_exec_output() {
+    if !perf_numbers_enabled() {
+        return;
+    }
+    let mut harness = ChatWidgetHarness::new();
+    let mut seq = 0_u64;
+    let call_id = "call_stream".to_string();
+    harness.handle_event(Event {
+        id: "exec-begin-stream".into(),
+        event_seq: 0,
+        msg: EventMsg::ExecCommandBegin(ExecCommandBeginEvent {
+            call_id: call_id.clone(),
+            command: vec!["cat".into(), "build.log".into()],
+            cwd: PathBuf::from("/tmp"),
+            parsed_cmd: Vec::new(),
+            parent_call_id: None,
+        }),
+        order: Some(next_order_meta(1, &mut seq)),
+    });
+
+    // 256 deltas of 64 KiB each: 16 MiB of output flowing into one exec cell.
+    let line = format!("{pad}\n", pad = "z".repeat(127));
+    let chunk = line.repeat(512);
+    let deltas = 256u64;
+    let started = Instant::now();
+    for idx in 0..deltas {
+        harness.handle_event(Event {
+            id: format!("exec-delta-{idx}"),
+            event_seq: idx + 1,
+            msg: EventMsg::ExecCommandOutputDelta(ExecCommandOutputDeltaEvent {
+                call_id: call_id.clone(),
+                stream: ExecOutputStream::Stdout,
+                chunk: ByteBuf::from(chunk.clone().into_bytes()),
+            }),
+            order: Some(next_order_meta(1, &mut seq)),
+        });
+    }
+    let elapsed = started.elapsed();
+    let _ = render_chat_widget_to_vt100(&mut harness, 120, 40);
+
+    let avg_delta_us = (elapsed.as_nanos() as f64) / (deltas as f64) / 1_000.0;
+    let mib = (chunk.len() as f64) * (deltas as f64) / (1024.0 * 1024.0);
+    println!(
+        "render_perf_numbers_exec_stream: deltas={deltas} mib={mib:.1} avg_delta_us={avg_delta_us:.1}"
+    );
+}
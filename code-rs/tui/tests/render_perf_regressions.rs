@@ -226,7 +226,7 @@ fn scrollback_repeated_renders_do_not_relayout_merged_exec_cells() {
                 status: ExecStatus::Success,
                 stdout_chunks: vec![ExecStreamChunk {
                     offset: 0,
-                    content: format!("line {idx}:{seg}: {pad}\n", pad = "x".repeat(140)),
+                    content: format!("line {idx}:{seg}: {pad}\n", pad = "x".repeat(140)).into(),
                 }],
                 stderr_chunks: Vec::new(),
                 exit_code: Some(0),
@@ -238,6 +238,7 @@ fn scrollback_repeated_renders_do_not_relayout_merged_exec_cells() {
                 working_dir: None,
                 env: Vec::new(),
                 tags: Vec::new(),
+                output_folded: None,
             });
         }
 
@@ -115,6 +115,7 @@ fn interleaved_reasoning_snapshot() -> HistorySnapshot {
             OrderKeySnapshot { req: 4, out: 0, seq: 4 },
         ],
         order_debug: Vec::new(),
+        bookmarks: Vec::new(),
     }
 }
 
@@ -132,6 +133,7 @@ fn final_reasoning_snapshot() -> HistorySnapshot {
             OrderKeySnapshot { req: 2, out: 0, seq: 2 },
         ],
         order_debug: Vec::new(),
+        bookmarks: Vec::new(),
     }
 }
 
@@ -792,6 +792,9 @@ fn running_exec_is_finalized_when_error_event_arrives() {
         event_seq: 2,
         msg: EventMsg::Error(ErrorEvent {
             message: "fatal: provider crashed".into(),
+            codex_error_info: None,
+            code: None,
+            category: None,
         }),
         order: None,
     });
@@ -73,6 +73,7 @@ fn cli_web_search_flag_defaults() {
         oss: false,
         config_profile: None,
         sandbox_mode: None,
+        sandbox_preset: None,
         approval_policy: None,
         full_auto: false,
         dangerously_bypass_approvals_and_sandbox: false,
@@ -83,6 +84,8 @@ fn cli_web_search_flag_defaults() {
         debug: false,
         order: false,
         timing: false,
+        low_bandwidth: false,
+        profile_startup: false,
         config_overrides: Default::default(),
         demo_developer_message: None,
         resume_picker: false,
@@ -108,6 +111,7 @@ fn cli_web_search_flag_explicit_enable() {
         oss: false,
         config_profile: None,
         sandbox_mode: None,
+        sandbox_preset: None,
         approval_policy: None,
         full_auto: false,
         dangerously_bypass_approvals_and_sandbox: false,
@@ -118,6 +122,8 @@ fn cli_web_search_flag_explicit_enable() {
         debug: false,
         order: false,
         timing: false,
+        low_bandwidth: false,
+        profile_startup: false,
         config_overrides: Default::default(),
         demo_developer_message: None,
         resume_picker: false,
@@ -143,6 +149,7 @@ fn cli_web_search_flag_disable() {
         oss: false,
         config_profile: None,
         sandbox_mode: None,
+        sandbox_preset: None,
         approval_policy: None,
         full_auto: false,
         dangerously_bypass_approvals_and_sandbox: false,
@@ -153,6 +160,8 @@ fn cli_web_search_flag_disable() {
         debug: false,
         order: false,
         timing: false,
+        low_bandwidth: false,
+        profile_startup: false,
         config_overrides: Default::default(),
         demo_developer_message: None,
         resume_picker: false,
@@ -579,6 +588,7 @@ fn assistant_stream_finalize_transitions_to_message() {
             reasoning_output_tokens: 0,
             total_tokens: 8,
         }),
+        sources: Vec::new(),
     };
 
     state.upsert_assistant_stream_state(stream_id, "First".into(), None, Some(&metadata));
@@ -707,7 +717,7 @@ fn smoke_exec_command_stream() {
     let stdout = exec_record
         .stdout_chunks
         .iter()
-        .map(|chunk| chunk.content.as_str())
+        .map(|chunk| chunk.content.as_ref())
         .collect::<String>();
     assert_eq!(stdout, "hi\n", "stdout should include streamed content");
 }